@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use pumpkin_util::PermissionLvl;
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +14,14 @@ pub struct CommandsConfig {
     pub log_console: bool, // TODO: commands...
     /// The `op` permission level of everyone that is not in the `ops` file.
     pub default_op_level: PermissionLvl,
+    /// How long players have to wait in milliseconds before using a command again, by command
+    /// name like `fill`.
+    pub cooldowns_ms: HashMap<String, u64>,
+    /// Players with at least this `op` permission level are not affected by `cooldowns_ms`.
+    pub cooldown_bypass_level: PermissionLvl,
+    /// The `op` permission level required for a command by command name like `fill`, replacing
+    /// its default level.
+    pub permission_levels: HashMap<String, PermissionLvl>,
 }
 
 impl Default for CommandsConfig {
@@ -21,6 +31,9 @@ impl Default for CommandsConfig {
             log_console: true,
             use_tty: true,
             default_op_level: PermissionLvl::Zero,
+            cooldowns_ms: HashMap::new(),
+            cooldown_bypass_level: PermissionLvl::Two,
+            permission_levels: HashMap::new(),
         }
     }
 }
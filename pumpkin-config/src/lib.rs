@@ -15,10 +15,12 @@ pub mod resource_pack;
 
 pub use chat::ChatConfig;
 pub use commands::CommandsConfig;
+pub use movement::MovementConfig;
 pub use networking::auth::AuthenticationConfig;
 pub use networking::compression::CompressionConfig;
 pub use networking::lan_broadcast::LANBroadcastConfig;
 pub use networking::rcon::RCONConfig;
+pub use permissions::PermissionsConfig;
 pub use pvp::PVPConfig;
 pub use server_links::ServerLinksConfig;
 
@@ -26,7 +28,9 @@ mod commands;
 
 mod chat;
 pub mod chunk;
+mod movement;
 pub mod op;
+mod permissions;
 mod player_data;
 mod pvp;
 mod server_links;
@@ -53,9 +57,11 @@ pub struct AdvancedConfiguration {
     pub commands: CommandsConfig,
     pub chat: ChatConfig,
     pub pvp: PVPConfig,
+    pub movement: MovementConfig,
     pub server_links: ServerLinksConfig,
     pub player_data: PlayerDataConfig,
     pub fun: FunConfig,
+    pub permissions: PermissionsConfig,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -75,7 +81,7 @@ pub struct BasicConfiguration {
     pub max_players: u32,
     /// The maximum view distance for players.
     pub view_distance: NonZeroU8,
-    /// The maximum simulated view distance.
+    /// The distance in chunks around players in which blocks and entities tick and mobs spawn.
     pub simulation_distance: NonZeroU8,
     /// The default game difficulty.
     pub default_difficulty: Difficulty,
@@ -111,6 +117,12 @@ pub struct BasicConfiguration {
     pub white_list: bool,
     /// Whether to enforce the whitelist
     pub enforce_whitelist: bool,
+    /// The message players who aren't on the whitelist are disconnected with. Leave it empty for
+    /// the vanilla message.
+    pub whitelist_message: String,
+    /// The radius around the world spawn in which only operators can break and place blocks.
+    /// Specifying `0` disables spawn protection.
+    pub spawn_protection: u32,
 }
 
 impl Default for BasicConfiguration {
@@ -141,6 +153,8 @@ impl Default for BasicConfiguration {
             allow_chat_reports: false,
             white_list: false,
             enforce_whitelist: false,
+            whitelist_message: String::new(),
+            spawn_protection: 16,
         }
     }
 }
@@ -151,6 +165,12 @@ impl BasicConfiguration {
     }
 }
 
+/// The folder the configuration files are in, next to where the server was started.
+#[must_use]
+pub fn config_dir() -> PathBuf {
+    std::env::current_dir().unwrap().join("config")
+}
+
 pub trait LoadConfiguration {
     fn load(config_dir: &Path) -> Self
     where
@@ -209,6 +229,26 @@ pub trait LoadConfiguration {
         config
     }
 
+    /// Reads the configuration again while the server is running. Unlike `load`, this neither
+    /// writes the file nor panics, an invalid file is reported as an error instead.
+    fn reload(config_dir: &Path) -> Result<Self, String>
+    where
+        Self: Sized + Default + Serialize + DeserializeOwned,
+    {
+        let path = config_dir.join(Self::get_path());
+        let file_content = fs::read_to_string(&path)
+            .map_err(|err| format!("Couldn't read configuration file at {path:?}: {err}"))?;
+        let parsed_toml_value: toml::Value = toml::from_str(&file_content)
+            .map_err(|err| format!("Couldn't parse TOML at {path:?}: {err}"))?;
+
+        let default_toml_value =
+            toml::Value::try_from(Self::default()).expect("Failed to parse default config");
+        let (merged_value, _) = Self::merge_toml_values(default_toml_value, parsed_toml_value);
+        merged_value
+            .try_into()
+            .map_err(|err| format!("Invalid configuration at {path:?}: {err}"))
+    }
+
     fn merge_with_default_toml(parsed_toml: toml::Value) -> (Self, bool)
     where
         Self: Sized + Default + Serialize + DeserializeOwned,
@@ -304,3 +344,37 @@ impl LoadConfiguration for BasicConfiguration {
         }
     }
 }
+
+/// The dotted paths of the values which differ between two configurations, like
+/// `world.mob_caps.monster`.
+pub fn changed_values<T: Serialize>(old: &T, new: &T) -> Vec<String> {
+    fn collect(path: &str, old: &toml::Value, new: &toml::Value, changed: &mut Vec<String>) {
+        match (old, new) {
+            (toml::Value::Table(old), toml::Value::Table(new)) => {
+                for key in old
+                    .keys()
+                    .chain(new.keys().filter(|key| !old.contains_key(*key)))
+                {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    match (old.get(key), new.get(key)) {
+                        (Some(old), Some(new)) => collect(&path, old, new, changed),
+                        _ => changed.push(path),
+                    }
+                }
+            }
+            (old, new) if old != new => changed.push(path.to_string()),
+            _ => {}
+        }
+    }
+
+    let (Ok(old), Ok(new)) = (toml::Value::try_from(old), toml::Value::try_from(new)) else {
+        return Vec::new();
+    };
+    let mut changed = Vec::new();
+    collect("", &old, &new, &mut changed);
+    changed
+}
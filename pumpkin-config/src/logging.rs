@@ -8,6 +8,9 @@ pub struct LoggingConfig {
     pub color: bool,
     pub timestamp: bool,
     pub file: String,
+    /// Whether op, ban, kick and game mode changes are written to `audit_file`.
+    pub audit: bool,
+    pub audit_file: String,
 }
 
 impl Default for LoggingConfig {
@@ -18,6 +21,8 @@ impl Default for LoggingConfig {
             color: true,
             timestamp: true,
             file: "latest.log".to_string(),
+            audit: false,
+            audit_file: "audit.log".to_string(),
         }
     }
 }
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Rejects player movement which is clearly impossible, like flying without being allowed to.
+/// The limits are generous so lagging players are not affected. By default violations are only
+/// logged.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct MovementConfig {
+    /// Whether player movement is validated.
+    pub enabled: bool,
+    /// The most blocks a player may rise in a single move.
+    pub max_vertical_speed: f64,
+    /// The most consecutive moves a player may stay in the air without falling.
+    pub max_hover_moves: u32,
+    /// Whether players are teleported back to their last valid position on a violation.
+    /// Otherwise violations are only logged.
+    pub teleport_back: bool,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_vertical_speed: 10.0,
+            max_hover_moves: 40,
+            teleport_back: false,
+        }
+    }
+}
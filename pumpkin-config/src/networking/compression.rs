@@ -8,6 +8,12 @@ pub struct CompressionConfig {
     pub enabled: bool,
     #[serde(flatten)]
     pub info: CompressionInfo,
+    /// IDs of clientbound play packets which are never compressed, whatever their size.
+    /// Useful for packets which barely shrink, like frequent tiny ones or already compressed data.
+    pub exempt_packets: Vec<i32>,
+    /// The largest, in bytes, a packet sent by a client may be once decompressed.
+    /// Clients sending bigger packets get kicked.
+    pub max_decompressed_size: usize,
 }
 
 impl Default for CompressionConfig {
@@ -15,6 +21,8 @@ impl Default for CompressionConfig {
         Self {
             enabled: true,
             info: Default::default(),
+            exempt_packets: Vec::new(),
+            max_decompressed_size: 8_388_608,
         }
     }
 }
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+/// Limits how fast new Java Edition connections are accepted, to mitigate connection floods.
+pub struct ConnectionThrottleConfig {
+    /// Whether connection throttling is enabled.
+    pub enabled: bool,
+    /// The maximum amount of connections a single IP can open within `window_ms`, `0` means unlimited.
+    pub max_connections_per_ip: u32,
+    /// The time window in milliseconds used for `max_connections_per_ip`.
+    pub window_ms: u64,
+    /// The maximum amount of clients which have not reached the play state yet, `0` means unlimited.
+    pub max_handshaking: u32,
+    /// Trusted IPs or CIDR ranges like `10.0.0.0/8`, which are never throttled, e.g. proxies or monitoring.
+    pub allowlist: Vec<String>,
+}
+
+impl Default for ConnectionThrottleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_connections_per_ip: 3,
+            window_ms: 4000,
+            max_handshaking: 64,
+            allowlist: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+/// Only lets players join through the given addresses, so a proxy in front of the server can't be
+/// bypassed by connecting to the server's IP directly.
+pub struct ForcedHostsConfig {
+    /// Whether joining is limited to `allowed_hosts`.
+    pub enabled: bool,
+    /// Hostnames players have to connect with, like `play.example.com` or `play.example.com:25565`.
+    /// Without a port, any port is accepted.
+    ///
+    /// SRV records are resolved by the client before the handshake, so players connecting through
+    /// one send the target of the record and its port instead of the name they typed in. The
+    /// server can't see that name, so add the target of the record as well.
+    pub allowed_hosts: Vec<String>,
+    /// The message players joining through any other address are disconnected with.
+    pub kick_message: String,
+}
+
+impl Default for ForcedHostsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_hosts: Vec::new(),
+            kick_message: "Please connect through a valid address".to_string(),
+        }
+    }
+}
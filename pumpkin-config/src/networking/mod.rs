@@ -1,17 +1,27 @@
 use auth::AuthenticationConfig;
+use connection_throttle::ConnectionThrottleConfig;
+use forced_hosts::ForcedHostsConfig;
+use packet_dump::PacketDumpConfig;
 use proxy::ProxyConfig;
+use proxy_protocol::ProxyProtocolConfig;
 use query::QueryConfig;
 use rcon::RCONConfig;
 use serde::{Deserialize, Serialize};
+use status_endpoint::StatusEndpointConfig;
 
 use crate::{CompressionConfig, LANBroadcastConfig};
 
 pub mod auth;
 pub mod compression;
+pub mod connection_throttle;
+pub mod forced_hosts;
 pub mod lan_broadcast;
+pub mod packet_dump;
 pub mod proxy;
+pub mod proxy_protocol;
 pub mod query;
 pub mod rcon;
+pub mod status_endpoint;
 
 #[derive(Deserialize, Serialize, Default)]
 pub struct NetworkingConfig {
@@ -19,6 +29,11 @@ pub struct NetworkingConfig {
     pub query: QueryConfig,
     pub rcon: RCONConfig,
     pub proxy: ProxyConfig,
+    pub proxy_protocol: ProxyProtocolConfig,
     pub packet_compression: CompressionConfig,
     pub lan_broadcast: LANBroadcastConfig,
+    pub connection_throttle: ConnectionThrottleConfig,
+    pub forced_hosts: ForcedHostsConfig,
+    pub packet_dump: PacketDumpConfig,
+    pub status_endpoint: StatusEndpointConfig,
 }
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+/// Writes every packet of Java Edition clients to a file, to debug protocol issues.
+pub struct PacketDumpConfig {
+    /// Whether packets are dumped. This writes a lot of data and should only be enabled while debugging.
+    pub enabled: bool,
+    /// The directory the dumps are written to, one file per client.
+    pub directory: String,
+}
+
+impl Default for PacketDumpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: "packet_dumps".to_string(),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Reads the real address of Java Edition clients from the PROXY protocol header (v1 or v2)
+/// which TCP load balancers like HAProxy send before the connection data, so bans apply to the
+/// client instead of the load balancer. Connections are throttled before the header is read, so
+/// the load balancers belong on the connection throttle's allowlist.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct ProxyProtocolConfig {
+    pub enabled: bool,
+    /// IPs or CIDR ranges like `10.0.0.0/8` of the load balancers. Only connections from them
+    /// have to start with a header, others connect as usual so nobody can fake their address.
+    pub trusted_proxies: Vec<String>,
+}
@@ -0,0 +1,21 @@
+use std::net::{Ipv4Addr, SocketAddr};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct StatusEndpointConfig {
+    /// Whether the HTTP endpoint serving the server status as JSON is enabled.
+    pub enabled: bool,
+    /// The network address and port the endpoint listens on, separate from the game port.
+    pub address: SocketAddr,
+}
+
+impl Default for StatusEndpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: SocketAddr::new(Ipv4Addr::new(127, 0, 0, 1).into(), 25580),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use pumpkin_util::permission::PermissionGroup;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct PermissionsConfig {
+    /// Permission groups players can be assigned to, by name.
+    pub groups: HashMap<String, PermissionGroup>,
+}
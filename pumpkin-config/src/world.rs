@@ -2,8 +2,163 @@ use serde::{Deserialize, Serialize};
 
 use crate::chunk::ChunkConfig;
 
-#[derive(Deserialize, Serialize, Default)]
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
 pub struct LevelConfig {
     pub chunk: ChunkConfig,
+    /// The minimum amount of ticks between two entity metadata packets for the same entity.
+    /// Changes made in between are combined into a single packet, `1` sends them every tick.
+    pub entity_metadata_interval: u32,
+    pub block_breaking: BlockBreakingConfig,
+    pub mob_caps: MobCapsConfig,
+    pub entity_tracking: EntityTrackingConfig,
+    pub entity_limits: EntityLimitsConfig,
+    /// Whether the periodic autosave is skipped while no players are online. The worlds are still
+    /// saved once when the last player leaves.
+    pub autosave_only_with_players: bool,
+    pub generator: GeneratorConfig,
+    pub chunk_generation: ChunkGenerationConfig,
+    /// Whether chunks which can't be read from their region file, like after a crash while saving,
+    /// are generated again. Otherwise they are left empty and only saved once they are changed.
+    pub regenerate_corrupt_chunks: bool,
     // TODO: More options
 }
+
+impl Default for LevelConfig {
+    fn default() -> Self {
+        Self {
+            chunk: ChunkConfig::default(),
+            entity_metadata_interval: 1,
+            block_breaking: BlockBreakingConfig::default(),
+            mob_caps: MobCapsConfig::default(),
+            entity_tracking: EntityTrackingConfig::default(),
+            entity_limits: EntityLimitsConfig::default(),
+            autosave_only_with_players: false,
+            generator: GeneratorConfig::default(),
+            chunk_generation: ChunkGenerationConfig::default(),
+            regenerate_corrupt_chunks: true,
+        }
+    }
+}
+
+/// Overrides the generator settings of a dimension, which decide its sea level, default block
+/// and noise shape. Possible values are `overworld`, `large_biomes`, `amplified`, `nether`,
+/// `end`, `caves`, `floating_islands` and `flat`, which skips the terrain noise for fast test
+/// worlds. Dimensions without an override use their vanilla settings.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct GeneratorConfig {
+    pub overworld: Option<String>,
+    pub the_nether: Option<String>,
+    pub the_end: Option<String>,
+}
+
+/// How much CPU time chunk generation may take, so hosts with few cores keep enough for the
+/// rest of the server. Each world has its own generation threads.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct ChunkGenerationConfig {
+    /// The number of generation threads per world, `0` uses all cores but two.
+    pub threads: u32,
+    /// How many generation tasks may wait for a free thread, `0` allows five more than there
+    /// are threads. Further chunks are only scheduled once a thread picked up a task.
+    pub max_pending_tasks: u32,
+}
+
+/// How strictly the server checks that a player mined a block long enough before breaking it.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct BlockBreakingConfig {
+    /// Whether blocks that were broken too early are restored for the player.
+    pub validate: bool,
+    /// How much of the breaking progress may be missing when the player finishes, between `0.0`
+    /// and `1.0`. Vanilla accepts `0.3`, so a break at 70% of the progress is fine.
+    pub leniency: f32,
+    /// Extra ticks counted towards the breaking progress, for players with a high latency.
+    pub grace_ticks: u32,
+}
+
+impl Default for BlockBreakingConfig {
+    fn default() -> Self {
+        Self {
+            validate: true,
+            leniency: 0.3,
+            grace_ticks: 0,
+        }
+    }
+}
+
+/// How many mobs of each category may naturally spawn around players, like vanilla's mob caps.
+/// A cap of `0` disables natural spawning of that category.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct MobCapsConfig {
+    pub monster: u32,
+    pub creature: u32,
+    pub ambient: u32,
+    pub axolotls: u32,
+    pub underground_water_creature: u32,
+    pub water_creature: u32,
+    pub water_ambient: u32,
+}
+
+impl Default for MobCapsConfig {
+    fn default() -> Self {
+        Self {
+            monster: 70,
+            creature: 10,
+            ambient: 15,
+            axolotls: 5,
+            underground_water_creature: 5,
+            water_creature: 5,
+            water_ambient: 20,
+        }
+    }
+}
+
+/// How close entities have to be to a player, in blocks, before they are spawned for their
+/// client. Entities further away are removed again, even while their chunk stays loaded.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EntityTrackingConfig {
+    pub players: u32,
+    pub mobs: u32,
+    /// Dropped items and experience orbs.
+    pub items: u32,
+    /// All other entities, like projectiles, vehicles and item frames.
+    pub other: u32,
+}
+
+impl Default for EntityTrackingConfig {
+    fn default() -> Self {
+        Self {
+            players: 48,
+            mobs: 48,
+            items: 32,
+            other: 64,
+        }
+    }
+}
+
+/// How many entities of each kind a single chunk may hold, so mob farms and item piles can't
+/// slow down the server. Mobs over the limit are not spawned, while the oldest items and
+/// experience orbs are removed to make room for new ones. A limit of `0` disables it.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EntityLimitsConfig {
+    pub mobs: u32,
+    /// Dropped items and experience orbs.
+    pub items: u32,
+    /// All other entities, like projectiles, vehicles and item frames.
+    pub other: u32,
+}
+
+impl Default for EntityLimitsConfig {
+    fn default() -> Self {
+        Self {
+            mobs: 100,
+            items: 256,
+            other: 256,
+        }
+    }
+}
@@ -23,6 +23,8 @@ pub(crate) fn build() -> TokenStream {
     sorted_attributes.sort_by_key(|(_, raw)| raw.id);
 
     let mut constant_defs = Vec::new();
+    let mut name_to_type = TokenStream::new();
+    let mut id_to_name = TokenStream::new();
 
     for (raw_name, raw_value) in sorted_attributes {
         let constant_ident = format_ident!("{}", raw_name.to_shouty_snake_case());
@@ -36,6 +38,12 @@ pub(crate) fn build() -> TokenStream {
                 default_value: #default_value_lit,
             };
         });
+        name_to_type.extend(quote! {
+            #raw_name => Some(&Self::#constant_ident),
+        });
+        id_to_name.extend(quote! {
+            #id_lit => #raw_name,
+        });
     }
 
     quote! {
@@ -59,6 +67,21 @@ pub(crate) fn build() -> TokenStream {
         }
         impl Attributes {
             #(#constant_defs)*
+
+            pub fn from_name(name: &str) -> Option<&'static Self> {
+                match name {
+                    #name_to_type
+                    _ => None
+                }
+            }
+
+            /// The name without the `minecraft:` namespace, like `max_health`.
+            pub const fn name(&self) -> &'static str {
+                match self.id {
+                    #id_to_name
+                    _ => unreachable!()
+                }
+            }
         }
     }
 }
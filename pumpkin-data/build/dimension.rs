@@ -17,6 +17,22 @@ pub struct Dimension {
     pub infiniburn: String,
     #[serde(rename = "fixed_time")]
     pub fixed_time: Option<i64>,
+    #[serde(default)]
+    pub attributes: DimensionAttributes,
+}
+
+#[derive(Deserialize, Default)]
+pub struct DimensionAttributes {
+    #[serde(rename = "minecraft:gameplay/bed_rule")]
+    pub bed_rule: Option<BedRule>,
+    #[serde(rename = "minecraft:gameplay/respawn_anchor_works", default)]
+    pub respawn_anchor_works: bool,
+}
+
+#[derive(Deserialize)]
+pub struct BedRule {
+    #[serde(default)]
+    pub explodes: bool,
 }
 
 // #[derive(Clone, PartialEq, Deserialize)]
@@ -73,6 +89,12 @@ pub(crate) fn build() -> TokenStream {
         let has_skylight = dim.has_skylight;
         let has_ceiling = dim.has_ceiling;
         let infiniburn = &dim.infiniburn;
+        let bed_explodes = dim
+            .attributes
+            .bed_rule
+            .as_ref()
+            .is_some_and(|rule| rule.explodes);
+        let respawn_anchor_works = dim.attributes.respawn_anchor_works;
 
         let minecraft_name = if name.contains(':') {
             name.clone()
@@ -93,6 +115,8 @@ pub(crate) fn build() -> TokenStream {
                 logical_height: #logical_height,
                 infiniburn: #infiniburn,
                 ambient_light: #ambient_light,
+                bed_explodes: #bed_explodes,
+                respawn_anchor_works: #respawn_anchor_works,
             };
         });
 
@@ -115,6 +139,10 @@ pub(crate) fn build() -> TokenStream {
             pub logical_height: i32,
             pub infiniburn: &'static str,
             pub ambient_light: f32,
+            /// Whether beds explode when used instead of setting the spawn point.
+            pub bed_explodes: bool,
+            /// Whether respawn anchors set the spawn point instead of exploding.
+            pub respawn_anchor_works: bool,
         }
 
         impl Dimension {
@@ -134,7 +134,7 @@ pub(crate) fn build() -> TokenStream {
         use crate::tag::Enchantment as EnchantmentTag;
         use crate::tag::Item as ItemTag;
         use crate::tag::{RegistryKey, Tag, Taggable};
-        use crate::data_component_impl::EnchantmentsImpl;
+        use crate::data_component_impl::{EnchantmentsImpl, EquipmentSlot, EquipmentType};
         use pumpkin_util::text::TextComponent;
         use pumpkin_util::text::color::NamedColor;
         use std::hash::{Hash, Hasher};
@@ -192,6 +192,25 @@ pub(crate) fn build() -> TokenStream {
             Saddle,
         }
 
+        impl AttributeModifierSlot {
+            /// Whether modifiers for this slot apply to an item worn or held in `slot`.
+            pub fn matches(&self, slot: &EquipmentSlot) -> bool {
+                match self {
+                    Self::Any => true,
+                    Self::MainHand => matches!(slot, EquipmentSlot::MainHand(_)),
+                    Self::OffHand => matches!(slot, EquipmentSlot::OffHand(_)),
+                    Self::Hand => slot.slot_type() == EquipmentType::Hand,
+                    Self::Feet => matches!(slot, EquipmentSlot::Feet(_)),
+                    Self::Legs => matches!(slot, EquipmentSlot::Legs(_)),
+                    Self::Chest => matches!(slot, EquipmentSlot::Chest(_)),
+                    Self::Head => matches!(slot, EquipmentSlot::Head(_)),
+                    Self::Armor => slot.is_armor_slot(),
+                    Self::Body => matches!(slot, EquipmentSlot::Body(_)),
+                    Self::Saddle => matches!(slot, EquipmentSlot::Saddle(_)),
+                }
+            }
+        }
+
         impl Enchantment {
             #variants
 
@@ -1,5 +1,5 @@
 use crate::enchantments::AttributeModifierSlot;
-use heck::ToShoutySnakeCase;
+use heck::{ToPascalCase, ToShoutySnakeCase};
 use proc_macro2::{Span, TokenStream};
 use pumpkin_util::registry::TagType;
 use pumpkin_util::text::TextContent;
@@ -37,6 +37,8 @@ pub struct ItemComponents {
     pub equippable: Option<EquippableComponent>,
     #[serde(rename = "minecraft:consumable")]
     pub consumable: Option<Consumable>,
+    #[serde(rename = "minecraft:use_cooldown")]
+    pub use_cooldown: Option<UseCooldown>,
     #[serde(rename = "minecraft:blocks_attacks")]
     pub blocks_attacks: Option<BlocksAttacks>,
     #[serde(rename = "minecraft:death_protection")]
@@ -230,8 +232,39 @@ impl ToTokens for ItemComponents {
                 Span::call_site(),
             );
 
+            let sound = consumable
+                .sound
+                .as_deref()
+                .unwrap_or("minecraft:entity.generic.eat");
+            let sound = format_ident!(
+                "{}",
+                sound
+                    .strip_prefix("minecraft:")
+                    .unwrap_or(sound)
+                    .to_pascal_case()
+            );
+            let has_consume_particles = consumable.has_consume_particles;
+
             tokens.extend(quote! { (Consumable, &ConsumableImpl {
                 consume_seconds: #consume_seconds,
+                sound: crate::sound::Sound::#sound,
+                has_consume_particles: #has_consume_particles,
+            }), });
+        };
+
+        if let Some(use_cooldown) = &self.use_cooldown {
+            let seconds = LitFloat::new(&format!("{:.1}", use_cooldown.seconds), Span::call_site());
+            let cooldown_group = match &use_cooldown.cooldown_group {
+                Some(group) => {
+                    let group = LitStr::new(group, Span::call_site());
+                    quote! { Some(#group) }
+                }
+                None => quote! { None },
+            };
+
+            tokens.extend(quote! { (UseCooldown, &UseCooldownImpl {
+                seconds: #seconds,
+                cooldown_group: #cooldown_group,
             }), });
         };
 
@@ -401,6 +434,15 @@ fn _true() -> bool {
 #[derive(Deserialize, Clone)]
 pub struct Consumable {
     consume_seconds: Option<f32>, // TODO
+    sound: Option<String>,
+    #[serde(default = "_true")]
+    has_consume_particles: bool,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct UseCooldown {
+    seconds: f32,
+    cooldown_group: Option<String>,
 }
 
 #[derive(Deserialize, Clone)]
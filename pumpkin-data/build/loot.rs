@@ -12,6 +12,7 @@ pub struct LootTableStruct {
     r#type: LootTableTypeStruct,
     random_sequence: Option<String>,
     pools: Option<Vec<LootPoolStruct>>,
+    functions: Option<Vec<LootFunctionStruct>>,
 }
 
 impl ToTokens for LootTableStruct {
@@ -28,12 +29,20 @@ impl ToTokens for LootTableStruct {
             }
             None => quote! { None },
         };
+        let functions_tokens = match &self.functions {
+            Some(fns) => {
+                let fn_tokens: Vec<_> = fns.iter().map(|f| f.to_token_stream()).collect();
+                quote! { Some(&[#(#fn_tokens),*]) }
+            }
+            None => quote! { None },
+        };
 
         tokens.extend(quote! {
             LootTable {
                 r#type: #loot_table_type,
                 random_sequence: #random_sequence,
                 pools: #pools,
+                functions: #functions_tokens,
             }
         });
     }
@@ -174,11 +183,11 @@ impl ToTokens for LootPoolEntryTypesStruct {
 #[serde(tag = "condition")]
 pub enum LootConditionStruct {
     #[serde(rename = "minecraft:inverted")]
-    Inverted,
+    Inverted { term: Box<LootConditionStruct> },
     #[serde(rename = "minecraft:any_of")]
-    AnyOf,
+    AnyOf { terms: Vec<LootConditionStruct> },
     #[serde(rename = "minecraft:all_of")]
-    AllOf,
+    AllOf { terms: Vec<LootConditionStruct> },
     #[serde(rename = "minecraft:random_chance")]
     RandomChance,
     #[serde(rename = "minecraft:random_chance_with_enchanted_bonus")]
@@ -195,9 +204,12 @@ pub enum LootConditionStruct {
         properties: BTreeMap<String, String>,
     },
     #[serde(rename = "minecraft:match_tool")]
-    MatchTool,
+    MatchTool { predicate: ItemPredicateStruct },
     #[serde(rename = "minecraft:table_bonus")]
-    TableBonus,
+    TableBonus {
+        enchantment: String,
+        chances: Vec<f32>,
+    },
     #[serde(rename = "minecraft:survives_explosion")]
     SurvivesExplosion,
     #[serde(rename = "minecraft:damage_source_properties")]
@@ -219,9 +231,15 @@ pub enum LootConditionStruct {
 impl ToTokens for LootConditionStruct {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let name = match self {
-            LootConditionStruct::Inverted => quote! { LootCondition::Inverted },
-            LootConditionStruct::AnyOf => quote! { LootCondition::AnyOf },
-            LootConditionStruct::AllOf => quote! { LootCondition::AllOf },
+            LootConditionStruct::Inverted { term } => {
+                quote! { LootCondition::Inverted { term: &#term } }
+            }
+            LootConditionStruct::AnyOf { terms } => {
+                quote! { LootCondition::AnyOf { terms: &[#(#terms),*] } }
+            }
+            LootConditionStruct::AllOf { terms } => {
+                quote! { LootCondition::AllOf { terms: &[#(#terms),*] } }
+            }
             LootConditionStruct::RandomChance => quote! { LootCondition::RandomChance },
             LootConditionStruct::RandomChanceWithEnchantedBonus => {
                 quote! { LootCondition::RandomChanceWithEnchantedBonus }
@@ -236,8 +254,39 @@ impl ToTokens for LootConditionStruct {
                     .collect();
                 quote! { LootCondition::BlockStateProperty { block: #block, properties: &[#(#properties),*] } }
             }
-            LootConditionStruct::MatchTool => quote! { LootCondition::MatchTool },
-            LootConditionStruct::TableBonus => quote! { LootCondition::TableBonus },
+            LootConditionStruct::MatchTool { predicate } => {
+                let items = match &predicate.items {
+                    Some(items) => quote! { Some(#items) },
+                    None => quote! { None },
+                };
+                let enchantments: Vec<_> = predicate
+                    .predicates
+                    .enchantments
+                    .iter()
+                    .map(|predicate| {
+                        let enchantment = &predicate.enchantments;
+                        let min_level = predicate.levels.min.unwrap_or(1);
+                        quote! { (#enchantment, #min_level) }
+                    })
+                    .collect();
+                quote! {
+                    LootCondition::MatchTool {
+                        items: #items,
+                        enchantments: &[#(#enchantments),*],
+                    }
+                }
+            }
+            LootConditionStruct::TableBonus {
+                enchantment,
+                chances,
+            } => {
+                quote! {
+                    LootCondition::TableBonus {
+                        enchantment: #enchantment,
+                        chances: &[#(#chances),*],
+                    }
+                }
+            }
             LootConditionStruct::SurvivesExplosion => quote! { LootCondition::SurvivesExplosion },
             LootConditionStruct::DamageSourceProperties => {
                 quote! { LootCondition::DamageSourceProperties }
@@ -256,6 +305,31 @@ impl ToTokens for LootConditionStruct {
     }
 }
 
+/// The parts of an item predicate used by loot tables.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ItemPredicateStruct {
+    items: Option<String>,
+    #[serde(default)]
+    predicates: ItemSubPredicatesStruct,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct ItemSubPredicatesStruct {
+    #[serde(rename = "minecraft:enchantments", default)]
+    enchantments: Vec<EnchantmentPredicateStruct>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnchantmentPredicateStruct {
+    enchantments: String,
+    levels: EnchantmentLevelsStruct,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnchantmentLevelsStruct {
+    min: Option<i32>,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct LootFunctionStruct {
     #[serde(flatten)]
@@ -468,11 +542,18 @@ pub struct LootPoolEntryStruct {
     content: LootPoolEntryTypesStruct,
     conditions: Option<Vec<LootConditionStruct>>,
     functions: Option<Vec<LootFunctionStruct>>,
+    #[serde(default = "default_weight")]
+    weight: i32,
+}
+
+const fn default_weight() -> i32 {
+    1
 }
 
 impl ToTokens for LootPoolEntryStruct {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let content = &self.content;
+        let weight = self.weight;
         let conditions_tokens = match &self.conditions {
             Some(conds) => {
                 let cond_tokens: Vec<_> = conds.iter().map(|c| c.to_token_stream()).collect();
@@ -493,6 +574,7 @@ impl ToTokens for LootPoolEntryStruct {
                 content: #content,
                 conditions: #conditions_tokens,
                 functions: #functions_tokens,
+                weight: #weight,
             }
         });
     }
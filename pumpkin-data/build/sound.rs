@@ -26,6 +26,19 @@ pub(crate) fn build() -> TokenStream {
         })
         .collect::<TokenStream>();
 
+    let type_from_id = &sound
+        .iter()
+        .enumerate()
+        .map(|(id, sound)| {
+            let id = id as u16;
+            let name = format_ident!("{}", sound.to_pascal_case());
+
+            quote! {
+                #id => Some(Self::#name),
+            }
+        })
+        .collect::<TokenStream>();
+
     let type_to_name = &sound
         .iter()
         .map(|sound| {
@@ -53,6 +66,13 @@ pub(crate) fn build() -> TokenStream {
                 }
             }
 
+            pub const fn from_id(id: u16) -> Option<Self> {
+                match id {
+                    #type_from_id
+                    _ => None
+                }
+            }
+
             pub const fn to_name(&self) -> &'static str {
                 match self {
                     #type_to_name
@@ -4,12 +4,14 @@ use crate::attributes::Attributes;
 use crate::data_component::DataComponent;
 use crate::data_component::DataComponent::*;
 use crate::entity_type::EntityType;
+use crate::sound::Sound;
 use crate::tag::{Tag, Taggable};
 use crate::{AttributeModifierSlot, Block, Enchantment};
 use crc_fast::CrcAlgorithm::Crc32Iscsi;
 use crc_fast::Digest;
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_nbt::tag::NbtTag;
+use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::registry::RegistryEntryList;
 use pumpkin_util::text::TextComponent;
 use serde::de::SeqAccess;
@@ -43,6 +45,7 @@ pub fn read_data(id: DataComponent, data: &NbtTag) -> Option<Box<dyn DataCompone
         MaxStackSize => Some(MaxStackSizeImpl::read_data(data)?.to_dyn()),
         Enchantments => Some(EnchantmentsImpl::read_data(data)?.to_dyn()),
         Damage => Some(DamageImpl::read_data(data)?.to_dyn()),
+        LodestoneTracker => Some(LodestoneTrackerImpl::read_data(data)?.to_dyn()),
         _ => None,
     }
 }
@@ -207,6 +210,35 @@ fn get_i32_hash(val: i32) -> u32 {
     digest.finalize() as u32
 }
 
+fn get_bool_hash(val: bool) -> u32 {
+    let mut digest = Digest::new(Crc32Iscsi);
+    digest.update(&[13u8, val as u8]);
+    digest.finalize() as u32
+}
+
+fn get_i32_array_hash(val: &[i32]) -> u32 {
+    let mut digest = Digest::new(Crc32Iscsi);
+    digest.update(&[16u8]);
+    for i in val {
+        digest.update(&i.to_le_bytes());
+    }
+    digest.update(&[17u8]);
+    digest.finalize() as u32
+}
+
+/// Hashes a map of (key hash, value hash) entries, vanilla sorts them before hashing
+fn get_map_hash(mut entries: Vec<(u32, u32)>) -> u32 {
+    entries.sort_unstable();
+    let mut digest = Digest::new(Crc32Iscsi);
+    digest.update(&[2u8]);
+    for (key, value) in entries {
+        digest.update(&key.to_le_bytes());
+        digest.update(&value.to_le_bytes());
+    }
+    digest.update(&[3u8]);
+    digest.finalize() as u32
+}
+
 #[test]
 fn test_hash() {
     assert_eq!(get_str_hash("minecraft:sharpness"), 2734053906u32);
@@ -307,6 +339,9 @@ impl Hash for FoodImpl {
 #[derive(Clone, Debug, PartialEq)]
 pub struct ConsumableImpl {
     pub consume_seconds: f32,
+    /// Played every few ticks while the item is consumed, like `entity.generic.eat`.
+    pub sound: Sound,
+    pub has_consume_particles: bool,
     // TODO: more
 }
 
@@ -314,6 +349,14 @@ impl ConsumableImpl {
     pub fn consume_ticks(&self) -> i32 {
         (self.consume_seconds * 20.0) as i32
     }
+
+    /// Whether to play the consume sound with `remaining_ticks` left, which happens every four
+    /// ticks once about a fifth of the time has passed.
+    pub fn should_play_effects(&self, remaining_ticks: i32) -> bool {
+        let consume_ticks = self.consume_ticks();
+        let elapsed = consume_ticks - remaining_ticks;
+        elapsed > (consume_ticks as f32 * 0.21875) as i32 && remaining_ticks % 4 == 0
+    }
 }
 
 impl DataComponentImpl for ConsumableImpl {
@@ -322,12 +365,33 @@ impl DataComponentImpl for ConsumableImpl {
 impl Hash for ConsumableImpl {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         unsafe { (*(&self.consume_seconds as *const f32 as *const u32)).hash(state) };
+        self.sound.hash(state);
+        self.has_consume_particles.hash(state);
     }
 }
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct UseRemainderImpl;
-#[derive(Clone, Debug, Hash, PartialEq)]
-pub struct UseCooldownImpl;
+#[derive(Clone, Debug, PartialEq)]
+pub struct UseCooldownImpl {
+    pub seconds: f32,
+    pub cooldown_group: Option<&'static str>,
+}
+
+impl UseCooldownImpl {
+    pub fn cooldown_ticks(&self) -> u32 {
+        (self.seconds * 20.0) as u32
+    }
+}
+
+impl DataComponentImpl for UseCooldownImpl {
+    default_impl!(UseCooldown);
+}
+impl Hash for UseCooldownImpl {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        unsafe { (*(&self.seconds as *const f32 as *const u32)).hash(state) };
+        self.cooldown_group.hash(state);
+    }
+}
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct DamageResistantImpl;
 
@@ -611,7 +675,18 @@ pub struct SuspiciousStewEffectsImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct WritableBookContentImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
-pub struct WrittenBookContentImpl;
+pub struct WrittenBookContentImpl {
+    pub title: String,
+    pub author: String,
+    /// 0 is the original, 1 a copy of the original, 2 a copy of a copy, 3 tattered
+    pub generation: i32,
+    pub pages: Vec<TextComponent>,
+    /// Whether selectors and scores in the pages were already resolved
+    pub resolved: bool,
+}
+impl DataComponentImpl for WrittenBookContentImpl {
+    default_impl!(WrittenBookContent);
+}
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct TrimImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
@@ -640,7 +715,63 @@ pub struct ProvidesBannerPatternsImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct RecipesImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
-pub struct LodestoneTrackerImpl;
+pub struct LodestoneTrackerImpl {
+    /// The dimension and position of the lodestone the compass points to
+    pub target: Option<(String, BlockPos)>,
+    /// Whether the target is cleared once the lodestone is gone
+    pub tracked: bool,
+}
+impl LodestoneTrackerImpl {
+    fn read_data(data: &NbtTag) -> Option<Self> {
+        let data = data.extract_compound()?;
+        let target = match data.get_compound("target") {
+            Some(target) => {
+                let dimension = target.get_string("dimension")?;
+                let &[x, y, z] = target.get_int_array("pos")? else {
+                    return None;
+                };
+                Some((dimension.to_owned(), BlockPos::new(x, y, z)))
+            }
+            None => None,
+        };
+        Some(Self {
+            target,
+            tracked: data.get_bool("tracked").unwrap_or(true),
+        })
+    }
+}
+impl DataComponentImpl for LodestoneTrackerImpl {
+    fn write_data(&self) -> NbtTag {
+        let mut data = NbtCompound::new();
+        if let Some((dimension, pos)) = &self.target {
+            let mut target = NbtCompound::new();
+            target.put_string("dimension", dimension.clone());
+            target.put("pos", NbtTag::IntArray(vec![pos.0.x, pos.0.y, pos.0.z]));
+            data.put_component("target", target);
+        }
+        data.put_bool("tracked", self.tracked);
+        NbtTag::Compound(data)
+    }
+    fn get_hash(&self) -> i32 {
+        let mut entries = Vec::with_capacity(2);
+        if let Some((dimension, pos)) = &self.target {
+            let target = get_map_hash(vec![
+                (get_str_hash("dimension"), get_str_hash(dimension)),
+                (
+                    get_str_hash("pos"),
+                    get_i32_array_hash(&[pos.0.x, pos.0.y, pos.0.z]),
+                ),
+            ]);
+            entries.push((get_str_hash("target"), target));
+        }
+        // Tracked is the default and left out
+        if !self.tracked {
+            entries.push((get_str_hash("tracked"), get_bool_hash(false)));
+        }
+        get_map_hash(entries) as i32
+    }
+    default_impl!(LodestoneTracker);
+}
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct FireworkExplosionImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use std::{any::Any, pin::Pin};
+
+use pumpkin_world::inventory::{Clearable, Inventory, InventoryFuture, split_stack};
+use pumpkin_world::item::ItemStack;
+use tokio::sync::Mutex;
+
+/// MerchantInventory.java
+///
+/// Only holds the two payment slots, the result is stored in the `TradeOutputSlot`.
+pub struct MerchantInventory {
+    pub items: [Arc<Mutex<ItemStack>>; 2],
+}
+
+impl MerchantInventory {
+    pub fn new() -> Self {
+        Self {
+            items: [
+                Arc::new(Mutex::new(ItemStack::EMPTY.clone())),
+                Arc::new(Mutex::new(ItemStack::EMPTY.clone())),
+            ],
+        }
+    }
+}
+
+impl Default for MerchantInventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Inventory for MerchantInventory {
+    fn size(&self) -> usize {
+        self.items.len()
+    }
+
+    fn is_empty(&self) -> InventoryFuture<'_, bool> {
+        Box::pin(async move {
+            for slot in self.items.iter() {
+                if !slot.lock().await.is_empty() {
+                    return false;
+                }
+            }
+
+            true
+        })
+    }
+
+    fn get_stack(&self, slot: usize) -> InventoryFuture<'_, Arc<Mutex<ItemStack>>> {
+        Box::pin(async move { self.items[slot].clone() })
+    }
+
+    fn remove_stack(&self, slot: usize) -> InventoryFuture<'_, ItemStack> {
+        Box::pin(async move {
+            let mut removed = ItemStack::EMPTY.clone();
+            let mut guard = self.items[slot].lock().await;
+            std::mem::swap(&mut removed, &mut *guard);
+            removed
+        })
+    }
+
+    fn remove_stack_specific(&self, slot: usize, amount: u8) -> InventoryFuture<'_, ItemStack> {
+        Box::pin(async move { split_stack(&self.items, slot, amount).await })
+    }
+
+    fn set_stack(&self, slot: usize, stack: ItemStack) -> InventoryFuture<'_, ()> {
+        Box::pin(async move {
+            *self.items[slot].lock().await = stack;
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Clearable for MerchantInventory {
+    fn clear(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            for item in self.items.iter() {
+                *item.lock().await = ItemStack::EMPTY.clone();
+            }
+        })
+    }
+}
@@ -0,0 +1,427 @@
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
+use crossbeam_utils::atomic::AtomicCell;
+use pumpkin_data::screen::WindowType;
+use pumpkin_world::inventory::Inventory;
+use pumpkin_world::item::ItemStack;
+use tokio::sync::Mutex;
+
+use super::Merchant;
+use super::merchant_inventory::MerchantInventory;
+use super::trade_offer::TradeOffer;
+use crate::player::player_inventory::PlayerInventory;
+use crate::screen_handler::{
+    InventoryPlayer, ItemStackFuture, ScreenHandler, ScreenHandlerBehaviour, ScreenHandlerFuture,
+    ScreenHandlerListener,
+};
+use crate::slot::{BoxFuture, NormalSlot, Slot};
+
+const OUTPUT_SLOT: usize = 2;
+const PLAYER_INVENTORY_START: i32 = 3;
+const PLAYER_HOTBAR_START: i32 = 30;
+const PLAYER_INVENTORY_END: i32 = 39;
+
+/// Finds the offer the payment slots can pay for, preferring the one selected by the player.
+fn find_offer(
+    offers: &[TradeOffer],
+    first: &ItemStack,
+    second: &ItemStack,
+    selected: usize,
+) -> Option<usize> {
+    let usable = |offer: &TradeOffer| !offer.is_disabled() && offer.matches(first, second);
+    if offers.get(selected).is_some_and(usable) {
+        return Some(selected);
+    }
+    offers.iter().position(usable)
+}
+
+/// TradeOutputSlot.java
+///
+/// Like the crafting `ResultSlot`, the result is stored directly in the slot and is recomputed
+/// every time the payment slots change.
+pub struct TradeOutputSlot {
+    pub merchant: Arc<dyn Merchant>,
+    pub inventory: Arc<MerchantInventory>,
+    pub id: AtomicU8,
+    pub result: Arc<Mutex<ItemStack>>,
+    selected_offer: AtomicUsize,
+    matched_offer: AtomicCell<Option<usize>>,
+}
+
+impl TradeOutputSlot {
+    pub fn new(merchant: Arc<dyn Merchant>, inventory: Arc<MerchantInventory>) -> Self {
+        Self {
+            merchant,
+            inventory,
+            id: AtomicU8::new(0),
+            result: Arc::new(Mutex::new(ItemStack::EMPTY.clone())),
+            selected_offer: AtomicUsize::new(0),
+            matched_offer: AtomicCell::new(None),
+        }
+    }
+
+    async fn refill_output(&self) -> ItemStack {
+        let first = self.inventory.items[0].lock().await.clone();
+        let second = self.inventory.items[1].lock().await.clone();
+        let offers = self.merchant.get_offers().await;
+
+        let matched = find_offer(
+            &offers,
+            &first,
+            &second,
+            self.selected_offer.load(Ordering::Relaxed),
+        );
+        self.matched_offer.store(matched);
+
+        let result = matched.map_or(ItemStack::EMPTY.clone(), |index| offers[index].sell.clone());
+        *self.result.lock().await = result.clone();
+        result
+    }
+
+    /// The index of the matched offer, if the payment slots can still pay for it.
+    async fn payable_offer(&self) -> Option<usize> {
+        let index = self.matched_offer.load()?;
+        let offers = self.merchant.get_offers().await;
+        let offer = offers.get(index)?;
+        let first = self.inventory.items[0].lock().await;
+        let second = self.inventory.items[1].lock().await;
+        (!offer.is_disabled() && offer.matches(&first, &second)).then_some(index)
+    }
+}
+
+impl Slot for TradeOutputSlot {
+    fn get_inventory(&self) -> Arc<dyn Inventory> {
+        self.inventory.clone()
+    }
+
+    fn get_index(&self) -> usize {
+        999 // this slot does not belong to any inventory
+    }
+
+    fn set_id(&self, id: usize) {
+        self.id.store(id as u8, Ordering::Relaxed);
+    }
+
+    fn on_quick_move_crafted(
+        &self,
+        _stack: ItemStack,
+        _stack_prev: ItemStack,
+    ) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            self.refill_output().await;
+        })
+    }
+
+    fn on_take_item<'a>(
+        &'a self,
+        _player: &'a dyn InventoryPlayer,
+        _stack: &'a ItemStack,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            // The result can only be taken while the offer is payable, see `can_take_items`
+            let Some(index) = self.payable_offer().await else {
+                return;
+            };
+            let offers = self.merchant.get_offers().await;
+            let offer = &offers[index];
+
+            let mut first = self.inventory.items[0].lock().await;
+            let mut second = self.inventory.items[1].lock().await;
+            first.decrement(offer.adjusted_first_buy().item_count);
+            if !offer.second_buy.is_empty() {
+                second.decrement(offer.second_buy.item_count);
+            }
+            drop(first);
+            drop(second);
+
+            self.merchant.trade(index).await;
+            self.refill_output().await;
+            self.mark_dirty().await;
+        })
+    }
+
+    fn can_take_items(&self, _player: &dyn InventoryPlayer) -> BoxFuture<'_, bool> {
+        Box::pin(async move { self.payable_offer().await.is_some() })
+    }
+
+    fn can_insert(&self, _stack: &ItemStack) -> BoxFuture<'_, bool> {
+        Box::pin(async move { false })
+    }
+
+    fn get_stack(&self) -> BoxFuture<'_, Arc<Mutex<ItemStack>>> {
+        Box::pin(async move { self.result.clone() })
+    }
+
+    fn get_cloned_stack(&self) -> BoxFuture<'_, ItemStack> {
+        Box::pin(async move { self.result.lock().await.clone() })
+    }
+
+    fn has_stack(&self) -> BoxFuture<'_, bool> {
+        Box::pin(async move { !self.result.lock().await.is_empty() })
+    }
+
+    fn set_stack(&self, _stack: ItemStack) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            self.refill_output().await;
+        })
+    }
+
+    fn set_stack_prev(&self, _stack: ItemStack, _previous_stack: ItemStack) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            self.refill_output().await;
+        })
+    }
+
+    fn mark_dirty(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            self.inventory.mark_dirty();
+        })
+    }
+
+    fn take_stack(&self, _amount: u8) -> BoxFuture<'_, ItemStack> {
+        Box::pin(async move {
+            // A trade always hands out the whole result
+            self.result.lock().await.clone()
+        })
+    }
+}
+
+impl ScreenHandlerListener for TradeOutputSlot {
+    fn on_slot_update<'a>(
+        &'a self,
+        screen_handler: &'a ScreenHandlerBehaviour,
+        slot: u8,
+        _stack: ItemStack,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        Box::pin(async move {
+            if (slot as usize) < self.inventory.size() {
+                let result = self.refill_output().await;
+
+                let next_revision = screen_handler.next_revision();
+                if let Some(sync_handler) = screen_handler.sync_handler.as_ref() {
+                    sync_handler
+                        .update_slot(screen_handler, OUTPUT_SLOT, &result, next_revision)
+                        .await;
+                }
+            }
+        })
+    }
+}
+
+/// MerchantScreenHandler.java
+pub struct MerchantScreenHandler {
+    behaviour: ScreenHandlerBehaviour,
+    merchant: Arc<dyn Merchant>,
+    inventory: Arc<MerchantInventory>,
+    output_slot: Arc<TradeOutputSlot>,
+    player_inventory: Arc<PlayerInventory>,
+}
+
+impl MerchantScreenHandler {
+    pub async fn new(
+        sync_id: u8,
+        player_inventory: &Arc<PlayerInventory>,
+        merchant: Arc<dyn Merchant>,
+    ) -> Self {
+        let inventory = Arc::new(MerchantInventory::new());
+        let output_slot = Arc::new(TradeOutputSlot::new(merchant.clone(), inventory.clone()));
+
+        let mut handler = Self {
+            behaviour: ScreenHandlerBehaviour::new(sync_id, Some(WindowType::Merchant)),
+            merchant,
+            inventory: inventory.clone(),
+            output_slot: output_slot.clone(),
+            player_inventory: player_inventory.clone(),
+        };
+
+        let payment_inventory: Arc<dyn Inventory> = inventory;
+        handler.add_slot(Arc::new(NormalSlot::new(payment_inventory.clone(), 0)));
+        handler.add_slot(Arc::new(NormalSlot::new(payment_inventory, 1)));
+        handler.add_slot(output_slot.clone());
+
+        let player_inventory: Arc<dyn Inventory> = player_inventory.clone();
+        handler.add_player_slots(&player_inventory);
+
+        handler.add_listener(output_slot).await;
+
+        handler
+    }
+
+    /// Selects the offer at `index` and moves matching items from the player's inventory into
+    /// the payment slots, like vanilla does when a trade is clicked in the offer list.
+    pub async fn switch_to(&self, index: usize) {
+        self.output_slot
+            .selected_offer
+            .store(index, Ordering::Relaxed);
+
+        let offers = self.merchant.get_offers().await;
+        let Some(offer) = offers.get(index) else {
+            return;
+        };
+
+        // Give back whatever the player already put in, we can't fill occupied slots
+        for payment in &self.inventory.items {
+            let mut stack = payment.lock().await;
+            if !stack.is_empty() {
+                self.player_inventory
+                    .insert_stack_anywhere(&mut stack)
+                    .await;
+                if !stack.is_empty() {
+                    return;
+                }
+            }
+        }
+
+        self.fill_payment_slot(0, &offer.adjusted_first_buy()).await;
+        self.fill_payment_slot(1, &offer.second_buy).await;
+    }
+
+    async fn fill_payment_slot(&self, payment: usize, cost: &ItemStack) {
+        if cost.is_empty() {
+            return;
+        }
+
+        let payment_stack = self.inventory.items[payment].clone();
+        for i in PLAYER_INVENTORY_START..PLAYER_INVENTORY_END {
+            let slot = self.get_behaviour().slots[i as usize].clone();
+            let stack = slot.get_stack().await;
+            let mut stack = stack.lock().await;
+            if stack.is_empty() || !ItemStack::are_items_and_components_equal(&stack, cost) {
+                continue;
+            }
+
+            let mut payment_stack = payment_stack.lock().await;
+            let current = if payment_stack.is_empty() {
+                0
+            } else {
+                payment_stack.item_count
+            };
+            let moved = (stack.get_max_stack_size() - current).min(stack.item_count);
+            if moved == 0 {
+                return;
+            }
+
+            if payment_stack.is_empty() {
+                *payment_stack = stack.copy_with_count(moved);
+            } else {
+                payment_stack.increment(moved);
+            }
+            stack.decrement(moved);
+        }
+    }
+}
+
+impl ScreenHandler for MerchantScreenHandler {
+    fn on_closed<'a>(&'a mut self, player: &'a dyn InventoryPlayer) -> ScreenHandlerFuture<'a, ()> {
+        Box::pin(async move {
+            self.default_on_closed(player).await;
+            self.drop_inventory(player, self.inventory.clone()).await;
+            self.merchant.on_close().await;
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_behaviour(&self) -> &ScreenHandlerBehaviour {
+        &self.behaviour
+    }
+
+    fn get_behaviour_mut(&mut self) -> &mut ScreenHandlerBehaviour {
+        &mut self.behaviour
+    }
+
+    fn quick_move<'a>(
+        &'a mut self,
+        player: &'a dyn InventoryPlayer,
+        slot_index: i32,
+    ) -> ItemStackFuture<'a> {
+        Box::pin(async move {
+            let slot = self.get_behaviour().slots[slot_index as usize].clone();
+            // Each trade of a shift click has to be paid for
+            if slot_index == OUTPUT_SLOT as i32 && !slot.can_take_items(player).await {
+                return ItemStack::EMPTY.clone();
+            }
+
+            if slot.has_stack().await {
+                let slot_stack = slot.get_stack().await;
+                let mut slot_stack = slot_stack.lock().await;
+                let stack_prev = slot_stack.clone();
+
+                let moved = if slot_index == OUTPUT_SLOT as i32 {
+                    self.insert_item(
+                        &mut slot_stack,
+                        PLAYER_INVENTORY_START,
+                        PLAYER_INVENTORY_END,
+                        true,
+                    )
+                    .await
+                } else if (PLAYER_INVENTORY_START..PLAYER_HOTBAR_START).contains(&slot_index) {
+                    // From main inventory to hotbar
+                    self.insert_item(
+                        &mut slot_stack,
+                        PLAYER_HOTBAR_START,
+                        PLAYER_INVENTORY_END,
+                        false,
+                    )
+                    .await
+                } else if (PLAYER_HOTBAR_START..PLAYER_INVENTORY_END).contains(&slot_index) {
+                    // From hotbar to main inventory
+                    self.insert_item(
+                        &mut slot_stack,
+                        PLAYER_INVENTORY_START,
+                        PLAYER_HOTBAR_START,
+                        false,
+                    )
+                    .await
+                } else {
+                    // From the payment slots back to the player
+                    self.insert_item(
+                        &mut slot_stack,
+                        PLAYER_INVENTORY_START,
+                        PLAYER_INVENTORY_END,
+                        false,
+                    )
+                    .await
+                };
+                if !moved {
+                    return ItemStack::EMPTY.clone();
+                }
+
+                let stack = slot_stack.clone();
+                drop(slot_stack); // release the lock before calling other methods
+
+                if stack.is_empty() {
+                    slot.set_stack_prev(ItemStack::EMPTY.clone(), stack_prev.clone())
+                        .await;
+                } else {
+                    slot.mark_dirty().await;
+                }
+
+                if stack.item_count == stack_prev.item_count {
+                    // Nothing changed
+                    return ItemStack::EMPTY.clone();
+                }
+
+                slot.on_take_item(player, &stack).await;
+
+                if slot_index == OUTPUT_SLOT as i32 {
+                    slot.on_quick_move_crafted(stack.clone(), stack_prev.clone())
+                        .await;
+                    // Whatever didn't fit is dropped, the payment is already gone
+                    if !stack.is_empty() {
+                        player.drop_item(stack, false).await;
+                    }
+                }
+
+                return stack_prev;
+            }
+
+            ItemStack::EMPTY.clone()
+        })
+    }
+}
@@ -0,0 +1,23 @@
+use crate::screen_handler::BoxFuture;
+
+pub mod merchant_inventory;
+pub mod merchant_screen_handler;
+pub mod trade_offer;
+
+use trade_offer::TradeOffer;
+
+/// Merchant.java
+///
+/// Implemented by anything a player can trade with, e.g. villagers.
+pub trait Merchant: Send + Sync {
+    /// Returns a snapshot of the merchant's current offers.
+    fn get_offers(&self) -> BoxFuture<'_, Vec<TradeOffer>>;
+
+    /// Called after the player took the result of the offer at `index`.
+    fn trade(&self, index: usize) -> BoxFuture<'_, ()>;
+
+    /// Called when the trading screen is closed.
+    fn on_close(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
+    }
+}
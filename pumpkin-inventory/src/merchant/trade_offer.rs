@@ -0,0 +1,105 @@
+use pumpkin_protocol::{
+    codec::item_stack_seralizer::ItemStackSerializer,
+    java::client::play::{MerchantOffer, TradeItem},
+};
+use pumpkin_world::item::ItemStack;
+use std::borrow::Cow;
+
+/// TradeOffer.java
+#[derive(Clone)]
+pub struct TradeOffer {
+    pub first_buy: ItemStack,
+    /// `ItemStack::EMPTY` when the offer only has a single cost.
+    pub second_buy: ItemStack,
+    pub sell: ItemStack,
+    pub uses: i32,
+    pub max_uses: i32,
+    /// Experience the merchant gains every time this offer is used.
+    pub merchant_experience: i32,
+    pub special_price: i32,
+    pub demand: i32,
+    pub price_multiplier: f32,
+}
+
+impl TradeOffer {
+    pub fn new(
+        first_buy: ItemStack,
+        second_buy: ItemStack,
+        sell: ItemStack,
+        max_uses: i32,
+        merchant_experience: i32,
+        price_multiplier: f32,
+    ) -> Self {
+        Self {
+            first_buy,
+            second_buy,
+            sell,
+            uses: 0,
+            max_uses,
+            merchant_experience,
+            special_price: 0,
+            demand: 0,
+            price_multiplier,
+        }
+    }
+
+    /// The first cost after demand and special price adjustments have been applied.
+    pub fn adjusted_first_buy(&self) -> ItemStack {
+        let base = i32::from(self.first_buy.item_count);
+        let demand_bonus = ((base * self.demand) as f32 * self.price_multiplier)
+            .floor()
+            .max(0.0) as i32;
+        let count = (base + demand_bonus + self.special_price)
+            .clamp(1, i32::from(self.first_buy.get_max_stack_size()));
+        self.first_buy.copy_with_count(count as u8)
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.uses >= self.max_uses
+    }
+
+    pub fn has_been_used(&self) -> bool {
+        self.uses > 0
+    }
+
+    /// Returns true if `first` and `second` can pay for this offer.
+    pub fn matches(&self, first: &ItemStack, second: &ItemStack) -> bool {
+        Self::accepts(&self.adjusted_first_buy(), first) && Self::accepts(&self.second_buy, second)
+    }
+
+    fn accepts(cost: &ItemStack, given: &ItemStack) -> bool {
+        if cost.is_empty() {
+            return given.is_empty();
+        }
+        given.item.id == cost.item.id && given.item_count >= cost.item_count
+    }
+
+    pub fn use_offer(&mut self) {
+        self.uses += 1;
+    }
+
+    pub fn reset_uses(&mut self) {
+        self.uses = 0;
+    }
+
+    /// Raises the price of offers that sold out and lowers the price of ones nobody wanted.
+    pub fn update_demand(&mut self) {
+        self.demand = self.demand + self.uses - (self.max_uses - self.uses);
+    }
+
+    pub fn to_protocol(&self) -> MerchantOffer<'_> {
+        MerchantOffer {
+            first_buy: TradeItem::new(self.first_buy.item.id, self.first_buy.item_count),
+            sell: ItemStackSerializer(Cow::Borrowed(&self.sell)),
+            second_buy: (!self.second_buy.is_empty())
+                .then(|| TradeItem::new(self.second_buy.item.id, self.second_buy.item_count)),
+            disabled: self.is_disabled(),
+            uses: self.uses,
+            max_uses: self.max_uses,
+            experience: self.merchant_experience,
+            special_price: self.special_price,
+            price_multiplier: self.price_multiplier,
+            demand: self.demand,
+        }
+    }
+}
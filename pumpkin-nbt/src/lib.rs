@@ -16,6 +16,7 @@ pub mod compound;
 pub mod deserializer;
 pub mod nbt_compress;
 pub mod serializer;
+pub mod snbt;
 pub mod tag;
 
 pub use deserializer::{from_bytes, from_bytes_unnamed};
@@ -55,6 +56,8 @@ pub enum Error {
     NegativeLength(i32),
     #[error("Length too large: {0}")]
     LargeLength(usize),
+    #[error("Invalid SNBT: {0}")]
+    InvalidSnbt(String),
 }
 
 impl ser::Error for Error {
@@ -0,0 +1,273 @@
+//! Parsing of stringified NBT (SNBT), the textual NBT format used in commands, like
+//! `{name:"Steve",Health:20.0f,Pos:[0.5d,64.0d,0.5d]}`.
+
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::Error;
+use crate::compound::NbtCompound;
+use crate::tag::NbtTag;
+
+/// How deep compounds and lists can be nested, like in vanilla. Deeper input is rejected instead
+/// of overflowing the stack.
+const MAX_DEPTH: usize = 512;
+
+/// Parses a single SNBT value, the whole input has to be consumed.
+pub fn from_snbt(input: &str) -> Result<NbtTag, Error> {
+    let mut parser = Parser {
+        input,
+        chars: input.char_indices().peekable(),
+        depth: 0,
+    };
+    let tag = parser.parse_value()?;
+    parser.skip_whitespace();
+    match parser.chars.next() {
+        None => Ok(tag),
+        Some((i, _)) => Err(parser.error(i, "trailing data")),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    /// How many compounds and lists the parser is inside of.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, at: usize, message: &str) -> Error {
+        Error::InvalidSnbt(format!("{message} at position {at} of {}", self.input))
+    }
+
+    fn position(&mut self) -> usize {
+        self.chars.peek().map_or(self.input.len(), |(i, _)| *i)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        self.skip_whitespace();
+        let at = self.position();
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            _ => Err(self.error(at, &format!("expected '{expected}'"))),
+        }
+    }
+
+    /// Consumes a separating comma, returns whether the sequence ended with `end` instead.
+    fn next_element(&mut self, end: char) -> Result<bool, Error> {
+        self.skip_whitespace();
+        let at = self.position();
+        match self.chars.next() {
+            Some((_, ',')) => Ok(false),
+            Some((_, c)) if c == end => Ok(true),
+            _ => Err(self.error(at, &format!("expected ',' or '{end}'"))),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<NbtTag, Error> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some((_, c @ ('{' | '['))) => {
+                let is_compound = *c == '{';
+                if self.depth == MAX_DEPTH {
+                    let at = self.position();
+                    return Err(self.error(at, &format!("nested deeper than {MAX_DEPTH} levels")));
+                }
+                self.depth += 1;
+                let value = if is_compound {
+                    self.parse_compound().map(NbtTag::Compound)
+                } else {
+                    self.parse_list()
+                };
+                self.depth -= 1;
+                value
+            }
+            Some((_, '"' | '\'')) => self.parse_quoted().map(NbtTag::String),
+            _ => {
+                let at = self.position();
+                let token = self.parse_unquoted();
+                if token.is_empty() {
+                    return Err(self.error(at, "expected a value"));
+                }
+                Ok(parse_primitive(token))
+            }
+        }
+    }
+
+    fn parse_compound(&mut self) -> Result<NbtCompound, Error> {
+        self.expect('{')?;
+        let mut compound = NbtCompound::new();
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == '}').is_some() {
+            return Ok(compound);
+        }
+        loop {
+            let key = self.parse_key()?;
+            self.expect(':')?;
+            let value = self.parse_value()?;
+            compound.put(&key, value);
+            if self.next_element('}')? {
+                return Ok(compound);
+            }
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<NbtTag, Error> {
+        self.expect('[')?;
+        // Typed arrays look like `[I;1,2,3]`
+        let input = self.input;
+        let start = self.position();
+        let array_type = input[start..]
+            .strip_prefix(['B', 'I', 'L'])
+            .filter(|rest| rest.starts_with(';'))
+            .map(|_| &input[start..=start]);
+        if array_type.is_some() {
+            self.chars.next();
+            self.chars.next();
+        }
+
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == ']').is_none() {
+            loop {
+                values.push(self.parse_value()?);
+                if self.next_element(']')? {
+                    break;
+                }
+            }
+        }
+
+        let Some(array_type) = array_type else {
+            return Ok(NbtTag::List(values));
+        };
+        let numbers = values
+            .iter()
+            .map(|value| match value {
+                NbtTag::Byte(n) => Some(i64::from(*n)),
+                NbtTag::Short(n) => Some(i64::from(*n)),
+                NbtTag::Int(n) => Some(i64::from(*n)),
+                NbtTag::Long(n) => Some(*n),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| self.error(start, "arrays can only hold numbers"))?;
+        Ok(match array_type {
+            "B" => NbtTag::ByteArray(numbers.iter().map(|n| *n as u8).collect()),
+            "I" => NbtTag::IntArray(numbers.iter().map(|n| *n as i32).collect()),
+            _ => NbtTag::LongArray(numbers),
+        })
+    }
+
+    fn parse_key(&mut self) -> Result<String, Error> {
+        self.skip_whitespace();
+        if matches!(self.chars.peek(), Some((_, '"' | '\''))) {
+            return self.parse_quoted();
+        }
+        let at = self.position();
+        let key = self.parse_unquoted();
+        if key.is_empty() {
+            return Err(self.error(at, "expected a key"));
+        }
+        Ok(key.to_string())
+    }
+
+    fn parse_quoted(&mut self) -> Result<String, Error> {
+        let at = self.position();
+        let Some((_, quote)) = self.chars.next() else {
+            return Err(self.error(at, "expected a string"));
+        };
+        let mut string = String::new();
+        while let Some((_, c)) = self.chars.next() {
+            match c {
+                '\\' => match self.chars.next() {
+                    Some((_, escaped)) => string.push(escaped),
+                    None => break,
+                },
+                c if c == quote => return Ok(string),
+                c => string.push(c),
+            }
+        }
+        Err(self.error(at, "unterminated string"))
+    }
+
+    fn parse_unquoted(&mut self) -> &'a str {
+        let start = self.position();
+        while self
+            .chars
+            .next_if(|(_, c)| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+' | ':'))
+            .is_some()
+        {}
+        let end = self.position();
+        &self.input[start..end]
+    }
+}
+
+/// Unquoted values are numbers if they parse as one, booleans are bytes and anything else is a
+/// string.
+fn parse_primitive(token: &str) -> NbtTag {
+    match token {
+        "true" => return NbtTag::Byte(1),
+        "false" => return NbtTag::Byte(0),
+        _ => {}
+    }
+    let (number, suffix) = token.split_at(token.len() - 1);
+    let parsed = match suffix {
+        "b" | "B" => number.parse().ok().map(NbtTag::Byte),
+        "s" | "S" => number.parse().ok().map(NbtTag::Short),
+        "l" | "L" => number.parse().ok().map(NbtTag::Long),
+        "f" | "F" => number.parse().ok().map(NbtTag::Float),
+        "d" | "D" => number.parse().ok().map(NbtTag::Double),
+        _ => token.parse().ok().map(NbtTag::Int).or_else(|| {
+            token
+                .contains('.')
+                .then(|| token.parse().ok().map(NbtTag::Double))
+                .flatten()
+        }),
+    };
+    parsed.unwrap_or_else(|| NbtTag::String(token.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MAX_DEPTH, from_snbt};
+    use crate::tag::NbtTag;
+
+    #[test]
+    fn parses_stringified_nbt() {
+        let tag = from_snbt(r#"{name:"Ste\"ve", 'minecraft:sharpness': 5, f: 0.5f, big: 3L, ok: true, pos: [1.5, 2d], ids: [I; 1, 2], empty: {}}"#).unwrap();
+        let NbtTag::Compound(compound) = tag else {
+            panic!("Expected a compound, got {tag:?}");
+        };
+        assert_eq!(compound.get_string("name"), Some("Ste\"ve"));
+        assert_eq!(compound.get_int("minecraft:sharpness"), Some(5));
+        assert_eq!(compound.get_float("f"), Some(0.5));
+        assert_eq!(compound.get_long("big"), Some(3));
+        assert_eq!(compound.get_byte("ok"), Some(1));
+        assert_eq!(
+            compound.get("pos"),
+            Some(&NbtTag::List(vec![
+                NbtTag::Double(1.5),
+                NbtTag::Double(2.0)
+            ]))
+        );
+        assert_eq!(compound.get("ids"), Some(&NbtTag::IntArray(vec![1, 2])));
+        assert!(compound.get_compound("empty").is_some());
+
+        assert_eq!(from_snbt("stone").unwrap(), NbtTag::String("stone".into()));
+        assert!(from_snbt("{a:1").is_err());
+        assert!(from_snbt("{a:1} b").is_err());
+        assert!(from_snbt("[B; 1, \"x\"]").is_err());
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected() {
+        let nested = |depth| "[".repeat(depth) + &"]".repeat(depth);
+        assert!(from_snbt(&nested(MAX_DEPTH)).is_ok());
+        assert!(from_snbt(&nested(MAX_DEPTH + 1)).is_err());
+        let compounds = "{a:".repeat(MAX_DEPTH + 1) + "1" + &"}".repeat(MAX_DEPTH + 1);
+        assert!(from_snbt(&compounds).is_err());
+    }
+}
@@ -5,6 +5,7 @@ use pumpkin_macros::packet;
 const MAX_ACK_RECORDS: u16 = 4096;
 
 use crate::{
+    bedrock::{RAKNET_ACK, RAKNET_NACK},
     codec::u24,
     serial::{PacketRead, PacketWrite},
 };
@@ -48,7 +49,8 @@ impl Ack {
             } else {
                 let start = u24::read(reader)?.0;
                 let end = u24::read(reader)?.0;
-                for i in start..end {
+                // Ranges include their end
+                for i in start..=end {
                     sequences.push(i);
                 }
             }
@@ -57,14 +59,23 @@ impl Ack {
     }
 
     pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
-        0xC0u8.write(writer)?;
+        self.write_with_id(RAKNET_ACK, writer)
+    }
+
+    /// Writes the sequences as a NACK, which asks the other side to resend them.
+    pub fn write_nack<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        self.write_with_id(RAKNET_NACK, writer)
+    }
+
+    fn write_with_id<W: Write>(&self, id: u8, writer: &mut W) -> Result<(), Error> {
+        id.write(writer)?;
         let mut count: u16 = 0;
 
         let mut buf = Vec::new();
 
         let mut start = self.sequences[0];
         let mut end = start;
-        for seq in self.sequences.clone() {
+        for &seq in &self.sequences[1..] {
             if seq == end + 1 {
                 end = seq
             } else {
@@ -1,3 +1,5 @@
+use std::io::{Error, Write};
+
 use pumpkin_macros::packet;
 
 use crate::{codec::var_uint::VarUInt, serial::PacketWrite};
@@ -8,8 +10,61 @@ pub struct CGamerulesChanged {
     pub rule_data: GameRules,
 }
 
-#[derive(PacketWrite, Default)]
+#[derive(Default)]
 pub struct GameRules {
-    // TODO https://mojang.github.io/bedrock-protocol-docs/html/GameRulesChangedPacketData.html
-    pub list_size: VarUInt,
+    // https://mojang.github.io/bedrock-protocol-docs/html/GameRulesChangedPacketData.html
+    pub rules: Vec<GameRule>,
+}
+
+impl GameRules {
+    /// Whether the client advances the time of day on its own.
+    pub fn daylight_cycle(enabled: bool) -> Self {
+        Self {
+            rules: vec![GameRule {
+                name: "dodaylightcycle".to_string(),
+                editable: false,
+                value: GameRuleValue::Bool(enabled),
+            }],
+        }
+    }
+}
+
+impl PacketWrite for GameRules {
+    fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        VarUInt(self.rules.len() as u32).write(writer)?;
+        self.rules.write(writer)
+    }
+}
+
+pub struct GameRule {
+    pub name: String,
+    pub editable: bool,
+    pub value: GameRuleValue,
+}
+
+pub enum GameRuleValue {
+    Bool(bool),
+    Int(u32),
+    Float(f32),
+}
+
+impl PacketWrite for GameRule {
+    fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        self.name.write(writer)?;
+        self.editable.write(writer)?;
+        match self.value {
+            GameRuleValue::Bool(value) => {
+                VarUInt(1).write(writer)?;
+                value.write(writer)
+            }
+            GameRuleValue::Int(value) => {
+                VarUInt(2).write(writer)?;
+                VarUInt(value).write(writer)
+            }
+            GameRuleValue::Float(value) => {
+                VarUInt(3).write(writer)?;
+                value.write(writer)
+            }
+        }
+    }
 }
@@ -0,0 +1,28 @@
+use pumpkin_macros::packet;
+use pumpkin_util::math::vector3::Vector3;
+
+use crate::{codec::var_int::VarInt, serial::PacketWrite};
+
+#[derive(PacketWrite)]
+#[packet(25)]
+pub struct CLevelEvent {
+    // https://mojang.github.io/bedrock-protocol-docs/html/LevelEventPacket.html
+    pub event_id: VarInt,
+    pub position: Vector3<f32>,
+    pub data: VarInt,
+}
+
+impl CLevelEvent {
+    pub const START_RAINING: i32 = 3001;
+    pub const START_THUNDERSTORM: i32 = 3002;
+    pub const STOP_RAINING: i32 = 3003;
+    pub const STOP_THUNDERSTORM: i32 = 3004;
+
+    pub const fn new(event_id: i32, position: Vector3<f32>, data: i32) -> Self {
+        Self {
+            event_id: VarInt(event_id),
+            position,
+            data: VarInt(data),
+        }
+    }
+}
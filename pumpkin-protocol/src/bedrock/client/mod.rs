@@ -7,9 +7,11 @@ pub mod gamerules_changed;
 pub mod handshake;
 pub mod inventory_content;
 pub mod level_chunk;
+pub mod level_event;
 pub mod move_player;
 pub mod network_chunk_publisher_update;
 pub mod network_settings;
+pub mod play_sound;
 pub mod play_status;
 pub mod player_hotbar;
 pub mod raknet;
@@ -19,5 +21,6 @@ pub mod set_actor_motion;
 pub mod set_player_gamemode;
 pub mod set_time;
 pub mod start_game;
+pub mod stop_sound;
 pub mod update_abilities;
 pub mod update_artributes;
@@ -0,0 +1,58 @@
+use pumpkin_macros::packet;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+use crate::{codec::bedrock_block_pos::NetworkPos, serial::PacketWrite};
+
+#[derive(PacketWrite)]
+#[packet(86)]
+pub struct CPlaySound {
+    // https://mojang.github.io/bedrock-protocol-docs/html/PlaySoundPacket.html
+    pub name: String,
+    /// The position in eighths of a block.
+    pub position: NetworkPos,
+    pub volume: f32,
+    pub pitch: f32,
+}
+
+impl CPlaySound {
+    pub fn new(name: String, position: &Vector3<f64>, volume: f32, pitch: f32) -> Self {
+        Self {
+            name,
+            position: NetworkPos(BlockPos::new(
+                (position.x * 8.0) as i32,
+                (position.y * 8.0) as i32,
+                (position.z * 8.0) as i32,
+            )),
+            volume,
+            pitch,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::CPlaySound;
+    use crate::serial::PacketWrite;
+
+    #[test]
+    fn position_is_sent_in_eighths_of_a_block() {
+        let packet = CPlaySound::new(
+            "dig.stone".to_string(),
+            &Vector3::new(1.0, 2.5, -1.0),
+            1.0,
+            0.5,
+        );
+        let mut data = Vec::new();
+        packet.write(&mut data).unwrap();
+
+        let mut expected = vec![9];
+        expected.extend_from_slice(b"dig.stone");
+        // Zigzag encoded x and z, unsigned y
+        expected.extend_from_slice(&[16, 20, 15]);
+        expected.extend_from_slice(&1.0f32.to_le_bytes());
+        expected.extend_from_slice(&0.5f32.to_le_bytes());
+        assert_eq!(data, expected);
+    }
+}
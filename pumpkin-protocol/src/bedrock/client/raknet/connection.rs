@@ -3,6 +3,21 @@ use std::net::SocketAddr;
 use pumpkin_macros::packet;
 
 use crate::serial::PacketWrite;
+
+#[derive(PacketWrite)]
+#[packet(0x00)]
+pub struct CConnectedPing {
+    /// Time the ping was sent, echoed back by the client
+    #[serial(big_endian)]
+    time: u64,
+}
+
+impl CConnectedPing {
+    pub fn new(time: u64) -> Self {
+        Self { time }
+    }
+}
+
 #[derive(PacketWrite)]
 #[packet(0x03)]
 pub struct CConnectedPong {
@@ -0,0 +1,23 @@
+use pumpkin_macros::packet;
+
+use crate::serial::PacketWrite;
+
+#[derive(PacketWrite)]
+#[packet(87)]
+pub struct CStopSound {
+    // https://mojang.github.io/bedrock-protocol-docs/html/StopSoundPacket.html
+    pub name: String,
+    pub stop_all: bool,
+    pub stop_legacy_music: bool,
+}
+
+impl CStopSound {
+    /// Stops the sound with the given name, or all sounds without one.
+    pub fn new(name: Option<String>) -> Self {
+        Self {
+            stop_all: name.is_none(),
+            name: name.unwrap_or_default(),
+            stop_legacy_music: false,
+        }
+    }
+}
@@ -5,6 +5,7 @@ pub mod network_item;
 pub mod packet_decoder;
 pub mod packet_encoder;
 pub mod server;
+pub mod sound;
 
 pub const UDP_HEADER_SIZE: u16 = 28;
 pub const MTU: usize = 1400;
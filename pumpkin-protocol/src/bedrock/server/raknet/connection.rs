@@ -12,6 +12,16 @@ pub struct SConnectedPing {
     pub time: u64,
 }
 
+#[derive(PacketRead)]
+#[packet(0x03)]
+pub struct SConnectedPong {
+    /// The time of our `CConnectedPing`
+    #[serial(big_endian)]
+    pub ping_time: u64,
+    #[serial(big_endian)]
+    pub pong_time: u64,
+}
+
 #[derive(PacketRead)]
 #[packet(0x09)]
 pub struct SConnectionRequest {
@@ -0,0 +1,100 @@
+/// Translates a Java sound name like `block.stone.break` to the Bedrock one like `dig.stone`.
+///
+/// Sounds with their own Bedrock name are looked up, block and entity sounds follow the Bedrock
+/// naming scheme and everything else keeps its Java name.
+#[must_use]
+pub fn bedrock_sound_name(java_name: &str) -> String {
+    let java_name = java_name.strip_prefix("minecraft:").unwrap_or(java_name);
+    if let Some(name) = renamed_sound(java_name) {
+        return name.to_string();
+    }
+
+    if let Some(block) = java_name.strip_prefix("block.")
+        && let Some((material, event)) = block.rsplit_once('.')
+    {
+        // Bedrock plays the break sound when placing a block
+        let event = match event {
+            "break" | "place" => Some("dig"),
+            "step" => Some("step"),
+            "hit" => Some("hit"),
+            "fall" => Some("fall"),
+            _ => None,
+        };
+        if let Some(event) = event {
+            let material = match material {
+                "wool" => "cloth",
+                material => material,
+            };
+            return format!("{event}.{material}");
+        }
+    }
+
+    if let Some(entity) = java_name.strip_prefix("entity.")
+        && let Some((mob, event)) = entity.split_once('.')
+    {
+        let event = match event {
+            "ambient" => "say",
+            event => event,
+        };
+        return format!("mob.{mob}.{event}");
+    }
+
+    java_name.to_string()
+}
+
+fn renamed_sound(java_name: &str) -> Option<&'static str> {
+    Some(match java_name {
+        "block.anvil.land" => "random.anvil_land",
+        "block.anvil.use" => "random.anvil_use",
+        "block.chest.close" => "random.chestclosed",
+        "block.chest.open" => "random.chestopen",
+        "block.fire.extinguish" => "random.fizz",
+        "block.glass.break" => "random.glass",
+        "block.lever.click" => "random.click",
+        "block.portal.ambient" => "portal.portal",
+        "block.stone_button.click_on" | "block.stone_button.click_off" => "random.click",
+        "block.wooden_door.close" => "random.door_close",
+        "block.wooden_door.open" => "random.door_open",
+        "entity.arrow.hit" => "random.bowhit",
+        "entity.arrow.shoot" => "random.bow",
+        "entity.creeper.primed" | "entity.tnt.primed" => "random.fuse",
+        "entity.experience_orb.pickup" => "random.orb",
+        "entity.generic.drink" => "random.drink",
+        "entity.generic.eat" => "random.eat",
+        "entity.generic.explode" => "random.explode",
+        "entity.item.break" => "random.break",
+        "entity.item.pickup" => "random.pop",
+        "entity.lightning_bolt.thunder" => "ambient.weather.thunder",
+        "entity.player.attack.strong" => "game.player.attack.strong",
+        "entity.player.burp" => "random.burp",
+        "entity.player.death" => "game.player.die",
+        "entity.player.hurt" => "game.player.hurt",
+        "entity.player.levelup" => "random.levelup",
+        "weather.rain" => "ambient.weather.rain",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::bedrock_sound_name;
+
+    #[test]
+    fn java_sounds_get_their_bedrock_names() {
+        assert_eq!(bedrock_sound_name("block.stone.break"), "dig.stone");
+        assert_eq!(
+            bedrock_sound_name("minecraft:block.stone.place"),
+            "dig.stone"
+        );
+        assert_eq!(bedrock_sound_name("block.wool.step"), "step.cloth");
+        assert_eq!(bedrock_sound_name("block.chest.open"), "random.chestopen");
+        assert_eq!(bedrock_sound_name("entity.cow.ambient"), "mob.cow.say");
+        assert_eq!(bedrock_sound_name("entity.zombie.hurt"), "mob.zombie.hurt");
+        assert_eq!(
+            bedrock_sound_name("entity.player.levelup"),
+            "random.levelup"
+        );
+        // Sounds without a known Bedrock name are passed through
+        assert_eq!(bedrock_sound_name("music.menu"), "music.menu");
+    }
+}
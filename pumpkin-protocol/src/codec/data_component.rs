@@ -2,8 +2,11 @@ use crate::codec::var_int::VarInt;
 use pumpkin_data::Enchantment;
 use pumpkin_data::data_component::DataComponent;
 use pumpkin_data::data_component_impl::{
-    DamageImpl, DataComponentImpl, EnchantmentsImpl, MaxStackSizeImpl, get,
+    DamageImpl, DataComponentImpl, EnchantmentsImpl, LodestoneTrackerImpl, MaxStackSizeImpl,
+    WrittenBookContentImpl, get,
 };
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::text::TextComponent;
 use serde::de;
 use serde::de::SeqAccess;
 use serde::ser::SerializeStruct;
@@ -79,6 +82,59 @@ impl DataComponentCodec<Self> for EnchantmentsImpl {
     }
 }
 
+impl DataComponentCodec<Self> for WrittenBookContentImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        // Filterable strings and pages, we never send a filtered version
+        seq.serialize_field::<String>("", &self.title)?;
+        seq.serialize_field::<bool>("", &false)?;
+        seq.serialize_field::<String>("", &self.author)?;
+        seq.serialize_field::<VarInt>("", &VarInt::from(self.generation))?;
+        seq.serialize_field::<VarInt>("", &VarInt::from(self.pages.len() as i32))?;
+        for page in &self.pages {
+            seq.serialize_field::<TextComponent>("", page)?;
+            seq.serialize_field::<bool>("", &false)?;
+        }
+        seq.serialize_field::<bool>("", &self.resolved)
+    }
+    fn deserialize<'a, A: SeqAccess<'a>>(_seq: &mut A) -> Result<Self, A::Error> {
+        // TODO: Reading text components, the client only sends written books in creative mode
+        Err(de::Error::custom(
+            "WrittenBookContent can not be deserialized!",
+        ))
+    }
+}
+
+impl DataComponentCodec<Self> for LodestoneTrackerImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        seq.serialize_field::<bool>("", &self.target.is_some())?;
+        if let Some((dimension, pos)) = &self.target {
+            seq.serialize_field::<String>("", dimension)?;
+            seq.serialize_field::<BlockPos>("", pos)?;
+        }
+        seq.serialize_field::<bool>("", &self.tracked)
+    }
+    fn deserialize<'a, A: SeqAccess<'a>>(seq: &mut A) -> Result<Self, A::Error> {
+        let has_target = seq
+            .next_element::<bool>()?
+            .ok_or(de::Error::custom("No LodestoneTracker target bool!"))?;
+        let target = if has_target {
+            let dimension = seq
+                .next_element::<String>()?
+                .ok_or(de::Error::custom("No LodestoneTracker dimension String!"))?;
+            let pos = seq
+                .next_element::<i64>()?
+                .ok_or(de::Error::custom("No LodestoneTracker pos i64!"))?;
+            Some((dimension, BlockPos::from_i64(pos)))
+        } else {
+            None
+        };
+        let tracked = seq
+            .next_element::<bool>()?
+            .ok_or(de::Error::custom("No LodestoneTracker tracked bool!"))?;
+        Ok(Self { target, tracked })
+    }
+}
+
 pub fn deserialize<'a, A: SeqAccess<'a>>(
     id: DataComponent,
     seq: &mut A,
@@ -87,6 +143,7 @@ pub fn deserialize<'a, A: SeqAccess<'a>>(
         DataComponent::MaxStackSize => Ok(MaxStackSizeImpl::deserialize(seq)?.to_dyn()),
         DataComponent::Enchantments => Ok(EnchantmentsImpl::deserialize(seq)?.to_dyn()),
         DataComponent::Damage => Ok(DamageImpl::deserialize(seq)?.to_dyn()),
+        DataComponent::LodestoneTracker => Ok(LodestoneTrackerImpl::deserialize(seq)?.to_dyn()),
         _ => todo!("{} not yet implemented", id.to_name()),
     }
 }
@@ -99,6 +156,8 @@ pub fn serialize<T: SerializeStruct>(
         DataComponent::MaxStackSize => get::<MaxStackSizeImpl>(value).serialize(seq),
         DataComponent::Enchantments => get::<EnchantmentsImpl>(value).serialize(seq),
         DataComponent::Damage => get::<DamageImpl>(value).serialize(seq),
+        DataComponent::LodestoneTracker => get::<LodestoneTrackerImpl>(value).serialize(seq),
+        DataComponent::WrittenBookContent => get::<WrittenBookContentImpl>(value).serialize(seq),
         _ => todo!("{} not yet implemented", id.to_name()),
     }
 }
@@ -50,11 +50,18 @@ impl VarInt {
         Ok(())
     }
 
-    // TODO: Validate that the first byte will not overflow a i32
+    /// The last byte may only hold the 4 highest bits of the `i32`, anything else would overflow.
+    const fn overflows(index: usize, byte: u8) -> bool {
+        index == Self::MAX_SIZE.get() - 1 && byte & 0xF0 != 0
+    }
+
     pub fn decode(read: &mut impl Read) -> Result<Self, ReadingError> {
         let mut val = 0;
         for i in 0..Self::MAX_SIZE.get() {
             let byte = read.get_u8()?;
+            if Self::overflows(i, byte) {
+                break;
+            }
             val |= (i32::from(byte) & 0x7F) << (i * 7);
             if byte & 0x80 == 0 {
                 return Ok(VarInt(val));
@@ -75,6 +82,9 @@ impl VarInt {
                     ReadingError::Incomplete(err.to_string())
                 }
             })?;
+            if Self::overflows(i, byte) {
+                break;
+            }
             val |= (i32::from(byte) & 0x7F) << (i * 7);
             if byte & 0x80 == 0 {
                 return Ok(VarInt(val));
@@ -183,6 +193,9 @@ impl<'de> Deserialize<'de> for VarInt {
                 let mut val = 0;
                 for i in 0..VarInt::MAX_SIZE.get() {
                     if let Some(byte) = seq.next_element::<u8>()? {
+                        if VarInt::overflows(i, byte) {
+                            break;
+                        }
                         val |= (i32::from(byte) & 0b01111111) << (i * 7);
                         if byte & 0b10000000 == 0 {
                             return Ok(VarInt(val));
@@ -218,11 +231,15 @@ impl PacketRead for VarInt {
         let mut val = 0;
         for i in 0..Self::MAX_SIZE.get() {
             let byte = u8::read(read)?;
-            val |= (i32::from(byte) & 0x7F) << (i * 7);
+            if Self::overflows(i, byte) {
+                break;
+            }
+            val |= (u32::from(byte) & 0x7F) << (i * 7);
             if byte & 0x80 == 0 {
-                return Ok(VarInt((val >> 1) ^ (val << 31)));
+                // Undo the zigzag encoding
+                return Ok(VarInt((val >> 1) as i32 ^ -((val & 1) as i32)));
             }
         }
-        Err(Error::new(ErrorKind::InvalidData, ""))
+        Err(Error::new(ErrorKind::InvalidData, "VarInt was too large"))
     }
 }
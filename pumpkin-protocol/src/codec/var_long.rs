@@ -39,11 +39,18 @@ impl VarLong {
         Ok(())
     }
 
-    // TODO: Validate that the first byte will not overflow a i64
+    /// The last byte may only hold the highest bit of the `i64`, anything else would overflow.
+    const fn overflows(index: usize, byte: u8) -> bool {
+        index == Self::MAX_SIZE.get() - 1 && byte & 0xFE != 0
+    }
+
     pub fn decode(read: &mut impl Read) -> Result<Self, ReadingError> {
         let mut val = 0;
         for i in 0..Self::MAX_SIZE.get() {
             let byte = read.get_u8()?;
+            if Self::overflows(i, byte) {
+                break;
+            }
             val |= (i64::from(byte) & 0x7F) << (i * 7);
             if byte & 0x80 == 0 {
                 return Ok(VarLong(val));
@@ -128,6 +135,9 @@ impl<'de> Deserialize<'de> for VarLong {
                 let mut val = 0;
                 for i in 0..VarLong::MAX_SIZE.get() {
                     if let Some(byte) = seq.next_element::<u8>()? {
+                        if VarLong::overflows(i, byte) {
+                            break;
+                        }
                         val |= (i64::from(byte) & 0b01111111) << (i * 7);
                         if byte & 0b10000000 == 0 {
                             return Ok(VarLong(val));
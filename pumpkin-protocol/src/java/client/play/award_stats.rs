@@ -0,0 +1,25 @@
+use pumpkin_data::packet::clientbound::PLAY_AWARD_STATS;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Sent in response to the client requesting its statistics.
+#[derive(Serialize)]
+#[packet(PLAY_AWARD_STATS)]
+pub struct CAwardStats<'a> {
+    pub stats: &'a [Statistic],
+}
+
+impl<'a> CAwardStats<'a> {
+    pub fn new(stats: &'a [Statistic]) -> Self {
+        Self { stats }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Statistic {
+    pub category_id: VarInt,
+    pub statistic_id: VarInt,
+    pub value: VarInt,
+}
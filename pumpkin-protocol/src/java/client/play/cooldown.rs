@@ -0,0 +1,23 @@
+use pumpkin_data::packet::clientbound::PLAY_COOLDOWN;
+use pumpkin_macros::packet;
+use pumpkin_util::resource_location::ResourceLocation;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Puts all items of a cooldown group on cooldown, a duration of 0 removes the cooldown.
+#[derive(Serialize)]
+#[packet(PLAY_COOLDOWN)]
+pub struct CCooldown<'a> {
+    pub cooldown_group: &'a ResourceLocation,
+    pub duration: VarInt,
+}
+
+impl<'a> CCooldown<'a> {
+    pub fn new(cooldown_group: &'a ResourceLocation, duration: VarInt) -> Self {
+        Self {
+            cooldown_group,
+            duration,
+        }
+    }
+}
@@ -37,4 +37,8 @@ impl<T> Metadata<T> {
             value,
         }
     }
+
+    pub const fn index(&self) -> u8 {
+        self.index
+    }
 }
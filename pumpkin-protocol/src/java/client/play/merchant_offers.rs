@@ -0,0 +1,70 @@
+use pumpkin_data::packet::clientbound::PLAY_MERCHANT_OFFERS;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+use crate::codec::item_stack_seralizer::ItemStackSerializer;
+
+/// An item a merchant wants in exchange for an offer.
+#[derive(Serialize)]
+pub struct TradeItem {
+    pub item_id: VarInt,
+    pub count: VarInt,
+    /// Exact component predicates are not supported yet, so this is always empty.
+    pub components: VarInt,
+}
+
+impl TradeItem {
+    pub fn new(item_id: u16, count: u8) -> Self {
+        Self {
+            item_id: item_id.into(),
+            count: count.into(),
+            components: VarInt(0),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MerchantOffer<'a> {
+    pub first_buy: TradeItem,
+    pub sell: ItemStackSerializer<'a>,
+    pub second_buy: Option<TradeItem>,
+    pub disabled: bool,
+    pub uses: i32,
+    pub max_uses: i32,
+    pub experience: i32,
+    pub special_price: i32,
+    pub price_multiplier: f32,
+    pub demand: i32,
+}
+
+#[derive(Serialize)]
+#[packet(PLAY_MERCHANT_OFFERS)]
+pub struct CMerchantOffers<'a> {
+    pub window_id: VarInt,
+    pub offers: &'a [MerchantOffer<'a>],
+    pub level: VarInt,
+    pub experience: VarInt,
+    pub show_progress: bool,
+    pub can_restock: bool,
+}
+
+impl<'a> CMerchantOffers<'a> {
+    pub fn new(
+        window_id: VarInt,
+        offers: &'a [MerchantOffer<'a>],
+        level: VarInt,
+        experience: VarInt,
+        show_progress: bool,
+        can_restock: bool,
+    ) -> Self {
+        Self {
+            window_id,
+            offers,
+            level,
+            experience,
+            show_progress,
+            can_restock,
+        }
+    }
+}
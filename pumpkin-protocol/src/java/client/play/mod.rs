@@ -1,5 +1,6 @@
 mod acknowledge_block;
 mod actionbar;
+mod award_stats;
 mod block_destroy_stage;
 mod block_entity_data;
 mod block_event;
@@ -17,6 +18,7 @@ mod combat_death;
 mod command_suggestions;
 mod commands;
 mod cookie_request;
+mod cooldown;
 mod damage_event;
 mod disconnect;
 mod disguised_chat_message;
@@ -35,7 +37,9 @@ mod initialize_world_border;
 mod keep_alive;
 mod level_event;
 mod login;
+mod merchant_offers;
 mod multi_block_update;
+mod open_book;
 mod open_screen;
 mod open_sign_editor;
 mod particle;
@@ -70,7 +74,9 @@ mod set_equipment;
 mod set_experience;
 mod set_health;
 mod set_held_item;
+mod set_passengers;
 mod set_player_inventory;
+mod set_player_team;
 mod set_time;
 mod set_title;
 mod set_title_animation;
@@ -87,6 +93,7 @@ mod ticking_step;
 mod transfer;
 mod unload_chunk;
 mod update_advancements;
+mod update_attributes;
 mod update_entity_pos;
 mod update_entity_pos_rot;
 mod update_entity_rot;
@@ -97,6 +104,7 @@ mod worldevent;
 
 pub use acknowledge_block::*;
 pub use actionbar::*;
+pub use award_stats::*;
 pub use block_destroy_stage::*;
 pub use block_entity_data::*;
 pub use block_event::*;
@@ -114,6 +122,7 @@ pub use combat_death::*;
 pub use command_suggestions::*;
 pub use commands::*;
 pub use cookie_request::*;
+pub use cooldown::*;
 pub use damage_event::*;
 pub use disconnect::*;
 pub use disguised_chat_message::*;
@@ -132,7 +141,9 @@ pub use initialize_world_border::*;
 pub use keep_alive::*;
 pub use level_event::*;
 pub use login::*;
+pub use merchant_offers::*;
 pub use multi_block_update::*;
+pub use open_book::*;
 pub use open_screen::*;
 pub use open_sign_editor::*;
 pub use particle::*;
@@ -167,7 +178,9 @@ pub use set_equipment::*;
 pub use set_experience::*;
 pub use set_health::*;
 pub use set_held_item::*;
+pub use set_passengers::*;
 pub use set_player_inventory::*;
+pub use set_player_team::*;
 pub use set_time::*;
 pub use set_title::*;
 pub use set_title_animation::*;
@@ -184,6 +197,7 @@ pub use ticking_step::*;
 pub use transfer::*;
 pub use unload_chunk::*;
 pub use update_advancements::*;
+pub use update_attributes::*;
 pub use update_entity_pos::*;
 pub use update_entity_pos_rot::*;
 pub use update_entity_rot::*;
@@ -0,0 +1,18 @@
+use pumpkin_data::packet::clientbound::PLAY_OPEN_BOOK;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Opens the written book held in `hand`.
+#[derive(Serialize)]
+#[packet(PLAY_OPEN_BOOK)]
+pub struct COpenBook {
+    pub hand: VarInt,
+}
+
+impl COpenBook {
+    pub fn new(hand: VarInt) -> Self {
+        Self { hand }
+    }
+}
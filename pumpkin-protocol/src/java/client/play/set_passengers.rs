@@ -0,0 +1,47 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_PASSENGERS;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Replaces all passengers of a vehicle, an empty list dismounts everyone.
+#[derive(Serialize)]
+#[packet(PLAY_SET_PASSENGERS)]
+pub struct CSetPassengers<'a> {
+    pub entity_id: VarInt,
+    pub passenger_ids: &'a [VarInt],
+}
+
+impl<'a> CSetPassengers<'a> {
+    pub fn new(entity_id: VarInt, passenger_ids: &'a [VarInt]) -> Self {
+        Self {
+            entity_id,
+            passenger_ids,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ClientPacket, VarInt};
+
+    use super::CSetPassengers;
+
+    #[test]
+    fn player_riding_player() {
+        // Player 12 rides on top of player 7
+        let passengers = [VarInt(12)];
+        let mut buf = Vec::new();
+        CSetPassengers::new(VarInt(7), &passengers)
+            .write_packet_data(&mut buf)
+            .unwrap();
+        assert_eq!(buf, vec![7, 1, 12]);
+
+        // Dismounting sends the vehicle without any passengers
+        let mut buf = Vec::new();
+        CSetPassengers::new(VarInt(7), &[])
+            .write_packet_data(&mut buf)
+            .unwrap();
+        assert_eq!(buf, vec![7, 0]);
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::Write;
+
+use pumpkin_data::packet::clientbound::PLAY_SET_PLAYER_TEAM;
+use pumpkin_macros::packet;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{ClientPacket, VarInt, WritingError, ser::NetworkWriteExt};
+
+/// Creates, changes or removes a team, or changes its members. Members are player names or the
+/// UUIDs of other entities.
+#[packet(PLAY_SET_PLAYER_TEAM)]
+pub struct CSetPlayerTeam<'a> {
+    pub name: &'a str,
+    pub method: TeamMethod<'a>,
+}
+
+impl<'a> CSetPlayerTeam<'a> {
+    pub fn new(name: &'a str, method: TeamMethod<'a>) -> Self {
+        Self { name, method }
+    }
+}
+
+pub enum TeamMethod<'a> {
+    Create(TeamInfo<'a>, &'a [String]),
+    Remove,
+    Update(TeamInfo<'a>),
+    AddMembers(&'a [String]),
+    RemoveMembers(&'a [String]),
+}
+
+/// How a team and its members are shown.
+pub struct TeamInfo<'a> {
+    pub display_name: &'a TextComponent,
+    pub friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+    pub name_tag_visibility: NameTagVisibility,
+    pub collision_rule: CollisionRule,
+    /// The color of the names of the members, `None` keeps their default color.
+    pub color: Option<NamedColor>,
+    pub prefix: &'a TextComponent,
+    pub suffix: &'a TextComponent,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NameTagVisibility {
+    #[default]
+    Always,
+    Never,
+    HideForOtherTeams,
+    HideForOwnTeam,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollisionRule {
+    #[default]
+    Always,
+    Never,
+    PushOtherTeams,
+    PushOwnTeam,
+}
+
+/// The id of the `reset` formatting, which doesn't change the color.
+const NO_COLOR: i32 = 21;
+
+impl ClientPacket for CSetPlayerTeam<'_> {
+    fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        let mut write = write;
+
+        write.write_string(self.name)?;
+        let (method, info, members) = match &self.method {
+            TeamMethod::Create(info, members) => (0, Some(info), Some(members)),
+            TeamMethod::Remove => (1, None, None),
+            TeamMethod::Update(info) => (2, Some(info), None),
+            TeamMethod::AddMembers(members) => (3, None, Some(members)),
+            TeamMethod::RemoveMembers(members) => (4, None, Some(members)),
+        };
+        write.write_u8(method)?;
+        if let Some(info) = info {
+            write.write_slice(&info.display_name.encode())?;
+            write.write_u8(
+                u8::from(info.friendly_fire) | (u8::from(info.see_friendly_invisibles) << 1),
+            )?;
+            write.write_var_int(&VarInt(info.name_tag_visibility as i32))?;
+            write.write_var_int(&VarInt(info.collision_rule as i32))?;
+            write.write_var_int(&VarInt(info.color.map_or(NO_COLOR, |color| color as i32)))?;
+            write.write_slice(&info.prefix.encode())?;
+            write.write_slice(&info.suffix.encode())?;
+        }
+        if let Some(members) = members {
+            write.write_list(members, |write, member| write.write_string(member))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ClientPacket;
+
+    use super::{CSetPlayerTeam, TeamMethod};
+
+    #[test]
+    fn team_members_are_written_by_name() {
+        let members = ["Alex".to_string()];
+        let mut buf = Vec::new();
+        CSetPlayerTeam::new("red", TeamMethod::AddMembers(&members))
+            .write_packet_data(&mut buf)
+            .unwrap();
+        assert_eq!(buf, b"\x03red\x03\x01\x04Alex");
+
+        let mut buf = Vec::new();
+        CSetPlayerTeam::new("red", TeamMethod::Remove)
+            .write_packet_data(&mut buf)
+            .unwrap();
+        assert_eq!(buf, b"\x03red\x01");
+    }
+}
@@ -0,0 +1,37 @@
+use pumpkin_data::packet::clientbound::PLAY_UPDATE_ATTRIBUTES;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Sets the base values and modifiers of an entity's attributes, the ones not sent stay the same.
+#[derive(Serialize)]
+#[packet(PLAY_UPDATE_ATTRIBUTES)]
+pub struct CUpdateAttributes {
+    pub entity_id: VarInt,
+    pub properties: Vec<AttributeProperty>,
+}
+
+impl CUpdateAttributes {
+    pub fn new(entity_id: VarInt, properties: Vec<AttributeProperty>) -> Self {
+        Self {
+            entity_id,
+            properties,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct AttributeProperty {
+    pub attribute_id: VarInt,
+    pub base_value: f64,
+    pub modifiers: Vec<PropertyModifier>,
+}
+
+#[derive(Serialize)]
+pub struct PropertyModifier {
+    pub id: String,
+    pub amount: f64,
+    /// `0` adds the amount, `1` multiplies the base value and `2` multiplies the total value.
+    pub operation: i8,
+}
@@ -73,6 +73,8 @@ impl<R: AsyncRead + Unpin> AsyncRead for DecryptionReader<R> {
 pub struct TCPNetworkDecoder<R: AsyncRead + Unpin> {
     reader: DecryptionReader<R>,
     compression: Option<CompressionThreshold>,
+    /// The largest a packet may be once decompressed.
+    max_decompressed_size: usize,
 }
 
 impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
@@ -80,6 +82,7 @@ impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
         Self {
             reader: DecryptionReader::None(reader),
             compression: None,
+            max_decompressed_size: MAX_PACKET_DATA_SIZE,
         }
     }
 
@@ -87,6 +90,11 @@ impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
         self.compression = Some(threshold);
     }
 
+    /// Packets declaring, or decompressing to, more than `size` bytes are rejected.
+    pub fn set_max_decompressed_size(&mut self, size: usize) {
+        self.max_decompressed_size = size.min(MAX_PACKET_DATA_SIZE);
+    }
+
     /// NOTE: Encryption can only be set; a minecraft stream cannot go back to being unencrypted
     pub fn set_encryption(&mut self, key: &[u8; 16]) {
         if matches!(self.reader, DecryptionReader::Decrypt(_)) {
@@ -112,16 +120,25 @@ impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
 
         let mut bounded_reader = (&mut self.reader).take(packet_len);
 
+        // The declared length of a compressed packet, its content is checked against it
+        let mut decompressed_length = None;
         let mut reader = if let Some(threshold) = self.compression {
-            let decompressed_length = VarInt::decode_async(&mut bounded_reader).await?;
-            let raw_packet_length = packet_len - decompressed_length.written_size() as u64;
-            let decompressed_length = decompressed_length.0 as usize;
+            let data_length = VarInt::decode_async(&mut bounded_reader).await?;
+            let raw_packet_length = packet_len - data_length.written_size() as u64;
 
-            if !(0..=MAX_PACKET_DATA_SIZE).contains(&decompressed_length) {
+            if data_length.0 < 0 {
                 Err(PacketDecodeError::TooLong)?
             }
+            let data_length = data_length.0 as usize;
+            if data_length > self.max_decompressed_size {
+                Err(ReadingError::TooLarge(format!(
+                    "packet declares {data_length} bytes once decompressed, the maximum is {}",
+                    self.max_decompressed_size
+                )))?
+            }
 
-            if decompressed_length > 0 {
+            if data_length > 0 {
+                decompressed_length = Some(data_length);
                 DecompressionReader::Decompress(ZlibDecoder::new(BufReader::new(bounded_reader)))
             } else {
                 // Validate that we are not less than the compression threshold
@@ -138,10 +155,13 @@ impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
         // TODO: Serde is sync so we need to write to a buffer here :(
         // Is there a way to deserialize in an asynchronous manner?
 
+        // Never decompress more than was declared, so a small packet can't inflate into a huge one
+        let limit = decompressed_length.map_or(packet_len, |length| length as u64 + 1);
+        let mut reader = reader.take(limit);
+
         let packet_id = VarInt::decode_async(&mut reader)
             .await
-            .map_err(|_| PacketDecodeError::DecodeID)?
-            .0;
+            .map_err(|_| PacketDecodeError::DecodeID)?;
 
         let mut payload = Vec::new();
         reader
@@ -149,6 +169,21 @@ impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
             .await
             .map_err(|err| PacketDecodeError::FailedDecompression(err.to_string()))?;
 
+        if let Some(length) = decompressed_length {
+            let actual = packet_id.written_size() + payload.len();
+            if actual > length {
+                Err(ReadingError::TooLarge(format!(
+                    "packet decompresses to more than the declared {length} bytes"
+                )))?
+            }
+            if actual < length {
+                Err(PacketDecodeError::FailedDecompression(format!(
+                    "packet decompresses to {actual} bytes, {length} were declared"
+                )))?
+            }
+        }
+        let packet_id = packet_id.0;
+
         Ok(RawPacket {
             id: packet_id,
             payload: payload.into(),
@@ -409,4 +444,45 @@ mod tests {
         assert_eq!(raw_packet.id, packet_id);
         assert_eq!(raw_packet.payload.as_ref(), payload);
     }
+
+    /// Test that packets declaring or inflating to more than the maximum are rejected
+    #[tokio::test]
+    async fn test_decode_rejects_oversized_decompressed_packet() {
+        // A tiny packet declaring a gigabyte once decompressed
+        let mut buffer = Vec::new();
+        buffer.write_var_int(&VarInt(1 << 30)).unwrap();
+        buffer.write_slice(&compress_zlib(&[0x01, 0x00])).unwrap();
+        let mut packet = Vec::new();
+        packet.write_var_int(&VarInt(buffer.len() as i32)).unwrap();
+        packet.extend_from_slice(&buffer);
+
+        let mut decoder = TCPNetworkDecoder::new(packet.as_slice());
+        decoder.set_compression(0);
+        // Rejected from the declared length alone, before decompressing anything
+        let err = decoder.get_raw_packet().await.unwrap_err();
+        assert!(err.to_string().contains("too large"), "{err}");
+
+        // Within the default maximum, but above the configured one
+        let payload = vec![0x41u8; 2048];
+        let packet = build_packet(9, &payload, true, None, None);
+        let mut decoder = TCPNetworkDecoder::new(packet.as_slice());
+        decoder.set_compression(0);
+        decoder.set_max_decompressed_size(1024);
+        let err = decoder.get_raw_packet().await.unwrap_err();
+        assert!(err.to_string().contains("too large"), "{err}");
+
+        // Declaring less than what the data inflates to
+        let data = compress_zlib(&[&[0x09][..], &payload].concat());
+        let mut buffer = Vec::new();
+        buffer.write_var_int(&VarInt(16)).unwrap();
+        buffer.write_slice(&data).unwrap();
+        let mut packet = Vec::new();
+        packet.write_var_int(&VarInt(buffer.len() as i32)).unwrap();
+        packet.extend_from_slice(&buffer);
+
+        let mut decoder = TCPNetworkDecoder::new(packet.as_slice());
+        decoder.set_compression(0);
+        let err = decoder.get_raw_packet().await.unwrap_err();
+        assert!(err.to_string().contains("too large"), "{err}");
+    }
 }
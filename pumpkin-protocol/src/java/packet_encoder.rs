@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use aes::cipher::KeyIvInit;
 use async_compression::{Level, tokio::write::ZlibEncoder};
 use bytes::Bytes;
@@ -83,6 +85,8 @@ pub struct TCPNetworkEncoder<W: AsyncWrite + Unpin> {
     writer: EncryptionWriter<W>,
     // compression and compression threshold
     compression: Option<(CompressionThreshold, CompressionLevel)>,
+    // packet IDs which are never compressed, whatever their size
+    compression_exempt: HashSet<i32>,
 }
 
 impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
@@ -90,6 +94,7 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
         Self {
             writer: EncryptionWriter::None(writer),
             compression: None,
+            compression_exempt: HashSet::new(),
         }
     }
 
@@ -97,6 +102,18 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
         self.compression = Some(compression_info);
     }
 
+    /// Sets the IDs of the packets which are always written uncompressed, even when they exceed the
+    /// compression threshold. Useful for packets which barely shrink, like already compressed data.
+    pub fn set_compression_exempt(&mut self, packet_ids: impl IntoIterator<Item = i32>) {
+        self.compression_exempt = packet_ids.into_iter().collect();
+    }
+
+    fn is_compression_exempt(&self, packet_data: &[u8]) -> bool {
+        !self.compression_exempt.is_empty()
+            && VarInt::decode(&mut &packet_data[..])
+                .is_ok_and(|packet_id| self.compression_exempt.contains(&packet_id.0))
+    }
+
     /// NOTE: Encryption can only be set; a minecraft stream cannot go back to being unencrypted
     pub fn set_encryption(&mut self, key: &[u8; 16]) {
         if matches!(self.writer, EncryptionWriter::Encrypt(_)) {
@@ -108,7 +125,8 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
 
     /// Appends a Clientbound `ClientPacket` to the internal buffer and applies compression when needed.
     ///
-    /// If compression is enabled and the packet size exceeds the threshold, the packet is compressed.
+    /// If compression is enabled and the packet size exceeds the threshold, the packet is compressed,
+    /// unless it is exempt from compression.
     /// The packet is prefixed with its length and, if compressed, the uncompressed data length.
     /// The packet format is as follows:
     ///
@@ -152,7 +170,7 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
         })?;
 
         if let Some((compression_threshold, compression_level)) = self.compression {
-            if data_len >= compression_threshold {
+            if data_len >= compression_threshold && !self.is_compression_exempt(&packet_data) {
                 // Pushed before data:
                 // Length of (Data Length) + length of compressed (Packet ID + Data)
                 // Length of uncompressed (Packet ID + Data)
@@ -274,7 +292,7 @@ mod tests {
     use std::io::Read;
 
     use super::*;
-    use crate::java::client::status::CStatusResponse;
+    use crate::java::client::status::{CPingResponse, CStatusResponse};
     use crate::packet::Packet;
     use crate::ser::{NetworkReadExt, NetworkWriteExt};
     use crate::{ClientPacket, ReadingError};
@@ -643,4 +661,54 @@ mod tests {
 
         assert_eq!(buffer, expected_payload);
     }
+
+    /// Test that exempt packets are written uncompressed even above the compression threshold
+    #[tokio::test]
+    async fn test_encode_compression_exempt_packet() {
+        async fn encode<T: ClientPacket>(packet: &T) -> Vec<u8> {
+            let mut buf = Vec::new();
+            let mut encoder = TCPNetworkEncoder::new(&mut buf);
+            // Compression threshold is set to 0 to force compression
+            encoder.set_compression((0, 6));
+            encoder.set_compression_exempt([CStatusResponse::PACKET_ID]);
+
+            let mut packet_buf = Vec::new();
+            packet_buf.write_var_int(&VarInt(T::PACKET_ID)).unwrap();
+            packet.write_packet_data(&mut packet_buf).unwrap();
+            encoder.write_packet(packet_buf.into()).await.unwrap();
+            buf
+        }
+
+        let exempt = CStatusResponse::new("{\"description\": \"A Minecraft Server\"}".to_string());
+        let packet_bytes = encode(&exempt).await;
+        let mut buffer = &packet_bytes[..];
+        decode_varint(&mut buffer).expect("Failed to decode packet length");
+        let data_length = decode_varint(&mut buffer).expect("Failed to decode data length");
+        assert_eq!(data_length, 0, "Exempt packets should not be compressed");
+        let decoded_packet_id = decode_varint(&mut buffer).expect("Failed to decode packet ID");
+        assert_eq!(decoded_packet_id, CStatusResponse::PACKET_ID);
+        let mut expected_payload = Vec::new();
+        exempt.write_packet_data(&mut expected_payload).unwrap();
+        assert_eq!(buffer, expected_payload);
+
+        let compressed = CPingResponse::new(42);
+        let packet_bytes = encode(&compressed).await;
+        let mut buffer = &packet_bytes[..];
+        decode_varint(&mut buffer).expect("Failed to decode packet length");
+        let data_length = decode_varint(&mut buffer).expect("Failed to decode data length");
+        let mut expected_payload = Vec::new();
+        compressed.write_packet_data(&mut expected_payload).unwrap();
+        assert_eq!(
+            data_length as usize,
+            VarInt(CPingResponse::PACKET_ID).written_size() + expected_payload.len(),
+            "Other packets should still be compressed"
+        );
+        let decompressed_data =
+            decompress_zlib(buffer, data_length as usize).expect("Failed to decompress data");
+        let mut decompressed_buffer = &decompressed_data[..];
+        let decoded_packet_id =
+            decode_varint(&mut decompressed_buffer).expect("Failed to decode packet ID");
+        assert_eq!(decoded_packet_id, CPingResponse::PACKET_ID);
+        assert_eq!(decompressed_buffer, expected_payload);
+    }
 }
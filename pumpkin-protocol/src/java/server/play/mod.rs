@@ -29,6 +29,7 @@ mod place_recipe;
 mod recipe_book_change_settings;
 mod recipe_book_seen_recipe;
 mod seen_advancements;
+mod select_trade;
 mod set_command_block;
 mod set_creative_slot;
 mod set_held_item;
@@ -68,6 +69,7 @@ pub use place_recipe::*;
 pub use recipe_book_change_settings::*;
 pub use recipe_book_seen_recipe::*;
 pub use seen_advancements::*;
+pub use select_trade::*;
 pub use set_command_block::*;
 pub use set_creative_slot::*;
 pub use set_held_item::*;
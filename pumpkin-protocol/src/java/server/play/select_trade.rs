@@ -0,0 +1,11 @@
+use pumpkin_data::packet::serverbound::PLAY_SELECT_TRADE;
+use pumpkin_macros::packet;
+use serde::{Deserialize, Serialize};
+
+use crate::VarInt;
+
+#[derive(Deserialize, Serialize)]
+#[packet(PLAY_SELECT_TRADE)]
+pub struct SSelectTrade {
+    pub selected_slot: VarInt,
+}
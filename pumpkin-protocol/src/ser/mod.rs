@@ -716,4 +716,81 @@ mod test {
                 .unwrap();
         assert_eq!(empty_map_original, empty_map_deserialized);
     }
+
+    #[test]
+    fn test_oversized_var_ints_are_rejected() {
+        use crate::codec::var_long::VarLong;
+        use crate::ser::{NetworkReadExt, ReadingError};
+
+        // Six bytes with continuation bits
+        let mut read = Cursor::new([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]);
+        assert!(matches!(read.get_var_int(), Err(ReadingError::TooLarge(_))));
+        // The fifth byte holds more than the 32 bits of an i32
+        let mut read = Cursor::new([0xFF, 0xFF, 0xFF, 0xFF, 0x1F]);
+        assert!(matches!(read.get_var_int(), Err(ReadingError::TooLarge(_))));
+        let mut read = Cursor::new([0x80, 0x80, 0x80, 0x80, 0x08]);
+        assert_eq!(read.get_var_int().unwrap(), VarInt(i32::MIN));
+        let mut read = Cursor::new([0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+        assert_eq!(read.get_var_int().unwrap(), VarInt(-1));
+
+        let mut bytes = [0xFF; 11];
+        bytes[10] = 0x01;
+        let mut read = Cursor::new(bytes);
+        assert!(matches!(
+            read.get_var_long(),
+            Err(ReadingError::TooLarge(_))
+        ));
+        let mut bytes = [0xFF; 10];
+        bytes[9] = 0x03;
+        let mut read = Cursor::new(bytes);
+        assert!(matches!(
+            read.get_var_long(),
+            Err(ReadingError::TooLarge(_))
+        ));
+        bytes[9] = 0x01;
+        let mut read = Cursor::new(bytes);
+        assert_eq!(read.get_var_long().unwrap(), VarLong(-1));
+    }
+
+    #[test]
+    fn test_oversized_bedrock_var_ints_are_rejected() {
+        use crate::serial::{PacketRead, PacketWrite};
+
+        // Six bytes with continuation bits
+        let mut read = Cursor::new([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]);
+        let err = VarInt::read(&mut read).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        // The fifth byte holds more than 32 bits
+        let mut read = Cursor::new([0xFF, 0xFF, 0xFF, 0xFF, 0x1F]);
+        assert!(VarInt::read(&mut read).is_err());
+
+        for value in [0, 1, -1, 300, -300, i32::MAX, i32::MIN] {
+            let mut bytes = Vec::new();
+            VarInt(value).write(&mut bytes).unwrap();
+            assert!(bytes.len() <= 5);
+            let read = VarInt::read(&mut Cursor::new(bytes)).unwrap();
+            assert_eq!(read, VarInt(value));
+        }
+    }
+
+    #[test]
+    fn test_oversized_var_ints_are_rejected_when_deserializing() {
+        use crate::codec::var_long::VarLong;
+
+        // The fifth byte holds more than the 32 bits of an i32
+        let cursor = Cursor::new([0xFF, 0xFF, 0xFF, 0xFF, 0x1F]);
+        assert!(VarInt::deserialize(&mut deserializer::Deserializer::new(cursor)).is_err());
+        let cursor = Cursor::new([0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+        let deserialized = VarInt::deserialize(&mut deserializer::Deserializer::new(cursor));
+        assert_eq!(deserialized.unwrap(), VarInt(-1));
+
+        let mut bytes = [0xFF; 10];
+        bytes[9] = 0x03;
+        let cursor = Cursor::new(bytes);
+        assert!(VarLong::deserialize(&mut deserializer::Deserializer::new(cursor)).is_err());
+        bytes[9] = 0x01;
+        let cursor = Cursor::new(bytes);
+        let deserialized = VarLong::deserialize(&mut deserializer::Deserializer::new(cursor));
+        assert_eq!(deserialized.unwrap(), VarLong(-1));
+    }
 }
@@ -9,6 +9,8 @@ pub struct LootTable {
     pub r#type: LootTableType,
     pub random_sequence: Option<&'static str>,
     pub pools: Option<&'static [LootPool]>,
+    /// Applied to the drops of all pools.
+    pub functions: Option<&'static [LootFunction]>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -44,9 +46,15 @@ pub enum LootPoolEntryTypes {
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum LootCondition {
-    Inverted,
-    AnyOf,
-    AllOf,
+    Inverted {
+        term: &'static LootCondition,
+    },
+    AnyOf {
+        terms: &'static [LootCondition],
+    },
+    AllOf {
+        terms: &'static [LootCondition],
+    },
     RandomChance,
     RandomChanceWithEnchantedBonus,
     EntityProperties,
@@ -56,8 +64,17 @@ pub enum LootCondition {
         block: &'static str,
         properties: &'static [(&'static str, &'static str)],
     },
-    MatchTool,
-    TableBonus,
+    /// The tool has to be `items` (an item or a `#` tag) and carry `enchantments` with at least
+    /// the given levels.
+    MatchTool {
+        items: Option<&'static str>,
+        enchantments: &'static [(&'static str, i32)],
+    },
+    /// Passes with the chance at the level of `enchantment` on the tool.
+    TableBonus {
+        enchantment: &'static str,
+        chances: &'static [f32],
+    },
     SurvivesExplosion,
     DamageSourceProperties,
     LocationCheck,
@@ -122,6 +139,8 @@ pub struct LootPoolEntry {
     pub content: LootPoolEntryTypes,
     pub conditions: Option<&'static [LootCondition]>,
     pub functions: Option<&'static [LootFunction]>,
+    /// How likely this entry is chosen compared to the other entries of the pool.
+    pub weight: i32,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
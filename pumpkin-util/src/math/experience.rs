@@ -28,31 +28,52 @@ pub fn points_in_level(level: i32) -> i32 {
     }
 }
 
-/// Calculate the total number of points to reach a level.
+/// Calculate the total number of points to reach a level, saturating at `i32::MAX`.
 pub fn points_to_level(level: i32) -> i32 {
-    match level {
-        0..=16 => level * level + 6 * level,
-        17..=31 => (2.5 * f64::from(level * level) - (40.5 * f64::from(level)) + 360.0) as i32,
-        _ => ((4.5 * f64::from(level * level)) - (162.5 * f64::from(level)) + 2220.0) as i32,
-    }
+    let level = i64::from(level);
+    // The halves always add up to whole numbers
+    let points = match level {
+        ..=16 => level * level + 6 * level,
+        17..=31 => (5 * level * level - 81 * level) / 2 + 360,
+        _ => (9 * level * level - 325 * level) / 2 + 2220,
+    };
+    i32::try_from(points).unwrap_or(i32::MAX)
 }
 
-/// Calculate level and points from total points.
+/// Calculate level and points from total points. Negative totals are treated as no points.
 pub fn total_to_level_and_points(total_points: i32) -> (i32, i32) {
-    let level = match total_points {
-        0..=352 => ((total_points as f64 + 9.0).sqrt() - 3.0) as i32,
-        353..=1507 => (8.1 + (0.4 * (total_points as f64 - (7839.0 / 40.0))).sqrt()) as i32,
-        _ => {
-            ((325.0 / 18.0) + (2.0 / 9.0 * (total_points as f64 - (54215.0 / 72.0))).sqrt()) as i32
-        }
+    let total_points = total_points.max(0);
+    let total = f64::from(total_points);
+    let estimate = match total_points {
+        0..=352 => (total + 9.0).sqrt() - 3.0,
+        353..=1507 => 8.1 + (0.4 * (total - (7839.0 / 40.0))).sqrt(),
+        _ => (325.0 / 18.0) + (2.0 / 9.0 * (total - (54215.0 / 72.0))).sqrt(),
     };
 
+    // The estimate may be off by one due to rounding right at the start of a level
+    let mut level = estimate as i32;
+    while level > 0 && points_to_level(level) > total_points {
+        level -= 1;
+    }
+    while points_to_level(level + 1) <= total_points {
+        level += 1;
+    }
+
     let level_start = points_to_level(level);
     let points_into_level = total_points - level_start;
 
     (level, points_into_level)
 }
 
+/// Calculate level and points after adding points to a level and the points within it.
+pub fn add_points(level: i32, points: i32, added_points: i32) -> (i32, i32) {
+    total_to_level_and_points(
+        points_to_level(level)
+            .saturating_add(points)
+            .saturating_add(added_points),
+    )
+}
+
 /// Calculate progress (0.0 to 1.0) from points within a level.
 pub fn progress_in_level(points: i32, level: i32) -> f32 {
     let max_points = points_in_level(level);
@@ -60,3 +81,43 @@ pub fn progress_in_level(points: i32, level: i32) -> f32 {
     let progress = (points as f32) / (max_points as f32);
     progress.clamp(0.0, 1.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_curve() {
+        assert_eq!(points_in_level(0), 7);
+        assert_eq!(points_in_level(15), 37);
+        assert_eq!(points_in_level(16), 42);
+        assert_eq!(points_in_level(31), 121);
+
+        assert_eq!(points_to_level(16), 352);
+        assert_eq!(points_to_level(17), 394);
+        assert_eq!(points_to_level(31), 1507);
+        assert_eq!(points_to_level(32), 1628);
+        for level in 0..1000 {
+            assert_eq!(
+                points_to_level(level + 1) - points_to_level(level),
+                points_in_level(level)
+            );
+        }
+    }
+
+    #[test]
+    fn test_total_to_level_and_points() {
+        for level in 0..1000 {
+            let start = points_to_level(level);
+            assert_eq!(total_to_level_and_points(start), (level, 0));
+            assert_eq!(
+                total_to_level_and_points(start + points_in_level(level) - 1),
+                (level, points_in_level(level) - 1)
+            );
+        }
+        assert_eq!(total_to_level_and_points(-5), (0, 0));
+        assert_eq!(add_points(1, 2, 7), (2, 0));
+        assert_eq!(add_points(2, 0, -10), (0, 6));
+        assert_eq!(add_points(0, 3, -10), (0, 0));
+    }
+}
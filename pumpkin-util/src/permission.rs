@@ -1,5 +1,5 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -49,12 +49,15 @@ impl Permission {
 pub struct PermissionRegistry {
     /// All registered permissions
     permissions: HashMap<String, Permission>,
+    /// Defaults replacing the registered ones, e.g. from the config
+    default_overrides: HashMap<String, PermissionDefault>,
 }
 
 impl PermissionRegistry {
     pub fn new() -> Self {
         Self {
             permissions: HashMap::new(),
+            default_overrides: HashMap::new(),
         }
     }
 
@@ -79,6 +82,21 @@ impl PermissionRegistry {
     pub fn has_permission(&self, node: &str) -> bool {
         self.permissions.contains_key(node)
     }
+
+    /// Replace the defaults of registered permissions, removing the previous overrides
+    pub fn set_default_overrides(&mut self, overrides: HashMap<String, PermissionDefault>) {
+        self.default_overrides = overrides;
+    }
+
+    /// The default value of a registered permission, considering the overrides
+    pub fn get_default(&self, node: &str) -> Option<&PermissionDefault> {
+        let permission = self.permissions.get(node)?;
+        Some(
+            self.default_overrides
+                .get(node)
+                .unwrap_or(&permission.default),
+        )
+    }
 }
 
 /// Storage for player permissions
@@ -116,6 +134,69 @@ impl PermissionAttachment {
     }
 }
 
+/// A named set of permissions which players can be assigned to
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PermissionGroup {
+    /// Permissions granted or denied by this group
+    pub permissions: HashMap<String, bool>,
+    /// Groups this group inherits permissions from, its own permissions take precedence
+    pub inherits: Vec<String>,
+}
+
+/// Looks up `permission_node` in `permissions`, considering wildcards and the children of the
+/// registered permissions
+fn resolve_permission(
+    permissions: &HashMap<String, bool>,
+    permission_node: &str,
+    reg: &PermissionRegistry,
+) -> Option<bool> {
+    // Check for exact permission match
+    if let Some(value) = permissions.get(permission_node) {
+        return Some(*value);
+    }
+
+    // Check parent nodes (for wildcard permissions)
+    let node_parts: Vec<&str> = permission_node.split(':').collect();
+    if node_parts.len() == 2 {
+        let namespace = node_parts[0];
+        let key_parts: Vec<&str> = node_parts[1].split('.').collect();
+
+        // Check wildcard permissions at each level
+        let mut current_node = namespace.to_string();
+        if let Some(value) = permissions.get(&format!("{current_node}:*")) {
+            return Some(*value);
+        }
+
+        current_node.push(':');
+        for (i, part) in key_parts.iter().enumerate() {
+            current_node.push_str(part);
+
+            if let Some(value) = permissions.get(&current_node) {
+                return Some(*value);
+            }
+
+            if i < key_parts.len() - 1 {
+                if let Some(value) = permissions.get(&format!("{current_node}.*")) {
+                    return Some(*value);
+                }
+                current_node.push('.');
+            }
+        }
+    }
+
+    // Check for inherited permissions from parent nodes
+    for (node, value) in permissions {
+        if let Some(permission) = reg.get_permission(node)
+            && let Some(child) = permission.children.get(permission_node)
+        {
+            return Some(*value && *child);
+        }
+    }
+
+    None
+}
+
 /// Manager for player permissions
 #[derive(Default)]
 pub struct PermissionManager {
@@ -123,6 +204,10 @@ pub struct PermissionManager {
     pub registry: Arc<RwLock<PermissionRegistry>>,
     /// Player permission attachments
     pub attachments: HashMap<uuid::Uuid, Arc<RwLock<PermissionAttachment>>>,
+    /// Permission groups by name
+    pub groups: HashMap<String, PermissionGroup>,
+    /// The groups each player is assigned to
+    pub player_groups: HashMap<uuid::Uuid, Vec<String>>,
 }
 
 impl PermissionManager {
@@ -130,6 +215,8 @@ impl PermissionManager {
         Self {
             registry,
             attachments: HashMap::new(),
+            groups: HashMap::new(),
+            player_groups: HashMap::new(),
         }
     }
 
@@ -146,6 +233,70 @@ impl PermissionManager {
         self.attachments.remove(player_id);
     }
 
+    /// Replace the known permission groups
+    pub fn set_groups(&mut self, groups: HashMap<String, PermissionGroup>) {
+        self.groups = groups;
+    }
+
+    /// Assign a player to a group, fails if the group doesn't exist
+    pub fn assign_group(&mut self, player_id: uuid::Uuid, group: &str) -> Result<(), String> {
+        if !self.groups.contains_key(group) {
+            return Err(format!("Permission group {group} doesn't exist"));
+        }
+        let groups = self.player_groups.entry(player_id).or_default();
+        if !groups.iter().any(|assigned| assigned == group) {
+            groups.push(group.to_string());
+        }
+        Ok(())
+    }
+
+    /// Remove a player from a group
+    pub fn unassign_group(&mut self, player_id: &uuid::Uuid, group: &str) {
+        if let Some(groups) = self.player_groups.get_mut(player_id) {
+            groups.retain(|assigned| assigned != group);
+            if groups.is_empty() {
+                self.player_groups.remove(player_id);
+            }
+        }
+    }
+
+    /// Get the groups a player is directly assigned to
+    pub fn get_groups(&self, player_id: &uuid::Uuid) -> &[String] {
+        self.player_groups
+            .get(player_id)
+            .map_or(&[], |groups| groups.as_slice())
+    }
+
+    /// Check if the groups of a player set a permission, walking up the inherited groups
+    fn group_permission(
+        &self,
+        player_id: &uuid::Uuid,
+        permission_node: &str,
+        reg: &PermissionRegistry,
+    ) -> Option<bool> {
+        // Breadth first, so closer groups take precedence over the ones they inherit from
+        let mut queue: VecDeque<&str> = self
+            .get_groups(player_id)
+            .iter()
+            .map(String::as_str)
+            .collect();
+        let mut visited = HashSet::new();
+        while let Some(name) = queue.pop_front() {
+            // Inheritance cycles would loop forever otherwise
+            if !visited.insert(name) {
+                continue;
+            }
+            let Some(group) = self.groups.get(name) else {
+                continue;
+            };
+            if let Some(value) = resolve_permission(&group.permissions, permission_node, reg) {
+                return Some(value);
+            }
+            queue.extend(group.inherits.iter().map(String::as_str));
+        }
+        None
+    }
+
     /// Check if a player has a permission, considering defaults and op status
     pub async fn has_permission(
         &self,
@@ -158,56 +309,21 @@ impl PermissionManager {
         // Check explicitly set permissions
         if let Some(attachment) = self.attachments.get(player_id) {
             let attachment = attachment.read().await;
-
-            // Check for exact permission match
-            if let Some(value) = attachment.has_permission_set(permission_node) {
+            if let Some(value) =
+                resolve_permission(attachment.get_permissions(), permission_node, &reg)
+            {
                 return value;
             }
+        }
 
-            // Check parent nodes (for wildcard permissions)
-            let node_parts: Vec<&str> = permission_node.split(':').collect();
-            if node_parts.len() == 2 {
-                let namespace = node_parts[0];
-                let key_parts: Vec<&str> = node_parts[1].split('.').collect();
-
-                // Check wildcard permissions at each level
-                let mut current_node = namespace.to_string();
-                if let Some(value) = attachment.has_permission_set(&format!("{current_node}:*")) {
-                    return value;
-                }
-
-                current_node.push(':');
-                for (i, part) in key_parts.iter().enumerate() {
-                    current_node.push_str(part);
-
-                    if let Some(value) = attachment.has_permission_set(&current_node) {
-                        return value;
-                    }
-
-                    if i < key_parts.len() - 1 {
-                        if let Some(value) =
-                            attachment.has_permission_set(&format!("{current_node}.*"))
-                        {
-                            return value;
-                        }
-                        current_node.push('.');
-                    }
-                }
-            }
-
-            // Check for inherited permissions from parent nodes
-            for (node, value) in attachment.get_permissions() {
-                if let Some(permission) = reg.get_permission(node)
-                    && permission.children.contains_key(permission_node)
-                {
-                    return *value && *permission.children.get(permission_node).unwrap();
-                }
-            }
+        // Check the permissions of the player's groups
+        if let Some(value) = self.group_permission(player_id, permission_node, &reg) {
+            return value;
         }
 
         // Fall back to default permission value
-        if let Some(permission) = reg.get_permission(permission_node) {
-            match permission.default {
+        if let Some(default) = reg.get_default(permission_node) {
+            match *default {
                 PermissionDefault::Allow => true,
                 PermissionDefault::Deny => false,
                 PermissionDefault::Op(required_level) => player_op_level >= required_level,
@@ -273,3 +389,107 @@ impl<'de> Deserialize<'de> for PermissionLvl {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{
+        Permission, PermissionDefault, PermissionGroup, PermissionLvl, PermissionManager,
+        PermissionRegistry,
+    };
+
+    fn group(permissions: &[(&str, bool)], inherits: &[&str]) -> PermissionGroup {
+        PermissionGroup {
+            permissions: permissions
+                .iter()
+                .map(|(node, value)| ((*node).to_string(), *value))
+                .collect(),
+            inherits: inherits.iter().map(|name| (*name).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn groups_inherit_permissions() {
+        let reg = PermissionRegistry::new();
+        let mut manager = PermissionManager::default();
+        manager.set_groups(HashMap::from([
+            (
+                "default".to_string(),
+                group(&[("minecraft:command.help", true)], &[]),
+            ),
+            (
+                "moderator".to_string(),
+                group(
+                    &[
+                        ("minecraft:command.kick", true),
+                        ("minecraft:command.help", false),
+                    ],
+                    &["default", "admin"],
+                ),
+            ),
+            (
+                "admin".to_string(),
+                group(&[("minecraft:command.*", true)], &["moderator"]),
+            ),
+        ]));
+        let player = uuid::Uuid::new_v4();
+        assert!(manager.assign_group(player, "builder").is_err());
+        assert_eq!(
+            manager.group_permission(&player, "minecraft:command.kick", &reg),
+            None
+        );
+
+        manager.assign_group(player, "moderator").unwrap();
+        manager.assign_group(player, "moderator").unwrap();
+        assert_eq!(manager.get_groups(&player), ["moderator"]);
+        assert_eq!(
+            manager.group_permission(&player, "minecraft:command.kick", &reg),
+            Some(true)
+        );
+        // The group's own permissions override the inherited ones
+        assert_eq!(
+            manager.group_permission(&player, "minecraft:command.help", &reg),
+            Some(false)
+        );
+        // Inherited from admin, even though admin inherits back from moderator
+        assert_eq!(
+            manager.group_permission(&player, "minecraft:command.stop", &reg),
+            Some(true)
+        );
+        assert_eq!(
+            manager.group_permission(&player, "pumpkin:command.tps", &reg),
+            None
+        );
+
+        manager.unassign_group(&player, "moderator");
+        assert!(manager.get_groups(&player).is_empty());
+    }
+
+    #[test]
+    fn overridden_defaults_replace_registered_ones() {
+        let mut reg = PermissionRegistry::new();
+        reg.register_permission(Permission::new(
+            "minecraft:command.fill",
+            "Fills a region",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
+        let lvl_three = PermissionDefault::Op(PermissionLvl::Three);
+        reg.set_default_overrides(HashMap::from([
+            ("minecraft:command.fill".to_string(), lvl_three.clone()),
+            (
+                "minecraft:command.unknown".to_string(),
+                PermissionDefault::Allow,
+            ),
+        ]));
+        assert_eq!(reg.get_default("minecraft:command.fill"), Some(&lvl_three));
+        assert_eq!(reg.get_default("minecraft:command.unknown"), None);
+
+        reg.set_default_overrides(HashMap::new());
+        assert_eq!(
+            reg.get_default("minecraft:command.fill"),
+            Some(&PermissionDefault::Op(PermissionLvl::Two))
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+use pumpkin_world::chunk::{ChunkSections, SubChunk};
+use pumpkin_world::level::split_box_by_chunk;
+use tokio::sync::RwLock;
+
+/// Chunks that count how often they were locked.
+struct Chunks {
+    chunks: HashMap<Vector2<i32>, RwLock<ChunkSections>>,
+    locks: AtomicUsize,
+}
+
+impl Chunks {
+    fn new() -> Self {
+        let mut chunks = HashMap::new();
+        for x in -1..=1 {
+            for z in -1..=1 {
+                let sections = vec![SubChunk::default(); 24].into_boxed_slice();
+                chunks.insert(
+                    Vector2::new(x, z),
+                    RwLock::new(ChunkSections::new(sections, -64)),
+                );
+            }
+        }
+        Self {
+            chunks,
+            locks: AtomicUsize::new(0),
+        }
+    }
+
+    /// What `World::get_block_state` does for every position.
+    fn per_block(&self, min: BlockPos, max: BlockPos) -> usize {
+        let mut read = 0;
+        for position in BlockPos::iterate(min, max) {
+            let chunk = self.chunks[&position.chunk_position()].blocking_read();
+            self.locks.fetch_add(1, Ordering::Relaxed);
+            read += usize::from(read_block(&chunk, position));
+        }
+        read
+    }
+
+    /// What `Level::get_block_states_in_box` does.
+    fn per_chunk(&self, min: BlockPos, max: BlockPos) -> usize {
+        let mut read = 0;
+        for (chunk, chunk_min, chunk_max) in split_box_by_chunk(min, max) {
+            let chunk = self.chunks[&chunk].blocking_read();
+            self.locks.fetch_add(1, Ordering::Relaxed);
+            for position in BlockPos::iterate(chunk_min, chunk_max) {
+                read += usize::from(read_block(&chunk, position));
+            }
+        }
+        read
+    }
+
+    fn take_locks(&self) -> usize {
+        self.locks.swap(0, Ordering::Relaxed)
+    }
+}
+
+fn read_block(sections: &ChunkSections, position: BlockPos) -> u16 {
+    let relative = position.chunk_relative_position();
+    sections
+        .get_block_absolute_y(relative.x as usize, relative.y, relative.z as usize)
+        .unwrap_or_default()
+}
+
+fn bench_block_box(c: &mut Criterion) {
+    let chunks = Chunks::new();
+    // A 10x10x10 box across a chunk corner
+    let (min, max) = (BlockPos::new(-5, 60, -5), BlockPos::new(4, 69, 4));
+
+    chunks.per_block(min, max);
+    let per_block_locks = chunks.take_locks();
+    chunks.per_chunk(min, max);
+    let per_chunk_locks = chunks.take_locks();
+    println!(
+        "10x10x10 box: {per_block_locks} lock acquisitions per block, {per_chunk_locks} per chunk"
+    );
+
+    c.bench_function("10x10x10 box per block", |b| {
+        b.iter(|| chunks.per_block(black_box(min), black_box(max)));
+    });
+    c.bench_function("10x10x10 box per chunk", |b| {
+        b.iter(|| chunks.per_chunk(black_box(min), black_box(max)));
+    });
+}
+
+criterion_group!(benches, bench_block_box);
+criterion_main!(benches);
@@ -9,7 +9,7 @@ use std::{
 };
 
 use pumpkin_data::{
-    Block, HorizontalFacingExt,
+    HorizontalFacingExt,
     block_properties::{BlockProperties, ChestLikeProperties, ChestType},
     sound::{Sound, SoundCategory},
 };
@@ -105,7 +105,7 @@ impl ViewerCountListener for ChestBlockEntity {
         _position: &'a BlockPos,
     ) -> ViewerFuture<'a, ()> {
         Box::pin(async move {
-            self.play_sound(world, Sound::BlockEnderChestOpen).await;
+            self.play_sound(world, Sound::BlockChestOpen).await;
         })
     }
 
@@ -115,7 +115,7 @@ impl ViewerCountListener for ChestBlockEntity {
         _position: &'a BlockPos,
     ) -> ViewerFuture<'a, ()> {
         Box::pin(async move {
-            self.play_sound(world, Sound::BlockEnderChestClose).await;
+            self.play_sound(world, Sound::BlockChestClose).await;
         })
     }
 
@@ -150,9 +150,12 @@ impl ChestBlockEntity {
     async fn play_sound(&self, world: &Arc<dyn SimpleWorld>, sound: Sound) {
         let mut rng = Xoroshiro::from_seed(get_seed());
 
+        // Trapped chests share the chest block entity
+        let block = world.get_block(&self.position).await;
         let state = world.get_block_state(&self.position).await;
-        let properties = ChestLikeProperties::from_state_id(state.id, &Block::CHEST);
+        let properties = ChestLikeProperties::from_state_id(state.id, block);
         let position = match properties.r#type {
+            // Double chests play one sound, from between both halves
             ChestType::Left => return,
             ChestType::Single => Vector3::new(
                 self.position.0.x as f64 + 0.5,
@@ -160,7 +163,11 @@ impl ChestBlockEntity {
                 self.position.0.z as f64 + 0.5,
             ),
             ChestType::Right => {
-                let direction = properties.facing.to_block_direction().to_offset();
+                let direction = properties
+                    .facing
+                    .rotate_counter_clockwise()
+                    .to_block_direction()
+                    .to_offset();
                 Vector3::new(
                     self.position.0.x as f64 + 0.5 + direction.x as f64 * 0.5,
                     self.position.0.y as f64 + 0.5,
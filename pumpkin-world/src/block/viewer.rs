@@ -35,7 +35,12 @@ impl ViewerCountTracker {
     }
 
     pub fn close_container(&self) {
-        self.current.fetch_sub(1, Ordering::Relaxed);
+        // Stays at zero, so a stray close can't wrap around and leave the lid open
+        let _ = self
+            .current
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                current.checked_sub(1)
+            });
     }
 
     pub async fn update_viewer_count<T>(
@@ -101,3 +106,22 @@ pub trait ViewerCountListener: Send + Sync {
         Box::pin(async {})
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::Ordering;
+
+    use super::ViewerCountTracker;
+
+    #[test]
+    fn closing_more_often_than_opening_keeps_no_viewers() {
+        let tracker = ViewerCountTracker::new();
+        tracker.open_container();
+        tracker.open_container();
+        tracker.close_container();
+        assert_eq!(tracker.current.load(Ordering::Relaxed), 1);
+        tracker.close_container();
+        tracker.close_container();
+        assert_eq!(tracker.current.load(Ordering::Relaxed), 0);
+    }
+}
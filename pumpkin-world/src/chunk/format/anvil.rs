@@ -5,7 +5,7 @@ use lz4_java_wrc::Context;
 use pumpkin_config::chunk::AnvilChunkConfig;
 use pumpkin_util::math::vector2::Vector2;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     io::{Read, SeekFrom, Write},
     marker::PhantomData,
     path::{Path, PathBuf},
@@ -109,6 +109,9 @@ struct AnvilChunkMetadata {
 
 pub struct AnvilChunkFile<S: SingleChunkDataSerializer> {
     chunks_data: [Option<AnvilChunkMetadata>; CHUNK_COUNT],
+    /// The indices of the chunks whose data in the file is corrupt, with the reason. They are
+    /// reported as errors when they are fetched instead of failing the whole region.
+    corrupt_chunks: HashMap<usize, String>,
     end_sector: u32,
     write_action: Mutex<WriteAction>,
 
@@ -252,9 +255,13 @@ impl AnvilChunkData {
     fn from_bytes(bytes: Bytes) -> Result<Self, ChunkReadingError> {
         let mut bytes = bytes;
         // Minus one for the compression byte
-        let length = bytes.get_u32() as usize - 1;
+        let Some(length) = (bytes.get_u32() as usize).checked_sub(1) else {
+            return Err(ChunkReadingError::ParsingError(
+                ChunkParsingError::ErrorDeserializingChunk("Chunk length is zero".to_string()),
+            ));
+        };
 
-        if length > bytes.len() {
+        if length >= bytes.len() {
             return Err(ChunkReadingError::ParsingError(
                 ChunkParsingError::ErrorDeserializingChunk(format!(
                     "Chunk length is greater than available bytes ({} vs {})",
@@ -505,6 +512,7 @@ impl<S: SingleChunkDataSerializer> Default for AnvilChunkFile<S> {
     fn default() -> Self {
         Self {
             chunks_data: [const { None }; CHUNK_COUNT],
+            corrupt_chunks: HashMap::new(),
             write_action: Mutex::new(WriteAction::Pass),
             // Two sectors for offset + timestamp
             end_sector: 2,
@@ -593,26 +601,34 @@ impl<S: SingleChunkDataSerializer> ChunkSerializer for AnvilChunkFile<S> {
             let bytes_offset = (sector_offset - 2) * SECTOR_BYTES;
             let bytes_count = sector_count * SECTOR_BYTES;
 
+            // A corrupt chunk shouldn't make the other chunks of the region unreadable
             if bytes_offset + bytes_count > raw_file_bytes.len() {
-                return Err(ChunkReadingError::ParsingError(
-                    ChunkParsingError::ErrorDeserializingChunk(format!(
+                chunk_file.corrupt_chunks.insert(
+                    i,
+                    format!(
                         "Not enough bytes available for the chunk {} ({} vs {})",
                         i,
                         bytes_count,
                         raw_file_bytes.len().saturating_sub(bytes_offset)
-                    )),
-                ));
+                    ),
+                );
+                continue;
             }
 
-            let serialized_data = AnvilChunkData::from_bytes(
+            match AnvilChunkData::from_bytes(
                 raw_file_bytes.slice(bytes_offset..bytes_offset + bytes_count),
-            )?;
-
-            chunk_file.chunks_data[i] = Some(AnvilChunkMetadata {
-                serialized_data,
-                timestamp,
-                file_sector_offset: sector_offset as u32,
-            });
+            ) {
+                Ok(serialized_data) => {
+                    chunk_file.chunks_data[i] = Some(AnvilChunkMetadata {
+                        serialized_data,
+                        timestamp,
+                        file_sector_offset: sector_offset as u32,
+                    });
+                }
+                Err(err) => {
+                    chunk_file.corrupt_chunks.insert(i, err.to_string());
+                }
+            }
         }
 
         chunk_file.end_sector = last_offset as u32;
@@ -636,6 +652,8 @@ impl<S: SingleChunkDataSerializer> ChunkSerializer for AnvilChunkFile<S> {
             .and_then(|chunk_data| chunk_data.serialized_data.compression);
         let new_chunk_data =
             AnvilChunkData::from_chunk(chunk, compression_type, chunk_config).await?;
+        // The new data replaces the corrupt one
+        self.corrupt_chunks.remove(&index);
 
         let mut write_action = self.write_action.lock().await;
         if !chunk_config.write_in_place {
@@ -805,7 +823,18 @@ impl<S: SingleChunkDataSerializer> ChunkSerializer for AnvilChunkFile<S> {
         for chunk in chunks.into_iter() {
             let index = AnvilChunkFile::<S>::get_chunk_index(chunk.x, chunk.y);
             let is_ok = match &self.chunks_data[index] {
-                None => stream.send(LoadedData::Missing(chunk)).await.is_ok(),
+                None => {
+                    let result = match self.corrupt_chunks.get(&index) {
+                        Some(reason) => LoadedData::Error((
+                            chunk,
+                            ChunkReadingError::ParsingError(
+                                ChunkParsingError::ErrorDeserializingChunk(reason.clone()),
+                            ),
+                        )),
+                        None => LoadedData::Missing(chunk),
+                    };
+                    stream.send(result).await.is_ok()
+                }
                 Some(chunk_metadata) => {
                     let chunk_data = &chunk_metadata.serialized_data;
                     let result = match chunk_data.to_chunk(chunk) {
@@ -1334,3 +1363,64 @@ mod tests {
     */
 }
  */
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use bytes::{BufMut, Bytes, BytesMut};
+    use pumpkin_config::chunk::AnvilChunkConfig;
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::{AnvilChunkFile, SECTOR_BYTES};
+    use crate::chunk::ChunkEntityData;
+    use crate::chunk::io::{ChunkSerializer, LoadedData};
+
+    async fn fetch(
+        file: &AnvilChunkFile<ChunkEntityData>,
+        chunk: Vector2<i32>,
+    ) -> LoadedData<ChunkEntityData, crate::chunk::ChunkReadingError> {
+        let (send, mut recv) = tokio::sync::mpsc::channel(1);
+        file.get_chunks(vec![chunk], send).await;
+        recv.recv().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn corrupt_chunks_do_not_fail_the_region() {
+        let mut region = BytesMut::new();
+        // Chunk 0 is a sector of zeros, chunk 1 points past the end of the truncated file
+        region.put_u32((2 << 8) | 1);
+        region.put_u32((3 << 8) | 1);
+        region.resize(SECTOR_BYTES * 3, 0);
+        region.put_bytes(0, 100);
+
+        let mut file = AnvilChunkFile::<ChunkEntityData>::read(Bytes::from(region)).unwrap();
+        assert!(matches!(
+            fetch(&file, Vector2::new(0, 0)).await,
+            LoadedData::Error(_)
+        ));
+        assert!(matches!(
+            fetch(&file, Vector2::new(1, 0)).await,
+            LoadedData::Error(_)
+        ));
+        assert!(matches!(
+            fetch(&file, Vector2::new(2, 0)).await,
+            LoadedData::Missing(_)
+        ));
+
+        // Saving the chunk again recovers it
+        let chunk = ChunkEntityData {
+            x: 0,
+            z: 0,
+            data: HashMap::new(),
+            dirty: true,
+        };
+        file.update_chunk(&chunk, &AnvilChunkConfig::default())
+            .await
+            .unwrap();
+        assert!(matches!(
+            fetch(&file, Vector2::new(0, 0)).await,
+            LoadedData::Loaded(_)
+        ));
+    }
+}
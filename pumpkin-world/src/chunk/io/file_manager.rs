@@ -154,6 +154,145 @@ impl<S: ChunkSerializer<WriteBackend = PathBuf>> ChunkFileManager<S> {
     }
 }
 
+impl<P, S> ChunkFileManager<S>
+where
+    P: PathFromLevelFolder + Send + Sync + Sized + Dirtiable + 'static,
+    S: ChunkSerializer<Data = P, WriteBackend = PathBuf>,
+    S::ChunkConfig: Send + Sync,
+{
+    /// Updates the dirty chunks in their files, files are only written when no chunk in them
+    /// is watched, unless `force_write` is set.
+    fn update_and_write_chunks<'a>(
+        &'a self,
+        folder: &'a LevelFolder,
+        chunks_data: Vec<(Vector2<i32>, Arc<RwLock<P>>)>,
+        force_write: bool,
+    ) -> BoxFuture<'a, Result<usize, ChunkWritingError>> {
+        Box::pin(async move {
+            let mut regions_chunks: BTreeMap<String, Vec<Arc<RwLock<P>>>> = BTreeMap::new();
+
+            for (at, chunk) in chunks_data {
+                let key = S::get_chunk_key(&at);
+
+                match regions_chunks.entry(key) {
+                    std::collections::btree_map::Entry::Occupied(mut occupied) => {
+                        occupied.get_mut().push(chunk);
+                    }
+                    std::collections::btree_map::Entry::Vacant(vacant) => {
+                        vacant.insert(vec![chunk]);
+                    }
+                }
+            }
+
+            // we use a Sync Closure with an Async Block to execute the tasks in parallel
+            // with out waiting the future. Also it improve we File Cache utilizations.
+            let tasks = regions_chunks
+                .into_iter()
+                // The closure now needs `move` to capture `self` and `folder` by value
+                .map(move |(file_name, chunk_locks)| async move {
+                    let path = P::file_path(folder, &file_name);
+                    log::trace!("Updating data for file {}", path.display());
+
+                    let chunk_serializer = match self.get_serializer(&path).await {
+                        Ok(file) => Ok(file),
+                        Err(ChunkReadingError::ChunkNotExist) => {
+                            unreachable!("Must be managed by the cache")
+                        }
+                        Err(ChunkReadingError::IoError(err)) => {
+                            error!("Error reading the data before write: {err}");
+                            Err(ChunkWritingError::IoError(err))
+                        }
+                        Err(_) => Err(ChunkWritingError::IoError(std::io::ErrorKind::Other)),
+                    }?;
+
+                    // Create a task for each chunk_lock
+                    let update_tasks = chunk_locks.into_iter().map(|chunk_lock| {
+                        let chunk_serializer = chunk_serializer.clone();
+                        async move {
+                            let mut chunk = chunk_lock.write().await;
+                            let chunk_is_dirty = chunk.is_dirty();
+                            // Edge case: this chunk is loaded while we were saving, mark it as cleaned since we are
+                            // updating what we will write here
+                            chunk.mark_dirty(false);
+                            // It is important that we keep the lock after we mark the chunk as clean so no one else
+                            // can modify it
+                            let chunk = chunk.downgrade();
+
+                            // We only need to update the chunk if it is dirty
+                            if chunk_is_dirty {
+                                chunk_serializer.write().await.update_chunk(&*chunk, &self.chunk_config).await?;
+                            }
+                            Ok::<bool, ChunkWritingError>(chunk_is_dirty)
+                        }
+                    });
+                    // Run all update tasks concurrently and propagate any error
+                    let updated = futures::future::try_join_all(update_tasks)
+                        .await?
+                        .into_iter()
+                        .filter(|dirty| *dirty)
+                        .count();
+                    log::trace!("Updated data for file {}", path.display());
+
+                    let is_watched = self
+                        .watchers
+                        .read()
+                        .await
+                        .get(&path)
+                        .is_some_and(|count| !count.is_zero());
+
+                    if force_write || !is_watched {
+                        // With the modification done, we can drop the write lock but keep the read lock
+                        // to avoid other threads to write/modify the data, but allow other threads to read it
+                        let serializer = chunk_serializer.read().await;
+
+                        log::debug!("Writing file for {}", path.display());
+                        serializer
+                            .write(&path)
+                            .await
+                            .map_err(|err| ChunkWritingError::IoError(err.kind()))?;
+
+                        // Remove lock
+                        drop(serializer);
+                        // Decrement strong count
+                        drop(chunk_serializer);
+
+                        // If there are still no watchers, drop from the locks
+                        let mut locks = self.file_locks.write().await;
+
+                        if self
+                            .watchers
+                            .read()
+                            .await
+                            .get(&path)
+                            .is_none_or(|count| count.is_zero())
+                        {
+                            let can_remove = if let Some(loader) = locks.get(&path) {
+                                loader.can_remove().await
+                            } else {
+                                true
+                            };
+
+                            if can_remove {
+                                locks.remove(&path);
+                                log::trace!("Removed lockfile cache {}", path.display());
+                            } else {
+                                log::trace!("Wanted to remove lockfile cache {} but someone still holds a reference to it!", path.display());
+                            }
+                        }
+                    }
+
+                    Ok(updated)
+                });
+
+            //TODO: we need to handle the errors and return the result
+            // files to save
+            let results: Vec<Result<usize, ChunkWritingError>> = join_all(tasks).await;
+
+            Ok(results.into_iter().filter_map(Result::ok).sum())
+        })
+    }
+}
+
 impl<P, S> FileIO for ChunkFileManager<S>
 where
     P: PathFromLevelFolder + Send + Sync + Sized + Dirtiable + 'static,
@@ -249,7 +388,16 @@ where
                             unreachable!("Default Serializer must be created")
                         }
                         Err(err) => {
-                            let _ = task_stream.send(LoadedData::Error((chunks[0], err))).await;
+                            // Every requested chunk of the region has to be answered, otherwise
+                            // their loading never finishes
+                            log::error!("Failed to read the region file {}: {err}", path.display());
+                            for chunk in chunks {
+                                let error =
+                                    LoadedData::Error((chunk, ChunkReadingError::RegionIsInvalid));
+                                if task_stream.send(error).await.is_err() {
+                                    return;
+                                }
+                            }
                             return;
                         }
                     };
@@ -284,130 +432,21 @@ where
         })
     }
 
-    // Changed: Return BoxFuture<Result<(), ChunkWritingError>>
+    // Changed: Return BoxFuture<Result<usize, ChunkWritingError>>
     fn save_chunks<'a>(
         &'a self,
         folder: &'a LevelFolder,
         chunks_data: Vec<(Vector2<i32>, Self::Data)>,
-    ) -> BoxFuture<'a, Result<(), ChunkWritingError>> {
-        Box::pin(async move {
-            let mut regions_chunks: BTreeMap<String, Vec<Self::Data>> = BTreeMap::new();
-
-            for (at, chunk) in chunks_data {
-                let key = S::get_chunk_key(&at);
-
-                match regions_chunks.entry(key) {
-                    std::collections::btree_map::Entry::Occupied(mut occupied) => {
-                        occupied.get_mut().push(chunk);
-                    }
-                    std::collections::btree_map::Entry::Vacant(vacant) => {
-                        vacant.insert(vec![chunk]);
-                    }
-                }
-            }
-
-            // we use a Sync Closure with an Async Block to execute the tasks in parallel
-            // with out waiting the future. Also it improve we File Cache utilizations.
-            let tasks = regions_chunks
-                .into_iter()
-                // The closure now needs `move` to capture `self` and `folder` by value
-                .map(move |(file_name, chunk_locks)| async move {
-                    let path = P::file_path(folder, &file_name);
-                    log::trace!("Updating data for file {}", path.display());
-
-                    let chunk_serializer = match self.get_serializer(&path).await {
-                        Ok(file) => Ok(file),
-                        Err(ChunkReadingError::ChunkNotExist) => {
-                            unreachable!("Must be managed by the cache")
-                        }
-                        Err(ChunkReadingError::IoError(err)) => {
-                            error!("Error reading the data before write: {err}");
-                            Err(ChunkWritingError::IoError(err))
-                        }
-                        Err(_) => Err(ChunkWritingError::IoError(std::io::ErrorKind::Other)),
-                    }?;
-
-                    // Create a task for each chunk_lock
-                    let update_tasks = chunk_locks.into_iter().map(|chunk_lock| {
-                        let chunk_serializer = chunk_serializer.clone();
-                        async move {
-                            let mut chunk = chunk_lock.write().await;
-                            let chunk_is_dirty = chunk.is_dirty();
-                            // Edge case: this chunk is loaded while we were saving, mark it as cleaned since we are
-                            // updating what we will write here
-                            chunk.mark_dirty(false);
-                            // It is important that we keep the lock after we mark the chunk as clean so no one else
-                            // can modify it
-                            let chunk = chunk.downgrade();
-
-                            // We only need to update the chunk if it is dirty
-                            if chunk_is_dirty {
-                                chunk_serializer.write().await.update_chunk(&*chunk, &self.chunk_config).await?;
-                            }
-                            Ok::<(), ChunkWritingError>(())
-                        }
-                    });
-                    // Run all update tasks concurrently and propagate any error
-                    futures::future::try_join_all(update_tasks).await?;
-                    log::trace!("Updated data for file {}", path.display());
-
-                    let is_watched = self
-                        .watchers
-                        .read()
-                        .await
-                        .get(&path)
-                        .is_some_and(|count| !count.is_zero());
-
-                    if !is_watched {
-                        // With the modification done, we can drop the write lock but keep the read lock
-                        // to avoid other threads to write/modify the data, but allow other threads to read it
-                        let serializer = chunk_serializer.read().await;
-
-                        log::debug!("Writing file for {}", path.display());
-                        serializer
-                            .write(&path)
-                            .await
-                            .map_err(|err| ChunkWritingError::IoError(err.kind()))?;
-
-                        // Remove lock
-                        drop(serializer);
-                        // Decrement strong count
-                        drop(chunk_serializer);
-
-                        // If there are still no watchers, drop from the locks
-                        let mut locks = self.file_locks.write().await;
-
-                        if self
-                            .watchers
-                            .read()
-                            .await
-                            .get(&path)
-                            .is_none_or(|count| count.is_zero())
-                        {
-                            let can_remove = if let Some(loader) = locks.get(&path) {
-                                loader.can_remove().await
-                            } else {
-                                true
-                            };
-
-                            if can_remove {
-                                locks.remove(&path);
-                                log::trace!("Removed lockfile cache {}", path.display());
-                            } else {
-                                log::trace!("Wanted to remove lockfile cache {} but someone still holds a reference to it!", path.display());
-                            }
-                        }
-                    }
-
-                    Ok(())
-                });
-
-            //TODO: we need to handle the errors and return the result
-            // files to save
-            let _test: Vec<Result<(), ChunkWritingError>> = join_all(tasks).await;
+    ) -> BoxFuture<'a, Result<usize, ChunkWritingError>> {
+        self.update_and_write_chunks(folder, chunks_data, false)
+    }
 
-            Ok(())
-        })
+    fn save_chunks_now<'a>(
+        &'a self,
+        folder: &'a LevelFolder,
+        chunks_data: Vec<(Vector2<i32>, Self::Data)>,
+    ) -> BoxFuture<'a, Result<usize, ChunkWritingError>> {
+        self.update_and_write_chunks(folder, chunks_data, true)
     }
 
     // Changed: Return BoxFuture<()>
@@ -435,3 +474,65 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use pumpkin_config::chunk::AnvilChunkConfig;
+    use pumpkin_util::math::vector2::Vector2;
+    use temp_dir::TempDir;
+    use tokio::sync::RwLock;
+
+    use super::ChunkFileManager;
+    use crate::chunk::ChunkEntityData;
+    use crate::chunk::format::anvil::AnvilChunkFile;
+    use crate::chunk::io::{Dirtiable, FileIO};
+    use crate::level::LevelFolder;
+
+    #[tokio::test]
+    async fn watched_chunks_can_be_saved_right_away() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = LevelFolder {
+            root_folder: temp_dir.path().to_path_buf(),
+            region_folder: temp_dir.path().join("region"),
+            entities_folder: temp_dir.path().join("entities"),
+        };
+        std::fs::create_dir(&folder.entities_folder).unwrap();
+        let region_file = folder.entities_folder.join("r.0.0.mca");
+
+        let saver =
+            ChunkFileManager::<AnvilChunkFile<ChunkEntityData>>::new(AnvilChunkConfig::default());
+        let position = Vector2::new(0, 0);
+        saver.watch_chunks(&folder, &[position]).await;
+        let chunk = Arc::new(RwLock::new(ChunkEntityData {
+            x: 0,
+            z: 0,
+            data: HashMap::new(),
+            dirty: true,
+        }));
+
+        // Watched chunks are only written by the autosave once they are unloaded
+        saver
+            .save_chunks(&folder, vec![(position, chunk.clone())])
+            .await
+            .unwrap();
+        assert!(!region_file.exists());
+
+        chunk.write().await.mark_dirty(true);
+        let updated = saver
+            .save_chunks_now(&folder, vec![(position, chunk.clone())])
+            .await
+            .unwrap();
+        assert_eq!(updated, 1);
+        assert!(region_file.exists());
+        assert!(!chunk.read().await.is_dirty());
+        // A clean chunk isn't updated again
+        let updated = saver
+            .save_chunks_now(&folder, vec![(position, chunk.clone())])
+            .await
+            .unwrap();
+        assert_eq!(updated, 0);
+    }
+}
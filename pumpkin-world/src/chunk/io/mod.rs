@@ -61,12 +61,19 @@ where
         stream: tokio::sync::mpsc::Sender<LoadedData<Self::Data, ChunkReadingError>>,
     ) -> BoxFuture<'a, ()>; // Returns BoxFuture<()>
 
-    /// Persist the chunks data
+    /// Persist the chunks data, returns how many of the chunks were dirty and got updated
     fn save_chunks<'a>(
         &'a self,
         folder: &'a LevelFolder,
         chunks_data: Vec<(Vector2<i32>, Self::Data)>,
-    ) -> BoxFuture<'a, Result<(), ChunkWritingError>>; // Returns BoxFuture<Result>
+    ) -> BoxFuture<'a, Result<usize, ChunkWritingError>>; // Returns BoxFuture<Result>
+
+    /// Persist the chunks data and write it to disk right away, even if the chunks are watched
+    fn save_chunks_now<'a>(
+        &'a self,
+        folder: &'a LevelFolder,
+        chunks_data: Vec<(Vector2<i32>, Self::Data)>,
+    ) -> BoxFuture<'a, Result<usize, ChunkWritingError>>;
 
     /// Tells the `ChunkIO` that these chunks are currently loaded in memory
     fn watch_chunks<'a>(
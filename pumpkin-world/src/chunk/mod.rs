@@ -149,6 +149,33 @@ pub enum ChunkHeightmapType {
     MotionBlocking = 1,
     MotionBlockingNoLeaves = 2,
 }
+
+impl ChunkHeightmapType {
+    pub const ALL: [Self; 3] = [
+        Self::WorldSurface,
+        Self::MotionBlocking,
+        Self::MotionBlockingNoLeaves,
+    ];
+
+    /// Whether the block counts towards the height of this heightmap.
+    pub fn is_opaque(self, block_state_id: BlockStateId) -> bool {
+        let block_state = BlockState::from_id(block_state_id);
+        let block = Block::from_state_id(block_state_id);
+        let is_motion_blocking = || {
+            blocks_movement(block_state, block)
+                || Fluid::from_registry_key(block.registry_key())
+                    .is_some_and(|fluid| !fluid.states.is_empty())
+        };
+        match self {
+            Self::WorldSurface => !block_state.is_air(),
+            Self::MotionBlocking => is_motion_blocking(),
+            Self::MotionBlockingNoLeaves => {
+                is_motion_blocking() && !block.has_tag(&MINECRAFT_LEAVES)
+            }
+        }
+    }
+}
+
 impl TryFrom<usize> for ChunkHeightmapType {
     type Error = &'static str;
 
@@ -470,9 +497,75 @@ impl ChunkData {
         relative_z: usize,
         block_state_id: BlockStateId,
     ) {
-        // TODO @LUK_ESC? update the heightmap
-        self.section
-            .set_relative_block(relative_x, relative_y, relative_z, block_state_id);
+        self.set_block_absolute_y(
+            relative_x,
+            relative_y as i32 + self.section.min_y,
+            relative_z,
+            block_state_id,
+        );
+    }
+
+    /// Sets the given block in the chunk and updates the heightmaps, returning the old block
+    /// state ID
+    pub fn set_block_absolute_y(
+        &mut self,
+        relative_x: usize,
+        y: i32,
+        relative_z: usize,
+        block_state_id: BlockStateId,
+    ) -> BlockStateId {
+        let replaced_block_state_id =
+            self.section
+                .set_block_absolute_y(relative_x, y, relative_z, block_state_id);
+        if replaced_block_state_id != block_state_id {
+            self.update_heightmaps(relative_x, y, relative_z, block_state_id);
+        }
+        replaced_block_state_id
+    }
+
+    /// Updates the heightmaps of a column after the block at `y` changed to `block_state_id`.
+    fn update_heightmaps(
+        &mut self,
+        relative_x: usize,
+        y: i32,
+        relative_z: usize,
+        block_state_id: BlockStateId,
+    ) {
+        let min_y = self.section.min_y;
+        let max_y = min_y + (self.section.sections.len() * BlockPalette::SIZE) as i32;
+        if y < min_y || y >= max_y {
+            return;
+        }
+
+        for heightmap in ChunkHeightmapType::ALL {
+            let top = self
+                .heightmap
+                .get(heightmap, relative_x as i32, relative_z as i32, min_y);
+            let new_top = if heightmap.is_opaque(block_state_id) {
+                if y <= top {
+                    continue;
+                }
+                y
+            } else {
+                if y != top {
+                    continue;
+                }
+                // The top block was removed, look for the next one below it
+                (min_y..y)
+                    .rev()
+                    .find(|y| {
+                        self.section
+                            .get_block_absolute_y(relative_x, *y, relative_z)
+                            .is_some_and(|state_id| heightmap.is_opaque(state_id))
+                    })
+                    .unwrap_or(min_y)
+            };
+            self.heightmap.set(
+                heightmap,
+                BlockPos::new(relative_x as i32, new_top, relative_z as i32),
+                min_y,
+            );
+        }
     }
 
     /// Sets the given block in the chunk, returning the old block
@@ -492,7 +585,6 @@ impl ChunkData {
             .set_relative_block(relative_x, relative_y, relative_z, block_state_id);
     }
 
-    //TODO: Tracking heightmaps update.
     pub fn calculate_heightmap(&mut self) -> ChunkHeightmaps {
         let highest_non_empty_subchunk = self.get_highest_non_empty_subchunk();
         let mut heightmaps = ChunkHeightmaps::default();
@@ -525,33 +617,12 @@ impl ChunkData {
         for y in (self.section.min_y..=start_height).rev() {
             let pos = BlockPos::new(x as i32, y, z as i32);
             let state_id = self.section.get_block_absolute_y(x, y, z).unwrap();
-            let block_state = BlockState::from_id(state_id);
-            let block = Block::from_state_id(state_id);
 
-            if !block_state.is_air() && !has_found[ChunkHeightmapType::WorldSurface as usize] {
-                heightmaps.set(ChunkHeightmapType::WorldSurface, pos, self.section.min_y);
-                has_found[ChunkHeightmapType::WorldSurface as usize] = true;
-            }
-
-            let is_motion_blocking = blocks_movement(block_state, block)
-                || Fluid::from_registry_key(block.registry_key())
-                    .is_some_and(|fluid| !fluid.states.is_empty());
-
-            if !has_found[ChunkHeightmapType::MotionBlocking as usize] && is_motion_blocking {
-                heightmaps.set(ChunkHeightmapType::MotionBlocking, pos, self.section.min_y);
-                has_found[ChunkHeightmapType::MotionBlocking as usize] = true;
-            }
-
-            if !has_found[ChunkHeightmapType::MotionBlockingNoLeaves as usize]
-                && is_motion_blocking
-                && !block.has_tag(&MINECRAFT_LEAVES)
-            {
-                heightmaps.set(
-                    ChunkHeightmapType::MotionBlockingNoLeaves,
-                    pos,
-                    self.section.min_y,
-                );
-                has_found[ChunkHeightmapType::MotionBlockingNoLeaves as usize] = true;
+            for heightmap in ChunkHeightmapType::ALL {
+                if !has_found[heightmap as usize] && heightmap.is_opaque(state_id) {
+                    heightmaps.set(heightmap, pos, self.section.min_y);
+                    has_found[heightmap as usize] = true;
+                }
             }
 
             if !has_found.contains(&false) {
@@ -592,3 +663,68 @@ pub enum ChunkSerializingError {
     #[error("Error serializing chunk: {0}")]
     ErrorSerializingChunk(pumpkin_nbt::Error),
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::chunk::ChunkStatus;
+
+    use super::{ChunkData, ChunkHeightmapType, ChunkHeightmaps, ChunkSections, SubChunk};
+
+    #[test]
+    fn heightmaps_track_block_changes() {
+        let min_y = -64;
+        let mut chunk = ChunkData {
+            section: ChunkSections::new(vec![SubChunk::default(); 24].into_boxed_slice(), min_y),
+            heightmap: ChunkHeightmaps::default(),
+            x: 0,
+            z: 0,
+            block_ticks: Default::default(),
+            fluid_ticks: Default::default(),
+            block_entities: Default::default(),
+            light_engine: Default::default(),
+            status: ChunkStatus::Full,
+            dirty: false,
+        };
+        let heights = |chunk: &ChunkData| {
+            ChunkHeightmapType::ALL.map(|heightmap| chunk.heightmap.get(heightmap, 3, 5, min_y))
+        };
+        let air = Block::AIR.default_state.id;
+        let stone = Block::STONE.default_state.id;
+        let leaves = Block::OAK_LEAVES.default_state.id;
+        let torch = Block::TORCH.default_state.id;
+        assert_eq!(heights(&chunk), [min_y; 3]);
+
+        chunk.set_block_absolute_y(3, 10, 5, stone);
+        assert_eq!(heights(&chunk), [10; 3]);
+        chunk.set_block_absolute_y(3, 20, 5, leaves);
+        assert_eq!(heights(&chunk), [20, 20, 10]);
+        chunk.set_block_absolute_y(3, 30, 5, torch);
+        assert_eq!(heights(&chunk), [30, 20, 10]);
+        // Blocks below the top don't change anything
+        chunk.set_block_absolute_y(3, 0, 5, stone);
+        assert_eq!(heights(&chunk), [30, 20, 10]);
+        // Other columns aren't affected
+        assert_eq!(
+            chunk
+                .heightmap
+                .get(ChunkHeightmapType::WorldSurface, 4, 5, min_y),
+            min_y
+        );
+        // The same as calculating the heightmaps from scratch
+        let calculated = chunk.calculate_heightmap();
+        assert_eq!(
+            ChunkHeightmapType::ALL.map(|heightmap| calculated.get(heightmap, 3, 5, min_y)),
+            heights(&chunk)
+        );
+
+        chunk.set_block_absolute_y(3, 30, 5, air);
+        assert_eq!(heights(&chunk), [20, 20, 10]);
+        chunk.set_block_absolute_y(3, 20, 5, air);
+        assert_eq!(heights(&chunk), [10; 3]);
+        chunk.set_block_absolute_y(3, 10, 5, air);
+        assert_eq!(heights(&chunk), [0; 3]);
+        chunk.set_block_absolute_y(3, 0, 5, air);
+        assert_eq!(heights(&chunk), [min_y; 3]);
+    }
+}
@@ -15,8 +15,9 @@ use std::pin::Pin;
 
 use crate::generation::height_limit::HeightLimitView;
 
+use crate::generation::generator::VanillaGenerator;
 use crate::generation::proto_chunk::{GenerationCache, TerrainCache};
-use crate::generation::settings::{GenerationSettings, gen_settings_from_dimension};
+use crate::generation::settings::GeneratorSetting;
 use crate::level::{Level, SyncChunk};
 use crate::world::{BlockAccessor, BlockRegistryExt};
 use crate::{BlockStateId, GlobalRandomConfig, ProtoChunk, ProtoNoiseRouters};
@@ -60,6 +61,9 @@ type ChunkPos = Vector2<i32>;
 type ChunkLevel = HashMapType<ChunkPos, i8>;
 type IOLock = Arc<(Mutex<HashMapType<ChunkPos, u8>>, Condvar)>;
 
+/// How many chunks are handed to the chunk saver at once while shutting down (one region)
+const SAVE_BATCH_SIZE: usize = 1024;
+
 pub struct HeapNode(i8, ChunkPos);
 impl PartialEq for HeapNode {
     fn eq(&self, other: &Self) -> bool {
@@ -1026,7 +1030,7 @@ impl GenerationCache for Cache {
         match &mut self.chunks[(dx * self.size + dz) as usize] {
             Chunk::Level(data) => {
                 let mut chunk = data.blocking_write();
-                chunk.section.set_block_absolute_y(
+                chunk.set_block_absolute_y(
                     (pos.x & 15) as usize,
                     pos.y,
                     (pos.z & 15) as usize,
@@ -1160,15 +1164,22 @@ impl Cache {
         &mut self,
         stage: StagedChunkEnum,
         block_registry: &dyn BlockRegistryExt,
-        settings: &GenerationSettings,
+        setting: GeneratorSetting,
         random_config: &GlobalRandomConfig,
         terrain_cache: &TerrainCache,
         noise_router: &ProtoNoiseRouters,
         dimension: Dimension,
     ) {
         let mid = ((self.size * self.size) >> 1) as usize;
+        let settings = setting.settings();
         match stage {
             Empty => panic!("empty stage"),
+            // Flat worlds are only made of their layers, without structures or features
+            StagedChunkEnum::StructureStart | StagedChunkEnum::StructureReferences | Features
+                if !setting.populates_noise() =>
+            {
+                self.chunks[mid].get_proto_chunk_mut().stage = stage;
+            }
             StagedChunkEnum::StructureStart => self.chunks[mid]
                 .get_proto_chunk_mut()
                 .set_structure_starts(random_config, settings),
@@ -1176,11 +1187,18 @@ impl Cache {
             Biomes => self.chunks[mid]
                 .get_proto_chunk_mut()
                 .step_to_biomes(dimension, noise_router),
+            Noise if !setting.populates_noise() => {
+                self.chunks[mid].get_proto_chunk_mut().step_to_flat();
+            }
             Noise => self.chunks[mid].get_proto_chunk_mut().step_to_noise(
                 settings,
                 random_config,
                 noise_router,
             ),
+            // Flat layers have no surface to build
+            Surface if !setting.populates_noise() => {
+                self.chunks[mid].get_proto_chunk_mut().stage = Surface;
+            }
             Surface => self.chunks[mid].get_proto_chunk_mut().step_to_surface(
                 settings,
                 random_config,
@@ -1201,6 +1219,60 @@ impl Cache {
     }
 }
 
+/// Generates the chunk at `chunk_x`, `chunk_z` through all stages up to its features, like the
+/// generation threads do. Its neighbors are generated up to their surface for the features, but
+/// structures starting further away don't reach into the chunk.
+pub(crate) fn generate_chunk_with_features(
+    world_gen: &VanillaGenerator,
+    block_registry: &dyn BlockRegistryExt,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> ProtoChunk {
+    use crate::biome::hash_seed;
+
+    let advance = |cache: &mut Cache, stage| {
+        cache.advance(
+            stage,
+            block_registry,
+            world_gen.setting,
+            &world_gen.random_config,
+            &world_gen.terrain_cache,
+            &world_gen.base_router,
+            world_gen.dimension,
+        );
+    };
+    let biome_mixer_seed = hash_seed(world_gen.random_config.seed);
+    let mut neighbors = Cache::new(chunk_x - 1, chunk_z - 1, 3);
+    for x in chunk_x - 1..=chunk_x + 1 {
+        for z in chunk_z - 1..=chunk_z + 1 {
+            let mut cache = Cache::new(x, z, 1);
+            cache.chunks.push(Proto(Box::new(ProtoChunk::new(
+                x,
+                z,
+                &world_gen.dimension,
+                world_gen.default_block,
+                biome_mixer_seed,
+            ))));
+            for stage in [
+                Biomes,
+                StagedChunkEnum::StructureStart,
+                StagedChunkEnum::StructureReferences,
+                Noise,
+                Surface,
+            ] {
+                advance(&mut cache, stage);
+            }
+            neighbors.chunks.append(&mut cache.chunks);
+        }
+    }
+    advance(&mut neighbors, Features);
+
+    let Proto(chunk) = neighbors.chunks.swap_remove(4) else {
+        unreachable!("the chunk was not upgraded to a level chunk");
+    };
+    *chunk
+}
+
 enum RecvChunk {
     IO(Chunk),
     Generation(Cache),
@@ -1402,6 +1474,7 @@ impl GenerationSchedule {
     pub fn create(
         oi_read_thread_count: usize,
         gen_thread_count: usize,
+        gen_pending_task_count: usize,
         level: Arc<Level>,
         level_channel: Arc<LevelChannel>,
         listener: Arc<ChunkListener>,
@@ -1412,7 +1485,7 @@ impl GenerationSchedule {
         let (send_read_io, recv_read_io) =
             crossfire::mpmc::bounded_tx_blocking_rx_async(oi_read_thread_count + 2);
         let (send_write_io, recv_write_io) = crossfire::spsc::unbounded_async();
-        let (send_gen, recv_gen) = crossfire::mpmc::bounded_blocking(gen_thread_count + 5);
+        let (send_gen, recv_gen) = crossfire::mpmc::bounded_blocking(gen_pending_task_count);
         let io_lock = Arc::new((Mutex::new(HashMapType::default()), Condvar::new()));
         for _ in 0..oi_read_thread_count {
             tracker.spawn(Self::io_read_work(
@@ -1674,8 +1747,30 @@ impl GenerationSchedule {
                     continue;
                 }
                 LoadedData::Missing(_) => {}
-                LoadedData::Error(_) => {
-                    log::warn!("chunk data read error pos: {pos:?}. regenerating");
+                LoadedData::Error((_, err)) if !level.regenerate_corrupt_chunks => {
+                    log::error!("Failed to read chunk {pos:?}, leaving it empty: {err}");
+                    let mut chunk = Proto(Box::new(ProtoChunk::new(
+                        pos.x,
+                        pos.y,
+                        dimension,
+                        level.world_gen.default_block,
+                        biome_mixer_seed,
+                    )));
+                    chunk.upgrade_to_level_chunk(dimension);
+                    if let Chunk::Level(data) = &chunk {
+                        let mut data = data.write().await;
+                        data.status = ChunkStatus::Full;
+                        // Only saved once it was changed, so the corrupt data isn't overwritten
+                        // right away
+                        data.dirty = false;
+                    }
+                    if send.send((pos, RecvChunk::IO(chunk))).is_err() {
+                        break;
+                    }
+                    continue;
+                }
+                LoadedData::Error((_, err)) => {
+                    log::warn!("Failed to read chunk {pos:?}, regenerating it: {err}");
                 }
             }
             if send
@@ -1714,11 +1809,28 @@ impl GenerationSchedule {
                 }
             }
             let pos = vec.iter().map(|(pos, _)| *pos).collect_vec();
-            level
-                .chunk_saver
-                .save_chunks(&level.level_folder, vec)
-                .await
-                .unwrap();
+            if level.is_shutting_down.load(Relaxed) {
+                // Only report progress for the final save, the batches are split so that the
+                // progress keeps moving for large worlds
+                level.save_progress.add_total(vec.len());
+                let mut vec = vec.into_iter().peekable();
+                while vec.peek().is_some() {
+                    let batch = vec.by_ref().take(SAVE_BATCH_SIZE).collect_vec();
+                    let saved = batch.len();
+                    let dirty = level
+                        .chunk_saver
+                        .save_chunks(&level.level_folder, batch)
+                        .await
+                        .unwrap();
+                    level.save_progress.add_saved(saved, dirty);
+                }
+            } else {
+                level
+                    .chunk_saver
+                    .save_chunks(&level.level_folder, vec)
+                    .await
+                    .unwrap();
+            }
             for i in pos {
                 let mut data = lock.0.lock().unwrap();
                 match data.entry(i) {
@@ -1754,13 +1866,12 @@ impl GenerationSchedule {
             thread::current().name().unwrap_or("unknown")
         );
 
-        let settings = gen_settings_from_dimension(&level.world_gen.dimension);
         while let Ok((pos, mut cache, stage)) = recv.recv() {
             // debug!("generation thread receive chunk pos {pos:?} to stage {stage:?}");
             cache.advance(
                 stage,
                 level.block_registry.as_ref(),
-                settings,
+                level.world_gen.setting,
                 &level.world_gen.random_config,
                 &level.world_gen.terrain_cache,
                 &level.world_gen.base_router,
@@ -2004,6 +2115,7 @@ impl GenerationSchedule {
             }
 
             'out2: while let Some(task) = self.queue.pop() {
+                level.generation_queue_len.store(self.queue.len(), Relaxed);
                 if self.resort_work(self.send_level.get()) {
                     self.queue.push(task);
                     break 'out2;
@@ -2121,6 +2233,7 @@ impl GenerationSchedule {
                 }
             }
 
+            level.generation_queue_len.store(self.queue.len(), Relaxed);
             if self.queue.is_empty() {
                 // debug!("the queue is empty. thread sleep");
                 while self.running_task_count > 0 && self.queue.is_empty() {
@@ -2181,3 +2294,82 @@ impl GenerationSchedule {
         true
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, BlockState, dimension::Dimension};
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::{Cache, Chunk};
+    use crate::biome::hash_seed;
+    use crate::generation::proto_chunk::GenerationCache;
+    use crate::world::{BlockAccessor, BlockRegistryExt};
+    use crate::{GlobalRandomConfig, ProtoChunk};
+
+    struct BlockRegistry;
+
+    impl BlockRegistryExt for BlockRegistry {
+        fn can_place_at(
+            &self,
+            _block: &Block,
+            _state: &BlockState,
+            _block_accessor: &dyn BlockAccessor,
+            _block_pos: &BlockPos,
+        ) -> bool {
+            true
+        }
+    }
+
+    /// A plains chunk of deepslate below y 0 and stone up to the sea level.
+    fn deep_chunk(x: i32, z: i32, random_config: &GlobalRandomConfig) -> ProtoChunk {
+        let mut chunk = ProtoChunk::new(
+            x,
+            z,
+            &Dimension::OVERWORLD,
+            Block::STONE.default_state,
+            hash_seed(random_config.seed),
+        );
+        for local_x in 0..16 {
+            for local_z in 0..16 {
+                for y in -64..0 {
+                    chunk.set_block_state(local_x, y, local_z, Block::DEEPSLATE.default_state);
+                }
+                for y in 0..63 {
+                    chunk.set_block_state(local_x, y, local_z, Block::STONE.default_state);
+                }
+            }
+        }
+        chunk
+    }
+
+    #[test]
+    fn features_place_diamond_ore_deep_down() {
+        let random_config = GlobalRandomConfig::new(13579, false);
+        let mut cache = Cache::new(-1, -1, 3);
+        for x in -1..=1 {
+            for z in -1..=1 {
+                cache
+                    .chunks
+                    .push(Chunk::Proto(Box::new(deep_chunk(x, z, &random_config))));
+            }
+        }
+
+        ProtoChunk::generate_features_and_structure(&mut cache, &BlockRegistry, &random_config);
+
+        let mut diamonds = 0;
+        for x in 0..16 {
+            for z in 0..16 {
+                for y in -64..63 {
+                    let state = GenerationCache::get_block_state(&cache, &Vector3::new(x, y, z));
+                    let block = state.to_block();
+                    if block == &Block::DIAMOND_ORE || block == &Block::DEEPSLATE_DIAMOND_ORE {
+                        // Vanilla only places diamonds up to y 16
+                        assert!(y <= 16, "diamond ore at y {y}");
+                        diamonds += 1;
+                    }
+                }
+            }
+        }
+        assert!(diamonds > 0, "no diamond ore was placed");
+    }
+}
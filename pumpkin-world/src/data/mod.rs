@@ -1 +1,2 @@
 pub mod player_data;
+pub mod player_stats;
@@ -21,6 +21,8 @@ pub enum PlayerDataError {
     Io(#[from] io::Error),
     #[error("NBT error: {0}")]
     Nbt(String),
+    #[error("JSON error: {0}")]
+    Json(String),
 }
 
 impl PlayerDataStorage {
@@ -0,0 +1,331 @@
+use std::collections::BTreeMap;
+use std::fs::{self, create_dir_all};
+use std::path::PathBuf;
+
+use pumpkin_data::Block;
+use pumpkin_data::entity::EntityType;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::player_data::PlayerDataError;
+use crate::chunk::format::anvil::WORLD_DATA_VERSION;
+
+/// Statistics that are not tied to a block, item or entity.
+///
+/// The discriminants are the network ids of the `custom_stat` registry.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CustomStat {
+    LeaveGame = 0,
+    PlayTime = 1,
+    TotalWorldTime = 2,
+    TimeSinceDeath = 3,
+    TimeSinceRest = 4,
+    SneakTime = 5,
+    WalkOneCm = 6,
+    CrouchOneCm = 7,
+    SprintOneCm = 8,
+    WalkOnWaterOneCm = 9,
+    FallOneCm = 10,
+    ClimbOneCm = 11,
+    FlyOneCm = 12,
+    WalkUnderWaterOneCm = 13,
+    MinecartOneCm = 14,
+    BoatOneCm = 15,
+    PigOneCm = 16,
+    HappyGhastOneCm = 17,
+    HorseOneCm = 18,
+    AviateOneCm = 19,
+    SwimOneCm = 20,
+    StriderOneCm = 21,
+    NautilusOneCm = 22,
+    Jump = 23,
+    Drop = 24,
+    DamageDealt = 25,
+    DamageDealtAbsorbed = 26,
+    DamageDealtResisted = 27,
+    DamageTaken = 28,
+    DamageBlockedByShield = 29,
+    DamageAbsorbed = 30,
+    DamageResisted = 31,
+    Deaths = 32,
+    MobKills = 33,
+    AnimalsBred = 34,
+    PlayerKills = 35,
+}
+
+impl CustomStat {
+    const ALL: [Self; 36] = [
+        Self::LeaveGame,
+        Self::PlayTime,
+        Self::TotalWorldTime,
+        Self::TimeSinceDeath,
+        Self::TimeSinceRest,
+        Self::SneakTime,
+        Self::WalkOneCm,
+        Self::CrouchOneCm,
+        Self::SprintOneCm,
+        Self::WalkOnWaterOneCm,
+        Self::FallOneCm,
+        Self::ClimbOneCm,
+        Self::FlyOneCm,
+        Self::WalkUnderWaterOneCm,
+        Self::MinecartOneCm,
+        Self::BoatOneCm,
+        Self::PigOneCm,
+        Self::HappyGhastOneCm,
+        Self::HorseOneCm,
+        Self::AviateOneCm,
+        Self::SwimOneCm,
+        Self::StriderOneCm,
+        Self::NautilusOneCm,
+        Self::Jump,
+        Self::Drop,
+        Self::DamageDealt,
+        Self::DamageDealtAbsorbed,
+        Self::DamageDealtResisted,
+        Self::DamageTaken,
+        Self::DamageBlockedByShield,
+        Self::DamageAbsorbed,
+        Self::DamageResisted,
+        Self::Deaths,
+        Self::MobKills,
+        Self::AnimalsBred,
+        Self::PlayerKills,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::LeaveGame => "leave_game",
+            Self::PlayTime => "play_time",
+            Self::TotalWorldTime => "total_world_time",
+            Self::TimeSinceDeath => "time_since_death",
+            Self::TimeSinceRest => "time_since_rest",
+            Self::SneakTime => "sneak_time",
+            Self::WalkOneCm => "walk_one_cm",
+            Self::CrouchOneCm => "crouch_one_cm",
+            Self::SprintOneCm => "sprint_one_cm",
+            Self::WalkOnWaterOneCm => "walk_on_water_one_cm",
+            Self::FallOneCm => "fall_one_cm",
+            Self::ClimbOneCm => "climb_one_cm",
+            Self::FlyOneCm => "fly_one_cm",
+            Self::WalkUnderWaterOneCm => "walk_under_water_one_cm",
+            Self::MinecartOneCm => "minecart_one_cm",
+            Self::BoatOneCm => "boat_one_cm",
+            Self::PigOneCm => "pig_one_cm",
+            Self::HappyGhastOneCm => "happy_ghast_one_cm",
+            Self::HorseOneCm => "horse_one_cm",
+            Self::AviateOneCm => "aviate_one_cm",
+            Self::SwimOneCm => "swim_one_cm",
+            Self::StriderOneCm => "strider_one_cm",
+            Self::NautilusOneCm => "nautilus_one_cm",
+            Self::Jump => "jump",
+            Self::Drop => "drop",
+            Self::DamageDealt => "damage_dealt",
+            Self::DamageDealtAbsorbed => "damage_dealt_absorbed",
+            Self::DamageDealtResisted => "damage_dealt_resisted",
+            Self::DamageTaken => "damage_taken",
+            Self::DamageBlockedByShield => "damage_blocked_by_shield",
+            Self::DamageAbsorbed => "damage_absorbed",
+            Self::DamageResisted => "damage_resisted",
+            Self::Deaths => "deaths",
+            Self::MobKills => "mob_kills",
+            Self::AnimalsBred => "animals_bred",
+            Self::PlayerKills => "player_kills",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL.into_iter().find(|stat| stat.name() == name)
+    }
+}
+
+/// A single statistic of a player.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Stat {
+    Mined(&'static Block),
+    Killed(&'static EntityType),
+    KilledBy(&'static EntityType),
+    Custom(CustomStat),
+}
+
+impl Stat {
+    const MINED: &str = "minecraft:mined";
+    const KILLED: &str = "minecraft:killed";
+    const KILLED_BY: &str = "minecraft:killed_by";
+    const CUSTOM: &str = "minecraft:custom";
+
+    /// The name of the stat type, like it is used in the stats file.
+    #[must_use]
+    pub const fn category(&self) -> &'static str {
+        match self {
+            Self::Mined(_) => Self::MINED,
+            Self::Killed(_) => Self::KILLED,
+            Self::KilledBy(_) => Self::KILLED_BY,
+            Self::Custom(_) => Self::CUSTOM,
+        }
+    }
+
+    #[must_use]
+    pub fn name(&self) -> String {
+        let name = match self {
+            Self::Mined(block) => block.name,
+            Self::Killed(entity_type) | Self::KilledBy(entity_type) => entity_type.resource_name,
+            Self::Custom(stat) => stat.name(),
+        };
+        format!("minecraft:{name}")
+    }
+
+    fn from_names(category: &str, name: &str) -> Option<Self> {
+        match category {
+            Self::MINED => Block::from_name(name).map(Self::Mined),
+            Self::KILLED => EntityType::from_name(name).map(Self::Killed),
+            Self::KILLED_BY => EntityType::from_name(name).map(Self::KilledBy),
+            Self::CUSTOM => CustomStat::from_name(name).map(Self::Custom),
+            _ => None,
+        }
+    }
+
+    /// The network ids of the stat type and the stat.
+    #[must_use]
+    pub fn network_ids(&self) -> (i32, i32) {
+        match self {
+            Self::Mined(block) => (0, i32::from(block.id)),
+            Self::Killed(entity_type) => (6, i32::from(entity_type.id)),
+            Self::KilledBy(entity_type) => (7, i32::from(entity_type.id)),
+            Self::Custom(stat) => (8, *stat as i32),
+        }
+    }
+}
+
+/// The statistics of a player, stored like vanilla does in `stats/<uuid>.json`.
+///
+/// Stats that Pumpkin doesn't know yet are kept, so they survive a round trip.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq)]
+pub struct PlayerStats {
+    stats: BTreeMap<String, BTreeMap<String, i32>>,
+    #[serde(rename = "DataVersion", default)]
+    data_version: i32,
+}
+
+impl PlayerStats {
+    #[must_use]
+    pub fn get(&self, stat: &Stat) -> i32 {
+        self.stats
+            .get(stat.category())
+            .and_then(|stats| stats.get(&stat.name()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Increments a stat and returns its new value, stats stop growing at `i32::MAX`.
+    pub fn increment(&mut self, stat: &Stat, amount: i32) -> i32 {
+        let value = self
+            .stats
+            .entry(stat.category().to_string())
+            .or_default()
+            .entry(stat.name())
+            .or_default();
+        *value = value.saturating_add(amount);
+        *value
+    }
+
+    /// Returns every known stat with its value.
+    pub fn iter(&self) -> impl Iterator<Item = (Stat, i32)> + '_ {
+        self.stats.iter().flat_map(|(category, stats)| {
+            stats.iter().filter_map(|(name, value)| {
+                Stat::from_names(category, name).map(|stat| (stat, *value))
+            })
+        })
+    }
+}
+
+/// Loads and saves the statistics of players as JSON files.
+pub struct PlayerStatsStorage {
+    /// Path to the directory where the stats are stored
+    data_path: PathBuf,
+    /// Whether player data saving is enabled
+    save_enabled: bool,
+}
+
+impl PlayerStatsStorage {
+    pub fn new(data_path: impl Into<PathBuf>, enabled: bool) -> Self {
+        Self {
+            data_path: data_path.into(),
+            save_enabled: enabled,
+        }
+    }
+
+    /// Returns the path for a player's stats file based on their UUID.
+    pub fn get_player_stats_path(&self, uuid: &Uuid) -> PathBuf {
+        self.data_path.join(format!("{uuid}.json"))
+    }
+
+    /// Loads the stats of a player, players without a stats file start with empty stats.
+    pub fn load_player_stats(&self, uuid: &Uuid) -> Result<PlayerStats, PlayerDataError> {
+        let path = self.get_player_stats_path(uuid);
+        if !self.save_enabled || !path.exists() {
+            return Ok(PlayerStats::default());
+        }
+        let data = fs::read(path)?;
+        serde_json::from_slice(&data).map_err(|e| PlayerDataError::Json(e.to_string()))
+    }
+
+    pub fn save_player_stats(
+        &self,
+        uuid: &Uuid,
+        stats: &PlayerStats,
+    ) -> Result<(), PlayerDataError> {
+        if !self.save_enabled {
+            return Ok(());
+        }
+        create_dir_all(&self.data_path)?;
+        let file = PlayerStats {
+            stats: stats.stats.clone(),
+            data_version: WORLD_DATA_VERSION,
+        };
+        let data = serde_json::to_vec(&file).map_err(|e| PlayerDataError::Json(e.to_string()))?;
+        fs::write(self.get_player_stats_path(uuid), data)?;
+        log::debug!("Saved player stats for {uuid} to disk");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::entity::EntityType;
+    use temp_dir::TempDir;
+    use uuid::Uuid;
+
+    use super::{CustomStat, PlayerStats, PlayerStatsStorage, Stat};
+
+    #[test]
+    fn stats_are_counted_and_saved() {
+        let mut stats = PlayerStats::default();
+        stats.increment(&Stat::Mined(&Block::STONE), 1);
+        stats.increment(&Stat::Mined(&Block::STONE), 2);
+        stats.increment(&Stat::Killed(&EntityType::ZOMBIE), 1);
+        stats.increment(&Stat::Custom(CustomStat::WalkOneCm), 150);
+
+        assert_eq!(stats.get(&Stat::Mined(&Block::STONE)), 3);
+        assert_eq!(stats.get(&Stat::Mined(&Block::DIRT)), 0);
+        assert_eq!(
+            Stat::Custom(CustomStat::WalkOneCm).network_ids(),
+            (8, CustomStat::WalkOneCm as i32)
+        );
+
+        let temp_dir = TempDir::new().unwrap();
+        let storage = PlayerStatsStorage::new(temp_dir.path(), true);
+        let uuid = Uuid::new_v4();
+        storage.save_player_stats(&uuid, &stats).unwrap();
+
+        let json = std::fs::read_to_string(storage.get_player_stats_path(&uuid)).unwrap();
+        assert!(json.contains(r#""minecraft:custom":{"minecraft:walk_one_cm":150}"#));
+        let loaded = storage.load_player_stats(&uuid).unwrap();
+        assert_eq!(loaded.get(&Stat::Mined(&Block::STONE)), 3);
+        assert_eq!(loaded.iter().count(), 3);
+    }
+}
@@ -32,6 +32,21 @@ impl Spike {
     }
 }
 
+/// How many obsidian spikes surround the main end island.
+pub const SPIKE_COUNT: usize = 10;
+/// How far the spikes are from the center of the main end island.
+const SPIKE_DISTANCE: f64 = 42.0;
+
+/// Returns the block x and z coordinates of the center of the spike with the given `index`.
+#[must_use]
+pub fn spike_center(index: usize) -> (i32, i32) {
+    let angle = 2.0 * (-std::f64::consts::PI + 0.3141592653589793 * index as f64);
+    (
+        (SPIKE_DISTANCE * angle.cos()).floor() as i32,
+        (SPIKE_DISTANCE * angle.sin()).floor() as i32,
+    )
+}
+
 impl EndSpikeFeature {
     #[expect(clippy::too_many_arguments)]
     pub fn generate<T: GenerationCache>(
@@ -46,10 +61,8 @@ impl EndSpikeFeature {
     ) -> bool {
         let mut spikes = self.spikes.clone();
         if spikes.is_empty() {
-            for i in 0..10 {
-                let angle = 2.0 * (-std::f64::consts::PI + 0.3141592653589793 * i as f64);
-                let center_x = (42.0 * angle.cos()).floor() as i32;
-                let center_z = (42.0 * angle.sin()).floor() as i32;
+            for i in 0..SPIKE_COUNT {
+                let (center_x, center_z) = spike_center(i);
 
                 let l = random.next_bounded_i32(10); // TODO
                 let radius = 2 + l / 3;
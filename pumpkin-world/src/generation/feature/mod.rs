@@ -5,3 +5,5 @@ pub mod placed_features;
 
 mod features;
 mod size;
+
+pub use features::end_spike;
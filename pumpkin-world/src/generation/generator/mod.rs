@@ -1,12 +1,7 @@
 use pumpkin_data::BlockState;
 use pumpkin_data::dimension::Dimension;
-use pumpkin_data::noise_router::{
-    END_BASE_NOISE_ROUTER, NETHER_BASE_NOISE_ROUTER, OVERWORLD_BASE_NOISE_ROUTER,
-};
 
-use super::{
-    noise::router::proto_noise_router::ProtoNoiseRouters, settings::gen_settings_from_dimension,
-};
+use super::{noise::router::proto_noise_router::ProtoNoiseRouters, settings::GeneratorSetting};
 use crate::generation::proto_chunk::TerrainCache;
 use crate::generation::{GlobalRandomConfig, Seed};
 
@@ -18,6 +13,8 @@ pub struct VanillaGenerator {
     pub random_config: GlobalRandomConfig,
     pub base_router: ProtoNoiseRouters,
     pub dimension: Dimension,
+    /// The settings the terrain is generated with, the dimension's own unless overridden
+    pub setting: GeneratorSetting,
 
     pub terrain_cache: TerrainCache,
 
@@ -26,28 +23,27 @@ pub struct VanillaGenerator {
 
 impl GeneratorInit for VanillaGenerator {
     fn new(seed: Seed, dimension: Dimension) -> Self {
+        Self::with_setting(
+            seed,
+            dimension,
+            GeneratorSetting::from_dimension(&dimension),
+        )
+    }
+}
+
+impl VanillaGenerator {
+    pub fn with_setting(seed: Seed, dimension: Dimension, setting: GeneratorSetting) -> Self {
         let random_config = GlobalRandomConfig::new(seed.0, false);
 
-        // TODO: The generation settings contains (part of?) the noise routers too; do we keep the separate or
-        // use only the generation settings?
-        let base = if dimension == Dimension::OVERWORLD {
-            OVERWORLD_BASE_NOISE_ROUTER
-        } else if dimension == Dimension::THE_NETHER {
-            NETHER_BASE_NOISE_ROUTER
-        } else if dimension == Dimension::THE_END {
-            END_BASE_NOISE_ROUTER
-        } else {
-            unreachable!()
-        };
+        let base = setting.base_router();
         let terrain_cache = TerrainCache::from_random(&random_config);
-        let generation_settings = gen_settings_from_dimension(&dimension);
-
-        let default_block = generation_settings.default_block.get_state();
+        let default_block = setting.settings().default_block.get_state();
         let base_router = ProtoNoiseRouters::generate(&base, &random_config);
         Self {
             random_config,
             base_router,
             dimension,
+            setting,
             terrain_cache,
             default_block,
         }
@@ -22,7 +22,9 @@ pub mod structure;
 mod surface;
 pub mod y_offset;
 
-use generator::{GeneratorInit, VanillaGenerator};
+pub use feature::end_spike;
+
+use generator::VanillaGenerator;
 use pumpkin_data::dimension::Dimension;
 use pumpkin_util::{
     random::{
@@ -31,10 +33,14 @@ use pumpkin_util::{
     },
     world_seed::Seed,
 };
-
-pub fn get_world_gen(seed: Seed, dimension: Dimension) -> Box<VanillaGenerator> {
-    // TODO decide which WorldGenerator to pick based on config.
-    Box::new(VanillaGenerator::new(seed, dimension))
+use settings::GeneratorSetting;
+
+pub fn get_world_gen(
+    seed: Seed,
+    dimension: Dimension,
+    setting: GeneratorSetting,
+) -> Box<VanillaGenerator> {
+    Box::new(VanillaGenerator::with_setting(seed, dimension, setting))
 }
 
 pub struct GlobalRandomConfig {
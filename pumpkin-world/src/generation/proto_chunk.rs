@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::pin::Pin;
 
 use pumpkin_data::dimension::Dimension;
@@ -26,8 +26,12 @@ use super::{
     },
     positions::chunk_pos::{start_block_x, start_block_z},
     section_coords,
-    settings::GenerationSettings,
-    surface::{MaterialRuleContext, estimate_surface_height, terrain::SurfaceTerrainBuilder},
+    settings::{GenerationSettings, GeneratorSetting},
+    surface::{
+        MaterialRuleContext, estimate_surface_height,
+        foliage::{place_foliage, replaces_feature},
+        terrain::SurfaceTerrainBuilder,
+    },
 };
 use crate::chunk::{ChunkData, ChunkHeightmapType};
 use crate::chunk_system::StagedChunkEnum;
@@ -555,6 +559,22 @@ impl ProtoChunk {
         self.stage = StagedChunkEnum::Noise;
     }
 
+    /// Fills the chunk with the flat world layers instead of sampling the terrain noise.
+    pub fn step_to_flat(&mut self) {
+        let mut y = self.bottom_y() as i32;
+        for (block, count) in GeneratorSetting::FLAT_LAYERS {
+            for _ in 0..count {
+                for x in 0..CHUNK_DIM as i32 {
+                    for z in 0..CHUNK_DIM as i32 {
+                        self.set_block_state(x, y, z, block.default_state);
+                    }
+                }
+                y += 1;
+            }
+        }
+        self.stage = StagedChunkEnum::Noise;
+    }
+
     pub fn step_to_surface(
         &mut self,
         settings: &GenerationSettings,
@@ -591,6 +611,7 @@ impl ProtoChunk {
             terrain_cache,
             &mut surface_height_estimate_sampler,
         );
+        place_foliage(self, random_config);
         self.stage = StagedChunkEnum::Surface;
     }
 
@@ -886,11 +907,11 @@ impl ProtoChunk {
     ) {
         let (center_x, center_z, min_y, height, biomes_in_chunk) = {
             let chunk = cache.get_center_chunk();
-            let mut biomes = HashSet::new();
-            // bad
-            for biome in chunk.flat_biome_map.clone() {
-                biomes.insert(biome);
-            }
+            // Sorted, so the features of a chunk are always run in the same order, which keeps
+            // their decorator seeds and thereby the generated ores stable for a world seed
+            let mut biomes = chunk.flat_biome_map.to_vec();
+            biomes.sort_unstable_by_key(|biome| biome.id);
+            biomes.dedup_by_key(|biome| biome.id);
 
             (chunk.x, chunk.z, chunk.bottom_y(), chunk.height(), biomes)
         };
@@ -946,19 +967,24 @@ impl ProtoChunk {
                 collector.generate_in_chunk(chunk, &mut random, random_config.seed as i64);
             }
 
-            let mut features_to_run = HashSet::new();
+            let mut features_to_run = Vec::new();
             for biome in &biomes_in_chunk {
                 if let Some(features_at_step) = biome.features.get(step) {
                     for feature_id in *features_at_step {
-                        features_to_run
-                            .insert(feature_id.strip_prefix("minecraft:").unwrap_or(feature_id));
+                        let feature_id =
+                            feature_id.strip_prefix("minecraft:").unwrap_or(feature_id);
+                        if !features_to_run.contains(&feature_id) {
+                            features_to_run.push(feature_id);
+                        }
                     }
                 }
             }
-            // let mut sorted_features: Vec<_> = features_to_run.into_iter().collect();
-            // sorted_features.sort_by_key(|f| f.registry_index());
 
             for (p, feature_id) in features_to_run.into_iter().enumerate() {
+                // Skipped after numbering, so the other features keep their decorator seeds
+                if replaces_feature(feature_id) {
+                    continue;
+                }
                 if let Some(feature) = PLACED_FEATURES.get(feature_id) {
                     let decorator_seed = get_decorator_seed(population_seed, p as u64, step as u64);
                     let mut random =
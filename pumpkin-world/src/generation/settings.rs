@@ -1,6 +1,11 @@
 use std::{collections::HashMap, sync::LazyLock};
 
+use pumpkin_config::world::GeneratorConfig;
+use pumpkin_data::Block;
 use pumpkin_data::dimension::Dimension;
+use pumpkin_data::noise_router::{
+    BaseNoiseRouters, END_BASE_NOISE_ROUTER, NETHER_BASE_NOISE_ROUTER, OVERWORLD_BASE_NOISE_ROUTER,
+};
 use pumpkin_util::include_json_static;
 use serde::Deserialize;
 
@@ -14,17 +19,31 @@ pub static GENERATION_SETTINGS: LazyLock<HashMap<GeneratorSetting, GenerationSet
     );
 
 pub fn gen_settings_from_dimension(dimension: &Dimension) -> &GenerationSettings {
-    let setting = if dimension == &Dimension::OVERWORLD {
-        GeneratorSetting::Overworld
+    GeneratorSetting::from_dimension(dimension).settings()
+}
+
+/// Returns the generator setting `dimension` uses, the one set in the config or its default.
+pub fn configured_setting(dimension: &Dimension, config: &GeneratorConfig) -> GeneratorSetting {
+    let name = if dimension == &Dimension::OVERWORLD {
+        &config.overworld
     } else if dimension == &Dimension::THE_NETHER {
-        GeneratorSetting::Nether
+        &config.the_nether
     } else {
-        GeneratorSetting::End
+        &config.the_end
     };
-    GENERATION_SETTINGS.get(&setting).unwrap()
+    match name.as_deref() {
+        Some(name) => GeneratorSetting::from_name(name).unwrap_or_else(|| {
+            log::warn!(
+                "Unknown generator setting {name} for {}, using the default",
+                dimension.minecraft_name
+            );
+            GeneratorSetting::from_dimension(dimension)
+        }),
+        None => GeneratorSetting::from_dimension(dimension),
+    }
 }
 
-#[derive(Deserialize, Hash, PartialEq, Eq)]
+#[derive(Deserialize, Hash, PartialEq, Eq, Clone, Copy, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum GeneratorSetting {
     Overworld,
@@ -34,6 +53,69 @@ pub enum GeneratorSetting {
     End,
     Caves,
     FloatingIslands,
+    /// Superflat layers instead of terrain noise, using the overworld settings otherwise
+    #[serde(skip)]
+    Flat,
+}
+
+impl GeneratorSetting {
+    /// The layers of a flat world from the bottom up, like vanilla's default superflat preset.
+    pub const FLAT_LAYERS: [(&'static Block, u8); 3] = [
+        (&Block::BEDROCK, 1),
+        (&Block::DIRT, 2),
+        (&Block::GRASS_BLOCK, 1),
+    ];
+
+    pub fn from_dimension(dimension: &Dimension) -> Self {
+        if dimension == &Dimension::OVERWORLD {
+            Self::Overworld
+        } else if dimension == &Dimension::THE_NETHER {
+            Self::Nether
+        } else {
+            Self::End
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.strip_prefix("minecraft:").unwrap_or(name) {
+            "overworld" => Self::Overworld,
+            "large_biomes" => Self::LargeBiomes,
+            "amplified" => Self::Amplified,
+            "nether" => Self::Nether,
+            "end" => Self::End,
+            "caves" => Self::Caves,
+            "floating_islands" => Self::FloatingIslands,
+            "flat" => Self::Flat,
+            _ => return None,
+        })
+    }
+
+    pub fn settings(self) -> &'static GenerationSettings {
+        let setting = if self == Self::Flat {
+            Self::Overworld
+        } else {
+            self
+        };
+        GENERATION_SETTINGS.get(&setting).unwrap()
+    }
+
+    /// The noise router of this setting. Only the routers of the three dimensions are extracted,
+    /// so the other settings use the router of the dimension they are based on.
+    pub fn base_router(self) -> BaseNoiseRouters {
+        match self {
+            Self::Overworld | Self::LargeBiomes | Self::Amplified | Self::Flat => {
+                OVERWORLD_BASE_NOISE_ROUTER
+            }
+            Self::Nether | Self::Caves => NETHER_BASE_NOISE_ROUTER,
+            Self::End | Self::FloatingIslands => END_BASE_NOISE_ROUTER,
+        }
+    }
+
+    /// Whether the terrain is shaped by noise, flat worlds are built from fixed layers instead.
+    /// They don't get structures or features either.
+    pub const fn populates_noise(self) -> bool {
+        !matches!(self, Self::Flat)
+    }
 }
 #[derive(Deserialize)]
 pub struct GenerationSettings {
@@ -99,3 +181,58 @@ impl GenerationShapeConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::world::GeneratorConfig;
+    use pumpkin_data::Block;
+    use pumpkin_data::dimension::Dimension;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{GeneratorSetting, configured_setting};
+    use crate::ProtoChunk;
+
+    #[test]
+    fn config_overrides_dimension_settings() {
+        let mut config = GeneratorConfig::default();
+        assert_eq!(
+            configured_setting(&Dimension::OVERWORLD, &config),
+            GeneratorSetting::Overworld
+        );
+        assert_eq!(
+            configured_setting(&Dimension::THE_NETHER, &config),
+            GeneratorSetting::Nether
+        );
+
+        config.overworld = Some("amplified".to_string());
+        config.the_end = Some("not_a_setting".to_string());
+        assert_eq!(
+            configured_setting(&Dimension::OVERWORLD, &config),
+            GeneratorSetting::Amplified
+        );
+        assert_eq!(
+            configured_setting(&Dimension::THE_END, &config),
+            GeneratorSetting::End
+        );
+
+        config.overworld = Some("flat".to_string());
+        let flat = configured_setting(&Dimension::OVERWORLD, &config);
+        assert!(!flat.populates_noise());
+        assert_eq!(flat.settings().sea_level, 63);
+
+        let mut chunk = ProtoChunk::new(
+            0,
+            0,
+            &Dimension::OVERWORLD,
+            flat.settings().default_block.get_state(),
+            0,
+        );
+        chunk.step_to_flat();
+        let block_at = |y| chunk.get_block_state(&Vector3::new(3, y, 7)).0;
+        assert_eq!(block_at(-64), Block::BEDROCK.default_state.id);
+        assert_eq!(block_at(-63), Block::DIRT.default_state.id);
+        assert_eq!(block_at(-62), Block::DIRT.default_state.id);
+        assert_eq!(block_at(-61), Block::GRASS_BLOCK.default_state.id);
+        assert_eq!(block_at(-60), Block::AIR.default_state.id);
+    }
+}
@@ -23,6 +23,7 @@ pub mod piece;
 pub mod placement;
 pub mod shiftable_piece;
 pub mod structures;
+pub mod template;
 
 #[derive(Deserialize)]
 pub struct StructureSet {
@@ -0,0 +1,615 @@
+use std::io::{Read, Seek};
+
+use pumpkin_data::Block;
+use pumpkin_nbt::{compound::NbtCompound, nbt_compress::read_gzip_compound_tag, tag::NbtTag};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use thiserror::Error;
+
+use crate::BlockStateId;
+
+#[derive(Error, Debug)]
+pub enum StructureTemplateError {
+    #[error("Failed to read structure NBT: {0}")]
+    Nbt(#[from] pumpkin_nbt::Error),
+    #[error("Missing or malformed structure tag `{0}`")]
+    MalformedTag(&'static str),
+    #[error("Unknown block `{0}` in structure palette")]
+    UnknownBlock(String),
+    #[error("Invalid properties for block `{0}` in structure palette")]
+    InvalidProperties(String),
+    #[error("Palette index {0} out of bounds")]
+    InvalidPaletteIndex(i32),
+}
+
+/// Rotation around the Y axis applied when placing a structure.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlockRotation {
+    #[default]
+    None,
+    Clockwise90,
+    Clockwise180,
+    CounterClockwise90,
+}
+
+impl BlockRotation {
+    /// Number of clockwise quarter turns.
+    #[must_use]
+    pub const fn quarter_turns(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Clockwise90 => 1,
+            Self::Clockwise180 => 2,
+            Self::CounterClockwise90 => 3,
+        }
+    }
+
+    #[must_use]
+    pub fn transform_pos(self, pos: BlockPos) -> BlockPos {
+        let Vector3 { x, y, z } = pos.0;
+        match self {
+            Self::None => pos,
+            Self::Clockwise90 => BlockPos::new(-z, y, x),
+            Self::Clockwise180 => BlockPos::new(-x, y, -z),
+            Self::CounterClockwise90 => BlockPos::new(z, y, -x),
+        }
+    }
+}
+
+/// Mirror applied when placing a structure, before the rotation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlockMirror {
+    #[default]
+    None,
+    /// Flips the Z axis (north <-> south)
+    LeftRight,
+    /// Flips the X axis (east <-> west)
+    FrontBack,
+}
+
+impl BlockMirror {
+    #[must_use]
+    pub fn transform_pos(self, pos: BlockPos) -> BlockPos {
+        let Vector3 { x, y, z } = pos.0;
+        match self {
+            Self::None => pos,
+            Self::LeftRight => BlockPos::new(x, y, -z),
+            Self::FrontBack => BlockPos::new(-x, y, z),
+        }
+    }
+}
+
+pub struct StructureBlockInfo {
+    /// Position relative to the structure origin
+    pub pos: BlockPos,
+    pub state: BlockStateId,
+    /// Block entity data, without the position
+    pub nbt: Option<NbtCompound>,
+}
+
+pub struct StructureEntityInfo {
+    pub pos: Vector3<f64>,
+    pub block_pos: BlockPos,
+    pub nbt: NbtCompound,
+}
+
+/// A structure in the vanilla `.nbt` structure format, as saved by structure blocks.
+pub struct StructureTemplate {
+    pub size: Vector3<i32>,
+    pub blocks: Vec<StructureBlockInfo>,
+    pub entities: Vec<StructureEntityInfo>,
+}
+
+impl StructureTemplate {
+    /// Reads a gzipped `.nbt` structure file.
+    pub fn read_gzip(input: impl Read + Seek) -> Result<Self, StructureTemplateError> {
+        Self::from_nbt(&read_gzip_compound_tag(input)?)
+    }
+
+    pub fn from_nbt(nbt: &NbtCompound) -> Result<Self, StructureTemplateError> {
+        let size = read_int_vec(nbt.get_list("size"))
+            .ok_or(StructureTemplateError::MalformedTag("size"))?;
+
+        // Structures with several palettes pick one at random in vanilla, we always use the first
+        let palette = match nbt.get_list("palette") {
+            Some(palette) => palette,
+            None => nbt
+                .get_list("palettes")
+                .and_then(|palettes| palettes.first())
+                .and_then(NbtTag::extract_list)
+                .ok_or(StructureTemplateError::MalformedTag("palette"))?,
+        };
+        let palette = palette
+            .iter()
+            .map(|entry| {
+                entry
+                    .extract_compound()
+                    .ok_or(StructureTemplateError::MalformedTag("palette"))
+                    .and_then(read_palette_entry)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut blocks = Vec::new();
+        for block in nbt.get_list("blocks").unwrap_or_default() {
+            let block = block
+                .extract_compound()
+                .ok_or(StructureTemplateError::MalformedTag("blocks"))?;
+            let pos = read_int_vec(block.get_list("pos"))
+                .ok_or(StructureTemplateError::MalformedTag("pos"))?;
+            let index = block
+                .get_int("state")
+                .ok_or(StructureTemplateError::MalformedTag("state"))?;
+            let state = *usize::try_from(index)
+                .ok()
+                .and_then(|index| palette.get(index))
+                .ok_or(StructureTemplateError::InvalidPaletteIndex(index))?;
+            blocks.push(StructureBlockInfo {
+                pos: BlockPos(pos),
+                state,
+                nbt: block.get_compound("nbt").cloned(),
+            });
+        }
+
+        let mut entities = Vec::new();
+        for entity in nbt.get_list("entities").unwrap_or_default() {
+            let entity = entity
+                .extract_compound()
+                .ok_or(StructureTemplateError::MalformedTag("entities"))?;
+            let pos = entity
+                .get_list("pos")
+                .and_then(|pos| {
+                    let pos = pos
+                        .iter()
+                        .map(NbtTag::extract_double)
+                        .collect::<Option<Vec<_>>>()?;
+                    (pos.len() == 3).then(|| Vector3::new(pos[0], pos[1], pos[2]))
+                })
+                .ok_or(StructureTemplateError::MalformedTag("pos"))?;
+            let block_pos = read_int_vec(entity.get_list("blockPos"))
+                .ok_or(StructureTemplateError::MalformedTag("blockPos"))?;
+            entities.push(StructureEntityInfo {
+                pos,
+                block_pos: BlockPos(block_pos),
+                nbt: entity.get_compound("nbt").cloned().unwrap_or_default(),
+            });
+        }
+
+        Ok(Self {
+            size,
+            blocks,
+            entities,
+        })
+    }
+
+    /// Writes the structure back into the vanilla `.nbt` layout.
+    #[must_use]
+    pub fn to_nbt(&self) -> NbtCompound {
+        let mut palette: Vec<BlockStateId> = Vec::new();
+        let mut blocks = Vec::with_capacity(self.blocks.len());
+        for block in &self.blocks {
+            let index = palette
+                .iter()
+                .position(|state| *state == block.state)
+                .unwrap_or_else(|| {
+                    palette.push(block.state);
+                    palette.len() - 1
+                });
+            let mut compound = NbtCompound::new();
+            compound.put_list("pos", write_int_vec(block.pos.0));
+            compound.put_int("state", index as i32);
+            if let Some(nbt) = &block.nbt {
+                compound.put_component("nbt", nbt.clone());
+            }
+            blocks.push(NbtTag::Compound(compound));
+        }
+
+        let palette = palette
+            .into_iter()
+            .map(|state| {
+                let block = Block::from_state_id(state);
+                let mut entry = NbtCompound::new();
+                entry.put_string("Name", format!("minecraft:{}", block.name));
+                if let Some(properties) = block.properties(state) {
+                    let mut compound = NbtCompound::new();
+                    for (key, value) in properties.to_props() {
+                        compound.put_string(key, value.to_string());
+                    }
+                    entry.put_component("Properties", compound);
+                }
+                NbtTag::Compound(entry)
+            })
+            .collect();
+
+        let entities = self
+            .entities
+            .iter()
+            .map(|entity| {
+                let mut compound = NbtCompound::new();
+                compound.put_list(
+                    "pos",
+                    vec![
+                        NbtTag::Double(entity.pos.x),
+                        NbtTag::Double(entity.pos.y),
+                        NbtTag::Double(entity.pos.z),
+                    ],
+                );
+                compound.put_list("blockPos", write_int_vec(entity.block_pos.0));
+                compound.put_component("nbt", entity.nbt.clone());
+                NbtTag::Compound(compound)
+            })
+            .collect();
+
+        let mut nbt = NbtCompound::new();
+        nbt.put_list("size", write_int_vec(self.size));
+        nbt.put_list("palette", palette);
+        nbt.put_list("blocks", blocks);
+        nbt.put_list("entities", entities);
+        nbt
+    }
+
+    /// Returns the position a block of the structure ends up at, relative to the placement origin.
+    #[must_use]
+    pub fn transform_pos(pos: BlockPos, rotation: BlockRotation, mirror: BlockMirror) -> BlockPos {
+        rotation.transform_pos(mirror.transform_pos(pos))
+    }
+}
+
+fn read_int_vec(list: Option<&[NbtTag]>) -> Option<Vector3<i32>> {
+    match list? {
+        [x, y, z] => Some(Vector3::new(
+            x.extract_int()?,
+            y.extract_int()?,
+            z.extract_int()?,
+        )),
+        _ => None,
+    }
+}
+
+fn write_int_vec(vec: Vector3<i32>) -> Vec<NbtTag> {
+    vec![NbtTag::Int(vec.x), NbtTag::Int(vec.y), NbtTag::Int(vec.z)]
+}
+
+fn read_palette_entry(entry: &NbtCompound) -> Result<BlockStateId, StructureTemplateError> {
+    let name = entry
+        .get_string("Name")
+        .ok_or(StructureTemplateError::MalformedTag("Name"))?;
+    let block = Block::from_name(name)
+        .ok_or_else(|| StructureTemplateError::UnknownBlock(name.to_string()))?;
+    let props = entry
+        .get_compound("Properties")
+        .map(|properties| {
+            properties
+                .child_tags
+                .iter()
+                .filter_map(|(key, value)| Some((key.as_str(), value.extract_string()?)))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    find_state(block, &props)
+        .ok_or_else(|| StructureTemplateError::InvalidProperties(name.to_string()))
+}
+
+/// Looks up the state of `block` with the given properties, unspecified properties keep their
+/// default value. Unlike `Block::from_properties` this never panics on unknown keys or values.
+fn find_state(block: &Block, props: &[(&str, &str)]) -> Option<BlockStateId> {
+    let Some(default) = block.properties(block.default_state.id) else {
+        return Some(block.default_state.id);
+    };
+    let wanted = default
+        .to_props()
+        .into_iter()
+        .map(|(key, default)| {
+            let value = props
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map_or(default, |(_, value)| *value);
+            (key, value)
+        })
+        .collect::<Vec<_>>();
+    block.states.iter().map(|state| state.id).find(|id| {
+        block
+            .properties(*id)
+            .is_some_and(|properties| properties.to_props() == wanted)
+    })
+}
+
+const HORIZONTAL: [&str; 4] = ["north", "east", "south", "west"];
+const ROTATION_VALUES: [&str; 16] = [
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15",
+];
+
+fn rotate_direction(direction: &'static str, rotation: BlockRotation) -> &'static str {
+    HORIZONTAL
+        .iter()
+        .position(|dir| *dir == direction)
+        .map_or(direction, |index| {
+            HORIZONTAL[(index + rotation.quarter_turns() as usize) % 4]
+        })
+}
+
+fn mirror_direction(direction: &'static str, mirror: BlockMirror) -> &'static str {
+    match (mirror, direction) {
+        (BlockMirror::LeftRight, "north") => "south",
+        (BlockMirror::LeftRight, "south") => "north",
+        (BlockMirror::FrontBack, "east") => "west",
+        (BlockMirror::FrontBack, "west") => "east",
+        _ => direction,
+    }
+}
+
+fn swap_left_right(value: &'static str) -> &'static str {
+    match value {
+        "left" => "right",
+        "right" => "left",
+        "inner_left" => "inner_right",
+        "inner_right" => "inner_left",
+        "outer_left" => "outer_right",
+        "outer_right" => "outer_left",
+        _ => value,
+    }
+}
+
+const RAIL_SHAPES: [&str; 10] = [
+    "north_south",
+    "east_west",
+    "ascending_east",
+    "ascending_west",
+    "ascending_north",
+    "ascending_south",
+    "south_east",
+    "south_west",
+    "north_west",
+    "north_east",
+];
+
+fn horizontal_direction(name: &str) -> Option<&'static str> {
+    HORIZONTAL
+        .iter()
+        .copied()
+        .find(|direction| *direction == name)
+}
+
+/// Transforms rail shapes such as `ascending_east` or `north_west`.
+fn transform_rail_shape(
+    shape: &'static str,
+    transform: impl Fn(&'static str) -> &'static str,
+) -> &'static str {
+    if let Some(direction) = shape
+        .strip_prefix("ascending_")
+        .and_then(horizontal_direction)
+    {
+        let direction = transform(direction);
+        return RAIL_SHAPES
+            .iter()
+            .copied()
+            .find(|candidate| candidate.strip_prefix("ascending_") == Some(direction))
+            .unwrap_or(shape);
+    }
+    let Some((first, second)) = shape.split_once('_').and_then(|(first, second)| {
+        Some((horizontal_direction(first)?, horizontal_direction(second)?))
+    }) else {
+        return shape;
+    };
+    let (first, second) = (transform(first), transform(second));
+    RAIL_SHAPES
+        .iter()
+        .copied()
+        .find(|candidate| {
+            candidate
+                .split_once('_')
+                .is_some_and(|pair| pair == (first, second) || pair == (second, first))
+        })
+        .unwrap_or(shape)
+}
+
+/// Mirrors and then rotates a block state, remapping every direction dependent property.
+#[must_use]
+pub fn transform_state(
+    state: BlockStateId,
+    rotation: BlockRotation,
+    mirror: BlockMirror,
+) -> BlockStateId {
+    if rotation == BlockRotation::None && mirror == BlockMirror::None {
+        return state;
+    }
+    let block = Block::from_state_id(state);
+    let Some(properties) = block.properties(state) else {
+        return state;
+    };
+    let props = properties.to_props();
+    let transform =
+        |direction: &'static str| rotate_direction(mirror_direction(direction, mirror), rotation);
+
+    // Stairs only swap their shape if the mirror flips the direction they are facing
+    let facing = props
+        .iter()
+        .find(|(key, _)| *key == "facing")
+        .map(|(_, value)| *value);
+    let flips_facing = match mirror {
+        BlockMirror::None => false,
+        BlockMirror::LeftRight => matches!(facing, Some("north" | "south")),
+        BlockMirror::FrontBack => matches!(facing, Some("east" | "west")),
+    };
+
+    let mut transformed = Vec::with_capacity(props.len());
+    for &(key, value) in &props {
+        let value = match key {
+            "facing" => transform(value),
+            "axis" if rotation.quarter_turns() % 2 == 1 => match value {
+                "x" => "z",
+                "z" => "x",
+                value => value,
+            },
+            "rotation" => {
+                let rotation_value = value.parse::<usize>().unwrap_or(0);
+                let rotation_value = match mirror {
+                    BlockMirror::None => rotation_value,
+                    BlockMirror::LeftRight => (24 - rotation_value) % 16,
+                    BlockMirror::FrontBack => (16 - rotation_value) % 16,
+                };
+                ROTATION_VALUES[(rotation_value + rotation.quarter_turns() as usize * 4) % 16]
+            }
+            "shape" if RAIL_SHAPES.contains(&value) => transform_rail_shape(value, transform),
+            "shape" if flips_facing => swap_left_right(value),
+            // Doors and chests always swap sides when mirrored
+            "type" | "hinge" if mirror != BlockMirror::None => swap_left_right(value),
+            _ => value,
+        };
+        // Connection properties (fences, walls, ...) are keyed by direction
+        let key = match key {
+            "north" | "east" | "south" | "west" => transform(key),
+            key => key,
+        };
+        transformed.push((key, value));
+    }
+
+    find_state(block, &transformed).unwrap_or(state)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use pumpkin_data::{
+        Block,
+        block_properties::{BlockProperties, HorizontalFacing, OakStairsLikeProperties},
+    };
+    use pumpkin_nbt::{
+        compound::NbtCompound, nbt_compress::write_gzip_compound_tag_to_bytes, tag::NbtTag,
+    };
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::{BlockMirror, BlockRotation, StructureTemplate, transform_state};
+
+    fn palette_entry(name: &str, props: &[(&str, &str)]) -> NbtTag {
+        let mut entry = NbtCompound::new();
+        entry.put_string("Name", name.to_string());
+        if !props.is_empty() {
+            let mut properties = NbtCompound::new();
+            for (key, value) in props {
+                properties.put_string(key, (*value).to_string());
+            }
+            entry.put_component("Properties", properties);
+        }
+        NbtTag::Compound(entry)
+    }
+
+    fn block_entry(pos: [i32; 3], state: i32, nbt: Option<NbtCompound>) -> NbtTag {
+        let mut block = NbtCompound::new();
+        block.put_list("pos", pos.into_iter().map(NbtTag::Int).collect());
+        block.put_int("state", state);
+        if let Some(nbt) = nbt {
+            block.put_component("nbt", nbt);
+        }
+        NbtTag::Compound(block)
+    }
+
+    fn small_structure() -> NbtCompound {
+        let mut chest = NbtCompound::new();
+        chest.put_string("id", "minecraft:chest".to_string());
+
+        let mut nbt = NbtCompound::new();
+        nbt.put_int("DataVersion", 4440);
+        nbt.put_list("size", vec![NbtTag::Int(2), NbtTag::Int(1), NbtTag::Int(2)]);
+        nbt.put_list(
+            "palette",
+            vec![
+                palette_entry("minecraft:stone", &[]),
+                palette_entry(
+                    "minecraft:oak_stairs",
+                    &[
+                        ("facing", "north"),
+                        ("half", "bottom"),
+                        ("shape", "straight"),
+                    ],
+                ),
+                palette_entry("minecraft:chest", &[("facing", "east")]),
+            ],
+        );
+        nbt.put_list(
+            "blocks",
+            vec![
+                block_entry([0, 0, 0], 0, None),
+                block_entry([1, 0, 0], 1, None),
+                block_entry([1, 0, 1], 2, Some(chest)),
+            ],
+        );
+        nbt.put_list("entities", vec![]);
+        nbt
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes = write_gzip_compound_tag_to_bytes(&small_structure()).unwrap();
+        let structure = StructureTemplate::read_gzip(Cursor::new(bytes)).unwrap();
+
+        assert_eq!(structure.size, Vector3::new(2, 1, 2));
+        assert_eq!(structure.blocks.len(), 3);
+        assert_eq!(structure.blocks[0].state, Block::STONE.default_state.id);
+
+        let stairs = &structure.blocks[1];
+        assert_eq!(stairs.pos, BlockPos::new(1, 0, 0));
+        assert_eq!(Block::from_state_id(stairs.state), &Block::OAK_STAIRS);
+        let props = OakStairsLikeProperties::from_state_id(stairs.state, &Block::OAK_STAIRS);
+        assert_eq!(props.facing, HorizontalFacing::North);
+
+        let chest = &structure.blocks[2];
+        assert_eq!(Block::from_state_id(chest.state), &Block::CHEST);
+        assert_eq!(
+            chest.nbt.as_ref().and_then(|nbt| nbt.get_string("id")),
+            Some("minecraft:chest")
+        );
+
+        // Writing it out again must result in the same states
+        let reread = StructureTemplate::from_nbt(&structure.to_nbt()).unwrap();
+        let states = |structure: &StructureTemplate| {
+            structure
+                .blocks
+                .iter()
+                .map(|block| (block.pos, block.state))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(states(&structure), states(&reread));
+    }
+
+    #[test]
+    fn rotate_and_mirror_states() {
+        let structure = StructureTemplate::from_nbt(&small_structure()).unwrap();
+        let stairs = structure.blocks[1].state;
+
+        let facing =
+            |state| OakStairsLikeProperties::from_state_id(state, &Block::OAK_STAIRS).facing;
+        assert_eq!(
+            facing(transform_state(
+                stairs,
+                BlockRotation::Clockwise90,
+                BlockMirror::None
+            )),
+            HorizontalFacing::East
+        );
+        assert_eq!(
+            facing(transform_state(
+                stairs,
+                BlockRotation::None,
+                BlockMirror::LeftRight
+            )),
+            HorizontalFacing::South
+        );
+        assert_eq!(
+            facing(transform_state(
+                stairs,
+                BlockRotation::CounterClockwise90,
+                BlockMirror::LeftRight
+            )),
+            HorizontalFacing::East
+        );
+
+        assert_eq!(
+            StructureTemplate::transform_pos(
+                BlockPos::new(1, 0, 0),
+                BlockRotation::Clockwise90,
+                BlockMirror::None
+            ),
+            BlockPos::new(0, 0, 1)
+        );
+    }
+}
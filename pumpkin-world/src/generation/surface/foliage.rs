@@ -0,0 +1,351 @@
+use pumpkin_data::{Block, chunk::Biome, tag, tag::Taggable};
+use pumpkin_util::{
+    math::vector3::Vector3,
+    random::{RandomGenerator, RandomImpl, get_decorator_seed, xoroshiro128::Xoroshiro},
+};
+
+use crate::generation::{
+    GlobalRandomConfig,
+    positions::chunk_pos::{start_block_x, start_block_z},
+    proto_chunk::ProtoChunk,
+};
+
+/// Keeps the foliage random independent of the feature randoms, which use the step as salt.
+const FOLIAGE_SALT: u64 = 0x666f_6c69;
+
+/// Chances per surface column to place each kind of foliage.
+struct SurfaceFoliage {
+    grass: f32,
+    fern: f32,
+    flower: f32,
+    dead_bush: f32,
+    flowers: &'static [&'static Block],
+}
+
+const PLAINS_FLOWERS: &[&Block] = &[
+    &Block::DANDELION,
+    &Block::POPPY,
+    &Block::AZURE_BLUET,
+    &Block::OXEYE_DAISY,
+    &Block::CORNFLOWER,
+    &Block::RED_TULIP,
+    &Block::ORANGE_TULIP,
+    &Block::WHITE_TULIP,
+    &Block::PINK_TULIP,
+];
+const FOREST_FLOWERS: &[&Block] = &[
+    &Block::DANDELION,
+    &Block::POPPY,
+    &Block::LILY_OF_THE_VALLEY,
+    &Block::ALLIUM,
+];
+const SWAMP_FLOWERS: &[&Block] = &[&Block::BLUE_ORCHID];
+const DEFAULT_FLOWERS: &[&Block] = &[&Block::DANDELION, &Block::POPPY];
+
+const PLAINS: SurfaceFoliage = SurfaceFoliage {
+    grass: 0.25,
+    fern: 0.0,
+    flower: 0.02,
+    dead_bush: 0.0,
+    flowers: PLAINS_FLOWERS,
+};
+const FOREST: SurfaceFoliage = SurfaceFoliage {
+    grass: 0.1,
+    fern: 0.0,
+    flower: 0.02,
+    dead_bush: 0.0,
+    flowers: FOREST_FLOWERS,
+};
+const FLOWER_FOREST: SurfaceFoliage = SurfaceFoliage {
+    grass: 0.05,
+    fern: 0.0,
+    flower: 0.15,
+    dead_bush: 0.0,
+    flowers: PLAINS_FLOWERS,
+};
+const TAIGA: SurfaceFoliage = SurfaceFoliage {
+    grass: 0.05,
+    fern: 0.1,
+    flower: 0.005,
+    dead_bush: 0.0,
+    flowers: DEFAULT_FLOWERS,
+};
+const JUNGLE: SurfaceFoliage = SurfaceFoliage {
+    grass: 0.2,
+    fern: 0.1,
+    flower: 0.005,
+    dead_bush: 0.0,
+    flowers: DEFAULT_FLOWERS,
+};
+const SAVANNA: SurfaceFoliage = SurfaceFoliage {
+    grass: 0.3,
+    fern: 0.0,
+    flower: 0.005,
+    dead_bush: 0.0,
+    flowers: DEFAULT_FLOWERS,
+};
+const SWAMP: SurfaceFoliage = SurfaceFoliage {
+    grass: 0.05,
+    fern: 0.0,
+    flower: 0.01,
+    dead_bush: 0.0,
+    flowers: SWAMP_FLOWERS,
+};
+const DESERT: SurfaceFoliage = SurfaceFoliage {
+    grass: 0.0,
+    fern: 0.0,
+    flower: 0.0,
+    dead_bush: 0.01,
+    flowers: &[],
+};
+const BADLANDS: SurfaceFoliage = SurfaceFoliage {
+    grass: 0.0,
+    fern: 0.0,
+    flower: 0.0,
+    dead_bush: 0.02,
+    flowers: &[],
+};
+const DEFAULT: SurfaceFoliage = SurfaceFoliage {
+    grass: 0.03,
+    fern: 0.0,
+    flower: 0.005,
+    dead_bush: 0.0,
+    flowers: DEFAULT_FLOWERS,
+};
+
+/// Whether the vegetal placed feature is replaced by [`place_foliage`], the feature step skips
+/// those so the foliage isn't placed twice.
+#[must_use]
+pub fn replaces_feature(feature_id: &str) -> bool {
+    feature_id.starts_with("patch_grass_")
+        || matches!(
+            feature_id,
+            "patch_dead_bush"
+                | "patch_dead_bush_2"
+                | "patch_dead_bush_badlands"
+                | "flower_default"
+                | "flower_plains"
+                | "flower_meadow"
+                | "flower_forest_flowers"
+                | "flower_flower_forest"
+                | "flower_swamp"
+        )
+}
+
+fn foliage_for(biome: &Biome) -> &'static SurfaceFoliage {
+    let is_any = |biomes: &[&Biome]| biomes.contains(&biome);
+    if is_any(&[&Biome::PLAINS, &Biome::SUNFLOWER_PLAINS, &Biome::MEADOW]) {
+        &PLAINS
+    } else if biome == &Biome::FLOWER_FOREST {
+        &FLOWER_FOREST
+    } else if is_any(&[
+        &Biome::FOREST,
+        &Biome::BIRCH_FOREST,
+        &Biome::OLD_GROWTH_BIRCH_FOREST,
+        &Biome::DARK_FOREST,
+    ]) {
+        &FOREST
+    } else if is_any(&[
+        &Biome::TAIGA,
+        &Biome::OLD_GROWTH_PINE_TAIGA,
+        &Biome::OLD_GROWTH_SPRUCE_TAIGA,
+    ]) {
+        &TAIGA
+    } else if is_any(&[&Biome::JUNGLE, &Biome::SPARSE_JUNGLE, &Biome::BAMBOO_JUNGLE]) {
+        &JUNGLE
+    } else if is_any(&[
+        &Biome::SAVANNA,
+        &Biome::SAVANNA_PLATEAU,
+        &Biome::WINDSWEPT_SAVANNA,
+    ]) {
+        &SAVANNA
+    } else if biome == &Biome::SWAMP {
+        &SWAMP
+    } else if biome == &Biome::DESERT {
+        &DESERT
+    } else if is_any(&[
+        &Biome::BADLANDS,
+        &Biome::ERODED_BADLANDS,
+        &Biome::WOODED_BADLANDS,
+    ]) {
+        &BADLANDS
+    } else {
+        &DEFAULT
+    }
+}
+
+/// Scatters biome dependent grass, ferns, flowers and dead bushes on top of the surface, in place
+/// of the vegetal features listed in [`replaces_feature`].
+///
+/// The random is derived from the world seed and the chunk position, so a chunk always gets
+/// the same foliage.
+pub fn place_foliage(chunk: &mut ProtoChunk, random_config: &GlobalRandomConfig) {
+    let start_x = start_block_x(chunk.x);
+    let start_z = start_block_z(chunk.z);
+    let population_seed = Xoroshiro::get_population_seed(random_config.seed, start_x, start_z);
+    let mut random = RandomGenerator::Xoroshiro(Xoroshiro::from_seed(get_decorator_seed(
+        population_seed,
+        FOLIAGE_SALT,
+        0,
+    )));
+
+    for local_x in 0..16 {
+        for local_z in 0..16 {
+            let x = start_x + local_x;
+            let z = start_z + local_z;
+            // Always roll, so one column never shifts the foliage of the following ones
+            let roll = random.next_f32();
+            let flower_index = random.next_bounded_i32(16) as usize;
+
+            let y = chunk.top_block_height_exclusive(local_x, local_z);
+            if !chunk
+                .get_block_state(&Vector3::new(x, y, z))
+                .to_state()
+                .is_air()
+            {
+                continue;
+            }
+            let ground = chunk.get_block_state(&Vector3::new(x, y - 1, z)).to_block();
+            let foliage = foliage_for(chunk.get_biome_for_terrain_gen(x, y, z));
+
+            let on_dirt = ground.has_tag(&tag::Block::MINECRAFT_DIRT);
+            let grass = foliage.grass;
+            let fern = grass + foliage.fern;
+            let flower = fern + foliage.flower;
+            let dead_bush = flower + foliage.dead_bush;
+            let block = if roll < grass {
+                on_dirt.then_some(&Block::SHORT_GRASS)
+            } else if roll < fern {
+                on_dirt.then_some(&Block::FERN)
+            } else if roll < flower {
+                (on_dirt && !foliage.flowers.is_empty())
+                    .then(|| foliage.flowers[flower_index % foliage.flowers.len()])
+            } else if roll < dead_bush {
+                (on_dirt
+                    || ground.has_tag(&tag::Block::MINECRAFT_SAND)
+                    || ground.has_tag(&tag::Block::MINECRAFT_TERRACOTTA))
+                .then_some(&Block::DEAD_BUSH)
+            } else {
+                None
+            };
+
+            if let Some(block) = block {
+                chunk.set_block_state(x, y, z, block.default_state);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, BlockState, dimension::Dimension};
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+    use pumpkin_util::world_seed::Seed;
+
+    use crate::biome::hash_seed;
+    use crate::chunk_system::generate_chunk_with_features;
+    use crate::generation::{
+        GlobalRandomConfig, generator::VanillaGenerator, proto_chunk::ProtoChunk,
+        settings::GeneratorSetting,
+    };
+    use crate::world::{BlockAccessor, BlockRegistryExt};
+
+    use super::{DEFAULT_FLOWERS, FOREST_FLOWERS, PLAINS_FLOWERS, SWAMP_FLOWERS, place_foliage};
+
+    struct BlockRegistry;
+
+    impl BlockRegistryExt for BlockRegistry {
+        fn can_place_at(
+            &self,
+            _block: &Block,
+            _state: &BlockState,
+            _block_accessor: &dyn BlockAccessor,
+            _block_pos: &BlockPos,
+        ) -> bool {
+            true
+        }
+    }
+
+    fn plains_chunk(random_config: &GlobalRandomConfig) -> ProtoChunk {
+        // Biomes default to plains
+        let mut chunk = ProtoChunk::new(
+            0,
+            0,
+            &Dimension::OVERWORLD,
+            Block::STONE.default_state,
+            hash_seed(random_config.seed),
+        );
+        for x in 0..16 {
+            for z in 0..16 {
+                chunk.set_block_state(x, 64, z, Block::GRASS_BLOCK.default_state);
+            }
+        }
+        place_foliage(&mut chunk, random_config);
+        chunk
+    }
+
+    #[test]
+    fn same_seed_same_foliage() {
+        let random_config = GlobalRandomConfig::new(13579, false);
+        let first = plains_chunk(&random_config);
+        let second = plains_chunk(&random_config);
+
+        let mut placed = 0;
+        for x in 0..16 {
+            for z in 0..16 {
+                let pos = Vector3::new(x, 65, z);
+                let first_state = first.get_block_state(&pos).0;
+                assert_eq!(first_state, second.get_block_state(&pos).0);
+                if first_state != Block::AIR.default_state.id {
+                    placed += 1;
+                }
+            }
+        }
+        assert!(placed > 0, "plains should have some grass");
+    }
+
+    /// The positions and states of all foliage blocks in the chunk.
+    fn foliage_blocks(chunk: &ProtoChunk) -> Vec<(i32, i32, i32, u16)> {
+        let is_foliage = |block: &Block| {
+            [&Block::SHORT_GRASS, &Block::FERN, &Block::DEAD_BUSH].contains(&block)
+                || [
+                    PLAINS_FLOWERS,
+                    FOREST_FLOWERS,
+                    SWAMP_FLOWERS,
+                    DEFAULT_FLOWERS,
+                ]
+                .iter()
+                .any(|flowers| flowers.contains(&block))
+        };
+        let mut blocks = Vec::new();
+        for x in 0..16 {
+            for y in 0..i32::from(chunk.height()) {
+                for z in 0..16 {
+                    let state = chunk.get_block_state_raw(x, y, z);
+                    if is_foliage(Block::from_state_id(state)) {
+                        blocks.push((x, y, z, state));
+                    }
+                }
+            }
+        }
+        blocks
+    }
+
+    #[test]
+    fn generated_chunks_get_the_same_foliage() {
+        let generate = || {
+            let world_gen = VanillaGenerator::with_setting(
+                Seed(13579),
+                Dimension::OVERWORLD,
+                GeneratorSetting::Overworld,
+            );
+            foliage_blocks(&generate_chunk_with_features(
+                &world_gen,
+                &BlockRegistry,
+                0,
+                0,
+            ))
+        };
+        assert_eq!(generate(), generate());
+    }
+}
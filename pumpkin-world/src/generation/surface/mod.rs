@@ -25,6 +25,7 @@ use super::{
     y_offset::YOffset,
 };
 
+pub mod foliage;
 pub mod rule;
 pub mod terrain;
 
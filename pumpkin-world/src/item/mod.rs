@@ -1,8 +1,8 @@
 use pumpkin_data::data_component::DataComponent;
 use pumpkin_data::data_component::DataComponent::Enchantments;
 use pumpkin_data::data_component_impl::{
-    BlocksAttacksImpl, ConsumableImpl, DataComponentImpl, EnchantmentsImpl, IDSet,
-    MaxStackSizeImpl, ToolImpl, get, get_mut, read_data,
+    BlocksAttacksImpl, ConsumableImpl, DamageImpl, DataComponentImpl, EnchantmentsImpl, IDSet,
+    MaxDamageImpl, MaxStackSizeImpl, ToolImpl, get, get_mut, read_data,
 };
 use pumpkin_data::item::Item;
 use pumpkin_data::recipes::RecipeResultStruct;
@@ -196,6 +196,60 @@ impl ItemStack {
             ));
         }
     }
+
+    /// Returns the level of `enchantment` on this item, or 0 if it does not have it.
+    pub fn get_enchantment_level(&self, enchantment: &Enchantment) -> i32 {
+        self.get_data_component::<EnchantmentsImpl>()
+            .and_then(|data| {
+                data.enchantment
+                    .iter()
+                    .find(|(enc, _)| *enc == enchantment)
+                    .map(|(_, level)| *level)
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn is_damageable(&self) -> bool {
+        self.get_data_component::<MaxDamageImpl>().is_some()
+    }
+
+    /// Adds `amount` damage to a damageable item, returns true if the item broke.
+    pub fn damage_item(&mut self, amount: i32) -> bool {
+        let Some(max_damage) = self
+            .get_data_component::<MaxDamageImpl>()
+            .map(|data| data.max_damage)
+        else {
+            return false;
+        };
+        let damage = self
+            .get_data_component::<DamageImpl>()
+            .map_or(0, |data| data.damage)
+            + amount;
+        if damage >= max_damage {
+            self.decrement(1);
+            return true;
+        }
+        if let Some(data) = self.get_data_component_mut::<DamageImpl>() {
+            data.damage = damage;
+        } else {
+            self.patch
+                .push((DataComponent::Damage, Some(DamageImpl { damage }.to_dyn())));
+        }
+        false
+    }
+
+    /// How much durability attacking with this item costs, swords, tridents and maces lose 1
+    /// and other tools 2.
+    pub fn damage_per_attack(&self) -> i32 {
+        if self.is_sword() || self.item == &Item::TRIDENT || self.item == &Item::MACE {
+            1
+        } else if self.get_data_component::<ToolImpl>().is_some() {
+            2
+        } else {
+            0
+        }
+    }
+
     pub fn are_items_and_components_equal(&self, other: &Self) -> bool {
         if self.item != other.item || self.patch.len() != other.patch.len() {
             return false;
@@ -352,3 +406,30 @@ impl From<&RecipeResultStruct> for ItemStack {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::data_component_impl::DamageImpl;
+    use pumpkin_data::item::Item;
+
+    use super::ItemStack;
+
+    #[test]
+    fn tools_break_without_durability() {
+        let mut pickaxe = ItemStack::new(1, &Item::DIAMOND_PICKAXE);
+        assert!(!pickaxe.damage_item(1560));
+        assert_eq!(
+            pickaxe
+                .get_data_component::<DamageImpl>()
+                .map(|data| data.damage),
+            Some(1560)
+        );
+        assert!(pickaxe.damage_item(1));
+        assert!(pickaxe.is_empty());
+
+        let mut stone = ItemStack::new(1, &Item::STONE);
+        assert!(!stone.is_damageable());
+        assert!(!stone.damage_item(100));
+        assert_eq!(stone.item_count, 1);
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
         format::{anvil::AnvilChunkFile, linear::LinearFile},
         io::{Dirtiable, FileIO, LoadedData, file_manager::ChunkFileManager},
     },
-    generation::get_world_gen,
+    generation::{get_world_gen, settings::configured_setting},
     tick::{OrderedTick, ScheduledTick, TickPriority},
     world::BlockRegistryExt,
 };
@@ -16,7 +16,10 @@ use crossbeam::channel::Sender;
 use dashmap::{DashMap, Entry};
 use log::trace;
 use num_traits::Zero;
-use pumpkin_config::{chunk::ChunkConfig, world::LevelConfig};
+use pumpkin_config::{
+    chunk::ChunkConfig,
+    world::{ChunkGenerationConfig, LevelConfig},
+};
 use pumpkin_data::biome::Biome;
 use pumpkin_data::dimension::Dimension;
 use pumpkin_data::{Block, block_properties::has_random_ticks, fluid::Fluid};
@@ -26,13 +29,14 @@ use rand::{Rng, SeedableRng, rngs::SmallRng};
 use std::sync::Mutex;
 // use std::time::Duration;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::PathBuf,
     sync::{
         Arc,
-        atomic::{AtomicBool, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
     },
     thread,
+    time::Duration,
 };
 // use tokio::runtime::Handle;
 use tokio::time::Instant;
@@ -79,6 +83,8 @@ pub struct Level {
     entity_saver: Arc<dyn FileIO<Data = SyncEntityChunk>>,
 
     pub world_gen: Arc<VanillaGenerator>,
+    /// Whether chunks which can't be read are generated again instead of being left empty.
+    pub regenerate_corrupt_chunks: bool,
 
     /// Tracks tasks associated with this world instance
     tasks: TaskTracker,
@@ -97,6 +103,72 @@ pub struct Level {
     pub level_channel: Arc<LevelChannel>,
     pub thread_tracker: Mutex<Vec<thread::JoinHandle<()>>>,
     pub chunk_listener: Arc<ChunkListener>,
+
+    /// Progress of the chunks being written while the level shuts down
+    pub save_progress: ChunkSaveProgress,
+    /// How many generation tasks are waiting to be scheduled, kept up to date by the schedule
+    /// thread.
+    pub generation_queue_len: AtomicUsize,
+}
+
+/// Counts the chunks queued for writing during shutdown, how many of them the chunk saver
+/// already went through and how many of those were dirty, so long saves can report their
+/// progress. The dirty count comes from the saver, which checks each chunk anyway.
+#[derive(Default)]
+pub struct ChunkSaveProgress {
+    total: AtomicUsize,
+    saved: AtomicUsize,
+    dirty: AtomicUsize,
+}
+
+impl ChunkSaveProgress {
+    pub fn add_total(&self, count: usize) {
+        self.total.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Records that the saver went through `count` chunks, `dirty` of which had to be updated.
+    pub fn add_saved(&self, count: usize, dirty: usize) {
+        self.saved.fetch_add(count, Ordering::Relaxed);
+        self.dirty.fetch_add(dirty, Ordering::Relaxed);
+    }
+
+    /// Returns `(saved, total)` chunk counts.
+    pub fn get(&self) -> (usize, usize) {
+        (
+            self.saved.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Returns how many of the saved chunks were dirty.
+    pub fn dirty(&self) -> usize {
+        self.dirty.load(Ordering::Relaxed)
+    }
+
+    /// Drives `future` to completion, calling `report` with `(saved, total)` every `interval`
+    /// while there are chunks that have not been saved yet.
+    pub async fn report_while<F: Future>(
+        &self,
+        future: F,
+        interval: Duration,
+        mut report: impl FnMut(usize, usize),
+    ) -> F::Output {
+        let mut ticker = tokio::time::interval(interval);
+        // The first tick completes immediately, nothing has been saved yet
+        ticker.tick().await;
+        tokio::pin!(future);
+        loop {
+            select! {
+                output = &mut future => return output,
+                _ = ticker.tick() => {
+                    let (saved, total) = self.get();
+                    if total > 0 && saved < total {
+                        report(saved, total);
+                    }
+                }
+            }
+        }
+    }
 }
 
 pub struct TickData {
@@ -151,7 +223,8 @@ impl Level {
 
         // TODO: Load info correctly based on world format type
         let seed = Seed(seed as u64);
-        let world_gen = get_world_gen(seed, dimension).into();
+        let setting = configured_setting(&dimension, &level_config.generator);
+        let world_gen = get_world_gen(seed, dimension, setting).into();
 
         let chunk_saver: Arc<dyn FileIO<Data = SyncChunk>> = match &level_config.chunk {
             ChunkConfig::Linear(chunk_config) => Arc::new(
@@ -181,6 +254,7 @@ impl Level {
             seed,
             block_registry,
             world_gen,
+            regenerate_corrupt_chunks: level_config.regenerate_corrupt_chunks,
             level_folder,
             chunk_saver,
             entity_saver,
@@ -201,13 +275,17 @@ impl Level {
             level_channel: level_channel.clone(),
             thread_tracker,
             chunk_listener: listener.clone(),
+            save_progress: ChunkSaveProgress::default(),
+            generation_queue_len: AtomicUsize::new(0),
         });
 
-        let num_threads = num_cpus::get().saturating_sub(2).max(1);
+        let (num_threads, pending_tasks) =
+            generation_workers(&level_config.chunk_generation, num_cpus::get());
 
         GenerationSchedule::create(
             4,
             num_threads,
+            pending_tasks,
             level_ref.clone(),
             level_channel,
             listener,
@@ -365,6 +443,59 @@ impl Level {
         self.write_entity_chunks(chunks_to_write).await;
     }
 
+    /// Writes every loaded chunk and entity chunk to disk while keeping them loaded.
+    ///
+    /// Only dirty chunks are actually written, see [`ChunkFileManager`]. Loaded chunks are
+    /// watched, so they have to be written right away instead of when they are unwatched.
+    pub async fn save_chunks(&self) {
+        let chunks_to_write = self
+            .loaded_chunks
+            .iter()
+            .map(|chunk| (*chunk.key(), chunk.value().clone()))
+            .collect::<Vec<_>>();
+        if let Err(error) = self
+            .chunk_saver
+            .save_chunks_now(&self.level_folder, chunks_to_write)
+            .await
+        {
+            log::error!("Failed writing Chunk to disk {error}");
+        }
+
+        let entity_chunks_to_write = self
+            .loaded_entity_chunks
+            .iter()
+            .map(|chunk| (*chunk.key(), chunk.value().clone()))
+            .collect::<Vec<_>>();
+        if let Err(error) = self
+            .entity_saver
+            .save_chunks_now(&self.level_folder, entity_chunks_to_write)
+            .await
+        {
+            log::error!("Failed writing entity Chunk to disk {error}");
+        }
+    }
+
+    /// Writes a single loaded chunk to disk right away, without waiting for the next autosave.
+    ///
+    /// Chunks without unsaved changes are skipped. The chunk is marked as clean while it is
+    /// written, so the next autosave doesn't write it again.
+    pub async fn save_chunk(&self, position: Vector2<i32>) {
+        let Some(chunk) = self.try_get_chunk(&position) else {
+            return;
+        };
+        if !chunk.read().await.is_dirty() {
+            return;
+        }
+
+        if let Err(error) = self
+            .chunk_saver
+            .save_chunks_now(&self.level_folder, vec![(position, chunk)])
+            .await
+        {
+            log::error!("Failed writing Chunk to disk {error}");
+        }
+    }
+
     pub fn loaded_chunk_count(&self) -> usize {
         self.loaded_chunks.len()
     }
@@ -492,20 +623,23 @@ impl Level {
     }
 
     // Gets random ticks, block ticks and fluid ticks
-    pub async fn get_tick_data(&self) -> TickData {
+    /// Steps the scheduled ticks and picks the random ticks of the loaded chunks in
+    /// `simulated_chunks`, the chunks outside of it are not ticked.
+    pub async fn get_tick_data(&self, simulated_chunks: &HashSet<Vector2<i32>>) -> TickData {
+        let chunks = self
+            .loaded_chunks
+            .iter()
+            .filter(|x| simulated_chunks.contains(x.key()))
+            .map(|x| x.value().clone())
+            .collect::<Vec<_>>();
         let mut ticks = TickData {
             block_ticks: Vec::new(),
             fluid_ticks: Vec::new(),
-            random_ticks: Vec::with_capacity(self.loaded_chunks.len() * 3 * 16 * 16),
+            random_ticks: Vec::with_capacity(chunks.len() * 3 * 16 * 16),
             block_entities: Vec::new(),
         };
 
         let mut rng = SmallRng::from_rng(&mut rand::rng());
-        let chunks = self
-            .loaded_chunks
-            .iter()
-            .map(|x| x.value().clone())
-            .collect::<Vec<_>>();
         for chunk in chunks {
             let mut chunk = chunk.write().await;
             ticks.block_ticks.append(&mut chunk.block_ticks.step_tick());
@@ -755,6 +889,29 @@ impl Level {
 
         RawBlockState(id)
     }
+    /// Reads all block states between `min` and `max` (inclusive), locking every chunk only once
+    /// instead of once per block.
+    pub async fn get_block_states_in_box(
+        self: &Arc<Self>,
+        min: BlockPos,
+        max: BlockPos,
+    ) -> HashMap<BlockPos, RawBlockState> {
+        let mut states = HashMap::new();
+        for (chunk_coordinate, chunk_min, chunk_max) in split_box_by_chunk(min, max) {
+            let chunk = self.get_chunk(chunk_coordinate).await;
+            let chunk = chunk.read().await;
+            for position in BlockPos::iterate(chunk_min, chunk_max) {
+                let relative = position.chunk_relative_position();
+                let id = chunk
+                    .section
+                    .get_block_absolute_y(relative.x as usize, relative.y, relative.z as usize)
+                    .unwrap_or(Block::VOID_AIR.default_state.id);
+                states.insert(position, RawBlockState(id));
+            }
+        }
+        states
+    }
+
     pub async fn get_rough_biome(self: &Arc<Self>, position: &BlockPos) -> &'static Biome {
         let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
         let chunk = self.get_chunk(chunk_coordinate).await;
@@ -779,7 +936,7 @@ impl Level {
         let chunk = self.get_chunk(chunk_coordinate).await;
         let mut chunk = chunk.write().await;
 
-        let replaced_block_state_id = chunk.section.set_block_absolute_y(
+        let replaced_block_state_id = chunk.set_block_absolute_y(
             relative.x as usize,
             relative.y,
             relative.z as usize,
@@ -900,3 +1057,126 @@ impl Level {
         chunk.fluid_ticks.is_scheduled(*block_pos, fluid)
     }
 }
+
+/// The number of generation threads and how many tasks may wait for them, out of `cores`.
+#[must_use]
+pub fn generation_workers(config: &ChunkGenerationConfig, cores: usize) -> (usize, usize) {
+    let threads = match config.threads {
+        0 => cores.saturating_sub(2).max(1),
+        threads => threads as usize,
+    };
+    let pending_tasks = match config.max_pending_tasks {
+        0 => threads + 5,
+        tasks => tasks as usize,
+    };
+    (threads, pending_tasks)
+}
+
+/// Splits the blocks between `min` and `max` (inclusive) into the boxes that lie in the same
+/// chunk, returned together with the chunk they lie in.
+#[must_use]
+pub fn split_box_by_chunk(min: BlockPos, max: BlockPos) -> Vec<(Vector2<i32>, BlockPos, BlockPos)> {
+    let (min, max) = (
+        BlockPos::new(
+            min.0.x.min(max.0.x),
+            min.0.y.min(max.0.y),
+            min.0.z.min(max.0.z),
+        ),
+        BlockPos::new(
+            min.0.x.max(max.0.x),
+            min.0.y.max(max.0.y),
+            min.0.z.max(max.0.z),
+        ),
+    );
+    let (min_chunk, max_chunk) = (min.chunk_position(), max.chunk_position());
+    let mut boxes = Vec::new();
+    for chunk_x in min_chunk.x..=max_chunk.x {
+        for chunk_z in min_chunk.y..=max_chunk.y {
+            let chunk_min = BlockPos::new(
+                min.0.x.max(chunk_x * 16),
+                min.0.y,
+                min.0.z.max(chunk_z * 16),
+            );
+            let chunk_max = BlockPos::new(
+                max.0.x.min(chunk_x * 16 + 15),
+                max.0.y,
+                max.0.z.min(chunk_z * 16 + 15),
+            );
+            boxes.push((Vector2::new(chunk_x, chunk_z), chunk_min, chunk_max));
+        }
+    }
+    boxes
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use pumpkin_util::math::position::BlockPos;
+
+    use pumpkin_config::world::ChunkGenerationConfig;
+
+    use super::{ChunkSaveProgress, generation_workers, split_box_by_chunk};
+
+    #[tokio::test]
+    async fn save_progress_reports_before_completion() {
+        let progress = ChunkSaveProgress::default();
+        let mut reports = Vec::new();
+
+        progress
+            .report_while(
+                async {
+                    progress.add_total(4096);
+                    for _ in 0..4 {
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        progress.add_saved(1024, 256);
+                    }
+                },
+                Duration::from_millis(5),
+                |saved, total| reports.push((saved, total)),
+            )
+            .await;
+
+        assert!(!reports.is_empty());
+        assert!(
+            reports
+                .iter()
+                .all(|&(saved, total)| saved < total && total == 4096)
+        );
+        assert_eq!(progress.get(), (4096, 4096));
+        assert_eq!(progress.dirty(), 1024);
+    }
+
+    #[test]
+    fn boxes_are_split_at_chunk_borders() {
+        let boxes = split_box_by_chunk(BlockPos::new(10, 60, -5), BlockPos::new(19, 69, 4));
+        assert_eq!(boxes.len(), 4);
+        let blocks: usize = boxes
+            .iter()
+            .map(|(_, min, max)| BlockPos::iterate(*min, *max).count())
+            .sum();
+        assert_eq!(blocks, 1000);
+        for (chunk, min, max) in &boxes {
+            assert_eq!(min.chunk_position(), *chunk);
+            assert_eq!(max.chunk_position(), *chunk);
+        }
+
+        assert_eq!(
+            split_box_by_chunk(BlockPos::new(1, 0, 1), BlockPos::new(10, 9, 10)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn generation_workers_follow_the_config() {
+        let automatic = ChunkGenerationConfig::default();
+        assert_eq!(generation_workers(&automatic, 8), (6, 11));
+        assert_eq!(generation_workers(&automatic, 2), (1, 6));
+
+        let limited = ChunkGenerationConfig {
+            threads: 2,
+            max_pending_tasks: 1,
+        };
+        assert_eq!(generation_workers(&limited, 32), (2, 1));
+    }
+}
@@ -239,3 +239,68 @@ pub fn bench_create_and_populate_noise_with_surface(
         &mut surface_height_estimate_sampler,
     );
 }
+
+/// A SHA-256 hash of the block states of an overworld chunk generated through all stages up to
+/// its features, see [`chunk_system::generate_chunk_with_features`]. Generation is
+/// deterministic, so the hash only changes when the output of the world generator does, which
+/// makes it useful to pin down the generator in tests.
+pub fn generation_fingerprint(
+    seed: u64,
+    chunk_x: i32,
+    chunk_z: i32,
+    block_registry: &dyn world::BlockRegistryExt,
+) -> [u8; 32] {
+    use generation::generator::VanillaGenerator;
+    use pumpkin_util::world_seed::Seed;
+    use sha2::{Digest, Sha256};
+
+    let world_gen = VanillaGenerator::with_setting(
+        Seed(seed),
+        Dimension::OVERWORLD,
+        GeneratorSetting::Overworld,
+    );
+    let chunk =
+        chunk_system::generate_chunk_with_features(&world_gen, block_registry, chunk_x, chunk_z);
+
+    let mut hasher = Sha256::new();
+    for x in 0..i32::from(CHUNK_DIM) {
+        for y in 0..i32::from(chunk.height()) {
+            for z in 0..i32::from(CHUNK_DIM) {
+                hasher.update(chunk.get_block_state_raw(x, y, z).to_le_bytes());
+            }
+        }
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, BlockState};
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::generation_fingerprint;
+    use crate::world::{BlockAccessor, BlockRegistryExt};
+
+    struct BlockRegistry;
+
+    impl BlockRegistryExt for BlockRegistry {
+        fn can_place_at(
+            &self,
+            _block: &Block,
+            _state: &BlockState,
+            _block_accessor: &dyn BlockAccessor,
+            _block_pos: &BlockPos,
+        ) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn generation_is_deterministic() {
+        // The spawn chunk of seed 0, generated by two independent generators
+        let spawn = generation_fingerprint(0, 0, 0, &BlockRegistry);
+        assert_eq!(spawn, generation_fingerprint(0, 0, 0, &BlockRegistry));
+        assert_ne!(spawn, generation_fingerprint(1, 0, 0, &BlockRegistry));
+        assert_ne!(spawn, generation_fingerprint(0, 1, 0, &BlockRegistry));
+    }
+}
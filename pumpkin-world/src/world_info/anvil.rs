@@ -153,7 +153,9 @@ mod test {
 
     use crate::{
         global_path,
-        world_info::{DataPacks, LevelData, WorldGenSettings, WorldInfoError, WorldVersion},
+        world_info::{
+            DataPacks, DragonFightData, LevelData, WorldGenSettings, WorldInfoError, WorldVersion,
+        },
     };
 
     use super::{AnvilLevelInfo, LEVEL_DAT_FILE_NAME, LevelDat, WorldInfoReader, WorldInfoWriter};
@@ -200,6 +202,7 @@ mod test {
             day_time: 1727,
             difficulty: Difficulty::Normal,
             difficulty_locked: false,
+            dragon_fight: DragonFightData::default(),
             game_rules: GameRuleRegistry {
                 block_explosion_drop_decay: true,
                 command_block_output: true,
@@ -67,6 +67,10 @@ pub struct LevelData {
     pub difficulty_locked: bool,
     // TODO: DimensionData
 
+    // The state of the ender dragon fight in the end.
+    #[serde(default)]
+    pub dragon_fight: DragonFightData,
+
     // Gamerules
     pub game_rules: GameRuleRegistry,
 
@@ -194,6 +198,27 @@ impl WorldGenSettings {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct DragonFightData {
+    // True if the end has not been set up for the fight yet, the crystals on the pillars and the dragon get spawned when a player first enters it.
+    pub needs_state_scanning: bool,
+    // True if the current dragon was killed.
+    pub dragon_killed: bool,
+    // True if a dragon has ever been killed.
+    pub previously_killed: bool,
+}
+
+impl Default for DragonFightData {
+    fn default() -> Self {
+        Self {
+            needs_state_scanning: true,
+            dragon_killed: false,
+            previously_killed: false,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct WorldVersion {
@@ -240,6 +265,7 @@ impl LevelData {
             day_time: 0,
             difficulty: Difficulty::Normal,
             difficulty_locked: false,
+            dragon_fight: DragonFightData::default(),
             game_rules: GameRuleRegistry::default(),
             world_gen_settings: WorldGenSettings::new(seed),
             last_played: -1,
@@ -1,8 +1,24 @@
 use std::collections::{HashMap, HashSet};
 
+use pumpkin_data::{Block, entity::EntityType};
 use pumpkin_protocol::java::client::play::AdvancementProgressMapping;
 use pumpkin_util::resource_location::ResourceLocation;
-use pumpkin_world::advancement::{AdvancementProgress, AdvancementRequirements};
+use pumpkin_world::advancement::{
+    AdvancementProgress, AdvancementRequirements, AdvancementRewards,
+};
+use pumpkin_world::item::ItemStack;
+
+use crate::world::loot::{LootContextParameters, LootTableExt};
+
+/// The rewards of a completed advancement that still have to be given to the player.
+#[derive(Debug, Default)]
+pub struct GrantedRewards {
+    pub experience: i32,
+    /// The recipes that were not unlocked before.
+    pub recipes: Vec<ResourceLocation>,
+    /// The items rolled from the reward loot table.
+    pub items: Vec<ItemStack>,
+}
 
 #[derive(Debug)]
 pub struct PlayerAdvancementTracker {
@@ -12,6 +28,7 @@ pub struct PlayerAdvancementTracker {
     current_tab: Option<ResourceLocation>,
     needs_reset: bool,
     visible: HashSet<ResourceLocation>,
+    unlocked_recipes: HashSet<ResourceLocation>,
 }
 
 impl Default for PlayerAdvancementTracker {
@@ -30,6 +47,7 @@ impl PlayerAdvancementTracker {
             current_tab: None,
             needs_reset: true,
             visible: HashSet::new(),
+            unlocked_recipes: HashSet::new(),
         }
     }
 
@@ -175,6 +193,43 @@ impl PlayerAdvancementTracker {
         &self.visible
     }
 
+    /// Unlocks recipes, e.g. as an advancement reward, and returns the ones that were new.
+    pub fn unlock_recipes(&mut self, recipes: &[ResourceLocation]) -> Vec<ResourceLocation> {
+        recipes
+            .iter()
+            .filter(|recipe| self.unlocked_recipes.insert((*recipe).clone()))
+            .cloned()
+            .collect()
+    }
+
+    #[must_use]
+    pub fn is_recipe_unlocked(&self, recipe: &ResourceLocation) -> bool {
+        self.unlocked_recipes.contains(recipe)
+    }
+
+    pub fn unlocked_recipes(&self) -> impl Iterator<Item = &ResourceLocation> {
+        self.unlocked_recipes.iter()
+    }
+
+    pub fn load_recipes(&mut self, recipes: impl IntoIterator<Item = ResourceLocation>) {
+        self.unlocked_recipes = recipes.into_iter().collect();
+    }
+
+    /// Unlocks the reward recipes and rolls the reward loot of a completed advancement.
+    pub fn grant_rewards(&mut self, rewards: &AdvancementRewards) -> GrantedRewards {
+        let items = rewards.loot.as_ref().map_or_else(Vec::new, |loot| {
+            roll_reward_loot(loot).unwrap_or_else(|| {
+                log::warn!("Unknown advancement reward loot table {loot}");
+                Vec::new()
+            })
+        });
+        GrantedRewards {
+            experience: rewards.experience,
+            recipes: self.unlock_recipes(&rewards.recipes),
+            items,
+        }
+    }
+
     pub fn load_progress(&mut self, data: HashMap<ResourceLocation, AdvancementProgress>) {
         self.progress = data;
         self.completed.clear();
@@ -247,3 +302,70 @@ impl PlayerAdvancementTracker {
             .collect()
     }
 }
+
+/// Rolls the loot table `loot`. Only block and entity loot tables can be looked up by id so far.
+fn roll_reward_loot(loot: &ResourceLocation) -> Option<Vec<ItemStack>> {
+    if loot.namespace != "minecraft" {
+        return None;
+    }
+    let loot_table = if let Some(block) = loot.path.strip_prefix("blocks/") {
+        Block::from_name(block)?.loot_table.as_ref()
+    } else if let Some(entity) = loot.path.strip_prefix("entities/") {
+        EntityType::from_name(entity)?.loot_table.as_ref()
+    } else {
+        None
+    }?;
+    Some(loot_table.get_loot(LootContextParameters::default()))
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_util::resource_location::ResourceLocation;
+    use pumpkin_world::advancement::{Advancement, AdvancementCriterion, AdvancementRewards};
+
+    use super::PlayerAdvancementTracker;
+
+    #[test]
+    fn completing_an_advancement_grants_its_rewards() {
+        let id = ResourceLocation::vanilla("story/mine_stone");
+        let recipe = ResourceLocation::vanilla("stone_pickaxe");
+        let mut rewards = AdvancementRewards::with_experience(100);
+        rewards.recipes.push(recipe.clone());
+        rewards.loot = Some(ResourceLocation::vanilla("blocks/dirt"));
+        let advancement = Advancement::builder()
+            .criterion(
+                "get_stone",
+                AdvancementCriterion::new(ResourceLocation::vanilla("inventory_changed")),
+            )
+            .rewards(rewards)
+            .build();
+        let mut tracker = PlayerAdvancementTracker::new();
+
+        assert!(!tracker.is_completed(&id));
+        assert!(tracker.grant_criterion(&id, "get_stone", &advancement.requirements));
+        assert!(tracker.is_completed(&id));
+
+        let granted = tracker.grant_rewards(&advancement.rewards);
+        assert_eq!(granted.experience, 100);
+        assert_eq!(granted.recipes, vec![recipe.clone()]);
+        assert!(tracker.is_recipe_unlocked(&recipe));
+        assert_eq!(granted.items.len(), 1);
+        assert_eq!(granted.items[0].item.id, Item::DIRT.id);
+        // Recipes are only unlocked once
+        assert!(
+            tracker
+                .grant_rewards(&advancement.rewards)
+                .recipes
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn unknown_loot_tables_give_nothing() {
+        let mut rewards = AdvancementRewards::empty();
+        rewards.loot = Some(ResourceLocation::vanilla("chests/not_a_chest"));
+        let granted = PlayerAdvancementTracker::new().grant_rewards(&rewards);
+        assert!(granted.items.is_empty());
+    }
+}
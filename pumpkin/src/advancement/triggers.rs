@@ -3,10 +3,12 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use pumpkin_protocol::java::client::play::CRecipeBookAdd;
 use pumpkin_util::resource_location::ResourceLocation;
 use pumpkin_world::advancement::triggers;
 
 use crate::entity::player::Player;
+use crate::net::ClientPlatform;
 use crate::server::Server;
 
 #[derive(Debug, Clone)]
@@ -75,6 +77,7 @@ impl AdvancementTriggers {
                     
                     if conditions_met {
                         let mut tracker = player.advancement_tracker.lock().await;
+                        let was_done = tracker.is_completed(advancement_id);
                         let was_granted = tracker.grant_criterion(
                             advancement_id,
                             criterion_name,
@@ -85,7 +88,8 @@ impl AdvancementTriggers {
                             let is_done = tracker.is_completed(advancement_id);
                             drop(tracker);
                             
-                            if is_done {
+                            // Criteria granted after completion must not reward twice
+                            if is_done && !was_done {
                                 Self::grant_rewards(player, server, advancement_entry).await;
                                 Self::announce_completion(player, server, advancement_entry).await;
                             }
@@ -392,15 +396,44 @@ impl AdvancementTriggers {
         true
     }
 
-    async fn grant_rewards(
+    /// Gives the player the rewards of a newly completed advancement.
+    pub async fn grant_rewards(
         player: &Arc<Player>,
         _server: &Server,
         advancement: &Arc<pumpkin_world::advancement::AdvancementEntry>,
     ) {
         let rewards = &advancement.advancement.rewards;
-        
-        if rewards.experience > 0 {
-            player.add_experience_points(rewards.experience).await;
+        let granted = player
+            .advancement_tracker
+            .lock()
+            .await
+            .grant_rewards(rewards);
+
+        if granted.experience > 0 {
+            player.add_experience_points(granted.experience).await;
+        }
+
+        if !granted.recipes.is_empty()
+            && let ClientPlatform::Java(client) = &player.client
+        {
+            client
+                .enqueue_packet(&CRecipeBookAdd::add_recipes(granted.recipes))
+                .await;
+        }
+
+        if !granted.items.is_empty() {
+            for mut stack in granted.items {
+                player.inventory().insert_stack_anywhere(&mut stack).await;
+                if !stack.is_empty() {
+                    player.drop_item(stack).await;
+                }
+            }
+            player.trigger_inventory_changed().await;
+        }
+
+        // TODO: Functions can't be run yet
+        if let Some(function) = &rewards.function {
+            log::debug!("Advancement function reward {function} is not supported yet");
         }
     }
 
@@ -422,13 +455,12 @@ impl AdvancementTriggers {
                 let message = pumpkin_util::text::TextComponent::translate(
                     key,
                     [
-                        pumpkin_util::text::TextComponent::text(player_name.clone()),
+                        pumpkin_util::text::TextComponent::text(player_name),
                         display.title.clone(),
                     ],
                 );
                 
-                let sender = pumpkin_util::text::TextComponent::text(player_name);
-                player.world().broadcast_message(&message, &sender, 0, None).await;
+                player.world().broadcast_system_message(&message).await;
             }
         }
     }
@@ -3,7 +3,6 @@ use std::sync::Arc;
 use pumpkin_data::Block;
 use pumpkin_data::block_properties::BedPart;
 use pumpkin_data::block_properties::BlockProperties;
-use pumpkin_data::dimension::Dimension;
 use pumpkin_data::entity::EntityType;
 use pumpkin_data::tag::{RegistryKey, get_tag_values};
 use pumpkin_macros::pumpkin_block_from_tag;
@@ -26,6 +25,9 @@ use crate::world::World;
 
 type BedProperties = pumpkin_data::block_properties::WhiteBedLikeProperties;
 
+/// How strong the explosion of a bed used outside of the overworld is.
+pub const EXPLOSION_POWER: f32 = 5.0;
+
 const NO_SLEEP_IDS: &[u16] = &[
     EntityType::BLAZE.id,
     EntityType::BOGGED.id,
@@ -204,8 +206,8 @@ impl BlockBehaviour for BedBlock {
                 )
             };
 
-            // Explode if not in the overworld
-            if args.world.dimension != Dimension::OVERWORLD {
+            // Beds explode instead of setting the spawn point in the nether and the end
+            if args.world.dimension.bed_explodes {
                 args.world
                     .break_block(&bed_head_pos, None, BlockFlags::SKIP_DROPS)
                     .await;
@@ -214,7 +216,7 @@ impl BlockBehaviour for BedBlock {
                     .await;
 
                 args.world
-                    .explode(bed_head_pos.to_centered_f64(), 5.0)
+                    .explode_with_fire(bed_head_pos.to_centered_f64(), EXPLOSION_POWER)
                     .await;
 
                 return BlockActionResult::SuccessServer;
@@ -385,3 +387,15 @@ async fn can_sleep(world: &Arc<World>) -> bool {
 fn entity_prevents_sleep(entity: &Entity) -> bool {
     NO_SLEEP_IDS.contains(&entity.entity_type.id)
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::dimension::Dimension;
+
+    #[test]
+    fn beds_explode_outside_of_the_overworld() {
+        assert!(!Dimension::OVERWORLD.bed_explodes);
+        assert!(Dimension::THE_NETHER.bed_explodes);
+        assert!(Dimension::THE_END.bed_explodes);
+    }
+}
@@ -0,0 +1,235 @@
+use pumpkin_data::block_properties::{Attachment, Axis, BellLikeProperties, BlockProperties};
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::potion::Effect;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_data::{BlockDirection, HorizontalFacingExt, effect::StatusEffect, tag};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::tick::TickPriority;
+use pumpkin_world::world::BlockFlags;
+
+use super::redstone::block_receives_redstone_power;
+use crate::block::registry::BlockActionResult;
+use crate::block::{
+    BlockBehaviour, BlockFuture, BlockHitResult, NormalUseArgs, OnNeighborUpdateArgs,
+    OnScheduledTickArgs, OnSyncedBlockEventArgs,
+};
+use crate::world::World;
+
+/// Raiders this close to a ringing bell hear it and start glowing.
+pub const RAIDER_HEARING_DISTANCE: f64 = 48.0;
+/// How long raiders keep glowing after hearing a bell.
+pub const RAIDER_GLOW_TICKS: i32 = 60;
+/// The bell starts resonating a few ticks after it was hit, like in vanilla.
+const RESONATE_DELAY_TICKS: u8 = 5;
+
+#[pumpkin_block("minecraft:bell")]
+pub struct BellBlock;
+
+impl BellBlock {
+    /// The block event the client uses to swing the bell, its data is the hit direction.
+    pub const RING_EVENT_TYPE: u8 = 1;
+}
+
+impl BlockBehaviour for BellBlock {
+    fn normal_use<'a>(&'a self, args: NormalUseArgs<'a>) -> BlockFuture<'a, BlockActionResult> {
+        Box::pin(async move {
+            let state_id = args.world.get_block_state_id(args.position).await;
+            let props = BellLikeProperties::from_state_id(state_id, args.block);
+            if !is_point_on_bell(&props, args.hit) {
+                return BlockActionResult::Pass;
+            }
+            ring(args.world, args.position, Some(*args.hit.face)).await;
+            // TODO: player.incrementStat(Stats.BELL_RING);
+            BlockActionResult::Success
+        })
+    }
+
+    fn on_neighbor_update<'a>(&'a self, args: OnNeighborUpdateArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            let state_id = args.world.get_block_state_id(args.position).await;
+            let mut props = BellLikeProperties::from_state_id(state_id, args.block);
+            let powered = block_receives_redstone_power(args.world, args.position).await;
+            if props.powered == powered {
+                return;
+            }
+            if powered {
+                ring(args.world, args.position, None).await;
+            }
+            props.powered = powered;
+            args.world
+                .set_block_state(
+                    args.position,
+                    props.to_state_id(args.block),
+                    BlockFlags::NOTIFY_ALL,
+                )
+                .await;
+        })
+    }
+
+    fn on_synced_block_event<'a>(
+        &'a self,
+        args: OnSyncedBlockEventArgs<'a>,
+    ) -> BlockFuture<'a, bool> {
+        Box::pin(async move {
+            // The client swings the bell on its own
+            args.r#type == Self::RING_EVENT_TYPE
+        })
+    }
+
+    fn on_scheduled_tick<'a>(&'a self, args: OnScheduledTickArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            if glow_raiders(args.world, args.position).await {
+                args.world
+                    .play_block_sound(
+                        Sound::BlockBellResonate,
+                        SoundCategory::Blocks,
+                        *args.position,
+                    )
+                    .await;
+            }
+        })
+    }
+}
+
+/// Returns whether the hit landed on the bell itself rather than on its frame.
+#[must_use]
+pub fn is_point_on_bell(props: &BellLikeProperties, hit: &BlockHitResult) -> bool {
+    let axis = hit.face.to_axis();
+    if axis == Axis::Y || hit.cursor_pos.y > 0.8125 {
+        return false;
+    }
+    let facing_axis = props.facing.to_block_direction().to_axis();
+    match props.attachment {
+        Attachment::Floor => axis == facing_axis,
+        Attachment::SingleWall | Attachment::DoubleWall => axis != facing_axis,
+        Attachment::Ceiling => true,
+    }
+}
+
+/// Returns whether an entity of `entity_type` at `entity_pos` is a raider that hears the bell.
+#[must_use]
+pub fn hears_bell(bell: &BlockPos, entity_type: &EntityType, entity_pos: Vector3<f64>) -> bool {
+    tag::EntityType::MINECRAFT_RAIDERS
+        .1
+        .contains(&entity_type.id)
+        && entity_pos.squared_distance_to_vec(bell.to_centered_f64())
+            <= RAIDER_HEARING_DISTANCE * RAIDER_HEARING_DISTANCE
+}
+
+/// The effect a bell gives to the raiders which hear it.
+#[must_use]
+pub fn raider_glow_effect() -> Effect {
+    Effect {
+        effect_type: &StatusEffect::GLOWING,
+        duration: RAIDER_GLOW_TICKS,
+        amplifier: 0,
+        ambient: false,
+        show_particles: true,
+        show_icon: true,
+        blend: false,
+    }
+}
+
+/// Rings the bell at `position`, `direction` is the side it was hit from.
+///
+/// Without a direction, e.g. when rung by redstone, the bell swings towards its facing.
+pub async fn ring(world: &World, position: &BlockPos, direction: Option<BlockDirection>) {
+    let direction = match direction {
+        Some(direction) => direction,
+        None => {
+            let (block, state_id) = world.get_block_and_state_id(position).await;
+            BellLikeProperties::from_state_id(state_id, block)
+                .facing
+                .to_block_direction()
+        }
+    };
+    world
+        .add_synced_block_event(*position, BellBlock::RING_EVENT_TYPE, direction.to_index())
+        .await;
+    world
+        .play_sound_fine(
+            Sound::BlockBellUse,
+            SoundCategory::Blocks,
+            &position.to_centered_f64(),
+            2.0,
+            1.0,
+        )
+        .await;
+    let block = world.get_block(position).await;
+    world
+        .schedule_block_tick(block, *position, RESONATE_DELAY_TICKS, TickPriority::Normal)
+        .await;
+}
+
+/// Makes every raider that hears the bell at `position` glow, returns whether there were any.
+async fn glow_raiders(world: &World, position: &BlockPos) -> bool {
+    let mut heard = false;
+    for entity in world
+        .get_nearby_entities(position.to_centered_f64(), RAIDER_HEARING_DISTANCE)
+        .await
+        .into_values()
+    {
+        let base = entity.get_entity();
+        if !base.is_alive() || !hears_bell(position, base.entity_type, base.pos.load()) {
+            continue;
+        }
+        if let Some(living) = entity.get_living_entity() {
+            living.add_effect(raider_glow_effect()).await;
+            heard = true;
+        }
+    }
+    heard
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::block_properties::{
+        Attachment, BellLikeProperties, BlockProperties, HorizontalFacing,
+    };
+    use pumpkin_data::effect::StatusEffect;
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_data::{Block, BlockDirection};
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{RAIDER_GLOW_TICKS, hears_bell, is_point_on_bell, raider_glow_effect};
+    use crate::block::BlockHitResult;
+
+    #[test]
+    fn ringing_bell_makes_nearby_raiders_glow() {
+        let mut props = BellLikeProperties::default(&Block::BELL);
+        props.attachment = Attachment::Floor;
+        props.facing = HorizontalFacing::North;
+
+        let front = Vector3::new(0.5, 0.5, 0.0);
+        let hit = |face| BlockHitResult {
+            face,
+            cursor_pos: &front,
+        };
+        assert!(is_point_on_bell(&props, &hit(&BlockDirection::North)));
+        assert!(is_point_on_bell(&props, &hit(&BlockDirection::South)));
+        // The sides of a floor bell are its frame
+        assert!(!is_point_on_bell(&props, &hit(&BlockDirection::East)));
+        assert!(!is_point_on_bell(&props, &hit(&BlockDirection::Up)));
+        props.attachment = Attachment::Ceiling;
+        assert!(is_point_on_bell(&props, &hit(&BlockDirection::East)));
+
+        let bell = BlockPos::new(0, 64, 0);
+        let nearby = Vector3::new(10.0, 64.0, 10.0);
+        assert!(hears_bell(&bell, &EntityType::PILLAGER, nearby));
+        assert!(hears_bell(&bell, &EntityType::WITCH, nearby));
+        assert!(!hears_bell(&bell, &EntityType::ZOMBIE, nearby));
+        assert!(!hears_bell(&bell, &EntityType::VILLAGER, nearby));
+        assert!(!hears_bell(
+            &bell,
+            &EntityType::PILLAGER,
+            Vector3::new(60.0, 64.0, 0.0)
+        ));
+
+        let effect = raider_glow_effect();
+        assert!(effect.effect_type == &StatusEffect::GLOWING);
+        assert_eq!(effect.duration, RAIDER_GLOW_TICKS);
+    }
+}
@@ -27,6 +27,35 @@ use rand::{Rng, rng};
 #[pumpkin_block("minecraft:cake")]
 pub struct CakeBlock;
 
+/// Food restored by a single slice of cake.
+const SLICE_FOOD: u8 = 2;
+/// Saturation modifier of a single slice of cake.
+const SLICE_SATURATION_MODIFIER: f32 = 0.1;
+
+/// Returns the food level and saturation after eating a slice of cake, or `None` if the player
+/// is too full to eat.
+#[must_use]
+pub fn eat_slice(food_level: u8, saturation: f32) -> Option<(u8, f32)> {
+    if food_level >= 20 {
+        return None;
+    }
+    let food_level = 20.min(food_level + SLICE_FOOD);
+    let saturation = (saturation + f32::from(SLICE_FOOD) * SLICE_SATURATION_MODIFIER * 2.0)
+        .min(f32::from(food_level));
+    Some((food_level, saturation))
+}
+
+/// Returns the cake with one more bite taken, or `None` if the last slice was eaten.
+#[must_use]
+pub fn take_bite(state_id: BlockStateId, block: &Block) -> Option<BlockStateId> {
+    let mut properties = CakeLikeProperties::from_state_id(state_id, block);
+    if properties.bites == Integer0To6::L6 {
+        return None;
+    }
+    properties.bites = Integer0To6::from_index(properties.bites.to_index() + 1);
+    Some(properties.to_state_id(block))
+}
+
 impl CakeBlock {
     pub async fn consume_if_hungry(
         world: &Arc<World>,
@@ -37,47 +66,25 @@ impl CakeBlock {
     ) -> BlockActionResult {
         match player.gamemode.load() {
             GameMode::Survival | GameMode::Adventure => {
-                let hunger_level = player.hunger_manager.level.load();
-                if hunger_level >= 20 {
+                let hunger = &player.hunger_manager;
+                let Some((level, saturation)) =
+                    eat_slice(hunger.level.load(), hunger.saturation.load())
+                else {
                     return BlockActionResult::Pass;
-                }
-                player.hunger_manager.level.store(20.min(hunger_level + 2));
-                player
-                    .hunger_manager
-                    .saturation
-                    .store(player.hunger_manager.saturation.load() + 0.4);
+                };
+                hunger.level.store(level);
+                hunger.saturation.store(saturation);
                 player.send_health().await;
             }
-            GameMode::Creative | GameMode::Spectator => {}
+            GameMode::Creative => {}
+            GameMode::Spectator => return BlockActionResult::Pass,
         }
 
-        let mut properties = CakeLikeProperties::from_state_id(state_id, block);
-        match properties.bites.to_index() {
-            0..=5 => {
-                properties.bites = Integer0To6::from_index(properties.bites.to_index() + 1);
-                world
-                    .set_block_state(
-                        location,
-                        properties.to_state_id(block),
-                        BlockFlags::NOTIFY_ALL,
-                    )
-                    .await;
-                BlockActionResult::Consume
-            }
-            6 => {
-                world
-                    .set_block_state(
-                        location,
-                        Block::AIR.default_state.id,
-                        BlockFlags::NOTIFY_ALL,
-                    )
-                    .await;
-                BlockActionResult::Consume
-            }
-            _ => {
-                panic!("invalid bite index");
-            }
-        }
+        let eaten = take_bite(state_id, block).unwrap_or(Block::AIR.default_state.id);
+        world
+            .set_block_state(location, eaten, BlockFlags::NOTIFY_ALL)
+            .await;
+        BlockActionResult::Consume
     }
 }
 
@@ -194,3 +201,28 @@ async fn can_place_at(world: &dyn BlockAccessor, position: &BlockPos) -> bool {
     let state = world.get_block_state(&position.down()).await;
     state.is_solid()
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::block_properties::{BlockProperties, CakeLikeProperties, Integer0To6};
+
+    use super::{eat_slice, take_bite};
+
+    #[test]
+    fn only_hungry_players_eat_cake() {
+        assert_eq!(eat_slice(10, 0.0), Some((12, 0.4)));
+        assert_eq!(eat_slice(19, 19.9), Some((20, 20.0)));
+        assert_eq!(eat_slice(20, 0.0), None);
+
+        let bitten = take_bite(Block::CAKE.default_state.id, &Block::CAKE).unwrap();
+        let properties = CakeLikeProperties::from_state_id(bitten, &Block::CAKE);
+        assert_eq!(properties.bites, Integer0To6::L1);
+
+        let mut state_id = bitten;
+        for _ in 0..5 {
+            state_id = take_bite(state_id, &Block::CAKE).unwrap();
+        }
+        assert_eq!(take_bite(state_id, &Block::CAKE), None);
+    }
+}
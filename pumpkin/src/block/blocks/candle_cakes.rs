@@ -2,7 +2,9 @@ use std::sync::Arc;
 
 use pumpkin_data::{
     Block,
+    block_properties::{BlockProperties, RedstoneOreLikeProperties},
     item::Item,
+    sound::{Sound, SoundCategory},
     tag::{RegistryKey, get_tag_values},
 };
 use pumpkin_macros::pumpkin_block_from_tag;
@@ -63,6 +65,8 @@ pub fn candle_from_cake(block: &Block) -> &'static Item {
         )
 }
 
+type CandleCakeProperties = RedstoneOreLikeProperties;
+
 #[pumpkin_block_from_tag("minecraft:candle_cakes")]
 pub struct CandleCakeBlock;
 
@@ -109,11 +113,37 @@ impl BlockBehaviour for CandleCakeBlock {
         args: UseWithItemArgs<'a>,
     ) -> BlockFuture<'a, BlockActionResult> {
         Box::pin(async move {
-            let item_id = args.item_stack.lock().await.item.id;
+            let item_stack = args.item_stack.lock().await;
+            let (item_id, is_empty) = (item_stack.item.id, item_stack.is_empty());
+            drop(item_stack);
             match item_id {
+                // The candles are lit by the item itself
                 id if id == Item::FIRE_CHARGE.id || id == Item::FLINT_AND_STEEL.id => {
                     BlockActionResult::Pass
-                } // Item::FIRE_CHARGE | Item::FLINT_AND_STEEL
+                }
+                _ if is_empty && args.hit.cursor_pos.y > 0.5 => {
+                    let state_id = args.world.get_block_state_id(args.position).await;
+                    let mut properties = CandleCakeProperties::from_state_id(state_id, args.block);
+                    if !properties.lit {
+                        return BlockActionResult::PassToDefaultBlockAction;
+                    }
+                    properties.lit = false;
+                    args.world
+                        .set_block_state(
+                            args.position,
+                            properties.to_state_id(args.block),
+                            BlockFlags::NOTIFY_ALL,
+                        )
+                        .await;
+                    args.world
+                        .play_block_sound(
+                            Sound::BlockCandleExtinguish,
+                            SoundCategory::Blocks,
+                            *args.position,
+                        )
+                        .await;
+                    BlockActionResult::Consume
+                }
                 _ => BlockActionResult::PassToDefaultBlockAction,
             }
         })
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use pumpkin_data::block_properties::{BlockProperties, EnumVariants, Integer0To5};
+use pumpkin_data::world::WorldEvent;
+use pumpkin_data::{Block, BlockDirection};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::tick::TickPriority;
+use pumpkin_world::world::{BlockAccessor, BlockFlags};
+use rand::Rng;
+
+use super::chorus_plant::with_connection_properties;
+use crate::block::{
+    BlockBehaviour, BlockFuture, CanPlaceAtArgs, GetStateForNeighborUpdateArgs,
+    OnScheduledTickArgs, RandomTickArgs,
+};
+use crate::world::World;
+
+type ChorusFlowerProperties = pumpkin_data::block_properties::ChorusFlowerLikeProperties;
+
+#[pumpkin_block("minecraft:chorus_flower")]
+pub struct ChorusFlowerBlock;
+
+impl BlockBehaviour for ChorusFlowerBlock {
+    fn on_scheduled_tick<'a>(&'a self, args: OnScheduledTickArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            if !can_flower_stay_at(args.world.as_ref(), args.position).await {
+                args.world
+                    .break_block(args.position, None, BlockFlags::empty())
+                    .await;
+            }
+        })
+    }
+
+    fn random_tick<'a>(&'a self, args: RandomTickArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            let world = args.world;
+            let above = args.position.up();
+            let top_y = world.dimension.min_y + world.dimension.height - 1;
+            if !world.get_block_state(&above).await.is_air() || above.0.y >= top_y {
+                return;
+            }
+            let state_id = world.get_block_state_id(args.position).await;
+            let age = ChorusFlowerProperties::from_state_id(state_id, args.block)
+                .age
+                .to_index();
+            if age >= 5 {
+                return;
+            }
+
+            let (can_grow_up, on_tall_plant) = grows_upwards(world.as_ref(), args.position).await;
+            if can_grow_up
+                && is_surrounded_by_air(world.as_ref(), &above, None).await
+                && world.get_block_state(&above.up()).await.is_air()
+            {
+                become_plant(world, args.position).await;
+                grow(world, &above, age).await;
+            } else if age < 4 {
+                let mut branches = rand::rng().random_range(0..4);
+                if on_tall_plant {
+                    branches += 1;
+                }
+                let mut branched = false;
+                for _ in 0..branches {
+                    let direction = BlockDirection::horizontal()[rand::rng().random_range(0..4)];
+                    let branch = args.position.offset(direction.to_offset());
+                    if world.get_block_state(&branch).await.is_air()
+                        && world.get_block_state(&branch.down()).await.is_air()
+                        && is_surrounded_by_air(world.as_ref(), &branch, Some(direction.opposite()))
+                            .await
+                    {
+                        grow(world, &branch, age + 1).await;
+                        branched = true;
+                    }
+                }
+                if branched {
+                    become_plant(world, args.position).await;
+                } else {
+                    die(world, args.position).await;
+                }
+            } else {
+                die(world, args.position).await;
+            }
+        })
+    }
+
+    fn get_state_for_neighbor_update<'a>(
+        &'a self,
+        args: GetStateForNeighborUpdateArgs<'a>,
+    ) -> BlockFuture<'a, BlockStateId> {
+        Box::pin(async move {
+            if args.direction != BlockDirection::Up
+                && !can_flower_stay_at(args.world, args.position).await
+            {
+                args.world
+                    .schedule_block_tick(args.block, *args.position, 1, TickPriority::Normal)
+                    .await;
+            }
+            args.state_id
+        })
+    }
+
+    fn can_place_at<'a>(&'a self, args: CanPlaceAtArgs<'a>) -> BlockFuture<'a, bool> {
+        Box::pin(async move { can_flower_stay_at(args.block_accessor, args.position).await })
+    }
+}
+
+/// A chorus flower sits on a chorus plant or end stone, or hangs from exactly one chorus plant
+/// next to it.
+pub async fn can_flower_stay_at(block_accessor: &dyn BlockAccessor, position: &BlockPos) -> bool {
+    let (below_block, below) = block_accessor.get_block_and_state(&position.down()).await;
+    if below_block == &Block::CHORUS_PLANT || below_block == &Block::END_STONE {
+        return true;
+    }
+    if !below.is_air() {
+        return false;
+    }
+    let mut attached = false;
+    for direction in BlockDirection::horizontal() {
+        let (neighbor_block, neighbor) = block_accessor
+            .get_block_and_state(&position.offset(direction.to_offset()))
+            .await;
+        if neighbor_block == &Block::CHORUS_PLANT {
+            if attached {
+                return false;
+            }
+            attached = true;
+        } else if !neighbor.is_air() {
+            return false;
+        }
+    }
+    attached
+}
+
+/// Returns whether the flower may grow straight up and whether it stands on a plant that is
+/// rooted in end stone, which makes it branch more.
+async fn grows_upwards(block_accessor: &dyn BlockAccessor, position: &BlockPos) -> (bool, bool) {
+    let (below_block, below) = block_accessor.get_block_and_state(&position.down()).await;
+    if below_block == &Block::END_STONE || below.is_air() {
+        return (true, false);
+    }
+    if below_block != &Block::CHORUS_PLANT {
+        return (false, false);
+    }
+    let mut plant_height = 1;
+    let mut on_tall_plant = false;
+    for _ in 0..4 {
+        let block = block_accessor
+            .get_block(&position.down_height(plant_height + 1))
+            .await;
+        if block != &Block::CHORUS_PLANT {
+            on_tall_plant = block == &Block::END_STONE;
+            break;
+        }
+        plant_height += 1;
+    }
+    let limit = if on_tall_plant { 5 } else { 4 };
+    (
+        plant_height < 2 || plant_height <= rand::rng().random_range(0..limit),
+        on_tall_plant,
+    )
+}
+
+async fn is_surrounded_by_air(
+    block_accessor: &dyn BlockAccessor,
+    position: &BlockPos,
+    except: Option<BlockDirection>,
+) -> bool {
+    for direction in BlockDirection::horizontal() {
+        if Some(direction) != except
+            && !block_accessor
+                .get_block_state(&position.offset(direction.to_offset()))
+                .await
+                .is_air()
+        {
+            return false;
+        }
+    }
+    true
+}
+
+async fn become_plant(world: &Arc<World>, position: &BlockPos) {
+    let state_id = with_connection_properties(world.as_ref(), position).await;
+    world
+        .set_block_state(position, state_id, BlockFlags::NOTIFY_LISTENERS)
+        .await;
+}
+
+async fn grow(world: &Arc<World>, position: &BlockPos, age: u16) {
+    let mut props = ChorusFlowerProperties::default(&Block::CHORUS_FLOWER);
+    props.age = Integer0To5::from_index(age);
+    world
+        .set_block_state(
+            position,
+            props.to_state_id(&Block::CHORUS_FLOWER),
+            BlockFlags::NOTIFY_LISTENERS,
+        )
+        .await;
+    world
+        .sync_world_event(WorldEvent::ChorusFlowerGrows, *position, 0)
+        .await;
+}
+
+async fn die(world: &Arc<World>, position: &BlockPos) {
+    let mut props = ChorusFlowerProperties::default(&Block::CHORUS_FLOWER);
+    props.age = Integer0To5::L5;
+    world
+        .set_block_state(
+            position,
+            props.to_state_id(&Block::CHORUS_FLOWER),
+            BlockFlags::NOTIFY_LISTENERS,
+        )
+        .await;
+    world
+        .sync_world_event(WorldEvent::ChorusFlowerDies, *position, 0)
+        .await;
+}
@@ -0,0 +1,151 @@
+use pumpkin_data::block_properties::BlockProperties;
+use pumpkin_data::{Block, BlockDirection};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::tick::TickPriority;
+use pumpkin_world::world::{BlockAccessor, BlockFlags};
+
+use crate::block::{
+    BlockBehaviour, BlockFuture, CanPlaceAtArgs, GetStateForNeighborUpdateArgs, OnPlaceArgs,
+    OnScheduledTickArgs,
+};
+
+type ChorusPlantProperties = pumpkin_data::block_properties::BrownMushroomBlockLikeProperties;
+
+#[pumpkin_block("minecraft:chorus_plant")]
+pub struct ChorusPlantBlock;
+
+impl BlockBehaviour for ChorusPlantBlock {
+    fn on_place<'a>(&'a self, args: OnPlaceArgs<'a>) -> BlockFuture<'a, BlockStateId> {
+        Box::pin(async move { with_connection_properties(args.world, args.position).await })
+    }
+
+    fn on_scheduled_tick<'a>(&'a self, args: OnScheduledTickArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            if !can_plant_stay_at(args.world.as_ref(), args.position).await {
+                args.world
+                    .break_block(args.position, None, BlockFlags::empty())
+                    .await;
+            }
+        })
+    }
+
+    fn get_state_for_neighbor_update<'a>(
+        &'a self,
+        args: GetStateForNeighborUpdateArgs<'a>,
+    ) -> BlockFuture<'a, BlockStateId> {
+        Box::pin(async move {
+            if !can_plant_stay_at(args.world, args.position).await {
+                args.world
+                    .schedule_block_tick(args.block, *args.position, 1, TickPriority::Normal)
+                    .await;
+                return args.state_id;
+            }
+            let neighbor = args.world.get_block(args.neighbor_position).await;
+            let mut props = ChorusPlantProperties::from_state_id(args.state_id, args.block);
+            set_connection(
+                &mut props,
+                args.direction,
+                connects_to(neighbor, args.direction),
+            );
+            props.to_state_id(args.block)
+        })
+    }
+
+    fn can_place_at<'a>(&'a self, args: CanPlaceAtArgs<'a>) -> BlockFuture<'a, bool> {
+        Box::pin(async move { can_plant_stay_at(args.block_accessor, args.position).await })
+    }
+}
+
+/// Returns the chorus plant state at `position`, connected to all neighbouring chorus blocks.
+pub async fn with_connection_properties(
+    block_accessor: &dyn BlockAccessor,
+    position: &BlockPos,
+) -> BlockStateId {
+    let mut props = ChorusPlantProperties::default(&Block::CHORUS_PLANT);
+    for direction in BlockDirection::all() {
+        let neighbor = block_accessor
+            .get_block(&position.offset(direction.to_offset()))
+            .await;
+        set_connection(&mut props, direction, connects_to(neighbor, direction));
+    }
+    props.to_state_id(&Block::CHORUS_PLANT)
+}
+
+/// A chorus plant needs another chorus plant or end stone below it, or a neighbouring chorus
+/// plant that is standing on one.
+pub async fn can_plant_stay_at(block_accessor: &dyn BlockAccessor, position: &BlockPos) -> bool {
+    let (below_block, below) = block_accessor.get_block_and_state(&position.down()).await;
+    let is_stem = !block_accessor
+        .get_block_state(&position.up())
+        .await
+        .is_air()
+        && !below.is_air();
+    for direction in BlockDirection::horizontal() {
+        let neighbor_pos = position.offset(direction.to_offset());
+        if block_accessor.get_block(&neighbor_pos).await != &Block::CHORUS_PLANT {
+            continue;
+        }
+        if is_stem {
+            return false;
+        }
+        if is_plant_base(block_accessor.get_block(&neighbor_pos.down()).await) {
+            return true;
+        }
+    }
+    is_plant_base(below_block)
+}
+
+fn is_plant_base(block: &Block) -> bool {
+    block == &Block::CHORUS_PLANT || block == &Block::END_STONE
+}
+
+fn connects_to(block: &Block, direction: BlockDirection) -> bool {
+    block == &Block::CHORUS_PLANT
+        || block == &Block::CHORUS_FLOWER
+        || (direction == BlockDirection::Down && block == &Block::END_STONE)
+}
+
+fn set_connection(props: &mut ChorusPlantProperties, direction: BlockDirection, connected: bool) {
+    match direction {
+        BlockDirection::Down => props.down = connected,
+        BlockDirection::Up => props.up = connected,
+        BlockDirection::North => props.north = connected,
+        BlockDirection::South => props.south = connected,
+        BlockDirection::West => props.west = connected,
+        BlockDirection::East => props.east = connected,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::block_properties::BlockProperties;
+    use pumpkin_data::{Block, dimension::Dimension};
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_world::generation::proto_chunk::ProtoChunk;
+
+    use super::{ChorusPlantProperties, can_plant_stay_at, with_connection_properties};
+
+    #[test]
+    fn chorus_plants_connect_and_need_support() {
+        let mut chunk = ProtoChunk::new(0, 0, &Dimension::OVERWORLD, Block::STONE.default_state, 0);
+        let position = BlockPos::new(8, 64, 8);
+        chunk.set_block_state(8, 63, 8, Block::END_STONE.default_state);
+        chunk.set_block_state(8, 65, 8, Block::CHORUS_FLOWER.default_state);
+        chunk.set_block_state(9, 64, 8, Block::END_STONE.default_state);
+
+        let state_id = futures::executor::block_on(with_connection_properties(&chunk, &position));
+        let props = ChorusPlantProperties::from_state_id(state_id, &Block::CHORUS_PLANT);
+        assert!(props.down && props.up);
+        assert!(!props.east && !props.west && !props.north && !props.south);
+        assert!(futures::executor::block_on(can_plant_stay_at(
+            &chunk, &position
+        )));
+
+        chunk.set_block_state(8, 63, 8, Block::AIR.default_state);
+        assert!(!futures::executor::block_on(can_plant_stay_at(
+            &chunk, &position
+        )));
+    }
+}
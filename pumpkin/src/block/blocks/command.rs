@@ -66,7 +66,9 @@ impl CommandBlock {
         }
     }
 
-    async fn conditions_met(world: &Arc<World>, pos: &BlockPos, facing: Facing) -> bool {
+    /// Whether a command block may run its command: unconditional ones always can, conditional
+    /// ones only if the command block behind them succeeded.
+    pub(crate) async fn conditions_met(world: &World, pos: &BlockPos) -> bool {
         let state_id = world.get_block_state_id(pos).await;
         let block = world.get_block(pos).await;
         let props = CommandBlockLikeProperties::from_state_id(state_id, block);
@@ -75,7 +77,8 @@ impl CommandBlock {
             return true;
         }
 
-        let Some(before) = Self::get_relative_facing(world, pos, facing.opposite()).await else {
+        let Some(before) = Self::get_relative_facing(world, pos, props.facing.opposite()).await
+        else {
             return false;
         };
         let Some(before_entity) = world.get_block_entity(&before.0).await else {
@@ -94,46 +97,43 @@ impl CommandBlock {
         pos: &BlockPos,
         powered: bool,
     ) {
-        let is_auto = command_block.auto.load(Ordering::Relaxed);
-        if command_block.powered.load(Ordering::Relaxed) == powered && !is_auto {
+        if command_block.powered.load(Ordering::Relaxed) == powered {
             return;
         }
         command_block.powered.store(powered, Ordering::Relaxed);
 
+        // Impulse and repeating command blocks run on the rising edge, chain command blocks only
+        // when the chain they are part of runs
+        let is_auto = command_block.auto.load(Ordering::Relaxed);
         if block.id == Block::CHAIN_COMMAND_BLOCK.id || is_auto || !powered {
             return;
         }
 
-        let state_id = world.get_block_state_id(pos).await;
-        let props = CommandBlockLikeProperties::from_state_id(state_id, block);
-
-        if !props.conditional {
-            world
-                .schedule_block_tick(block, *pos, 1, TickPriority::Normal)
-                .await;
-            return;
-        }
+        command_block
+            .condition_met
+            .store(Self::conditions_met(world, pos).await, Ordering::Relaxed);
+        world
+            .schedule_block_tick(block, *pos, 1, TickPriority::Normal)
+            .await;
+    }
 
-        let Some(behind) = Self::get_relative_facing(world, pos, props.facing.opposite()).await
-        else {
-            return;
-        };
-        let Some(behind_entity) = world.get_block_entity(&behind.0).await else {
-            warn!(
-                "Command Block exists at {} with no matching block entity!",
-                behind.0
-            );
-            return;
-        };
-        let behind_entity: &CommandBlockEntity = behind_entity
-            .as_any()
-            .downcast_ref()
-            .expect("behind should always be a command block");
-
-        if behind_entity.success_count.load(Ordering::Relaxed) > 0 {
-            world
-                .schedule_block_tick(block, *pos, 1, TickPriority::Normal)
-                .await;
+    /// Runs the command and the chain in front of the command block if its condition was met.
+    async fn run(
+        server: &Arc<Server>,
+        world: &Arc<World>,
+        block_entity: Arc<dyn BlockEntity>,
+        props: &CommandBlockLikeProperties,
+        condition_met: bool,
+    ) {
+        let command_entity: &CommandBlockEntity = block_entity.as_any().downcast_ref().unwrap();
+        command_entity.dirty.store(true, Ordering::Relaxed);
+        if condition_met {
+            let position = block_entity.get_position();
+            let command = command_entity.command.lock().await.clone();
+            Self::execute(server, world.clone(), block_entity.clone(), &command).await;
+            Self::chain_execute(server, world, position, props.facing).await;
+        } else if props.conditional {
+            command_entity.success_count.store(0, Ordering::Release);
         }
     }
 
@@ -166,53 +166,53 @@ impl CommandBlock {
         }
     }
 
+    /// Runs the chain command blocks in front of `start`, following the direction each of them
+    /// faces.
     async fn chain_execute(
         server: &Arc<Server>,
-        world: Arc<World>,
+        world: &Arc<World>,
         start: BlockPos,
         direction: Facing,
     ) {
-        let mut i = u16::MAX;
-        let mut pos = start;
+        let mut pos = start.offset(Self::facing_to_offset(direction));
 
-        while i > 0 {
+        for _ in 0..u16::MAX {
             let block = world.get_block(&pos).await;
-
             if block.id != Block::CHAIN_COMMAND_BLOCK.id {
-                break;
+                return;
             }
             let Some(block_entity) = world.get_block_entity(&pos).await else {
                 warn!("Missing command block entity");
-                break;
+                return;
             };
-
             let command_entity: &CommandBlockEntity = block_entity.as_any().downcast_ref().unwrap();
-            let powered = command_entity.powered.load(Ordering::Relaxed);
-            let auto = command_entity.auto.load(Ordering::Relaxed);
-            let state_id = world.get_block_state_id(&pos).await;
-            let props = CommandBlockLikeProperties::from_state_id(state_id, block);
-
-            if powered || auto {
-                let conditions_met = Self::conditions_met(&world, &pos, direction).await;
-                if conditions_met {
-                    let command = command_entity.command.lock().await;
-                    let entity = world.get_block_entity(&pos).await.unwrap();
-                    Self::execute(server, world.clone(), entity, &command).await;
+            let props = CommandBlockLikeProperties::from_state_id(
+                world.get_block_state_id(&pos).await,
+                block,
+            );
+
+            if command_entity.powered.load(Ordering::Relaxed)
+                || command_entity.auto.load(Ordering::Relaxed)
+            {
+                let condition_met = Self::conditions_met(world, &pos).await;
+                command_entity
+                    .condition_met
+                    .store(condition_met, Ordering::Relaxed);
+                command_entity.dirty.store(true, Ordering::Relaxed);
+                if condition_met {
+                    let command = command_entity.command.lock().await.clone();
+                    Self::execute(server, world.clone(), block_entity.clone(), &command).await;
                 } else if props.conditional {
                     command_entity.success_count.store(0, Ordering::Release);
                 }
             }
 
-            pos = pos.offset(Self::facing_to_offset(direction));
-
-            i -= 1;
-            if i == 0 {
-                warn!(
-                    "Command block chain executed {} times (the maximum)!",
-                    u16::MAX
-                );
-            }
+            pos = pos.offset(Self::facing_to_offset(props.facing));
         }
+        warn!(
+            "Command block chain executed {} times (the maximum)!",
+            u16::MAX
+        );
     }
 }
 
@@ -284,7 +284,6 @@ impl BlockBehaviour for CommandBlock {
                 return;
             }
 
-            let command_entity: &CommandBlockEntity = block_entity.as_any().downcast_ref().unwrap();
             let Some(server) = args.world.server.upgrade() else {
                 return;
             };
@@ -293,30 +292,35 @@ impl BlockBehaviour for CommandBlock {
                 args.block,
             );
 
-            Self::execute(
-                &server,
-                args.world.clone(),
-                block_entity.clone(),
-                &command_entity.command.lock().await,
-            )
-            .await;
+            let command_entity: &CommandBlockEntity = block_entity.as_any().downcast_ref().unwrap();
+            let condition_met = command_entity.condition_met.load(Ordering::Relaxed);
+
+            // Repeating command blocks run every tick while they are active, checking their
+            // condition for the next run
+            if args.block == &Block::REPEATING_COMMAND_BLOCK {
+                command_entity.condition_met.store(
+                    Self::conditions_met(args.world, args.position).await,
+                    Ordering::Relaxed,
+                );
+                if command_entity.powered.load(Ordering::Relaxed)
+                    || command_entity.auto.load(Ordering::Relaxed)
+                {
+                    args.world
+                        .schedule_block_tick(args.block, *args.position, 1, TickPriority::Normal)
+                        .await;
+                }
+            } else if args.block == &Block::CHAIN_COMMAND_BLOCK {
+                return;
+            }
 
-            Self::chain_execute(
+            Self::run(
                 &server,
-                args.world.clone(),
-                args.position.offset(Self::facing_to_offset(props.facing)),
-                props.facing,
+                args.world,
+                block_entity.clone(),
+                &props,
+                condition_met,
             )
             .await;
-
-            let block = args.world.get_block(args.position).await;
-            let is_auto = command_entity.auto.load(Ordering::Relaxed);
-            let can_run = command_entity.powered.load(Ordering::Relaxed) || is_auto;
-            if block == &Block::REPEATING_COMMAND_BLOCK && can_run {
-                args.world
-                    .schedule_block_tick(block, *args.position, 1, TickPriority::Normal)
-                    .await;
-            }
         })
     }
 
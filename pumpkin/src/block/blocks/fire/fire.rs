@@ -1,12 +1,10 @@
 use pumpkin_data::block_properties::{BlockProperties, EnumVariants, HorizontalAxis};
 use pumpkin_data::dimension::Dimension;
-use pumpkin_data::entity::EntityType;
 use pumpkin_data::fluid::Fluid;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_world::world::{BlockAccessor, BlockFlags};
 use rand::Rng;
 use std::sync::Arc;
-use std::sync::atomic::Ordering;
 
 use pumpkin_data::{Block, BlockDirection, BlockState};
 use pumpkin_macros::pumpkin_block;
@@ -35,6 +33,24 @@ impl FireBlock {
         30 + rand::rng().random_range(0..10)
     }
 
+    /// Whether fire at `pos` burns out and spreads, the `fire_spread_radius_around_player` game
+    /// rule limits this to fires near players.
+    async fn can_spread_at(world: &World, pos: &BlockPos) -> bool {
+        let radius = world
+            .level_info
+            .read()
+            .await
+            .game_rules
+            .fire_spread_radius_around_player;
+        let center = pos.to_centered_f64();
+        let players = world.players.read().await;
+        let distances = players.values().map(|player| {
+            let position = player.living_entity.entity.pos.load();
+            position.squared_distance_to_vec(center)
+        });
+        is_in_spread_radius(radius, distances)
+    }
+
     fn is_flammable(block_state: &BlockState) -> bool {
         if Block::from_state_id(block_state.id)
             .properties(block_state.id)
@@ -195,21 +211,7 @@ impl BlockBehaviour for FireBlock {
 
     fn on_entity_collision<'a>(&'a self, args: OnEntityCollisionArgs<'a>) -> BlockFuture<'a, ()> {
         Box::pin(async move {
-            let base_entity = args.entity.get_entity();
-            if !base_entity.entity_type.fire_immune {
-                let ticks = base_entity.fire_ticks.load(Ordering::Relaxed);
-                if ticks < 0 {
-                    base_entity.fire_ticks.store(ticks + 1, Ordering::Relaxed);
-                } else if base_entity.entity_type == &EntityType::PLAYER {
-                    let rnd_ticks = rand::rng().random_range(1..3);
-                    base_entity
-                        .fire_ticks
-                        .store(ticks + rnd_ticks, Ordering::Relaxed);
-                }
-                if base_entity.fire_ticks.load(Ordering::Relaxed) >= 0 {
-                    base_entity.set_on_fire_for(8.0);
-                }
-            }
+            FireBlockBase::on_entity_collision(args.entity, 1.0).await;
         })
     }
 
@@ -269,6 +271,9 @@ impl BlockBehaviour for FireBlock {
                     TickPriority::Normal,
                 )
                 .await;
+            if !Self::can_spread_at(world, pos).await {
+                return;
+            }
             if !Self
                 .can_place_at(CanPlaceAtArgs {
                     server: None,
@@ -418,3 +423,34 @@ impl BlockBehaviour for FireBlock {
         })
     }
 }
+
+/// Whether fire spreads with the `fire_spread_radius_around_player` game rule set to `radius`
+/// and players at the given squared distances, -1 lets fire spread everywhere and 0 nowhere.
+fn is_in_spread_radius(radius: i64, player_distances_sq: impl IntoIterator<Item = f64>) -> bool {
+    match radius {
+        radius if radius < 0 => true,
+        0 => false,
+        radius => {
+            let radius = radius as f64;
+            player_distances_sq
+                .into_iter()
+                .any(|distance_sq| distance_sq <= radius * radius)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_in_spread_radius;
+
+    #[test]
+    fn fire_only_spreads_near_players() {
+        assert!(is_in_spread_radius(128, [100.0 * 100.0]));
+        assert!(!is_in_spread_radius(128, [200.0 * 200.0]));
+        assert!(!is_in_spread_radius(128, []));
+        // Disabled
+        assert!(!is_in_spread_radius(0, [0.0]));
+        // Everywhere
+        assert!(is_in_spread_radius(-1, []));
+    }
+}
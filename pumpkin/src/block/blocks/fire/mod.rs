@@ -1,6 +1,9 @@
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
+use pumpkin_data::damage::DamageType;
 use pumpkin_data::dimension::Dimension;
+use pumpkin_data::entity::EntityType;
 use pumpkin_data::tag::Taggable;
 use pumpkin_data::world::WorldEvent;
 use pumpkin_data::{Block, BlockDirection, tag};
@@ -12,6 +15,7 @@ use soul_fire::SoulFireBlock;
 
 use crate::block::blocks::fire::fire::FireBlock;
 use crate::block::{BlockBehaviour, CanPlaceAtArgs};
+use crate::entity::EntityBase;
 use crate::world::World;
 use crate::world::portal::nether::NetherPortal;
 
@@ -113,6 +117,27 @@ impl FireBlockBase {
             .is_some();
     }
 
+    /// Sets an entity inside a fire block on fire and burns it for `damage`.
+    pub async fn on_entity_collision(entity: &dyn EntityBase, damage: f32) {
+        let base_entity = entity.get_entity();
+        if base_entity.entity_type.fire_immune {
+            return;
+        }
+        let ticks = base_entity.fire_ticks.load(Ordering::Relaxed);
+        if ticks < 0 {
+            base_entity.fire_ticks.store(ticks + 1, Ordering::Relaxed);
+        } else if base_entity.entity_type == &EntityType::PLAYER {
+            let rnd_ticks = rand::rng().random_range(1..3);
+            base_entity
+                .fire_ticks
+                .store(ticks + rnd_ticks, Ordering::Relaxed);
+        }
+        if base_entity.fire_ticks.load(Ordering::Relaxed) >= 0 {
+            base_entity.set_on_fire_for(8.0);
+        }
+        entity.damage(entity, damage, DamageType::IN_FIRE).await;
+    }
+
     async fn broken(world: &World, block_pos: BlockPos) {
         world
             .sync_world_event(WorldEvent::FireExtinguished, block_pos, 0)
@@ -5,6 +5,7 @@ use pumpkin_world::BlockStateId;
 
 use crate::block::{
     BlockBehaviour, BlockFuture, BrokenArgs, CanPlaceAtArgs, GetStateForNeighborUpdateArgs,
+    OnEntityCollisionArgs,
 };
 
 use super::FireBlockBase;
@@ -39,6 +40,12 @@ impl BlockBehaviour for SoulFireBlock {
         })
     }
 
+    fn on_entity_collision<'a>(&'a self, args: OnEntityCollisionArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            FireBlockBase::on_entity_collision(args.entity, 2.0).await;
+        })
+    }
+
     fn broken<'a>(&'a self, args: BrokenArgs<'a>) -> BlockFuture<'a, ()> {
         Box::pin(async move {
             FireBlockBase::broken(args.world, *args.position).await;
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use pumpkin_data::block_properties::{
+    BlockProperties, EnumVariants, Integer0To3, NetherWartLikeProperties,
+};
+use pumpkin_data::{Block, BlockDirection, tag, tag::Taggable};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::tick::TickPriority;
+use pumpkin_world::world::{BlockAccessor, BlockFlags};
+use rand::Rng;
+
+use crate::block::{BlockBehaviour, BlockFuture, OnScheduledTickArgs};
+use crate::world::World;
+
+type FrostedIceProperties = NetherWartLikeProperties;
+
+#[pumpkin_block("minecraft:frosted_ice")]
+pub struct FrostedIceBlock;
+
+impl BlockBehaviour for FrostedIceBlock {
+    fn on_scheduled_tick<'a>(&'a self, args: OnScheduledTickArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            // TODO: Vanilla only melts when the light level is high enough
+            let melts = rand::rng().random_range(0..3) == 0
+                || frosted_neighbours(args.world.as_ref(), args.position).await < 4;
+            if melts {
+                let state_id = args.world.get_block_state_id(args.position).await;
+                let melted = slightly_melt(state_id);
+                args.world
+                    .set_block_state(args.position, melted, BlockFlags::NOTIFY_ALL)
+                    .await;
+                if melted == Block::WATER.default_state.id {
+                    return;
+                }
+            }
+            schedule_melt(
+                args.world,
+                *args.position,
+                rand::rng().random_range(20..=40),
+            )
+            .await;
+        })
+    }
+}
+
+/// Returns the state of frosted ice that melted a bit, or water if it was about to melt.
+#[must_use]
+pub fn slightly_melt(state_id: BlockStateId) -> BlockStateId {
+    let mut props = FrostedIceProperties::from_state_id(state_id, &Block::FROSTED_ICE);
+    if props.age == Integer0To3::L3 {
+        return Block::WATER.default_state.id;
+    }
+    props.age = Integer0To3::from_index(props.age.to_index() + 1);
+    props.to_state_id(&Block::FROSTED_ICE)
+}
+
+async fn frosted_neighbours(block_accessor: &dyn BlockAccessor, position: &BlockPos) -> usize {
+    let mut count = 0;
+    for direction in BlockDirection::all() {
+        let neighbour = position.offset(direction.to_offset());
+        if block_accessor.get_block(&neighbour).await == &Block::FROSTED_ICE {
+            count += 1;
+        }
+    }
+    count
+}
+
+async fn schedule_melt(world: &World, position: BlockPos, delay: u8) {
+    world
+        .schedule_block_tick(&Block::FROSTED_ICE, position, delay, TickPriority::Normal)
+        .await;
+}
+
+/// Returns the water sources frost walker of `level` turns into frosted ice, when the walker
+/// stands at `position`.
+pub async fn frost_walker_targets(
+    block_accessor: &dyn BlockAccessor,
+    position: Vector3<f64>,
+    level: i32,
+) -> Vec<BlockPos> {
+    let radius = (2 + level).clamp(0, 16);
+    let center = BlockPos::floored(position.x, position.y, position.z).down();
+    let mut targets = Vec::new();
+    for x in -radius..=radius {
+        for z in -radius..=radius {
+            let target = center.offset(Vector3::new(x, 0, z));
+            let dx = f64::from(target.0.x) + 0.5 - position.x;
+            let dz = f64::from(target.0.z) + 0.5 - position.z;
+            if dx * dx + dz * dz >= f64::from(radius * radius) {
+                continue;
+            }
+            if block_accessor.get_block_state_id(&target).await == Block::WATER.default_state.id
+                && block_accessor
+                    .get_block(&target.up())
+                    .await
+                    .has_tag(&tag::Block::MINECRAFT_AIR)
+            {
+                targets.push(target);
+            }
+        }
+    }
+    targets
+}
+
+/// Freezes the water below a frost walker, the frosted ice melts again over time.
+pub async fn frost_walk(world: &Arc<World>, position: Vector3<f64>, level: i32) {
+    for target in frost_walker_targets(world.as_ref(), position, level).await {
+        world
+            .set_block_state(
+                &target,
+                Block::FROSTED_ICE.default_state.id,
+                BlockFlags::NOTIFY_ALL,
+            )
+            .await;
+        schedule_melt(world, target, rand::rng().random_range(60..=120)).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, dimension::Dimension};
+    use pumpkin_util::math::vector3::Vector3;
+    use pumpkin_world::generation::proto_chunk::ProtoChunk;
+    use pumpkin_world::world::BlockAccessor;
+
+    use super::{frost_walker_targets, slightly_melt};
+
+    #[test]
+    fn frost_walker_freezes_water_that_melts_later() {
+        let mut chunk = ProtoChunk::new(0, 0, &Dimension::OVERWORLD, Block::STONE.default_state, 0);
+        for x in 0..16 {
+            for z in 0..16 {
+                chunk.set_block_state(x, 62, z, Block::WATER.default_state);
+            }
+        }
+
+        // Walking over the water with Frost Walker I
+        let position = Vector3::new(8.5, 63.0, 8.5);
+        let targets = futures::executor::block_on(frost_walker_targets(&chunk, position, 1));
+        assert_eq!(targets.len(), 25);
+        for target in &targets {
+            assert_eq!(target.0.y, 62);
+            chunk.set_block_state(
+                target.0.x,
+                target.0.y,
+                target.0.z,
+                Block::FROSTED_ICE.default_state,
+            );
+        }
+        assert_eq!(
+            futures::executor::block_on(chunk.get_block(&targets[0])),
+            &Block::FROSTED_ICE
+        );
+        // Frozen water is no water source anymore
+        assert!(futures::executor::block_on(frost_walker_targets(&chunk, position, 1)).is_empty());
+
+        let mut state_id = Block::FROSTED_ICE.default_state.id;
+        for _ in 0..3 {
+            state_id = slightly_melt(state_id);
+            assert_eq!(Block::from_state_id(state_id), &Block::FROSTED_ICE);
+        }
+        assert_eq!(slightly_melt(state_id), Block::WATER.default_state.id);
+    }
+}
@@ -0,0 +1,198 @@
+use pumpkin_data::Block;
+use pumpkin_data::data_component::DataComponent;
+use pumpkin_data::data_component_impl::{DataComponentImpl, LodestoneTrackerImpl};
+use pumpkin_data::dimension::Dimension;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::GameMode;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::item::ItemStack;
+
+use crate::block::registry::BlockActionResult;
+use crate::block::{BlockBehaviour, BlockFuture, UseWithItemArgs};
+use crate::world::World;
+
+#[pumpkin_block("minecraft:lodestone")]
+pub struct LodestoneBlock;
+
+impl BlockBehaviour for LodestoneBlock {
+    fn use_with_item<'a>(
+        &'a self,
+        args: UseWithItemArgs<'a>,
+    ) -> BlockFuture<'a, BlockActionResult> {
+        Box::pin(async move {
+            let mut stack = args.item_stack.lock().await;
+            if stack.item != &Item::COMPASS {
+                return BlockActionResult::PassToDefaultBlockAction;
+            }
+            let dimension = &args.world.dimension;
+            // A single compass is bound in place, otherwise one is taken off the stack
+            let gamemode = args.player.gamemode.load();
+            let new_compass = if gamemode != GameMode::Creative && stack.item_count == 1 {
+                bind_compass(&mut stack, dimension, *args.position);
+                None
+            } else {
+                let mut compass = stack.copy_with_count(1);
+                stack.decrement_unless_creative(gamemode, 1);
+                bind_compass(&mut compass, dimension, *args.position);
+                Some(compass)
+            };
+            drop(stack);
+
+            if let Some(mut compass) = new_compass
+                && !args
+                    .player
+                    .inventory
+                    .insert_stack_anywhere(&mut compass)
+                    .await
+            {
+                args.player.drop_item(compass).await;
+            }
+            args.world
+                .play_sound(
+                    Sound::ItemLodestoneCompassLock,
+                    SoundCategory::Players,
+                    &args.position.to_centered_f64(),
+                )
+                .await;
+            BlockActionResult::Success
+        })
+    }
+}
+
+/// Points `compass` at the lodestone at `position` in `dimension`.
+pub fn bind_compass(compass: &mut ItemStack, dimension: &Dimension, position: BlockPos) {
+    let target = Some((dimension.minecraft_name.to_owned(), position));
+    if let Some(tracker) = compass.get_data_component_mut::<LodestoneTrackerImpl>() {
+        tracker.target = target;
+        tracker.tracked = true;
+    } else {
+        compass.patch.push((
+            DataComponent::LodestoneTracker,
+            Some(
+                LodestoneTrackerImpl {
+                    target,
+                    tracked: true,
+                }
+                .to_dyn(),
+            ),
+        ));
+    }
+}
+
+/// Returns the position of the lodestone a tracked `compass` points to, if it is in `dimension`.
+#[must_use]
+pub fn tracked_lodestone(compass: &ItemStack, dimension: &Dimension) -> Option<BlockPos> {
+    let tracker = compass.get_data_component::<LodestoneTrackerImpl>()?;
+    match &tracker.target {
+        Some((target_dimension, position))
+            if tracker.tracked && target_dimension == dimension.minecraft_name =>
+        {
+            Some(*position)
+        }
+        _ => None,
+    }
+}
+
+/// Clears the target of a tracked `compass` when `block`, the block at its target, is no
+/// longer a lodestone. Returns whether the target was cleared.
+pub fn check_lodestone(compass: &mut ItemStack, dimension: &Dimension, block: &Block) -> bool {
+    if tracked_lodestone(compass, dimension).is_none() || block == &Block::LODESTONE {
+        return false;
+    }
+    if let Some(tracker) = compass.get_data_component_mut::<LodestoneTrackerImpl>() {
+        tracker.target = None;
+    }
+    true
+}
+
+/// Checks that the lodestone a `compass` in `world` points to still exists, returns whether
+/// its target was cleared.
+///
+/// Lodestones in unloaded chunks are assumed to still be there.
+pub async fn update_compass(world: &World, compass: &mut ItemStack) -> bool {
+    let Some(position) = tracked_lodestone(compass, &world.dimension) else {
+        return false;
+    };
+    let min_y = world.dimension.min_y;
+    let block = if (min_y..min_y + world.dimension.height).contains(&position.0.y) {
+        if !world
+            .level
+            .loaded_chunks
+            .contains_key(&position.chunk_position())
+        {
+            return false;
+        }
+        world.get_block(&position).await
+    } else {
+        &Block::AIR
+    };
+    check_lodestone(compass, &world.dimension, block)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::data_component_impl::LodestoneTrackerImpl;
+    use pumpkin_data::dimension::Dimension;
+    use pumpkin_data::item::Item;
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_world::item::ItemStack;
+
+    use super::{bind_compass, check_lodestone, tracked_lodestone};
+
+    #[test]
+    fn compass_tracks_lodestone_until_destroyed() {
+        let lodestone = BlockPos::new(12, 70, -5);
+        let mut compass = ItemStack::new(1, &Item::COMPASS);
+        bind_compass(&mut compass, &Dimension::OVERWORLD, lodestone);
+
+        let mut nbt = NbtCompound::new();
+        compass.write_item_stack(&mut nbt);
+        let tracker = nbt
+            .get_compound("components")
+            .and_then(|components| components.get_compound("minecraft:lodestone_tracker"))
+            .unwrap();
+        let target = tracker.get_compound("target").unwrap();
+        assert_eq!(target.get_string("dimension"), Some("minecraft:overworld"));
+        assert_eq!(target.get_int_array("pos"), Some(&[12, 70, -5][..]));
+        assert_eq!(tracker.get_bool("tracked"), Some(true));
+
+        let mut compass = ItemStack::read_item_stack(&nbt).unwrap();
+        assert_eq!(
+            tracked_lodestone(&compass, &Dimension::OVERWORLD),
+            Some(lodestone)
+        );
+        assert_eq!(tracked_lodestone(&compass, &Dimension::THE_NETHER), None);
+
+        // The lodestone still stands
+        assert!(!check_lodestone(
+            &mut compass,
+            &Dimension::OVERWORLD,
+            &Block::LODESTONE
+        ));
+        assert_eq!(
+            tracked_lodestone(&compass, &Dimension::OVERWORLD),
+            Some(lodestone)
+        );
+
+        // It was destroyed
+        assert!(check_lodestone(
+            &mut compass,
+            &Dimension::OVERWORLD,
+            &Block::AIR
+        ));
+        let tracker = compass
+            .get_data_component::<LodestoneTrackerImpl>()
+            .unwrap();
+        assert_eq!(tracker.target, None);
+        assert!(tracker.tracked);
+        assert!(!check_lodestone(
+            &mut compass,
+            &Dimension::OVERWORLD,
+            &Block::AIR
+        ));
+    }
+}
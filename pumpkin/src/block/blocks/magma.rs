@@ -0,0 +1,42 @@
+use std::sync::atomic::Ordering;
+
+use pumpkin_data::Enchantment;
+use pumpkin_data::damage::DamageType;
+use pumpkin_data::data_component_impl::EquipmentSlot;
+use pumpkin_macros::pumpkin_block;
+
+use crate::block::{BlockBehaviour, BlockFuture, OnSteppedOnArgs};
+
+#[pumpkin_block("minecraft:magma_block")]
+pub struct MagmaBlock;
+
+impl BlockBehaviour for MagmaBlock {
+    fn on_stepped_on<'a>(&'a self, args: OnSteppedOnArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            let entity = args.entity.get_entity();
+            if entity.sneaking.load(Ordering::Relaxed) || entity.entity_type.fire_immune {
+                return;
+            }
+            let Some(living) = args.entity.get_living_entity() else {
+                return;
+            };
+            // Frost Walker boots protect from the hot floor
+            let boots = living
+                .entity_equipment
+                .lock()
+                .await
+                .get(&EquipmentSlot::FEET);
+            if boots
+                .lock()
+                .await
+                .get_enchantment_level(&Enchantment::FROST_WALKER)
+                > 0
+            {
+                return;
+            }
+            args.entity
+                .damage(args.entity, 1.0, DamageType::HOT_FLOOR)
+                .await;
+        })
+    }
+}
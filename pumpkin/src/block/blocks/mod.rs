@@ -2,6 +2,7 @@ pub mod anvil;
 pub mod barrel;
 pub mod barrier;
 pub mod bed;
+pub mod bell;
 pub mod cake;
 pub mod campfire;
 pub mod candle_cakes;
@@ -11,6 +12,8 @@ pub mod carved_pumpkin;
 pub mod chain;
 pub mod chests;
 pub mod chiseled_bookshelf;
+pub mod chorus_flower;
+pub mod chorus_plant;
 pub mod command;
 pub mod composter;
 pub mod crafting_table;
@@ -25,6 +28,7 @@ pub mod fence_gates;
 pub mod fences;
 pub mod fire;
 pub mod flower_pots;
+pub mod frosted_ice;
 pub mod furnace;
 pub mod glass_panes;
 pub mod glazed_terracotta;
@@ -34,7 +38,9 @@ pub mod iron_bars;
 pub mod jukebox;
 pub mod ladder;
 pub mod lanterns;
+pub mod lodestone;
 pub mod logs;
+pub mod magma;
 pub mod mangrove_roots;
 pub mod nether_portal;
 pub mod note;
@@ -42,6 +48,8 @@ pub mod piston;
 pub mod plant;
 pub mod pumpkin;
 pub mod redstone;
+pub mod respawn_anchor;
+pub mod scaffolding;
 pub mod signs;
 pub mod skull_block;
 pub mod slabs;
@@ -51,6 +59,7 @@ pub mod stairs;
 pub mod tnt;
 pub mod torches;
 pub mod trapdoor;
+pub mod turtle_egg;
 pub mod vine;
 pub mod walls;
 
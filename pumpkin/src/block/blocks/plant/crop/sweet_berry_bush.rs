@@ -10,6 +10,7 @@ use pumpkin_data::{
 };
 use pumpkin_macros::pumpkin_block;
 use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
 use pumpkin_world::{
     BlockStateId,
     item::ItemStack,
@@ -27,6 +28,9 @@ use crate::{
     world::World,
 };
 
+/// How much a sweet berry bush slows down the entities walking through it.
+const SLOWDOWN: Vector3<f64> = Vector3::new(0.8, 0.75, 0.8);
+
 #[pumpkin_block("minecraft:sweet_berry_bush")]
 pub struct SweetBerryBushBlock;
 
@@ -104,10 +108,15 @@ impl BlockBehaviour for SweetBerryBushBlock {
         Box::pin(async move {
             let entity = args.entity.get_entity();
 
-            if entity.entity_type == &EntityType::FOX || entity.entity_type == &EntityType::BEE {
+            if args.entity.get_living_entity().is_none()
+                || entity.entity_type == &EntityType::FOX
+                || entity.entity_type == &EntityType::BEE
+            {
                 return;
             }
 
+            entity.movement_multiplier.store(SLOWDOWN);
+
             let state_id = args.world.get_block_state_id(args.position).await;
             let props = NetherWartLikeProperties::from_state_id(state_id, args.block);
             if props.age == Integer0To3::L0 {
@@ -346,7 +346,7 @@ impl RedstoneWireTurbo {
     ) -> RedstoneWireProps {
         let mut wire = unwrap_wire(self.nodes[upd.index].state);
         let i = wire.power;
-        let mut block_power = 0;
+        let mut wire_power = 0;
 
         if self.nodes[upd.index].neighbors.is_none() {
             self.identify_neighbors(world, upd).await;
@@ -354,11 +354,11 @@ impl RedstoneWireTurbo {
 
         let pos = self.nodes[upd.index].pos;
 
-        let mut wire_power = 0;
+        let mut source_power = 0;
         for side in BlockDirection::all() {
             let neighbor_pos = pos.offset(side.to_offset());
             let neighbor = &self.nodes[self.node_cache[&neighbor_pos].index].state;
-            wire_power = wire_power.max(
+            source_power = source_power.max(
                 get_redstone_power_no_dust(
                     Block::from_state_id(neighbor.id),
                     neighbor,
@@ -370,7 +370,7 @@ impl RedstoneWireTurbo {
             );
         }
 
-        if wire_power < 15 {
+        if source_power < 15 {
             let neighbors = self.nodes[upd.index].neighbors.as_ref().unwrap();
 
             let center_up = self.nodes[neighbors[1].index].state;
@@ -380,22 +380,19 @@ impl RedstoneWireTurbo {
 
                 let neighbor_id = neighbors[n];
                 let neighbor = self.get_node(neighbor_id).state;
-                block_power = self.get_max_current_strength(neighbor_id, block_power);
+                wire_power = self.get_max_current_strength(neighbor_id, wire_power);
 
                 if !neighbor.is_solid_block() {
                     let neighbor_down = neighbors[Self::RS_NEIGHBORS_DN[m]];
-                    block_power = self.get_max_current_strength(neighbor_down, block_power);
+                    wire_power = self.get_max_current_strength(neighbor_down, wire_power);
                 } else if !center_up.is_solid_block() && neighbor.is_solid_block() {
                     let neighbor_up = neighbors[Self::RS_NEIGHBORS_UP[m]];
-                    block_power = self.get_max_current_strength(neighbor_up, block_power);
+                    wire_power = self.get_max_current_strength(neighbor_up, wire_power);
                 }
             }
         }
 
-        let mut j = block_power.saturating_sub(1);
-        if wire_power > j {
-            j = wire_power;
-        }
+        let j = resulting_power(source_power, wire_power);
         if i.to_index() as u8 != j {
             wire.power = Integer0To15::from_index(j.into());
             world
@@ -419,3 +416,138 @@ impl RedstoneWireTurbo {
         }
     }
 }
+
+/// The power of a wire next to `source_power` from non-wire blocks and connected to wires with at
+/// most `wire_power`. Power from wires drops by one per block, so it runs out after 15 wires.
+fn resulting_power(source_power: u8, wire_power: u8) -> u8 {
+    source_power.max(wire_power.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_data::block_properties::{
+        BlockFace, BlockProperties, EastWireConnection, EnumVariants, Integer0To15,
+        LeverLikeProperties, RedstoneOreLikeProperties, WestWireConnection,
+    };
+    use pumpkin_data::{Block, BlockDirection};
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+    use pumpkin_world::world::BlockFlags;
+
+    use super::{RedstoneWireProps, resulting_power, unwrap_wire};
+    use crate::block::BlockHitResult;
+    use crate::entity::player::Player;
+    use crate::server::Server;
+    use crate::world::World;
+
+    #[test]
+    fn power_drops_by_one_per_wire() {
+        let lever = 15;
+        let first = resulting_power(lever, 0);
+        let second = resulting_power(0, first);
+        let third = resulting_power(0, second);
+        assert_eq!([first, second, third], [15, 14, 13]);
+
+        // The 16th wire from the lever stays off
+        let mut power = resulting_power(lever, 0);
+        for _ in 0..15 {
+            power = resulting_power(0, power);
+        }
+        assert_eq!(power, 0);
+
+        // Unpowered wires stay off, and a stronger source wins over the wires
+        assert_eq!(resulting_power(0, 1), 0);
+        assert_eq!(resulting_power(0, 0), 0);
+        assert_eq!(resulting_power(12, 10), 12);
+        assert_eq!(resulting_power(3, 10), 9);
+    }
+
+    // High enough to be clear of the generated terrain
+    fn circuit_pos(x: i32) -> BlockPos {
+        BlockPos::new(x, 300, 0)
+    }
+
+    async fn flip_lever(server: &Server, world: &Arc<World>, player: &Player) {
+        let hit = BlockHitResult {
+            face: &BlockDirection::Up,
+            cursor_pos: &Vector3::new(0.5, 0.0, 0.5),
+        };
+        world
+            .block_registry
+            .on_use(&Block::LEVER, player, &circuit_pos(0), &hit, server, world)
+            .await;
+    }
+
+    async fn wire_powers(world: &World) -> Vec<u16> {
+        let mut powers = Vec::new();
+        for x in 1..=3 {
+            let state = world.get_block_state(&circuit_pos(x)).await;
+            powers.push(unwrap_wire(state).power.to_index());
+        }
+        powers
+    }
+
+    async fn lamp_lit(world: &World) -> bool {
+        let state = world.get_block_state(&circuit_pos(4)).await;
+        RedstoneOreLikeProperties::from_state_id(state.id, &Block::REDSTONE_LAMP).lit
+    }
+
+    #[tokio::test]
+    async fn lever_lights_lamp_through_wires() {
+        let (server, _world_dir) = Server::new_for_test().await;
+        let world = server.worlds.read().await[0].clone();
+        let (player, _packets) = Player::new_for_test(world.clone(), "Engineer").await;
+
+        for x in 0..=4 {
+            world
+                .set_block_state(
+                    &circuit_pos(x).down(),
+                    Block::STONE.default_state.id,
+                    BlockFlags::empty(),
+                )
+                .await;
+        }
+        let mut lever = LeverLikeProperties::default(&Block::LEVER);
+        lever.face = BlockFace::Floor;
+        lever.powered = false;
+        world
+            .set_block_state(
+                &circuit_pos(0),
+                lever.to_state_id(&Block::LEVER),
+                BlockFlags::empty(),
+            )
+            .await;
+        let mut wire = RedstoneWireProps::default(&Block::REDSTONE_WIRE);
+        wire.east = EastWireConnection::Side;
+        wire.west = WestWireConnection::Side;
+        wire.power = Integer0To15::L0;
+        for x in 1..=3 {
+            world
+                .set_block_state(
+                    &circuit_pos(x),
+                    wire.to_state_id(&Block::REDSTONE_WIRE),
+                    BlockFlags::empty(),
+                )
+                .await;
+        }
+        let mut lamp = RedstoneOreLikeProperties::default(&Block::REDSTONE_LAMP);
+        lamp.lit = false;
+        world
+            .set_block_state(
+                &circuit_pos(4),
+                lamp.to_state_id(&Block::REDSTONE_LAMP),
+                BlockFlags::empty(),
+            )
+            .await;
+
+        flip_lever(&server, &world, &player).await;
+        assert_eq!(wire_powers(&world).await, [15, 14, 13]);
+        assert!(lamp_lit(&world).await);
+
+        // The wires turn off at once, the lamp only on its scheduled tick
+        flip_lever(&server, &world, &player).await;
+        assert_eq!(wire_powers(&world).await, [0, 0, 0]);
+        assert!(lamp_lit(&world).await);
+    }
+}
@@ -0,0 +1,136 @@
+use pumpkin_data::Block;
+use pumpkin_data::block_properties::{
+    BlockProperties, EnumVariants, Integer0To4, RespawnAnchorLikeProperties,
+};
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::world::BlockFlags;
+
+use crate::block::registry::BlockActionResult;
+use crate::block::{BlockBehaviour, BlockFuture, NormalUseArgs, UseWithItemArgs};
+use crate::entity::EntityBase;
+
+/// How many times a respawn anchor can be charged with glowstone.
+pub const MAX_CHARGES: u16 = 4;
+/// How strong the explosion of a respawn anchor used outside of the nether is.
+pub const EXPLOSION_POWER: f32 = 5.0;
+
+#[pumpkin_block("minecraft:respawn_anchor")]
+pub struct RespawnAnchorBlock;
+
+impl BlockBehaviour for RespawnAnchorBlock {
+    fn use_with_item<'a>(
+        &'a self,
+        args: UseWithItemArgs<'a>,
+    ) -> BlockFuture<'a, BlockActionResult> {
+        Box::pin(async move {
+            let mut stack = args.item_stack.lock().await;
+            if stack.item != &Item::GLOWSTONE {
+                return BlockActionResult::PassToDefaultBlockAction;
+            }
+            let state_id = args.world.get_block_state_id(args.position).await;
+            let mut props = RespawnAnchorLikeProperties::from_state_id(state_id, args.block);
+            let Some(charges) = charged(props.charges) else {
+                return BlockActionResult::PassToDefaultBlockAction;
+            };
+            stack.decrement_unless_creative(args.player.gamemode.load(), 1);
+            drop(stack);
+
+            props.charges = charges;
+            args.world
+                .set_block_state(
+                    args.position,
+                    props.to_state_id(args.block),
+                    BlockFlags::NOTIFY_ALL,
+                )
+                .await;
+            args.world
+                .play_sound(
+                    Sound::BlockRespawnAnchorCharge,
+                    SoundCategory::Blocks,
+                    &args.position.to_centered_f64(),
+                )
+                .await;
+            BlockActionResult::Success
+        })
+    }
+
+    fn normal_use<'a>(&'a self, args: NormalUseArgs<'a>) -> BlockFuture<'a, BlockActionResult> {
+        Box::pin(async move {
+            let state_id = args.world.get_block_state_id(args.position).await;
+            let props = RespawnAnchorLikeProperties::from_state_id(state_id, args.block);
+            if props.charges.to_index() == 0 {
+                return BlockActionResult::Pass;
+            }
+
+            // Respawn anchors explode instead of setting the spawn point outside of the nether
+            if !args.world.dimension.respawn_anchor_works {
+                args.world
+                    .set_block_state(
+                        args.position,
+                        Block::AIR.default_state.id,
+                        BlockFlags::NOTIFY_ALL,
+                    )
+                    .await;
+                args.world
+                    .explode_with_fire(args.position.to_centered_f64(), EXPLOSION_POWER)
+                    .await;
+                return BlockActionResult::SuccessServer;
+            }
+
+            if args
+                .player
+                .set_respawn_point(
+                    args.world.dimension,
+                    *args.position,
+                    args.player.get_entity().yaw.load(),
+                    args.player.get_entity().pitch.load(),
+                )
+                .await
+            {
+                args.world
+                    .play_sound(
+                        Sound::BlockRespawnAnchorSetSpawn,
+                        SoundCategory::Blocks,
+                        &args.position.to_centered_f64(),
+                    )
+                    .await;
+                args.player
+                    .send_system_message(&TextComponent::translate("block.minecraft.set_spawn", []))
+                    .await;
+            }
+            BlockActionResult::SuccessServer
+        })
+    }
+}
+
+/// The charges after adding glowstone, `None` if the anchor is fully charged.
+#[must_use]
+pub fn charged(charges: Integer0To4) -> Option<Integer0To4> {
+    let charges = charges.to_index();
+    (charges < MAX_CHARGES).then(|| Integer0To4::from_index(charges + 1))
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::block_properties::{EnumVariants, Integer0To4};
+    use pumpkin_data::dimension::Dimension;
+
+    use super::{MAX_CHARGES, charged};
+
+    #[test]
+    fn respawn_anchors_only_work_in_the_nether() {
+        assert!(Dimension::THE_NETHER.respawn_anchor_works);
+        assert!(!Dimension::OVERWORLD.respawn_anchor_works);
+        assert!(!Dimension::THE_END.respawn_anchor_works);
+
+        let mut charges = Integer0To4::from_index(0);
+        for expected in 1..=MAX_CHARGES {
+            charges = charged(charges).unwrap();
+            assert_eq!(charges.to_index(), expected);
+        }
+        assert!(charged(charges).is_none());
+    }
+}
@@ -0,0 +1,203 @@
+use crate::block::{
+    BlockBehaviour, BlockFuture, CanPlaceAtArgs, GetStateForNeighborUpdateArgs, OnPlaceArgs,
+    OnScheduledTickArgs, PlacedArgs,
+};
+use crate::entity::falling::FallingEntity;
+use pumpkin_data::block_properties::{
+    BlockProperties, EnumVariants, Integer0To7, ScaffoldingLikeProperties,
+};
+use pumpkin_data::fluid::Fluid;
+use pumpkin_data::{Block, BlockDirection};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::tick::TickPriority;
+use pumpkin_world::world::{BlockAccessor, BlockFlags};
+
+/// Scaffolding this far from a supported column can't stand.
+pub const MAX_DISTANCE: u16 = 7;
+
+#[pumpkin_block("minecraft:scaffolding")]
+pub struct ScaffoldingBlock;
+
+impl BlockBehaviour for ScaffoldingBlock {
+    fn on_place<'a>(&'a self, args: OnPlaceArgs<'a>) -> BlockFuture<'a, BlockStateId> {
+        Box::pin(async move {
+            let distance = calculate_distance(args.world, args.position).await;
+            let mut props = ScaffoldingLikeProperties::default(args.block);
+            props.waterlogged = args.replacing.water_source();
+            props.distance = Integer0To7::from_index(distance);
+            props.bottom = should_be_bottom(args.world, args.position, distance).await;
+            props.to_state_id(args.block)
+        })
+    }
+
+    fn can_place_at<'a>(&'a self, args: CanPlaceAtArgs<'a>) -> BlockFuture<'a, bool> {
+        Box::pin(async move {
+            calculate_distance(args.block_accessor, args.position).await < MAX_DISTANCE
+        })
+    }
+
+    fn placed<'a>(&'a self, args: PlacedArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            args.world
+                .schedule_block_tick(args.block, *args.position, 1, TickPriority::Normal)
+                .await;
+        })
+    }
+
+    fn get_state_for_neighbor_update<'a>(
+        &'a self,
+        args: GetStateForNeighborUpdateArgs<'a>,
+    ) -> BlockFuture<'a, BlockStateId> {
+        Box::pin(async move {
+            let props = ScaffoldingLikeProperties::from_state_id(args.state_id, args.block);
+            if props.waterlogged {
+                args.world
+                    .schedule_fluid_tick(
+                        &Fluid::WATER,
+                        *args.position,
+                        Fluid::WATER.flow_speed as u8,
+                        TickPriority::Normal,
+                    )
+                    .await;
+            }
+            // The distance is updated in the scheduled tick, so a whole tower doesn't update at once
+            args.world
+                .schedule_block_tick(args.block, *args.position, 1, TickPriority::Normal)
+                .await;
+            args.state_id
+        })
+    }
+
+    fn on_scheduled_tick<'a>(&'a self, args: OnScheduledTickArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            let state_id = args.world.get_block_state_id(args.position).await;
+            let props = ScaffoldingLikeProperties::from_state_id(state_id, args.block);
+            let distance = calculate_distance(args.world.as_ref(), args.position).await;
+            let mut new_props = ScaffoldingLikeProperties::from_state_id(state_id, args.block);
+            new_props.distance = Integer0To7::from_index(distance);
+            new_props.bottom = should_be_bottom(args.world.as_ref(), args.position, distance).await;
+
+            if distance == MAX_DISTANCE {
+                if props.distance.to_index() == MAX_DISTANCE {
+                    FallingEntity::replace_spawn(
+                        args.world,
+                        *args.position,
+                        new_props.to_state_id(args.block),
+                    )
+                    .await;
+                } else {
+                    args.world
+                        .break_block(args.position, None, BlockFlags::empty())
+                        .await;
+                }
+            } else {
+                let new_state_id = new_props.to_state_id(args.block);
+                if new_state_id != state_id {
+                    args.world
+                        .set_block_state(args.position, new_state_id, BlockFlags::NOTIFY_ALL)
+                        .await;
+                }
+            }
+        })
+    }
+}
+
+/// How far scaffolding is from a supported column.
+///
+/// `below` is the distance of the scaffolding below, if there is one, and `neighbors` are the
+/// distances of the horizontally adjacent scaffolding.
+#[must_use]
+pub fn distance_from(below: Option<u16>, solid_below: bool, neighbors: &[Option<u16>]) -> u16 {
+    let mut distance = match below {
+        Some(distance) => distance,
+        None if solid_below => return 0,
+        None => MAX_DISTANCE,
+    };
+    for neighbor in neighbors.iter().flatten() {
+        distance = distance.min(neighbor + 1);
+        if distance == 1 {
+            break;
+        }
+    }
+    distance
+}
+
+fn scaffolding_distance(block: &Block, state_id: BlockStateId) -> Option<u16> {
+    (block == &Block::SCAFFOLDING).then(|| {
+        ScaffoldingLikeProperties::from_state_id(state_id, block)
+            .distance
+            .to_index()
+    })
+}
+
+async fn calculate_distance(world: &dyn BlockAccessor, position: &BlockPos) -> u16 {
+    let (below_block, below_state) = world.get_block_and_state(&position.down()).await;
+    let below = scaffolding_distance(below_block, below_state.id);
+    let solid_below = below_state.is_side_solid(BlockDirection::Up);
+
+    let mut neighbors = Vec::with_capacity(4);
+    for direction in BlockDirection::horizontal() {
+        let (block, state) = world
+            .get_block_and_state(&position.offset(direction.to_offset()))
+            .await;
+        neighbors.push(scaffolding_distance(block, state.id));
+    }
+    distance_from(below, solid_below, &neighbors)
+}
+
+/// Scaffolding which is held up by neighbors has a bottom frame, unless it is standing on
+/// scaffolding.
+async fn should_be_bottom(world: &dyn BlockAccessor, position: &BlockPos, distance: u16) -> bool {
+    distance > 0 && world.get_block(&position.down()).await != &Block::SCAFFOLDING
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::tag::{self, Taggable};
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{MAX_DISTANCE, distance_from};
+    use crate::entity::living::LivingEntity;
+
+    #[test]
+    fn scaffolding_breaks_beyond_the_support_distance() {
+        // Standing on the ground or on a supported column
+        assert_eq!(distance_from(None, true, &[None; 4]), 0);
+        assert_eq!(distance_from(Some(0), false, &[None; 4]), 0);
+
+        // Extending sideways from a supported column, one block further every time
+        let mut distance = 0;
+        for _ in 0..6 {
+            distance = distance_from(None, false, &[Some(distance), None, None, None]);
+        }
+        assert_eq!(distance, 6);
+        let beyond = distance_from(None, false, &[Some(distance), None, None, None]);
+        assert_eq!(beyond, MAX_DISTANCE);
+
+        // The closest neighbor counts
+        assert_eq!(
+            distance_from(None, false, &[Some(5), Some(0), None, None]),
+            1
+        );
+        // Nothing holds it up
+        assert_eq!(distance_from(None, false, &[None; 4]), MAX_DISTANCE);
+    }
+
+    #[test]
+    fn players_can_climb_scaffolding() {
+        assert!(Block::SCAFFOLDING.has_tag(&tag::Block::MINECRAFT_CLIMBABLE));
+
+        let up = LivingEntity::climbing_velocity(Vector3::new(0.3, 0.2, -0.3), false);
+        assert_eq!((up.x, up.y, up.z), (0.15, 0.2, -0.15));
+
+        // Falling is slowed down, players sneaking in scaffolding still climb down
+        let down = LivingEntity::climbing_velocity(Vector3::new(0.0, -0.5, 0.0), false);
+        assert_eq!(down.y, -0.15);
+        // While sneaking on a ladder holds on
+        let held = LivingEntity::climbing_velocity(Vector3::new(0.0, -0.5, 0.0), true);
+        assert_eq!(held.y, 0.0);
+    }
+}
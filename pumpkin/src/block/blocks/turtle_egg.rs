@@ -0,0 +1,275 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use pumpkin_data::block_properties::{BlockProperties, EnumVariants, Integer0To2, Integer1To4};
+use pumpkin_data::entity::{EntityPose, EntityType};
+use pumpkin_data::meta_data_type::MetaDataType;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_data::tag::Taggable;
+use pumpkin_data::tracked_data::TrackedData;
+use pumpkin_data::{Block, tag};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::java::client::play::Metadata;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::world::{BlockAccessor, BlockFlags};
+use rand::Rng;
+use uuid::Uuid;
+
+use crate::block::{
+    BlockBehaviour, BlockFuture, BlockIsReplacing, CanUpdateAtArgs, OnLandedUponArgs, OnPlaceArgs,
+    OnSteppedOnArgs, RandomTickArgs,
+};
+use crate::entity::EntityBase;
+use crate::entity::r#type::from_type;
+use crate::world::World;
+
+type TurtleEggProperties = pumpkin_data::block_properties::TurtleEggLikeProperties;
+
+#[pumpkin_block("minecraft:turtle_egg")]
+pub struct TurtleEggBlock;
+
+impl BlockBehaviour for TurtleEggBlock {
+    fn on_place<'a>(&'a self, args: OnPlaceArgs<'a>) -> BlockFuture<'a, BlockStateId> {
+        Box::pin(async move {
+            if let BlockIsReplacing::Itself(state_id) = args.replacing {
+                let mut props = TurtleEggProperties::from_state_id(state_id, args.block);
+                if props.eggs != Integer1To4::L4 {
+                    props.eggs = Integer1To4::from_index(props.eggs.to_index() + 1);
+                }
+                return props.to_state_id(args.block);
+            }
+            args.block.default_state.id
+        })
+    }
+
+    fn can_update_at<'a>(&'a self, args: CanUpdateAtArgs<'a>) -> BlockFuture<'a, bool> {
+        Box::pin(async move {
+            args.player.get_entity().pose.load() != EntityPose::Crouching
+                && TurtleEggProperties::from_state_id(args.state_id, args.block).eggs
+                    != Integer1To4::L4
+        })
+    }
+
+    fn random_tick<'a>(&'a self, args: RandomTickArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            let progresses = is_hatching_time(args.world.level_time.lock().await.sky_angle())
+                || rand::rng().random_range(0..500) == 0;
+            let state_id = args.world.get_block_state_id(args.position).await;
+            match tick_egg(args.world.as_ref(), args.position, state_id, progresses).await {
+                EggTick::Wait => {}
+                EggTick::Crack(cracked) => {
+                    play_egg_sound(args.world, args.position, Sound::EntityTurtleEggCrack).await;
+                    args.world
+                        .set_block_state(args.position, cracked, BlockFlags::NOTIFY_LISTENERS)
+                        .await;
+                }
+                EggTick::Hatch(turtles) => {
+                    play_egg_sound(args.world, args.position, Sound::EntityTurtleEggHatch).await;
+                    args.world
+                        .break_block(args.position, None, BlockFlags::SKIP_DROPS)
+                        .await;
+                    for i in 0..turtles {
+                        spawn_baby_turtle(args.world, args.position, i).await;
+                    }
+                }
+            }
+        })
+    }
+
+    fn on_stepped_on<'a>(&'a self, args: OnSteppedOnArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            if !args.entity.get_entity().sneaking.load(Ordering::Relaxed) {
+                try_break_egg(args.world, args.position, args.entity, 100).await;
+            }
+        })
+    }
+
+    fn on_landed_upon<'a>(&'a self, args: OnLandedUponArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async move {
+            if !is_zombie(args.entity.get_entity().entity_type) {
+                try_break_egg(args.world, args.position, args.entity, 3).await;
+            }
+            if let Some(living) = args.entity.get_living_entity() {
+                living.handle_fall_damage(args.fall_distance, 1.0).await;
+            }
+        })
+    }
+}
+
+/// What a random tick does to a turtle egg.
+#[derive(Debug, PartialEq, Eq)]
+pub enum EggTick {
+    Wait,
+    /// The eggs crack a bit more and get this state.
+    Crack(BlockStateId),
+    /// The eggs hatch into this many baby turtles.
+    Hatch(u8),
+}
+
+/// Turtle eggs progress faster around dawn, like in vanilla.
+#[must_use]
+pub fn is_hatching_time(sky_angle: f32) -> bool {
+    sky_angle > 0.65 && sky_angle < 0.69
+}
+
+/// Returns what happens to the eggs at `position` on a random tick. Eggs only progress on sand.
+pub async fn tick_egg(
+    block_accessor: &dyn BlockAccessor,
+    position: &BlockPos,
+    state_id: BlockStateId,
+    progresses: bool,
+) -> EggTick {
+    if !progresses
+        || !block_accessor
+            .get_block(&position.down())
+            .await
+            .has_tag(&tag::Block::MINECRAFT_SAND)
+    {
+        return EggTick::Wait;
+    }
+    let mut props = TurtleEggProperties::from_state_id(state_id, &Block::TURTLE_EGG);
+    if props.hatch == Integer0To2::L2 {
+        return EggTick::Hatch(props.eggs.to_index() as u8 + 1);
+    }
+    props.hatch = Integer0To2::from_index(props.hatch.to_index() + 1);
+    EggTick::Crack(props.to_state_id(&Block::TURTLE_EGG))
+}
+
+async fn try_break_egg(
+    world: &Arc<World>,
+    position: &BlockPos,
+    entity: &dyn EntityBase,
+    inverse_chance: i32,
+) {
+    if breaks_eggs(world, entity).await && rand::rng().random_range(0..inverse_chance) == 0 {
+        break_egg(world, position).await;
+    }
+}
+
+/// Players always trample eggs, other mobs only when `mobGriefing` is enabled.
+async fn breaks_eggs(world: &World, entity: &dyn EntityBase) -> bool {
+    let entity_type = entity.get_entity().entity_type;
+    if entity_type == &EntityType::TURTLE
+        || entity_type == &EntityType::BAT
+        || entity.get_living_entity().is_none()
+    {
+        return false;
+    }
+    entity.get_player().is_some() || world.level_info.read().await.game_rules.mob_griefing
+}
+
+async fn break_egg(world: &Arc<World>, position: &BlockPos) {
+    play_egg_sound(world, position, Sound::EntityTurtleEggBreak).await;
+    let (block, state_id) = world.get_block_and_state_id(position).await;
+    if block != &Block::TURTLE_EGG {
+        return;
+    }
+    let mut props = TurtleEggProperties::from_state_id(state_id, block);
+    if props.eggs == Integer1To4::L1 {
+        world
+            .break_block(position, None, BlockFlags::SKIP_DROPS)
+            .await;
+    } else {
+        props.eggs = Integer1To4::from_index(props.eggs.to_index() - 1);
+        world
+            .set_block_state(
+                position,
+                props.to_state_id(block),
+                BlockFlags::NOTIFY_LISTENERS,
+            )
+            .await;
+    }
+}
+
+async fn play_egg_sound(world: &World, position: &BlockPos, sound: Sound) {
+    let pitch = 0.9 + rand::rng().random::<f32>() * 0.2;
+    world
+        .play_sound_fine(
+            sound,
+            SoundCategory::Blocks,
+            &position.to_centered_f64(),
+            0.7,
+            pitch,
+        )
+        .await;
+}
+
+async fn spawn_baby_turtle(world: &Arc<World>, position: &BlockPos, index: u8) {
+    let spawn_pos = Vector3::new(
+        f64::from(position.0.x) + 0.3 + f64::from(index) * 0.2,
+        f64::from(position.0.y),
+        f64::from(position.0.z) + 0.3,
+    );
+    let turtle = from_type(&EntityType::TURTLE, spawn_pos, world, Uuid::new_v4()).await;
+    world.spawn_entity(turtle.clone()).await;
+    // TODO: Set the home position and growing age once turtles have AI
+    turtle
+        .get_entity()
+        .send_meta_data(&[Metadata::new(
+            TrackedData::DATA_BABY,
+            MetaDataType::Boolean,
+            true,
+        )])
+        .await;
+}
+
+fn is_zombie(entity_type: &EntityType) -> bool {
+    [
+        &EntityType::ZOMBIE,
+        &EntityType::DROWNED,
+        &EntityType::HUSK,
+        &EntityType::ZOMBIE_VILLAGER,
+        &EntityType::ZOMBIFIED_PIGLIN,
+    ]
+    .contains(&entity_type)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::block_properties::{BlockProperties, Integer0To2, Integer1To4};
+    use pumpkin_data::{Block, dimension::Dimension};
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_world::generation::proto_chunk::ProtoChunk;
+
+    use super::{EggTick, TurtleEggProperties, is_hatching_time, tick_egg};
+    use crate::world::time::LevelTime;
+
+    #[test]
+    fn incubated_eggs_hatch_at_night_on_sand() {
+        let mut chunk = ProtoChunk::new(0, 0, &Dimension::OVERWORLD, Block::STONE.default_state, 0);
+        let position = BlockPos::new(8, 64, 8);
+        chunk.set_block_state(8, 63, 8, Block::SAND.default_state);
+
+        let mut props = TurtleEggProperties::default(&Block::TURTLE_EGG);
+        props.eggs = Integer1To4::L2;
+        props.hatch = Integer0To2::L2;
+        let incubated = props.to_state_id(&Block::TURTLE_EGG);
+
+        let mut time = LevelTime::new();
+        time.set_time(21500);
+        assert!(is_hatching_time(time.sky_angle()));
+        time.set_time(6000);
+        assert!(!is_hatching_time(time.sky_angle()));
+
+        let tick = futures::executor::block_on(tick_egg(&chunk, &position, incubated, true));
+        assert_eq!(tick, EggTick::Hatch(2));
+
+        let fresh = Block::TURTLE_EGG.default_state.id;
+        let EggTick::Crack(cracked) =
+            futures::executor::block_on(tick_egg(&chunk, &position, fresh, true))
+        else {
+            panic!("fresh eggs should crack");
+        };
+        assert_eq!(
+            TurtleEggProperties::from_state_id(cracked, &Block::TURTLE_EGG).hatch,
+            Integer0To2::L1
+        );
+
+        // Eggs don't progress away from sand
+        chunk.set_block_state(8, 63, 8, Block::DIRT.default_state);
+        let tick = futures::executor::block_on(tick_egg(&chunk, &position, incubated, true));
+        assert_eq!(tick, EggTick::Wait);
+    }
+}
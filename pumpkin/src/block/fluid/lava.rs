@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use pumpkin_data::{
     Block, BlockDirection,
+    damage::DamageType,
     dimension::Dimension,
     fluid::{Falling, Fluid, FluidProperties, Level},
     world::WorldEvent,
@@ -133,6 +134,7 @@ impl FluidBehaviour for FlowingLava {
             let base_entity = entity.get_entity();
             if !base_entity.entity_type.fire_immune {
                 base_entity.set_on_fire_for(15.0);
+                entity.damage(entity, 4.0, DamageType::LAVA).await;
             }
         })
     }
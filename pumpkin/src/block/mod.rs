@@ -1,5 +1,6 @@
+use pumpkin_config::world::BlockBreakingConfig;
 use pumpkin_data::block_properties::Integer0To15;
-use pumpkin_data::{Block, BlockState};
+use pumpkin_data::{Block, BlockState, Enchantment};
 
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::random::{RandomGenerator, get_seed, xoroshiro128::Xoroshiro};
@@ -55,6 +56,11 @@ pub trait BlockBehaviour: Send + Sync {
         Box::pin(async {})
     }
 
+    /// Called every tick for an entity standing on the block.
+    fn on_stepped_on<'a>(&'a self, _args: OnSteppedOnArgs<'a>) -> BlockFuture<'a, ()> {
+        Box::pin(async {})
+    }
+
     fn should_drop_items_on_explosion(&self) -> bool {
         true
     }
@@ -202,6 +208,14 @@ pub struct OnEntityCollisionArgs<'a> {
     pub entity: &'a dyn EntityBase,
 }
 
+pub struct OnSteppedOnArgs<'a> {
+    pub world: &'a Arc<World>,
+    pub block: &'a Block,
+    pub state: &'a BlockState,
+    pub position: &'a BlockPos,
+    pub entity: &'a dyn EntityBase,
+}
+
 pub struct ExplodeArgs<'a> {
     pub world: &'a Arc<World>,
     pub block: &'a Block,
@@ -274,6 +288,7 @@ pub struct PlayerPlacedArgs<'a> {
 
 pub struct OnLandedUponArgs<'a> {
     pub world: &'a Arc<World>,
+    pub position: &'a BlockPos,
     pub fall_distance: f32,
     pub entity: &'a dyn EntityBase,
 }
@@ -362,16 +377,23 @@ pub async fn drop_loot(
     experience: bool,
     params: LootContextParameters,
 ) {
+    // Blocks mined with silk touch drop themselves instead of experience
+    let silk_touch = params
+        .tool
+        .as_ref()
+        .is_some_and(|tool| tool.get_enchantment_level(&Enchantment::SILK_TOUCH) > 0);
     if let Some(loot_table) = &block.loot_table {
         for stack in loot_table.get_loot(params) {
             world.drop_stack(pos, stack).await;
         }
     }
 
-    if experience && let Some(experience) = &block.experience {
+    if experience
+        && !silk_touch
+        && let Some(experience) = &block.experience
+    {
         let mut random = RandomGenerator::Xoroshiro(Xoroshiro::from_seed(get_seed()));
         let amount = experience.experience.get(&mut random);
-        // TODO: Silk touch gives no exp
         if amount > 0 {
             ExperienceOrbEntity::spawn(world, pos.to_f64(), amount as u32).await;
         }
@@ -398,6 +420,29 @@ pub async fn calc_block_breaking(
     player.get_mining_speed(block).await / hardness / i
 }
 
+/// How many ticks breaking a block at `speed` progress per tick takes, like
+/// `AbstractBlock.calcBlockBreakingDelta()` is summed up by the client. `0` means it breaks
+/// instantly, `None` that it never breaks.
+#[must_use]
+pub fn break_ticks(speed: f32) -> Option<u32> {
+    if speed <= 0.0 {
+        None
+    } else if speed >= 1.0 {
+        Some(0)
+    } else {
+        Some((1.0 / speed).ceil() as u32)
+    }
+}
+
+/// Whether a block breaking at `speed` progress per tick may be broken after `elapsed_ticks`,
+/// allowing for the latency leniency of `config`.
+#[must_use]
+pub fn is_break_in_time(speed: f32, elapsed_ticks: i32, config: &BlockBreakingConfig) -> bool {
+    // The tick the player started mining in counts as well
+    let ticks = elapsed_ticks.max(0) as f32 + 1.0 + config.grace_ticks as f32;
+    speed * ticks >= 1.0 - config.leniency.clamp(0.0, 1.0)
+}
+
 #[derive(PartialEq, Debug)]
 pub enum BlockIsReplacing {
     Itself(BlockStateId),
@@ -417,3 +462,44 @@ impl BlockIsReplacing {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::world::BlockBreakingConfig;
+
+    use super::{break_ticks, is_break_in_time};
+
+    #[test]
+    fn lenient_breaking_accepts_laggy_players() {
+        // Stone with a wooden pickaxe takes 8 ticks
+        let speed = 0.125;
+        let strict = BlockBreakingConfig {
+            validate: true,
+            leniency: 0.0,
+            grace_ticks: 0,
+        };
+        let lenient = BlockBreakingConfig {
+            validate: true,
+            leniency: 0.5,
+            grace_ticks: 2,
+        };
+
+        assert!(is_break_in_time(speed, 7, &strict));
+        // One tick early
+        assert!(!is_break_in_time(speed, 6, &strict));
+        assert!(is_break_in_time(speed, 6, &lenient));
+        // Instantly mining is still too early
+        assert!(!is_break_in_time(speed, 0, &lenient));
+    }
+
+    #[test]
+    fn break_ticks_match_vanilla() {
+        // Stone with a wooden pickaxe: 2 speed / 1.5 hardness / 30
+        assert_eq!(break_ticks(2.0 / 1.5 / 30.0), Some(23));
+        // Stone by hand can't be harvested: 1 / 1.5 / 100
+        assert_eq!(break_ticks(1.0 / 1.5 / 100.0), Some(150));
+        assert_eq!(break_ticks(1.5), Some(0));
+        // Bedrock
+        assert_eq!(break_ticks(0.0), None);
+    }
+}
@@ -3,9 +3,12 @@ use crate::block::blocks::anvil::AnvilBlock;
 use crate::block::blocks::barrel::BarrelBlock;
 use crate::block::blocks::barrier::BarrierBlock;
 use crate::block::blocks::bed::BedBlock;
+use crate::block::blocks::bell::BellBlock;
 use crate::block::blocks::carpet::{CarpetBlock, MossCarpetBlock, PaleMossCarpetBlock};
 use crate::block::blocks::carved_pumpkin::CarvedPumpkinBlock;
 use crate::block::blocks::chests::ChestBlock;
+use crate::block::blocks::chorus_flower::ChorusFlowerBlock;
+use crate::block::blocks::chorus_plant::ChorusPlantBlock;
 use crate::block::blocks::command::CommandBlock;
 use crate::block::blocks::composter::ComposterBlock;
 use crate::block::blocks::dirt_path::DirtPathBlock;
@@ -18,12 +21,15 @@ use crate::block::blocks::fence_gates::FenceGateBlock;
 use crate::block::blocks::fences::FenceBlock;
 use crate::block::blocks::fire::fire::FireBlock;
 use crate::block::blocks::fire::soul_fire::SoulFireBlock;
+use crate::block::blocks::frosted_ice::FrostedIceBlock;
 use crate::block::blocks::furnace::FurnaceBlock;
 use crate::block::blocks::glass_panes::GlassPaneBlock;
 use crate::block::blocks::grindstone::GrindstoneBlock;
 use crate::block::blocks::hay::HayBlock;
 use crate::block::blocks::iron_bars::IronBarsBlock;
+use crate::block::blocks::lodestone::LodestoneBlock;
 use crate::block::blocks::logs::LogBlock;
+use crate::block::blocks::magma::MagmaBlock;
 use crate::block::blocks::mangrove_roots::MangroveRootsBlock;
 use crate::block::blocks::nether_portal::NetherPortalBlock;
 use crate::block::blocks::note::NoteBlock;
@@ -66,6 +72,8 @@ use crate::block::blocks::redstone::repeater::RepeaterBlock;
 use crate::block::blocks::redstone::target_block::TargetBlock;
 use crate::block::blocks::redstone::tripwire::TripwireBlock;
 use crate::block::blocks::redstone::tripwire_hook::TripwireHookBlock;
+use crate::block::blocks::respawn_anchor::RespawnAnchorBlock;
+use crate::block::blocks::scaffolding::ScaffoldingBlock;
 use crate::block::blocks::signs::SignBlock;
 use crate::block::blocks::slabs::SlabBlock;
 use crate::block::blocks::snow::LayeredSnowBlock;
@@ -74,12 +82,14 @@ use crate::block::blocks::stairs::StairBlock;
 use crate::block::blocks::tnt::TNTBlock;
 use crate::block::blocks::torches::TorchBlock;
 use crate::block::blocks::trapdoor::TrapDoorBlock;
+use crate::block::blocks::turtle_egg::TurtleEggBlock;
 use crate::block::blocks::vine::VineBlock;
 use crate::block::blocks::walls::WallBlock;
 use crate::block::fluid::lava::FlowingLava;
 use crate::block::fluid::water::FlowingWater;
 use crate::block::{
     BlockBehaviour, BlockHitResult, BlockMetadata, OnEntityCollisionArgs, OnLandedUponArgs,
+    OnSteppedOnArgs,
 };
 use crate::entity::EntityBase;
 use crate::entity::player::Player;
@@ -149,8 +159,11 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     // Blocks
     manager.register(AnvilBlock);
     manager.register(BedBlock);
+    manager.register(BellBlock);
     manager.register(SaplingBlock);
     manager.register(CactusBlock);
+    manager.register(ChorusFlowerBlock);
+    manager.register(ChorusPlantBlock);
     manager.register(CarpetBlock);
     manager.register(CarvedPumpkinBlock);
     manager.register(CampfireBlock);
@@ -165,6 +178,7 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     manager.register(FenceGateBlock);
     manager.register(FenceBlock);
     manager.register(FlowerPotBlock);
+    manager.register(FrostedIceBlock);
     manager.register(FurnaceBlock);
     manager.register(BlastFurnaceBlock);
     manager.register(SmokerBlock);
@@ -174,7 +188,10 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     manager.register(GrindstoneBlock);
     manager.register(IronBarsBlock);
     manager.register(JukeboxBlock);
+    manager.register(LodestoneBlock);
+    manager.register(RespawnAnchorBlock);
     manager.register(LogBlock);
+    manager.register(MagmaBlock);
     manager.register(BambooBlock);
     manager.register(BambooSaplingBlock);
     manager.register(BannerBlock);
@@ -217,6 +234,7 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     manager.register(EndPortalFrameBlock);
     manager.register(CandleBlock);
     manager.register(SeaPickleBlock);
+    manager.register(TurtleEggBlock);
     manager.register(CakeBlock);
     manager.register(CandleCakeBlock);
     manager.register(SkullBlock);
@@ -230,6 +248,7 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     manager.register(BarrierBlock);
     manager.register(MangroveRootsBlock);
     manager.register(LayeredSnowBlock);
+    manager.register(ScaffoldingBlock);
 
     manager.register(FallingBlock);
 
@@ -398,6 +417,28 @@ impl BlockRegistry {
         }
     }
 
+    pub async fn on_stepped_on(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        entity: &dyn EntityBase,
+        position: &BlockPos,
+        state: &BlockState,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block.id);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_stepped_on(OnSteppedOnArgs {
+                    world,
+                    block,
+                    state,
+                    position,
+                    entity,
+                })
+                .await;
+        }
+    }
+
     pub async fn on_entity_collision_fluid(&self, fluid: &Fluid, entity: &dyn EntityBase) {
         let pumpkin_fluid = self.get_pumpkin_fluid(fluid.id);
         if let Some(pumpkin_fluid) = pumpkin_fluid {
@@ -646,6 +687,7 @@ impl BlockRegistry {
         &self,
         block: &Block,
         world: &Arc<World>,
+        position: &BlockPos,
         fall_distance: f32,
         entity: &dyn EntityBase,
     ) {
@@ -654,6 +696,7 @@ impl BlockRegistry {
             pumpkin_block
                 .on_landed_upon(OnLandedUponArgs {
                     world,
+                    position,
                     fall_distance,
                     entity,
                 })
@@ -1,7 +1,11 @@
 use pumpkin_data::Block;
 use pumpkin_data::tag::{RegistryKey, get_tag_ids};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_nbt::snbt::from_snbt;
+use pumpkin_nbt::tag::NbtTag;
 use pumpkin_protocol::java::client::play::{ArgumentType, SuggestionProviders};
 use pumpkin_util::text::TextComponent;
+use pumpkin_world::BlockStateId;
 
 use crate::command::args::ConsumeResult;
 use crate::{command::dispatcher::CommandError, server::Server};
@@ -33,11 +37,19 @@ impl ArgumentConsumer for BlockArgumentConsumer {
         _server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> ConsumeResult<'a> {
-        let block = args.pop();
-        match block {
-            Some(s) => Box::pin(async move { Some(Arg::Block(s)) }),
-            None => Box::pin(async move { None }),
+        let Some(first_word) = args.pop() else {
+            return Box::pin(async { None });
+        };
+        // Block entity NBT can contain spaces
+        let mut block = first_word.to_string();
+        while !is_closed(&block) {
+            let Some(word) = args.pop() else {
+                break;
+            };
+            block.push(' ');
+            block.push_str(word);
         }
+        Box::pin(async move { Some(Arg::BlockState(block)) })
     }
 }
 
@@ -48,31 +60,191 @@ impl DefaultNameArgConsumer for BlockArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for BlockArgumentConsumer {
-    type Data = &'static Block;
+    type Data = BlockStateArgument;
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Block(name)) => Block::from_name(name).map_or_else(
-                || {
-                    if name.starts_with("minecraft:") {
-                        Err(CommandError::CommandFailed(TextComponent::translate(
-                            "argument.block.id.invalid",
-                            [TextComponent::text((*name).to_string())],
-                        )))
-                    } else {
-                        Err(CommandError::CommandFailed(TextComponent::translate(
-                            "argument.block.id.invalid",
-                            [TextComponent::text("minecraft:".to_string() + *name)],
-                        )))
-                    }
-                },
-                Result::Ok,
-            ),
+            Some(Arg::BlockState(block)) => parse_block_state(block),
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
 }
 
+/// A block state as written in commands, like `oak_log[axis=x]` or `chest[facing=west]{Lock:{}}`.
+#[derive(Debug)]
+pub struct BlockStateArgument {
+    pub block: &'static Block,
+    /// The state with the given properties, unspecified properties keep their default value.
+    pub state_id: BlockStateId,
+    /// The NBT to apply to the block entity.
+    pub nbt: Option<NbtCompound>,
+}
+
+/// Parses a block state argument, see [`BlockStateArgument`].
+pub fn parse_block_state(input: &str) -> Result<BlockStateArgument, CommandError> {
+    let (name, rest) = input.split_at(input.find(['[', '{']).unwrap_or(input.len()));
+    let block = find_block(name)?;
+    let (props, nbt) = match rest.strip_prefix('[') {
+        Some(rest) => {
+            let (props, rest) = rest.split_once(']').ok_or_else(|| {
+                CommandError::CommandFailed(TextComponent::translate(
+                    "argument.block.property.unclosed",
+                    [],
+                ))
+            })?;
+            (parse_properties(block, props)?, rest)
+        }
+        None => (Vec::new(), rest),
+    };
+    let state_id = find_state(block, &props)?;
+    let nbt = if nbt.is_empty() {
+        None
+    } else {
+        match from_snbt(nbt) {
+            Ok(NbtTag::Compound(compound)) => Some(compound),
+            _ => {
+                return Err(CommandError::CommandFailed(TextComponent::translate(
+                    "argument.nbt.expected.compound",
+                    [],
+                )));
+            }
+        }
+    };
+    Ok(BlockStateArgument {
+        block,
+        state_id,
+        nbt,
+    })
+}
+
+/// Whether all brackets and braces of the input are closed.
+fn is_closed(input: &str) -> bool {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in input.chars() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '[' | '{' => depth += 1,
+            ']' | '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth <= 0 && quote.is_none()
+}
+
+fn find_block(name: &str) -> Result<&'static Block, CommandError> {
+    Block::from_name(name).ok_or_else(|| {
+        let name = if name.starts_with("minecraft:") {
+            name.to_string()
+        } else {
+            "minecraft:".to_string() + name
+        };
+        CommandError::CommandFailed(TextComponent::translate(
+            "argument.block.id.invalid",
+            [TextComponent::text(name)],
+        ))
+    })
+}
+
+fn property_error(key: &str, args: [&str; 2]) -> CommandError {
+    CommandError::CommandFailed(TextComponent::translate(
+        key,
+        args.map(|arg| TextComponent::text(arg.to_string())),
+    ))
+}
+
+/// Parses properties like `axis=x,waterlogged=true`, every property has to exist on the block.
+fn parse_properties<'a>(
+    block: &Block,
+    input: &'a str,
+) -> Result<Vec<(&'a str, &'a str)>, CommandError> {
+    let known = block
+        .properties(block.default_state.id)
+        .map(|properties| properties.to_props())
+        .unwrap_or_default();
+    let mut props: Vec<(&str, &str)> = Vec::new();
+    for property in input.split(',') {
+        let property = property.trim();
+        if property.is_empty() {
+            continue;
+        }
+        let Some((key, value)) = property.split_once('=') else {
+            return Err(property_error(
+                "argument.block.property.novalue",
+                [property, block.name],
+            ));
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if !known.iter().any(|(name, _)| *name == key) {
+            return Err(property_error(
+                "argument.block.property.unknown",
+                [block.name, key],
+            ));
+        }
+        if props.iter().any(|(name, _)| *name == key) {
+            return Err(property_error(
+                "argument.block.property.duplicate",
+                [key, block.name],
+            ));
+        }
+        props.push((key, value));
+    }
+    Ok(props)
+}
+
+/// Looks up the state of `block` with the given properties. Like `Block::from_properties`, but
+/// reports invalid values instead of panicking.
+fn find_state(block: &Block, props: &[(&str, &str)]) -> Result<BlockStateId, CommandError> {
+    let Some(default) = block.properties(block.default_state.id) else {
+        return Ok(block.default_state.id);
+    };
+    let wanted = default
+        .to_props()
+        .into_iter()
+        .map(|(key, default)| {
+            let value = props
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map_or(default, |(_, value)| *value);
+            (key, value)
+        })
+        .collect::<Vec<_>>();
+    let states = || {
+        block
+            .states
+            .iter()
+            .filter_map(|state| Some((state.id, block.properties(state.id)?.to_props())))
+    };
+    if let Some((id, _)) = states().find(|(_, state_props)| *state_props == wanted) {
+        return Ok(id);
+    }
+    let invalid = props.iter().find(|(key, value)| {
+        !states().any(|(_, state_props)| {
+            state_props
+                .iter()
+                .any(|property| *property == (*key, *value))
+        })
+    });
+    let Some((key, value)) = invalid.or(props.first()) else {
+        return Ok(block.default_state.id);
+    };
+    Err(property_error(
+        "argument.block.property.invalid",
+        [block.name, value, key],
+    ))
+}
+
 pub struct BlockPredicateArgumentConsumer;
 #[derive(Debug)]
 pub enum BlockPredicate {
@@ -117,24 +289,7 @@ impl<'a> FindArg<'a> for BlockPredicateArgumentConsumer {
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
             Some(Arg::BlockPredicate(name)) => name.strip_prefix("#").map_or_else(
-                || {
-                    Block::from_name(name).map_or_else(
-                        || {
-                            if name.starts_with("minecraft:") {
-                                Err(CommandError::CommandFailed(TextComponent::translate(
-                                    "argument.block.id.invalid",
-                                    [TextComponent::text((*name).to_string())],
-                                )))
-                            } else {
-                                Err(CommandError::CommandFailed(TextComponent::translate(
-                                    "argument.block.id.invalid",
-                                    [TextComponent::text("minecraft:".to_string() + *name)],
-                                )))
-                            }
-                        },
-                        |block| Ok(Some(BlockPredicate::Block(block.id))),
-                    )
-                },
+                || find_block(name).map(|block| Some(BlockPredicate::Block(block.id))),
                 |tag| {
                     get_tag_ids(RegistryKey::Block, tag).map_or_else(
                         || {
@@ -151,3 +306,39 @@ impl<'a> FindArg<'a> for BlockPredicateArgumentConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+
+    use super::{is_closed, parse_block_state};
+
+    #[test]
+    fn parses_block_states() {
+        let log = parse_block_state("minecraft:oak_log[axis=x]").unwrap();
+        assert_eq!(log.block, &Block::OAK_LOG);
+        let props = Block::OAK_LOG.properties(log.state_id).unwrap().to_props();
+        assert!(props.contains(&("axis", "x")));
+        assert!(log.nbt.is_none());
+
+        let stone = parse_block_state("stone").unwrap();
+        assert_eq!(stone.state_id, Block::STONE.default_state.id);
+
+        let chest = parse_block_state("chest[facing=west]{Lock:{}}").unwrap();
+        assert!(
+            chest
+                .nbt
+                .is_some_and(|nbt| nbt.get_compound("Lock").is_some())
+        );
+
+        assert!(parse_block_state("not_a_block").is_err());
+        assert!(parse_block_state("oak_log[axis=q]").is_err());
+        assert!(parse_block_state("oak_log[color=red]").is_err());
+        assert!(parse_block_state("oak_log[axis=x,axis=y]").is_err());
+        assert!(parse_block_state("oak_log[axis=x").is_err());
+        assert!(parse_block_state("chest{Lock:").is_err());
+
+        assert!(is_closed("chest{CustomName:\"a }\"}"));
+        assert!(!is_closed("chest{CustomName:"));
+    }
+}
@@ -34,6 +34,16 @@ impl<const IS_Y: bool> MaybeRelativeCoordinate<IS_Y> {
     }
 }
 
+/// Parses a `^` coordinate, which is relative to where the command source is looking.
+pub fn parse_local_coordinate(s: &str) -> Option<f64> {
+    let offset = s.strip_prefix('^')?;
+    if offset.is_empty() {
+        Some(0.0)
+    } else {
+        offset.parse().ok()
+    }
+}
+
 #[derive(Debug)]
 pub enum MaybeRelativeBlockCoordinate<const IS_Y: bool> {
     Absolute(i32),
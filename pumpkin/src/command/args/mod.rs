@@ -5,8 +5,8 @@ use bounded_num::{NotInBounds, Number};
 use pumpkin_data::Enchantment;
 use pumpkin_data::damage::DamageType;
 use pumpkin_data::effect::StatusEffect;
-use pumpkin_data::particle::Particle;
 use pumpkin_data::sound::SoundCategory;
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_protocol::java::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
 use pumpkin_util::Difficulty;
 use pumpkin_util::text::TextComponent;
@@ -36,6 +36,7 @@ pub mod entities;
 pub mod entity;
 pub mod gamemode;
 pub mod message;
+pub mod nbt;
 pub mod players;
 pub mod position_2d;
 pub mod position_3d;
@@ -101,12 +102,15 @@ pub enum Arg<'a> {
     Difficulty(Difficulty),
     CommandTree(CommandTree),
     Item(&'a str),
+    ItemPredicate(&'a str),
     ResourceLocation(&'a str),
     Block(&'a str),
+    /// A block state with its properties and block entity NBT, which can span several words.
+    BlockState(String),
     BlockPredicate(&'a str),
     BossbarColor(BossbarColor),
     BossbarStyle(BossbarDivisions),
-    Particle(Particle),
+    Particle(&'a str),
     Msg(String),
     TextComponent(TextComponent),
     Time(i32),
@@ -117,6 +121,7 @@ pub enum Arg<'a> {
     DamageType(DamageType),
     Effect(&'static StatusEffect),
     Enchantment(&'static Enchantment),
+    Nbt(NbtCompound),
 }
 
 /// see [`crate::commands::tree::builder::argument`] and [`CommandTree::execute`]/[`crate::commands::tree::builder::NonLeafNodeBuilder::execute`]
@@ -0,0 +1,72 @@
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_nbt::snbt::from_snbt;
+use pumpkin_nbt::tag::NbtTag;
+use pumpkin_protocol::java::client::play::{ArgumentType, SuggestionProviders};
+
+use crate::{
+    command::{args::ConsumeResult, dispatcher::CommandError},
+    server::Server,
+};
+
+use super::{
+    super::{
+        CommandSender,
+        args::{ArgumentConsumer, RawArgs},
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// Consumes an SNBT compound like `{Health:5f,CustomName:"Bob"}`. Compounds can contain spaces,
+/// so all remaining words are consumed.
+pub struct NbtCompoundArgumentConsumer;
+
+impl GetClientSideArgParser for NbtCompoundArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType<'_> {
+        ArgumentType::Nbt
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+impl ArgumentConsumer for NbtCompoundArgumentConsumer {
+    fn consume<'a>(
+        &'a self,
+        _sender: &'a CommandSender,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> ConsumeResult<'a> {
+        let Some(first_word) = args.pop() else {
+            return Box::pin(async { None });
+        };
+        let mut snbt = first_word.to_string();
+        while let Some(word) = args.pop() {
+            snbt.push(' ');
+            snbt.push_str(word);
+        }
+
+        let compound = match from_snbt(&snbt) {
+            Ok(NbtTag::Compound(compound)) => Some(compound),
+            _ => None,
+        };
+        Box::pin(async move { compound.map(Arg::Nbt) })
+    }
+}
+
+impl DefaultNameArgConsumer for NbtCompoundArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "nbt"
+    }
+}
+
+impl<'a> FindArg<'a> for NbtCompoundArgumentConsumer {
+    type Data = &'a NbtCompound;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Nbt(compound)) => Ok(compound),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -8,7 +8,7 @@ use crate::command::tree::RawArgs;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
-use super::coordinate::MaybeRelativeCoordinate;
+use super::coordinate::{MaybeRelativeCoordinate, parse_local_coordinate};
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
 /// x, y and z coordinates
@@ -39,9 +39,16 @@ impl ArgumentConsumer for Position3DArgumentConsumer {
             return Box::pin(async move { None });
         };
 
-        let result: Option<Arg<'a>> = MaybeRelativePosition3D::try_new(x_str, y_str, z_str)
-            .and_then(|pos| pos.try_to_absolute(sender.position()))
-            .map(Arg::Pos3D);
+        let result: Option<Arg<'a>> = if x_str.starts_with('^') {
+            LocalPosition3D::try_new(x_str, y_str, z_str).and_then(|pos| {
+                let (yaw, pitch) = sender.rotation()?;
+                Some(pos.to_absolute(sender.position()?, yaw, pitch))
+            })
+        } else {
+            MaybeRelativePosition3D::try_new(x_str, y_str, z_str)
+                .and_then(|pos| pos.try_to_absolute(sender.position()))
+        }
+        .map(Arg::Pos3D);
 
         Box::pin(async move { result })
     }
@@ -71,6 +78,49 @@ impl MaybeRelativePosition3D {
     }
 }
 
+/// `^left ^up ^forwards`, relative to the position and view direction of the command source.
+struct LocalPosition3D {
+    left: f64,
+    up: f64,
+    forwards: f64,
+}
+
+impl LocalPosition3D {
+    fn try_new(x: &str, y: &str, z: &str) -> Option<Self> {
+        // Local coordinates can't be mixed with other ones
+        Some(Self {
+            left: parse_local_coordinate(x)?,
+            up: parse_local_coordinate(y)?,
+            forwards: parse_local_coordinate(z)?,
+        })
+    }
+
+    fn to_absolute(&self, origin: Vector3<f64>, yaw: f32, pitch: f32) -> Vector3<f64> {
+        let yaw = f64::from(yaw + 90.0).to_radians();
+        let pitch = f64::from(-pitch).to_radians();
+        let up_pitch = pitch + 90f64.to_radians();
+
+        let forwards = Vector3::new(
+            yaw.cos() * pitch.cos(),
+            pitch.sin(),
+            yaw.sin() * pitch.cos(),
+        );
+        let up = Vector3::new(
+            yaw.cos() * up_pitch.cos(),
+            up_pitch.sin(),
+            yaw.sin() * up_pitch.cos(),
+        );
+        // left = up x forwards
+        let left = Vector3::new(
+            up.y * forwards.z - up.z * forwards.y,
+            up.z * forwards.x - up.x * forwards.z,
+            up.x * forwards.y - up.y * forwards.x,
+        );
+
+        origin + forwards * self.forwards + up * self.up + left * self.left
+    }
+}
+
 impl DefaultNameArgConsumer for Position3DArgumentConsumer {
     fn default_name(&self) -> &'static str {
         "pos"
@@ -87,3 +137,52 @@ impl<'a> FindArg<'a> for Position3DArgumentConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::LocalPosition3D;
+
+    fn assert_close(actual: Vector3<f64>, expected: Vector3<f64>) {
+        assert!(
+            (actual.x - expected.x).abs() < 1e-6
+                && (actual.y - expected.y).abs() < 1e-6
+                && (actual.z - expected.z).abs() < 1e-6,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn local_coordinates_follow_view_direction() {
+        let origin = Vector3::new(10.0, 64.0, -5.0);
+        let local = |x: &str, y: &str, z: &str| LocalPosition3D::try_new(x, y, z).unwrap();
+
+        // Yaw 0 looks south, so left is east
+        assert_close(
+            local("^", "^", "^2").to_absolute(origin, 0.0, 0.0),
+            Vector3::new(10.0, 64.0, -3.0),
+        );
+        assert_close(
+            local("^1", "^", "^").to_absolute(origin, 0.0, 0.0),
+            Vector3::new(11.0, 64.0, -5.0),
+        );
+        assert_close(
+            local("^", "^1", "^").to_absolute(origin, 0.0, 0.0),
+            Vector3::new(10.0, 65.0, -5.0),
+        );
+        // Looking west
+        assert_close(
+            local("^1", "^", "^3").to_absolute(origin, 90.0, 0.0),
+            Vector3::new(7.0, 64.0, -4.0),
+        );
+        // Looking straight up, forwards goes up and up goes north
+        assert_close(
+            local("^", "^1", "^2").to_absolute(origin, 0.0, -90.0),
+            Vector3::new(10.0, 66.0, -6.0),
+        );
+
+        assert!(LocalPosition3D::try_new("^", "~", "^").is_none());
+        assert!(LocalPosition3D::try_new("^a", "^", "^").is_none());
+    }
+}
@@ -1,6 +1,11 @@
+use pumpkin_data::data_component::DataComponent;
+use pumpkin_data::data_component_impl::{DataComponentImpl, read_data};
 use pumpkin_data::item::Item;
+use pumpkin_data::tag::{RegistryKey, get_tag_ids};
+use pumpkin_nbt::snbt::from_snbt;
 use pumpkin_protocol::java::client::play::{ArgumentType, SuggestionProviders};
 use pumpkin_util::text::TextComponent;
+use pumpkin_world::item::ItemStack;
 
 use crate::command::{
     CommandSender,
@@ -13,6 +18,8 @@ use crate::command::{
 };
 use crate::server::Server;
 
+type ComponentPatch = Vec<(DataComponent, Option<Box<dyn DataComponentImpl>>)>;
+
 pub struct ItemArgumentConsumer;
 
 impl GetClientSideArgParser for ItemArgumentConsumer {
@@ -47,30 +54,239 @@ impl DefaultNameArgConsumer for ItemArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for ItemArgumentConsumer {
-    type Data = (&'a str, &'static Item);
+    /// The item id as written, and a single item with the given components.
+    type Data = (&'a str, ItemStack);
+
+    fn find_arg(args: &'a ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Item(arg)) => {
+                let (name, components) = match arg.split_once('[') {
+                    Some((name, components)) => (name, Some(components)),
+                    None => (*arg, None),
+                };
+                let item = find_item(name)?;
+                let patch = match components {
+                    Some(components) => parse_components(components)?,
+                    None => Vec::new(),
+                };
+                Ok((name, ItemStack::new_with_component(1, item, patch)))
+            }
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
+
+fn find_item(name: &str) -> Result<&'static Item, CommandError> {
+    Item::from_registry_key(name.strip_prefix("minecraft:").unwrap_or(name)).ok_or_else(|| {
+        let name = if name.starts_with("minecraft:") {
+            name.to_string()
+        } else {
+            "minecraft:".to_string() + name
+        };
+        CommandError::CommandFailed(TextComponent::translate(
+            "argument.item.id.invalid",
+            [TextComponent::text(name)],
+        ))
+    })
+}
+
+/// Parses the components of an item argument like `diamond_sword[damage=5,!unbreakable]`,
+/// `components` is everything after the opening bracket.
+fn parse_components(components: &str) -> Result<ComponentPatch, CommandError> {
+    let malformed = || {
+        CommandError::CommandFailed(TextComponent::translate(
+            "arguments.item.malformed",
+            [TextComponent::text(components.to_string())],
+        ))
+    };
+    let components = components.strip_suffix(']').ok_or_else(malformed)?;
+
+    let mut patch = ComponentPatch::new();
+    for entry in split_top_level(components) {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+        let (name, value) = match entry.strip_prefix('!') {
+            Some(name) => (name, None),
+            None => {
+                let (name, value) = entry.split_once('=').ok_or_else(malformed)?;
+                (name, Some(value))
+            }
+        };
+        let name = name.trim();
+        let full_name = if name.contains(':') {
+            name.to_string()
+        } else {
+            format!("minecraft:{name}")
+        };
+        let component = DataComponent::try_from_name(&full_name).ok_or_else(|| {
+            CommandError::CommandFailed(TextComponent::translate(
+                "arguments.item.component.unknown",
+                [TextComponent::text(full_name.clone())],
+            ))
+        })?;
+        if patch.iter().any(|(id, _)| *id == component) {
+            return Err(CommandError::CommandFailed(TextComponent::translate(
+                "arguments.item.component.repeated",
+                [TextComponent::text(full_name)],
+            )));
+        }
+
+        let data = match value {
+            Some(value) => {
+                let data = from_snbt(value)
+                    .ok()
+                    .and_then(|tag| read_data(component, &tag))
+                    .ok_or_else(|| {
+                        CommandError::CommandFailed(TextComponent::translate(
+                            "arguments.item.component.malformed",
+                            [
+                                TextComponent::text(full_name),
+                                TextComponent::text(value.to_string()),
+                            ],
+                        ))
+                    })?;
+                Some(data)
+            }
+            None => None,
+        };
+        patch.push((component, data));
+    }
+    Ok(patch)
+}
+
+/// Splits on the commas which are not nested in a compound, list or string.
+fn split_top_level(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in input.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+pub struct ItemPredicateArgumentConsumer;
+
+#[derive(Debug)]
+pub enum ItemPredicate {
+    Tag(Vec<u16>),
+    Item(u16),
+}
+
+impl ItemPredicate {
+    #[must_use]
+    pub fn test(&self, item: &Item) -> bool {
+        match self {
+            Self::Tag(items) => items.contains(&item.id),
+            Self::Item(id) => *id == item.id,
+        }
+    }
+}
+
+impl GetClientSideArgParser for ItemPredicateArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType<'_> {
+        ArgumentType::ItemPredicate
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+impl ArgumentConsumer for ItemPredicateArgumentConsumer {
+    fn consume<'a>(
+        &'a self,
+        _sender: &'a CommandSender,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> ConsumeResult<'a> {
+        let item = args.pop();
+        match item {
+            Some(s) => Box::pin(async move { Some(Arg::ItemPredicate(s)) }),
+            None => Box::pin(async move { None }),
+        }
+    }
+}
+
+impl DefaultNameArgConsumer for ItemPredicateArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "item"
+    }
+}
+
+impl<'a> FindArg<'a> for ItemPredicateArgumentConsumer {
+    type Data = Option<ItemPredicate>;
 
     fn find_arg(args: &'a ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Item(name)) => {
-                Item::from_registry_key(name.strip_prefix("minecraft:").unwrap_or(name))
-                    .map_or_else(
+            Some(Arg::ItemPredicate(name)) => name.strip_prefix('#').map_or_else(
+                || find_item(name).map(|item| Some(ItemPredicate::Item(item.id))),
+                |tag| {
+                    get_tag_ids(RegistryKey::Item, tag).map_or_else(
                         || {
-                            if name.starts_with("minecraft:") {
-                                Err(CommandError::CommandFailed(TextComponent::translate(
-                                    "argument.item.id.invalid",
-                                    [TextComponent::text((*name).to_string())],
-                                )))
-                            } else {
-                                Err(CommandError::CommandFailed(TextComponent::translate(
-                                    "argument.item.id.invalid",
-                                    [TextComponent::text("minecraft:".to_string() + *name)],
-                                )))
-                            }
+                            Err(CommandError::CommandFailed(TextComponent::translate(
+                                "arguments.item.tag.unknown",
+                                [TextComponent::text((*tag).to_string())],
+                            )))
                         },
-                        |item| Ok((*name, item)),
+                        |items| Ok(Some(ItemPredicate::Tag(items.to_vec()))),
                     )
-            }
-            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+                },
+            ),
+            _ => Ok(None),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::data_component::DataComponent;
+    use pumpkin_data::data_component_impl::{DamageImpl, get};
+
+    use super::{parse_components, split_top_level};
+
+    #[test]
+    fn parses_item_components() {
+        assert_eq!(
+            split_top_level(r#"a={b:1,c:[1,2]},d="x,y",e=2"#),
+            vec!["a={b:1,c:[1,2]}", r#"d="x,y""#, "e=2"]
+        );
+
+        let patch = parse_components("damage=5,!minecraft:unbreakable]").unwrap();
+        assert_eq!(patch.len(), 2);
+        assert_eq!(patch[0].0, DataComponent::Damage);
+        let damage = patch[0].1.as_deref().unwrap();
+        assert_eq!(get::<DamageImpl>(damage).damage, 5);
+        assert_eq!(patch[1].0, DataComponent::Unbreakable);
+        assert!(patch[1].1.is_none());
+
+        assert!(parse_components("damage=5").is_err());
+        assert!(parse_components("not_a_component=1]").is_err());
+        assert!(parse_components("damage=\"a lot\"]").is_err());
+        assert!(parse_components("damage=1,damage=2]").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use pumpkin_data::particle::Particle;
 use pumpkin_protocol::java::client::play::{ArgumentType, SuggestionProviders};
+use pumpkin_util::text::TextComponent;
 
 use crate::command::{
     CommandSender,
@@ -33,15 +34,7 @@ impl ArgumentConsumer for ParticleArgumentConsumer {
         _server: &'a Server,
         args: &'b mut RawArgs<'a>,
     ) -> ConsumeResult<'a> {
-        let name_opt: Option<&'a str> = args.pop();
-
-        let result: Option<Arg<'a>> = name_opt.map_or_else(
-            || None,
-            |name| {
-                Particle::from_name(name.strip_prefix("minecraft:").unwrap_or(name))
-                    .map(Arg::Particle)
-            },
-        );
+        let result = args.pop().map(Arg::Particle);
 
         Box::pin(async move { result })
     }
@@ -54,11 +47,19 @@ impl DefaultNameArgConsumer for ParticleArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for ParticleArgumentConsumer {
-    type Data = &'a Particle;
+    type Data = Particle;
 
     fn find_arg(args: &'a ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Particle(data)) => Ok(data),
+            Some(Arg::Particle(name)) => Particle::from_name(
+                name.strip_prefix("minecraft:").unwrap_or(name),
+            )
+            .ok_or_else(|| {
+                CommandError::CommandFailed(TextComponent::translate(
+                    "particle.notFound",
+                    [TextComponent::text((*name).to_string())],
+                ))
+            }),
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
@@ -25,7 +25,7 @@ impl GetClientSideArgParser for RotationArgumentConsumer {
 impl ArgumentConsumer for RotationArgumentConsumer {
     fn consume<'a, 'b>(
         &'a self,
-        _sender: &'a CommandSender,
+        sender: &'a CommandSender,
         _server: &'a Server,
         args: &'b mut RawArgs<'a>,
     ) -> ConsumeResult<'a> {
@@ -36,25 +36,36 @@ impl ArgumentConsumer for RotationArgumentConsumer {
             return Box::pin(async move { None });
         };
 
-        let result: Option<Arg<'a>> = yaw_str.parse::<f32>().ok().and_then(|mut yaw| {
-            pitch_str.parse::<f32>().ok().map(|mut pitch| {
-                yaw %= 360.0;
-                if yaw >= 180.0 {
-                    yaw -= 360.0;
-                }
-                pitch %= 360.0;
-                if pitch >= 180.0 {
-                    pitch -= 360.0;
-                }
+        let origin = sender.rotation();
+        let result: Option<Arg<'a>> =
+            parse_angle(yaw_str, origin.map(|(yaw, _)| yaw)).and_then(|mut yaw| {
+                parse_angle(pitch_str, origin.map(|(_, pitch)| pitch)).map(|mut pitch| {
+                    yaw %= 360.0;
+                    if yaw >= 180.0 {
+                        yaw -= 360.0;
+                    }
+                    pitch %= 360.0;
+                    if pitch >= 180.0 {
+                        pitch -= 360.0;
+                    }
 
-                Arg::Rotation(yaw, pitch)
-            })
-        });
+                    Arg::Rotation(yaw, pitch)
+                })
+            });
 
         Box::pin(async move { result })
     }
 }
 
+/// Parses an absolute angle or one relative to `origin` with `~`.
+fn parse_angle(s: &str, origin: Option<f32>) -> Option<f32> {
+    match s.strip_prefix('~') {
+        Some("") => origin,
+        Some(offset) => Some(origin? + offset.parse::<f32>().ok()?),
+        None => s.parse().ok(),
+    }
+}
+
 impl DefaultNameArgConsumer for RotationArgumentConsumer {
     fn default_name(&self) -> &'static str {
         "rotation"
@@ -51,16 +51,18 @@ impl<'a> FindArg<'a> for SummonableEntitiesArgumentConsumer {
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Block(name)) => {
-                EntityType::from_name(name.strip_prefix("minecraft:").unwrap_or(name)).map_or_else(
-                    || {
-                        Err(CommandError::CommandFailed(TextComponent::text(
-                            "Can't find Entity",
-                        )))
-                    },
-                    Result::Ok,
-                )
-            }
+            Some(Arg::Block(name)) => EntityType::from_name(
+                name.strip_prefix("minecraft:").unwrap_or(name),
+            )
+            .ok_or_else(|| {
+                CommandError::CommandFailed(TextComponent::translate(
+                    "argument.resource.not_found",
+                    [
+                        TextComponent::text((*name).to_string()),
+                        TextComponent::text("minecraft:entity_type"),
+                    ],
+                ))
+            }),
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
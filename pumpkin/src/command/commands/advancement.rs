@@ -67,8 +67,16 @@ impl CommandExecutor for GrantOnlyExecutor {
             if let Some(adv) = advancement {
                 for target in targets {
                     let mut tracker = target.advancement_tracker.lock().await;
+                    let was_done = tracker.is_completed(&advancement_id);
                     tracker.grant_advancement(&advancement_id, &adv.advancement.requirements);
+                    let is_done = tracker.is_completed(&advancement_id);
                     drop(tracker);
+                    if is_done && !was_done {
+                        crate::advancement::AdvancementTriggers::grant_rewards(
+                            target, server, &adv,
+                        )
+                        .await;
+                    }
                     crate::advancement::AdvancementTriggers::send_update(target, server).await;
                 }
                 sender
@@ -163,12 +171,20 @@ async fn grant_all_advancements(player: &Arc<Player>) {
         let registry = server.advancement_registry.read().await;
         let mut tracker = player.advancement_tracker.lock().await;
 
+        let mut completed = Vec::new();
         for (id, entry) in registry.iter() {
+            let was_done = tracker.is_completed(id);
             tracker.grant_advancement(id, &entry.advancement.requirements);
+            if !was_done && tracker.is_completed(id) {
+                completed.push(entry.clone());
+            }
         }
         tracker.mark_needs_reset();
         drop(tracker);
         drop(registry);
+        for entry in &completed {
+            crate::advancement::AdvancementTriggers::grant_rewards(player, &server, entry).await;
+        }
         crate::advancement::AdvancementTriggers::send_update(player, &server).await;
     }
 }
@@ -0,0 +1,272 @@
+use pumpkin_data::attributes::Attributes;
+use pumpkin_data::data_component_impl::Operation;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::entity::EntityArgumentConsumer;
+use crate::command::args::resource_location::ResourceLocationArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal};
+use crate::command::{CommandExecutor, CommandResult, CommandSender};
+use crate::entity::attributes::AttributeModifier;
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["attribute"];
+
+const DESCRIPTION: &str = "Queries, adds, removes or sets an entity attribute.";
+
+const ARG_TARGET: &str = "target";
+const ARG_ATTRIBUTE: &str = "attribute";
+const ARG_VALUE: &str = "value";
+const ARG_ID: &str = "id";
+
+/// Looks up an attribute by its vanilla name, with or without the `minecraft:` namespace. The
+/// names from before 1.21.2, like `generic.max_health`, are accepted as well.
+fn parse_attribute(name: &str) -> Option<&'static Attributes> {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    let name = ["generic.", "player.", "zombie."]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name);
+    Attributes::from_name(name)
+}
+
+/// Formats an attribute value with at most five decimals, leaving out trailing zeros.
+fn format_value(value: f64) -> String {
+    let formatted = format!("{value:.5}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn value_consumer() -> BoundedNumArgumentConsumer<f64> {
+    BoundedNumArgumentConsumer::new().name(ARG_VALUE)
+}
+
+fn failed<const N: usize>(key: &'static str, with: [TextComponent; N]) -> CommandError {
+    CommandError::CommandFailed(TextComponent::translate(key, with))
+}
+
+#[derive(Clone, Copy)]
+enum Action {
+    Get,
+    BaseGet,
+    BaseSet,
+    ModifierAdd(Operation),
+    ModifierRemove,
+    ModifierGet,
+}
+
+struct Executor(Action);
+
+impl CommandExecutor for Executor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        _server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+            let attribute_name = ResourceLocationArgumentConsumer::find_arg(args, ARG_ATTRIBUTE)?;
+            let Some(attribute) = parse_attribute(attribute_name) else {
+                return Err(CommandError::CommandFailed(TextComponent::text(format!(
+                    "Unknown attribute {attribute_name}"
+                ))));
+            };
+            let entity_name = target.get_display_name().await;
+            let Some(living) = target.get_living_entity() else {
+                return Err(failed("commands.attribute.failed.entity", [entity_name]));
+            };
+            let attribute_text =
+                TextComponent::translate(format!("attribute.name.{}", attribute.name()), []);
+            // Modifier ids without a namespace belong to `minecraft`
+            let modifier_id = ResourceLocationArgumentConsumer::find_arg(args, ARG_ID)
+                .map(|id| {
+                    if id.contains(':') {
+                        id.to_string()
+                    } else {
+                        format!("minecraft:{id}")
+                    }
+                })
+                .ok();
+            let value = match BoundedNumArgumentConsumer::<f64>::find_arg(args, ARG_VALUE) {
+                Ok(Ok(value)) => Some(value),
+                Ok(Err(_)) => return Err(CommandError::InvalidConsumption(Some(ARG_VALUE.into()))),
+                Err(_) => None,
+            };
+
+            // The changed attributes are sent on the next tick
+            let mut attributes = living.attributes.lock().await;
+            let message = match (self.0, modifier_id, value) {
+                (Action::Get, _, _) => TextComponent::translate(
+                    "commands.attribute.value.get.success",
+                    [
+                        attribute_text,
+                        entity_name,
+                        TextComponent::text(format_value(attributes.value(attribute))),
+                    ],
+                ),
+                (Action::BaseGet, _, _) => TextComponent::translate(
+                    "commands.attribute.base_value.get.success",
+                    [
+                        attribute_text,
+                        entity_name,
+                        TextComponent::text(format_value(attributes.base_value(attribute))),
+                    ],
+                ),
+                (Action::BaseSet, _, Some(value)) => {
+                    attributes.set_base_value(attribute, value);
+                    TextComponent::translate(
+                        "commands.attribute.base_value.set.success",
+                        [
+                            attribute_text,
+                            entity_name,
+                            TextComponent::text(format_value(value)),
+                        ],
+                    )
+                }
+                (Action::ModifierAdd(operation), Some(id), Some(amount)) => {
+                    if attributes.modifier(attribute, &id).is_some() {
+                        return Err(failed(
+                            "commands.attribute.failed.modifier_already_present",
+                            [TextComponent::text(id), attribute_text, entity_name],
+                        ));
+                    }
+                    attributes.add_modifier(
+                        attribute,
+                        AttributeModifier {
+                            id: id.clone(),
+                            amount,
+                            operation,
+                        },
+                    );
+                    TextComponent::translate(
+                        "commands.attribute.modifier.add.success",
+                        [TextComponent::text(id), attribute_text, entity_name],
+                    )
+                }
+                (Action::ModifierRemove, Some(id), _) => {
+                    if attributes.remove_modifier(attribute, &id).is_none() {
+                        return Err(failed(
+                            "commands.attribute.failed.no_modifier",
+                            [attribute_text, entity_name, TextComponent::text(id)],
+                        ));
+                    }
+                    TextComponent::translate(
+                        "commands.attribute.modifier.remove.success",
+                        [TextComponent::text(id), attribute_text, entity_name],
+                    )
+                }
+                (Action::ModifierGet, Some(id), _) => {
+                    let Some(modifier) = attributes.modifier(attribute, &id) else {
+                        return Err(failed(
+                            "commands.attribute.failed.no_modifier",
+                            [attribute_text, entity_name, TextComponent::text(id)],
+                        ));
+                    };
+                    let amount = format_value(modifier.amount);
+                    TextComponent::translate(
+                        "commands.attribute.modifier.value.get.success",
+                        [
+                            TextComponent::text(id),
+                            attribute_text,
+                            entity_name,
+                            TextComponent::text(amount),
+                        ],
+                    )
+                }
+                (_, None, _) => return Err(CommandError::InvalidConsumption(Some(ARG_ID.into()))),
+                (_, _, None) => {
+                    return Err(CommandError::InvalidConsumption(Some(ARG_VALUE.into())));
+                }
+            };
+            drop(attributes);
+            sender.send_message(message).await;
+            Ok(())
+        })
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let modifier_id = || argument(ARG_ID, ResourceLocationArgumentConsumer::new(true));
+    let operation =
+        |name: &str, operation| literal(name).execute(Executor(Action::ModifierAdd(operation)));
+
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGET, EntityArgumentConsumer).then(
+            argument(ARG_ATTRIBUTE, ResourceLocationArgumentConsumer::new(true))
+                .then(literal("get").execute(Executor(Action::Get)))
+                .then(
+                    literal("base")
+                        .then(literal("get").execute(Executor(Action::BaseGet)))
+                        .then(
+                            literal("set").then(
+                                argument(ARG_VALUE, value_consumer())
+                                    .execute(Executor(Action::BaseSet)),
+                            ),
+                        ),
+                )
+                .then(
+                    literal("modifier")
+                        .then(
+                            literal("add").then(
+                                modifier_id().then(
+                                    argument(ARG_VALUE, value_consumer())
+                                        .then(operation("add_value", Operation::AddValue))
+                                        .then(operation(
+                                            "add_multiplied_base",
+                                            Operation::AddMultipliedBase,
+                                        ))
+                                        .then(operation(
+                                            "add_multiplied_total",
+                                            Operation::AddMultipliedTotal,
+                                        )),
+                                ),
+                            ),
+                        )
+                        .then(
+                            literal("remove")
+                                .then(modifier_id().execute(Executor(Action::ModifierRemove))),
+                        )
+                        .then(
+                            literal("value").then(
+                                literal("get")
+                                    .then(modifier_id().execute(Executor(Action::ModifierGet))),
+                            ),
+                        ),
+                ),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::attributes::Attributes;
+
+    use super::{format_value, parse_attribute};
+
+    #[test]
+    fn attributes_are_found_by_their_vanilla_names() {
+        assert_eq!(parse_attribute("max_health"), Some(&Attributes::MAX_HEALTH));
+        assert_eq!(
+            parse_attribute("minecraft:generic.movement_speed"),
+            Some(&Attributes::MOVEMENT_SPEED)
+        );
+        assert_eq!(
+            parse_attribute("player.block_interaction_range"),
+            Some(&Attributes::BLOCK_INTERACTION_RANGE)
+        );
+        assert_eq!(parse_attribute("generic.unknown"), None);
+
+        assert_eq!(format_value(20.0), "20");
+        assert_eq!(format_value(0.1), "0.1");
+        assert_eq!(format_value(0.699_999_999), "0.7");
+        assert_eq!(format_value(-0.000_001), "0");
+    }
+}
@@ -12,6 +12,7 @@ use crate::{
     },
     entity::player::Player,
     net::DisconnectReason,
+    server::Server,
 };
 use CommandError::InvalidConsumption;
 use pumpkin_util::text::TextComponent;
@@ -28,7 +29,7 @@ impl CommandExecutor for NoReasonExecutor {
     fn execute<'a>(
         &'a self,
         sender: &'a CommandSender,
-        _server: &'a crate::server::Server,
+        server: &'a crate::server::Server,
         args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
@@ -36,7 +37,7 @@ impl CommandExecutor for NoReasonExecutor {
                 return Err(InvalidConsumption(Some(ARG_TARGET.into())));
             };
 
-            ban_player(sender, &targets[0], None).await;
+            ban_player(sender, server, &targets[0], None).await;
             Ok(())
         })
     }
@@ -48,7 +49,7 @@ impl CommandExecutor for ReasonExecutor {
     fn execute<'a>(
         &'a self,
         sender: &'a CommandSender,
-        _server: &'a crate::server::Server,
+        server: &'a crate::server::Server,
         args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
@@ -60,13 +61,18 @@ impl CommandExecutor for ReasonExecutor {
                 return Err(InvalidConsumption(Some(ARG_REASON.into())));
             };
 
-            ban_player(sender, &targets[0], Some(reason.clone())).await;
+            ban_player(sender, server, &targets[0], Some(reason.clone())).await;
             Ok(())
         })
     }
 }
 
-async fn ban_player(sender: &CommandSender, player: &Player, reason: Option<String>) {
+async fn ban_player(
+    sender: &CommandSender,
+    server: &Server,
+    player: &Player,
+    reason: Option<String>,
+) {
     let mut banned_players = BANNED_PLAYER_LIST.write().await;
 
     let reason = reason.unwrap_or_else(|| "Banned by an operator.".to_string());
@@ -79,12 +85,9 @@ async fn ban_player(sender: &CommandSender, player: &Player, reason: Option<Stri
         return;
     }
 
-    banned_players.banned_players.push(BannedPlayerEntry::new(
-        profile,
-        sender.to_string(),
-        None,
-        reason.clone(),
-    ));
+    let entry = BannedPlayerEntry::new(profile, sender.to_string(), None, reason.clone());
+    let disconnect_message = entry.disconnect_message();
+    banned_players.ban(entry, &server.audit_log);
 
     banned_players.save();
     drop(banned_players);
@@ -98,10 +101,7 @@ async fn ban_player(sender: &CommandSender, player: &Player, reason: Option<Stri
         .await;
 
     player
-        .kick(
-            DisconnectReason::Kicked,
-            TextComponent::translate("multiplayer.disconnect.banned", []),
-        )
+        .kick(DisconnectReason::Kicked, disconnect_message)
         .await;
 }
 
@@ -97,12 +97,9 @@ async fn ban_ip(sender: &CommandSender, server: &Server, target: &str, reason: O
         return;
     }
 
-    banned_ips.banned_ips.push(BannedIpEntry::new(
-        target_ip,
-        sender.to_string(),
-        None,
-        reason.clone(),
-    ));
+    let entry = BannedIpEntry::new(target_ip, sender.to_string(), None, reason.clone());
+    let disconnect_message = entry.disconnect_message();
+    banned_ips.ban(entry, &server.audit_log);
 
     banned_ips.save();
     drop(banned_ips);
@@ -137,10 +134,7 @@ async fn ban_ip(sender: &CommandSender, server: &Server, target: &str, reason: O
 
     for target in affected {
         target
-            .kick(
-                DisconnectReason::Kicked,
-                TextComponent::translate("multiplayer.disconnect.ip_banned", []),
-            )
+            .kick(DisconnectReason::Kicked, disconnect_message.clone())
             .await;
     }
 }
@@ -4,10 +4,12 @@ use pumpkin_util::text::TextComponent;
 use pumpkin_util::text::color::NamedColor;
 use pumpkin_world::item::ItemStack;
 
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
 use crate::command::args::players::PlayersArgumentConsumer;
-use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::args::resource::item::{ItemPredicate, ItemPredicateArgumentConsumer};
+use crate::command::args::{Arg, ConsumedArgs, FindArg, FindArgDefaultName};
 use crate::command::tree::CommandTree;
-use crate::command::tree::builder::{argument, require};
+use crate::command::tree::builder::{argument, argument_default_name, require};
 use crate::command::{CommandError, CommandExecutor, CommandResult, CommandSender};
 use crate::entity::EntityBase;
 use crate::entity::player::Player;
@@ -17,37 +19,78 @@ const NAMES: [&str; 1] = ["clear"];
 const DESCRIPTION: &str = "Clear yours or targets inventory.";
 
 const ARG_TARGET: &str = "target";
+const ARG_ITEM: &str = "item";
 
-async fn clear_player(target: &Player) -> u64 {
-    let inventory = target.inventory();
-    let mut count: u64 = 0;
-    for slot in &inventory.main_inventory {
-        let mut slot_lock = slot.lock().await;
-        count += u64::from(slot_lock.item_count);
-        *slot_lock = ItemStack::EMPTY.clone();
+fn max_count_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name("maxCount")
+        .min(0)
+        .max(i32::MAX)
+}
+
+/// Removes the items of `stack` matching `predicate`, but no more than `limit`, returns how many
+/// were removed. Without a `limit`, they are only counted.
+fn clear_stack(
+    stack: &mut ItemStack,
+    predicate: Option<&ItemPredicate>,
+    limit: Option<u64>,
+) -> u64 {
+    if stack.is_empty() || predicate.is_some_and(|predicate| !predicate.test(stack.item)) {
+        return 0;
     }
+    let count = u64::from(stack.item_count);
+    let Some(limit) = limit else {
+        return count;
+    };
+    let removed = count.min(limit);
+    stack.decrement(removed as u8);
+    removed
+}
 
+/// Clears the items matching `predicate` from the inventory of `target`, up to `max_count` of
+/// them. A `max_count` of `0` only counts them.
+async fn clear_player(
+    target: &Player,
+    predicate: Option<&ItemPredicate>,
+    max_count: Option<u64>,
+) -> u64 {
+    let inventory = target.inventory();
     let entity_equipment_lock = inventory.entity_equipment.lock().await;
-    for slot in entity_equipment_lock.equipment.values() {
-        let mut slot_lock = slot.lock().await;
-        if slot_lock.is_empty() {
-            continue;
-        }
-        count += 1u64;
-        *slot_lock = ItemStack::EMPTY.clone();
+    let slots = inventory
+        .main_inventory
+        .iter()
+        .chain(entity_equipment_lock.equipment.values());
+
+    let mut count: u64 = 0;
+    for slot in slots {
+        let limit = match max_count {
+            Some(0) => None,
+            Some(max_count) if count >= max_count => break,
+            Some(max_count) => Some(max_count - count),
+            None => Some(u64::MAX),
+        };
+        count += clear_stack(&mut *slot.lock().await, predicate, limit);
     }
 
     count
 }
 
-async fn clear_command_text_output(item_count: u64, targets: &[Arc<Player>]) -> TextComponent {
+async fn clear_command_text_output(
+    item_count: u64,
+    targets: &[Arc<Player>],
+    test: bool,
+) -> TextComponent {
     match targets {
         [target] if item_count == 0 => {
             TextComponent::translate("clear.failed.single", [target.get_display_name().await])
                 .color_named(NamedColor::Red)
         }
         [target] => TextComponent::translate(
-            "commands.clear.success.single",
+            if test {
+                "commands.clear.test.single"
+            } else {
+                "commands.clear.success.single"
+            },
             [
                 TextComponent::text(item_count.to_string()),
                 target.get_display_name().await,
@@ -59,7 +102,11 @@ async fn clear_command_text_output(item_count: u64, targets: &[Arc<Player>]) ->
         )
         .color_named(NamedColor::Red),
         targets => TextComponent::translate(
-            "commands.clear.success.multiple",
+            if test {
+                "commands.clear.test.multiple"
+            } else {
+                "commands.clear.success.multiple"
+            },
             [
                 TextComponent::text(item_count.to_string()),
                 TextComponent::text(targets.len().to_string()),
@@ -82,12 +129,27 @@ impl CommandExecutor for Executor {
                 return Err(InvalidConsumption(Some(ARG_TARGET.into())));
             };
 
+            let predicate = ItemPredicateArgumentConsumer::find_arg(args, ARG_ITEM)?;
+            let max_count = match max_count_consumer().find_arg_default_name(args) {
+                Err(_) => None,
+                Ok(Ok(max_count)) => Some(max_count as u64),
+                Ok(Err(_)) => {
+                    sender
+                        .send_message(
+                            TextComponent::text("Can't clear less than 0 items")
+                                .color_named(NamedColor::Red),
+                        )
+                        .await;
+                    return Ok(());
+                }
+            };
+
             let mut item_count = 0;
             for target in targets {
-                item_count += clear_player(target).await;
+                item_count += clear_player(target, predicate.as_ref(), max_count).await;
             }
 
-            let msg = clear_command_text_output(item_count, targets).await;
+            let msg = clear_command_text_output(item_count, targets, max_count == Some(0)).await;
 
             sender.send_message(msg).await;
 
@@ -108,10 +170,10 @@ impl CommandExecutor for SelfExecutor {
         Box::pin(async move {
             let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
 
-            let item_count = clear_player(&target).await;
+            let item_count = clear_player(&target, None, None).await;
 
             let hold_target = [target];
-            let msg = clear_command_text_output(item_count, &hold_target).await;
+            let msg = clear_command_text_output(item_count, &hold_target, false).await;
 
             sender.send_message(msg).await;
 
@@ -123,6 +185,42 @@ impl CommandExecutor for SelfExecutor {
 // #[expect(clippy::redundant_closure_for_method_calls)] // causes lifetime issues
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(Executor))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(Executor)
+                .then(
+                    argument(ARG_ITEM, ItemPredicateArgumentConsumer)
+                        .execute(Executor)
+                        .then(argument_default_name(max_count_consumer()).execute(Executor)),
+                ),
+        )
         .then(require(super::super::CommandSender::is_player).execute(SelfExecutor))
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_world::item::ItemStack;
+
+    use super::clear_stack;
+    use crate::command::args::resource::item::ItemPredicate;
+
+    #[test]
+    fn clearing_removes_matching_items_up_to_the_limit() {
+        let dirt = ItemPredicate::Item(Item::DIRT.id);
+
+        let mut stack = ItemStack::new(10, &Item::STONE);
+        assert_eq!(clear_stack(&mut stack, Some(&dirt), Some(u64::MAX)), 0);
+        assert_eq!(stack.item_count, 10);
+
+        // Counting only
+        let mut stack = ItemStack::new(10, &Item::DIRT);
+        assert_eq!(clear_stack(&mut stack, Some(&dirt), None), 10);
+        assert_eq!(stack.item_count, 10);
+
+        assert_eq!(clear_stack(&mut stack, Some(&dirt), Some(4)), 4);
+        assert_eq!(stack.item_count, 6);
+        assert_eq!(clear_stack(&mut stack, None, Some(u64::MAX)), 6);
+        assert!(stack.is_empty());
+    }
+}
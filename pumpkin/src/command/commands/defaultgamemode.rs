@@ -33,7 +33,7 @@ impl CommandExecutor for DefaultGamemodeExecutor {
 
             if server.basic_config.force_gamemode {
                 for player in server.get_all_players().await {
-                    player.set_gamemode(gamemode).await;
+                    player.set_gamemode(gamemode, &sender.to_string()).await;
                 }
             }
 
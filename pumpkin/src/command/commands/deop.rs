@@ -33,13 +33,12 @@ impl CommandExecutor for Executor {
             };
 
             for player in targets {
-                if let Some(op_index) = config
-                    .ops
-                    .iter()
-                    .position(|o| o.uuid == player.gameprofile.id)
-                {
-                    config.ops.remove(op_index);
-                }
+                config.deop(
+                    &player.gameprofile.id,
+                    &player.gameprofile.name,
+                    &sender.to_string(),
+                    &server.audit_log,
+                );
                 config.save();
 
                 {
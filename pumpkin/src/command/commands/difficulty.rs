@@ -1,10 +1,11 @@
 use crate::command::CommandResult;
 use crate::command::args::difficulty::DifficultyArgumentConsumer;
 use crate::command::args::{Arg, GetCloned};
-use crate::command::dispatcher::CommandError::InvalidConsumption;
-use crate::command::tree::builder::argument;
+use crate::command::dispatcher::CommandError::{CommandFailed, InvalidConsumption};
+use crate::command::tree::builder::{argument, literal};
 use crate::command::{CommandExecutor, CommandSender, args::ConsumedArgs, tree::CommandTree};
 
+use pumpkin_util::difficulty::Difficulty;
 use pumpkin_util::text::TextComponent;
 
 const NAMES: [&str; 1] = ["difficulty"];
@@ -26,8 +27,13 @@ impl CommandExecutor for DifficultyExecutor {
                 return Err(InvalidConsumption(Some(ARG_DIFFICULTY.into())));
             };
 
-            let difficulty_string = format!("{difficulty:?}").to_lowercase();
-            let translation_key = format!("options.difficulty.{difficulty_string}");
+            if server.basic_config.hardcore {
+                return Err(CommandFailed(TextComponent::text(
+                    "The difficulty of a hardcore world can't be changed",
+                )));
+            }
+
+            let translation_key = difficulty_translation_key(difficulty);
 
             {
                 let level_info = server.level_info.read().await;
@@ -57,8 +63,75 @@ impl CommandExecutor for DifficultyExecutor {
     }
 }
 
+struct QueryExecutor;
+
+impl CommandExecutor for QueryExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a crate::server::Server,
+        _args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let difficulty = server.level_info.read().await.difficulty;
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.difficulty.query",
+                    [TextComponent::translate(
+                        difficulty_translation_key(difficulty),
+                        [],
+                    )],
+                ))
+                .await;
+            Ok(())
+        })
+    }
+}
+
+struct LockExecutor {
+    locked: bool,
+}
+
+impl CommandExecutor for LockExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a crate::server::Server,
+        _args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            if server.level_info.read().await.difficulty_locked == self.locked {
+                return Err(CommandFailed(TextComponent::text(if self.locked {
+                    "The difficulty is already locked"
+                } else {
+                    "The difficulty is not locked"
+                })));
+            }
+
+            server.set_difficulty_locked(self.locked).await;
+
+            sender
+                .send_message(TextComponent::text(if self.locked {
+                    "The difficulty has been locked"
+                } else {
+                    "The difficulty has been unlocked"
+                }))
+                .await;
+            Ok(())
+        })
+    }
+}
+
+fn difficulty_translation_key(difficulty: Difficulty) -> String {
+    let difficulty_string = format!("{difficulty:?}").to_lowercase();
+    format!("options.difficulty.{difficulty_string}")
+}
+
 #[must_use]
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("lock").execute(LockExecutor { locked: true }))
+        .then(literal("unlock").execute(LockExecutor { locked: false }))
         .then(argument(ARG_DIFFICULTY, DifficultyArgumentConsumer).execute(DifficultyExecutor))
+        .execute(QueryExecutor)
 }
@@ -1,7 +1,7 @@
 use crate::TextComponent;
 use crate::command::args::bool::BoolArgConsumer;
 use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
-use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::entities::EntitiesArgumentConsumer;
 use crate::command::args::resource::effect::EffectTypeArgumentConsumer;
 use crate::command::args::{Arg, ConsumedArgs, FindArgDefaultName};
 use crate::command::dispatcher::CommandError::InvalidConsumption;
@@ -46,7 +46,7 @@ impl CommandExecutor for GiveExecutor {
         args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
-            let Some(Arg::Players(targets)) = args.get(ARG_TARGET) else {
+            let Some(Arg::Entities(targets)) = args.get(ARG_TARGET) else {
                 return Err(InvalidConsumption(Some(ARG_TARGET.into())));
             };
             let Some(Arg::Effect(effect)) = args.get(ARG_EFFECT) else {
@@ -89,28 +89,33 @@ impl CommandExecutor for GiveExecutor {
             let mut failed = 0;
 
             for target in targets {
-                if target.living_entity.has_effect(effect).await
-                    && target
-                        .living_entity
-                        .get_effect(effect)
-                        .await
-                        .unwrap()
-                        .amplifier
-                        > amplifier
+                // Only living entities can have effects
+                let Some(living_entity) = target.get_living_entity() else {
+                    failed += 1;
+                    continue;
+                };
+                if living_entity
+                    .get_effect(effect)
+                    .await
+                    .is_some_and(|current| current.amplifier > amplifier)
                 {
                     failed += 1;
+                    continue;
+                }
+                let new_effect = Effect {
+                    effect_type: effect,
+                    duration: second,
+                    amplifier,
+                    ambient: false, //this is not a beacon effect
+                    show_particles: hide_particles,
+                    show_icon: true,
+                    blend: true, //Currently only used in the DARKNESS effect to apply extra void fog and adjust the gamma value for lighting.
+                };
+                // Players are also told about their effects
+                if let Some(player) = target.get_player() {
+                    player.add_effect(new_effect).await;
                 } else {
-                    target
-                        .add_effect(Effect {
-                            effect_type: effect,
-                            duration: second,
-                            amplifier,
-                            ambient: false, //this is not a beacon effect
-                            show_particles: hide_particles,
-                            show_icon: true,
-                            blend: true, //Currently only used in the DARKNESS effect to apply extra void fog and adjust the gamma value for lighting.
-                        })
-                        .await;
+                    living_entity.add_effect(new_effect).await;
                 }
             }
 
@@ -157,7 +162,7 @@ impl CommandExecutor for ClearExecutor {
         args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
-            let Some(Arg::Players(targets)) = args.get(ARG_TARGET) else {
+            let Some(Arg::Entities(targets)) = args.get(ARG_TARGET) else {
                 return Err(InvalidConsumption(Some(ARG_TARGET.into())));
             };
 
@@ -166,9 +171,8 @@ impl CommandExecutor for ClearExecutor {
             if self.0 {
                 let mut effect_number = 0;
                 for target in targets {
-                    let effect_number_temp = target.remove_all_effect().await;
-                    if effect_number_temp > effect_number {
-                        effect_number = effect_number_temp;
+                    if let Some(living_entity) = target.get_living_entity() {
+                        effect_number += living_entity.clear_effects().await;
                     }
                 }
 
@@ -203,28 +207,24 @@ impl CommandExecutor for ClearExecutor {
                 };
 
                 effect = *effect_type;
-                let mut has_effect = vec![];
+                let mut removed = 0;
 
                 for target in targets {
-                    if !target.living_entity.has_effect(effect).await {
-                        target.remove_effect(effect).await;
+                    if let Some(living_entity) = target.get_living_entity()
+                        && living_entity.has_effect(effect).await
+                    {
+                        living_entity.remove_effect(effect).await;
+                        removed += 1;
                     }
-                    has_effect.push(target.living_entity.has_effect(effect).await);
                 }
 
-                if has_effect.contains(&false) {
-                    //contain false for 1 player == don't have
-                    if targets.len() == 1 || !has_effect.contains(&true) {
-                        sender
-                            .send_message(
-                                TextComponent::translate(
-                                    "commands.effect.clear.specific.failed",
-                                    [],
-                                )
+                if removed == 0 {
+                    sender
+                        .send_message(
+                            TextComponent::translate("commands.effect.clear.specific.failed", [])
                                 .color(Color::Named(NamedColor::Red)),
-                            )
-                            .await;
-                    }
+                        )
+                        .await;
                 } else {
                     //true for 1 player = have the effect
                     if targets.len() == 1 {
@@ -262,7 +262,7 @@ pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
         .then(
             literal(ARG_CLEAR).then(
-                argument(ARG_TARGET, PlayersArgumentConsumer)
+                argument(ARG_TARGET, EntitiesArgumentConsumer)
                     .execute(ClearExecutor(true))
                     .then(
                         argument(ARG_EFFECT, EffectTypeArgumentConsumer)
@@ -272,7 +272,7 @@ pub fn init_command_tree() -> CommandTree {
         )
         .then(
             literal(ARG_GIVE).then(
-                argument(ARG_TARGET, PlayersArgumentConsumer).then(
+                argument(ARG_TARGET, EntitiesArgumentConsumer).then(
                     argument(ARG_EFFECT, EffectTypeArgumentConsumer)
                         .execute(GiveExecutor(Time::Base, Amplifier::Base, true))
                         //for specified time
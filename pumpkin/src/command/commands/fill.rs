@@ -1,11 +1,12 @@
 use crate::command::args::block::{
-    BlockArgumentConsumer, BlockPredicate, BlockPredicateArgumentConsumer,
+    BlockArgumentConsumer, BlockPredicate, BlockPredicateArgumentConsumer, BlockStateArgument,
 };
 use crate::command::args::position_block::BlockPosArgumentConsumer;
 use crate::command::args::{ConsumedArgs, FindArg};
 use crate::command::tree::CommandTree;
 use crate::command::tree::builder::{argument, literal};
 use crate::command::{CommandError, CommandExecutor, CommandResult, CommandSender};
+use crate::world::World;
 
 use pumpkin_data::Block;
 use pumpkin_util::math::position::BlockPos;
@@ -48,6 +49,14 @@ fn not_in_filter(filter: &BlockPredicate, old_block: &Block) -> bool {
     }
 }
 
+/// Places the block and merges its NBT into the block entity, if any NBT was given.
+async fn place_block(world: &World, pos: &BlockPos, block: &BlockStateArgument, flags: BlockFlags) {
+    world.set_block_state(pos, block.state_id, flags).await;
+    if let Some(nbt) = &block.nbt {
+        world.merge_block_entity_nbt(pos, nbt).await;
+    }
+}
+
 #[expect(clippy::too_many_lines)]
 impl CommandExecutor for Executor {
     fn execute<'a>(
@@ -58,7 +67,6 @@ impl CommandExecutor for Executor {
     ) -> CommandResult<'a> {
         Box::pin(async move {
             let block = BlockArgumentConsumer::find_arg(args, ARG_BLOCK)?;
-            let block_state_id = block.default_state.id;
             let from = BlockPosArgumentConsumer::find_arg(args, ARG_FROM)?;
             let to = BlockPosArgumentConsumer::find_arg(args, ARG_TO)?;
             let option_filter = BlockPredicateArgumentConsumer::find_arg(args, ARG_FILTER)?;
@@ -94,13 +102,13 @@ impl CommandExecutor for Executor {
                                         BlockFlags::SKIP_DROPS | BlockFlags::FORCE_STATE,
                                     )
                                     .await;
-                                world
-                                    .set_block_state(
-                                        &block_position,
-                                        block_state_id,
-                                        BlockFlags::FORCE_STATE,
-                                    )
-                                    .await;
+                                place_block(
+                                    &world,
+                                    &block_position,
+                                    &block,
+                                    BlockFlags::FORCE_STATE,
+                                )
+                                .await;
                                 placed_blocks += 1;
                                 to_update.push(block_position);
                             }
@@ -117,13 +125,13 @@ impl CommandExecutor for Executor {
                                 {
                                     continue;
                                 }
-                                world
-                                    .set_block_state(
-                                        &block_position,
-                                        block_state_id,
-                                        BlockFlags::FORCE_STATE,
-                                    )
-                                    .await;
+                                place_block(
+                                    &world,
+                                    &block_position,
+                                    &block,
+                                    BlockFlags::FORCE_STATE,
+                                )
+                                .await;
                                 placed_blocks += 1;
                                 to_update.push(block_position);
                             }
@@ -145,13 +153,13 @@ impl CommandExecutor for Executor {
                                     {
                                         continue;
                                     }
-                                    world
-                                        .set_block_state(
-                                            &block_position,
-                                            block_state_id,
-                                            BlockFlags::FORCE_STATE,
-                                        )
-                                        .await;
+                                    place_block(
+                                        &world,
+                                        &block_position,
+                                        &block,
+                                        BlockFlags::FORCE_STATE,
+                                    )
+                                    .await;
                                     placed_blocks += 1;
                                     to_update.push(block_position);
                                 }
@@ -176,13 +184,13 @@ impl CommandExecutor for Executor {
                                     continue;
                                 }
                                 if is_edge {
-                                    world
-                                        .set_block_state(
-                                            &block_position,
-                                            block_state_id,
-                                            BlockFlags::FORCE_STATE,
-                                        )
-                                        .await;
+                                    place_block(
+                                        &world,
+                                        &block_position,
+                                        &block,
+                                        BlockFlags::FORCE_STATE,
+                                    )
+                                    .await;
                                 } else {
                                     world
                                         .set_block_state(
@@ -217,13 +225,13 @@ impl CommandExecutor for Executor {
                                 {
                                     continue;
                                 }
-                                world
-                                    .set_block_state(
-                                        &block_position,
-                                        block_state_id,
-                                        BlockFlags::FORCE_STATE,
-                                    )
-                                    .await;
+                                place_block(
+                                    &world,
+                                    &block_position,
+                                    &block,
+                                    BlockFlags::FORCE_STATE,
+                                )
+                                .await;
                                 placed_blocks += 1;
                                 to_update.push(block_position);
                             }
@@ -240,13 +248,13 @@ impl CommandExecutor for Executor {
                                 {
                                     continue;
                                 }
-                                world
-                                    .set_block_state(
-                                        &block_position,
-                                        block_state_id,
-                                        BlockFlags::SKIP_BLOCK_ADDED_CALLBACK,
-                                    )
-                                    .await;
+                                place_block(
+                                    &world,
+                                    &block_position,
+                                    &block,
+                                    BlockFlags::SKIP_BLOCK_ADDED_CALLBACK,
+                                )
+                                .await;
                                 placed_blocks += 1;
                             }
                         }
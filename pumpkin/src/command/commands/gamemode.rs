@@ -36,7 +36,7 @@ impl CommandExecutor for TargetSelfExecutor {
 
             if let Player(target) = sender {
                 if target.gamemode.load() != gamemode {
-                    target.set_gamemode(gamemode).await;
+                    target.set_gamemode(gamemode, &sender.to_string()).await;
                     let gamemode_string = format!("{gamemode:?}").to_lowercase();
                     let gamemode_string = format!("gameMode.{gamemode_string}");
                     target
@@ -75,7 +75,7 @@ impl CommandExecutor for TargetPlayerExecutor {
 
             for target in targets {
                 if target.gamemode.load() != gamemode {
-                    target.set_gamemode(gamemode).await;
+                    target.set_gamemode(gamemode, &sender.to_string()).await;
                     let gamemode_string = format!("{gamemode:?}").to_lowercase();
                     let gamemode_string = format!("gameMode.{gamemode_string}");
                     target
@@ -69,8 +69,15 @@ impl CommandExecutor for SetExecutor {
                     arg_value.to_string()
                 }
             });
+            let advance_time = level_info.game_rules.advance_time;
             drop(level_info);
 
+            if self.0 == GameRule::AdvanceTime {
+                for world in server.worlds.read().await.iter() {
+                    world.send_daylight_cycle(advance_time).await;
+                }
+            }
+
             sender
                 .send_message(TextComponent::translate(
                     "commands.gamerule.set",
@@ -1,9 +1,6 @@
-use pumpkin_data::data_component::DataComponent::MaxStackSize;
-use pumpkin_data::data_component_impl::{MaxStackSizeImpl, get};
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::text::color::{Color, NamedColor};
 use pumpkin_util::text::hover::HoverEvent;
-use pumpkin_world::item::ItemStack;
 
 use crate::command::args::bounded_num::{BoundedNumArgumentConsumer, NotInBounds};
 use crate::command::args::players::PlayersArgumentConsumer;
@@ -11,7 +8,7 @@ use crate::command::args::resource::item::ItemArgumentConsumer;
 use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
 use crate::command::tree::CommandTree;
 use crate::command::tree::builder::{argument, argument_default_name};
-use crate::command::{CommandExecutor, CommandResult, CommandSender};
+use crate::command::{CommandError, CommandExecutor, CommandResult, CommandSender};
 use crate::entity::EntityBase;
 
 const NAMES: [&str; 1] = ["give"];
@@ -20,6 +17,9 @@ const DESCRIPTION: &str = "Give items to player(s).";
 
 const ARG_ITEM: &str = "item";
 
+/// At most this many stacks of an item can be given at once.
+const MAX_STACKS: i32 = 100;
+
 fn item_count_consumer() -> BoundedNumArgumentConsumer<i32> {
     BoundedNumArgumentConsumer::new()
         .name("count")
@@ -39,7 +39,8 @@ impl CommandExecutor for Executor {
         Box::pin(async move {
             let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
 
-            let (item_name, item) = ItemArgumentConsumer::find_arg(args, ARG_ITEM)?;
+            let (item_name, template) = ItemArgumentConsumer::find_arg(args, ARG_ITEM)?;
+            let item = template.item;
 
             let item_count = match item_count_consumer().find_arg_default_name(args) {
                 Err(_) => 1,
@@ -63,20 +64,23 @@ impl CommandExecutor for Executor {
                 }
             };
 
+            let max_stack = i32::from(template.get_max_stack_size());
+            if item_count > max_stack * MAX_STACKS {
+                return Err(CommandError::CommandFailed(TextComponent::translate(
+                    "commands.give.failed.toomanyitems",
+                    [
+                        TextComponent::text((max_stack * MAX_STACKS).to_string()),
+                        item.translated_name(),
+                    ],
+                )));
+            }
+
             for target in targets {
-                let max_stack = i32::from(
-                    item.components
-                        .iter()
-                        .find_map(|(id, component)| {
-                            (id == &MaxStackSize).then(|| get::<MaxStackSizeImpl>(*component).size)
-                        })
-                        .unwrap(),
-                );
                 let mut remaining = item_count;
 
                 while remaining > 0 {
                     let take = remaining.min(max_stack);
-                    let mut stack = ItemStack::new(take as u8, item);
+                    let mut stack = template.copy_with_count(take as u8);
                     target.inventory().insert_stack_anywhere(&mut stack).await;
                     if !stack.is_empty() {
                         target.drop_item(stack).await;
@@ -9,7 +9,6 @@ use crate::command::tree::builder::argument;
 use crate::command::{CommandError, CommandResult};
 use crate::command::{CommandExecutor, CommandSender};
 use crate::entity::EntityBase;
-use crate::net::DisconnectReason;
 use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["kick"];
@@ -39,7 +38,7 @@ impl CommandExecutor for Executor {
             };
 
             for target in targets {
-                target.kick(DisconnectReason::Kicked, reason.clone()).await;
+                target.kick_by(&sender.to_string(), reason.clone()).await;
                 let mut msg = TextComponent::text("Kicked: ");
                 msg = msg.add_child(target.get_display_name().await);
                 sender.send_message(msg.color_named(NamedColor::Blue)).await;
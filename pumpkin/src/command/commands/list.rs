@@ -1,36 +1,82 @@
 use std::sync::Arc;
 
 use pumpkin_util::text::TextComponent;
+use pumpkin_util::translation::Locale;
 
 use crate::{
     command::{
-        CommandExecutor, CommandResult, CommandSender, args::ConsumedArgs, tree::CommandTree,
+        CommandError, CommandExecutor, CommandResult, CommandSender,
+        args::{Arg, ConsumedArgs, simple::SimpleArgConsumer},
+        tree::{
+            CommandTree,
+            builder::{argument, literal},
+        },
     },
     entity::player::Player,
+    server::Server,
 };
 
 const NAMES: [&str; 1] = ["list"];
 
 const DESCRIPTION: &str = "Print the list of online players.";
 
-struct Executor;
+const ARG_WORLD: &str = "world";
+
+/// At most this many players are listed, the others are only counted.
+const MAX_LISTED_PLAYERS: usize = 100;
+
+struct Executor {
+    uuids: bool,
+}
 
 impl CommandExecutor for Executor {
     fn execute<'a>(
         &'a self,
         sender: &'a CommandSender,
-        server: &'a crate::server::Server,
-        _args: &'a ConsumedArgs<'a>,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
-            let players: Vec<Arc<Player>> = server.get_all_players().await;
+            let players: Vec<Arc<Player>> = match args.get(&ARG_WORLD) {
+                Some(Arg::Simple(name)) => {
+                    let worlds = server.worlds.read().await;
+                    let Some(world) = worlds.iter().find(|world| {
+                        let dimension = world.dimension.minecraft_name;
+                        dimension == *name || dimension.strip_prefix("minecraft:") == Some(*name)
+                    }) else {
+                        return Err(CommandError::CommandFailed(TextComponent::translate(
+                            "argument.dimension.invalid",
+                            [TextComponent::text((*name).to_string())],
+                        )));
+                    };
+                    world.players.read().await.values().cloned().collect()
+                }
+                _ => server.get_all_players().await,
+            };
+            let names: Vec<String> = players
+                .iter()
+                .map(|player| {
+                    if self.uuids {
+                        TextComponent::translate(
+                            "commands.list.nameAndId",
+                            [
+                                TextComponent::text(player.gameprofile.name.clone()),
+                                TextComponent::text(player.gameprofile.id.to_string()),
+                            ],
+                        )
+                        .get_text()
+                    } else {
+                        player.gameprofile.name.clone()
+                    }
+                })
+                .collect();
             sender
                 .send_message(TextComponent::translate(
                     "commands.list.players",
                     [
                         TextComponent::text(players.len().to_string()),
                         TextComponent::text(server.basic_config.max_players.to_string()),
-                        TextComponent::text(get_player_names(players)),
+                        join_names(&names, MAX_LISTED_PLAYERS, sender.get_locale().await),
                     ],
                 ))
                 .await;
@@ -39,17 +85,48 @@ impl CommandExecutor for Executor {
     }
 }
 
-fn get_player_names(players: Vec<Arc<Player>>) -> String {
-    let mut names = String::new();
-    for player in players {
-        if !names.is_empty() {
-            names.push_str(", ");
-        }
-        names.push_str(&player.gameprofile.name);
+/// Joins the first `limit` names, the remaining ones are only counted.
+fn join_names(names: &[String], limit: usize, locale: Locale) -> TextComponent {
+    let listed = TextComponent::text(names[..names.len().min(limit)].join(", "));
+    if names.len() > limit {
+        TextComponent::custom(
+            "pumpkin",
+            "commands.list.more",
+            locale,
+            vec![
+                listed,
+                TextComponent::text((names.len() - limit).to_string()),
+            ],
+        )
+    } else {
+        listed
     }
-    names
 }
 
 pub fn init_command_tree() -> CommandTree {
-    CommandTree::new(NAMES, DESCRIPTION).execute(Executor)
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("uuids").execute(Executor { uuids: true }))
+        .then(
+            argument(ARG_WORLD, SimpleArgConsumer)
+                .then(literal("uuids").execute(Executor { uuids: true }))
+                .execute(Executor { uuids: false }),
+        )
+        .execute(Executor { uuids: false })
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::translation::Locale;
+
+    use super::join_names;
+
+    #[test]
+    fn long_player_lists_are_cut_off() {
+        let names: Vec<String> = ["Alex", "Steve", "Notch"].map(String::from).into();
+        let joined = |names: &[String], limit| join_names(names, limit, Locale::EnUs).get_text();
+        assert_eq!(joined(&names, 5), "Alex, Steve, Notch");
+        assert_eq!(joined(&names, 3), "Alex, Steve, Notch");
+        assert_eq!(joined(&names, 1), "Alex and 2 more");
+        assert_eq!(joined(&[], 1), "");
+    }
 }
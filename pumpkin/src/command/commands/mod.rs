@@ -9,6 +9,7 @@ use crate::PERMISSION_REGISTRY;
 use super::dispatcher::CommandDispatcher;
 
 mod advancement;
+mod attribute;
 mod ban;
 mod banip;
 mod banlist;
@@ -40,13 +41,17 @@ mod playsound;
 mod plugin;
 mod plugins;
 mod pumpkin;
+mod reload;
+mod save_all;
 mod say;
 mod seed;
 mod setblock;
 mod setworldspawn;
+mod spreadplayers;
 mod stop;
 mod stopsound;
 mod summon;
+mod team;
 mod teleport;
 mod tellraw;
 mod tick;
@@ -59,9 +64,14 @@ mod worldborder;
 
 #[must_use]
 pub async fn default_dispatcher(basic_config: &BasicConfiguration) -> CommandDispatcher {
-    let mut dispatcher = CommandDispatcher::default();
-
     register_permissions().await;
+    builtin_dispatcher(basic_config)
+}
+
+/// A dispatcher with all builtin commands, their permissions have to be registered already.
+#[must_use]
+pub fn builtin_dispatcher(basic_config: &BasicConfiguration) -> CommandDispatcher {
+    let mut dispatcher = CommandDispatcher::default();
 
     // Zero
     dispatcher.register(pumpkin::init_command_tree(), "pumpkin:command.pumpkin");
@@ -77,6 +87,10 @@ pub async fn default_dispatcher(basic_config: &BasicConfiguration) -> CommandDis
     );
     dispatcher.register(effect::init_command_tree(), "minecraft:command.effect");
     dispatcher.register(teleport::init_command_tree(), "minecraft:command.teleport");
+    dispatcher.register(
+        spreadplayers::init_command_tree(),
+        "minecraft:command.spreadplayers",
+    );
     dispatcher.register(time::init_command_tree(), "minecraft:command.time");
     dispatcher.register(
         tick::init_command_tree(basic_config.tps),
@@ -103,6 +117,11 @@ pub async fn default_dispatcher(basic_config: &BasicConfiguration) -> CommandDis
     dispatcher.register(particle::init_command_tree(), "minecraft:command.particle");
     dispatcher.register(damage::init_command_tree(), "minecraft:command.damage");
     dispatcher.register(bossbar::init_command_tree(), "minecraft:command.bossbar");
+    dispatcher.register(team::init_command_tree(), "minecraft:command.team");
+    dispatcher.register(
+        attribute::init_command_tree(),
+        "minecraft:command.attribute",
+    );
     dispatcher.register(say::init_command_tree(), "minecraft:command.say");
     dispatcher.register(gamemode::init_command_tree(), "minecraft:command.gamemode");
     dispatcher.register(gamerule::init_command_tree(), "minecraft:command.gamerule");
@@ -127,6 +146,7 @@ pub async fn default_dispatcher(basic_config: &BasicConfiguration) -> CommandDis
         advancement::init_command_tree(),
         "minecraft:command.advancement",
     );
+    dispatcher.register(reload::init_command_tree(), "minecraft:command.reload");
     // Three
     dispatcher.register(op::init_command_tree(), "minecraft:command.op");
     dispatcher.register(deop::init_command_tree(), "minecraft:command.deop");
@@ -145,6 +165,7 @@ pub async fn default_dispatcher(basic_config: &BasicConfiguration) -> CommandDis
     dispatcher.register(transfer::init_command_tree(), "minecraft:command.transfer");
     // Four
     dispatcher.register(stop::init_command_tree(), "minecraft:command.stop");
+    dispatcher.register(save_all::init_command_tree(), "minecraft:command.save-all");
 
     dispatcher
 }
@@ -235,6 +256,13 @@ fn register_level_2_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Two),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.spreadplayers",
+            "Teleports entities to random surface locations within an area",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
     registry
         .register_permission(Permission::new(
             "minecraft:command.time",
@@ -340,6 +368,20 @@ fn register_level_2_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Two),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.team",
+            "Controls teams",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.attribute",
+            "Queries and changes entity attributes",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
     registry
         .register_permission(Permission::new(
             "minecraft:command.say",
@@ -396,6 +438,21 @@ fn register_level_2_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Two),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.reload",
+            "Reloads the commands and configuration",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
+    // Not a command, but uses the same level as vanilla
+    registry
+        .register_permission(Permission::new(
+            "pumpkin:bypass.spawn_protection",
+            "Allows breaking and placing blocks inside the spawn protection",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
 }
 
 fn register_level_3_permissions(registry: &mut PermissionRegistry) {
@@ -509,4 +566,11 @@ fn register_level_4_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Four),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.save-all",
+            "Saves the server to disk",
+            PermissionDefault::Op(PermissionLvl::Four),
+        ))
+        .unwrap();
 }
@@ -46,21 +46,13 @@ impl CommandExecutor for Executor {
                     continue;
                 }
 
-                if let Some(op) = config
-                    .ops
-                    .iter_mut()
-                    .find(|o| o.uuid == player.gameprofile.id)
-                {
-                    op.level = new_level;
-                } else {
-                    let op_entry = Op::new(
-                        player.gameprofile.id,
-                        player.gameprofile.name.clone(),
-                        new_level,
-                        false,
-                    );
-                    config.ops.push(op_entry);
-                }
+                let op_entry = Op::new(
+                    player.gameprofile.id,
+                    player.gameprofile.name.clone(),
+                    new_level,
+                    false,
+                );
+                config.op(op_entry, &sender.to_string(), &server.audit_log);
 
                 config.save();
 
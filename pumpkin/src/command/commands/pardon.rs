@@ -20,7 +20,7 @@ impl CommandExecutor for Executor {
     fn execute<'a>(
         &'a self,
         sender: &'a CommandSender,
-        _server: &'a crate::server::Server,
+        server: &'a crate::server::Server,
         args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
@@ -31,13 +31,7 @@ impl CommandExecutor for Executor {
 
             let mut lock = BANNED_PLAYER_LIST.write().await;
 
-            if let Some(idx) = lock
-                .banned_players
-                .iter()
-                .position(|entry| entry.name == target)
-            {
-                lock.banned_players.remove(idx);
-            } else {
+            if !lock.pardon(&target, &sender.to_string(), &server.audit_log) {
                 sender
                     .send_message(TextComponent::translate("commands.pardon.failed", []))
                     .await;
@@ -45,6 +39,7 @@ impl CommandExecutor for Executor {
             }
 
             lock.save();
+            drop(lock);
 
             sender
                 .send_message(TextComponent::translate(
@@ -22,7 +22,7 @@ impl CommandExecutor for Executor {
     fn execute<'a>(
         &'a self,
         sender: &'a CommandSender,
-        _server: &'a crate::server::Server,
+        server: &'a crate::server::Server,
         args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
@@ -39,9 +39,7 @@ impl CommandExecutor for Executor {
 
             let mut lock = BANNED_IP_LIST.write().await;
 
-            if let Some(idx) = lock.banned_ips.iter().position(|entry| entry.ip == ip) {
-                lock.banned_ips.remove(idx);
-            } else {
+            if !lock.pardon(&ip, &sender.to_string(), &server.audit_log) {
                 sender
                     .send_message(TextComponent::translate("commands.pardonip.failed", []))
                     .await;
@@ -49,6 +47,7 @@ impl CommandExecutor for Executor {
             }
 
             lock.save();
+            drop(lock);
 
             sender
                 .send_message(TextComponent::translate(
@@ -1,12 +1,18 @@
+use std::sync::Arc;
+
 use pumpkin_util::{math::vector3::Vector3, text::TextComponent};
 
 use crate::command::{
     CommandError, CommandExecutor, CommandResult, CommandSender,
     args::{
         ConsumedArgs, FindArg, bounded_num::BoundedNumArgumentConsumer,
-        position_3d::Position3DArgumentConsumer, resource::particle::ParticleArgumentConsumer,
+        players::PlayersArgumentConsumer, position_3d::Position3DArgumentConsumer,
+        resource::particle::ParticleArgumentConsumer,
+    },
+    tree::{
+        CommandTree,
+        builder::{NonLeafNodeBuilder, argument, literal},
     },
-    tree::{CommandTree, builder::argument},
 };
 const NAMES: [&str; 1] = ["particle"];
 
@@ -18,8 +24,33 @@ const ARG_POS: &str = "pos";
 const ARG_DELTA: &str = "delta";
 const ARG_SPEED: &str = "speed";
 const ARG_COUNT: &str = "count";
+const ARG_VIEWERS: &str = "viewers";
+
+/// How close players have to be to see a particle.
+const VIEW_DISTANCE: f64 = 32.0;
+/// How close players have to be to see a forced particle.
+const FORCED_VIEW_DISTANCE: f64 = 512.0;
 
-struct Executor;
+#[derive(Clone, Copy)]
+enum Mode {
+    /// Shows the particle to players up to 512 blocks away, even with minimal particles
+    Force,
+    /// Shows the particle to players up to 32 blocks away
+    Normal,
+}
+
+impl Mode {
+    /// Returns whether a player at `viewer` sees a particle spawned at `position`.
+    fn is_visible(self, viewer: Vector3<f64>, position: Vector3<f64>) -> bool {
+        let distance = match self {
+            Self::Force => FORCED_VIEW_DISTANCE,
+            Self::Normal => VIEW_DISTANCE,
+        };
+        viewer.squared_distance_to_vec(position) < distance * distance
+    }
+}
+
+struct Executor(Mode);
 
 impl CommandExecutor for Executor {
     fn execute<'a>(
@@ -28,6 +59,7 @@ impl CommandExecutor for Executor {
         server: &'a crate::server::Server,
         args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
+        let Self(mode) = *self;
         Box::pin(async move {
             let particle = ParticleArgumentConsumer::find_arg(args, ARG_NAME)?;
             let pos = Position3DArgumentConsumer::find_arg(args, ARG_POS);
@@ -69,14 +101,45 @@ impl CommandExecutor for Executor {
                 }
             };
 
-            world
-                .spawn_particle(pos, delta, speed, count, *particle)
-                .await;
+            let viewers = match PlayersArgumentConsumer::find_arg(args, ARG_VIEWERS) {
+                Ok(viewers) => viewers.to_vec(),
+                Err(_) => world.players.read().await.values().cloned().collect(),
+            };
+
+            let mut shown = false;
+            for viewer in viewers {
+                if !Arc::ptr_eq(viewer.world(), &world)
+                    || !mode.is_visible(viewer.living_entity.entity.pos.load(), pos)
+                {
+                    continue;
+                }
+                viewer
+                    .send_particle(
+                        matches!(mode, Mode::Force),
+                        pos,
+                        delta,
+                        speed,
+                        count,
+                        particle,
+                    )
+                    .await;
+                shown = true;
+            }
+
+            if !shown {
+                return Err(CommandError::CommandFailed(TextComponent::translate(
+                    "commands.particle.failed",
+                    [],
+                )));
+            }
 
             sender
                 .send_message(TextComponent::translate(
                     "commands.particle.success",
-                    [TextComponent::text(format!("{particle:?}"))],
+                    [TextComponent::text(format!(
+                        "minecraft:{}",
+                        particle.to_name()
+                    ))],
                 ))
                 .await;
 
@@ -88,25 +151,27 @@ impl CommandExecutor for Executor {
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_NAME, ParticleArgumentConsumer)
-            .execute(Executor)
+            .execute(Executor(Mode::Normal))
             .then(
                 argument(ARG_POS, Position3DArgumentConsumer)
-                    .execute(Executor)
+                    .execute(Executor(Mode::Normal))
                     .then(
                         argument(ARG_DELTA, Position3DArgumentConsumer)
-                            .execute(Executor)
+                            .execute(Executor(Mode::Normal))
                             .then(
                                 argument(
                                     ARG_SPEED,
                                     BoundedNumArgumentConsumer::<f32>::new().min(0.0),
                                 )
-                                .execute(Executor)
+                                .execute(Executor(Mode::Normal))
                                 .then(
                                     argument(
                                         ARG_COUNT,
                                         BoundedNumArgumentConsumer::<i32>::new().min(0),
                                     )
-                                    .execute(Executor),
+                                    .execute(Executor(Mode::Normal))
+                                    .then(mode_node("force", Mode::Force))
+                                    .then(mode_node("normal", Mode::Normal)),
                                 ),
                             ),
                     ),
@@ -114,3 +179,30 @@ pub fn init_command_tree() -> CommandTree {
         // TODO: Add NBT
     )
 }
+
+fn mode_node(name: &str, mode: Mode) -> NonLeafNodeBuilder {
+    literal(name)
+        .execute(Executor(mode))
+        .then(argument(ARG_VIEWERS, PlayersArgumentConsumer).execute(Executor(mode)))
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::Mode;
+
+    #[test]
+    fn forced_particles_bypass_the_distance_cull() {
+        let particle = Vector3::new(0.0, 64.0, 0.0);
+        let near = Vector3::new(10.0, 64.0, 10.0);
+        let far = Vector3::new(100.0, 64.0, 0.0);
+        let very_far = Vector3::new(600.0, 64.0, 0.0);
+
+        assert!(Mode::Normal.is_visible(near, particle));
+        assert!(!Mode::Normal.is_visible(far, particle));
+        assert!(Mode::Force.is_visible(near, particle));
+        assert!(Mode::Force.is_visible(far, particle));
+        assert!(!Mode::Force.is_visible(very_far, particle));
+    }
+}
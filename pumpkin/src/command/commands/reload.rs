@@ -0,0 +1,42 @@
+use pumpkin_config::config_dir;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::tree::CommandTree;
+use crate::command::{CommandExecutor, CommandResult, CommandSender};
+
+const NAMES: [&str; 1] = ["reload"];
+
+const DESCRIPTION: &str = "Reloads the commands and the configuration.";
+
+struct Executor;
+
+impl CommandExecutor for Executor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a crate::server::Server,
+        _args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            match server.reload(&config_dir()).await {
+                Ok(()) => {
+                    sender
+                        .send_message(TextComponent::translate("commands.reload.success", []))
+                        .await;
+                }
+                Err(err) => {
+                    log::error!("Failed to reload: {err}");
+                    sender
+                        .send_message(TextComponent::translate("commands.reload.failure", []))
+                        .await;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(Executor)
+}
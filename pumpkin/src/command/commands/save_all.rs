@@ -0,0 +1,38 @@
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::tree::CommandTree;
+use crate::command::{CommandExecutor, CommandResult, CommandSender};
+
+const NAMES: [&str; 1] = ["save-all"];
+
+const DESCRIPTION: &str = "Saves the server to disk.";
+
+struct Executor;
+
+impl CommandExecutor for Executor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a crate::server::Server,
+        _args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            sender
+                .send_message(TextComponent::translate("commands.save.saving", []))
+                .await;
+            if let Err(e) = server.player_data_storage.save_all_players(server).await {
+                log::error!("Error saving all players: {e}");
+            }
+            server.save_all().await;
+            sender
+                .send_message(TextComponent::translate("commands.save.success", []))
+                .await;
+            Ok(())
+        })
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(Executor)
+}
@@ -38,7 +38,6 @@ impl CommandExecutor for Executor {
     ) -> CommandResult<'a> {
         Box::pin(async move {
             let block = BlockArgumentConsumer::find_arg(args, ARG_BLOCK)?;
-            let block_state_id = block.default_state.id;
             let pos = BlockPosArgumentConsumer::find_arg(args, ARG_BLOCK_POS)?;
             let mode = self.0;
             let world = match sender {
@@ -53,45 +52,29 @@ impl CommandExecutor for Executor {
                 CommandSender::Player(player) => player.world().clone(),
                 CommandSender::CommandBlock(_, w) => w.clone(),
             };
+            let old_state_id = world.get_block_state_id(&pos).await;
             let success = match mode {
-                Mode::Destroy => {
-                    world
-                        .clone()
-                        .break_block(&pos, None, BlockFlags::SKIP_DROPS | BlockFlags::FORCE_STATE)
-                        .await;
-                    world
-                        .set_block_state(
-                            &pos,
-                            block_state_id,
-                            BlockFlags::FORCE_STATE | BlockFlags::NOTIFY_NEIGHBORS,
-                        )
-                        .await;
-                    true
-                }
-                Mode::Replace => {
+                Mode::Keep if !world.get_block_state(&pos).await.is_air() => false,
+                // Setting the same block again only makes sense to change its block entity
+                Mode::Replace if old_state_id == block.state_id && block.nbt.is_none() => false,
+                _ => {
+                    if matches!(mode, Mode::Destroy) {
+                        world
+                            .clone()
+                            .break_block(&pos, None, BlockFlags::FORCE_STATE)
+                            .await;
+                    }
                     world
                         .set_block_state(
                             &pos,
-                            block_state_id,
+                            block.state_id,
                             BlockFlags::FORCE_STATE | BlockFlags::NOTIFY_NEIGHBORS,
                         )
                         .await;
-                    true
-                }
-                Mode::Keep => {
-                    let old_state = world.get_block_state(&pos).await;
-                    if old_state.is_air() {
-                        world
-                            .set_block_state(
-                                &pos,
-                                block_state_id,
-                                BlockFlags::FORCE_STATE | BlockFlags::NOTIFY_NEIGHBORS,
-                            )
-                            .await;
-                        true
-                    } else {
-                        false
+                    if let Some(nbt) = &block.nbt {
+                        world.merge_block_entity_nbt(&pos, nbt).await;
                     }
+                    true
                 }
             };
 
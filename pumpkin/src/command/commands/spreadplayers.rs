@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use pumpkin_data::{tag, tag::Taggable};
+use pumpkin_util::math::{
+    boundingbox::BoundingBox, position::BlockPos, vector2::Vector2, vector3::Vector3,
+};
+use pumpkin_util::text::TextComponent;
+use rand::Rng;
+
+use crate::command::args::bool::BoolArgConsumer;
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::entities::EntitiesArgumentConsumer;
+use crate::command::args::position_2d::Position2DArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::argument;
+use crate::command::{CommandError, CommandExecutor, CommandResult, CommandSender};
+use crate::entity::EntityBase;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["spreadplayers"];
+const DESCRIPTION: &str = "Teleports entities to random surface locations within an area.";
+
+const ARG_CENTER: &str = "center";
+const ARG_SPREAD_DISTANCE: &str = "spreadDistance";
+const ARG_MAX_RANGE: &str = "maxRange";
+const ARG_RESPECT_TEAMS: &str = "respectTeams";
+const ARG_TARGETS: &str = "targets";
+
+/// How often the positions are pushed apart before a spread counts as impossible.
+const MAX_ITERATIONS: u32 = 10000;
+/// How often new positions are rolled when some of them are not safe to stand on.
+const MAX_ATTEMPTS: u32 = 100;
+
+fn spread_distance_consumer() -> BoundedNumArgumentConsumer<f32> {
+    BoundedNumArgumentConsumer::new()
+        .name(ARG_SPREAD_DISTANCE)
+        .min(0.0)
+}
+
+fn max_range_consumer() -> BoundedNumArgumentConsumer<f32> {
+    BoundedNumArgumentConsumer::new()
+        .name(ARG_MAX_RANGE)
+        .min(1.0)
+}
+
+/// Places `count` random positions within `max_range` of `center` and pushes every position
+/// that is closer than `spread` to another one away from it, like vanilla does.
+///
+/// Returns `None` if the positions could not be spread apart in time.
+fn spread_positions(
+    center: Vector2<f64>,
+    spread: f64,
+    max_range: f64,
+    count: usize,
+    random: &mut impl Rng,
+) -> Option<Vec<Vector2<f64>>> {
+    let (min_x, max_x) = (center.x - max_range, center.x + max_range);
+    let (min_z, max_z) = (center.y - max_range, center.y + max_range);
+    let random_position = |random: &mut dyn rand::RngCore| {
+        Vector2::new(
+            random.random_range(min_x..=max_x),
+            random.random_range(min_z..=max_z),
+        )
+    };
+    let mut positions: Vec<_> = (0..count).map(|_| random_position(random)).collect();
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut moved = false;
+        for i in 0..positions.len() {
+            let mut push = Vector2::new(0.0, 0.0);
+            let mut too_close = false;
+            for j in 0..positions.len() {
+                if i == j {
+                    continue;
+                }
+                let dx = positions[i].x - positions[j].x;
+                let dz = positions[i].y - positions[j].y;
+                if dx.hypot(dz) < spread {
+                    too_close = true;
+                    push.x += dx;
+                    push.y += dz;
+                }
+            }
+
+            if too_close {
+                moved = true;
+                let length = push.x.hypot(push.y);
+                if length > 0.0 {
+                    positions[i].x += push.x / length;
+                    positions[i].y += push.y / length;
+                } else {
+                    // Both positions are the same, so there is no direction to push into
+                    positions[i] = random_position(random);
+                }
+            }
+
+            let position = &mut positions[i];
+            if position.x < min_x || position.x > max_x || position.y < min_z || position.y > max_z
+            {
+                moved = true;
+                position.x = position.x.clamp(min_x, max_x);
+                position.y = position.y.clamp(min_z, max_z);
+            }
+        }
+        if !moved {
+            return Some(positions);
+        }
+    }
+    None
+}
+
+/// Returns where an entity of `target` size can stand on top of the column, or `None` if the
+/// column is void or its surface is lava, water or fire.
+async fn safe_position(
+    world: &World,
+    target: &dyn EntityBase,
+    position: Vector2<f64>,
+) -> Option<Vector3<f64>> {
+    let x = position.x.floor() as i32;
+    let z = position.y.floor() as i32;
+    let top = world.get_top_block(Vector2::new(x, z)).await;
+    let (block, state) = world.get_block_and_state(&BlockPos::new(x, top, z)).await;
+    if state.is_air() || state.is_liquid() || block.has_tag(&tag::Block::MINECRAFT_FIRE) {
+        return None;
+    }
+
+    let feet = Vector3::new(f64::from(x) + 0.5, f64::from(top + 1), f64::from(z) + 0.5);
+    let dimension = target.get_entity().entity_dimension.load();
+    world
+        .is_space_empty(BoundingBox::new_from_pos(
+            feet.x, feet.y, feet.z, &dimension,
+        ))
+        .await
+        .then_some(feet)
+}
+
+/// The average distance of every position to its closest neighbour.
+fn average_distance(positions: &[Vector2<f64>]) -> f64 {
+    if positions.len() < 2 {
+        return 0.0;
+    }
+    let total: f64 = positions
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            positions
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| i != *j)
+                .map(|(_, b)| (a.x - b.x).hypot(a.y - b.y))
+                .fold(f64::MAX, f64::min)
+        })
+        .sum();
+    total / positions.len() as f64
+}
+
+struct Executor;
+
+impl CommandExecutor for Executor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a crate::server::Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let center = Position2DArgumentConsumer::find_arg(args, ARG_CENTER)?;
+            let Ok(Ok(spread)) =
+                BoundedNumArgumentConsumer::<f32>::find_arg(args, ARG_SPREAD_DISTANCE)
+            else {
+                return Err(CommandError::InvalidConsumption(Some(
+                    ARG_SPREAD_DISTANCE.into(),
+                )));
+            };
+            let Ok(Ok(max_range)) =
+                BoundedNumArgumentConsumer::<f32>::find_arg(args, ARG_MAX_RANGE)
+            else {
+                return Err(CommandError::InvalidConsumption(Some(ARG_MAX_RANGE.into())));
+            };
+            // Teams are not implemented yet, so every entity is spread on its own
+            let _respect_teams = BoolArgConsumer::find_arg(args, ARG_RESPECT_TEAMS)?;
+            let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+            let world = match sender.world() {
+                Some(world) => world,
+                None => server.worlds.read().await.first().unwrap().clone(),
+            };
+            let (spread, max_range) = (f64::from(spread), f64::from(max_range));
+
+            let mut placement = None;
+            for _ in 0..MAX_ATTEMPTS {
+                let Some(positions) =
+                    spread_positions(center, spread, max_range, targets.len(), &mut rand::rng())
+                else {
+                    break;
+                };
+                let mut destinations = Vec::with_capacity(targets.len());
+                for (target, position) in targets.iter().zip(&positions) {
+                    let Some(destination) = safe_position(&world, &**target, *position).await
+                    else {
+                        break;
+                    };
+                    destinations.push(destination);
+                }
+                if destinations.len() == targets.len() {
+                    placement = Some((positions, destinations));
+                    break;
+                }
+            }
+
+            let Some((positions, destinations)) = placement else {
+                let side = max_range * 2.0;
+                let max_spread = side / (targets.len() as f64).sqrt().ceil();
+                return Err(CommandError::CommandFailed(TextComponent::translate(
+                    "commands.spreadplayers.failed.entities",
+                    [
+                        TextComponent::text(targets.len().to_string()),
+                        TextComponent::text(center.x.to_string()),
+                        TextComponent::text(center.y.to_string()),
+                        TextComponent::text(format!("{max_spread:.2}")),
+                    ],
+                )));
+            };
+
+            for (target, destination) in targets.iter().zip(destinations) {
+                Arc::clone(target)
+                    .teleport(destination, None, None, world.clone())
+                    .await;
+            }
+
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.spreadplayers.success.entities",
+                    [
+                        TextComponent::text(targets.len().to_string()),
+                        TextComponent::text(center.x.to_string()),
+                        TextComponent::text(center.y.to_string()),
+                        TextComponent::text(format!("{:.2}", average_distance(&positions))),
+                    ],
+                ))
+                .await;
+
+            Ok(())
+        })
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_CENTER, Position2DArgumentConsumer).then(
+            argument(ARG_SPREAD_DISTANCE, spread_distance_consumer()).then(
+                argument(ARG_MAX_RANGE, max_range_consumer()).then(
+                    argument(ARG_RESPECT_TEAMS, BoolArgConsumer)
+                        .then(argument(ARG_TARGETS, EntitiesArgumentConsumer).execute(Executor)),
+                ),
+            ),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::spread_positions;
+
+    #[test]
+    fn positions_are_spread_within_range() {
+        let center = Vector2::new(100.0, -50.0);
+        let positions = spread_positions(center, 10.0, 50.0, 12, &mut rand::rng())
+            .expect("12 positions fit into a 100x100 area");
+
+        assert_eq!(positions.len(), 12);
+        for (i, a) in positions.iter().enumerate() {
+            assert!((a.x - center.x).abs() <= 50.0 && (a.y - center.y).abs() <= 50.0);
+            for b in &positions[i + 1..] {
+                assert!((a.x - b.x).hypot(a.y - b.y) >= 10.0);
+            }
+        }
+    }
+
+    #[test]
+    fn impossible_spread_fails() {
+        assert!(
+            spread_positions(Vector2::new(0.0, 0.0), 100.0, 1.0, 4, &mut rand::rng()).is_none()
+        );
+    }
+}
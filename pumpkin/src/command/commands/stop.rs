@@ -1,37 +1,102 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::text::color::NamedColor;
 
-use crate::command::args::ConsumedArgs;
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
 use crate::command::tree::CommandTree;
-use crate::command::{CommandExecutor, CommandResult, CommandSender};
-use crate::stop_server;
+use crate::command::tree::builder::argument;
+use crate::command::{CommandError, CommandExecutor, CommandResult, CommandSender};
+use crate::server::Server;
+use crate::{SHOULD_STOP, stop_server};
 
 const NAMES: [&str; 1] = ["stop"];
 
 const DESCRIPTION: &str = "Stop the server.";
 
+const ARG_SECONDS: &str = "seconds";
+
+fn seconds_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_SECONDS).min(0)
+}
+
+/// Whether players are reminded of the shutdown when `remaining` seconds are left.
+const fn announce_at(remaining: i32) -> bool {
+    remaining <= 5 || remaining % 10 == 0
+}
+
+/// Counts down and stops the server, unless it is stopped some other way in the meantime.
+async fn stop_after(server: Arc<Server>, seconds: i32) {
+    for remaining in (1..=seconds).rev() {
+        if SHOULD_STOP.load(Ordering::Relaxed) {
+            return;
+        }
+        if remaining == seconds || announce_at(remaining) {
+            let message = TextComponent::text(format!(
+                "Server stopping in {remaining} second{}",
+                if remaining == 1 { "" } else { "s" }
+            ))
+            .color_named(NamedColor::Red);
+            for player in server.get_all_players().await {
+                player.send_system_message(&message).await;
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    stop_server();
+}
+
 struct Executor;
 
 impl CommandExecutor for Executor {
     fn execute<'a>(
         &'a self,
         sender: &'a CommandSender,
-        _server: &'a crate::server::Server,
-        _args: &'a ConsumedArgs<'a>,
+        server: &'a crate::server::Server,
+        args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
+            let seconds = match BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_SECONDS) {
+                Ok(Ok(seconds)) => seconds,
+                Ok(Err(_)) => {
+                    return Err(CommandError::InvalidConsumption(Some(ARG_SECONDS.into())));
+                }
+                Err(_) => 0,
+            };
+
             sender
                 .send_message(
                     TextComponent::translate("commands.stop.stopping", [])
                         .color_named(NamedColor::Red),
                 )
                 .await;
-            stop_server();
+            // The shutdown saves the players and worlds
+            if seconds == 0 {
+                stop_server();
+                return Ok(());
+            }
+            // Counted down in its own task, the command must not hold the dispatcher meanwhile
+            let server = server
+                .worlds
+                .read()
+                .await
+                .first()
+                .and_then(|world| world.server.upgrade());
+            let Some(server) = server else {
+                stop_server();
+                return Ok(());
+            };
+            tokio::spawn(stop_after(server, seconds));
             Ok(())
         })
     }
 }
 
 pub fn init_command_tree() -> CommandTree {
-    CommandTree::new(NAMES, DESCRIPTION).execute(Executor)
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_SECONDS, seconds_consumer()).execute(Executor))
+        .execute(Executor)
 }
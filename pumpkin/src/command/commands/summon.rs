@@ -1,3 +1,4 @@
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_util::{math::vector3::Vector3, text::TextComponent};
 use uuid::Uuid;
 
@@ -5,7 +6,8 @@ use crate::{
     command::{
         CommandError, CommandExecutor, CommandResult, CommandSender,
         args::{
-            ConsumedArgs, FindArg, position_3d::Position3DArgumentConsumer,
+            ConsumedArgs, FindArg, nbt::NbtCompoundArgumentConsumer,
+            position_3d::Position3DArgumentConsumer,
             summonable_entities::SummonableEntitiesArgumentConsumer,
         },
         tree::{CommandTree, builder::argument},
@@ -20,6 +22,8 @@ const ARG_ENTITY: &str = "entity";
 
 const ARG_POS: &str = "pos";
 
+const ARG_NBT: &str = "nbt";
+
 struct Executor;
 
 impl CommandExecutor for Executor {
@@ -57,10 +61,22 @@ impl CommandExecutor for Executor {
                     (player.world().clone(), pos)
                 }
                 CommandSender::CommandBlock(c, w) => {
-                    (w.clone(), c.get_position().to_centered_f64())
+                    (w.clone(), pos.unwrap_or(c.get_position().to_centered_f64()))
                 }
             };
             let entity = from_type(entity_type, pos, &world, Uuid::new_v4()).await;
+            if let Ok(nbt) = NbtCompoundArgumentConsumer::find_arg(args, ARG_NBT) {
+                // The given values replace the ones of a freshly created entity, the position
+                // argument takes precedence over `Pos`
+                let mut nbt = nbt.clone();
+                nbt.child_tags.retain(|(key, _)| key != "Pos");
+                let mut defaults = NbtCompound::new();
+                entity.write_nbt(&mut defaults).await;
+                for (key, value) in defaults.child_tags {
+                    nbt.put(&key, value);
+                }
+                entity.read_nbt_non_mut(&nbt).await;
+            }
             let name = entity.get_display_name().await;
             world.spawn_entity(entity).await;
             sender
@@ -76,7 +92,10 @@ pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_ENTITY, SummonableEntitiesArgumentConsumer)
             .execute(Executor)
-            .then(argument(ARG_POS, Position3DArgumentConsumer).execute(Executor)),
-        // TODO: Add NBT
+            .then(
+                argument(ARG_POS, Position3DArgumentConsumer)
+                    .execute(Executor)
+                    .then(argument(ARG_NBT, NbtCompoundArgumentConsumer).execute(Executor)),
+            ),
     )
 }
@@ -0,0 +1,536 @@
+use pumpkin_protocol::java::client::play::{CollisionRule, NameTagVisibility};
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::command::args::bool::BoolArgConsumer;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::textcomponent::TextComponentArgConsumer;
+use crate::command::args::{Arg, ArgumentConsumer, ConsumedArgs, FindArg};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{NonLeafNodeBuilder, argument, literal};
+use crate::command::{CommandExecutor, CommandResult, CommandSender};
+use crate::server::Server;
+use crate::world::scoreboard::Team;
+
+const NAMES: [&str; 1] = ["team"];
+
+const DESCRIPTION: &str = "Controls teams.";
+
+const ARG_TEAM: &str = "team";
+const ARG_DISPLAY_NAME: &str = "displayName";
+const ARG_MEMBERS: &str = "members";
+const ARG_VALUE: &str = "value";
+
+const COLORS: [&str; 17] = [
+    "black",
+    "dark_blue",
+    "dark_green",
+    "dark_aqua",
+    "dark_red",
+    "dark_purple",
+    "gold",
+    "gray",
+    "dark_gray",
+    "blue",
+    "green",
+    "aqua",
+    "red",
+    "light_purple",
+    "yellow",
+    "white",
+    "reset",
+];
+
+const COLLISION_RULES: [(&str, CollisionRule); 4] = [
+    ("always", CollisionRule::Always),
+    ("never", CollisionRule::Never),
+    ("pushOtherTeams", CollisionRule::PushOtherTeams),
+    ("pushOwnTeam", CollisionRule::PushOwnTeam),
+];
+
+const NAME_TAG_VISIBILITIES: [(&str, NameTagVisibility); 4] = [
+    ("always", NameTagVisibility::Always),
+    ("never", NameTagVisibility::Never),
+    ("hideForOtherTeams", NameTagVisibility::HideForOtherTeams),
+    ("hideForOwnTeam", NameTagVisibility::HideForOwnTeam),
+];
+
+fn team_arg<'a>(args: &'a ConsumedArgs<'a>) -> Result<&'a str, CommandError> {
+    match args.get(&ARG_TEAM) {
+        Some(Arg::Simple(name)) => Ok(*name),
+        _ => Err(CommandError::InvalidConsumption(Some(ARG_TEAM.into()))),
+    }
+}
+
+/// Fails with a message which takes no arguments.
+fn failed(key: &'static str) -> CommandError {
+    CommandError::CommandFailed(TextComponent::translate(key, []))
+}
+
+fn team_not_found(name: &str) -> CommandError {
+    CommandError::CommandFailed(TextComponent::translate(
+        "team.notFound",
+        [TextComponent::text(name.to_string())],
+    ))
+}
+
+struct AddExecutor;
+
+impl CommandExecutor for AddExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let name = team_arg(args)?;
+            let display_name = TextComponentArgConsumer::find_arg(args, ARG_DISPLAY_NAME)
+                .unwrap_or_else(|_| TextComponent::text(name.to_string()));
+            let team = Team::new(name.to_string(), display_name.clone());
+            if !server.teams.lock().await.add_team(server, team).await {
+                return Err(failed("commands.team.add.duplicate"));
+            }
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.team.add.success",
+                    [display_name],
+                ))
+                .await;
+            Ok(())
+        })
+    }
+}
+
+struct RemoveExecutor;
+
+impl CommandExecutor for RemoveExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let name = team_arg(args)?;
+            let Some(team) = server.teams.lock().await.remove_team(server, name).await else {
+                return Err(team_not_found(name));
+            };
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.team.remove.success",
+                    [team.display_name],
+                ))
+                .await;
+            Ok(())
+        })
+    }
+}
+
+struct EmptyExecutor;
+
+impl CommandExecutor for EmptyExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let name = team_arg(args)?;
+            let mut teams = server.teams.lock().await;
+            let Some(team) = teams.get_team(name) else {
+                return Err(team_not_found(name));
+            };
+            let display_name = team.display_name.clone();
+            let members: Vec<String> = team.members.iter().cloned().collect();
+            if members.is_empty() {
+                return Err(failed("commands.team.empty.unchanged"));
+            }
+            for member in &members {
+                teams.leave_team(server, member).await;
+            }
+            drop(teams);
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.team.empty.success",
+                    [TextComponent::text(members.len().to_string()), display_name],
+                ))
+                .await;
+            Ok(())
+        })
+    }
+}
+
+/// Returns the members given as argument, or the sender if there is no such argument.
+fn members_or_sender(
+    sender: &CommandSender,
+    args: &ConsumedArgs<'_>,
+) -> Result<Vec<String>, CommandError> {
+    match PlayersArgumentConsumer::find_arg(args, ARG_MEMBERS) {
+        Ok(players) => Ok(players
+            .iter()
+            .map(|player| player.gameprofile.name.clone())
+            .collect()),
+        Err(_) => sender
+            .as_player()
+            .map(|player| vec![player.gameprofile.name.clone()])
+            .ok_or(CommandError::InvalidRequirement),
+    }
+}
+
+struct JoinExecutor;
+
+impl CommandExecutor for JoinExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let name = team_arg(args)?;
+            let members = members_or_sender(sender, args)?;
+            let mut teams = server.teams.lock().await;
+            let Some(display_name) = teams.get_team(name).map(|team| team.display_name.clone())
+            else {
+                return Err(team_not_found(name));
+            };
+            for member in &members {
+                teams.join_team(server, name, member.clone()).await;
+            }
+            drop(teams);
+            let message = match members.as_slice() {
+                [member] => TextComponent::translate(
+                    "commands.team.join.success.single",
+                    [TextComponent::text(member.clone()), display_name],
+                ),
+                _ => TextComponent::translate(
+                    "commands.team.join.success.multiple",
+                    [TextComponent::text(members.len().to_string()), display_name],
+                ),
+            };
+            sender.send_message(message).await;
+            Ok(())
+        })
+    }
+}
+
+struct LeaveExecutor;
+
+impl CommandExecutor for LeaveExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let members = members_or_sender(sender, args)?;
+            let mut teams = server.teams.lock().await;
+            for member in &members {
+                teams.leave_team(server, member).await;
+            }
+            drop(teams);
+            let message = match members.as_slice() {
+                [member] => TextComponent::translate(
+                    "commands.team.leave.success.single",
+                    [TextComponent::text(member.clone())],
+                ),
+                _ => TextComponent::translate(
+                    "commands.team.leave.success.multiple",
+                    [TextComponent::text(members.len().to_string())],
+                ),
+            };
+            sender.send_message(message).await;
+            Ok(())
+        })
+    }
+}
+
+struct ListExecutor;
+
+impl CommandExecutor for ListExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let teams = server.teams.lock().await;
+            let message = if let Ok(name) = team_arg(args) {
+                let Some(team) = teams.get_team(name) else {
+                    return Err(team_not_found(name));
+                };
+                let mut members: Vec<&str> = team.members.iter().map(String::as_str).collect();
+                members.sort_unstable();
+                if members.is_empty() {
+                    TextComponent::translate(
+                        "commands.team.list.members.empty",
+                        [team.display_name.clone()],
+                    )
+                } else {
+                    TextComponent::translate(
+                        "commands.team.list.members.success",
+                        [
+                            team.display_name.clone(),
+                            TextComponent::text(members.len().to_string()),
+                            TextComponent::text(members.join(", ")),
+                        ],
+                    )
+                }
+            } else {
+                let mut names: Vec<&str> = teams.teams().map(|team| team.name.as_str()).collect();
+                names.sort_unstable();
+                if names.is_empty() {
+                    TextComponent::translate("commands.team.list.teams.empty", [])
+                } else {
+                    TextComponent::translate(
+                        "commands.team.list.teams.success",
+                        [
+                            TextComponent::text(names.len().to_string()),
+                            TextComponent::text(names.join(", ")),
+                        ],
+                    )
+                }
+            };
+            drop(teams);
+            sender.send_message(message).await;
+            Ok(())
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TeamOption {
+    DisplayName,
+    Color(&'static str),
+    FriendlyFire,
+    SeeFriendlyInvisibles,
+    NameTagVisibility(&'static str, NameTagVisibility),
+    CollisionRule(&'static str, CollisionRule),
+    Prefix,
+    Suffix,
+}
+
+struct ModifyExecutor(TeamOption);
+
+impl CommandExecutor for ModifyExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let name = team_arg(args)?;
+            let mut teams = server.teams.lock().await;
+            let Some(team) = teams.get_team(name) else {
+                return Err(team_not_found(name));
+            };
+            let team_name = team.display_name.clone();
+
+            let message = match self.0 {
+                TeamOption::DisplayName => {
+                    let display_name = TextComponentArgConsumer::find_arg(args, ARG_VALUE)?;
+                    if team.display_name == display_name {
+                        return Err(failed("commands.team.option.name.unchanged"));
+                    }
+                    teams
+                        .update_team(server, name, |team| team.display_name = display_name)
+                        .await;
+                    TextComponent::translate("commands.team.option.name.success", [team_name])
+                }
+                TeamOption::Color(color_name) => {
+                    let color = NamedColor::try_from(color_name).ok();
+                    if team.color == color {
+                        return Err(failed("commands.team.option.color.unchanged"));
+                    }
+                    teams
+                        .update_team(server, name, |team| team.color = color)
+                        .await;
+                    TextComponent::translate(
+                        "commands.team.option.color.success",
+                        [team_name, TextComponent::text(color_name)],
+                    )
+                }
+                TeamOption::FriendlyFire => {
+                    let enabled = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+                    if team.friendly_fire == enabled {
+                        return Err(unchanged(if enabled {
+                            "commands.team.option.friendlyfire.alreadyEnabled"
+                        } else {
+                            "commands.team.option.friendlyfire.alreadyDisabled"
+                        }));
+                    }
+                    teams
+                        .update_team(server, name, |team| team.friendly_fire = enabled)
+                        .await;
+                    let key = if enabled {
+                        "commands.team.option.friendlyfire.enabled"
+                    } else {
+                        "commands.team.option.friendlyfire.disabled"
+                    };
+                    TextComponent::translate(key, [team_name])
+                }
+                TeamOption::SeeFriendlyInvisibles => {
+                    let enabled = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+                    if team.see_friendly_invisibles == enabled {
+                        return Err(unchanged(if enabled {
+                            "commands.team.option.seeFriendlyInvisibles.alreadyEnabled"
+                        } else {
+                            "commands.team.option.seeFriendlyInvisibles.alreadyDisabled"
+                        }));
+                    }
+                    teams
+                        .update_team(server, name, |team| {
+                            team.see_friendly_invisibles = enabled;
+                        })
+                        .await;
+                    let key = if enabled {
+                        "commands.team.option.seeFriendlyInvisibles.enabled"
+                    } else {
+                        "commands.team.option.seeFriendlyInvisibles.disabled"
+                    };
+                    TextComponent::translate(key, [team_name])
+                }
+                TeamOption::NameTagVisibility(value, visibility) => {
+                    if team.name_tag_visibility == visibility {
+                        return Err(failed("commands.team.option.nametagVisibility.unchanged"));
+                    }
+                    teams
+                        .update_team(server, name, |team| {
+                            team.name_tag_visibility = visibility;
+                        })
+                        .await;
+                    TextComponent::translate(
+                        "commands.team.option.nametagVisibility.success",
+                        [
+                            team_name,
+                            TextComponent::translate(format!("team.visibility.{value}"), []),
+                        ],
+                    )
+                }
+                TeamOption::CollisionRule(value, rule) => {
+                    if team.collision_rule == rule {
+                        return Err(failed("commands.team.option.collisionRule.unchanged"));
+                    }
+                    teams
+                        .update_team(server, name, |team| team.collision_rule = rule)
+                        .await;
+                    TextComponent::translate(
+                        "commands.team.option.collisionRule.success",
+                        [
+                            team_name,
+                            TextComponent::translate(format!("team.collision.{value}"), []),
+                        ],
+                    )
+                }
+                TeamOption::Prefix => {
+                    let prefix = TextComponentArgConsumer::find_arg(args, ARG_VALUE)?;
+                    teams
+                        .update_team(server, name, |team| team.prefix = prefix.clone())
+                        .await;
+                    TextComponent::translate("commands.team.option.prefix.success", [prefix])
+                }
+                TeamOption::Suffix => {
+                    let suffix = TextComponentArgConsumer::find_arg(args, ARG_VALUE)?;
+                    teams
+                        .update_team(server, name, |team| team.suffix = suffix.clone())
+                        .await;
+                    TextComponent::translate("commands.team.option.suffix.success", [suffix])
+                }
+            };
+            drop(teams);
+            sender.send_message(message).await;
+            Ok(())
+        })
+    }
+}
+
+fn option(
+    name: &str,
+    consumer: impl ArgumentConsumer + 'static,
+    option: TeamOption,
+) -> NonLeafNodeBuilder {
+    literal(name).then(argument(ARG_VALUE, consumer).execute(ModifyExecutor(option)))
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut color = literal("color");
+    for name in COLORS {
+        color = color.then(literal(name).execute(ModifyExecutor(TeamOption::Color(name))));
+    }
+    let mut collision_rule = literal("collisionRule");
+    for (name, rule) in COLLISION_RULES {
+        collision_rule = collision_rule
+            .then(literal(name).execute(ModifyExecutor(TeamOption::CollisionRule(name, rule))));
+    }
+    let mut name_tag_visibility = literal("nametagVisibility");
+    for (name, visibility) in NAME_TAG_VISIBILITIES {
+        name_tag_visibility = name_tag_visibility.then(literal(name).execute(ModifyExecutor(
+            TeamOption::NameTagVisibility(name, visibility),
+        )));
+    }
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("add").then(
+                argument(ARG_TEAM, SimpleArgConsumer)
+                    .then(argument(ARG_DISPLAY_NAME, TextComponentArgConsumer).execute(AddExecutor))
+                    .execute(AddExecutor),
+            ),
+        )
+        .then(literal("remove").then(argument(ARG_TEAM, SimpleArgConsumer).execute(RemoveExecutor)))
+        .then(literal("empty").then(argument(ARG_TEAM, SimpleArgConsumer).execute(EmptyExecutor)))
+        .then(
+            literal("join").then(
+                argument(ARG_TEAM, SimpleArgConsumer)
+                    .then(argument(ARG_MEMBERS, PlayersArgumentConsumer).execute(JoinExecutor))
+                    .execute(JoinExecutor),
+            ),
+        )
+        .then(
+            literal("leave")
+                .then(argument(ARG_MEMBERS, PlayersArgumentConsumer).execute(LeaveExecutor)),
+        )
+        .then(
+            literal("list")
+                .then(argument(ARG_TEAM, SimpleArgConsumer).execute(ListExecutor))
+                .execute(ListExecutor),
+        )
+        .then(
+            literal("modify").then(
+                argument(ARG_TEAM, SimpleArgConsumer)
+                    .then(option(
+                        "displayName",
+                        TextComponentArgConsumer,
+                        TeamOption::DisplayName,
+                    ))
+                    .then(color)
+                    .then(option(
+                        "friendlyFire",
+                        BoolArgConsumer,
+                        TeamOption::FriendlyFire,
+                    ))
+                    .then(option(
+                        "seeFriendlyInvisibles",
+                        BoolArgConsumer,
+                        TeamOption::SeeFriendlyInvisibles,
+                    ))
+                    .then(name_tag_visibility)
+                    .then(collision_rule)
+                    .then(option(
+                        "prefix",
+                        TextComponentArgConsumer,
+                        TeamOption::Prefix,
+                    ))
+                    .then(option(
+                        "suffix",
+                        TextComponentArgConsumer,
+                        TeamOption::Suffix,
+                    )),
+            ),
+        )
+}
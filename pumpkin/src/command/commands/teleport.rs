@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::text::TextComponent;
 
@@ -51,12 +53,69 @@ fn yaw_pitch_facing_position(
     (yaw_degrees as f32, pitch_degrees as f32)
 }
 
+/// The world the command runs in, the console uses the first world.
+async fn source_world(sender: &CommandSender, server: &crate::server::Server) -> Arc<World> {
+    match sender.world() {
+        Some(world) => world,
+        None => server.worlds.read().await.first().unwrap().clone(),
+    }
+}
+
+/// Returns the display name of a single target, or the number of targets.
+async fn describe_targets(targets: &[Arc<dyn EntityBase>]) -> TextComponent {
+    if let [target] = targets {
+        target.get_display_name().await
+    } else {
+        TextComponent::text(targets.len().to_string())
+    }
+}
+
+async fn send_location_feedback(
+    sender: &CommandSender,
+    targets: &[Arc<dyn EntityBase>],
+    pos: Vector3<f64>,
+) {
+    let key = if targets.len() == 1 {
+        "commands.teleport.success.location.single"
+    } else {
+        "commands.teleport.success.location.multiple"
+    };
+    let [x, y, z] = [pos.x, pos.y, pos.z].map(|c| TextComponent::text(format!("{c:.6}")));
+    sender
+        .send_message(TextComponent::translate(
+            key,
+            [describe_targets(targets).await, x, y, z],
+        ))
+        .await;
+}
+
+async fn send_entity_feedback(
+    sender: &CommandSender,
+    targets: &[Arc<dyn EntityBase>],
+    destination: &dyn EntityBase,
+) {
+    let key = if targets.len() == 1 {
+        "commands.teleport.success.entity.single"
+    } else {
+        "commands.teleport.success.entity.multiple"
+    };
+    sender
+        .send_message(TextComponent::translate(
+            key,
+            [
+                describe_targets(targets).await,
+                destination.get_display_name().await,
+            ],
+        ))
+        .await;
+}
+
 struct EntitiesToEntityExecutor;
 
 impl CommandExecutor for EntitiesToEntityExecutor {
     fn execute<'a>(
         &'a self,
-        _sender: &'a CommandSender,
+        sender: &'a CommandSender,
         _server: &'a crate::server::Server,
         args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
@@ -71,16 +130,17 @@ impl CommandExecutor for EntitiesToEntityExecutor {
                     [],
                 )));
             }
+            let world = destination.get_entity().world.clone();
             for target in targets {
                 let base_entity = target.get_entity();
                 let yaw = base_entity.yaw.load();
                 let pitch = base_entity.pitch.load();
-                let world = base_entity.world.clone();
                 target
                     .clone()
-                    .teleport(pos, yaw.into(), pitch.into(), world)
+                    .teleport(pos, yaw.into(), pitch.into(), world.clone())
                     .await;
             }
+            send_entity_feedback(sender, targets, destination.as_ref()).await;
 
             Ok(())
         })
@@ -108,14 +168,7 @@ impl CommandExecutor for EntitiesToPosFacingPosExecutor {
             }
             let facing_pos = Position3DArgumentConsumer::find_arg(args, ARG_FACING_LOCATION)?;
             let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_pos);
-            //todo
-            let world = match sender {
-                CommandSender::Rcon(_) | CommandSender::Console => {
-                    server.worlds.read().await.first().unwrap().clone()
-                }
-                CommandSender::Player(player) => player.world().clone(),
-                CommandSender::CommandBlock(_, w) => w.clone(),
-            };
+            let world = source_world(sender, server).await;
 
             for target in targets {
                 target
@@ -123,6 +176,7 @@ impl CommandExecutor for EntitiesToPosFacingPosExecutor {
                     .teleport(pos, Some(yaw), Some(pitch), world.clone())
                     .await;
             }
+            send_location_feedback(sender, targets, pos).await;
 
             Ok(())
         })
@@ -134,8 +188,8 @@ struct EntitiesToPosFacingEntityExecutor;
 impl CommandExecutor for EntitiesToPosFacingEntityExecutor {
     fn execute<'a>(
         &'a self,
-        _sender: &'a CommandSender,
-        _server: &'a crate::server::Server,
+        sender: &'a CommandSender,
+        server: &'a crate::server::Server,
         args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
@@ -151,18 +205,15 @@ impl CommandExecutor for EntitiesToPosFacingEntityExecutor {
             let facing_entity = EntityArgumentConsumer::find_arg(args, ARG_FACING_ENTITY)?;
             let (yaw, pitch) =
                 yaw_pitch_facing_position(&pos, &facing_entity.get_entity().pos.load());
+            let world = source_world(sender, server).await;
 
             for target in targets {
                 target
                     .clone()
-                    .teleport(
-                        pos,
-                        Some(yaw),
-                        Some(pitch),
-                        facing_entity.get_entity().world.clone(),
-                    )
+                    .teleport(pos, Some(yaw), Some(pitch), world.clone())
                     .await;
             }
+            send_location_feedback(sender, targets, pos).await;
 
             Ok(())
         })
@@ -174,7 +225,7 @@ struct EntitiesToPosWithRotationExecutor;
 impl CommandExecutor for EntitiesToPosWithRotationExecutor {
     fn execute<'a>(
         &'a self,
-        _sender: &'a CommandSender,
+        sender: &'a CommandSender,
         server: &'a crate::server::Server,
         args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
@@ -189,15 +240,15 @@ impl CommandExecutor for EntitiesToPosWithRotationExecutor {
                 )));
             }
             let (yaw, pitch) = RotationArgumentConsumer::find_arg(args, ARG_ROTATION)?;
+            let world = source_world(sender, server).await;
 
-            // todo command context
-            let world = server.worlds.read().await.first().unwrap().clone();
             for target in targets {
                 target
                     .clone()
                     .teleport(pos, Some(yaw), Some(pitch), world.clone())
                     .await;
             }
+            send_location_feedback(sender, targets, pos).await;
 
             Ok(())
         })
@@ -223,14 +274,8 @@ impl CommandExecutor for EntitiesToPosExecutor {
                     [],
                 )));
             }
-            // todo command context
-            let world = match sender {
-                CommandSender::Rcon(_) | CommandSender::Console => {
-                    server.worlds.read().await.first().unwrap().clone()
-                }
-                CommandSender::Player(player) => player.world().clone(),
-                CommandSender::CommandBlock(_, w) => w.clone(),
-            };
+            let world = source_world(sender, server).await;
+
             for target in targets {
                 let yaw = target.get_entity().yaw.load();
                 let pitch = target.get_entity().pitch.load();
@@ -239,6 +284,7 @@ impl CommandExecutor for EntitiesToPosExecutor {
                     .teleport(pos, Some(yaw), Some(pitch), world.clone())
                     .await;
             }
+            send_location_feedback(sender, targets, pos).await;
 
             Ok(())
         })
@@ -273,6 +319,8 @@ impl CommandExecutor for SelfToEntityExecutor {
                         .clone()
                         .teleport(pos, Some(yaw), Some(pitch), world)
                         .await;
+                    let target: Arc<dyn EntityBase> = player.clone();
+                    send_entity_feedback(sender, &[target], destination.as_ref()).await;
                 }
                 _ => {
                     sender
@@ -310,6 +358,8 @@ impl CommandExecutor for SelfToPosExecutor {
                         .clone()
                         .teleport(pos, Some(yaw), Some(pitch), player.world().clone())
                         .await;
+                    let target: Arc<dyn EntityBase> = player.clone();
+                    send_location_feedback(sender, &[target], pos).await;
                 }
                 _ => {
                     sender
@@ -1,13 +1,18 @@
+use std::sync::Arc;
+
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::text::color::{Color, NamedColor};
 
 use crate::command::CommandResult;
 use crate::command::args::{FindArg, time::TimeArgumentConsumer};
 use crate::command::tree::builder::{argument, literal};
-use crate::command::{CommandExecutor, CommandSender, ConsumedArgs, tree::CommandTree};
+use crate::command::{
+    CommandError, CommandExecutor, CommandSender, ConsumedArgs, tree::CommandTree,
+};
+use crate::world::World;
 
 const NAMES: [&str; 1] = ["time"];
-const DESCRIPTION: &str = "Query the world time.";
+const DESCRIPTION: &str = "Changes or queries the world's game time.";
 const ARG_TIME: &str = "time";
 
 #[derive(Clone, Copy)]
@@ -42,6 +47,23 @@ enum QueryMode {
     Day,
 }
 
+/// The world of the sender, or the first world for the console.
+async fn sender_world(
+    sender: &CommandSender,
+    server: &crate::server::Server,
+) -> Result<Arc<World>, CommandError> {
+    match sender.world() {
+        Some(world) => Ok(world),
+        None => server
+            .worlds
+            .read()
+            .await
+            .first()
+            .cloned()
+            .ok_or(CommandError::InvalidRequirement),
+    }
+}
+
 struct QueryExecutor(QueryMode);
 
 impl CommandExecutor for QueryExecutor {
@@ -53,11 +75,7 @@ impl CommandExecutor for QueryExecutor {
     ) -> CommandResult<'a> {
         Box::pin(async move {
             let mode = self.0;
-            // TODO: Maybe ask player for world, or get the current world
-            let worlds = server.worlds.read().await;
-            let world = worlds
-                .first()
-                .expect("There should always be at least one world");
+            let world = sender_world(sender, server).await?;
             let level_time = world.level_time.lock().await;
 
             let msg = match mode {
@@ -114,34 +132,30 @@ impl CommandExecutor for ChangeExecutor {
                 return Ok(());
             };
 
-            let mode = self.0;
-            // TODO: Maybe ask player for world, or get the current world
-            let worlds = server.worlds.read().await;
-            let world = worlds
-                .first()
-                .expect("There should always be at least one world");
-            let mut level_time = world.level_time.lock().await;
+            // Like in vanilla, the time changes in all worlds. This also works while the
+            // `advance_time` game rule freezes the time.
+            for world in server.worlds.read().await.iter() {
+                let mut level_time = world.level_time.lock().await;
+                match self.0 {
+                    Mode::Add => level_time.add_time(time_count.into()),
+                    Mode::Set(_) => level_time.set_time(time_count.into()),
+                }
+                level_time.send_time(world).await;
+            }
 
-            let msg = match mode {
+            let msg = match self.0 {
                 Mode::Add => {
-                    // add
-                    level_time.add_time(time_count.into());
-                    level_time.send_time(world).await;
-                    let curr_time = level_time.query_daytime();
+                    let world = sender_world(sender, server).await?;
+                    let curr_time = world.level_time.lock().await.query_daytime();
                     TextComponent::translate(
                         "commands.time.set",
                         [TextComponent::text(curr_time.to_string())],
                     )
                 }
-                Mode::Set(_) => {
-                    // set
-                    level_time.set_time(time_count.into());
-                    level_time.send_time(world).await;
-                    TextComponent::translate(
-                        "commands.time.set",
-                        [TextComponent::text(time_count.to_string())],
-                    )
-                }
+                Mode::Set(_) => TextComponent::translate(
+                    "commands.time.set",
+                    [TextComponent::text(time_count.to_string())],
+                ),
             };
 
             sender.send_message(msg).await;
@@ -8,6 +8,7 @@ use crate::command::{
         builder::{argument, literal},
     },
 };
+use crate::world::weather::has_weather;
 
 const NAMES: [&str; 1] = ["weather"];
 const DESCRIPTION: &str = "Changes the weather.";
@@ -32,37 +33,36 @@ impl CommandExecutor for Executor {
     ) -> CommandResult<'a> {
         Box::pin(async move {
             let duration = TimeArgumentConsumer::find_arg(args, ARG_DURATION).unwrap_or(6000);
-            let world = {
-                let guard = server.worlds.read().await;
+            // Like in vanilla, changing the weather from a dimension without weather changes
+            // it in the overworld
+            let world = match sender.world() {
+                Some(world) if has_weather(&world.dimension) => world,
+                _ => {
+                    let guard = server.worlds.read().await;
 
-                guard
-                    .first()
-                    .cloned()
-                    .ok_or(CommandError::InvalidRequirement)?
+                    guard
+                        .first()
+                        .cloned()
+                        .ok_or(CommandError::InvalidRequirement)?
+                }
             };
             let mut weather = world.weather.lock().await;
 
             match self.mode {
                 WeatherMode::Clear => {
-                    weather
-                        .set_weather_parameters(&world, duration, 0, false, false)
-                        .await;
+                    weather.set_weather_parameters(duration, 0, false, false);
                     sender
                         .send_message(TextComponent::translate("commands.weather.set.clear", []))
                         .await;
                 }
                 WeatherMode::Rain => {
-                    weather
-                        .set_weather_parameters(&world, 0, duration, true, false)
-                        .await;
+                    weather.set_weather_parameters(0, duration, true, false);
                     sender
                         .send_message(TextComponent::translate("commands.weather.set.rain", []))
                         .await;
                 }
                 WeatherMode::Thunder => {
-                    weather
-                        .set_weather_parameters(&world, 0, duration, true, true)
-                        .await;
+                    weather.set_weather_parameters(0, duration, true, true);
                     sender
                         .send_message(TextComponent::translate("commands.weather.set.thunder", []))
                         .await;
@@ -17,7 +17,8 @@ use crate::{
     },
     data::{
         LoadJSONConfiguration, SaveJSONConfiguration,
-        whitelist_data::{WHITELIST_CONFIG, WhitelistConfig},
+        op_data::OPERATOR_CONFIG,
+        whitelist_data::{WHITELIST_CONFIG, WhitelistConfig, not_whitelisted_message},
     },
     net::DisconnectReason,
     server::Server,
@@ -28,16 +29,18 @@ const DESCRIPTION: &str = "Manage server whitelists.";
 const ARG_TARGETS: &str = "targets";
 
 async fn kick_non_whitelisted_players(server: &Server) {
+    // Same lock order as `can_not_join`, so the two can't deadlock
+    let ops = OPERATOR_CONFIG.read().await;
     let whitelist = WHITELIST_CONFIG.read().await;
     if server.basic_config.enforce_whitelist && server.white_list.load(Ordering::Relaxed) {
         for player in server.get_all_players().await {
-            if whitelist.is_whitelisted(&player.gameprofile) {
+            if whitelist.allows(&ops, &player.gameprofile) {
                 continue;
             }
             player
                 .kick(
                     DisconnectReason::Kicked,
-                    TextComponent::translate("multiplayer.disconnect.not_whitelisted", &[]),
+                    not_whitelisted_message(&server.basic_config),
                 )
                 .await;
         }
@@ -63,7 +63,7 @@ impl CommandExecutor for GetExecutor {
                 .expect("There should always be at least one world");
             let border = world.worldborder.lock().await;
 
-            let diameter = border.new_diameter.round() as i32;
+            let diameter = border.diameter().round() as i32;
             sender
                 .send_message(TextComponent::translate(
                     "commands.worldborder.get",
@@ -105,7 +105,7 @@ impl CommandExecutor for SetExecutor {
                 return Ok(());
             };
 
-            if (distance - border.new_diameter).abs() < f64::EPSILON {
+            if (distance - border.diameter()).abs() < f64::EPSILON {
                 sender
                     .send_message(
                         TextComponent::translate(NOTHING_CHANGED_EXCEPTION, [])
@@ -170,7 +170,7 @@ impl CommandExecutor for SetTimeExecutor {
                 return Ok(());
             };
 
-            match distance.total_cmp(&border.new_diameter) {
+            match distance.total_cmp(&border.diameter()) {
                 std::cmp::Ordering::Equal => {
                     sender
                         .send_message(
@@ -254,7 +254,7 @@ impl CommandExecutor for AddExecutor {
                 return Ok(());
             }
 
-            let distance = border.new_diameter + distance;
+            let distance = border.diameter() + distance;
 
             let dist = format!("{distance:.1}");
             sender
@@ -311,9 +311,9 @@ impl CommandExecutor for AddTimeExecutor {
                 return Ok(());
             };
 
-            let distance = distance + border.new_diameter;
+            let distance = distance + border.diameter();
 
-            match distance.total_cmp(&border.new_diameter) {
+            match distance.total_cmp(&border.diameter()) {
                 std::cmp::Ordering::Equal => {
                     sender
                         .send_message(
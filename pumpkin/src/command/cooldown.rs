@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use pumpkin_config::commands::CommandsConfig;
+use pumpkin_util::PermissionLvl;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+use uuid::Uuid;
+
+use crate::entity::player::Player;
+use crate::server::Server;
+
+/// Keeps players from spamming commands which have a cooldown configured.
+pub struct CommandCooldowns {
+    /// Replaced when the configuration is reloaded.
+    settings: RwLock<CooldownSettings>,
+    /// When each player may use each command again.
+    ready_at: Mutex<HashMap<(Uuid, String), Instant>>,
+}
+
+struct CooldownSettings {
+    cooldowns: HashMap<String, Duration>,
+    bypass_level: PermissionLvl,
+}
+
+impl CooldownSettings {
+    fn new(config: &CommandsConfig) -> Self {
+        Self {
+            cooldowns: config
+                .cooldowns_ms
+                .iter()
+                .map(|(command, cooldown)| (command.clone(), Duration::from_millis(*cooldown)))
+                .collect(),
+            bypass_level: config.cooldown_bypass_level,
+        }
+    }
+}
+
+impl CommandCooldowns {
+    #[must_use]
+    pub fn new(config: &CommandsConfig) -> Self {
+        Self {
+            settings: RwLock::new(CooldownSettings::new(config)),
+            ready_at: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Applies the cooldowns of a reloaded configuration, running cooldowns are kept.
+    pub fn reload(&self, config: &CommandsConfig) {
+        *self.settings.write().unwrap() = CooldownSettings::new(config);
+    }
+
+    /// Records that `player` uses `command`, returns how long they still have to wait if it is
+    /// on cooldown.
+    pub fn try_use(
+        &self,
+        player: Uuid,
+        permission: PermissionLvl,
+        command: &str,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let settings = self.settings.read().unwrap();
+        let Some(cooldown) = settings.cooldowns.get(command) else {
+            return Ok(());
+        };
+        if permission >= settings.bypass_level {
+            return Ok(());
+        }
+
+        let mut ready_at = self.ready_at.lock().unwrap();
+        if let Some(ready) = ready_at.get(&(player, command.to_string()))
+            && *ready > now
+        {
+            return Err(*ready - now);
+        }
+        // Forget the cooldowns that are over
+        ready_at.retain(|_, ready| *ready > now);
+        ready_at.insert((player, command.to_string()), now + *cooldown);
+        Ok(())
+    }
+}
+
+/// Checks the cooldown of the command line `command` sent by `player`, tells them to wait and
+/// returns false if it may not be executed yet.
+pub async fn check_cooldown(player: &Player, server: &Server, command: &str) -> bool {
+    let Some(name) = command.split_whitespace().next() else {
+        return true;
+    };
+    let name = server
+        .command_dispatcher
+        .read()
+        .await
+        .resolve_alias(name)
+        .to_string();
+    let result = server.command_cooldowns.try_use(
+        player.gameprofile.id,
+        player.permission_lvl.load(),
+        &name,
+        Instant::now(),
+    );
+    let Err(remaining) = result else {
+        return true;
+    };
+    player
+        .send_system_message(
+            &TextComponent::text(format!(
+                "Please wait {:.1} seconds before using /{name} again",
+                remaining.as_secs_f32()
+            ))
+            .color_named(NamedColor::Red),
+        )
+        .await;
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::time::{Duration, Instant};
+
+    use pumpkin_config::commands::CommandsConfig;
+    use pumpkin_util::PermissionLvl;
+    use uuid::Uuid;
+
+    use super::CommandCooldowns;
+
+    #[test]
+    fn commands_on_cooldown_are_rejected() {
+        let cooldowns = CommandCooldowns::new(&CommandsConfig {
+            cooldowns_ms: HashMap::from([("fill".to_string(), 5000)]),
+            ..Default::default()
+        });
+        let player = Uuid::new_v4();
+        let op = Uuid::new_v4();
+        let now = Instant::now();
+
+        assert_eq!(
+            cooldowns.try_use(player, PermissionLvl::Zero, "fill", now),
+            Ok(())
+        );
+        let later = now + Duration::from_secs(1);
+        assert_eq!(
+            cooldowns.try_use(player, PermissionLvl::Zero, "fill", later),
+            Err(Duration::from_secs(4))
+        );
+        // Other commands and players are not affected
+        assert_eq!(
+            cooldowns.try_use(player, PermissionLvl::Zero, "help", later),
+            Ok(())
+        );
+        assert_eq!(
+            cooldowns.try_use(Uuid::new_v4(), PermissionLvl::Zero, "fill", later),
+            Ok(())
+        );
+        let over = now + Duration::from_secs(5);
+        assert_eq!(
+            cooldowns.try_use(player, PermissionLvl::Zero, "fill", over),
+            Ok(())
+        );
+
+        // Operators bypass the cooldown
+        assert_eq!(
+            cooldowns.try_use(op, PermissionLvl::Four, "fill", now),
+            Ok(())
+        );
+        assert_eq!(
+            cooldowns.try_use(op, PermissionLvl::Four, "fill", later),
+            Ok(())
+        );
+
+        // Reloading applies new cooldowns right away
+        cooldowns.reload(&CommandsConfig {
+            cooldowns_ms: HashMap::from([("help".to_string(), 1000)]),
+            ..Default::default()
+        });
+        assert_eq!(
+            cooldowns.try_use(player, PermissionLvl::Zero, "fill", later),
+            Ok(())
+        );
+        assert_eq!(
+            cooldowns.try_use(player, PermissionLvl::Zero, "help", over),
+            Ok(())
+        );
+        assert_eq!(
+            cooldowns.try_use(player, PermissionLvl::Zero, "help", over),
+            Err(Duration::from_secs(1))
+        );
+    }
+}
@@ -1,4 +1,6 @@
 use pumpkin_protocol::java::client::play::CommandSuggestion;
+use pumpkin_util::PermissionLvl;
+use pumpkin_util::permission::PermissionDefault;
 use pumpkin_util::text::TextComponent;
 
 use super::args::ConsumedArgs;
@@ -294,6 +296,15 @@ impl CommandDispatcher {
         }
     }
 
+    /// Returns the primary name of the command `key` refers to, `key` itself if it is no alias.
+    #[must_use]
+    pub fn resolve_alias<'a>(&'a self, key: &'a str) -> &'a str {
+        match self.commands.get(key) {
+            Some(Command::Alias(target)) => target,
+            _ => key,
+        }
+    }
+
     async fn try_is_fitting_path<'a>(
         src: &'a CommandSender,
         server: &'a Server,
@@ -413,6 +424,25 @@ impl CommandDispatcher {
             .insert(primary_name.clone(), Command::Tree(tree));
     }
 
+    /// The permission defaults replacing the ones of the commands in `levels`, by command name.
+    /// Aliases share the permission of their command, so they are affected as well.
+    #[must_use]
+    pub fn permission_overrides(
+        &self,
+        levels: &HashMap<String, PermissionLvl>,
+    ) -> HashMap<String, PermissionDefault> {
+        let mut overrides = HashMap::new();
+        for (command, lvl) in levels {
+            match self.permissions.get(command) {
+                Some(permission) => {
+                    overrides.insert(permission.clone(), PermissionDefault::Op(*lvl));
+                }
+                None => log::warn!("Can't set the permission level of unknown command {command}"),
+            }
+        }
+        overrides
+    }
+
     /// Remove a command from the dispatcher by its primary name.
     pub fn unregister(&mut self, name: &str) {
         let mut to_remove = Vec::new();
@@ -435,9 +465,16 @@ impl CommandDispatcher {
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
+
     use pumpkin_config::BasicConfiguration;
+    use pumpkin_util::PermissionLvl;
+    use pumpkin_util::permission::PermissionDefault;
 
     use crate::command::{commands::default_dispatcher, tree::CommandTree};
+
+    use super::CommandDispatcher;
+
     #[tokio::test]
     async fn test_dynamic_command() {
         let config = BasicConfiguration::default();
@@ -445,4 +482,24 @@ mod test {
         let tree = CommandTree::new(["test"], "test_desc");
         dispatcher.register(tree, "minecraft:test");
     }
+
+    #[test]
+    fn permission_levels_are_mapped_to_nodes() {
+        let mut dispatcher = CommandDispatcher::default();
+        dispatcher.register(
+            CommandTree::new(["teleport", "tp"], "test_desc"),
+            "minecraft:tp",
+        );
+        let overrides = dispatcher.permission_overrides(&HashMap::from([
+            ("tp".to_string(), PermissionLvl::Three),
+            ("unknown".to_string(), PermissionLvl::Zero),
+        ]));
+        assert_eq!(
+            overrides,
+            HashMap::from([(
+                "minecraft:tp".to_string(),
+                PermissionDefault::Op(PermissionLvl::Three)
+            )])
+        );
+    }
 }
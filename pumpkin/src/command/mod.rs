@@ -19,6 +19,7 @@ use pumpkin_world::block::entities::command_block::CommandBlockEntity;
 pub mod args;
 pub mod client_suggestions;
 pub mod commands;
+pub mod cooldown;
 pub mod dispatcher;
 pub mod tree;
 
@@ -116,10 +117,10 @@ impl CommandSender {
             Self::Player(p) => p.has_permission(node).await,
             Self::CommandBlock(..) => {
                 let perm_reg = crate::PERMISSION_REGISTRY.read().await;
-                let Some(p) = perm_reg.get_permission(node) else {
+                let Some(default) = perm_reg.get_default(node) else {
                     return false;
                 };
-                match p.default {
+                match *default {
                     PermissionDefault::Allow => true,
                     PermissionDefault::Deny => false,
                     PermissionDefault::Op(o) => o <= PermissionLvl::Two,
@@ -137,6 +138,19 @@ impl CommandSender {
         }
     }
 
+    /// Returns the yaw and pitch the sender is looking at, command blocks always look south.
+    #[must_use]
+    pub fn rotation(&self) -> Option<(f32, f32)> {
+        match self {
+            Self::Console | Self::Rcon(..) => None,
+            Self::Player(p) => Some((
+                p.living_entity.entity.yaw.load(),
+                p.living_entity.entity.pitch.load(),
+            )),
+            Self::CommandBlock(..) => Some((0.0, 0.0)),
+        }
+    }
+
     #[must_use]
     pub fn world(&self) -> Option<Arc<World>> {
         match self {
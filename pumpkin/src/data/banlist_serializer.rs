@@ -1,11 +1,37 @@
 use std::net::IpAddr;
 
+use pumpkin_util::text::TextComponent;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::net::GameProfile;
 
+const EXPIRATION_FORMAT: &[time::format_description::FormatItem<'static>] = time::macros::format_description!(
+    "[year]-[month]-[day] at [hour]:[minute]:[second] [offset_hour sign:mandatory]:[offset_minute]"
+);
+
+/// The message banned clients are disconnected with, `kind` is `banned` or `banned_ip`.
+fn disconnect_message(kind: &str, reason: &str, expires: Option<OffsetDateTime>) -> TextComponent {
+    let text = TextComponent::translate(
+        format!("multiplayer.disconnect.{kind}.reason"),
+        [TextComponent::text(reason.to_string())],
+    );
+    match expires {
+        Some(expires) => text.add_child(TextComponent::translate(
+            format!("multiplayer.disconnect.{kind}.expiration"),
+            [TextComponent::text(
+                expires.format(EXPIRATION_FORMAT).unwrap(),
+            )],
+        )),
+        None => text,
+    }
+}
+
+fn has_expired(expires: Option<OffsetDateTime>, now: OffsetDateTime) -> bool {
+    expires.is_some_and(|expires| expires < now)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BannedPlayerEntry {
     pub uuid: Uuid,
@@ -35,6 +61,17 @@ impl BannedPlayerEntry {
             reason,
         }
     }
+
+    /// Whether this is a temporary ban which ran out before `now`.
+    #[must_use]
+    pub fn has_expired(&self, now: OffsetDateTime) -> bool {
+        has_expired(self.expires, now)
+    }
+
+    #[must_use]
+    pub fn disconnect_message(&self) -> TextComponent {
+        disconnect_message("banned", &self.reason, self.expires)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -64,6 +101,17 @@ impl BannedIpEntry {
             reason,
         }
     }
+
+    /// Whether this is a temporary ban which ran out before `now`.
+    #[must_use]
+    pub fn has_expired(&self, now: OffsetDateTime) -> bool {
+        has_expired(self.expires, now)
+    }
+
+    #[must_use]
+    pub fn disconnect_message(&self) -> TextComponent {
+        disconnect_message("banned_ip", &self.reason, self.expires)
+    }
 }
 
 mod format {
@@ -127,3 +175,45 @@ mod format {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use time::{Duration, OffsetDateTime};
+
+    use super::{BannedIpEntry, BannedPlayerEntry};
+
+    #[test]
+    fn temporary_bans_expire() {
+        let players: Vec<BannedPlayerEntry> = serde_json::from_str(
+            r#"[
+                {
+                    "uuid": "069a79f4-44e9-4726-a5be-fca90e38aaf5",
+                    "name": "Notch",
+                    "created": "2025-01-01 12:00:00 +00:00",
+                    "source": "Server",
+                    "expires": "forever",
+                    "reason": "Banned by an operator."
+                }
+            ]"#,
+        )
+        .unwrap();
+        let ips: Vec<BannedIpEntry> = serde_json::from_str(
+            r#"[
+                {
+                    "ip": "192.168.0.1",
+                    "created": "2025-01-01 12:00:00 +00:00",
+                    "source": "Server",
+                    "expires": "2025-01-02 12:00:00 +00:00",
+                    "reason": "Spam"
+                }
+            ]"#,
+        )
+        .unwrap();
+        let expires = ips[0].expires.unwrap();
+
+        assert!(!players[0].has_expired(expires + Duration::days(1000)));
+        assert!(!ips[0].has_expired(expires));
+        assert!(ips[0].has_expired(expires + Duration::seconds(1)));
+        assert!(ips[0].has_expired(OffsetDateTime::now_utc()));
+    }
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use super::{LoadJSONConfiguration, SaveJSONConfiguration, banlist_serializer::BannedIpEntry};
+use crate::server::audit_log::{AuditAction, AuditLog};
 
 pub static BANNED_IP_LIST: LazyLock<tokio::sync::RwLock<BannedIpList>> =
     LazyLock::new(|| tokio::sync::RwLock::new(BannedIpList::load()));
@@ -21,14 +22,32 @@ impl BannedIpList {
         self.banned_ips.iter().find(|entry| entry.ip == *ip)
     }
 
+    /// Adds the ban `entry`, its source is recorded as the one who banned the IP.
+    pub fn ban(&mut self, entry: BannedIpEntry, audit_log: &AuditLog) {
+        audit_log.record(
+            AuditAction::BanIp,
+            &entry.source,
+            &entry.ip.to_string(),
+            Some(&entry.reason),
+        );
+        self.banned_ips.push(entry);
+    }
+
+    /// Lifts the ban of `ip` on behalf of `actor`, returns `false` if it wasn't banned.
+    pub fn pardon(&mut self, ip: &IpAddr, actor: &str, audit_log: &AuditLog) -> bool {
+        let Some(index) = self.banned_ips.iter().position(|entry| entry.ip == *ip) else {
+            return false;
+        };
+        self.banned_ips.remove(index);
+        audit_log.record(AuditAction::PardonIp, actor, &ip.to_string(), None);
+        true
+    }
+
     fn remove_invalid_entries(&mut self) {
         let original_len = self.banned_ips.len();
 
-        self.banned_ips.retain(|entry| {
-            entry
-                .expires
-                .is_none_or(|expires| expires >= OffsetDateTime::now_utc())
-        });
+        let now = OffsetDateTime::now_utc();
+        self.banned_ips.retain(|entry| !entry.has_expired(now));
 
         if original_len != self.banned_ips.len() {
             self.save();
@@ -6,6 +6,7 @@ use time::OffsetDateTime;
 use crate::net::GameProfile;
 
 use super::{LoadJSONConfiguration, SaveJSONConfiguration, banlist_serializer::BannedPlayerEntry};
+use crate::server::audit_log::{AuditAction, AuditLog};
 
 pub static BANNED_PLAYER_LIST: LazyLock<tokio::sync::RwLock<BannedPlayerList>> =
     LazyLock::new(|| tokio::sync::RwLock::new(BannedPlayerList::load()));
@@ -17,22 +18,46 @@ pub struct BannedPlayerList {
 }
 
 impl BannedPlayerList {
+    /// Bans follow the UUID, so they stick when the player changes their name.
     #[must_use]
     pub fn get_entry(&mut self, profile: &GameProfile) -> Option<&BannedPlayerEntry> {
         self.remove_invalid_entries();
         self.banned_players
             .iter()
-            .find(|entry| entry.name == profile.name && entry.uuid == profile.id)
+            .find(|entry| entry.uuid == profile.id)
+    }
+
+    /// Adds the ban `entry`, its source is recorded as the one who banned the player.
+    pub fn ban(&mut self, entry: BannedPlayerEntry, audit_log: &AuditLog) {
+        audit_log.record(
+            AuditAction::Ban,
+            &entry.source,
+            &entry.name,
+            Some(&entry.reason),
+        );
+        self.banned_players.push(entry);
+    }
+
+    /// Lifts the ban of the player called `name` on behalf of `actor`, returns `false` if they
+    /// weren't banned.
+    pub fn pardon(&mut self, name: &str, actor: &str, audit_log: &AuditLog) -> bool {
+        let Some(index) = self
+            .banned_players
+            .iter()
+            .position(|entry| entry.name.eq_ignore_ascii_case(name))
+        else {
+            return false;
+        };
+        let entry = self.banned_players.remove(index);
+        audit_log.record(AuditAction::Pardon, actor, &entry.name, None);
+        true
     }
 
     fn remove_invalid_entries(&mut self) {
         let original_len = self.banned_players.len();
 
-        self.banned_players.retain(|entry| {
-            entry
-                .expires
-                .is_none_or(|expires| expires >= OffsetDateTime::now_utc())
-        });
+        let now = OffsetDateTime::now_utc();
+        self.banned_players.retain(|entry| !entry.has_expired(now));
 
         if original_len != self.banned_players.len() {
             self.save();
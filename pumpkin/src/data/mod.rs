@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 const DATA_FOLDER: &str = "data/";
 
 pub mod op_data;
+pub mod permission_group_data;
 
 pub mod banlist_serializer;
 pub mod banned_ip_data;
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+use crate::server::audit_log::{AuditAction, AuditLog};
 
 pub static OPERATOR_CONFIG: LazyLock<tokio::sync::RwLock<OperatorConfig>> =
     LazyLock::new(|| tokio::sync::RwLock::new(OperatorConfig::load()));
@@ -20,6 +21,21 @@ impl OperatorConfig {
     pub fn get_entry(&self, uuid: &Uuid) -> Option<&op::Op> {
         self.ops.iter().find(|entry| entry.uuid.eq(uuid))
     }
+
+    /// Makes `op` an operator or changes the level of an existing one on behalf of `actor`.
+    pub fn op(&mut self, op: op::Op, actor: &str, audit_log: &AuditLog) {
+        audit_log.record(AuditAction::Op, actor, &op.name, None);
+        match self.ops.iter_mut().find(|entry| entry.uuid == op.uuid) {
+            Some(entry) => entry.level = op.level,
+            None => self.ops.push(op),
+        }
+    }
+
+    /// Takes the operator status of the player `uuid` called `name` on behalf of `actor`.
+    pub fn deop(&mut self, uuid: &Uuid, name: &str, actor: &str, audit_log: &AuditLog) {
+        audit_log.record(AuditAction::Deop, actor, name, None);
+        self.ops.retain(|entry| entry.uuid != *uuid);
+    }
 }
 
 impl LoadJSONConfiguration for OperatorConfig {
@@ -0,0 +1,25 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+
+/// The permission groups players are assigned to, the groups themselves are defined in the
+/// advanced configuration.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct PermissionGroupConfig {
+    pub player_groups: HashMap<Uuid, Vec<String>>,
+}
+
+impl LoadJSONConfiguration for PermissionGroupConfig {
+    fn get_path() -> &'static Path {
+        Path::new("permission_groups.json")
+    }
+    fn validate(&self) {
+        // Unknown groups are reported when they are assigned
+    }
+}
+
+impl SaveJSONConfiguration for PermissionGroupConfig {}
@@ -6,6 +6,7 @@ use crossbeam::atomic::AtomicCell;
 use pumpkin_inventory::screen_handler::ScreenHandler;
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_world::data::player_data::{PlayerDataError, PlayerDataStorage};
+use pumpkin_world::data::player_stats::{PlayerStats, PlayerStatsStorage};
 use std::sync::Arc;
 use std::{
     path::PathBuf,
@@ -17,15 +18,22 @@ use std::{
 /// convenience methods for player handling.
 pub struct ServerPlayerData {
     storage: Arc<PlayerDataStorage>,
+    stats_storage: PlayerStatsStorage,
     save_interval: Duration,
     last_save: AtomicCell<Instant>,
 }
 
 impl ServerPlayerData {
     /// Creates a new `ServerPlayerData` with specified configuration.
-    pub fn new(data_path: impl Into<PathBuf>, save_interval: Duration, enabled: bool) -> Self {
+    pub fn new(
+        data_path: impl Into<PathBuf>,
+        stats_path: impl Into<PathBuf>,
+        save_interval: Duration,
+        enabled: bool,
+    ) -> Self {
         Self {
             storage: Arc::new(PlayerDataStorage::new(data_path, enabled)),
+            stats_storage: PlayerStatsStorage::new(stats_path, enabled),
             save_interval,
             last_save: AtomicCell::new(Instant::now()),
         }
@@ -56,8 +64,23 @@ impl ServerPlayerData {
 
         // Save to disk
         self.storage.save_player_data(&player.gameprofile.id, nbt)?;
+        self.save_stats(player).await
+    }
 
-        Ok(())
+    /// Loads the stats of a player, starting with empty stats if they can't be read.
+    pub fn load_stats(&self, uuid: &uuid::Uuid) -> PlayerStats {
+        self.stats_storage
+            .load_player_stats(uuid)
+            .unwrap_or_else(|e| {
+                log::error!("Error loading player stats for {uuid}: {e}");
+                PlayerStats::default()
+            })
+    }
+
+    async fn save_stats(&self, player: &Player) -> Result<(), PlayerDataError> {
+        let stats = player.stats.lock().await;
+        self.stats_storage
+            .save_player_stats(&player.gameprofile.id, &stats)
     }
 
     /// Performs periodic maintenance tasks.
@@ -86,6 +109,12 @@ impl ServerPlayerData {
                             player.gameprofile.id,
                         );
                     }
+                    if let Err(e) = self.save_stats(player).await {
+                        log::error!(
+                            "Failed to save player stats for {}: {e}",
+                            player.gameprofile.id,
+                        );
+                    }
                 }
             }
 
@@ -171,7 +200,8 @@ impl ServerPlayerData {
         let uuid = &player.gameprofile.id;
         let mut nbt = NbtCompound::new();
         player.write_nbt(&mut nbt).await;
-        self.storage.save_player_data(uuid, nbt)
+        self.storage.save_player_data(uuid, nbt)?;
+        self.save_stats(player).await
     }
 }
 
@@ -278,7 +308,12 @@ mod test {
         let path = temp_dir.path().to_path_buf();
         let save_interval = Duration::from_secs(300);
 
-        let player_data = ServerPlayerData::new(path, save_interval, true);
+        let player_data = ServerPlayerData::new(
+            path.join("playerdata"),
+            path.join("stats"),
+            save_interval,
+            true,
+        );
 
         assert_eq!(player_data.save_interval, save_interval);
         assert!(
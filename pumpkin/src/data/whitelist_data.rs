@@ -1,11 +1,12 @@
 use std::{path::Path, sync::LazyLock};
 
-use pumpkin_config::whitelist::WhitelistEntry;
+use pumpkin_config::{BasicConfiguration, whitelist::WhitelistEntry};
+use pumpkin_util::text::TextComponent;
 use serde::{Deserialize, Serialize};
 
 use crate::net::GameProfile;
 
-use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+use super::{LoadJSONConfiguration, SaveJSONConfiguration, op_data::OperatorConfig};
 
 pub static WHITELIST_CONFIG: LazyLock<tokio::sync::RwLock<WhitelistConfig>> =
     LazyLock::new(|| tokio::sync::RwLock::new(WhitelistConfig::load()));
@@ -17,11 +18,26 @@ pub struct WhitelistConfig {
 }
 
 impl WhitelistConfig {
+    /// Entries follow the UUID, so they stick when the player changes their name.
     #[must_use]
     pub fn is_whitelisted(&self, profile: &GameProfile) -> bool {
-        self.whitelist
-            .iter()
-            .any(|entry| entry.uuid == profile.id && entry.name == profile.name)
+        self.whitelist.iter().any(|entry| entry.uuid == profile.id)
+    }
+
+    /// Whether the player may join while the whitelist is on, which operators always may.
+    #[must_use]
+    pub fn allows(&self, ops: &OperatorConfig, profile: &GameProfile) -> bool {
+        ops.get_entry(&profile.id).is_some() || self.is_whitelisted(profile)
+    }
+}
+
+/// The message players who aren't on the whitelist are disconnected with.
+#[must_use]
+pub fn not_whitelisted_message(config: &BasicConfiguration) -> TextComponent {
+    if config.whitelist_message.is_empty() {
+        TextComponent::translate("multiplayer.disconnect.not_whitelisted", [])
+    } else {
+        TextComponent::text(config.whitelist_message.clone())
     }
 }
 
@@ -35,3 +51,42 @@ impl LoadJSONConfiguration for WhitelistConfig {
 }
 
 impl SaveJSONConfiguration for WhitelistConfig {}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::{op::Op, whitelist::WhitelistEntry};
+    use pumpkin_util::PermissionLvl;
+    use uuid::Uuid;
+
+    use super::WhitelistConfig;
+    use crate::{data::op_data::OperatorConfig, net::GameProfile};
+
+    fn profile(id: u128, name: &str) -> GameProfile {
+        GameProfile {
+            id: Uuid::from_u128(id),
+            name: name.to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        }
+    }
+
+    #[test]
+    fn operators_and_whitelisted_players_may_join() {
+        let whitelist = WhitelistConfig {
+            whitelist: vec![WhitelistEntry::new(Uuid::from_u128(1), "Alex".to_string())],
+        };
+        let ops = OperatorConfig {
+            ops: vec![Op::new(
+                Uuid::from_u128(2),
+                "Steve".to_string(),
+                PermissionLvl::Four,
+                false,
+            )],
+        };
+
+        assert!(whitelist.allows(&ops, &profile(1, "Alex")));
+        assert!(whitelist.allows(&ops, &profile(1, "Renamed")));
+        assert!(whitelist.allows(&ops, &profile(2, "Steve")));
+        assert!(!whitelist.allows(&ops, &profile(3, "Alex")));
+    }
+}
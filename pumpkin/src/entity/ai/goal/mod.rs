@@ -10,6 +10,7 @@ mod melee_attack;
 pub mod move_to_target_pos;
 pub mod step_and_destroy_block;
 mod track_target;
+pub mod wander_around;
 pub mod zombie_attack;
 
 #[must_use]
@@ -0,0 +1,163 @@
+use super::{Controls, Goal, to_goal_ticks};
+use crate::entity::ai::path::NavigatorGoal;
+use crate::entity::{ai::goal::GoalFuture, mob::Mob};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::world::BlockAccessor;
+use rand::Rng;
+
+const DEFAULT_CHANCE: i32 = 120;
+/// How far away from the mob a wander target may be.
+const HORIZONTAL_RANGE: i32 = 10;
+/// How many random positions are tried before the mob keeps standing around.
+const MAX_TRIES: i32 = 10;
+
+pub struct WanderAroundGoal {
+    goal_control: Controls,
+    speed: f64,
+    chance: i32,
+    target: Option<Vector3<f64>>,
+}
+
+impl WanderAroundGoal {
+    #[must_use]
+    pub fn new(speed: f64, chance: i32) -> Box<Self> {
+        Box::new(Self {
+            goal_control: Controls::MOVE,
+            speed,
+            chance,
+            target: None,
+        })
+    }
+
+    #[must_use]
+    pub fn with_default(speed: f64) -> Box<Self> {
+        Self::new(speed, DEFAULT_CHANCE)
+    }
+
+    async fn find_target(mob: &dyn Mob) -> Option<Vector3<f64>> {
+        let entity = mob.get_entity();
+        let block_pos = entity.block_pos.load();
+        let height = entity.entity_dimension.load().height.ceil() as i32;
+        for _ in 0..MAX_TRIES {
+            let (x, z) = {
+                let mut random = mob.get_random();
+                (
+                    random.random_range(-HORIZONTAL_RANGE..=HORIZONTAL_RANGE),
+                    random.random_range(-HORIZONTAL_RANGE..=HORIZONTAL_RANGE),
+                )
+            };
+            // The navigator can't climb yet, so stay on the current level
+            let target = block_pos.offset(Vector3::new(x, 0, z));
+            if target != block_pos && is_walkable(entity.world.as_ref(), &target, height).await {
+                return Some(target.to_f64());
+            }
+        }
+        None
+    }
+}
+
+/// Whether a mob of `height` blocks can stand at `position`, it needs solid ground below and
+/// free space to fit in.
+pub async fn is_walkable(
+    block_accessor: &dyn BlockAccessor,
+    position: &BlockPos,
+    height: i32,
+) -> bool {
+    if !block_accessor
+        .get_block_state(&position.down())
+        .await
+        .is_solid()
+    {
+        return false;
+    }
+    for y in 0..height.max(1) {
+        let state = block_accessor
+            .get_block_state(&position.offset(Vector3::new(0, y, 0)))
+            .await;
+        if !state.get_block_collision_shapes().is_empty() || state.is_liquid() {
+            return false;
+        }
+    }
+    true
+}
+
+impl Goal for WanderAroundGoal {
+    fn can_start<'a>(&'a mut self, mob: &'a dyn Mob) -> GoalFuture<'a, bool> {
+        Box::pin(async {
+            if mob
+                .get_random()
+                .random_range(0..to_goal_ticks(self.chance).max(1))
+                != 0
+            {
+                return false;
+            }
+            self.target = Self::find_target(mob).await;
+            self.target.is_some()
+        })
+    }
+
+    fn should_continue<'a>(&'a self, mob: &'a dyn Mob) -> GoalFuture<'a, bool> {
+        Box::pin(async { !mob.get_mob_entity().navigator.lock().await.is_idle() })
+    }
+
+    fn start<'a>(&'a mut self, mob: &'a dyn Mob) -> GoalFuture<'a, ()> {
+        Box::pin(async {
+            let Some(destination) = self.target.take() else {
+                return;
+            };
+            mob.get_mob_entity()
+                .navigator
+                .lock()
+                .await
+                .set_progress(NavigatorGoal {
+                    current_progress: mob.get_entity().pos.load(),
+                    destination,
+                    speed: self.speed,
+                });
+        })
+    }
+
+    fn stop<'a>(&'a mut self, mob: &'a dyn Mob) -> GoalFuture<'a, ()> {
+        Box::pin(async {
+            mob.get_mob_entity().navigator.lock().await.cancel();
+        })
+    }
+
+    fn controls(&self) -> Controls {
+        self.goal_control
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, dimension::Dimension};
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_world::generation::proto_chunk::ProtoChunk;
+
+    use super::is_walkable;
+
+    #[test]
+    fn mobs_only_wander_onto_free_ground() {
+        let mut chunk = ProtoChunk::new(0, 0, &Dimension::OVERWORLD, Block::STONE.default_state, 0);
+        for x in 0..16 {
+            for z in 0..16 {
+                chunk.set_block_state(x, 63, z, Block::GRASS_BLOCK.default_state);
+            }
+        }
+        chunk.set_block_state(4, 64, 4, Block::STONE.default_state);
+        chunk.set_block_state(6, 65, 6, Block::STONE.default_state);
+
+        let walkable = |x, y, z, height| {
+            futures::executor::block_on(is_walkable(&chunk, &BlockPos::new(x, y, z), height))
+        };
+        assert!(walkable(2, 64, 2, 2));
+        // Inside a wall
+        assert!(!walkable(4, 64, 4, 2));
+        // In the air
+        assert!(!walkable(2, 66, 2, 2));
+        // Too low for a tall mob, fine for a small one
+        assert!(!walkable(6, 64, 6, 2));
+        assert!(walkable(6, 64, 6, 1));
+    }
+}
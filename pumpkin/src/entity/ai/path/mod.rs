@@ -24,8 +24,11 @@ impl Navigator {
 
     pub async fn tick(&mut self, entity: &LivingEntity) {
         if let Some(goal) = &mut self.current_goal {
-            // First, let's check if we have reached the destination
-            if goal.current_progress == goal.destination {
+            // First, let's check if we have reached the destination, we move in steps of
+            // `speed` so we may never land on it exactly
+            let dx = goal.destination.x - goal.current_progress.x;
+            let dz = goal.destination.z - goal.current_progress.z;
+            if dx * dx + dz * dz <= goal.speed * goal.speed {
                 // If yes, we are done here.
                 self.current_goal = None;
                 return;
@@ -67,6 +70,8 @@ impl Navigator {
             // This is important. Firstly, this saves us many packets when we don't actually move. Secondly, this prevents division using zero
             // when normalize
             if best_move.x == 0.0 && best_move.z == 0.0 {
+                // We are stuck, so give up instead of waiting here forever
+                self.current_goal = None;
                 return;
             }
 
@@ -81,8 +86,7 @@ impl Navigator {
 
     #[must_use]
     pub fn is_idle(&self) -> bool {
-        // TODO: implement
-        false
+        self.current_goal.is_none()
     }
 }
 
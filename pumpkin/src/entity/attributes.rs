@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet};
+
+use pumpkin_data::attributes::Attributes;
+use pumpkin_data::data_component_impl::{Modifier, Operation};
+
+/// Changes the value of an attribute. Modifiers are identified by their `id`, adding one with an
+/// id that is already applied replaces it instead of stacking.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeModifier {
+    pub id: String,
+    pub amount: f64,
+    pub operation: Operation,
+}
+
+impl From<&Modifier> for AttributeModifier {
+    fn from(modifier: &Modifier) -> Self {
+        Self {
+            id: modifier.id.to_string(),
+            amount: modifier.amount,
+            operation: modifier.operation,
+        }
+    }
+}
+
+/// The base value of an attribute and the modifiers applied to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeInstance {
+    pub base_value: f64,
+    pub modifiers: Vec<AttributeModifier>,
+}
+
+impl AttributeInstance {
+    #[must_use]
+    pub const fn new(base_value: f64) -> Self {
+        Self {
+            base_value,
+            modifiers: Vec::new(),
+        }
+    }
+
+    /// The base value with all modifiers applied. `EntityAttributeInstance.computeValue()`
+    #[must_use]
+    pub fn value(&self) -> f64 {
+        let with_operation = |operation| {
+            self.modifiers
+                .iter()
+                .filter(move |modifier| modifier.operation == operation)
+        };
+        let base = self.base_value
+            + with_operation(Operation::AddValue)
+                .map(|modifier| modifier.amount)
+                .sum::<f64>();
+        let mut value = base;
+        for modifier in with_operation(Operation::AddMultipliedBase) {
+            value += base * modifier.amount;
+        }
+        for modifier in with_operation(Operation::AddMultipliedTotal) {
+            value *= 1.0 + modifier.amount;
+        }
+        value
+    }
+}
+
+/// The attributes of a living entity. Only attributes which were changed are stored, all others
+/// have their default value.
+#[derive(Default)]
+pub struct AttributeContainer {
+    instances: HashMap<&'static Attributes, AttributeInstance>,
+    /// The attributes which changed since they were last sent to clients.
+    dirty: HashSet<&'static Attributes>,
+}
+
+impl AttributeContainer {
+    /// A container with the base values that differ from the attribute defaults, like the ones of
+    /// a player.
+    #[must_use]
+    pub fn with_base_values(base_values: &[(&'static Attributes, f64)]) -> Self {
+        Self {
+            instances: base_values
+                .iter()
+                .map(|(attribute, base_value)| (*attribute, AttributeInstance::new(*base_value)))
+                .collect(),
+            dirty: HashSet::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn base_value(&self, attribute: &'static Attributes) -> f64 {
+        self.instances
+            .get(attribute)
+            .map_or(attribute.default_value, |instance| instance.base_value)
+    }
+
+    pub fn set_base_value(&mut self, attribute: &'static Attributes, base_value: f64) {
+        let instance = self.instance_mut(attribute);
+        if instance.base_value != base_value {
+            instance.base_value = base_value;
+            self.dirty.insert(attribute);
+        }
+    }
+
+    /// The value of `attribute` with all modifiers applied.
+    #[must_use]
+    pub fn value(&self, attribute: &'static Attributes) -> f64 {
+        self.instances
+            .get(attribute)
+            .map_or(attribute.default_value, AttributeInstance::value)
+    }
+
+    /// The modifier with the given id, if it is applied to `attribute`.
+    #[must_use]
+    pub fn modifier(&self, attribute: &'static Attributes, id: &str) -> Option<&AttributeModifier> {
+        self.instances
+            .get(attribute)?
+            .modifiers
+            .iter()
+            .find(|modifier| modifier.id == id)
+    }
+
+    /// Applies `modifier`, replacing a modifier with the same id.
+    pub fn add_modifier(&mut self, attribute: &'static Attributes, modifier: AttributeModifier) {
+        let instance = self.instance_mut(attribute);
+        match instance
+            .modifiers
+            .iter_mut()
+            .find(|applied| applied.id == modifier.id)
+        {
+            Some(applied) if *applied == modifier => return,
+            Some(applied) => *applied = modifier,
+            None => instance.modifiers.push(modifier),
+        }
+        self.dirty.insert(attribute);
+    }
+
+    /// Removes the modifier with the given id, returns it if it was applied.
+    pub fn remove_modifier(
+        &mut self,
+        attribute: &'static Attributes,
+        id: &str,
+    ) -> Option<AttributeModifier> {
+        let instance = self.instances.get_mut(attribute)?;
+        let index = instance
+            .modifiers
+            .iter()
+            .position(|modifier| modifier.id == id)?;
+        self.dirty.insert(attribute);
+        Some(instance.modifiers.remove(index))
+    }
+
+    /// The attributes which changed since the last call, to send them to clients.
+    pub fn take_dirty(&mut self) -> Vec<(&'static Attributes, AttributeInstance)> {
+        self.dirty
+            .drain()
+            .map(|attribute| (attribute, self.instances[attribute].clone()))
+            .collect()
+    }
+
+    fn instance_mut(&mut self, attribute: &'static Attributes) -> &mut AttributeInstance {
+        self.instances
+            .entry(attribute)
+            .or_insert_with(|| AttributeInstance::new(attribute.default_value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::attributes::Attributes;
+    use pumpkin_data::data_component_impl::Operation;
+
+    use super::{AttributeContainer, AttributeModifier};
+
+    fn modifier(id: &str, amount: f64, operation: Operation) -> AttributeModifier {
+        AttributeModifier {
+            id: id.to_string(),
+            amount,
+            operation,
+        }
+    }
+
+    #[test]
+    fn modifiers_with_the_same_id_do_not_stack() {
+        let mut attributes = AttributeContainer::default();
+        assert_eq!(attributes.value(&Attributes::ARMOR), 0.0);
+        assert!(attributes.take_dirty().is_empty());
+
+        let helmet = modifier("minecraft:armor.helmet", 3.0, Operation::AddValue);
+        attributes.add_modifier(&Attributes::ARMOR, helmet.clone());
+        attributes.add_modifier(&Attributes::ARMOR, helmet.clone());
+        assert_eq!(attributes.value(&Attributes::ARMOR), 3.0);
+        let dirty = attributes.take_dirty();
+        assert_eq!(dirty.len(), 1);
+        assert_eq!(dirty[0].1.modifiers, [helmet]);
+
+        // Re-equipping the same item doesn't change anything
+        attributes.add_modifier(
+            &Attributes::ARMOR,
+            modifier("minecraft:armor.helmet", 3.0, Operation::AddValue),
+        );
+        assert!(attributes.take_dirty().is_empty());
+
+        attributes.add_modifier(
+            &Attributes::ARMOR,
+            modifier("minecraft:armor.chestplate", 8.0, Operation::AddValue),
+        );
+        assert_eq!(attributes.value(&Attributes::ARMOR), 11.0);
+        assert!(
+            attributes
+                .remove_modifier(&Attributes::ARMOR, "minecraft:armor.helmet")
+                .is_some()
+        );
+        assert!(
+            attributes
+                .remove_modifier(&Attributes::ARMOR, "minecraft:armor.helmet")
+                .is_none()
+        );
+        assert_eq!(attributes.value(&Attributes::ARMOR), 8.0);
+    }
+
+    #[test]
+    fn modifier_operations_are_applied_in_order() {
+        let mut attributes = AttributeContainer::default();
+        attributes.set_base_value(&Attributes::MOVEMENT_SPEED, 0.1);
+        attributes.add_modifier(
+            &Attributes::MOVEMENT_SPEED,
+            modifier("a", 0.1, Operation::AddValue),
+        );
+        attributes.add_modifier(
+            &Attributes::MOVEMENT_SPEED,
+            modifier("b", 0.5, Operation::AddMultipliedBase),
+        );
+        attributes.add_modifier(
+            &Attributes::MOVEMENT_SPEED,
+            modifier("c", 1.0, Operation::AddMultipliedTotal),
+        );
+        // (0.1 + 0.1) * 1.5 * 2
+        let value = attributes.value(&Attributes::MOVEMENT_SPEED);
+        assert!((value - 0.6).abs() < 1e-9);
+        assert_eq!(attributes.base_value(&Attributes::MOVEMENT_SPEED), 0.1);
+    }
+}
@@ -0,0 +1,284 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering::Relaxed;
+
+use crossbeam::atomic::AtomicCell;
+use pumpkin_data::{
+    damage::DamageType, entity::EntityType, meta_data_type::MetaDataType, tracked_data::TrackedData,
+};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::{codec::var_int::VarInt, java::client::play::Metadata};
+use pumpkin_util::math::vector3::Vector3;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::entity::{
+    Entity, EntityBase, EntityBaseFuture, NBTStorage, NbtFuture, living::LivingEntity,
+};
+use crate::server::Server;
+
+/// How close an end crystal has to be to heal the dragon.
+const CRYSTAL_RANGE: f64 = 32.0;
+/// How often the dragon heals a point of health while an end crystal is healing it.
+const HEAL_INTERVAL: i32 = 10;
+/// How much damage the dragon takes when the end crystal healing it gets destroyed.
+const CRYSTAL_DESTROYED_DAMAGE: f32 = 10.0;
+
+/// What the dragon is currently doing, the ids are the ones the client expects.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DragonPhase {
+    HoldingPattern = 0,
+    StrafePlayer = 1,
+    LandingApproach = 2,
+    Landing = 3,
+    Takeoff = 4,
+    SittingFlaming = 5,
+    SittingScanning = 6,
+    SittingAttacking = 7,
+    ChargingPlayer = 8,
+    Dying = 9,
+    Hover = 10,
+}
+
+/// The end crystal which is currently healing the dragon.
+#[derive(Default)]
+pub struct CrystalLink {
+    crystal: Option<Uuid>,
+}
+
+impl CrystalLink {
+    #[must_use]
+    pub const fn crystal(&self) -> Option<Uuid> {
+        self.crystal
+    }
+
+    pub fn link(&mut self, crystal: Option<Uuid>) {
+        self.crystal = crystal;
+    }
+
+    /// How much the dragon heals at the given `age`.
+    #[must_use]
+    pub fn healing(&self, age: i32, health: f32, max_health: f32) -> f32 {
+        if self.crystal.is_none() || age % HEAL_INTERVAL != 0 || health >= max_health {
+            return 0.0;
+        }
+        (max_health - health).min(1.0)
+    }
+
+    /// Unlinks `crystal` if it was healing the dragon, returns the damage the dragon takes from
+    /// its destruction.
+    pub fn on_crystal_destroyed(&mut self, crystal: Uuid) -> Option<f32> {
+        if self.crystal != Some(crystal) {
+            return None;
+        }
+        self.crystal = None;
+        Some(CRYSTAL_DESTROYED_DAMAGE)
+    }
+}
+
+pub struct EnderDragonEntity {
+    pub living_entity: LivingEntity,
+    phase: AtomicCell<DragonPhase>,
+    crystal_link: Mutex<CrystalLink>,
+}
+
+impl EnderDragonEntity {
+    pub async fn make(entity: Entity) -> Arc<Self> {
+        let dragon = Arc::new(Self {
+            living_entity: LivingEntity::new(entity),
+            phase: AtomicCell::new(DragonPhase::HoldingPattern),
+            crystal_link: Mutex::new(CrystalLink::default()),
+        });
+        let world = &dragon.living_entity.entity.world;
+        if let Some(fight) = &world.dragon_fight {
+            fight.set_dragon(dragon.clone()).await;
+        }
+        dragon
+    }
+
+    #[must_use]
+    pub fn phase(&self) -> DragonPhase {
+        self.phase.load()
+    }
+
+    pub async fn set_phase(&self, phase: DragonPhase) {
+        if self.phase.swap(phase) == phase {
+            return;
+        }
+        self.living_entity
+            .entity
+            .send_meta_data(&[Metadata::new(
+                TrackedData::DATA_PHASE_TYPE,
+                MetaDataType::Integer,
+                VarInt(phase as i32),
+            )])
+            .await;
+    }
+
+    /// Links the nearest end crystal to the dragon if it has none, or the one it had is gone.
+    async fn check_crystals(&self) {
+        let entity = &self.living_entity.entity;
+        let entities = entity.world.entities.read().await;
+        let mut link = self.crystal_link.lock().await;
+        if let Some(crystal) = link.crystal()
+            && entities.contains_key(&crystal)
+        {
+            return;
+        }
+        let pos = entity.pos.load();
+        let nearest = entities
+            .values()
+            .map(|other| other.get_entity())
+            .filter(|other| other.entity_type == &EntityType::END_CRYSTAL)
+            .map(|crystal| {
+                let distance = crystal.pos.load().squared_distance_to_vec(pos);
+                (crystal.entity_uuid, distance)
+            })
+            .filter(|(_, distance)| *distance < CRYSTAL_RANGE * CRYSTAL_RANGE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(uuid, _)| uuid);
+        link.link(nearest);
+    }
+
+    /// Called when an end crystal gets destroyed, the dragon gets hurt if it was healing it.
+    pub async fn on_crystal_destroyed(
+        &self,
+        crystal: &dyn EntityBase,
+        cause: Option<&dyn EntityBase>,
+    ) {
+        let crystal_entity = crystal.get_entity();
+        let damage = self
+            .crystal_link
+            .lock()
+            .await
+            .on_crystal_destroyed(crystal_entity.entity_uuid);
+        let Some(damage) = damage else {
+            return;
+        };
+        self.damage_with_context(
+            self,
+            damage,
+            DamageType::EXPLOSION,
+            Some(crystal_entity.pos.load()),
+            Some(crystal),
+            cause,
+        )
+        .await;
+    }
+}
+
+impl NBTStorage for EnderDragonEntity {
+    fn write_nbt<'a>(&'a self, nbt: &'a mut NbtCompound) -> NbtFuture<'a, ()> {
+        Box::pin(async move {
+            self.living_entity.write_nbt(nbt).await;
+        })
+    }
+
+    fn read_nbt_non_mut<'a>(&'a self, nbt: &'a NbtCompound) -> NbtFuture<'a, ()> {
+        Box::pin(async move {
+            self.living_entity.read_nbt_non_mut(nbt).await;
+        })
+    }
+}
+
+impl EntityBase for EnderDragonEntity {
+    fn tick<'a>(
+        &'a self,
+        caller: Arc<dyn EntityBase>,
+        server: &'a Server,
+    ) -> EntityBaseFuture<'a, ()> {
+        Box::pin(async move {
+            self.living_entity.tick(caller, server).await;
+
+            let entity = &self.living_entity.entity;
+            let max_health = entity.entity_type.max_health.unwrap_or(200.0);
+            if self.living_entity.dead.load(Relaxed) {
+                self.set_phase(DragonPhase::Dying).await;
+                if let Some(fight) = &entity.world.dragon_fight {
+                    fight
+                        .on_dragon_killed(&entity.world, entity.entity_uuid)
+                        .await;
+                }
+                return;
+            }
+
+            self.check_crystals().await;
+            let health = self.living_entity.health.load();
+            let healing = self.crystal_link.lock().await.healing(
+                entity.age.load(Relaxed),
+                health,
+                max_health,
+            );
+            if healing > 0.0 {
+                self.living_entity.heal(healing).await;
+            }
+
+            if let Some(fight) = &entity.world.dragon_fight {
+                fight
+                    .update_health(&entity.world, self.living_entity.health.load() / max_health)
+                    .await;
+            }
+        })
+    }
+
+    fn damage_with_context<'a>(
+        &'a self,
+        caller: &'a dyn EntityBase,
+        amount: f32,
+        damage_type: DamageType,
+        position: Option<Vector3<f64>>,
+        source: Option<&'a dyn EntityBase>,
+        cause: Option<&'a dyn EntityBase>,
+    ) -> EntityBaseFuture<'a, bool> {
+        Box::pin(async move {
+            self.living_entity
+                .damage_with_context(caller, amount, damage_type, position, source, cause)
+                .await
+        })
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.living_entity.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        Some(&self.living_entity)
+    }
+
+    fn get_gravity(&self) -> f64 {
+        // The dragon flies
+        0.0
+    }
+
+    fn as_nbt_storage(&self) -> &dyn NBTStorage {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::CrystalLink;
+
+    #[test]
+    fn destroying_the_healing_crystal_stops_the_healing() {
+        let crystal = Uuid::new_v4();
+        let mut link = CrystalLink::default();
+        assert_eq!(link.healing(10, 150.0, 200.0), 0.0);
+
+        link.link(Some(crystal));
+        assert_eq!(link.healing(10, 150.0, 200.0), 1.0);
+        // Only every tenth tick, and never above the maximum health
+        assert_eq!(link.healing(11, 150.0, 200.0), 0.0);
+        assert_eq!(link.healing(20, 199.5, 200.0), 0.5);
+        assert_eq!(link.healing(30, 200.0, 200.0), 0.0);
+
+        // Other crystals don't matter
+        assert_eq!(link.on_crystal_destroyed(Uuid::new_v4()), None);
+        assert_eq!(link.crystal(), Some(crystal));
+
+        assert_eq!(link.on_crystal_destroyed(crystal), Some(10.0));
+        assert_eq!(link.crystal(), None);
+        assert_eq!(link.healing(40, 150.0, 200.0), 0.0);
+    }
+}
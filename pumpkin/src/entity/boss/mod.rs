@@ -0,0 +1 @@
+pub mod ender_dragon;
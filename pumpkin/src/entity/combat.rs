@@ -1,8 +1,11 @@
 use std::sync::atomic::Ordering;
 
 use pumpkin_data::{
+    damage::DamageType,
+    entity::EntityType,
     particle::Particle,
     sound::{Sound, SoundCategory},
+    tag::Tag,
 };
 use pumpkin_protocol::{codec::var_int::VarInt, java::client::play::CEntityVelocity};
 use pumpkin_util::math::vector3::Vector3;
@@ -51,25 +54,55 @@ impl AttackType {
     }
 }
 
-pub async fn handle_knockback(attacker: &Entity, world: &World, victim: &Entity, strength: f64) {
-    let yaw = attacker.yaw.load();
+/// Knocks `victim` away from `from`, along the horizontal vector between them.
+pub async fn handle_knockback(world: &World, victim: &Entity, from: Vector3<f64>, strength: f64) {
+    let (x, z) = knockback_direction(from, victim.pos.load());
 
     let saved_velo = victim.velocity.load();
-    victim.knockback(
-        strength * 0.5,
-        f64::from((yaw.to_radians()).sin()),
-        f64::from(-(yaw.to_radians()).cos()),
-    );
+    victim.knockback(strength, x, z);
 
     let entity_id = VarInt(victim.entity_id);
     let victim_velocity = victim.velocity.load();
 
     let packet = CEntityVelocity::new(entity_id, victim_velocity);
-    let velocity = attacker.velocity.load();
-    attacker.velocity.store(velocity.multiply(0.6, 1.0, 0.6));
 
-    victim.velocity.store(saved_velo);
-    world.broadcast_packet_all(&packet).await;
+    // Players move themselves, the client applies the velocity we send
+    if victim.entity_type == &EntityType::PLAYER {
+        victim.velocity.store(saved_velo);
+    }
+    world
+        .broadcast_to_trackers_and_self(victim.entity_id, &packet)
+        .await;
+}
+
+/// The direction to pass to [`Entity::knockback`] to knock `victim` away from `from`.
+#[must_use]
+pub fn knockback_direction(from: Vector3<f64>, victim: Vector3<f64>) -> (f64, f64) {
+    (from.x - victim.x, from.z - victim.z)
+}
+
+/// Whether `damage_type` is in the damage type `tag`.
+#[must_use]
+pub fn damage_type_has_tag(damage_type: DamageType, tag: &Tag) -> bool {
+    tag.0
+        .iter()
+        .any(|name| DamageType::from_name(name) == Some(damage_type))
+}
+
+/// The damage left after armor, `DamageUtil.getDamageLeft()`
+#[must_use]
+pub fn damage_after_armor(damage: f32, armor: f32, toughness: f32) -> f32 {
+    let toughness_factor = 2.0 + toughness / 4.0;
+    let effective_armor = (armor - damage / toughness_factor)
+        .max(armor * 0.2)
+        .min(20.0);
+    damage * (1.0 - effective_armor / 25.0)
+}
+
+/// The damage left after protection enchantments, `DamageUtil.getInflictedDamage()`
+#[must_use]
+pub fn damage_after_protection(damage: f32, protection: f32) -> f32 {
+    damage * (1.0 - protection.clamp(0.0, 20.0) / 25.0)
 }
 
 pub async fn spawn_sweep_particle(attacker_entity: &Entity, world: &World, pos: &Vector3<f64>) {
@@ -124,3 +157,44 @@ pub async fn player_attack_sound(pos: &Vector3<f64>, world: &World, attack_type:
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{damage::DamageType, tag};
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{
+        damage_after_armor, damage_after_protection, damage_type_has_tag, knockback_direction,
+    };
+
+    #[test]
+    fn armor_and_protection_reduce_damage() {
+        assert_eq!(damage_after_armor(10.0, 0.0, 0.0), 10.0);
+        // Full diamond armor
+        let reduced = damage_after_armor(10.0, 20.0, 8.0);
+        assert!((reduced - 3.0).abs() < 1.0e-5);
+        // Armor is less effective against big hits, but always blocks a fifth of its points
+        let reduced = damage_after_armor(100.0, 20.0, 0.0);
+        assert!((reduced - 84.0).abs() < 1.0e-4);
+
+        assert!((damage_after_protection(10.0, 5.0) - 8.0).abs() < 1.0e-5);
+        // Capped at 20 protection
+        assert!((damage_after_protection(10.0, 64.0) - 2.0).abs() < 1.0e-5);
+
+        assert!(damage_type_has_tag(
+            DamageType::STARVE,
+            &tag::DamageType::MINECRAFT_BYPASSES_ARMOR
+        ));
+        assert!(!damage_type_has_tag(
+            DamageType::PLAYER_ATTACK,
+            &tag::DamageType::MINECRAFT_BYPASSES_ARMOR
+        ));
+    }
+
+    #[test]
+    fn knockback_pushes_away_from_the_attacker() {
+        let (x, z) =
+            knockback_direction(Vector3::new(0.0, 64.0, 0.0), Vector3::new(3.0, 70.0, 4.0));
+        assert_eq!((x, z), (-3.0, -4.0));
+    }
+}
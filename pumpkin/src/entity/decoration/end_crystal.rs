@@ -5,6 +5,22 @@ use pumpkin_data::{damage::DamageType, meta_data_type::MetaDataType, tracked_dat
 use pumpkin_protocol::java::client::play::Metadata;
 use pumpkin_util::math::vector3::Vector3;
 
+/// How strong the explosion of a destroyed end crystal is.
+const EXPLOSION_POWER: f32 = 6.0;
+/// Damage types which destroy an end crystal without making it explode, so crystals can't blow
+/// each other up in a chain.
+const EXPLOSION_DAMAGE_TYPES: [DamageType; 4] = [
+    DamageType::EXPLOSION,
+    DamageType::PLAYER_EXPLOSION,
+    DamageType::FIREWORKS,
+    DamageType::BAD_RESPAWN_POINT,
+];
+
+/// Returns the power of the explosion an end crystal destroyed by `damage_type` causes.
+fn explosion_power(damage_type: &DamageType) -> Option<f32> {
+    (!EXPLOSION_DAMAGE_TYPES.contains(damage_type)).then_some(EXPLOSION_POWER)
+}
+
 pub struct EndCrystalEntity {
     entity: Entity,
 }
@@ -45,15 +61,20 @@ impl EntityBase for EndCrystalEntity {
         damage_type: DamageType,
         _position: Option<Vector3<f64>>,
         _source: Option<&'a dyn EntityBase>,
-        _cause: Option<&'a dyn EntityBase>,
+        cause: Option<&'a dyn EntityBase>,
     ) -> EntityBaseFuture<'a, bool> {
         Box::pin(async move {
-            if damage_type != DamageType::EXPLOSION {
-                self.entity.world.explode(self.entity.pos.load(), 6.0).await;
+            if self.entity.is_invulnerable_to(&damage_type) {
+                return false;
             }
-
-            // TODO
+            let world = &self.entity.world;
             self.entity.remove().await;
+            if let Some(power) = explosion_power(&damage_type) {
+                world.explode(self.entity.pos.load(), power).await;
+            }
+            if let Some(dragon_fight) = &world.dragon_fight {
+                dragon_fight.on_crystal_destroyed(self, cause).await;
+            }
             true
         })
     }
@@ -62,3 +83,18 @@ impl EntityBase for EndCrystalEntity {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::damage::DamageType;
+
+    use super::explosion_power;
+
+    #[test]
+    fn only_non_explosion_damage_makes_crystals_explode() {
+        assert_eq!(explosion_power(&DamageType::PLAYER_ATTACK), Some(6.0));
+        assert_eq!(explosion_power(&DamageType::ARROW), Some(6.0));
+        assert_eq!(explosion_power(&DamageType::EXPLOSION), None);
+        assert_eq!(explosion_power(&DamageType::PLAYER_EXPLOSION), None);
+    }
+}
@@ -95,7 +95,7 @@ impl EntityBase for FallingEntity {
     fn init_data_tracker(&self) -> EntityBaseFuture<'_, ()> {
         Box::pin(async move {
             self.entity
-                .send_meta_data(&[Metadata::new(
+                .send_meta_data_now(&[Metadata::new(
                     TrackedData::DATA_BLOCK_POS,
                     MetaDataType::BlockPos,
                     self.entity.block_pos.load(),
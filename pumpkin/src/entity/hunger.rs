@@ -16,6 +16,8 @@ pub struct HungerManager {
     pub saturation: AtomicCell<f32>,
     pub exhaustion: AtomicCell<f32>,
     pub tick_timer: AtomicCell<u32>,
+    /// Counts ticks on peaceful, the food bar refills every 10 of them
+    peaceful_timer: AtomicCell<u32>,
 }
 
 impl Default for HungerManager {
@@ -25,75 +27,125 @@ impl Default for HungerManager {
             saturation: AtomicCell::new(5.0),
             exhaustion: AtomicCell::new(0.0),
             tick_timer: AtomicCell::new(0),
+            peaceful_timer: AtomicCell::new(0),
         }
     }
 }
 
+/// What the player has to do after the hunger manager ticked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HungerEffect {
+    None,
+    /// Natural regeneration heals this much health
+    Heal(f32),
+    /// The player is starving and takes damage
+    Starve,
+}
+
 impl HungerManager {
+    pub const MAX_LEVEL: u8 = 20;
+
     pub async fn tick(&self, player: &Arc<Player>) {
+        let (difficulty, natural_regen) = {
+            let level_info = player.world().level_info.read().await;
+            (
+                level_info.difficulty,
+                level_info.game_rules.natural_health_regeneration,
+            )
+        };
+        let health = player.living_entity.health.load();
+        match self.update(health, player.can_food_heal(), difficulty, natural_regen) {
+            HungerEffect::None => {}
+            HungerEffect::Heal(amount) => player.heal(amount).await,
+            HungerEffect::Starve => {
+                player.damage(&**player, 1.0, DamageType::STARVE).await;
+            }
+        }
+    }
+
+    /// Burns exhaustion and advances the regeneration/starvation timer by one tick.
+    pub fn update(
+        &self,
+        health: f32,
+        can_heal: bool,
+        difficulty: Difficulty,
+        natural_regen: bool,
+    ) -> HungerEffect {
+        // Peaceful slowly refills the food bar
+        if difficulty == Difficulty::Peaceful && natural_regen {
+            let timer = self.peaceful_timer.load() + 1;
+            self.peaceful_timer.store(timer);
+            if timer.is_multiple_of(10) && self.level.load() < Self::MAX_LEVEL {
+                self.level.store(self.level.load() + 1);
+            }
+        }
+
         let saturation = self.saturation.load();
         let level = self.level.load();
         let exhaustion = self.exhaustion.load();
-        let health = player.living_entity.health.load();
-        let difficulty = player.world().level_info.read().await.difficulty;
         // Decrease hunger level on exhaustion
-        if level != 0 && exhaustion > 4.0 {
+        if exhaustion > 4.0 {
             self.exhaustion.store(exhaustion - 4.0);
             if saturation > 0.0 {
                 self.saturation.store((saturation - 1.0).max(0.0));
             } else if difficulty != Difficulty::Peaceful {
-                self.level.store(level - 1);
-                player.send_health().await;
+                self.level.store(level.saturating_sub(1));
             }
         }
+        let saturation = self.saturation.load();
+        let level = self.level.load();
 
         // Heal when hunger is full
-        let natural_regen = true; // TODO: Get the actual value when this will be implemented.
-        if natural_regen && saturation > 0.0 && player.can_food_heal() && level >= 20 {
+        if natural_regen && saturation > 0.0 && can_heal && level >= Self::MAX_LEVEL {
             self.tick_timer.fetch_add(1);
             if self.tick_timer.load() >= 10 {
                 let saturation = saturation.min(6.0);
-                player.heal(saturation / 6.0).await;
                 self.add_exhaustion(saturation);
                 self.tick_timer.store(0);
+                return HungerEffect::Heal(saturation / 6.0);
             }
-        } else if natural_regen && level >= 18 && player.can_food_heal() {
+        } else if natural_regen && level >= 18 && can_heal {
             self.tick_timer.fetch_add(1);
             if self.tick_timer.load() >= 80 {
-                player.heal(1.0).await;
                 self.add_exhaustion(6.0);
                 self.tick_timer.store(0);
+                return HungerEffect::Heal(1.0);
             }
         } else if level == 0 {
             self.tick_timer.fetch_add(1);
             if self.tick_timer.load() >= 80 {
+                self.tick_timer.store(0);
                 if (health > 10.0)
                     || (difficulty == Difficulty::Hard)
                     || (health > 1.0 && difficulty == Difficulty::Normal)
                 {
-                    player.damage(&**player, 1.0, DamageType::STARVE).await;
+                    return HungerEffect::Starve;
                 }
-                self.tick_timer.store(0);
             }
         } else {
             self.tick_timer.store(0);
         }
+        HungerEffect::None
     }
 
     pub async fn add_modifier(&self, player: &Player, food: u8, saturation_modifier: f32) {
         let saturation = f32::from(food) * saturation_modifier * 2.0;
-        self.level.store(food + self.level.load());
-        self.saturation.store(saturation + self.saturation.load());
-        player.send_health().await;
+        self.eat(player, food, saturation).await;
     }
 
+    /// Fills the food bar, saturation can never be higher than the food level.
     pub async fn eat(&self, player: &Player, food: u8, saturation: f32) {
-        self.level.store(food + self.level.load());
-        self.saturation.store(saturation + self.saturation.load());
-
+        self.add_food(food, saturation);
         player.send_health().await;
     }
 
+    pub fn add_food(&self, food: u8, saturation: f32) {
+        let level = food.saturating_add(self.level.load()).min(Self::MAX_LEVEL);
+        self.level.store(level);
+        self.saturation
+            .store((saturation + self.saturation.load()).clamp(0.0, f32::from(level)));
+    }
+
     pub fn add_exhaustion(&self, exhaustion: f32) {
         self.exhaustion
             .store((self.exhaustion.load() + exhaustion).min(40.0));
@@ -104,6 +156,7 @@ impl HungerManager {
         self.saturation.store(5.0);
         self.exhaustion.store(0.0);
         self.tick_timer.store(0);
+        self.peaceful_timer.store(0);
     }
 }
 
@@ -133,3 +186,65 @@ impl NBTStorage for HungerManager {
 }
 
 impl NBTStorageInit for HungerManager {}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::Difficulty;
+
+    use super::{HungerEffect, HungerManager};
+
+    #[test]
+    fn hunger_drains_regenerates_and_starves() {
+        let hunger = HungerManager::default();
+        hunger.saturation.store(1.0);
+
+        // Exhaustion eats saturation first, then the food bar
+        hunger.add_exhaustion(4.5);
+        hunger.update(20.0, false, Difficulty::Normal, true);
+        assert_eq!(hunger.saturation.load(), 0.0);
+        assert_eq!(hunger.level.load(), 20);
+        hunger.add_exhaustion(4.0);
+        hunger.update(20.0, false, Difficulty::Normal, true);
+        assert_eq!(hunger.level.load(), 19);
+
+        // Regenerates every 80 ticks with at least 18 food
+        let effects: Vec<_> = (0..80)
+            .map(|_| hunger.update(15.0, true, Difficulty::Normal, true))
+            .collect();
+        assert!(
+            effects[..79]
+                .iter()
+                .all(|effect| *effect == HungerEffect::None)
+        );
+        assert_eq!(effects[79], HungerEffect::Heal(1.0));
+        assert!((hunger.exhaustion.load() - 6.5).abs() < f32::EPSILON);
+        // But not without the game rule
+        for _ in 0..80 {
+            assert_eq!(
+                hunger.update(15.0, true, Difficulty::Normal, false),
+                HungerEffect::None
+            );
+        }
+
+        // Starving stops at half a heart on normal, but not on hard
+        hunger.level.store(0);
+        hunger.exhaustion.store(0.0);
+        hunger.tick_timer.store(0);
+        let starve = |health, difficulty| {
+            let mut effect = HungerEffect::None;
+            for _ in 0..80 {
+                effect = hunger.update(health, true, difficulty, true);
+            }
+            effect
+        };
+        assert_eq!(starve(5.0, Difficulty::Normal), HungerEffect::Starve);
+        assert_eq!(starve(1.0, Difficulty::Normal), HungerEffect::None);
+        assert_eq!(starve(1.0, Difficulty::Hard), HungerEffect::Starve);
+        assert_eq!(starve(5.0, Difficulty::Easy), HungerEffect::None);
+
+        // Eating is capped at a full food bar
+        hunger.add_food(25, 30.0);
+        assert_eq!(hunger.level.load(), 20);
+        assert_eq!(hunger.saturation.load(), 20.0);
+    }
+}
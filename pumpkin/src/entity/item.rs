@@ -339,7 +339,7 @@ impl EntityBase for ItemEntity {
     fn init_data_tracker(&self) -> EntityBaseFuture<'_, ()> {
         Box::pin(async {
             self.entity
-                .send_meta_data(&[Metadata::new(
+                .send_meta_data_now(&[Metadata::new(
                     TrackedData::DATA_STACK,
                     MetaDataType::ItemStack,
                     &ItemStackSerializer::from(self.item_stack.lock().await.clone()),
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use pumpkin_data::data_component_impl::UseCooldownImpl;
+use pumpkin_util::resource_location::ResourceLocation;
+use pumpkin_world::item::ItemStack;
+
+/// Keeps track of the item groups a player can't use right now, like an ender pearl that was
+/// just thrown.
+#[derive(Default)]
+pub struct ItemCooldownManager {
+    /// The tick each cooldown group can be used again
+    cooldowns: HashMap<ResourceLocation, u64>,
+    tick: u64,
+}
+
+impl ItemCooldownManager {
+    /// Returns the group of the stack, items of the same group share their cooldown.
+    #[must_use]
+    pub fn group_of(stack: &ItemStack) -> ResourceLocation {
+        stack
+            .get_data_component::<UseCooldownImpl>()
+            .and_then(|cooldown| cooldown.cooldown_group)
+            .and_then(|group| group.parse().ok())
+            .unwrap_or_else(|| ResourceLocation::vanilla(stack.item.registry_key))
+    }
+
+    #[must_use]
+    pub fn is_cooling_down(&self, group: &ResourceLocation) -> bool {
+        self.cooldowns
+            .get(group)
+            .is_some_and(|end| *end > self.tick)
+    }
+
+    pub fn set(&mut self, group: ResourceLocation, ticks: u32) {
+        self.cooldowns.insert(group, self.tick + u64::from(ticks));
+    }
+
+    /// Advances the cooldowns by one tick and returns the groups which can be used again.
+    pub fn tick(&mut self) -> Vec<ResourceLocation> {
+        self.tick += 1;
+        let tick = self.tick;
+        let mut expired = Vec::new();
+        self.cooldowns.retain(|group, end| {
+            if *end > tick {
+                return true;
+            }
+            expired.push(group.clone());
+            false
+        });
+        expired
+    }
+}
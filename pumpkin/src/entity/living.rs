@@ -14,32 +14,50 @@ use std::sync::atomic::{
 };
 use std::{collections::HashMap, sync::atomic::AtomicI32};
 
-use super::{Entity, NBTStorage};
+use super::attributes::{AttributeContainer, AttributeInstance, AttributeModifier};
+use super::experience_orb::ExperienceOrbEntity;
+use super::{Entity, Flag, NBTStorage};
 use super::{EntityBase, NBTStorageInit};
 use crate::block::OnLandedUponArgs;
+use crate::block::blocks::frosted_ice::frost_walk;
+use crate::entity::combat::{self, damage_type_has_tag};
 use crate::entity::{EntityBaseFuture, NbtFuture};
+use crate::item::items::chorus_fruit::{CHORUS_FRUIT_TELEPORT_DIAMETER, teleport_randomly};
 use crate::server::Server;
 use crate::world::loot::{LootContextParameters, LootTableExt};
 use crossbeam::atomic::AtomicCell;
-use pumpkin_data::Block;
+use pumpkin_data::attributes::Attributes;
 use pumpkin_data::damage::DeathMessageType;
-use pumpkin_data::data_component_impl::{DeathProtectionImpl, EquipmentSlot, FoodImpl};
+use pumpkin_data::data_component_impl::{
+    AttributeModifiersImpl, ConsumableImpl, DeathProtectionImpl, EquipmentSlot, FoodImpl, Modifier,
+    UseCooldownImpl,
+};
 use pumpkin_data::effect::StatusEffect;
-use pumpkin_data::entity::{EntityPose, EntityStatus, EntityType};
+use pumpkin_data::entity::{EntityPose, EntityStatus, EntityType, MobCategory};
+use pumpkin_data::item::Item;
+use pumpkin_data::particle::Particle;
 use pumpkin_data::sound::SoundCategory;
+use pumpkin_data::{Block, Enchantment, tag, tag::Tag, tag::Taggable};
 use pumpkin_data::{damage::DamageType, sound::Sound};
 use pumpkin_inventory::entity_equipment::EntityEquipment;
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_nbt::tag::NbtTag;
 use pumpkin_protocol::codec::var_int::VarInt;
-use pumpkin_protocol::java::client::play::{CHurtAnimation, CTakeItemEntity};
+use pumpkin_protocol::java::client::play::{
+    AttributeProperty, CEntityStatus, CHurtAnimation, CParticle, CTakeItemEntity,
+    CUpdateAttributes, PropertyModifier,
+};
 use pumpkin_protocol::{
     codec::item_stack_seralizer::ItemStackSerializer,
     java::client::play::{CDamageEvent, CSetEquipment, Metadata},
+    ser::serializer::Serializer,
 };
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::text::TextComponent;
+use pumpkin_world::data::player_stats::{CustomStat, Stat};
 use pumpkin_world::item::ItemStack;
+use rand::Rng;
+use serde::Serialize;
 use tokio::sync::Mutex;
 
 /// Represents a living entity within the game world.
@@ -56,6 +74,8 @@ pub struct LivingEntity {
     pub health: AtomicCell<f32>,
     pub item_use_time: AtomicI32,
     pub item_in_use: Mutex<Option<ItemStack>>,
+    /// The hand holding `item_in_use`.
+    pub active_hand: AtomicCell<Hand>,
     pub death_time: AtomicU8,
     /// Indicates whether the entity is dead. (`on_death` called)
     pub dead: AtomicBool,
@@ -65,6 +85,10 @@ pub struct LivingEntity {
     pub entity_equipment: Arc<Mutex<EntityEquipment>>,
     pub movement_input: AtomicCell<Vector3<f64>>,
     pub equipment_slots: Arc<HashMap<usize, EquipmentSlot>>,
+    /// The attributes with the modifiers of the equipment and effects applied.
+    pub attributes: Mutex<AttributeContainer>,
+    /// The equipment whose attribute modifiers are applied, to notice when it changes.
+    last_equipment: Mutex<HashMap<EquipmentSlot, ItemStack>>,
 
     pub movement_speed: AtomicCell<f64>,
 
@@ -79,6 +103,8 @@ pub struct LivingEntity {
 
     water_movement_speed_multiplier: f32,
     livings_flags: AtomicU8,
+    /// The block position of the last tick, location based enchantments only apply after moving.
+    last_block_pos: AtomicCell<Option<BlockPos>>,
 }
 
 impl LivingEntity {
@@ -98,6 +124,18 @@ impl LivingEntity {
         // TODO: Extract default MOVEMENT_SPEED Entity Attribute
         let default_movement_speed = 0.25;
         let health = entity.entity_type.max_health.unwrap_or(20.0);
+        let attributes = if entity.entity_type == &EntityType::PLAYER {
+            AttributeContainer::with_base_values(&[
+                (&Attributes::MAX_HEALTH, f64::from(health)),
+                (&Attributes::ATTACK_DAMAGE, 1.0),
+                (&Attributes::MOVEMENT_SPEED, 0.1),
+            ])
+        } else {
+            AttributeContainer::with_base_values(&[
+                (&Attributes::MAX_HEALTH, f64::from(health)),
+                (&Attributes::MOVEMENT_SPEED, default_movement_speed),
+            ])
+        };
         Self {
             entity,
             hurt_cooldown: AtomicI32::new(0),
@@ -108,10 +146,13 @@ impl LivingEntity {
             dead: AtomicBool::new(false),
             item_use_time: AtomicI32::new(0),
             item_in_use: Mutex::new(None),
+            active_hand: AtomicCell::new(Hand::Left),
             livings_flags: AtomicU8::new(0),
             active_effects: Mutex::new(HashMap::new()),
             entity_equipment: Arc::new(Mutex::new(EntityEquipment::new())),
             equipment_slots: Arc::new(build_equipment_slots()),
+            attributes: Mutex::new(attributes),
+            last_equipment: Mutex::new(HashMap::new()),
             jumping: AtomicBool::new(false),
             jumping_cooldown: AtomicU8::new(0),
             climbing: AtomicBool::new(false),
@@ -119,6 +160,7 @@ impl LivingEntity {
             movement_input: AtomicCell::new(Vector3::default()),
             movement_speed: AtomicCell::new(default_movement_speed),
             water_movement_speed_multiplier,
+            last_block_pos: AtomicCell::new(None),
         }
     }
 
@@ -158,6 +200,7 @@ impl LivingEntity {
     pub async fn set_active_hand(&self, hand: Hand, stack: ItemStack) {
         self.item_use_time
             .store(stack.get_max_use_time(), Ordering::Relaxed);
+        self.active_hand.store(hand);
         *self.item_in_use.lock().await = Some(stack);
         self.set_living_flag(Self::USING_ITEM_FLAG, true).await;
         self.set_living_flag(Self::OFF_HAND_ACTIVE_FLAG, hand == Hand::Left)
@@ -182,6 +225,147 @@ impl LivingEntity {
             .await;
     }
 
+    /// Counts down the item in use. Consumables take effect once their full time is up, using
+    /// stops early when the item leaves the hand.
+    async fn tick_active_item(&self, caller: &Arc<dyn EntityBase>) {
+        let Some(item) = self.item_in_use.lock().await.clone() else {
+            return;
+        };
+        let player = caller.get_player();
+        let hand_stack = match player {
+            Some(player) if self.active_hand.load() == Hand::Left => {
+                Some(player.inventory.held_item())
+            }
+            Some(player) => Some(player.inventory.off_hand_item().await),
+            None => None,
+        };
+        // Switching slots or changing the stack in any other way than its count stops using it
+        if let Some(hand_stack) = &hand_stack
+            && !hand_stack
+                .lock()
+                .await
+                .are_items_and_components_equal(&item)
+        {
+            self.clear_active_hand().await;
+            return;
+        }
+
+        let remaining = self.item_use_time.fetch_sub(1, Ordering::Relaxed);
+        // Other used items, like shields, stay in use until released
+        let Some(consumable) = item.get_data_component::<ConsumableImpl>() else {
+            return;
+        };
+        if consumable.should_play_effects(remaining) {
+            self.play_consume_effects(&item, consumable, 5).await;
+        }
+        if remaining > 1 {
+            return;
+        }
+
+        self.play_consume_effects(&item, consumable, 16).await;
+        if let Some(food) = item.get_data_component::<FoodImpl>()
+            && let Some(player) = player
+        {
+            player
+                .hunger_manager
+                .eat(player, food.nutrition as u8, food.saturation)
+                .await;
+            let pitch = rand::rng().random_range(0.9..1.0);
+            self.entity
+                .world
+                .play_sound_fine(
+                    Sound::EntityPlayerBurp,
+                    SoundCategory::Players,
+                    &self.entity.pos.load(),
+                    0.5,
+                    pitch,
+                )
+                .await;
+        }
+        if let Some(player) = player
+            && let Some(hand_stack) = hand_stack
+        {
+            hand_stack
+                .lock()
+                .await
+                .decrement_unless_creative(player.gamemode.load(), 1);
+            if let Some(cooldown) = item.get_data_component::<UseCooldownImpl>() {
+                player
+                    .set_item_cooldown(&item, cooldown.cooldown_ticks())
+                    .await;
+            }
+            // Tells the client to finish eating, even if its own countdown is behind
+            self.entity
+                .world
+                .broadcast_to_trackers_and_self(
+                    self.entity.entity_id,
+                    &CEntityStatus::new(self.entity.entity_id, EntityStatus::ConsumeItem as i8),
+                )
+                .await;
+        }
+        if item.item == &Item::CHORUS_FRUIT {
+            teleport_randomly(caller.clone(), CHORUS_FRUIT_TELEPORT_DIAMETER).await;
+        }
+
+        self.clear_active_hand().await;
+    }
+
+    /// Plays the consume sound and, if the consumable has them, spawns `particles` crumbs of
+    /// `item` in front of the mouth. `Consumable.spawnParticlesAndPlaySound()`
+    async fn play_consume_effects(
+        &self,
+        item: &ItemStack,
+        consumable: &ConsumableImpl,
+        particles: i32,
+    ) {
+        self.play_consume_sound(consumable).await;
+        if !consumable.has_consume_particles {
+            return;
+        }
+        let mut data = Vec::new();
+        ItemStackSerializer::from(item.clone())
+            .serialize(&mut Serializer::new(&mut data))
+            .unwrap();
+        let rotation = self.entity.rotation();
+        let mouth = Vector3::new(
+            self.entity.pos.load().x + f64::from(rotation.x) * 0.6,
+            self.entity.get_eye_y() - 0.3 + f64::from(rotation.y) * 0.6,
+            self.entity.pos.load().z + f64::from(rotation.z) * 0.6,
+        );
+        self.entity
+            .world
+            .broadcast_packet_all(&CParticle::new(
+                false,
+                false,
+                mouth,
+                Vector3::new(0.1, 0.1, 0.1),
+                0.05,
+                particles,
+                VarInt(Particle::Item as i32),
+                &data,
+            ))
+            .await;
+    }
+
+    async fn play_consume_sound(&self, consumable: &ConsumableImpl) {
+        let (volume, pitch) = {
+            let mut rng = rand::rng();
+            let volume = 0.5 + 0.5 * f32::from(rng.random_range(0..2u8));
+            let pitch = (rng.random::<f32>() - rng.random::<f32>()) * 0.2 + 1.0;
+            (volume, pitch)
+        };
+        self.entity
+            .world
+            .play_sound_fine(
+                consumable.sound,
+                SoundCategory::Neutral,
+                &self.entity.pos.load(),
+                volume,
+                pitch,
+            )
+            .await;
+    }
+
     pub async fn clear_active_hand(&self) {
         *self.item_in_use.lock().await = None;
         self.item_use_time.store(0, Ordering::Relaxed);
@@ -199,7 +383,7 @@ impl LivingEntity {
         self.health.store(health.max(0.0));
         // tell everyone entities health changed
         self.entity
-            .send_meta_data(&[Metadata::new(
+            .send_meta_data_now(&[Metadata::new(
                 TrackedData::DATA_HEALTH,
                 MetaDataType::Float,
                 health,
@@ -212,21 +396,39 @@ impl LivingEntity {
     }
 
     pub async fn add_effect(&self, effect: Effect) {
-        self.active_effects
-            .lock()
-            .await
-            .insert(effect.effect_type, effect);
+        let effect_type = effect.effect_type;
+        let amplifier = effect.amplifier;
+        self.active_effects.lock().await.insert(effect_type, effect);
+        self.update_effect_attributes(effect_type, Some(amplifier))
+            .await;
+        if effect_type == &StatusEffect::GLOWING {
+            self.entity.set_flag(Flag::Glowing, true).await;
+        }
         // TODO broadcast metadata
     }
 
     pub async fn remove_effect(&self, effect_type: &'static StatusEffect) {
         self.active_effects.lock().await.remove(&effect_type);
+        self.update_effect_attributes(effect_type, None).await;
+        if effect_type == &StatusEffect::GLOWING {
+            self.entity.set_flag(Flag::Glowing, false).await;
+        }
         self.entity
             .world
             .send_remove_mob_effect(&self.entity, effect_type)
             .await;
     }
 
+    /// Removes all effects, returns how many there were.
+    pub async fn clear_effects(&self) -> usize {
+        let effects: Vec<_> = self.active_effects.lock().await.keys().copied().collect();
+        let count = effects.len();
+        for effect_type in effects {
+            self.remove_effect(effect_type).await;
+        }
+        count
+    }
+
     pub async fn has_effect(&self, effect: &'static StatusEffect) -> bool {
         let effects = self.active_effects.lock().await;
         effects.contains_key(&effect)
@@ -237,6 +439,36 @@ impl LivingEntity {
         effects.get(&effect).cloned()
     }
 
+    /// Applies the attribute modifiers of a status effect at `amplifier`, or removes them when
+    /// `None`. They are sent with the other attribute changes on the next tick.
+    /// `StatusEffect.onApplied()` and `StatusEffect.onRemoved()`
+    async fn update_effect_attributes(
+        &self,
+        effect_type: &'static StatusEffect,
+        amplifier: Option<u8>,
+    ) {
+        let mut attributes = self.attributes.lock().await;
+        for modifier in effect_type.attribute_modifiers {
+            match amplifier {
+                Some(amplifier) => attributes.add_modifier(
+                    modifier.attribute,
+                    AttributeModifier {
+                        id: modifier.id.to_string(),
+                        amount: modifier.base_value * (f64::from(amplifier) + 1.0),
+                        operation: modifier.operation,
+                    },
+                ),
+                None => {
+                    attributes.remove_modifier(modifier.attribute, modifier.id);
+                }
+            }
+        }
+    }
+
+    pub async fn get_max_health(&self) -> f32 {
+        self.attributes.lock().await.value(&Attributes::MAX_HEALTH) as f32
+    }
+
     // Check if the entity is in water
     pub async fn is_in_water(&self) -> bool {
         let block_pos = self.entity.block_pos.load();
@@ -339,7 +571,7 @@ impl LivingEntity {
             self.travel_in_air(caller.clone()).await;
         }
 
-        //self.entity.tick_block_underneath(&caller);
+        self.entity.tick_block_underneath(caller.as_ref()).await;
 
         let suffocating = self.entity.tick_block_collisions(&caller, server).await;
 
@@ -381,7 +613,7 @@ impl LivingEntity {
         self.entity
             .update_velocity_from_input(self.movement_input.load(), speed);
 
-        self.apply_climbing_speed();
+        self.apply_climbing_speed().await;
 
         self.make_move(caller.clone()).await;
 
@@ -530,58 +762,22 @@ impl LivingEntity {
             .move_entity(caller, self.entity.velocity.load())
             .await;
 
-        self.check_climbing();
+        self.check_climbing().await;
     }
 
-    fn check_climbing(&self) {
+    /// Updates whether the entity is climbing, i.e. is inside a ladder, vine or scaffolding.
+    pub async fn check_climbing(&self) {
         // If spectator: return false
 
-        // TODO
-        // let mut pos = self.entity.block_pos.load();
-
-        // let world = self.entity.world.read().await;
-
-        // let (block, state) = world.get_block_and_state(&pos).await;
-
-        // let name = block.properties(state.id).map(|props| props.name());
-
-        // if let Some(name) = name {
-        //     if name == "LadderLikeProperties"
-        //         || name == "ScaffoldingLikeProperties"
-        //         || name == "CaveVinesLikeProperties"
-        //         || name == "CaveVinesPlantLikeProperties"
-        //     {
-        //         self.climbing.store(true, Relaxed);
-
-        //         self.climbing_pos.store(Some(pos));
-
-        //         return;
-        //     }
-
-        //     if name == "OakTrapdoorLikeProperties" {
-        //         let trapdoor = OakTrapdoorLikeProperties::from_state_id(state.id, &block);
-
-        //         pos.0.y -= 1;
-
-        //         let (down_block, down_state) = world.get_block_and_state(&pos).await;
-
-        //         let is_ladder = down_block
-        //             .properties(down_state.id)
-        //             .is_some_and(|down_props| down_props.name() == "LadderLikeProperties");
-
-        //         if is_ladder {
-        //             let ladder = LadderLikeProperties::from_state_id(down_state.id, &down_block);
-
-        //             if trapdoor.r#facing == ladder.r#facing {
-        //                 self.climbing.store(true, Relaxed);
-
-        //                 self.climbing_pos.store(Some(pos));
+        let pos = self.entity.block_pos.load();
+        let block = self.entity.world.get_block(&pos).await;
+        if block.has_tag(&tag::Block::MINECRAFT_CLIMBABLE) {
+            self.climbing.store(true, Relaxed);
+            self.climbing_pos.store(Some(pos));
+            return;
+        }
 
-        //                 return;
-        //             }
-        //         }
-        //     }
-        // }
+        // TODO: Open trapdoors above a ladder facing the same way are climbable too
 
         self.climbing.store(false, Relaxed);
 
@@ -590,52 +786,208 @@ impl LivingEntity {
         }
     }
 
-    fn apply_climbing_speed(&self) {
+    async fn apply_climbing_speed(&self) {
         if self.climbing.load(Relaxed) {
             self.fall_distance.store(0.0);
 
-            let mut velo = self.entity.velocity.load();
+            // Sneaking players hold onto ladders, but climb down scaffolding
+            let holding_on = self.entity.entity_type == &EntityType::PLAYER
+                && self.entity.sneaking.load(Relaxed)
+                && self
+                    .entity
+                    .world
+                    .get_block(&self.entity.block_pos.load())
+                    .await
+                    != &Block::SCAFFOLDING;
 
-            let pos = 0.15;
+            let velo = Self::climbing_velocity(self.entity.velocity.load(), holding_on);
+            self.entity.velocity.store(velo);
+        }
+    }
+
+    /// Limits the velocity of a climbing entity, which stops falling when `holding_on`.
+    #[must_use]
+    pub fn climbing_velocity(mut velo: Vector3<f64>, holding_on: bool) -> Vector3<f64> {
+        const MAX_SPEED: f64 = 0.15;
 
-            let neg = -0.15;
+        velo.x = velo.x.clamp(-MAX_SPEED, MAX_SPEED);
+        velo.z = velo.z.clamp(-MAX_SPEED, MAX_SPEED);
+        velo.y = velo.y.max(-MAX_SPEED);
+
+        if velo.y < 0.0 && holding_on {
+            velo.y = 0.0;
+        }
 
-            if velo.x < neg {
-                velo.x = neg;
-            } else if velo.x > pos {
-                velo.x = pos;
+        velo
+    }
+
+    /// Reduces `amount` by the worn armor and its protection enchantments.
+    /// `LivingEntity.applyArmorToDamage()` and `LivingEntity.modifyAppliedDamage()`
+    async fn apply_armor_to_damage(&self, damage_type: DamageType, mut amount: f32) -> f32 {
+        let (armor, toughness) = {
+            let attributes = self.attributes.lock().await;
+            (
+                attributes.value(&Attributes::ARMOR) as f32,
+                attributes.value(&Attributes::ARMOR_TOUGHNESS) as f32,
+            )
+        };
+        let mut protection = 0;
+        let equipment = self.entity_equipment.lock().await;
+        for slot in self.equipment_slots.values() {
+            if !slot.is_armor_slot() {
+                continue;
             }
+            let item = equipment.get(slot);
+            protection += Self::get_protection(&*item.lock().await, damage_type);
+        }
+        drop(equipment);
+
+        if !damage_type_has_tag(damage_type, &tag::DamageType::MINECRAFT_BYPASSES_ARMOR) {
+            amount = combat::damage_after_armor(amount, armor, toughness);
+        }
+        if !damage_type_has_tag(
+            damage_type,
+            &tag::DamageType::MINECRAFT_BYPASSES_ENCHANTMENTS,
+        ) {
+            amount = combat::damage_after_protection(amount, protection as f32);
+        }
+        amount
+    }
 
-            if velo.z < neg {
-                velo.z = neg;
-            } else if velo.z > pos {
-                velo.z = pos;
+    /// The slots whose equipment applies attribute modifiers.
+    const ATTRIBUTE_SLOTS: [EquipmentSlot; 7] = [
+        EquipmentSlot::MAIN_HAND,
+        EquipmentSlot::OFF_HAND,
+        EquipmentSlot::FEET,
+        EquipmentSlot::LEGS,
+        EquipmentSlot::CHEST,
+        EquipmentSlot::HEAD,
+        EquipmentSlot::BODY,
+    ];
+
+    /// Swaps the attribute modifiers of equipment which changed since the last tick and sends all
+    /// changed attributes, including the ones changed by effects. `LivingEntity.sendEquipmentChanges()`
+    async fn tick_attributes(&self, caller: &dyn EntityBase) {
+        let mut changes = Vec::new();
+        {
+            let equipment = self.entity_equipment.lock().await;
+            let mut last_equipment = self.last_equipment.lock().await;
+            for slot in Self::ATTRIBUTE_SLOTS {
+                // The main hand of players is the selected hotbar slot
+                let stack = match caller.get_player() {
+                    Some(player) if slot == EquipmentSlot::MAIN_HAND => {
+                        player.inventory.held_item()
+                    }
+                    _ => equipment.get(&slot),
+                };
+                let stack = stack.lock().await.clone();
+                let last = last_equipment.get(&slot).unwrap_or(ItemStack::EMPTY);
+                if last.are_items_and_components_equal(&stack) {
+                    continue;
+                }
+                let last = last_equipment
+                    .insert(slot.clone(), stack.clone())
+                    .unwrap_or_else(|| ItemStack::EMPTY.clone());
+                changes.push((slot, last, stack));
             }
+        }
 
-            velo.y = velo.y.max(neg);
+        let changed = {
+            let mut attributes = self.attributes.lock().await;
+            for (slot, old, new) in &changes {
+                for modifier in Self::attribute_modifiers(old, slot) {
+                    attributes.remove_modifier(modifier.r#type, modifier.id);
+                }
+                for modifier in Self::attribute_modifiers(new, slot) {
+                    attributes.add_modifier(modifier.r#type, AttributeModifier::from(modifier));
+                }
+            }
+            attributes.take_dirty()
+        };
+        // Players move on their own, only other entities use the speed on the server
+        if caller.get_player().is_none()
+            && changed
+                .iter()
+                .any(|(attribute, _)| *attribute == &Attributes::MOVEMENT_SPEED)
+        {
+            let speed = self
+                .attributes
+                .lock()
+                .await
+                .value(&Attributes::MOVEMENT_SPEED);
+            self.movement_speed.store(speed);
+        }
+        // A lower maximum health cuts the health, a higher one doesn't heal
+        if changed
+            .iter()
+            .any(|(attribute, _)| *attribute == &Attributes::MAX_HEALTH)
+        {
+            let max_health = self.get_max_health().await;
+            if self.health.load() > max_health {
+                match caller.get_player() {
+                    Some(player) => player.set_health(max_health).await,
+                    None => self.set_health(max_health).await,
+                }
+            }
+        }
+        self.send_attributes(changed).await;
+    }
 
-            // TODO
-            // if velo.y < 0.0
-            //     && self.entity.entity_type == &EntityType::PLAYER
-            //     && self.entity.sneaking.load(Relaxed)
-            // {
-            //     let block = self
-            //         .entity
-            //         .world
-            //         .read()
-            //         .await
-            //         .get_block(&self.entity.block_pos.load())
-            //         .await;
-
-            //     if let Some(props) = block.properties(block.default_state.id) {
-            //         if props.name() == "ScaffoldingLikeProperties" {
-            //             velo.y = 0.0;
-            //         }
-            //     }
-            // }
+    /// The attribute modifiers of `stack` which apply while it is in `slot`.
+    fn attribute_modifiers<'a>(
+        stack: &'a ItemStack,
+        slot: &'a EquipmentSlot,
+    ) -> impl Iterator<Item = &'a Modifier> {
+        stack
+            .get_data_component::<AttributeModifiersImpl>()
+            .into_iter()
+            .flat_map(|modifiers| modifiers.attribute_modifiers.iter())
+            .filter(move |modifier| modifier.slot.matches(slot))
+    }
 
-            self.entity.velocity.store(velo);
+    /// Sends the values and modifiers of `attributes` to all players.
+    pub async fn send_attributes(&self, attributes: Vec<(&'static Attributes, AttributeInstance)>) {
+        if attributes.is_empty() {
+            return;
         }
+        let properties = attributes
+            .into_iter()
+            .map(|(attribute, instance)| AttributeProperty {
+                attribute_id: VarInt(i32::from(attribute.id)),
+                base_value: instance.base_value,
+                modifiers: instance
+                    .modifiers
+                    .into_iter()
+                    .map(|modifier| PropertyModifier {
+                        id: modifier.id,
+                        amount: modifier.amount,
+                        operation: modifier.operation as i8,
+                    })
+                    .collect(),
+            })
+            .collect();
+        self.entity
+            .world
+            .broadcast_packet_all(&CUpdateAttributes::new(self.entity_id().into(), properties))
+            .await;
+    }
+
+    /// The protection a piece of armor gives against `damage_type` through its enchantments.
+    fn get_protection(item: &ItemStack, damage_type: DamageType) -> i32 {
+        let protection = item.get_enchantment_level(&Enchantment::PROTECTION);
+        let is = |tag: &Tag| damage_type_has_tag(damage_type, tag);
+        let specific = if is(&tag::DamageType::MINECRAFT_IS_FIRE) {
+            item.get_enchantment_level(&Enchantment::FIRE_PROTECTION) * 2
+        } else if is(&tag::DamageType::MINECRAFT_IS_EXPLOSION) {
+            item.get_enchantment_level(&Enchantment::BLAST_PROTECTION) * 2
+        } else if is(&tag::DamageType::MINECRAFT_IS_PROJECTILE) {
+            item.get_enchantment_level(&Enchantment::PROJECTILE_PROTECTION) * 2
+        } else if is(&tag::DamageType::MINECRAFT_IS_FALL) {
+            item.get_enchantment_level(&Enchantment::FEATHER_FALLING) * 3
+        } else {
+            0
+        };
+        protection + specific
     }
 
     pub fn get_swim_height(&self) -> f64 {
@@ -703,14 +1055,14 @@ impl LivingEntity {
                 return;
             }
             let world = &self.entity.world;
-            let block = world
-                .get_block(&self.entity.get_pos_with_y_offset(0.2).await.0)
-                .await;
+            let position = self.entity.get_pos_with_y_offset(0.2).await.0;
+            let block = world.get_block(&position).await;
             let pumpkin_block = world.block_registry.get_pumpkin_block(block.id);
             if let Some(pumpkin_block) = pumpkin_block {
                 pumpkin_block
                     .on_landed_upon(OnLandedUponArgs {
                         world,
+                        position: &position,
                         fall_distance,
                         entity: caller.as_ref(),
                     })
@@ -718,6 +1070,9 @@ impl LivingEntity {
             } else {
                 self.handle_fall_damage(fall_distance, 1.0).await;
             }
+        } else if self.climbing.load(Relaxed) {
+            // Climbing breaks the fall
+            self.fall_distance.store(0.0);
         } else if height_difference < 0.0 {
             let new_fall_distance = if !self.is_in_water().await && !self.is_in_powder_snow().await
             {
@@ -833,13 +1188,41 @@ impl LivingEntity {
                         if player.entity_id() == attacker_entity.entity_id {
                             let entity_type_id = pumpkin_util::resource_location::ResourceLocation::vanilla(self.entity.entity_type.resource_name);
                             player.trigger_player_killed_entity(&entity_type_id).await;
+                            player
+                                .increment_stat(Stat::Killed(self.entity.entity_type), 1)
+                                .await;
+                            let kills = if self.entity.entity_type == &EntityType::PLAYER {
+                                CustomStat::PlayerKills
+                            } else {
+                                CustomStat::MobKills
+                            };
+                            player.increment_stat(Stat::Custom(kills), 1).await;
                             break;
                         }
                     }
                 }
             }
 
+            if let Some(player) = dyn_self.get_player() {
+                player
+                    .increment_stat(Stat::Custom(CustomStat::Deaths), 1)
+                    .await;
+                if let Some(cause) = cause {
+                    player
+                        .increment_stat(Stat::KilledBy(cause.get_entity().entity_type), 1)
+                        .await;
+                }
+            }
+
             self.drop_loot(params).await;
+            // Players drop their experience when they are killed, see `Player::handle_killed`
+            if killed_by_player && dyn_self.get_player().is_none() {
+                let amount =
+                    experience_reward(self.entity.entity_type, rand::rng().random_range(0..3));
+                if amount > 0 {
+                    ExperienceOrbEntity::spawn(world, self.entity.pos.load(), amount).await;
+                }
+            }
             self.entity.pose.store(EntityPose::Dying);
 
             let block_pos = self.entity.block_pos.load();
@@ -878,24 +1261,132 @@ impl LivingEntity {
         }
     }
 
-    async fn tick_effects(&self) {
+    /// Applies the ground effects of Frost Walker and Soul Speed boots.
+    async fn tick_boot_enchantments(&self, caller: &dyn EntityBase) {
+        const SOUL_SPEED_DURATION: i32 = 10;
+
+        let block_pos = self.entity.block_pos.load();
+        let moved = self.last_block_pos.swap(Some(block_pos)) != Some(block_pos);
+        if !self.entity.on_ground.load(Relaxed) || self.entity.get_vehicle().await.is_some() {
+            return;
+        }
+
+        let boots = self.entity_equipment.lock().await.get(&EquipmentSlot::FEET);
+        let (frost_walker, soul_speed) = {
+            let boots = boots.lock().await;
+            (
+                boots.get_enchantment_level(&Enchantment::FROST_WALKER),
+                boots.get_enchantment_level(&Enchantment::SOUL_SPEED),
+            )
+        };
+
+        if frost_walker > 0 && moved {
+            frost_walk(&self.entity.world, self.entity.pos.load(), frost_walker).await;
+        }
+
+        if soul_speed > 0
+            && self
+                .entity
+                .world
+                .get_block(&block_pos.down())
+                .await
+                .has_tag(&tag::Block::MINECRAFT_SOUL_SPEED_BLOCKS)
+        {
+            // Vanilla adds 0.0405 + 0.0105 per level above the first to the base speed of 0.1,
+            // there are no attribute modifiers yet so the closest speed effect is used instead
+            let bonus = (0.0405 + 0.0105 * f64::from(soul_speed - 1)) / 0.1;
+            let amplifier = ((bonus / 0.2).round() as u8).saturating_sub(1);
+            let current = self.get_effect(&StatusEffect::SPEED).await;
+            if current.is_none_or(|effect| {
+                effect.amplifier <= amplifier && effect.duration < SOUL_SPEED_DURATION / 2
+            }) {
+                let effect = Effect {
+                    effect_type: &StatusEffect::SPEED,
+                    duration: SOUL_SPEED_DURATION,
+                    amplifier,
+                    ambient: true,
+                    show_particles: false,
+                    show_icon: false,
+                    blend: false,
+                };
+                if let Some(player) = caller.get_player() {
+                    player.add_effect(effect).await;
+                } else {
+                    self.add_effect(effect).await;
+                }
+            }
+
+            if rand::random::<f32>() < 0.04 && boots.lock().await.damage_item(1) {
+                let boots = boots.lock().await.clone();
+                self.send_equipment_changes(&[(EquipmentSlot::FEET, boots)])
+                    .await;
+            }
+        }
+    }
+
+    async fn tick_effects(&self, caller: &dyn EntityBase) {
         let mut effects_to_remove = Vec::new();
+        let mut effects_to_apply = Vec::new();
 
         {
+            let age = self.entity.age.load(Relaxed);
             let mut effects = self.active_effects.lock().await;
             for effect in effects.values_mut() {
-                if effect.duration == 0 {
+                // Infinite effects use the age of the entity to know when to apply
+                let infinite = effect.duration == -1;
+                if !infinite && effect.duration <= 0 {
                     effects_to_remove.push(effect.effect_type);
+                    continue;
+                }
+                let duration = if infinite { age } else { effect.duration };
+                if should_apply_effect(effect.effect_type, duration, effect.amplifier) {
+                    effects_to_apply.push((effect.effect_type, effect.amplifier));
+                }
+                if !infinite {
+                    effect.duration -= 1;
                 }
-                effect.duration -= 1;
             }
         }
 
+        for (effect_type, amplifier) in effects_to_apply {
+            self.apply_effect_tick(caller, effect_type, amplifier).await;
+        }
         for effect_type in effects_to_remove {
             self.remove_effect(effect_type).await;
         }
     }
 
+    /// The per-tick consequences of an effect. `StatusEffect.applyUpdateEffect()`
+    async fn apply_effect_tick(
+        &self,
+        caller: &dyn EntityBase,
+        effect_type: &'static StatusEffect,
+        amplifier: u8,
+    ) {
+        if effect_type == &StatusEffect::REGENERATION {
+            let missing = self.get_max_health().await - self.health.load();
+            if missing > 0.0 {
+                if let Some(player) = caller.get_player() {
+                    player.heal(missing.min(1.0)).await;
+                } else {
+                    self.heal(missing.min(1.0)).await;
+                }
+            }
+        } else if effect_type == &StatusEffect::POISON {
+            if self.health.load() > 1.0 {
+                caller.damage(caller, 1.0, DamageType::MAGIC).await;
+            }
+        } else if effect_type == &StatusEffect::WITHER {
+            caller.damage(caller, 1.0, DamageType::WITHER).await;
+        } else if effect_type == &StatusEffect::HUNGER
+            && let Some(player) = caller.get_player()
+        {
+            player
+                .add_exhaustion(0.005 * (f32::from(amplifier) + 1.0))
+                .await;
+        }
+    }
+
     async fn try_use_death_protector(&self, caller: &dyn EntityBase) -> bool {
         for hand in Hand::all() {
             let stack = self.get_stack_in_hand(caller, hand).await;
@@ -962,6 +1453,24 @@ impl LivingEntity {
     }
 }
 
+/// Whether an effect with `duration` ticks left does something this tick, stronger effects apply
+/// more often. `StatusEffect.canApplyUpdateEffect()`
+fn should_apply_effect(effect_type: &'static StatusEffect, duration: i32, amplifier: u8) -> bool {
+    let interval = if effect_type == &StatusEffect::REGENERATION {
+        50
+    } else if effect_type == &StatusEffect::POISON {
+        25
+    } else if effect_type == &StatusEffect::WITHER {
+        40
+    } else if effect_type == &StatusEffect::HUNGER {
+        return true;
+    } else {
+        return false;
+    };
+    let interval = interval >> amplifier.min(31);
+    interval == 0 || duration % interval == 0
+}
+
 impl NBTStorage for LivingEntity {
     fn write_nbt<'a>(&'a self, nbt: &'a mut NbtCompound) -> NbtFuture<'a, ()> {
         Box::pin(async move {
@@ -992,6 +1501,7 @@ impl NBTStorage for LivingEntity {
             self.health.store(nbt.get_float("Health").unwrap_or(0.0));
             self.fall_distance
                 .store(nbt.get_float("fall_distance").unwrap_or(0.0));
+            let mut loaded_effects = Vec::new();
             {
                 let mut active_effects = self.active_effects.lock().await;
                 let nbt_effects = nbt.get_list("active_effects");
@@ -1005,11 +1515,16 @@ impl NBTStorage for LivingEntity {
                             }
                             let mut effect = effect.unwrap();
                             effect.blend = true; // TODO: change, is taken from effect give command
+                            loaded_effects.push((effect.effect_type, effect.amplifier));
                             active_effects.insert(effect.effect_type, effect);
                         }
                     }
                 }
             }
+            for (effect_type, amplifier) in loaded_effects {
+                self.update_effect_attributes(effect_type, Some(amplifier))
+                    .await;
+            }
         })
         // todo more...
     }
@@ -1039,7 +1554,10 @@ impl EntityBase for LivingEntity {
                 return false;
             }
 
-            if (damage_type == DamageType::IN_FIRE || damage_type == DamageType::ON_FIRE)
+            if (damage_type == DamageType::IN_FIRE
+                || damage_type == DamageType::ON_FIRE
+                || damage_type == DamageType::HOT_FLOOR
+                || damage_type == DamageType::LAVA)
                 && self.has_effect(&StatusEffect::FIRE_RESISTANCE).await
             {
                 return false; // Fire resistance
@@ -1061,7 +1579,9 @@ impl EntityBase for LivingEntity {
                 amount
             };
             self.last_damage_taken.store(amount);
-            damage_amount = damage_amount.max(0.0);
+            damage_amount = self
+                .apply_armor_to_damage(damage_type, damage_amount.max(0.0))
+                .await;
 
             let config = &world.server.upgrade().unwrap().advanced_config.pvp;
 
@@ -1119,7 +1639,17 @@ impl EntityBase for LivingEntity {
                         &self.entity.pos.load(),
                     )
                     .await;
-                // todo: calculate knockback
+
+                let from = position.or_else(|| source.map(|source| source.get_entity().pos.load()));
+                // Players attacking each other respect the PvP knockback setting
+                let pvp = cause.is_some_and(|cause| cause.get_player().is_some());
+                let knockback = (config.knockback || !pvp)
+                    && !damage_type_has_tag(damage_type, &tag::DamageType::MINECRAFT_NO_KNOCKBACK);
+                if let Some(from) = from
+                    && knockback
+                {
+                    combat::handle_knockback(world, &self.entity, from, 0.4).await;
+                }
             }
 
             let new_health = self.health.load() - damage_amount;
@@ -1157,38 +1687,15 @@ impl EntityBase for LivingEntity {
         Box::pin(async move {
             self.entity.tick(caller.clone(), server).await;
             self.tick_movement(server, caller.clone()).await;
+            self.tick_boot_enchantments(caller.as_ref()).await;
+            self.tick_attributes(caller.as_ref()).await;
             // TODO
             if caller.get_player().is_none() {
                 self.entity.send_pos_rot().await;
                 self.entity.send_velocity().await;
             }
-            self.tick_effects().await;
-            // Current active item
-            {
-                let item_in_use = self.item_in_use.lock().await.clone();
-                if let Some(item) = item_in_use.as_ref()
-                    && self.item_use_time.fetch_sub(1, Ordering::Relaxed) <= 0
-                {
-                    if let Some(food) = item.get_data_component::<FoodImpl>()
-                        && let Some(player) = caller.get_player()
-                    {
-                        player
-                            .hunger_manager
-                            .eat(player, food.nutrition as u8, food.saturation)
-                            .await;
-                    }
-                    if let Some(player) = caller.get_player() {
-                        player
-                            .inventory
-                            .held_item()
-                            .lock()
-                            .await
-                            .decrement_unless_creative(player.gamemode.load(), 1);
-                    }
-
-                    self.clear_active_hand().await;
-                }
-            }
+            self.tick_effects(caller.as_ref()).await;
+            self.tick_active_item(&caller).await;
 
             if self.hurt_cooldown.load(Relaxed) > 0 {
                 self.hurt_cooldown.fetch_sub(1, Relaxed);
@@ -1219,3 +1726,77 @@ impl EntityBase for LivingEntity {
         self
     }
 }
+
+/// The experience a mob drops when it is killed by a player, `random` is between `0` and `2`.
+/// `Mob.getBaseExperienceReward()`
+fn experience_reward(entity_type: &EntityType, random: u32) -> u32 {
+    if entity_type == &EntityType::WITHER {
+        50
+    } else if entity_type == &EntityType::BLAZE
+        || entity_type == &EntityType::GUARDIAN
+        || entity_type == &EntityType::ELDER_GUARDIAN
+        || entity_type == &EntityType::EVOKER
+    {
+        10
+    } else if entity_type == &EntityType::ENDER_DRAGON {
+        // What every kill after the first one is worth
+        500
+    } else if entity_type.category == &MobCategory::MONSTER {
+        5
+    } else if entity_type.category == &MobCategory::MISC
+        || entity_type.category == &MobCategory::AMBIENT
+    {
+        0
+    } else {
+        1 + random
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::data_component_impl::ConsumableImpl;
+    use pumpkin_data::effect::StatusEffect;
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_data::sound::Sound;
+
+    use super::{experience_reward, should_apply_effect};
+
+    #[test]
+    fn eating_sounds_start_after_the_first_bite() {
+        let bread = ConsumableImpl {
+            consume_seconds: 1.6,
+            sound: Sound::EntityGenericEat,
+            has_consume_particles: true,
+        };
+        // 32 ticks, of which the first 7 are silent
+        assert!(!bread.should_play_effects(28));
+        assert!(bread.should_play_effects(24));
+        assert!(!bread.should_play_effects(23));
+        assert!(bread.should_play_effects(4));
+    }
+
+    #[test]
+    fn killed_mobs_drop_experience() {
+        assert_eq!(experience_reward(&EntityType::ZOMBIE, 2), 5);
+        assert_eq!(experience_reward(&EntityType::BLAZE, 0), 10);
+        assert_eq!(experience_reward(&EntityType::COW, 0), 1);
+        assert_eq!(experience_reward(&EntityType::COW, 2), 3);
+        assert_eq!(experience_reward(&EntityType::BAT, 2), 0);
+        assert_eq!(experience_reward(&EntityType::VILLAGER, 2), 0);
+    }
+
+    #[test]
+    fn stronger_effects_apply_more_often() {
+        let ticks = |effect, amplifier| {
+            (1..=100)
+                .filter(|duration| should_apply_effect(effect, *duration, amplifier))
+                .count()
+        };
+        assert_eq!(ticks(&StatusEffect::REGENERATION, 0), 2);
+        assert_eq!(ticks(&StatusEffect::REGENERATION, 1), 4);
+        assert_eq!(ticks(&StatusEffect::POISON, 0), 4);
+        assert_eq!(ticks(&StatusEffect::WITHER, 255), 100);
+        assert_eq!(ticks(&StatusEffect::HUNGER, 0), 100);
+        assert_eq!(ticks(&StatusEffect::SPEED, 0), 0);
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+
+use bytes::BufMut;
+
+/// Collects the metadata changes of an entity, so at most one `CSetEntityMetadata` is sent
+/// every few ticks instead of one per change. Only entries which differ from what clients
+/// already have are sent.
+#[derive(Default)]
+pub struct MetadataBuffer {
+    /// Serialized entries keyed by their index, a later change replaces an earlier one.
+    pending: BTreeMap<u8, Vec<u8>>,
+    /// The entries clients have, sent in full to new viewers.
+    sent: BTreeMap<u8, Vec<u8>>,
+    ticks_since_sent: u32,
+}
+
+impl MetadataBuffer {
+    pub fn push(&mut self, index: u8, entry: Vec<u8>) {
+        if self.sent.get(&index) == Some(&entry) {
+            // Also drops a pending change which was reverted before it was sent
+            self.pending.remove(&index);
+        } else {
+            self.pending.insert(index, entry);
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Called once every tick, returns the packet payload once `interval` ticks have passed
+    /// since the last one was sent.
+    pub fn tick(&mut self, interval: u32) -> Option<Vec<u8>> {
+        self.ticks_since_sent = self.ticks_since_sent.saturating_add(1);
+        if self.pending.is_empty() || self.ticks_since_sent < interval {
+            return None;
+        }
+        Some(self.take())
+    }
+
+    /// Returns the payload of all pending entries regardless of the interval.
+    pub fn take(&mut self) -> Vec<u8> {
+        self.ticks_since_sent = 0;
+        let mut buf = Vec::new();
+        for (index, entry) in std::mem::take(&mut self.pending) {
+            buf.extend(&entry);
+            self.sent.insert(index, entry);
+        }
+        buf.put_u8(255);
+        buf
+    }
+
+    /// Queues every sent entry again, pending changes are kept.
+    pub fn resend(&mut self) {
+        for (index, entry) in std::mem::take(&mut self.sent) {
+            self.pending.entry(index).or_insert(entry);
+        }
+    }
+
+    /// Returns the payload of every entry, including pending ones, for clients which just
+    /// started viewing the entity. `None` if no metadata was set.
+    #[must_use]
+    pub fn full(&self) -> Option<Vec<u8>> {
+        let mut entries = self.sent.clone();
+        entries.extend(self.pending.clone());
+        if entries.is_empty() {
+            return None;
+        }
+        let mut buf = entries.into_values().flatten().collect::<Vec<_>>();
+        buf.put_u8(255);
+        Some(buf)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::java::client::play::CSetEntityMetadata;
+    use pumpkin_protocol::packet::Packet;
+    use pumpkin_util::math::vector3::Vector3;
+    use pumpkin_util::text::TextComponent;
+
+    use super::MetadataBuffer;
+    use crate::entity::player::Player;
+    use crate::net::java::queued_packet_ids;
+    use crate::server::Server;
+
+    #[test]
+    fn changes_within_interval_are_combined() {
+        let mut buffer = MetadataBuffer::default();
+        let mut packets = Vec::new();
+
+        // A flag and the pose change every tick, but only one packet may go out every 4 ticks
+        for tick in 0..4u8 {
+            buffer.push(0, vec![0, 0, tick]);
+            buffer.push(6, vec![6, 21, tick]);
+            packets.extend(buffer.tick(4));
+        }
+
+        assert_eq!(packets, vec![vec![0, 0, 3, 6, 21, 3, 255]]);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.tick(4), None);
+    }
+
+    #[test]
+    fn only_changed_entries_are_sent() {
+        let mut buffer = MetadataBuffer::default();
+        buffer.push(0, vec![0, 0, 0]);
+        buffer.push(6, vec![6, 21, 0]);
+        assert_eq!(buffer.take(), vec![0, 0, 0, 6, 21, 0, 255]);
+
+        // The player starts sneaking, the pose is sent again with the same value
+        buffer.push(0, vec![0, 0, 2]);
+        buffer.push(6, vec![6, 21, 0]);
+        assert_eq!(buffer.take(), vec![0, 0, 2, 255]);
+
+        // A change reverted before it was sent is dropped
+        buffer.push(6, vec![6, 21, 5]);
+        buffer.push(6, vec![6, 21, 0]);
+        assert!(buffer.is_empty());
+
+        // New viewers get everything
+        buffer.push(6, vec![6, 21, 1]);
+        assert_eq!(buffer.full(), Some(vec![0, 0, 2, 6, 21, 1, 255]));
+        assert_eq!(MetadataBuffer::default().full(), None);
+
+        // Respawned entities send everything again
+        buffer.resend();
+        assert_eq!(buffer.take(), vec![0, 0, 2, 6, 21, 1, 255]);
+    }
+
+    #[tokio::test]
+    async fn entity_metadata_is_flushed_once_per_tick() {
+        let (server, _world_dir) = Server::new_for_test().await;
+        let world = server.worlds.read().await[0].clone();
+        let (player, mut packets) = Player::new_for_test(world.clone(), "Sprinter").await;
+        world
+            .players
+            .write()
+            .await
+            .insert(player.gameprofile.id, player.clone());
+        let entity = &player.living_entity.entity;
+        // Clear of the terrain, so the pose can change
+        entity.set_pos(Vector3::new(0.0, 300.0, 0.0));
+        let mut metadata_packets = || {
+            queued_packet_ids(&mut packets)
+                .into_iter()
+                .filter(|id| *id == CSetEntityMetadata::PACKET_ID)
+                .count()
+        };
+
+        entity.set_sprinting(true).await;
+        entity.set_on_fire(true).await;
+        entity
+            .set_custom_name(TextComponent::text("Sprinter"))
+            .await;
+        assert_eq!(metadata_packets(), 0);
+        entity.flush_meta_data(1).await;
+        assert_eq!(metadata_packets(), 1);
+        entity.flush_meta_data(1).await;
+        assert_eq!(metadata_packets(), 0);
+
+        // The pose goes out right away, together with the sneaking flag queued before it
+        entity.set_sneaking(true).await;
+        assert_eq!(metadata_packets(), 1);
+        entity.flush_meta_data(1).await;
+        assert_eq!(metadata_packets(), 0);
+
+        // Respawning sends everything again at once
+        entity.resend_meta_data().await;
+        assert_eq!(metadata_packets(), 1);
+    }
+}
@@ -2,6 +2,7 @@ use super::{Entity, EntityBase, NBTStorage, ai::path::Navigator, living::LivingE
 use crate::entity::EntityBaseFuture;
 use crate::entity::ai::control::look_control::LookControl;
 use crate::entity::ai::goal::goal_selector::GoalSelector;
+use crate::entity::player::Player;
 use crate::server::Server;
 use crate::world::World;
 use crossbeam::atomic::AtomicCell;
@@ -85,6 +86,16 @@ pub trait Mob: EntityBase + Send + Sync {
     fn get_path_aware_entity(&self) -> Option<&dyn PathAwareEntity> {
         None
     }
+
+    /// Called every tick after the goals and navigation have been ticked.
+    fn mob_tick<'a>(&'a self, _server: &'a Server) -> EntityBaseFuture<'a, ()> {
+        Box::pin(async {})
+    }
+
+    /// Called when a player right clicks this mob, returns true if the interaction was consumed.
+    fn mob_interact<'a>(&'a self, _player: &'a Arc<Player>) -> EntityBaseFuture<'a, bool> {
+        Box::pin(async { false })
+    }
 }
 
 impl<T: Mob + Send + 'static> EntityBase for T {
@@ -123,9 +134,15 @@ impl<T: Mob + Send + 'static> EntityBase for T {
             let mut look_control = mob_entity.look_control.lock().await;
             look_control.tick(self).await;
             drop(look_control);
+
+            self.mob_tick(server).await;
         })
     }
 
+    fn interact<'a>(&'a self, player: &'a Arc<Player>) -> EntityBaseFuture<'a, bool> {
+        self.mob_interact(player)
+    }
+
     fn damage_with_context<'a>(
         &'a self,
         caller: &'a dyn EntityBase,
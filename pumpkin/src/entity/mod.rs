@@ -1,9 +1,9 @@
 use crate::entity::item::ItemEntity;
 use crate::world::World;
 use crate::{server::Server, world::portal::PortalManager};
-use bytes::BufMut;
 use crossbeam::atomic::AtomicCell;
 use living::LivingEntity;
+use metadata::MetadataBuffer;
 use player::Player;
 use pumpkin_data::BlockState;
 use pumpkin_data::block_properties::{EnumVariants, Integer0To15};
@@ -23,8 +23,8 @@ use pumpkin_protocol::java::client::play::{CUpdateEntityPos, CUpdateEntityPosRot
 use pumpkin_protocol::{
     codec::var_int::VarInt,
     java::client::play::{
-        CEntityPositionSync, CEntityVelocity, CHeadRot, CSetEntityMetadata, CSpawnEntity,
-        CUpdateEntityRot, Metadata,
+        CEntityPositionSync, CEntityVelocity, CHeadRot, CSetEntityMetadata, CSetPassengers,
+        CSpawnEntity, CUpdateEntityRot, Metadata,
     },
     ser::serializer::Serializer,
 };
@@ -44,7 +44,7 @@ use std::collections::BTreeMap;
 use std::f32::consts::PI;
 use std::pin::Pin;
 use std::sync::{
-    Arc,
+    Arc, Weak,
     atomic::{
         AtomicBool, AtomicI32, AtomicU32,
         Ordering::{self, Relaxed},
@@ -53,14 +53,20 @@ use std::sync::{
 use tokio::sync::Mutex;
 
 pub mod ai;
+pub mod attributes;
+pub mod boss;
 pub mod decoration;
 pub mod effect;
 pub mod experience_orb;
 pub mod falling;
 pub mod hunger;
 pub mod item;
+pub mod item_cooldown;
 pub mod living;
+pub mod metadata;
 pub mod mob;
+pub mod movement_validator;
+pub mod passive;
 pub mod player;
 pub mod projectile;
 pub mod projectile_deflection;
@@ -181,6 +187,11 @@ pub trait EntityBase: Send + Sync + NBTStorage {
         Box::pin(async {})
     }
 
+    /// Called when a player right clicks this entity, returns true if the interaction was consumed
+    fn interact<'a>(&'a self, _player: &'a Arc<Player>) -> EntityBaseFuture<'a, bool> {
+        Box::pin(async { false })
+    }
+
     fn get_entity(&self) -> &Entity;
     fn get_living_entity(&self) -> Option<&LivingEntity>;
 
@@ -338,10 +349,10 @@ pub struct Entity {
     pub fire_ticks: AtomicI32,
     pub has_visual_fire: AtomicBool,
     pub removal_reason: AtomicCell<Option<RemovalReason>>,
-    // The passengers that entity has
-    pub passengers: Mutex<Vec<Arc<dyn EntityBase>>>,
-    /// The vehicle that entity is in
-    pub vehicle: Mutex<Option<Arc<dyn EntityBase>>>,
+    /// The passengers riding this entity
+    pub passengers: Mutex<Vec<Weak<dyn EntityBase>>>,
+    /// The vehicle this entity is riding
+    pub vehicle: Mutex<Option<Weak<dyn EntityBase>>>,
     pub age: AtomicI32,
 
     pub first_loaded_chunk_position: AtomicCell<Option<Vector3<i32>>>,
@@ -363,6 +374,8 @@ pub struct Entity {
     pub velocity_dirty: AtomicBool,
     /// Set when an Entity is to be removed but could still be referenced
     pub removed: AtomicBool,
+    /// Metadata changes waiting to be sent, see `flush_meta_data`
+    metadata_buffer: Mutex<MetadataBuffer>,
 }
 
 impl Entity {
@@ -437,6 +450,7 @@ impl Entity {
             movement_multiplier: AtomicCell::new(Vector3::default()),
             velocity_dirty: AtomicBool::new(true),
             removed: AtomicBool::new(false),
+            metadata_buffer: Mutex::new(MetadataBuffer::default()),
         }
     }
 
@@ -458,7 +472,10 @@ impl Entity {
     pub async fn send_velocity(&self) {
         let velocity = self.velocity.load();
         self.world
-            .broadcast_packet_all(&CEntityVelocity::new(self.entity_id.into(), velocity))
+            .broadcast_to_trackers_and_self(
+                self.entity_id,
+                &CEntityVelocity::new(self.entity_id.into(), velocity),
+            )
             .await;
     }
 
@@ -554,12 +571,15 @@ impl Entity {
         let pitch = (pitch * 256.0 / 360.0).rem_euclid(256.0);
 
         self.world
-            .broadcast_packet_all(&CUpdateEntityRot::new(
-                self.entity_id.into(),
-                yaw,
-                pitch as u8,
-                self.on_ground.load(Relaxed),
-            ))
+            .broadcast_to_trackers(
+                self.entity_id,
+                &CUpdateEntityRot::new(
+                    self.entity_id.into(),
+                    yaw,
+                    pitch as u8,
+                    self.on_ground.load(Relaxed),
+                ),
+            )
             .await;
 
         self.send_head_rot(yaw).await;
@@ -567,7 +587,10 @@ impl Entity {
 
     pub async fn send_head_rot(&self, head_yaw: u8) {
         self.world
-            .broadcast_packet_all(&CHeadRot::new(self.entity_id.into(), head_yaw))
+            .broadcast_to_trackers(
+                self.entity_id,
+                &CHeadRot::new(self.entity_id.into(), head_yaw),
+            )
             .await;
     }
 
@@ -742,68 +765,18 @@ impl Entity {
         self.velocity.store(motion);
     }
 
-    #[expect(dead_code)]
-    fn tick_block_underneath(_caller: &Arc<dyn EntityBase>) {
-        // let world = self.world.read().await;
-
-        // let (pos, block, state) = self.get_block_with_y_offset(0.2).await;
-
-        // world
-        //     .block_registry
-        //     .on_stepped_on(&world, caller.as_ref(), pos, block, state)
-        //     .await;
-
-        // TODO: Add this to on_stepped_on
-
-        /*
-
-
-        if self.on_ground.load(Ordering::SeqCst) {
-
-
-            let (_pos, block, state) = self.get_block_with_y_offset(0.2).await;
-
-
-            if let Some(live) = living {
-
-
-                if block == Block::CAMPFIRE
-
-
-                    || block == Block::SOUL_CAMPFIRE
-
-
-                        && CampfireLikeProperties::from_state_id(state.id, &block).r#signal_fire
-
-
-                {
-
-
-                    let _ = live.damage(1.0, DamageType::CAMPFIRE).await;
-
-
-                }
-
-
-
-
-
-                if block == Block::MAGMA_BLOCK {
-
-
-                    let _ = live.damage(1.0, DamageType::HOT_FLOOR).await;
-
-
-                }
-
-
-            }
-
-
+    pub async fn tick_block_underneath(&self, caller: &dyn EntityBase) {
+        if !self.on_ground.load(Ordering::SeqCst) {
+            return;
         }
 
+        let (pos, block, state) = self.get_block_with_y_offset(0.2).await;
 
-        */
+        // TODO: Move campfire damage into on_stepped_on
+        self.world
+            .block_registry
+            .on_stepped_on(block, &self.world, caller, &pos, state)
+            .await;
     }
 
     // Returns whether the entity's eye level is in a wall
@@ -844,7 +817,7 @@ impl Entity {
                         },
                     );
 
-                    if collided {
+                    if collided || World::check_contact(&bounding_box, pos, state) {
                         self.world
                             .block_registry
                             .on_entity_collision(
@@ -888,13 +861,16 @@ impl Entity {
         let pitch = (pitch * 256.0 / 360.0).rem_euclid(256.0);
 
         self.world
-            .broadcast_packet_all(&CUpdateEntityPosRot::new(
-                self.entity_id.into(),
-                Vector3::new(converted.x, converted.y, converted.z),
-                yaw,
-                pitch as u8,
-                self.on_ground.load(Relaxed),
-            ))
+            .broadcast_to_trackers(
+                self.entity_id,
+                &CUpdateEntityPosRot::new(
+                    self.entity_id.into(),
+                    Vector3::new(converted.x, converted.y, converted.z),
+                    yaw,
+                    pitch as u8,
+                    self.on_ground.load(Relaxed),
+                ),
+            )
             .await;
         self.send_head_rot(yaw).await;
     }
@@ -918,11 +894,14 @@ impl Entity {
         );
 
         self.world
-            .broadcast_packet_all(&CUpdateEntityPos::new(
-                self.entity_id.into(),
-                Vector3::new(converted.x, converted.y, converted.z),
-                self.on_ground.load(Relaxed),
-            ))
+            .broadcast_to_trackers(
+                self.entity_id,
+                &CUpdateEntityPos::new(
+                    self.entity_id.into(),
+                    Vector3::new(converted.x, converted.y, converted.z),
+                    self.on_ground.load(Relaxed),
+                ),
+            )
             .await;
     }
 
@@ -1396,6 +1375,8 @@ impl Entity {
 
     /// Removes the `Entity` from their current `World`
     pub async fn remove(&self) {
+        self.stop_riding().await;
+        self.remove_all_passengers().await;
         self.world.remove_entity(self).await;
     }
 
@@ -1634,17 +1615,69 @@ impl Entity {
             .await;
     }
 
+    /// Queues metadata changes, they are sent together with other changes by `flush_meta_data`.
     pub async fn send_meta_data<T: Serialize>(&self, meta: &[Metadata<T>]) {
-        let mut buf = Vec::new();
+        let mut buffer = self.metadata_buffer.lock().await;
+        Self::buffer_meta_data(&mut buffer, meta);
+    }
+
+    /// Sends metadata changes right away together with all queued ones, use this for changes
+    /// clients must not miss or have to see before the next packet, like the health of a dying
+    /// entity or a new pose.
+    pub async fn send_meta_data_now<T: Serialize>(&self, meta: &[Metadata<T>]) {
+        let buf = {
+            let mut buffer = self.metadata_buffer.lock().await;
+            Self::buffer_meta_data(&mut buffer, meta);
+            // Clients may already have all of it
+            (!buffer.is_empty()).then(|| buffer.take())
+        };
+        if let Some(buf) = buf {
+            self.broadcast_meta_data(buf).await;
+        }
+    }
+
+    /// Sends the queued metadata changes if `interval` ticks have passed since the last packet.
+    pub async fn flush_meta_data(&self, interval: u32) {
+        let buf = self.metadata_buffer.lock().await.tick(interval);
+        if let Some(buf) = buf {
+            self.broadcast_meta_data(buf).await;
+        }
+    }
+
+    /// All metadata of this entity, including changes which were not sent yet. Sent to clients
+    /// which just started viewing the entity.
+    pub async fn full_meta_data(&self) -> Option<Vec<u8>> {
+        self.metadata_buffer.lock().await.full()
+    }
+
+    /// Sends all metadata again right away, for when the entity is spawned again for all
+    /// viewers, like after respawning.
+    pub async fn resend_meta_data(&self) {
+        let buf = {
+            let mut buffer = self.metadata_buffer.lock().await;
+            buffer.resend();
+            (!buffer.is_empty()).then(|| buffer.take())
+        };
+        if let Some(buf) = buf {
+            self.broadcast_meta_data(buf).await;
+        }
+    }
+
+    fn buffer_meta_data<T: Serialize>(buffer: &mut MetadataBuffer, meta: &[Metadata<T>]) {
         for meta in meta {
             let mut serializer_buf = Vec::new();
             let mut serializer = Serializer::new(&mut serializer_buf);
             meta.serialize(&mut serializer).unwrap();
-            buf.extend(serializer_buf);
+            buffer.push(meta.index(), serializer_buf);
         }
-        buf.put_u8(255);
+    }
+
+    async fn broadcast_meta_data(&self, buf: Vec<u8>) {
         self.world
-            .broadcast_packet_all(&CSetEntityMetadata::new(self.entity_id.into(), buf.into()))
+            .broadcast_to_trackers_and_self(
+                self.entity_id,
+                &CSetEntityMetadata::new(self.entity_id.into(), buf.into()),
+            )
             .await;
     }
 
@@ -1658,7 +1691,8 @@ impl Entity {
             self.bounding_box.store(aabb);
             self.entity_dimension.store(dimension);
             let pose = pose as i32;
-            self.send_meta_data(&[Metadata::new(
+            // The client has to know the new bounding box before the next movement
+            self.send_meta_data_now(&[Metadata::new(
                 TrackedData::DATA_POSE,
                 MetaDataType::EntityPose,
                 VarInt(pose),
@@ -1770,12 +1804,97 @@ impl Entity {
     }
 
     pub async fn has_passengers(&self) -> bool {
-        !self.passengers.lock().await.is_empty()
+        !self.get_passengers().await.is_empty()
     }
 
     pub async fn has_vehicle(&self) -> bool {
-        let vehicle = self.vehicle.lock().await;
-        vehicle.is_some()
+        self.get_vehicle().await.is_some()
+    }
+
+    pub async fn get_passengers(&self) -> Vec<Arc<dyn EntityBase>> {
+        self.passengers
+            .lock()
+            .await
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect()
+    }
+
+    pub async fn get_vehicle(&self) -> Option<Arc<dyn EntityBase>> {
+        self.vehicle.lock().await.as_ref().and_then(Weak::upgrade)
+    }
+
+    /// Lets `caller`, which has to be this entity, ride `vehicle`.
+    ///
+    /// Returns false if the entity would end up riding itself.
+    pub async fn start_riding(
+        &self,
+        caller: &Arc<dyn EntityBase>,
+        vehicle: &Arc<dyn EntityBase>,
+    ) -> bool {
+        let mut next_vehicle = Some(vehicle.clone());
+        while let Some(current) = next_vehicle {
+            if current.get_entity().entity_id == self.entity_id {
+                return false;
+            }
+            next_vehicle = current.get_entity().get_vehicle().await;
+        }
+
+        self.stop_riding().await;
+        let vehicle_entity = vehicle.get_entity();
+        vehicle_entity
+            .passengers
+            .lock()
+            .await
+            .push(Arc::downgrade(caller));
+        *self.vehicle.lock().await = Some(Arc::downgrade(vehicle));
+        vehicle_entity.send_passengers().await;
+        true
+    }
+
+    /// Dismounts the entity from its vehicle, if it has one.
+    pub async fn stop_riding(&self) {
+        let Some(vehicle) = self.vehicle.lock().await.take().and_then(|v| v.upgrade()) else {
+            return;
+        };
+        let vehicle_entity = vehicle.get_entity();
+        vehicle_entity.passengers.lock().await.retain(|passenger| {
+            passenger
+                .upgrade()
+                .is_some_and(|passenger| passenger.get_entity().entity_id != self.entity_id)
+        });
+        vehicle_entity.send_passengers().await;
+    }
+
+    pub async fn remove_all_passengers(&self) {
+        let passengers = std::mem::take(&mut *self.passengers.lock().await);
+        if passengers.is_empty() {
+            return;
+        }
+        for passenger in passengers.iter().filter_map(Weak::upgrade) {
+            *passenger.get_entity().vehicle.lock().await = None;
+        }
+        self.send_passengers().await;
+    }
+
+    pub async fn send_passengers(&self) {
+        let passenger_ids: Vec<VarInt> = self
+            .get_passengers()
+            .await
+            .iter()
+            .map(|passenger| passenger.get_entity().entity_id.into())
+            .collect();
+        self.world
+            .broadcast_packet_all(&CSetPassengers::new(self.entity_id.into(), &passenger_ids))
+            .await;
+    }
+
+    /// Moves the passengers on top of this entity, called after the vehicle has moved.
+    pub async fn update_passenger_positions(&self) {
+        let seat = self.pos.load().add_raw(0.0, f64::from(self.height()), 0.0);
+        for passenger in self.get_passengers().await {
+            passenger.get_entity().set_pos(seat);
+        }
     }
 
     pub async fn check_out_of_world(&self, dyn_self: &dyn EntityBase) {
@@ -1886,6 +2005,11 @@ impl EntityBase for Entity {
             self.tick_portal(&caller).await;
             self.update_fluid_state(&caller).await;
             self.check_out_of_world(&*caller).await;
+            if self.fire_ticks.load(Ordering::Relaxed) > 0
+                && self.world.is_raining_at(&self.block_pos.load()).await
+            {
+                self.extinguish();
+            }
             let fire_ticks = self.fire_ticks.load(Ordering::Relaxed);
             if fire_ticks > 0 {
                 if self.entity_type.fire_immune {
@@ -1894,7 +2018,8 @@ impl EntityBase for Entity {
                         self.extinguish();
                     }
                 } else {
-                    if fire_ticks % 20 == 0 {
+                    // Lava deals its own damage
+                    if fire_ticks % 20 == 0 && !self.touching_lava.load(Ordering::SeqCst) {
                         caller.damage(&*caller, 1.0, DamageType::ON_FIRE).await;
                     }
 
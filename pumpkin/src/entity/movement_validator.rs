@@ -0,0 +1,138 @@
+use std::fmt;
+
+use pumpkin_config::MovementConfig;
+use pumpkin_util::math::vector3::Vector3;
+
+/// A move which is impossible for a player who is not allowed to fly.
+#[derive(Debug, PartialEq)]
+pub enum MovementViolation {
+    /// The player rose this many blocks in a single move.
+    VerticalSpeed(f64),
+    /// The player stayed in the air without falling for this many moves.
+    Hovering(u32),
+}
+
+impl fmt::Display for MovementViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VerticalSpeed(rise) => write!(f, "rose {rise:.2} blocks in a single move"),
+            Self::Hovering(moves) => write!(f, "hovered for {moves} moves"),
+        }
+    }
+}
+
+/// Catches players flying or rising faster than possible while they are neither allowed to fly
+/// nor lifted by something like water, ladders or levitation.
+#[derive(Default)]
+pub struct MovementValidator {
+    /// The last position which passed validation, players are sent back there.
+    last_valid_pos: Option<Vector3<f64>>,
+    /// The consecutive moves in the air in which the player did not fall.
+    hover_moves: u32,
+}
+
+impl MovementValidator {
+    /// Checks a move from `from` to `to`. `exempt` moves, like the ones of flying or swimming
+    /// players, are always valid. `on_ground` has to be found out by the server, the client's
+    /// own claim can't be trusted.
+    pub fn check(
+        &mut self,
+        config: &MovementConfig,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+        on_ground: bool,
+        exempt: bool,
+    ) -> Result<(), MovementViolation> {
+        let rise = to.y - from.y;
+        if exempt || on_ground {
+            self.hover_moves = 0;
+        } else if rise > config.max_vertical_speed {
+            return Err(MovementViolation::VerticalSpeed(rise));
+        } else if rise >= 0.0 {
+            self.hover_moves += 1;
+            if self.hover_moves > config.max_hover_moves {
+                return Err(MovementViolation::Hovering(self.hover_moves));
+            }
+        } else {
+            self.hover_moves = 0;
+        }
+        self.last_valid_pos = Some(to);
+        Ok(())
+    }
+
+    /// Where the player is sent back to after a violation.
+    #[must_use]
+    pub const fn last_valid_pos(&self) -> Option<Vector3<f64>> {
+        self.last_valid_pos
+    }
+
+    /// Forgets the last valid position, used when the server moves the player itself.
+    pub const fn reset(&mut self) {
+        self.last_valid_pos = None;
+        self.hover_moves = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::MovementConfig;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{MovementValidator, MovementViolation};
+
+    #[test]
+    fn only_impossible_moves_are_rejected() {
+        let config = MovementConfig::default();
+        let mut validator = MovementValidator::default();
+        let ground = Vector3::new(0.5, 64.0, 0.5);
+        let up = |blocks: f64| Vector3::new(0.5, 64.0 + blocks, 0.5);
+
+        // A jump rises for a few moves and then falls back down
+        assert!(
+            validator
+                .check(&config, ground, up(0.42), false, false)
+                .is_ok()
+        );
+        assert!(
+            validator
+                .check(&config, up(0.42), up(1.0), false, false)
+                .is_ok()
+        );
+        assert!(
+            validator
+                .check(&config, up(1.0), up(1.2), false, false)
+                .is_ok()
+        );
+        assert!(
+            validator
+                .check(&config, up(1.2), up(0.5), false, false)
+                .is_ok()
+        );
+        assert!(
+            validator
+                .check(&config, up(0.5), ground, true, false)
+                .is_ok()
+        );
+
+        assert_eq!(
+            validator.check(&config, ground, up(50.0), false, false),
+            Err(MovementViolation::VerticalSpeed(50.0))
+        );
+        assert_eq!(validator.last_valid_pos(), Some(ground));
+        assert!(
+            validator
+                .check(&config, ground, up(50.0), false, true)
+                .is_ok()
+        );
+
+        let mut hovered = Ok(());
+        for _ in 0..=config.max_hover_moves {
+            hovered = validator.check(&config, up(50.0), up(50.0), false, false);
+        }
+        assert_eq!(
+            hovered,
+            Err(MovementViolation::Hovering(config.max_hover_moves + 1))
+        );
+        assert_eq!(validator.last_valid_pos(), Some(up(50.0)));
+    }
+}
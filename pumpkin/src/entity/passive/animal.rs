@@ -0,0 +1,50 @@
+use std::sync::{Arc, Weak};
+
+use pumpkin_data::entity::EntityType;
+
+use crate::entity::ai::goal::look_around::LookAroundGoal;
+use crate::entity::ai::goal::look_at_entity::LookAtEntityGoal;
+use crate::entity::ai::goal::wander_around::WanderAroundGoal;
+use crate::entity::mob::{Mob, MobEntity};
+use crate::entity::{Entity, NBTStorage};
+
+/// How many blocks an animal walks per tick while wandering around.
+const WANDER_SPEED: f64 = 0.1;
+
+/// A passive creature like a cow or a pig, until they get their own entities they share the
+/// basic animal goals.
+pub struct AnimalEntity {
+    pub mob_entity: MobEntity,
+}
+
+impl AnimalEntity {
+    pub async fn make(entity: Entity) -> Arc<Self> {
+        let mob_arc = Arc::new(Self {
+            mob_entity: MobEntity::new(entity),
+        });
+        let mob_weak: Weak<dyn Mob> = {
+            let mob_arc: Arc<dyn Mob> = mob_arc.clone();
+            Arc::downgrade(&mob_arc)
+        };
+
+        {
+            let mut goal_selector = mob_arc.mob_entity.goals_selector.lock().await;
+            goal_selector.add_goal(5, WanderAroundGoal::with_default(WANDER_SPEED));
+            goal_selector.add_goal(
+                6,
+                LookAtEntityGoal::with_default(mob_weak, &EntityType::PLAYER, 6.0),
+            );
+            goal_selector.add_goal(7, Box::new(LookAroundGoal::default()));
+        };
+
+        mob_arc
+    }
+}
+
+impl NBTStorage for AnimalEntity {}
+
+impl Mob for AnimalEntity {
+    fn get_mob_entity(&self) -> &MobEntity {
+        &self.mob_entity
+    }
+}
@@ -0,0 +1,2 @@
+pub mod animal;
+pub mod villager;
@@ -0,0 +1,819 @@
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+
+use pumpkin_data::Block;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::meta_data_type::MetaDataType;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_inventory::merchant::Merchant;
+use pumpkin_inventory::merchant::merchant_screen_handler::MerchantScreenHandler;
+use pumpkin_inventory::merchant::trade_offer::TradeOffer;
+use pumpkin_inventory::player::player_inventory::PlayerInventory;
+use pumpkin_inventory::screen_handler::{
+    BoxFuture, InventoryPlayer, ScreenHandlerFactory, SharedScreenHandler,
+};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_nbt::tag::NbtTag;
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::java::client::play::{CMerchantOffers, MerchantOffer, Metadata};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::item::ItemStack;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::entity::ai::goal::look_around::LookAroundGoal;
+use crate::entity::ai::goal::look_at_entity::LookAtEntityGoal;
+use crate::entity::mob::{Mob, MobEntity};
+use crate::entity::player::Player;
+use crate::entity::{Entity, EntityBaseFuture, NBTStorage, NbtFuture};
+use crate::server::Server;
+use crate::world::World;
+
+use profession::{VillagerProfession, VillagerType};
+use trades::roll_offers;
+
+pub mod profession;
+pub mod trades;
+
+/// Experience needed to reach each level, see VillagerData.java
+const LEVEL_EXPERIENCE: [i32; 5] = [0, 10, 70, 150, 250];
+const MAX_LEVEL: i32 = 5;
+
+/// How often a villager checks its job site, in ticks.
+const JOB_SITE_CHECK_INTERVAL: i32 = 100;
+const JOB_SITE_HORIZONTAL_RANGE: i32 = 6;
+const JOB_SITE_VERTICAL_RANGE: i32 = 2;
+
+const RESTOCK_COOLDOWN: i64 = 2400;
+const MAX_RESTOCKS_PER_DAY: u8 = 2;
+/// The part of the day villagers spend working, which is also when they restock.
+const WORK_HOURS: Range<i64> = 2000..9000;
+
+/// `TrackedData::DATA_VILLAGER_DATA` is the index used by zombie villagers.
+const VILLAGER_DATA_INDEX: u8 = 18;
+
+#[derive(Serialize)]
+struct VillagerDataMeta {
+    villager_type: VarInt,
+    profession: VarInt,
+    level: VarInt,
+}
+
+/// The profession, experience and offers of a villager.
+pub struct VillagerTrading {
+    pub villager_type: VillagerType,
+    pub profession: VillagerProfession,
+    pub level: i32,
+    pub experience: i32,
+    pub offers: Vec<TradeOffer>,
+    pub job_site: Option<BlockPos>,
+    last_restock: i64,
+    last_restock_day: i64,
+    restocks_today: u8,
+    /// Set when a trade reached the next level, the level up itself happens on the next tick.
+    pending_level_up: bool,
+}
+
+impl Default for VillagerTrading {
+    fn default() -> Self {
+        Self {
+            villager_type: VillagerType::default(),
+            profession: VillagerProfession::default(),
+            level: 1,
+            experience: 0,
+            offers: Vec::new(),
+            job_site: None,
+            last_restock: 0,
+            last_restock_day: 0,
+            restocks_today: 0,
+            pending_level_up: false,
+        }
+    }
+}
+
+impl VillagerTrading {
+    /// Claims `block` at `pos` as job site. Villagers without a profession take the one of the
+    /// job site, villagers with a profession only accept their own job site.
+    pub fn claim_job_site(&mut self, pos: BlockPos, block: &Block) -> bool {
+        let Some(profession) = VillagerProfession::from_job_site(block) else {
+            return false;
+        };
+        if self.job_site.is_some()
+            || self.profession == VillagerProfession::Nitwit
+            || (self.profession != VillagerProfession::None && self.profession != profession)
+        {
+            return false;
+        }
+
+        self.job_site = Some(pos);
+        if self.profession != profession {
+            self.profession = profession;
+            self.offers.clear();
+        }
+        true
+    }
+
+    /// Villagers that never traded lose their profession together with their job site.
+    pub fn lose_job_site(&mut self) {
+        self.job_site = None;
+        if self.experience == 0 && self.level <= 1 {
+            self.profession = VillagerProfession::None;
+            self.offers.clear();
+        }
+    }
+
+    /// Returns the offers to show a player, rolling them on the first interaction.
+    pub fn offers_for_trading(&mut self) -> Option<&[TradeOffer]> {
+        if !self.profession.can_trade() {
+            return None;
+        }
+        if self.offers.is_empty() {
+            for level in 1..=self.level {
+                self.offers.extend(roll_offers(self.profession, level));
+            }
+        }
+        (!self.offers.is_empty()).then_some(self.offers.as_slice())
+    }
+
+    /// Uses the offer at `index`, returns false if it is sold out.
+    pub fn trade(&mut self, index: usize) -> bool {
+        let Some(offer) = self.offers.get_mut(index) else {
+            return false;
+        };
+        if offer.is_disabled() {
+            return false;
+        }
+
+        offer.use_offer();
+        self.experience += offer.merchant_experience;
+        if self.can_level_up() {
+            self.pending_level_up = true;
+        }
+        true
+    }
+
+    fn can_level_up(&self) -> bool {
+        self.level < MAX_LEVEL && self.experience >= LEVEL_EXPERIENCE[self.level as usize]
+    }
+
+    /// Applies a pending level up and unlocks the offers of the new levels.
+    pub fn try_level_up(&mut self) -> bool {
+        if !std::mem::take(&mut self.pending_level_up) {
+            return false;
+        }
+        while self.can_level_up() {
+            self.level += 1;
+            self.offers.extend(roll_offers(self.profession, self.level));
+        }
+        true
+    }
+
+    /// Restocks all offers if the villager is at work and hasn't restocked too recently.
+    pub fn try_restock(&mut self, world_age: i64, time_of_day: i64) -> bool {
+        let day = time_of_day.div_euclid(24000);
+        if day != self.last_restock_day {
+            self.last_restock_day = day;
+            self.restocks_today = 0;
+        }
+
+        if self.job_site.is_none()
+            || !WORK_HOURS.contains(&time_of_day.rem_euclid(24000))
+            || self.restocks_today >= MAX_RESTOCKS_PER_DAY
+            || world_age < self.last_restock + RESTOCK_COOLDOWN
+            || !self.offers.iter().any(TradeOffer::has_been_used)
+        {
+            return false;
+        }
+
+        for offer in &mut self.offers {
+            offer.update_demand();
+            offer.reset_uses();
+        }
+        self.last_restock = world_age;
+        self.restocks_today += 1;
+        true
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let mut data = NbtCompound::new();
+        data.put_string("type", format!("minecraft:{}", self.villager_type.name()));
+        data.put_string(
+            "profession",
+            format!("minecraft:{}", self.profession.name()),
+        );
+        data.put_int("level", self.level);
+        nbt.put_component("VillagerData", data);
+        nbt.put_int("Xp", self.experience);
+        nbt.put_long("LastRestock", self.last_restock);
+        nbt.put_int("RestocksToday", i32::from(self.restocks_today));
+
+        let recipes = self
+            .offers
+            .iter()
+            .map(|offer| {
+                let mut recipe = NbtCompound::new();
+                let mut buy = NbtCompound::new();
+                offer.first_buy.write_item_stack(&mut buy);
+                recipe.put_component("buy", buy);
+                if !offer.second_buy.is_empty() {
+                    let mut buy_b = NbtCompound::new();
+                    offer.second_buy.write_item_stack(&mut buy_b);
+                    recipe.put_component("buyB", buy_b);
+                }
+                let mut sell = NbtCompound::new();
+                offer.sell.write_item_stack(&mut sell);
+                recipe.put_component("sell", sell);
+                recipe.put_int("uses", offer.uses);
+                recipe.put_int("maxUses", offer.max_uses);
+                recipe.put_int("xp", offer.merchant_experience);
+                recipe.put_int("specialPrice", offer.special_price);
+                recipe.put_int("demand", offer.demand);
+                recipe.put_float("priceMultiplier", offer.price_multiplier);
+                NbtTag::Compound(recipe)
+            })
+            .collect();
+        let mut offers = NbtCompound::new();
+        offers.put_list("Recipes", recipes);
+        nbt.put_component("Offers", offers);
+
+        if let Some(job_site) = self.job_site {
+            let mut value = NbtCompound::new();
+            value.put(
+                "pos",
+                NbtTag::IntArray(vec![job_site.0.x, job_site.0.y, job_site.0.z]),
+            );
+            let mut memory = NbtCompound::new();
+            memory.put_component("value", value);
+            let mut memories = NbtCompound::new();
+            memories.put_component("minecraft:job_site", memory);
+            let mut brain = NbtCompound::new();
+            brain.put_component("memories", memories);
+            nbt.put_component("Brain", brain);
+        }
+    }
+
+    fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(data) = nbt.get_compound("VillagerData") {
+            self.villager_type = data
+                .get_string("type")
+                .and_then(VillagerType::from_name)
+                .unwrap_or_default();
+            self.profession = data
+                .get_string("profession")
+                .and_then(VillagerProfession::from_name)
+                .unwrap_or_default();
+            self.level = data.get_int("level").unwrap_or(1).clamp(1, MAX_LEVEL);
+        }
+        self.experience = nbt.get_int("Xp").unwrap_or(0);
+        self.last_restock = nbt.get_long("LastRestock").unwrap_or(0);
+        self.restocks_today = nbt
+            .get_int("RestocksToday")
+            .unwrap_or(0)
+            .clamp(0, i32::from(MAX_RESTOCKS_PER_DAY)) as u8;
+
+        self.offers = nbt
+            .get_compound("Offers")
+            .and_then(|offers| offers.get_list("Recipes"))
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|recipe| {
+                let NbtTag::Compound(recipe) = recipe else {
+                    return None;
+                };
+                let first_buy = ItemStack::read_item_stack(recipe.get_compound("buy")?)?;
+                let second_buy = recipe
+                    .get_compound("buyB")
+                    .and_then(ItemStack::read_item_stack)
+                    .unwrap_or(ItemStack::EMPTY.clone());
+                let sell = ItemStack::read_item_stack(recipe.get_compound("sell")?)?;
+                let mut offer = TradeOffer::new(
+                    first_buy,
+                    second_buy,
+                    sell,
+                    recipe.get_int("maxUses").unwrap_or(4),
+                    recipe.get_int("xp").unwrap_or(1),
+                    recipe.get_float("priceMultiplier").unwrap_or(0.0),
+                );
+                offer.uses = recipe.get_int("uses").unwrap_or(0);
+                offer.special_price = recipe.get_int("specialPrice").unwrap_or(0);
+                offer.demand = recipe.get_int("demand").unwrap_or(0);
+                Some(offer)
+            })
+            .collect();
+
+        self.job_site = nbt
+            .get_compound("Brain")
+            .and_then(|brain| brain.get_compound("memories"))
+            .and_then(|memories| memories.get_compound("minecraft:job_site"))
+            .and_then(|memory| memory.get_compound("value"))
+            .and_then(|value| value.get_int_array("pos"))
+            .and_then(|pos| match pos {
+                [x, y, z] => Some(BlockPos::new(*x, *y, *z)),
+                _ => None,
+            });
+    }
+}
+
+/// The trading side of a villager, shared with the merchant screen of its customer.
+pub struct VillagerMerchant {
+    pub trading: Mutex<VillagerTrading>,
+    /// The player currently trading and the sync id of their merchant screen.
+    customer: Mutex<Option<(Arc<Player>, u8)>>,
+}
+
+impl VillagerMerchant {
+    /// Sends the current offers to the trading player, if there is one.
+    async fn send_offers(&self) {
+        let Some((player, sync_id)) = self.customer.lock().await.clone() else {
+            return;
+        };
+        let trading = self.merchant_snapshot().await;
+        let offers: Vec<MerchantOffer> = trading.0.iter().map(TradeOffer::to_protocol).collect();
+        player
+            .client
+            .enqueue_packet(&CMerchantOffers::new(
+                VarInt(i32::from(sync_id)),
+                &offers,
+                VarInt(trading.1),
+                VarInt(trading.2),
+                true,
+                true,
+            ))
+            .await;
+    }
+
+    /// Returns the offers, level and experience of the villager.
+    async fn merchant_snapshot(&self) -> (Vec<TradeOffer>, i32, i32) {
+        let trading = self.trading.lock().await;
+        (trading.offers.clone(), trading.level, trading.experience)
+    }
+}
+
+impl Merchant for VillagerMerchant {
+    fn get_offers(&self) -> BoxFuture<'_, Vec<TradeOffer>> {
+        Box::pin(async move { self.trading.lock().await.offers.clone() })
+    }
+
+    fn trade(&self, index: usize) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            if !self.trading.lock().await.trade(index) {
+                return;
+            }
+            let customer = self.customer.lock().await.clone();
+            if let Some((player, _)) = customer {
+                player.trigger_villager_trade().await;
+            }
+        })
+    }
+
+    fn on_close(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            *self.customer.lock().await = None;
+        })
+    }
+}
+
+struct MerchantScreenFactory {
+    merchant: Arc<VillagerMerchant>,
+    title: TextComponent,
+}
+
+impl ScreenHandlerFactory for MerchantScreenFactory {
+    fn create_screen_handler<'a>(
+        &'a self,
+        sync_id: u8,
+        player_inventory: &'a Arc<PlayerInventory>,
+        _player: &'a dyn InventoryPlayer,
+    ) -> BoxFuture<'a, Option<SharedScreenHandler>> {
+        Box::pin(async move {
+            let handler =
+                MerchantScreenHandler::new(sync_id, player_inventory, self.merchant.clone()).await;
+            Some(Arc::new(Mutex::new(handler)) as SharedScreenHandler)
+        })
+    }
+
+    fn get_display_name(&self) -> TextComponent {
+        self.title.clone()
+    }
+}
+
+pub struct VillagerEntity {
+    pub mob_entity: MobEntity,
+    pub merchant: Arc<VillagerMerchant>,
+    /// Set when the villager data changed and has to be sent to the clients.
+    data_dirty: AtomicBool,
+}
+
+impl VillagerEntity {
+    pub async fn make(entity: Entity) -> Arc<Self> {
+        let villager = Self {
+            mob_entity: MobEntity::new(entity),
+            merchant: Arc::new(VillagerMerchant {
+                trading: Mutex::new(VillagerTrading::default()),
+                customer: Mutex::new(None),
+            }),
+            data_dirty: AtomicBool::new(true),
+        };
+        let mob_arc = Arc::new(villager);
+        let mob_weak: Weak<dyn Mob> = {
+            let mob_arc: Arc<dyn Mob> = mob_arc.clone();
+            Arc::downgrade(&mob_arc)
+        };
+
+        {
+            let mut goal_selector = mob_arc.mob_entity.goals_selector.lock().await;
+            goal_selector.add_goal(
+                9,
+                LookAtEntityGoal::with_default(mob_weak, &EntityType::PLAYER, 8.0),
+            );
+            goal_selector.add_goal(10, Box::new(LookAroundGoal::default()));
+        };
+
+        mob_arc
+    }
+
+    async fn send_villager_data(&self) {
+        let (villager_type, profession, level) = {
+            let trading = self.merchant.trading.lock().await;
+            (trading.villager_type, trading.profession, trading.level)
+        };
+        self.mob_entity
+            .living_entity
+            .entity
+            .send_meta_data(&[Metadata::new(
+                VILLAGER_DATA_INDEX,
+                MetaDataType::VillagerData,
+                VillagerDataMeta {
+                    villager_type: VarInt(villager_type.registry_id()),
+                    profession: VarInt(profession.registry_id()),
+                    level: VarInt(level),
+                },
+            )])
+            .await;
+    }
+
+    /// Keeps the claimed job site valid or looks for a new one nearby.
+    async fn tick_job_site(&self, world: &Arc<World>) {
+        let entity = &self.mob_entity.living_entity.entity;
+        let uuid = entity.entity_uuid;
+        let (job_site, profession) = {
+            let trading = self.merchant.trading.lock().await;
+            (trading.job_site, trading.profession)
+        };
+
+        if let Some(pos) = job_site {
+            let block = world.get_block(&pos).await;
+            let mut claims = world.job_site_claims.lock().await;
+            let claimed_by_other = claims.get(&pos).is_some_and(|owner| *owner != uuid);
+            if !claimed_by_other {
+                if VillagerProfession::from_job_site(block) == Some(profession) {
+                    claims.insert(pos, uuid);
+                    return;
+                }
+                claims.remove(&pos);
+            }
+            drop(claims);
+
+            self.merchant.trading.lock().await.lose_job_site();
+            self.data_dirty.store(true, Ordering::Relaxed);
+            return;
+        }
+
+        if profession == VillagerProfession::Nitwit {
+            return;
+        }
+
+        let center = entity.block_pos.load();
+        let range = Vector3::new(
+            JOB_SITE_HORIZONTAL_RANGE,
+            JOB_SITE_VERTICAL_RANGE,
+            JOB_SITE_HORIZONTAL_RANGE,
+        );
+        let states = world
+            .get_states_between(center.offset(-range), center.offset(range))
+            .await;
+        let mut sites: Vec<_> = states
+            .into_iter()
+            .filter_map(|(pos, state)| {
+                let block = Block::from_state_id(state.id);
+                let site_profession = VillagerProfession::from_job_site(block)?;
+                (profession == VillagerProfession::None || site_profession == profession)
+                    .then_some((pos, block, site_profession))
+            })
+            .collect();
+        if sites.is_empty() {
+            return;
+        }
+        // Nearest first, the position only breaks ties so the choice doesn't depend on the map
+        sites.sort_by_key(|(pos, ..)| (pos.squared_distance(center), pos.0.x, pos.0.y, pos.0.z));
+
+        let candidates: Vec<_> = {
+            let claims = world.job_site_claims.lock().await;
+            sites
+                .into_iter()
+                .map(|(pos, block, site_profession)| {
+                    (pos, block, site_profession, claims.get(&pos).copied())
+                })
+                .collect()
+        };
+        // Sites of villagers which are gone can be claimed again
+        let candidates: Vec<_> = {
+            let entities = world.entities.read().await;
+            candidates
+                .into_iter()
+                .filter(|(.., owner)| owner.is_none_or(|owner| !entities.contains_key(&owner)))
+                .collect()
+        };
+
+        for (pos, block, site_profession, owner) in candidates {
+            let mut claims = world.job_site_claims.lock().await;
+            // Someone else claimed it in the meantime
+            if claims.get(&pos).copied() != owner {
+                continue;
+            }
+            if self
+                .merchant
+                .trading
+                .lock()
+                .await
+                .claim_job_site(pos, block)
+            {
+                claims.insert(pos, uuid);
+                drop(claims);
+                self.data_dirty.store(true, Ordering::Relaxed);
+                if let Some(sound) = site_profession.work_sound() {
+                    world
+                        .play_sound(sound, SoundCategory::Neutral, &entity.pos.load())
+                        .await;
+                }
+                return;
+            }
+        }
+    }
+}
+
+impl NBTStorage for VillagerEntity {
+    fn write_nbt<'a>(&'a self, nbt: &'a mut NbtCompound) -> NbtFuture<'a, ()> {
+        Box::pin(async move {
+            self.mob_entity.living_entity.write_nbt(nbt).await;
+            self.merchant.trading.lock().await.write_nbt(nbt);
+        })
+    }
+
+    fn read_nbt_non_mut<'a>(&'a self, nbt: &'a NbtCompound) -> NbtFuture<'a, ()> {
+        Box::pin(async move {
+            self.mob_entity.living_entity.read_nbt_non_mut(nbt).await;
+            self.merchant.trading.lock().await.read_nbt(nbt);
+            self.data_dirty.store(true, Ordering::Relaxed);
+        })
+    }
+}
+
+impl Mob for VillagerEntity {
+    fn get_mob_entity(&self) -> &MobEntity {
+        &self.mob_entity
+    }
+
+    fn mob_tick<'a>(&'a self, _server: &'a Server) -> EntityBaseFuture<'a, ()> {
+        Box::pin(async move {
+            let entity = &self.mob_entity.living_entity.entity;
+            let world = &entity.world;
+            if (entity.age.load(Ordering::Relaxed) + entity.entity_id) % JOB_SITE_CHECK_INTERVAL
+                == 0
+            {
+                self.tick_job_site(world).await;
+            }
+
+            let (world_age, time_of_day) = {
+                let level_time = world.level_time.lock().await;
+                (level_time.world_age, level_time.time_of_day)
+            };
+            let (leveled_up, restocked) = {
+                let mut trading = self.merchant.trading.lock().await;
+                (
+                    trading.try_level_up(),
+                    trading.try_restock(world_age, time_of_day),
+                )
+            };
+            if leveled_up {
+                self.data_dirty.store(true, Ordering::Relaxed);
+                world
+                    .play_sound(
+                        Sound::EntityVillagerYes,
+                        SoundCategory::Neutral,
+                        &entity.pos.load(),
+                    )
+                    .await;
+            }
+            if leveled_up || restocked {
+                self.merchant.send_offers().await;
+            }
+
+            if self.data_dirty.swap(false, Ordering::Relaxed) {
+                self.send_villager_data().await;
+            }
+        })
+    }
+
+    fn mob_interact<'a>(&'a self, player: &'a Arc<Player>) -> EntityBaseFuture<'a, bool> {
+        Box::pin(async move {
+            let entity = &self.mob_entity.living_entity.entity;
+            let profession = {
+                let mut trading = self.merchant.trading.lock().await;
+                trading
+                    .offers_for_trading()
+                    .is_some()
+                    .then_some(trading.profession)
+            };
+            let Some(profession) = profession else {
+                entity
+                    .world
+                    .play_sound(
+                        Sound::EntityVillagerNo,
+                        SoundCategory::Neutral,
+                        &entity.pos.load(),
+                    )
+                    .await;
+                return true;
+            };
+
+            let busy = self
+                .merchant
+                .customer
+                .lock()
+                .await
+                .as_ref()
+                .is_some_and(|(customer, _)| !Arc::ptr_eq(customer, player));
+            if busy {
+                return true;
+            }
+
+            let factory = MerchantScreenFactory {
+                merchant: self.merchant.clone(),
+                title: TextComponent::translate(
+                    format!("entity.minecraft.villager.{}", profession.name()),
+                    &[],
+                ),
+            };
+            if let Some(sync_id) = player.open_handled_screen(&factory).await {
+                *self.merchant.customer.lock().await = Some((player.clone(), sync_id));
+                self.merchant.send_offers().await;
+            }
+            true
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_data::item::Item;
+    use pumpkin_data::sound::Sound;
+    use pumpkin_inventory::merchant::trade_offer::TradeOffer;
+    use pumpkin_protocol::codec::var_int::VarInt;
+    use pumpkin_protocol::java::server::play::{SSelectTrade, SlotActionType};
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_util::math::vector3::Vector3;
+    use pumpkin_world::item::ItemStack;
+    use uuid::Uuid;
+
+    use super::profession::VillagerProfession;
+    use super::trades::trades_for;
+    use super::{VillagerEntity, VillagerTrading};
+    use crate::entity::Entity;
+    use crate::entity::mob::Mob;
+    use crate::entity::player::Player;
+    use crate::net::ClientPlatform;
+    use crate::server::Server;
+
+    #[test]
+    fn claiming_composter_makes_farmer() {
+        let mut trading = VillagerTrading::default();
+        assert!(trading.offers_for_trading().is_none());
+
+        assert!(trading.claim_job_site(BlockPos::new(1, 64, 1), &Block::COMPOSTER));
+        assert_eq!(trading.profession, VillagerProfession::Farmer);
+
+        let offers = trading.offers_for_trading().expect("farmers can trade");
+        assert!(!offers.is_empty());
+        let novice_trades = trades_for(VillagerProfession::Farmer, 1);
+        for offer in offers {
+            assert!(novice_trades.iter().any(|trade| {
+                trade.buy.0.id == offer.first_buy.item.id && trade.sell.0.id == offer.sell.item.id
+            }));
+        }
+
+        // A farmer won't take another profession's job site
+        let mut other = VillagerTrading {
+            profession: VillagerProfession::Farmer,
+            ..Default::default()
+        };
+        assert!(!other.claim_job_site(BlockPos::new(2, 64, 2), &Block::LECTERN));
+    }
+
+    #[test]
+    fn professions_have_their_own_work_sound() {
+        assert_eq!(
+            VillagerProfession::Librarian.work_sound(),
+            Some(Sound::EntityVillagerWorkLibrarian)
+        );
+        assert_eq!(
+            VillagerProfession::Farmer.work_sound(),
+            Some(Sound::EntityVillagerWorkFarmer)
+        );
+        assert_eq!(VillagerProfession::Nitwit.work_sound(), None);
+    }
+
+    async fn count(player: &Player, item: &Item) -> u32 {
+        let mut count = 0;
+        for stack in &player.inventory.main_inventory {
+            let stack = stack.lock().await;
+            if stack.item.id == item.id {
+                count += u32::from(stack.item_count);
+            }
+        }
+        count
+    }
+
+    async fn select_trade(player: &Player) {
+        let ClientPlatform::Java(client) = &player.client else {
+            unreachable!();
+        };
+        client
+            .handle_select_trade(
+                player,
+                SSelectTrade {
+                    selected_slot: VarInt(0),
+                },
+            )
+            .await;
+    }
+
+    /// Shift clicks the result, like the client does to trade.
+    async fn take_result(player: &Player) {
+        let screen_handler = player.current_screen_handler.lock().await.clone();
+        screen_handler
+            .lock()
+            .await
+            .on_slot_click(2, 0, SlotActionType::QuickMove, player)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn trading_pays_for_the_result() {
+        let (server, _world_dir) = Server::new_for_test().await;
+        let world = server.worlds.read().await[0].clone();
+        let (player, _packets) = Player::new_for_test(world.clone(), "Trader").await;
+        let villager = VillagerEntity::make(Entity::new(
+            Uuid::new_v4(),
+            world,
+            Vector3::new(0.0, 100.0, 0.0),
+            &EntityType::VILLAGER,
+            false,
+        ))
+        .await;
+        {
+            let mut trading = villager.merchant.trading.lock().await;
+            trading.profession = VillagerProfession::Farmer;
+            trading.offers = vec![TradeOffer::new(
+                ItemStack::new(20, &Item::WHEAT),
+                ItemStack::EMPTY.clone(),
+                ItemStack::new(1, &Item::EMERALD),
+                2,
+                2,
+                0.05,
+            )];
+        }
+
+        player
+            .inventory
+            .insert_stack_anywhere(&mut ItemStack::new(40, &Item::WHEAT))
+            .await;
+        assert!(villager.mob_interact(&player).await);
+        select_trade(&player).await;
+        take_result(&player).await;
+        assert_eq!(count(&player, &Item::EMERALD).await, 2);
+        assert_eq!(count(&player, &Item::WHEAT).await, 0);
+        {
+            let trading = villager.merchant.trading.lock().await;
+            assert_eq!(trading.offers[0].uses, 2);
+            assert_eq!(trading.experience, 4);
+        }
+
+        // The result still shows, but the offer sold out before it was taken
+        player
+            .inventory
+            .insert_stack_anywhere(&mut ItemStack::new(20, &Item::WHEAT))
+            .await;
+        villager.merchant.trading.lock().await.offers[0].uses = 0;
+        select_trade(&player).await;
+        villager.merchant.trading.lock().await.offers[0].uses = 2;
+        take_result(&player).await;
+        assert_eq!(count(&player, &Item::EMERALD).await, 2);
+        assert_eq!(villager.merchant.trading.lock().await.offers[0].uses, 2);
+    }
+}
@@ -0,0 +1,185 @@
+use pumpkin_data::Block;
+use pumpkin_data::sound::Sound;
+
+/// The profession of a villager, the order matches the vanilla registry so `registry_id` can be
+/// sent to the client as is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum VillagerProfession {
+    #[default]
+    None,
+    Armorer,
+    Butcher,
+    Cartographer,
+    Cleric,
+    Farmer,
+    Fisherman,
+    Fletcher,
+    Leatherworker,
+    Librarian,
+    Mason,
+    Nitwit,
+    Shepherd,
+    Toolsmith,
+    Weaponsmith,
+}
+
+impl VillagerProfession {
+    pub const ALL: [Self; 15] = [
+        Self::None,
+        Self::Armorer,
+        Self::Butcher,
+        Self::Cartographer,
+        Self::Cleric,
+        Self::Farmer,
+        Self::Fisherman,
+        Self::Fletcher,
+        Self::Leatherworker,
+        Self::Librarian,
+        Self::Mason,
+        Self::Nitwit,
+        Self::Shepherd,
+        Self::Toolsmith,
+        Self::Weaponsmith,
+    ];
+
+    /// Returns the profession a villager gains by claiming `block` as its job site.
+    #[must_use]
+    pub fn from_job_site(block: &Block) -> Option<Self> {
+        let profession = match block.id {
+            id if id == Block::BLAST_FURNACE.id => Self::Armorer,
+            id if id == Block::SMOKER.id => Self::Butcher,
+            id if id == Block::CARTOGRAPHY_TABLE.id => Self::Cartographer,
+            id if id == Block::BREWING_STAND.id => Self::Cleric,
+            id if id == Block::COMPOSTER.id => Self::Farmer,
+            id if id == Block::BARREL.id => Self::Fisherman,
+            id if id == Block::FLETCHING_TABLE.id => Self::Fletcher,
+            id if id == Block::CAULDRON.id
+                || id == Block::WATER_CAULDRON.id
+                || id == Block::LAVA_CAULDRON.id
+                || id == Block::POWDER_SNOW_CAULDRON.id =>
+            {
+                Self::Leatherworker
+            }
+            id if id == Block::LECTERN.id => Self::Librarian,
+            id if id == Block::STONECUTTER.id => Self::Mason,
+            id if id == Block::LOOM.id => Self::Shepherd,
+            id if id == Block::SMITHING_TABLE.id => Self::Toolsmith,
+            id if id == Block::GRINDSTONE.id => Self::Weaponsmith,
+            _ => return None,
+        };
+        Some(profession)
+    }
+
+    /// Professions that can be traded with and gain experience.
+    #[must_use]
+    pub const fn can_trade(self) -> bool {
+        !matches!(self, Self::None | Self::Nitwit)
+    }
+
+    /// The sound played when working at the job site, professions without one are silent.
+    #[must_use]
+    pub const fn work_sound(self) -> Option<Sound> {
+        let sound = match self {
+            Self::None | Self::Nitwit => return None,
+            Self::Armorer => Sound::EntityVillagerWorkArmorer,
+            Self::Butcher => Sound::EntityVillagerWorkButcher,
+            Self::Cartographer => Sound::EntityVillagerWorkCartographer,
+            Self::Cleric => Sound::EntityVillagerWorkCleric,
+            Self::Farmer => Sound::EntityVillagerWorkFarmer,
+            Self::Fisherman => Sound::EntityVillagerWorkFisherman,
+            Self::Fletcher => Sound::EntityVillagerWorkFletcher,
+            Self::Leatherworker => Sound::EntityVillagerWorkLeatherworker,
+            Self::Librarian => Sound::EntityVillagerWorkLibrarian,
+            Self::Mason => Sound::EntityVillagerWorkMason,
+            Self::Shepherd => Sound::EntityVillagerWorkShepherd,
+            Self::Toolsmith => Sound::EntityVillagerWorkToolsmith,
+            Self::Weaponsmith => Sound::EntityVillagerWorkWeaponsmith,
+        };
+        Some(sound)
+    }
+
+    #[must_use]
+    pub const fn registry_id(self) -> i32 {
+        self as i32
+    }
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Armorer => "armorer",
+            Self::Butcher => "butcher",
+            Self::Cartographer => "cartographer",
+            Self::Cleric => "cleric",
+            Self::Farmer => "farmer",
+            Self::Fisherman => "fisherman",
+            Self::Fletcher => "fletcher",
+            Self::Leatherworker => "leatherworker",
+            Self::Librarian => "librarian",
+            Self::Mason => "mason",
+            Self::Nitwit => "nitwit",
+            Self::Shepherd => "shepherd",
+            Self::Toolsmith => "toolsmith",
+            Self::Weaponsmith => "weaponsmith",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL
+            .into_iter()
+            .find(|profession| profession.name() == name)
+    }
+}
+
+/// The biome variant of a villager, which only changes its looks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum VillagerType {
+    Desert,
+    Jungle,
+    #[default]
+    Plains,
+    Savanna,
+    Snow,
+    Swamp,
+    Taiga,
+}
+
+impl VillagerType {
+    pub const ALL: [Self; 7] = [
+        Self::Desert,
+        Self::Jungle,
+        Self::Plains,
+        Self::Savanna,
+        Self::Snow,
+        Self::Swamp,
+        Self::Taiga,
+    ];
+
+    #[must_use]
+    pub const fn registry_id(self) -> i32 {
+        self as i32
+    }
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Desert => "desert",
+            Self::Jungle => "jungle",
+            Self::Plains => "plains",
+            Self::Savanna => "savanna",
+            Self::Snow => "snow",
+            Self::Swamp => "swamp",
+            Self::Taiga => "taiga",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL
+            .into_iter()
+            .find(|villager_type| villager_type.name() == name)
+    }
+}
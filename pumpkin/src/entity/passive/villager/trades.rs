@@ -0,0 +1,404 @@
+use pumpkin_data::item::Item;
+use pumpkin_inventory::merchant::trade_offer::TradeOffer;
+use pumpkin_world::item::ItemStack;
+use rand::seq::IndexedRandom;
+
+use super::profession::VillagerProfession;
+
+/// How many new offers a villager rolls every time it reaches a level.
+const OFFERS_PER_LEVEL: usize = 2;
+
+const LOW_TIER_PRICE_MULTIPLIER: f32 = 0.05;
+const HIGH_TIER_PRICE_MULTIPLIER: f32 = 0.2;
+
+/// A trade a villager can roll when reaching a level, see VillagerTrades.java.
+///
+/// Only trades with fixed items are listed, enchanted, dyed and map trades are not supported yet.
+pub struct TradeTemplate {
+    pub buy: (&'static Item, u8),
+    pub buy_b: Option<(&'static Item, u8)>,
+    pub sell: (&'static Item, u8),
+    pub max_uses: i32,
+    pub experience: i32,
+    pub price_multiplier: f32,
+}
+
+impl TradeTemplate {
+    #[must_use]
+    pub fn create_offer(&self) -> TradeOffer {
+        TradeOffer::new(
+            ItemStack::new(self.buy.1, self.buy.0),
+            self.buy_b
+                .map_or(ItemStack::EMPTY.clone(), |(item, count)| {
+                    ItemStack::new(count, item)
+                }),
+            ItemStack::new(self.sell.1, self.sell.0),
+            self.max_uses,
+            self.experience,
+            self.price_multiplier,
+        )
+    }
+}
+
+/// EmeraldForItems
+const fn buy(item: &'static Item, count: u8, max_uses: i32, experience: i32) -> TradeTemplate {
+    TradeTemplate {
+        buy: (item, count),
+        buy_b: None,
+        sell: (&Item::EMERALD, 1),
+        max_uses,
+        experience,
+        price_multiplier: LOW_TIER_PRICE_MULTIPLIER,
+    }
+}
+
+/// ItemsForEmeralds
+const fn sell(
+    item: &'static Item,
+    emeralds: u8,
+    count: u8,
+    max_uses: i32,
+    experience: i32,
+) -> TradeTemplate {
+    TradeTemplate {
+        buy: (&Item::EMERALD, emeralds),
+        buy_b: None,
+        sell: (item, count),
+        max_uses,
+        experience,
+        price_multiplier: LOW_TIER_PRICE_MULTIPLIER,
+    }
+}
+
+/// ItemsForEmeralds for tools and armor, which get expensive faster
+const fn sell_gear(item: &'static Item, emeralds: u8, experience: i32) -> TradeTemplate {
+    TradeTemplate {
+        buy: (&Item::EMERALD, emeralds),
+        buy_b: None,
+        sell: (item, 1),
+        max_uses: 12,
+        experience,
+        price_multiplier: HIGH_TIER_PRICE_MULTIPLIER,
+    }
+}
+
+/// ItemsAndEmeraldsToItems
+const fn process(
+    item: &'static Item,
+    count: u8,
+    result: &'static Item,
+    max_uses: i32,
+    experience: i32,
+) -> TradeTemplate {
+    TradeTemplate {
+        buy: (item, count),
+        buy_b: Some((&Item::EMERALD, 1)),
+        sell: (result, count),
+        max_uses,
+        experience,
+        price_multiplier: LOW_TIER_PRICE_MULTIPLIER,
+    }
+}
+
+static FARMER: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::WHEAT, 20, 16, 2),
+        buy(&Item::POTATO, 26, 16, 2),
+        buy(&Item::CARROT, 22, 16, 2),
+        buy(&Item::BEETROOT, 15, 16, 2),
+        sell(&Item::BREAD, 1, 6, 16, 1),
+    ],
+    &[
+        buy(&Item::PUMPKIN, 6, 12, 10),
+        sell(&Item::PUMPKIN_PIE, 1, 4, 12, 5),
+        sell(&Item::APPLE, 1, 4, 16, 5),
+    ],
+    &[
+        sell(&Item::COOKIE, 3, 18, 12, 10),
+        buy(&Item::MELON, 4, 12, 20),
+    ],
+    &[sell(&Item::CAKE, 1, 1, 12, 15)],
+    &[
+        sell(&Item::GOLDEN_CARROT, 3, 3, 12, 30),
+        sell(&Item::GLISTERING_MELON_SLICE, 4, 3, 12, 30),
+    ],
+];
+
+static FISHERMAN: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::STRING, 20, 16, 2),
+        buy(&Item::COAL, 10, 16, 2),
+        process(&Item::COD, 6, &Item::COOKED_COD, 16, 1),
+        sell(&Item::COD_BUCKET, 3, 1, 16, 1),
+    ],
+    &[
+        buy(&Item::COD, 15, 16, 10),
+        process(&Item::SALMON, 6, &Item::COOKED_SALMON, 16, 5),
+        sell(&Item::CAMPFIRE, 2, 1, 12, 5),
+    ],
+    &[buy(&Item::SALMON, 13, 16, 20)],
+    &[buy(&Item::TROPICAL_FISH, 6, 12, 30)],
+    &[buy(&Item::PUFFERFISH, 4, 12, 30)],
+];
+
+static SHEPHERD: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::WHITE_WOOL, 18, 16, 2),
+        buy(&Item::BROWN_WOOL, 18, 16, 2),
+        buy(&Item::BLACK_WOOL, 18, 16, 2),
+        buy(&Item::GRAY_WOOL, 18, 16, 2),
+        sell(&Item::SHEARS, 2, 1, 12, 1),
+    ],
+    &[
+        buy(&Item::WHITE_DYE, 12, 16, 10),
+        buy(&Item::GRAY_DYE, 12, 16, 10),
+        buy(&Item::BLACK_DYE, 12, 16, 10),
+        buy(&Item::LIGHT_BLUE_DYE, 12, 16, 10),
+        buy(&Item::LIME_DYE, 12, 16, 10),
+        sell(&Item::WHITE_WOOL, 1, 1, 16, 5),
+        sell(&Item::WHITE_CARPET, 1, 4, 16, 5),
+    ],
+    &[
+        buy(&Item::YELLOW_DYE, 12, 16, 20),
+        sell(&Item::WHITE_BED, 3, 1, 12, 10),
+    ],
+    &[
+        buy(&Item::BROWN_DYE, 12, 16, 30),
+        sell(&Item::WHITE_BANNER, 3, 1, 12, 15),
+    ],
+    &[sell(&Item::PAINTING, 2, 3, 12, 30)],
+];
+
+static FLETCHER: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::STICK, 32, 16, 2),
+        sell(&Item::ARROW, 1, 16, 12, 1),
+        process(&Item::GRAVEL, 10, &Item::FLINT, 12, 1),
+    ],
+    &[buy(&Item::FLINT, 26, 12, 10), sell_gear(&Item::BOW, 2, 5)],
+    &[
+        buy(&Item::STRING, 14, 16, 20),
+        sell_gear(&Item::CROSSBOW, 3, 10),
+    ],
+    &[buy(&Item::FEATHER, 24, 16, 30)],
+    &[buy(&Item::TRIPWIRE_HOOK, 8, 12, 30)],
+];
+
+static LIBRARIAN: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::PAPER, 24, 16, 2),
+        sell(&Item::BOOKSHELF, 9, 1, 12, 1),
+    ],
+    &[
+        buy(&Item::BOOK, 4, 12, 10),
+        sell(&Item::LANTERN, 1, 1, 12, 5),
+    ],
+    &[
+        buy(&Item::INK_SAC, 5, 12, 20),
+        sell(&Item::GLASS, 1, 4, 12, 10),
+    ],
+    &[
+        buy(&Item::WRITABLE_BOOK, 2, 12, 30),
+        sell(&Item::CLOCK, 5, 1, 12, 15),
+        sell(&Item::COMPASS, 4, 1, 12, 15),
+    ],
+    &[sell(&Item::NAME_TAG, 20, 1, 12, 30)],
+];
+
+static CARTOGRAPHER: [&[TradeTemplate]; 5] = [
+    &[buy(&Item::PAPER, 24, 16, 2), sell(&Item::MAP, 7, 1, 12, 1)],
+    &[buy(&Item::GLASS_PANE, 11, 16, 10)],
+    &[buy(&Item::COMPASS, 1, 12, 20)],
+    &[
+        sell(&Item::ITEM_FRAME, 7, 1, 12, 15),
+        sell(&Item::WHITE_BANNER, 3, 1, 12, 15),
+    ],
+    &[sell(&Item::GLOBE_BANNER_PATTERN, 8, 1, 12, 30)],
+];
+
+static CLERIC: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::ROTTEN_FLESH, 32, 16, 2),
+        sell(&Item::REDSTONE, 1, 2, 12, 1),
+    ],
+    &[
+        buy(&Item::GOLD_INGOT, 3, 12, 10),
+        sell(&Item::LAPIS_LAZULI, 1, 1, 12, 5),
+    ],
+    &[
+        buy(&Item::RABBIT_FOOT, 2, 12, 20),
+        sell(&Item::GLOWSTONE, 4, 1, 12, 10),
+    ],
+    &[
+        buy(&Item::TURTLE_SCUTE, 4, 12, 30),
+        buy(&Item::GLASS_BOTTLE, 9, 12, 30),
+        sell(&Item::ENDER_PEARL, 5, 1, 12, 15),
+    ],
+    &[
+        buy(&Item::NETHER_WART, 22, 12, 30),
+        sell(&Item::EXPERIENCE_BOTTLE, 3, 1, 12, 30),
+    ],
+];
+
+static ARMORER: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::COAL, 15, 16, 2),
+        sell_gear(&Item::IRON_LEGGINGS, 7, 1),
+        sell_gear(&Item::IRON_BOOTS, 4, 1),
+        sell_gear(&Item::IRON_HELMET, 5, 1),
+        sell_gear(&Item::IRON_CHESTPLATE, 9, 1),
+    ],
+    &[
+        buy(&Item::IRON_INGOT, 4, 12, 10),
+        sell_gear(&Item::BELL, 36, 5),
+        sell_gear(&Item::CHAINMAIL_BOOTS, 1, 5),
+        sell_gear(&Item::CHAINMAIL_LEGGINGS, 3, 5),
+    ],
+    &[
+        buy(&Item::LAVA_BUCKET, 1, 12, 20),
+        buy(&Item::DIAMOND, 1, 12, 20),
+        sell_gear(&Item::CHAINMAIL_HELMET, 1, 10),
+        sell_gear(&Item::CHAINMAIL_CHESTPLATE, 4, 10),
+        sell_gear(&Item::SHIELD, 5, 10),
+    ],
+    &[],
+    &[],
+];
+
+static BUTCHER: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::CHICKEN, 14, 16, 2),
+        buy(&Item::PORKCHOP, 7, 16, 2),
+        buy(&Item::RABBIT, 4, 16, 2),
+        sell(&Item::RABBIT_STEW, 1, 1, 12, 1),
+    ],
+    &[
+        buy(&Item::COAL, 15, 16, 2),
+        sell(&Item::COOKED_PORKCHOP, 1, 5, 16, 5),
+        sell(&Item::COOKED_CHICKEN, 1, 8, 16, 5),
+    ],
+    &[buy(&Item::MUTTON, 7, 16, 20), buy(&Item::BEEF, 10, 16, 20)],
+    &[buy(&Item::DRIED_KELP_BLOCK, 10, 12, 30)],
+    &[buy(&Item::SWEET_BERRIES, 10, 12, 30)],
+];
+
+static LEATHERWORKER: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::LEATHER, 6, 16, 2),
+        sell_gear(&Item::LEATHER_LEGGINGS, 3, 1),
+        sell_gear(&Item::LEATHER_CHESTPLATE, 7, 1),
+    ],
+    &[
+        buy(&Item::FLINT, 26, 12, 10),
+        sell_gear(&Item::LEATHER_HELMET, 5, 5),
+        sell_gear(&Item::LEATHER_BOOTS, 4, 5),
+    ],
+    &[
+        buy(&Item::RABBIT_HIDE, 9, 12, 20),
+        sell_gear(&Item::LEATHER_CHESTPLATE, 7, 10),
+    ],
+    &[
+        buy(&Item::TURTLE_SCUTE, 4, 12, 30),
+        sell_gear(&Item::LEATHER_HORSE_ARMOR, 6, 15),
+    ],
+    &[
+        sell_gear(&Item::SADDLE, 6, 30),
+        sell_gear(&Item::LEATHER_HELMET, 5, 30),
+    ],
+];
+
+static MASON: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::CLAY_BALL, 10, 16, 2),
+        sell(&Item::BRICK, 1, 10, 16, 1),
+    ],
+    &[
+        buy(&Item::STONE, 20, 16, 10),
+        sell(&Item::CHISELED_STONE_BRICKS, 1, 4, 16, 5),
+    ],
+    &[
+        buy(&Item::GRANITE, 16, 16, 20),
+        buy(&Item::ANDESITE, 16, 16, 20),
+        buy(&Item::DIORITE, 16, 16, 20),
+        sell(&Item::DRIPSTONE_BLOCK, 1, 4, 16, 10),
+        sell(&Item::POLISHED_ANDESITE, 1, 4, 16, 10),
+    ],
+    &[
+        buy(&Item::QUARTZ, 12, 12, 30),
+        sell(&Item::ORANGE_TERRACOTTA, 1, 1, 12, 15),
+        sell(&Item::WHITE_GLAZED_TERRACOTTA, 1, 1, 12, 15),
+    ],
+    &[
+        sell(&Item::QUARTZ_PILLAR, 1, 1, 12, 30),
+        sell(&Item::QUARTZ_BLOCK, 1, 1, 12, 30),
+    ],
+];
+
+static TOOLSMITH: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::COAL, 15, 16, 2),
+        sell_gear(&Item::STONE_AXE, 1, 1),
+        sell_gear(&Item::STONE_SHOVEL, 1, 1),
+        sell_gear(&Item::STONE_PICKAXE, 1, 1),
+        sell_gear(&Item::STONE_HOE, 1, 1),
+    ],
+    &[
+        buy(&Item::IRON_INGOT, 4, 12, 10),
+        sell_gear(&Item::BELL, 36, 5),
+    ],
+    &[
+        buy(&Item::FLINT, 30, 12, 20),
+        sell_gear(&Item::DIAMOND_HOE, 4, 10),
+    ],
+    &[buy(&Item::DIAMOND, 1, 12, 30)],
+    &[],
+];
+
+static WEAPONSMITH: [&[TradeTemplate]; 5] = [
+    &[
+        buy(&Item::COAL, 15, 16, 2),
+        sell_gear(&Item::IRON_AXE, 3, 1),
+    ],
+    &[
+        buy(&Item::IRON_INGOT, 4, 12, 10),
+        sell_gear(&Item::BELL, 36, 5),
+    ],
+    &[buy(&Item::FLINT, 24, 12, 20)],
+    &[buy(&Item::DIAMOND, 1, 12, 30)],
+    &[],
+];
+
+/// Returns the trades `profession` can roll when reaching `level` (1 to 5).
+#[must_use]
+pub fn trades_for(profession: VillagerProfession, level: i32) -> &'static [TradeTemplate] {
+    let table = match profession {
+        VillagerProfession::None | VillagerProfession::Nitwit => return &[],
+        VillagerProfession::Armorer => &ARMORER,
+        VillagerProfession::Butcher => &BUTCHER,
+        VillagerProfession::Cartographer => &CARTOGRAPHER,
+        VillagerProfession::Cleric => &CLERIC,
+        VillagerProfession::Farmer => &FARMER,
+        VillagerProfession::Fisherman => &FISHERMAN,
+        VillagerProfession::Fletcher => &FLETCHER,
+        VillagerProfession::Leatherworker => &LEATHERWORKER,
+        VillagerProfession::Librarian => &LIBRARIAN,
+        VillagerProfession::Mason => &MASON,
+        VillagerProfession::Shepherd => &SHEPHERD,
+        VillagerProfession::Toolsmith => &TOOLSMITH,
+        VillagerProfession::Weaponsmith => &WEAPONSMITH,
+    };
+    usize::try_from(level - 1)
+        .ok()
+        .and_then(|index| table.get(index))
+        .copied()
+        .unwrap_or(&[])
+}
+
+/// Rolls the offers a villager unlocks when reaching `level`.
+#[must_use]
+pub fn roll_offers(profession: VillagerProfession, level: i32) -> Vec<TradeOffer> {
+    trades_for(profession, level)
+        .choose_multiple(&mut rand::rng(), OFFERS_PER_LEVEL)
+        .map(TradeTemplate::create_offer)
+        .collect()
+}
@@ -16,27 +16,34 @@ use pumpkin_data::meta_data_type::MetaDataType;
 use pumpkin_data::tracked_data::TrackedData;
 use pumpkin_inventory::player::ender_chest_inventory::EnderChestInventory;
 use pumpkin_protocol::bedrock::client::level_chunk::CLevelChunk;
-use pumpkin_protocol::bedrock::client::set_time::CSetTime;
+use pumpkin_protocol::bedrock::client::play_sound::CPlaySound;
+use pumpkin_protocol::bedrock::client::stop_sound::CStopSound as CStopSoundBedrock;
 use pumpkin_protocol::bedrock::client::update_abilities::{
     Ability, AbilityLayer, CUpdateAbilities,
 };
 use pumpkin_protocol::bedrock::server::text::SText;
+use pumpkin_protocol::bedrock::sound::bedrock_sound_name;
 use pumpkin_protocol::codec::item_stack_seralizer::ItemStackSerializer;
 use pumpkin_world::chunk::{ChunkData, ChunkEntityData};
+use pumpkin_world::data::player_stats::{CustomStat, PlayerStats, Stat};
 use pumpkin_world::inventory::Inventory;
 use tokio::sync::{Mutex, RwLock};
 use tokio::task::JoinHandle;
 use uuid::Uuid;
 
+use pumpkin_data::attributes::Attributes;
 use pumpkin_data::damage::DamageType;
-use pumpkin_data::data_component_impl::{AttributeModifiersImpl, Operation};
+use pumpkin_data::data_component::DataComponent;
+use pumpkin_data::data_component_impl::{DataComponentImpl, WrittenBookContentImpl};
 use pumpkin_data::data_component_impl::{EquipmentSlot, EquippableImpl};
+use pumpkin_data::data_component_impl::{ToolImpl, read_data};
 use pumpkin_data::effect::StatusEffect;
 use pumpkin_data::entity::{EntityPose, EntityStatus, EntityType};
+use pumpkin_data::item::Item;
 use pumpkin_data::particle::Particle;
 use pumpkin_data::sound::{Sound, SoundCategory};
 use pumpkin_data::tag::Taggable;
-use pumpkin_data::{Block, BlockState, tag};
+use pumpkin_data::{Block, BlockState, Enchantment, tag};
 use pumpkin_inventory::player::{
     player_inventory::PlayerInventory, player_screen_handler::PlayerScreenHandler,
 };
@@ -50,14 +57,15 @@ use pumpkin_nbt::tag::NbtTag;
 use pumpkin_protocol::IdOr;
 use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::java::client::play::{
-    Animation, CAcknowledgeBlockChange, CActionBar, CChangeDifficulty, CChunkBatchEnd,
-    CChunkBatchStart, CChunkData, CCloseContainer, CCombatDeath, CDisguisedChatMessage,
-    CEntityAnimation, CEntityPositionSync, CGameEvent, CKeepAlive, COpenScreen, CParticle,
-    CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition, CPlayerSpawnPosition, CRespawn,
-    CSetContainerContent, CSetContainerProperty, CSetContainerSlot, CSetCursorItem, CSetEquipment,
-    CSetExperience, CSetHealth, CSetPlayerInventory, CSetSelectedSlot, CSoundEffect, CStopSound,
-    CSubtitle, CSystemChatMessage, CTitleAnimation, CTitleText, CUnloadChunk, CUpdateMobEffect,
-    CUpdateTime, GameEvent, Metadata, PlayerAction, PlayerInfoFlags, PreviousMessage,
+    Animation, CAcknowledgeBlockChange, CActionBar, CAwardStats, CChangeDifficulty, CChunkBatchEnd,
+    CChunkBatchStart, CChunkData, CCloseContainer, CCombatDeath, CCooldown, CDisguisedChatMessage,
+    CEntityAnimation, CEntityPositionSync, CGameEvent, CKeepAlive, COpenBook, COpenScreen,
+    CParticle, CPlayCookieRequest, CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition,
+    CPlayerSpawnPosition, CRespawn, CSetContainerContent, CSetContainerProperty, CSetContainerSlot,
+    CSetCursorItem, CSetEquipment, CSetExperience, CSetHealth, CSetPlayerInventory,
+    CSetSelectedSlot, CSoundEffect, CStopSound, CStoreCookie, CSubtitle, CSystemChatMessage,
+    CTitleAnimation, CTitleText, CUnloadChunk, CUpdateMobEffect, GameEvent, Metadata, PlayerAction,
+    PlayerInfoFlags, PreviousMessage, Statistic,
 };
 use pumpkin_protocol::java::server::play::SClickSlot;
 use pumpkin_util::math::{
@@ -75,24 +83,32 @@ use pumpkin_world::item::ItemStack;
 use pumpkin_world::level::{Level, SyncChunk, SyncEntityChunk};
 
 use crate::block::blocks::bed::BedBlock;
+use crate::block::blocks::lodestone;
 use crate::command::client_suggestions;
 use crate::command::dispatcher::CommandDispatcher;
 use crate::data::op_data::OPERATOR_CONFIG;
 use crate::entity::{EntityBaseFuture, NbtFuture, TeleportFuture};
+use crate::net::chunk_send_rate::ChunkSendRate;
+use crate::net::cookie::{COOKIE_RESPONSE_TIMEOUT, MAX_COOKIE_SIZE};
 use crate::net::{ClientPlatform, GameProfile};
 use crate::net::{DisconnectReason, PlayerConfig};
 use crate::plugin::player::player_change_world::PlayerChangeWorldEvent;
 use crate::plugin::player::player_gamemode_change::PlayerGamemodeChangeEvent;
 use crate::plugin::player::player_teleport::PlayerTeleportEvent;
 use crate::server::Server;
+use crate::server::audit_log::AuditAction;
 use crate::world::World;
+use crate::world::tracking::TrackedEntities;
 use crate::{PERMISSION_MANAGER, block};
 
 use crate::advancement::PlayerAdvancementTracker;
 use super::combat::{self, AttackType, player_attack_sound};
+use super::experience_orb::ExperienceOrbEntity;
 use super::hunger::HungerManager;
 use super::item::ItemEntity;
+use super::item_cooldown::ItemCooldownManager;
 use super::living::LivingEntity;
+use super::movement_validator::MovementValidator;
 use super::{Entity, EntityBase, NBTStorage, NBTStorageInit};
 use pumpkin_data::potion::Effect;
 use pumpkin_world::chunk_system::ChunkLoading;
@@ -131,7 +147,7 @@ impl Ord for HeapNode {
 }
 
 pub struct ChunkManager {
-    chunks_per_tick: usize,
+    send_rate: ChunkSendRate,
     center: Vector2<i32>,
     view_distance: u8,
     chunk_listener: Receiver<(Vector2<i32>, SyncChunk)>,
@@ -145,12 +161,9 @@ impl ChunkManager {
     pub const NOTCHIAN_BATCHES_WITHOUT_ACK_UNTIL_PAUSE: u8 = 10;
 
     #[must_use]
-    pub fn new(
-        chunks_per_tick: usize,
-        chunk_listener: Receiver<(Vector2<i32>, SyncChunk)>,
-    ) -> Self {
+    pub fn new(chunks_per_tick: f32, chunk_listener: Receiver<(Vector2<i32>, SyncChunk)>) -> Self {
         Self {
-            chunks_per_tick,
+            send_rate: ChunkSendRate::new(chunks_per_tick),
             center: Vector2::<i32>::new(0, 0),
             view_distance: 0,
             chunk_listener,
@@ -179,12 +192,14 @@ impl ChunkManager {
         // log::debug!("chunk_sent size {}", self.chunk_sent.len());
     }
 
+    /// Moves the area of chunks sent to the client, returns the chunks which were sent or queued
+    /// before but are now out of view, so the client has to forget them.
     pub fn update_center_and_view_distance(
         &mut self,
         center: Vector2<i32>,
         mut view_distance: u8,
         level: &Arc<Level>,
-    ) {
+    ) -> Vec<Vector2<i32>> {
         view_distance += 1;
         let old_center = self.center;
         let old_view_distance = self.view_distance;
@@ -208,13 +223,7 @@ impl ChunkManager {
         let view_distance_i32 = i32::from(view_distance);
 
         let mut chunks_to_watch = Vec::new();
-        let mut chunks_to_unwatch = Vec::new();
-
-        for pos in &self.chunk_sent {
-            if (pos.x - center.x).abs().max((pos.y - center.y).abs()) > view_distance_i32 {
-                chunks_to_unwatch.push(*pos);
-            }
-        }
+        let chunks_to_unwatch = self.forget_chunks_out_of_view(center, view_distance_i32);
 
         let level_clone = level.clone();
         let chunks_to_unwatch_clone = chunks_to_unwatch.clone();
@@ -224,10 +233,6 @@ impl ChunkManager {
                 .await;
         });
 
-        self.chunk_sent.retain(|pos| {
-            (pos.x - center.x).abs().max((pos.y - center.y).abs()) <= view_distance_i32
-        });
-
         let mut new_queue = BinaryHeap::with_capacity(self.chunk_queue.len());
         for node in &self.chunk_queue {
             let dst = (node.1.x - center.x).abs().max((node.1.y - center.y).abs());
@@ -257,6 +262,25 @@ impl ChunkManager {
                 .mark_chunks_as_newly_watched(&chunks_to_watch)
                 .await;
         });
+
+        chunks_to_unwatch
+    }
+
+    /// Stops tracking the sent chunks further than `view_distance` from `center` and returns them.
+    fn forget_chunks_out_of_view(
+        &mut self,
+        center: Vector2<i32>,
+        view_distance: i32,
+    ) -> Vec<Vector2<i32>> {
+        let mut forgotten = Vec::new();
+        self.chunk_sent.retain(|pos| {
+            let in_view = (pos.x - center.x).abs().max((pos.y - center.y).abs()) <= view_distance;
+            if !in_view {
+                forgotten.push(*pos);
+            }
+            in_view
+        });
+        forgotten
     }
 
     pub fn clean_up(&mut self, level: &Arc<Level>) {
@@ -284,7 +308,13 @@ impl ChunkManager {
 
     pub fn handle_acknowledge(&mut self, chunks_per_tick: f32) {
         self.batches_sent_since_ack = BatchState::Count(0);
-        self.chunks_per_tick = chunks_per_tick.ceil() as usize;
+        self.send_rate.set_chunks_per_tick(chunks_per_tick);
+    }
+
+    /// The rate chunks are sent with, as requested by the client.
+    #[must_use]
+    pub const fn chunks_per_tick(&self) -> f32 {
+        self.send_rate.chunks_per_tick()
     }
 
     pub fn push_chunk(&mut self, position: Vector2<i32>, chunk: SyncChunk) {
@@ -310,8 +340,12 @@ impl ChunkManager {
         state_available && !self.chunk_queue.is_empty()
     }
 
+    /// Takes the chunks to send this tick, which are none if the send rate does not allow any yet.
     pub fn next_chunk(&mut self) -> Box<[SyncChunk]> {
-        let mut chunk_size = self.chunk_queue.len().min(self.chunks_per_tick);
+        let mut chunk_size = self.send_rate.take(self.chunk_queue.len());
+        if chunk_size == 0 {
+            return Box::default();
+        }
         let mut chunks = Vec::<Arc<RwLock<ChunkData>>>::with_capacity(chunk_size);
         while chunk_size > 0 {
             chunks.push(self.chunk_queue.pop().unwrap().2);
@@ -329,7 +363,10 @@ impl ChunkManager {
     }
 
     pub fn next_entity(&mut self) -> Box<[SyncEntityChunk]> {
-        let chunk_size = self.entity_chunk_queue.len().min(self.chunks_per_tick);
+        let chunk_size = self
+            .entity_chunk_queue
+            .len()
+            .min(self.send_rate.chunks_per_tick().ceil() as usize);
         let chunks: Vec<Arc<RwLock<ChunkEntityData>>> = self
             .entity_chunk_queue
             .drain(0..chunk_size)
@@ -435,6 +472,14 @@ pub struct Player {
     pub screen_handler_sync_handler: Arc<SyncHandler>,
     /// The player's advancement progress tracker.
     pub advancement_tracker: Mutex<PlayerAdvancementTracker>,
+    /// The player's statistics, like blocks mined and distance walked.
+    pub stats: Mutex<PlayerStats>,
+    /// The item groups the player can't use right now.
+    pub item_cooldowns: Mutex<ItemCooldownManager>,
+    /// Rejects moves which are impossible without flying.
+    pub movement_validator: Mutex<MovementValidator>,
+    /// The ids of the entities which are spawned for the client, see [`crate::world::tracking`].
+    pub tracked_entities: Mutex<TrackedEntities>,
 }
 
 impl Player {
@@ -523,7 +568,7 @@ impl Player {
             experience_points: AtomicI32::new(0),
             // Default to sending 16 chunks per tick.
             chunk_manager: Mutex::new(ChunkManager::new(
-                16,
+                16.0,
                 world.level.chunk_listener.add_global_chunk_listener(),
             )),
             last_sent_xp: AtomicI32::new(-1),
@@ -539,9 +584,39 @@ impl Player {
             screen_handler_listener: Arc::new(ScreenListener {}),
             screen_handler_sync_handler: Arc::new(SyncHandler::new()),
             advancement_tracker: Mutex::new(PlayerAdvancementTracker::new()),
+            stats: Mutex::new(PlayerStats::default()),
+            item_cooldowns: Mutex::new(ItemCooldownManager::default()),
+            movement_validator: Mutex::new(MovementValidator::default()),
+            tracked_entities: Mutex::new(TrackedEntities::default()),
         }
     }
 
+    /// A survival player in `world` for tests, on a client from
+    /// [`JavaClient::new_for_test`](crate::net::java::JavaClient::new_for_test). The player is not
+    /// added to the world.
+    #[cfg(test)]
+    pub async fn new_for_test(
+        world: Arc<World>,
+        name: &str,
+    ) -> (Arc<Self>, tokio::sync::mpsc::Receiver<bytes::Bytes>) {
+        let (client, packets) = crate::net::java::JavaClient::new_for_test().await;
+        let gameprofile = GameProfile {
+            id: crate::net::offline_uuid(name).unwrap(),
+            name: name.to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        };
+        let player = Self::new(
+            ClientPlatform::Java(Arc::new(client)),
+            gameprofile,
+            PlayerConfig::default(),
+            world,
+            GameMode::Survival,
+        )
+        .await;
+        (Arc::new(player), packets)
+    }
+
     /// Spawns a task associated with this player-client. All tasks spawned with this method are awaited
     /// when the client. This means tasks should complete in a reasonable amount of time or select
     /// on `Self::await_close_interrupt` to cancel the task when the client is closed
@@ -565,6 +640,8 @@ impl Player {
 
     /// Removes the [`Player`] out of the current [`World`].
     pub async fn remove(self: &Arc<Self>) {
+        self.get_entity().stop_riding().await;
+        self.get_entity().remove_all_passengers().await;
         let world = self.world();
         world.remove_player(self, true).await;
 
@@ -612,32 +689,16 @@ impl Player {
         let inventory = self.inventory();
         let item_stack = inventory.held_item();
 
-        let base_damage = 1.0;
-        let base_attack_speed = 4.0;
+        let (base_damage, attack_damage, attack_speed) = {
+            let attributes = self.living_entity.attributes.lock().await;
+            (
+                attributes.base_value(&Attributes::ATTACK_DAMAGE),
+                attributes.value(&Attributes::ATTACK_DAMAGE),
+                attributes.value(&Attributes::ATTACK_SPEED),
+            )
+        };
 
         let mut damage_multiplier = 1.0;
-        let mut add_damage = 0.0;
-        let mut add_speed = 0.0;
-
-        // Get the attack damage
-        // TODO: this should be cached in memory, we shouldn't just use default here either
-        if let Some(modifiers) = item_stack
-            .lock()
-            .await
-            .get_data_component::<AttributeModifiersImpl>()
-        {
-            for item_mod in modifiers.attribute_modifiers.iter() {
-                if item_mod.operation == Operation::AddValue {
-                    if item_mod.id == "minecraft:base_attack_damage" {
-                        add_damage = item_mod.amount;
-                    } else if item_mod.id == "minecraft:base_attack_speed" {
-                        add_speed = item_mod.amount;
-                    }
-                }
-            }
-        }
-
-        let attack_speed = base_attack_speed + add_speed;
 
         let attack_cooldown_progress = self.get_attack_cooldown_progress(
             f64::from(server.basic_config.tps),
@@ -647,12 +708,11 @@ impl Player {
         self.last_attacked_ticks.store(0, Ordering::Relaxed);
 
         // Only reduce attack damage if in cooldown
-        // TODO: Enchantments are reduced in the same way, just without the square.
         if attack_cooldown_progress < 1.0 {
             damage_multiplier = 0.2 + attack_cooldown_progress.powi(2) * 0.8;
         }
         // Modify the added damage based on the multiplier.
-        let mut damage = base_damage + add_damage * damage_multiplier;
+        let mut damage = base_damage + (attack_damage - base_damage) * damage_multiplier;
 
         let pos = victim_entity.pos.load();
 
@@ -661,6 +721,14 @@ impl Player {
         if matches!(attack_type, AttackType::Critical) {
             damage *= 1.5;
         }
+        // Enchantments are reduced by the cooldown in the same way, just without the square
+        let sharpness = item_stack
+            .lock()
+            .await
+            .get_enchantment_level(&Enchantment::SHARPNESS);
+        if sharpness > 0 {
+            damage += (0.5 * f64::from(sharpness) + 0.5) * attack_cooldown_progress;
+        }
 
         if !victim
             .damage_with_context(
@@ -684,7 +752,8 @@ impl Player {
         }
 
         if victim.get_living_entity().is_some() {
-            let mut knockback_strength = 1.0;
+            // The base knockback is applied by the damage, this is the extra one
+            let mut knockback_strength = 0.0;
             player_attack_sound(&pos, world, attack_type).await;
             match attack_type {
                 AttackType::Knockback => knockback_strength += 1.0,
@@ -693,10 +762,21 @@ impl Player {
                 }
                 _ => {}
             }
-            if config.knockback {
-                combat::handle_knockback(attacker_entity, world, victim_entity, knockback_strength)
-                    .await;
+            if config.knockback && knockback_strength > 0.0 {
+                combat::handle_knockback(
+                    world,
+                    victim_entity,
+                    attacker_entity.pos.load(),
+                    knockback_strength * 0.5,
+                )
+                .await;
+                let velocity = attacker_entity.velocity.load();
+                attacker_entity
+                    .velocity
+                    .store(velocity.multiply(0.6, 1.0, 0.6));
             }
+            let damage_per_attack = item_stack.lock().await.damage_per_attack();
+            self.damage_held_item(damage_per_attack).await;
         }
 
         if config.swing {}
@@ -767,7 +847,7 @@ impl Player {
             .entity
             .set_pos(bed_head_pos.to_f64().add_raw(0.5, 0.6875, 0.5));
         self.get_entity()
-            .send_meta_data(&[Metadata::new(
+            .send_meta_data_now(&[Metadata::new(
                 TrackedData::DATA_SLEEPING_POSITION,
                 MetaDataType::OptionalBlockPos,
                 Some(bed_head_pos),
@@ -818,7 +898,7 @@ impl Player {
         self.living_entity.entity.set_pos(self.position());
         self.living_entity
             .entity
-            .send_meta_data(&[Metadata::new(
+            .send_meta_data_now(&[Metadata::new(
                 TrackedData::DATA_SLEEPING_POSITION,
                 MetaDataType::OptionalBlockPos,
                 None::<BlockPos>,
@@ -856,10 +936,25 @@ impl Player {
         max_speed: f32,
         particle_count: i32,
         particle: Particle,
+    ) {
+        self.send_particle(false, position, offset, max_speed, particle_count, particle)
+            .await;
+    }
+
+    /// Spawns a particle for this player, `force` makes the client show it even if it is far
+    /// away or the player's particle setting is minimal.
+    pub async fn send_particle(
+        &self,
+        force: bool,
+        position: Vector3<f64>,
+        offset: Vector3<f32>,
+        max_speed: f32,
+        particle_count: i32,
+        particle: Particle,
     ) {
         self.client
             .enqueue_packet(&CParticle::new(
-                false,
+                force,
                 false,
                 position,
                 offset,
@@ -880,16 +975,33 @@ impl Player {
         pitch: f32,
         seed: f64,
     ) {
-        self.client
-            .enqueue_packet(&CSoundEffect::new(
-                IdOr::Id(sound_id),
-                category,
-                position,
-                volume,
-                pitch,
-                seed,
-            ))
-            .await;
+        match &self.client {
+            ClientPlatform::Java(java_client) => {
+                java_client
+                    .enqueue_packet(&CSoundEffect::new(
+                        IdOr::Id(sound_id),
+                        category,
+                        position,
+                        volume,
+                        pitch,
+                        seed,
+                    ))
+                    .await;
+            }
+            ClientPlatform::Bedrock(bedrock_client) => {
+                // Bedrock only knows the sounds by name
+                if let Some(sound) = Sound::from_id(sound_id) {
+                    bedrock_client
+                        .send_game_packet(&CPlaySound::new(
+                            bedrock_sound_name(sound.to_name()),
+                            position,
+                            volume,
+                            pitch,
+                        ))
+                        .await;
+                }
+            }
+        }
     }
 
     /// Stops a sound playing on the client.
@@ -898,14 +1010,27 @@ impl Player {
     ///
     /// * `sound_id`: An optional [`ResourceLocation`] specifying the sound to stop. If [`None`], all sounds in the specified category (if any) will be stopped.
     /// * `category`: An optional [`SoundCategory`] specifying the sound category to stop. If [`None`], all sounds with the specified resource location (if any) will be stopped.
+    ///
+    /// Bedrock has no sound categories, so without a `sound_id` all sounds are stopped there.
     pub async fn stop_sound(
         &self,
         sound_id: Option<ResourceLocation>,
         category: Option<SoundCategory>,
     ) {
-        self.client
-            .enqueue_packet(&CStopSound::new(sound_id, category))
-            .await;
+        match &self.client {
+            ClientPlatform::Java(java_client) => {
+                java_client
+                    .enqueue_packet(&CStopSound::new(sound_id, category))
+                    .await;
+            }
+            ClientPlatform::Bedrock(bedrock_client) => {
+                bedrock_client
+                    .send_game_packet(&CStopSoundBedrock::new(
+                        sound_id.map(|sound| bedrock_sound_name(&sound.path)),
+                    ))
+                    .await;
+            }
+        }
     }
 
     // TODO Abstract the chunk sending
@@ -950,20 +1075,27 @@ impl Player {
             }
         };
 
-        if let Some(chunk_of_chunks) = chunk_of_chunks {
+        if let Some(chunk_of_chunks) = chunk_of_chunks
+            && !chunk_of_chunks.is_empty()
+        {
             let chunk_count = chunk_of_chunks.len();
             match &self.client {
                 ClientPlatform::Java(java_client) => {
-                    java_client.send_packet_now(&CChunkBatchStart).await;
+                    java_client.enqueue_bulk_packet(&CChunkBatchStart).await;
                     for chunk in chunk_of_chunks {
                         let chunk = chunk.read().await;
                         // log::debug!("send chunk {:?}", chunk.position);
                         // TODO: Can we check if we still need to send the chunk? Like if it's a fast moving
                         // player or something.
-                        java_client.send_packet_now(&CChunkData(&chunk)).await;
+                        java_client
+                            .enqueue_chunk_packet(
+                                Vector2::new(chunk.x, chunk.z),
+                                &CChunkData(&chunk),
+                            )
+                            .await;
                     }
                     java_client
-                        .send_packet_now(&CChunkBatchEnd::new(chunk_count as u16))
+                        .enqueue_bulk_packet(&CChunkBatchEnd::new(chunk_count as u16))
                         .await;
                 }
                 ClientPlatform::Bedrock(bedrock_client) => {
@@ -1016,6 +1148,8 @@ impl Player {
 
         self.living_entity.tick(self.clone(), server).await;
         self.hunger_manager.tick(self).await;
+        self.tick_item_cooldowns().await;
+        self.tick_lodestone_compasses().await;
 
         // experience handling
         self.tick_experience().await;
@@ -1027,7 +1161,10 @@ impl Player {
         // TODO This should only be handled by the ClientPlatform
         let now = Instant::now();
         if now.duration_since(self.last_keep_alive_time.load()) >= Duration::from_secs(15) {
-            if matches!(self.client, ClientPlatform::Bedrock(_)) {
+            if let ClientPlatform::Bedrock(bedrock) = &self.client {
+                // Bedrock uses RakNet pings instead, which are only used to measure the latency
+                self.last_keep_alive_time.store(now);
+                bedrock.send_connected_ping().await;
                 return;
             }
             // We never got a response from the last keep alive we sent.
@@ -1068,6 +1205,7 @@ impl Player {
     }
 
     pub async fn jump(&self) {
+        self.increment_stat(Stat::Custom(CustomStat::Jump), 1).await;
         if self.living_entity.entity.sprinting.load(Ordering::Relaxed) {
             self.add_exhaustion(0.2).await;
         } else {
@@ -1077,18 +1215,90 @@ impl Player {
 
     pub async fn progress_motion(&self, delta_pos: Vector3<f64>) {
         // TODO: Swimming, gliding...
-        if self.living_entity.entity.on_ground.load(Ordering::Relaxed) {
-            let delta = (delta_pos.horizontal_length() * 100.0).round() as f32;
+        let entity = &self.living_entity.entity;
+        let delta = (delta_pos.horizontal_length() * 100.0).round() as f32;
+        if entity.touching_water.load(Ordering::Relaxed) {
+            if delta > 0.0 {
+                self.add_exhaustion(0.01 * delta * 0.01).await;
+                self.increment_stat(Stat::Custom(CustomStat::WalkOnWaterOneCm), delta as i32)
+                    .await;
+            }
+        } else if entity.on_ground.load(Ordering::Relaxed) {
             if delta > 0.0 {
-                if self.living_entity.entity.sprinting.load(Ordering::Relaxed) {
+                let stat = if entity.sprinting.load(Ordering::Relaxed) {
                     self.add_exhaustion(0.1 * delta * 0.01).await;
+                    CustomStat::SprintOneCm
                 } else {
                     self.add_exhaustion(0.0 * delta * 0.01).await;
-                }
+                    if entity.sneaking.load(Ordering::Relaxed) {
+                        CustomStat::CrouchOneCm
+                    } else {
+                        CustomStat::WalkOneCm
+                    }
+                };
+                self.increment_stat(Stat::Custom(stat), delta as i32).await;
+            }
+        } else if self.abilities.lock().await.flying {
+            self.increment_stat(Stat::Custom(CustomStat::FlyOneCm), delta as i32)
+                .await;
+        }
+    }
+
+    /// Wears out the held tool after it broke `state`, blocks which break instantly are free.
+    pub async fn damage_held_item_for_block(&self, state: &BlockState) {
+        if state.hardness == 0.0 {
+            return;
+        }
+        let amount = self
+            .inventory
+            .held_item()
+            .lock()
+            .await
+            .get_data_component::<ToolImpl>()
+            .map_or(0, |tool| tool.damage_per_block as i32);
+        self.damage_held_item(amount).await;
+    }
+
+    /// Damages the held item and tells the clients about its new durability, the item breaks
+    /// once it has no durability left.
+    pub async fn damage_held_item(&self, amount: i32) {
+        if amount <= 0 || self.gamemode.load() == GameMode::Creative {
+            return;
+        }
+        let held_item = self.inventory.held_item();
+        let (stack, broke) = {
+            let mut stack = held_item.lock().await;
+            if !stack.is_damageable() {
+                return;
+            }
+            let broke = stack.damage_item(amount);
+            if broke {
+                *stack = ItemStack::EMPTY.clone();
             }
+            (stack.clone(), broke)
+        };
+
+        self.client
+            .enqueue_packet(&CSetPlayerInventory::new(
+                VarInt(i32::from(self.inventory.get_selected_slot())),
+                &ItemStackSerializer::from(stack.clone()),
+            ))
+            .await;
+        self.living_entity
+            .send_equipment_changes(&[(EquipmentSlot::MAIN_HAND, stack)])
+            .await;
+        if broke {
+            self.world()
+                .send_entity_status(&self.living_entity.entity, EntityStatus::BreakMainhand)
+                .await;
         }
     }
 
+    /// The round trip time to the client in milliseconds, regardless of its edition.
+    pub fn latency(&self) -> u32 {
+        self.client.latency()
+    }
+
     pub fn has_client_loaded(&self) -> bool {
         self.client_loaded.load(Ordering::Relaxed)
             || self.client_loaded_timeout.load(Ordering::Relaxed) == 0
@@ -1139,6 +1349,68 @@ impl Player {
         )
     }
 
+    pub async fn increment_stat(&self, stat: Stat, amount: i32) {
+        if amount > 0 {
+            self.stats.lock().await.increment(&stat, amount);
+        }
+    }
+
+    /// Sends all stats to the client, for its statistics screen.
+    pub async fn send_stats(&self) {
+        let stats: Vec<_> = self
+            .stats
+            .lock()
+            .await
+            .iter()
+            .map(|(stat, value)| {
+                let (category_id, statistic_id) = stat.network_ids();
+                Statistic {
+                    category_id: VarInt(category_id),
+                    statistic_id: VarInt(statistic_id),
+                    value: VarInt(value),
+                }
+            })
+            .collect();
+        self.client.enqueue_packet(&CAwardStats::new(&stats)).await;
+    }
+
+    /// Puts the group of the stack on cooldown, so no item of that group can be used for `ticks`.
+    pub async fn set_item_cooldown(&self, stack: &ItemStack, ticks: u32) {
+        let group = ItemCooldownManager::group_of(stack);
+        self.client
+            .enqueue_packet(&CCooldown::new(&group, VarInt(ticks as i32)))
+            .await;
+        self.item_cooldowns.lock().await.set(group, ticks);
+    }
+
+    pub async fn is_item_cooling_down(&self, stack: &ItemStack) -> bool {
+        self.item_cooldowns
+            .lock()
+            .await
+            .is_cooling_down(&ItemCooldownManager::group_of(stack))
+    }
+
+    async fn tick_item_cooldowns(&self) {
+        let expired = self.item_cooldowns.lock().await.tick();
+        for group in expired {
+            self.client
+                .enqueue_packet(&CCooldown::new(&group, VarInt(0)))
+                .await;
+        }
+    }
+
+    /// Clears lodestone compasses whose lodestone was destroyed.
+    async fn tick_lodestone_compasses(&self) {
+        let world = self.world();
+        let off_hand = self.inventory.off_hand_item().await;
+        for slot in self.inventory.main_inventory.iter().chain([&off_hand]) {
+            let mut stack = slot.lock().await;
+            if stack.item == &Item::COMPASS {
+                lodestone::update_compass(&world, &mut stack).await;
+            }
+        }
+    }
+
     /// Updates the current abilities the player has.
     pub async fn send_abilities_update(&self) {
         match &self.client {
@@ -1253,22 +1525,18 @@ impl Player {
 
     /// Sends the world time to only this player.
     pub async fn send_time(&self, world: &World) {
+        // Same lock order as `World::tick_environment`, the time first
         let l_world = world.level_time.lock().await;
+        let advance_time = world.level_info.read().await.game_rules.advance_time;
         match &self.client {
             ClientPlatform::Java(java_client) => {
                 java_client
-                    .enqueue_packet(&CUpdateTime::new(
-                        l_world.world_age,
-                        l_world.time_of_day,
-                        true,
-                    ))
+                    .enqueue_packet(&l_world.java_packet(advance_time))
                     .await;
             }
             ClientPlatform::Bedrock(bedrock_client) => {
                 bedrock_client
-                    .send_game_packet(&CSetTime {
-                        time: VarInt(l_world.query_daytime() as _),
-                    })
+                    .send_game_packet(&l_world.bedrock_packet())
                     .await;
             }
         }
@@ -1277,11 +1545,12 @@ impl Player {
     async fn unload_watched_chunks(&self, world: &World) {
         let radial_chunks = self.watched_section.load().all_chunks_within();
         let level = &world.level;
-        let chunks_to_clean = level.mark_chunks_as_not_watched(&radial_chunks).await;
-        // level.clean_chunks(&chunks_to_clean).await;
-        for chunk in chunks_to_clean {
+        level.mark_chunks_as_not_watched(&radial_chunks).await;
+        // The client has to forget every chunk, even those still watched by other players
+        for chunk in radial_chunks {
+            // Bulk, so the client doesn't get the unload before the chunk itself
             self.client
-                .enqueue_packet(&CUnloadChunk::new(chunk.x, chunk.y))
+                .enqueue_bulk_packet(&CUnloadChunk::new(chunk.x, chunk.y))
                 .await;
         }
 
@@ -1354,6 +1623,8 @@ impl Player {
                     last_pos.y.round() as i32,
                     last_pos.z.round() as i32,
                 ));
+                // Chunks of the old world still queued are of no use to the client anymore
+                self.client.discard_bulk_packets();
                 self.client
                     .send_packet_now(&CRespawn::new(
                         (new_world.dimension.id).into(),
@@ -1379,7 +1650,7 @@ impl Player {
                 self.on_screen_handler_opened(self.player_screen_handler.clone()).await;
                 self.send_health().await;
 
-                new_world.send_world_info(self, position, yaw, pitch).await;
+                new_world.send_world_info(self).await;
             }
         }}
     }
@@ -1448,6 +1719,20 @@ impl Player {
         self.client.kick(reason, message).await;
     }
 
+    /// Kicks the player on behalf of `actor`, unlike [`Self::kick`] this is recorded in the
+    /// audit log. Disconnects the server causes itself, like timeouts, use [`Self::kick`].
+    pub async fn kick_by(&self, actor: &str, message: TextComponent) {
+        if let Some(server) = self.world().server.upgrade() {
+            server.audit_log.record(
+                AuditAction::Kick,
+                actor,
+                &self.gameprofile.name,
+                Some(&message.clone().get_text()),
+            );
+        }
+        self.kick(DisconnectReason::Kicked, message).await;
+    }
+
     pub fn can_food_heal(&self) -> bool {
         let health = self.living_entity.health.load();
         let max_health = 20.0;
@@ -1699,6 +1984,13 @@ impl Player {
                     )
                     .await;
             }
+
+            // `Player.getBaseExperienceReward()`
+            let level = self.experience_level.load(Ordering::Relaxed);
+            let amount = level.saturating_mul(7).min(100);
+            if amount > 0 && !self.is_spectator() {
+                ExperienceOrbEntity::spawn(self.world(), self.position(), amount as u32).await;
+            }
         }
 
         self.client
@@ -1706,7 +1998,9 @@ impl Player {
             .await;
     }
 
-    pub async fn set_gamemode(self: &Arc<Self>, gamemode: GameMode) {
+    /// Changes the game mode on behalf of `actor`, which is recorded in the audit log unless a
+    /// plugin cancels the change.
+    pub async fn set_gamemode(self: &Arc<Self>, gamemode: GameMode, actor: &str) {
         // We could send the same gamemode without any problems. But why waste bandwidth?
         assert_ne!(
             self.gamemode.load(),
@@ -1723,6 +2017,14 @@ impl Player {
 
             'after: {
                 let gamemode = event.new_gamemode;
+                if let Some(server) = self.world().server.upgrade() {
+                    server.audit_log.record(
+                        AuditAction::GameModeChange,
+                        actor,
+                        &self.gameprofile.name,
+                        Some(&format!("{gamemode:?}").to_lowercase()),
+                    );
+                }
                 self.gamemode.store(gamemode);
                 // TODO: Fix this when mojang fixes it
                 // This is intentional to keep the pure vanilla mojang experience
@@ -1790,7 +2092,17 @@ impl Player {
     }
 
     pub async fn get_mining_speed(&self, block: &'static Block) -> f32 {
-        let mut speed = self.inventory.held_item().lock().await.get_speed(block);
+        let held_item = self.inventory.held_item();
+        let held_item = held_item.lock().await;
+        let mut speed = held_item.get_speed(block);
+        // Efficiency only helps with tools suited for the block
+        if speed > 1.0 {
+            let efficiency = held_item.get_enchantment_level(&Enchantment::EFFICIENCY);
+            if efficiency > 0 {
+                speed += (efficiency * efficiency + 1) as f32;
+            }
+        }
+        drop(held_item);
         // Haste
         if self.living_entity.has_effect(&StatusEffect::HASTE).await
             || self
@@ -1814,7 +2126,27 @@ impl Player {
             };
             speed *= fatigue_speed;
         }
-        // TODO: Handle when in water
+        // With the eyes under water, unless the helmet has Aqua Affinity
+        let entity = &self.living_entity.entity;
+        let eye_height = f64::from(entity.entity_dimension.load().eye_height);
+        if entity.touching_water.load(Ordering::Relaxed)
+            && entity.water_height.load() > eye_height - 0.111_11
+        {
+            let helmet = self
+                .inventory
+                .entity_equipment
+                .lock()
+                .await
+                .get(&EquipmentSlot::HEAD);
+            if helmet
+                .lock()
+                .await
+                .get_enchantment_level(&Enchantment::AQUA_AFFINITY)
+                == 0
+            {
+                speed /= 5.0;
+            }
+        }
         if !self.living_entity.entity.on_ground.load(Ordering::Relaxed) {
             speed /= 5.0;
         }
@@ -1921,6 +2253,40 @@ impl Player {
         // todo this.player.stopUsingItem();
     }
 
+    /// Stores a cookie on the client, it is kept when the player is transferred to another
+    /// server. Bedrock clients do not support cookies.
+    pub async fn store_cookie(&self, key: &ResourceLocation, payload: &[u8]) {
+        if payload.len() > MAX_COOKIE_SIZE {
+            log::warn!(
+                "Not storing cookie {key} for {}, it is larger than {MAX_COOKIE_SIZE} bytes",
+                self.gameprofile.name
+            );
+            return;
+        }
+        if let ClientPlatform::Java(client) = &self.client {
+            client
+                .enqueue_packet(&CStoreCookie::new(key, payload))
+                .await;
+        }
+    }
+
+    /// Requests a cookie the client stored, e.g. on another server before being transferred.
+    ///
+    /// Returns `None` if the client has no such cookie or does not answer in time. The response
+    /// is read by the player's packet handling, so don't await this while handling one of its
+    /// packets, e.g. in a command, spawn a task instead.
+    pub async fn request_cookie(&self, key: &ResourceLocation) -> Option<Vec<u8>> {
+        let ClientPlatform::Java(client) = &self.client else {
+            return None;
+        };
+        let response = client.pending_cookies.register(key.clone()).await;
+        client.enqueue_packet(&CPlayCookieRequest::new(key)).await;
+        client
+            .pending_cookies
+            .wait(key, response, COOKIE_RESPONSE_TIMEOUT)
+            .await
+    }
+
     pub async fn send_system_message(&self, text: &TextComponent) {
         match &self.client {
             ClientPlatform::Java(client) => {
@@ -2119,9 +2485,8 @@ impl Player {
     pub async fn add_experience_points(&self, added_points: i32) {
         let current_level = self.experience_level.load(Ordering::Relaxed);
         let current_points = self.experience_points.load(Ordering::Relaxed);
-        let total_exp = experience::points_to_level(current_level) + current_points;
-        let new_total_exp = total_exp + added_points;
-        let (new_level, new_points) = experience::total_to_level_and_points(new_total_exp);
+        let (new_level, new_points) =
+            experience::add_points(current_level, current_points, added_points);
         let progress = experience::progress_in_level(new_points, new_level);
         self.set_experience(new_level, progress, new_points).await;
     }
@@ -2184,6 +2549,46 @@ impl Player {
             .await;
     }
 
+    /// Opens a written book GUI showing `pages`, the player does not need to hold a book.
+    ///
+    /// The book is only put into the selected hotbar slot on the client and replaced with the
+    /// actual item right after opening it.
+    pub async fn open_book(&self, pages: Vec<TextComponent>) {
+        let slot = VarInt(i32::from(self.inventory.get_selected_slot()));
+        let book = ItemStack::new_with_component(
+            1,
+            &Item::WRITTEN_BOOK,
+            vec![(
+                DataComponent::WrittenBookContent,
+                Some(
+                    WrittenBookContentImpl {
+                        title: String::new(),
+                        author: String::new(),
+                        generation: 0,
+                        pages,
+                        resolved: true,
+                    }
+                    .to_dyn(),
+                ),
+            )],
+        );
+        self.client
+            .enqueue_packet(&CSetPlayerInventory::new(
+                slot,
+                &ItemStackSerializer::from(book),
+            ))
+            .await;
+        self.client.enqueue_packet(&COpenBook::new(VarInt(0))).await;
+
+        let held_item = self.inventory.held_item().lock().await.clone();
+        self.client
+            .enqueue_packet(&CSetPlayerInventory::new(
+                slot,
+                &ItemStackSerializer::from(held_item),
+            ))
+            .await;
+    }
+
     pub async fn open_handled_screen(
         &self,
         screen_handler_factory: &dyn ScreenHandlerFactory,
@@ -2362,6 +2767,17 @@ impl NBTStorage for Player {
             // Store food level, saturation, exhaustion, and tick timer
             self.hunger_manager.write_nbt(nbt).await;
 
+            let recipes = self
+                .advancement_tracker
+                .lock()
+                .await
+                .unlocked_recipes()
+                .map(|recipe| NbtTag::String(recipe.to_string()))
+                .collect();
+            let mut recipe_book = NbtCompound::new();
+            recipe_book.put("recipes", NbtTag::List(recipes));
+            nbt.put_component("recipeBook", recipe_book);
+
             nbt.put_string(
                 "Dimension",
                 ResourceLocation::from(self.world().dimension.minecraft_name).to_string(),
@@ -2394,6 +2810,18 @@ impl NBTStorage for Player {
             // Load food level, saturation, exhaustion, and tick timer
             self.hunger_manager.read_nbt(nbt).await;
 
+            if let Some(recipes) = nbt
+                .get_compound("recipeBook")
+                .and_then(|recipe_book| recipe_book.get_list("recipes"))
+            {
+                self.advancement_tracker.lock().await.load_recipes(
+                    recipes
+                        .iter()
+                        .filter_map(NbtTag::extract_string)
+                        .map(ResourceLocation::from),
+                );
+            }
+
             // Load from total XP
             let total_exp = nbt.get_int("XpTotal").unwrap_or(0);
             let (level, points) = experience::total_to_level_and_points(total_exp);
@@ -2468,46 +2896,42 @@ impl NBTStorage for PlayerInventory {
             // Process inventory list
             if let Some(inventory_list) = nbt.get_list("Inventory") {
                 for tag in inventory_list {
-                    if let Some(item_compound) = tag.extract_compound()
-                        && let Some(slot_byte) = item_compound.get_byte("Slot")
-                    {
-                        let slot = slot_byte as usize;
-                        if let Some(item_stack) = ItemStack::read_item_stack(item_compound) {
-                            self.set_stack(slot, item_stack).await;
+                    let Some(item_compound) = tag.extract_compound() else {
+                        log::warn!("Skipping a malformed inventory slot");
+                        continue;
+                    };
+                    let slot = item_compound.get_byte("Slot").map(i32::from);
+                    let slot = match slot.map(usize::try_from) {
+                        Some(Ok(slot))
+                            if slot < Self::MAIN_SIZE
+                                || self.equipment_slots.contains_key(&slot) =>
+                        {
+                            slot
+                        }
+                        _ => {
+                            log::warn!("Skipping an item in the invalid inventory slot {slot:?}");
+                            continue;
                         }
+                    };
+                    if let Some(item_stack) = read_saved_stack(item_compound, "the inventory") {
+                        self.set_stack(slot, item_stack).await;
                     }
                 }
             }
 
             if let Some(equipment) = nbt.get_compound("equipment") {
-                if let Some(offhand) = equipment.get_compound("offhand")
-                    && let Some(item_stack) = ItemStack::read_item_stack(offhand)
-                {
-                    self.set_stack(40, item_stack).await;
-                }
-
-                if let Some(head) = equipment.get_compound("head")
-                    && let Some(item_stack) = ItemStack::read_item_stack(head)
-                {
-                    self.set_stack(39, item_stack).await;
-                }
-
-                if let Some(chest) = equipment.get_compound("chest")
-                    && let Some(item_stack) = ItemStack::read_item_stack(chest)
-                {
-                    self.set_stack(38, item_stack).await;
-                }
-
-                if let Some(legs) = equipment.get_compound("legs")
-                    && let Some(item_stack) = ItemStack::read_item_stack(legs)
-                {
-                    self.set_stack(37, item_stack).await;
-                }
-
-                if let Some(feet) = equipment.get_compound("feet")
-                    && let Some(item_stack) = ItemStack::read_item_stack(feet)
-                {
-                    self.set_stack(36, item_stack).await;
+                for (name, slot) in [
+                    ("offhand", Self::OFF_HAND_SLOT),
+                    ("head", 39),
+                    ("chest", 38),
+                    ("legs", 37),
+                    ("feet", 36),
+                ] {
+                    if let Some(compound) = equipment.get_compound(name)
+                        && let Some(item_stack) = read_saved_stack(compound, name)
+                    {
+                        self.set_stack(slot, item_stack).await;
+                    }
                 }
             }
         })
@@ -2541,13 +2965,20 @@ impl NBTStorage for EnderChestInventory {
             // Process item list
             if let Some(item_list) = nbt.get_list("EnderItems") {
                 for tag in item_list {
-                    if let Some(item_compound) = tag.extract_compound()
-                        && let Some(slot_byte) = item_compound.get_byte("Slot")
-                    {
-                        let slot = slot_byte as usize;
-                        if let Some(item_stack) = ItemStack::read_item_stack(item_compound) {
-                            self.set_stack(slot, item_stack).await;
+                    let Some(item_compound) = tag.extract_compound() else {
+                        log::warn!("Skipping a malformed ender chest slot");
+                        continue;
+                    };
+                    let slot = item_compound.get_byte("Slot").map(i32::from);
+                    let slot = match slot.map(usize::try_from) {
+                        Some(Ok(slot)) if slot < Self::INVENTORY_SIZE => slot,
+                        _ => {
+                            log::warn!("Skipping an item in the invalid ender chest slot {slot:?}");
+                            continue;
                         }
+                    };
+                    if let Some(item_stack) = read_saved_stack(item_compound, "the ender chest") {
+                        self.set_stack(slot, item_stack).await;
                     }
                 }
             }
@@ -2555,6 +2986,46 @@ impl NBTStorage for EnderChestInventory {
     }
 }
 
+/// Reads an item of a saved inventory, repairing it where possible so a single bad item, e.g.
+/// after a version change, doesn't cost the rest of the inventory. Unknown items are dropped,
+/// counts are clamped to the stack size and unknown or malformed components are skipped.
+fn read_saved_stack(compound: &NbtCompound, location: &str) -> Option<ItemStack> {
+    let Some(id) = compound.get_string("id") else {
+        log::warn!("Dropping an item without id from {location}");
+        return None;
+    };
+    let Some(item) = Item::from_registry_key(id.strip_prefix("minecraft:").unwrap_or(id)) else {
+        log::warn!("Dropping the unknown item {id} from {location}");
+        return None;
+    };
+    let mut stack = ItemStack::new(1, item);
+
+    if let Some(components) = compound.get_compound("components") {
+        for (name, data) in &components.child_tags {
+            let removed = name.strip_prefix('!');
+            let Some(component) = DataComponent::try_from_name(removed.unwrap_or(name)) else {
+                log::warn!("Skipping the unknown component {name} of {id} in {location}");
+                continue;
+            };
+            if removed.is_some() {
+                stack.patch.push((component, None));
+            } else if let Some(data) = read_data(component, data) {
+                stack.patch.push((component, Some(data)));
+            } else {
+                log::warn!("Skipping the malformed component {name} of {id} in {location}");
+            }
+        }
+    }
+
+    let count = compound.get_int("count").unwrap_or(1);
+    let max_count = i32::from(stack.get_max_stack_size());
+    if !(1..=max_count).contains(&count) {
+        log::warn!("Clamping the count {count} of {id} in {location} to 1..={max_count}");
+    }
+    stack.item_count = count.clamp(1, max_count) as u8;
+    Some(stack)
+}
+
 impl NBTStorageInit for EnderChestInventory {}
 
 impl EntityBase for Player {
@@ -2571,6 +3042,20 @@ impl EntityBase for Player {
             if self.abilities.lock().await.invulnerable && damage_type != DamageType::GENERIC_KILL {
                 return false;
             }
+            // Teams decide whether players may hurt each other, no matter if by hand, with a
+            // projectile or anything else they caused
+            let world = &self.living_entity.entity.world;
+            if let Some(cause) = cause
+                && let Some(attacker) = world.get_player_by_id(cause.get_entity().entity_id).await
+                && let Some(server) = world.server.upgrade()
+                && !server
+                    .teams
+                    .lock()
+                    .await
+                    .can_hurt(&attacker.gameprofile.name, &self.gameprofile.name)
+            {
+                return false;
+            }
             let dyn_self = self
                 .living_entity
                 .entity
@@ -2583,6 +3068,7 @@ impl EntityBase for Player {
                 .damage_with_context(caller, amount, damage_type, position, source, cause)
                 .await;
             if result {
+                self.add_exhaustion(damage_type.exhaustion).await;
                 let health = self.living_entity.health.load();
                 if health <= 0.0 {
                     let death_message =
@@ -3037,3 +3523,96 @@ impl InventoryPlayer for Player {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use pumpkin_data::item::Item;
+    use pumpkin_inventory::build_equipment_slots;
+    use pumpkin_inventory::entity_equipment::EntityEquipment;
+    use pumpkin_inventory::player::player_inventory::PlayerInventory;
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_nbt::tag::NbtTag;
+    use pumpkin_util::math::vector2::Vector2;
+    use tokio::sync::Mutex;
+
+    use super::{ChunkManager, NBTStorage};
+
+    fn saved_item(slot: i8, id: &str, count: i32) -> NbtCompound {
+        let mut item = NbtCompound::new();
+        item.put_byte("Slot", slot);
+        item.put_string("id", id.to_string());
+        item.put_int("count", count);
+        item
+    }
+
+    #[tokio::test]
+    async fn bad_items_do_not_break_the_inventory() {
+        let mut unknown_component = saved_item(3, "minecraft:dirt", 2);
+        let mut components = NbtCompound::new();
+        components.put_int("minecraft:not_a_component", 1);
+        components.put_int("minecraft:max_stack_size", 16);
+        unknown_component.put_component("components", components);
+        let items = vec![
+            saved_item(0, "minecraft:diamond", 5),
+            saved_item(1, "minecraft:removed_in_a_later_version", 1),
+            saved_item(2, "minecraft:stone", 300),
+            unknown_component,
+            saved_item(100, "minecraft:stick", 1),
+            saved_item(-1, "minecraft:stick", 1),
+        ];
+        let mut nbt = NbtCompound::new();
+        nbt.put(
+            "Inventory",
+            NbtTag::List(items.into_iter().map(NbtTag::Compound).collect()),
+        );
+
+        let inventory = PlayerInventory::new(
+            Arc::new(Mutex::new(EntityEquipment::new())),
+            Arc::new(build_equipment_slots()),
+        );
+        inventory.read_nbt_non_mut(&nbt).await;
+
+        let slots = HashMap::from([
+            (0, (&Item::DIAMOND, 5)),
+            (1, (&Item::AIR, 0)),
+            (2, (&Item::STONE, 64)),
+            (3, (&Item::DIRT, 2)),
+        ]);
+        for (slot, (item, count)) in slots {
+            let stack = inventory.main_inventory[slot].lock().await;
+            assert_eq!(stack.item.id, item.id, "slot {slot}");
+            assert_eq!(stack.item_count, count, "slot {slot}");
+        }
+        let dirt = inventory.main_inventory[3].lock().await;
+        assert_eq!(dirt.patch.len(), 1);
+        assert_eq!(dirt.get_max_stack_size(), 16);
+    }
+
+    #[test]
+    fn chunks_out_of_view_are_forgotten() {
+        let (_sender, receiver) = crossbeam::channel::unbounded();
+        let mut chunk_manager = ChunkManager::new(9.0, receiver);
+        for x in -3..=3 {
+            for z in -3..=3 {
+                chunk_manager.chunk_sent.insert(Vector2::new(x, z));
+            }
+        }
+
+        // Moving one chunk east leaves the western column behind
+        let mut forgotten = chunk_manager.forget_chunks_out_of_view(Vector2::new(1, 0), 3);
+        forgotten.sort_by_key(|pos| pos.y);
+        assert_eq!(
+            forgotten,
+            (-3..=3).map(|z| Vector2::new(-3, z)).collect::<Vec<_>>()
+        );
+        assert_eq!(chunk_manager.chunk_sent.len(), 6 * 7);
+        assert!(
+            chunk_manager
+                .forget_chunks_out_of_view(Vector2::new(1, 0), 3)
+                .is_empty()
+        );
+    }
+}
@@ -47,10 +47,7 @@ impl EntityPredicate<'_> {
                 EntityPredicate::Rides(target_entity) => {
                     let target: &Entity = target_entity;
 
-                    let mut opt_vehicle_arc = {
-                        let vehicle_lock = entity.vehicle.lock().await;
-                        vehicle_lock.clone()
-                    };
+                    let mut opt_vehicle_arc = entity.get_vehicle().await;
 
                     while let Some(vehicle_arc) = opt_vehicle_arc {
                         let vehicle_entity_base: &dyn EntityBase = &*vehicle_arc;
@@ -60,11 +57,7 @@ impl EntityPredicate<'_> {
                             return false;
                         }
 
-                        opt_vehicle_arc = {
-                            let vehicle_lock =
-                                vehicle_entity_base.get_entity().vehicle.lock().await;
-                            vehicle_lock.clone()
-                        }
+                        opt_vehicle_arc = vehicle_entity_base.get_entity().get_vehicle().await;
                     }
                     true
                 }
@@ -51,7 +51,7 @@ impl FireworkRocketEntity {
         entity
             .entity
             .entity
-            .send_meta_data(&[Metadata::new(
+            .send_meta_data_now(&[Metadata::new(
                 TrackedData::DATA_SHOOTER_ENTITY_ID,
                 MetaDataType::OptionalInt,
                 OptionalInt(Some(shooter.entity_id)),
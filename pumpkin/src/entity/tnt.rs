@@ -83,7 +83,7 @@ impl EntityBase for TNTEntity {
                 .await;
             // We can merge multiple `Metadata`s into one meta packet.
             self.entity
-                .send_meta_data(&[
+                .send_meta_data_now(&[
                     Metadata::new(
                         TrackedData::DATA_FUSE,
                         MetaDataType::Integer,
@@ -1,17 +1,19 @@
 use std::sync::Arc;
 
-use pumpkin_data::entity::EntityType;
+use pumpkin_data::entity::{EntityType, MobCategory};
 use pumpkin_util::math::vector3::Vector3;
 use uuid::Uuid;
 
 use crate::{
     entity::{
         Entity, EntityBase,
+        boss::ender_dragon::EnderDragonEntity,
         decoration::{
             armor_stand::ArmorStandEntity, end_crystal::EndCrystalEntity, painting::PaintingEntity,
         },
         living::LivingEntity,
         mob::{drowned::DrownedEntity, zombie::ZombieEntity},
+        passive::{animal::AnimalEntity, villager::VillagerEntity},
     },
     world::World,
 };
@@ -27,9 +29,12 @@ pub async fn from_type(
     let mob: Arc<dyn EntityBase> = match entity_type.id {
         id if id == EntityType::ZOMBIE.id => ZombieEntity::make(entity).await,
         id if id == EntityType::DROWNED.id => DrownedEntity::make(entity).await,
+        id if id == EntityType::VILLAGER.id => VillagerEntity::make(entity).await,
         id if id == EntityType::ARMOR_STAND.id => Arc::new(ArmorStandEntity::new(entity)),
         id if id == EntityType::PAINTING.id => Arc::new(PaintingEntity::new(entity)),
         id if id == EntityType::END_CRYSTAL.id => Arc::new(EndCrystalEntity::new(entity)),
+        id if id == EntityType::ENDER_DRAGON.id => EnderDragonEntity::make(entity).await,
+        _ if entity_type.category == &MobCategory::CREATURE => AnimalEntity::make(entity).await,
         // Fallback Entity
         _ => {
             if entity_type.max_health.is_some() {
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::world::BlockAccessor;
+use rand::Rng;
+
+use crate::entity::EntityBase;
+
+/// How far chorus fruit can teleport its eater, like in vanilla.
+pub const CHORUS_FRUIT_TELEPORT_DIAMETER: f64 = 16.0;
+const TELEPORT_ATTEMPTS: usize = 16;
+
+/// Returns a random position around `origin`, with the height clamped to `min_y..=max_y`.
+#[must_use]
+pub fn random_teleport_target(
+    origin: Vector3<f64>,
+    diameter: f64,
+    min_y: i32,
+    max_y: i32,
+) -> Vector3<f64> {
+    let mut rng = rand::rng();
+    let mut offset = || (rng.random::<f64>() - 0.5) * diameter;
+    Vector3::new(
+        origin.x + offset(),
+        (origin.y + offset()).clamp(f64::from(min_y), f64::from(max_y)),
+        origin.z + offset(),
+    )
+}
+
+/// Moves `target` down onto the first solid block and returns it if an entity of `height` fits
+/// there without touching a fluid.
+pub async fn find_landing_spot(
+    block_accessor: &dyn BlockAccessor,
+    target: Vector3<f64>,
+    height: f32,
+    min_y: i32,
+) -> Option<Vector3<f64>> {
+    let mut position = BlockPos::floored(target.x, target.y, target.z);
+    while position.0.y > min_y
+        && !block_accessor
+            .get_block_state(&position.down())
+            .await
+            .is_solid()
+    {
+        position = position.down();
+    }
+    if !block_accessor
+        .get_block_state(&position.down())
+        .await
+        .is_solid()
+    {
+        return None;
+    }
+    for y in 0..height.ceil() as i32 {
+        let state = block_accessor.get_block_state(&position.up_height(y)).await;
+        if state.is_solid() || state.is_liquid() {
+            return None;
+        }
+    }
+    Some(Vector3::new(target.x, f64::from(position.0.y), target.z))
+}
+
+/// Teleports the entity to a random safe spot nearby, like eating chorus fruit does.
+///
+/// Returns whether a spot was found.
+pub async fn teleport_randomly(caller: Arc<dyn EntityBase>, diameter: f64) -> bool {
+    let entity = caller.get_entity();
+    let world = entity.world.clone();
+    let min_y = world.dimension.min_y;
+    let max_y = min_y + world.dimension.logical_height - 1;
+    let origin = entity.pos.load();
+    let height = entity.entity_dimension.load().height;
+
+    for _ in 0..TELEPORT_ATTEMPTS {
+        let target = random_teleport_target(origin, diameter, min_y, max_y);
+        let Some(destination) = find_landing_spot(world.as_ref(), target, height, min_y).await
+        else {
+            continue;
+        };
+        caller
+            .clone()
+            .teleport(destination, None, None, world.clone())
+            .await;
+        let category = if caller.get_player().is_some() {
+            SoundCategory::Players
+        } else {
+            SoundCategory::Neutral
+        };
+        world
+            .play_sound_fine(
+                Sound::ItemChorusFruitTeleport,
+                category,
+                &destination,
+                1.0,
+                1.0,
+            )
+            .await;
+        if let Some(living) = caller.get_living_entity() {
+            living.fall_distance.store(0.0);
+        }
+        return true;
+    }
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_data::{Block, dimension::Dimension};
+    use pumpkin_util::math::vector3::Vector3;
+    use pumpkin_world::generation::proto_chunk::ProtoChunk;
+    use pumpkin_world::item::ItemStack;
+
+    use super::{CHORUS_FRUIT_TELEPORT_DIAMETER, find_landing_spot, random_teleport_target};
+    use crate::entity::item_cooldown::ItemCooldownManager;
+
+    #[test]
+    fn chorus_fruit_teleports_to_safe_ground_and_cools_down() {
+        let mut chunk = ProtoChunk::new(0, 0, &Dimension::OVERWORLD, Block::STONE.default_state, 0);
+        for x in 0..16 {
+            for z in 0..16 {
+                chunk.set_block_state(x, 63, z, Block::STONE.default_state);
+            }
+        }
+        let origin = Vector3::new(8.0, 64.0, 8.0);
+        let min_y = Dimension::OVERWORLD.min_y;
+
+        for _ in 0..32 {
+            let target = random_teleport_target(
+                origin,
+                CHORUS_FRUIT_TELEPORT_DIAMETER,
+                min_y,
+                min_y + Dimension::OVERWORLD.logical_height - 1,
+            );
+            assert!((target.x - origin.x).abs() <= 8.0);
+            assert!((target.z - origin.z).abs() <= 8.0);
+            let above_floor = Vector3::new(target.x, 70.5, target.z);
+            let landing =
+                futures::executor::block_on(find_landing_spot(&chunk, above_floor, 1.8, min_y));
+            assert_eq!(landing, Some(Vector3::new(target.x, 64.0, target.z)));
+        }
+
+        // There is no ground below the floor
+        let below_floor = Vector3::new(8.0, 60.0, 8.0);
+        let landing =
+            futures::executor::block_on(find_landing_spot(&chunk, below_floor, 1.8, min_y));
+        assert_eq!(landing, None);
+
+        let mut cooldowns = ItemCooldownManager::default();
+        let chorus_fruit = ItemCooldownManager::group_of(&ItemStack::new(1, &Item::CHORUS_FRUIT));
+        cooldowns.set(chorus_fruit.clone(), 20);
+        assert!(cooldowns.is_cooling_down(&chorus_fruit));
+        for _ in 0..19 {
+            assert!(cooldowns.tick().is_empty());
+        }
+        assert_eq!(cooldowns.tick(), vec![chorus_fruit.clone()]);
+        assert!(!cooldowns.is_cooling_down(&chorus_fruit));
+    }
+}
@@ -1,6 +1,7 @@
 pub mod armor_stand;
 pub mod axe;
 pub mod bucket;
+pub mod chorus_fruit;
 pub mod dye;
 pub mod egg;
 pub mod end_crystal;
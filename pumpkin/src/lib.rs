@@ -1,11 +1,15 @@
 // Not warn event sending macros
 #![allow(unused_labels)]
 
+use crate::data::{banned_ip_data::BANNED_IP_LIST, banned_player_data::BANNED_PLAYER_LIST};
 use crate::logging::{GzipRollingLogger, ReadlineLogWrapper};
 use crate::net::DisconnectReason;
 use crate::net::bedrock::BedrockClient;
+use crate::net::connection_throttle::ConnectionThrottle;
 use crate::net::java::JavaClient;
-use crate::net::{lan_broadcast::LANBroadcast, query, rcon::RCONServer};
+use crate::net::packet_dump::PacketDump;
+use crate::net::proxy_protocol::{self, ProxyProtocol};
+use crate::net::{lan_broadcast::LANBroadcast, query, rcon::RCONServer, status_endpoint};
 use crate::server::{Server, ticker::Ticker};
 use log::{Level, LevelFilter};
 use net::authentication::fetch_mojang_public_keys;
@@ -22,7 +26,7 @@ use std::io::{Cursor, IsTerminal, stdin};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{net::SocketAddr, sync::LazyLock};
 use tokio::net::{TcpListener, UdpSocket};
 use tokio::select;
@@ -167,6 +171,8 @@ pub struct PumpkinServer {
     pub server: Arc<Server>,
     pub tcp_listener: Option<TcpListener>,
     pub udp_socket: Option<Arc<UdpSocket>>,
+    pub connection_throttle: ConnectionThrottle,
+    pub proxy_protocol: ProxyProtocol,
 }
 
 impl PumpkinServer {
@@ -176,6 +182,10 @@ impl PumpkinServer {
     ) -> Self {
         let server = Server::new(basic_config, advanced_config).await;
 
+        // Load the ban lists now, so a broken file stops the startup instead of the first login
+        LazyLock::force(&BANNED_PLAYER_LIST);
+        LazyLock::force(&BANNED_IP_LIST);
+
         let rcon = server.advanced_config.networking.rcon.clone();
 
         let mut ticker = Ticker::new();
@@ -205,6 +215,15 @@ impl PumpkinServer {
             });
         }
 
+        let status_endpoint = &server.advanced_config.networking.status_endpoint;
+        if status_endpoint.enabled {
+            log::info!("Status endpoint is enabled. Starting...");
+            server.spawn_task(status_endpoint::start_status_endpoint(
+                server.clone(),
+                status_endpoint.address,
+            ));
+        }
+
         let mut tcp_listener = None;
 
         if server.basic_config.java_edition {
@@ -263,10 +282,16 @@ impl PumpkinServer {
             ));
         }
 
+        let connection_throttle =
+            ConnectionThrottle::new(&server.advanced_config.networking.connection_throttle);
+        let proxy_protocol = ProxyProtocol::new(&server.advanced_config.networking.proxy_protocol);
+
         Self {
             server: server.clone(),
             tcp_listener,
             udp_socket,
+            connection_throttle,
+            proxy_protocol,
         }
     }
 
@@ -352,6 +377,21 @@ impl PumpkinServer {
             tcp_result = resolve_some(self.tcp_listener.as_ref(), |listener| listener.accept()) => {
                 match tcp_result {
                     Ok((connection, client_addr)) => {
+                        let formatted_address = if self.server.basic_config.scrub_ips {
+                            scrub_address(&format!("{client_addr}"))
+                        } else {
+                            format!("{client_addr}")
+                        };
+
+                        if !self.connection_throttle.allow_address(client_addr.ip(), Instant::now()) {
+                            log::debug!("Dropped connection from {formatted_address}: too many connections from this address");
+                            return true;
+                        }
+                        let Some(handshake_permit) = self.connection_throttle.try_start_handshake(client_addr.ip()) else {
+                            log::debug!("Dropped connection from {formatted_address}: too many clients are handshaking");
+                            return true;
+                        };
+
                         if let Err(e) = connection.set_nodelay(true) {
                             log::warn!("Failed to set TCP_NODELAY: {e}");
                         }
@@ -359,20 +399,37 @@ impl PumpkinServer {
                         let client_id = *master_client_id_counter;
                         *master_client_id_counter += 1;
 
-                        let formatted_address = if self.server.basic_config.scrub_ips {
-                            scrub_address(&format!("{client_addr}"))
-                        } else {
-                            format!("{client_addr}")
-                        };
                         log::debug!("Accepted connection from Java Edition: {formatted_address} (id {client_id})");
 
-                        let mut java_client = JavaClient::new(connection, client_addr, client_id);
-                        java_client.start_outgoing_packet_task();
-                        let java_client = Arc::new(java_client);
+                        let expects_proxy_header = self.proxy_protocol.expects_header(client_addr.ip());
+                        let packet_dump = PacketDump::for_client(
+                            &self.server.advanced_config.networking.packet_dump,
+                            client_id,
+                        )
+                        .map(Arc::new);
 
                         let server_clone = self.server.clone();
 
                         tasks.spawn(async move {
+                            let mut connection = connection;
+                            let mut client_addr = client_addr;
+                            if expects_proxy_header {
+                                match proxy_protocol::read_header(&mut connection).await {
+                                    Ok(Some(source)) => client_addr = source,
+                                    Ok(None) => {}
+                                    Err(e) => {
+                                        log::debug!("Closed connection {client_id}: {e}");
+                                        return;
+                                    }
+                                }
+                            }
+
+                            let mut java_client = JavaClient::new(connection, client_addr, client_id);
+                            *java_client.handshake_permit.get_mut() = handshake_permit;
+                            java_client.packet_dump = packet_dump;
+                            java_client.start_outgoing_packet_task();
+                            let java_client = Arc::new(java_client);
+
                             java_client.process_packets(&server_clone).await;
                             java_client.close();
                             java_client.await_tasks().await;
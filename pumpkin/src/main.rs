@@ -103,8 +103,7 @@ async fn main() {
     console_subscriber::init();
     let time = Instant::now();
 
-    let exec_dir = std::env::current_dir().unwrap();
-    let config_dir = exec_dir.join("config");
+    let config_dir = pumpkin_config::config_dir();
 
     let basic_config = BasicConfiguration::load(&config_dir);
     let advanced_config = AdvancedConfiguration::load(&config_dir);
@@ -6,12 +6,14 @@ use std::{
 
 use pumpkin_protocol::bedrock::{
     RakReliability,
-    client::raknet::connection::{CConnectedPong, CConnectionRequestAccepted},
-    server::raknet::connection::{SConnectedPing, SConnectionRequest, SNewIncomingConnection},
+    client::raknet::connection::{CConnectedPing, CConnectedPong, CConnectionRequestAccepted},
+    server::raknet::connection::{
+        SConnectedPing, SConnectedPong, SConnectionRequest, SNewIncomingConnection,
+    },
 };
 use pumpkin_protocol::{codec::u24, serial::PacketRead};
 
-use crate::net::bedrock::BedrockClient;
+use crate::net::{bedrock::BedrockClient, latency::Latency};
 
 impl BedrockClient {
     pub fn is_connection_request(reader: &mut Cursor<&[u8]>) -> Result<SConnectionRequest, Error> {
@@ -60,4 +62,60 @@ impl BedrockClient {
         //    println!("ping procedet");
         //});
     }
+
+    /// Sends a ping, the client answers with a `SConnectedPong` which is used to measure the latency.
+    pub async fn send_connected_ping(&self) {
+        self.send_framed_packet(
+            &CConnectedPing::new(UNIX_EPOCH.elapsed().unwrap().as_millis() as u64),
+            RakReliability::Unreliable,
+        )
+        .await;
+    }
+
+    pub fn handle_connected_pong(&self, packet: &SConnectedPong) {
+        record_round_trip(
+            &self.latency,
+            packet,
+            UNIX_EPOCH.elapsed().unwrap().as_millis() as u64,
+        );
+    }
+}
+
+fn record_round_trip(latency: &Latency, packet: &SConnectedPong, now: u64) {
+    latency.record(now.saturating_sub(packet.ping_time) as u32);
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use pumpkin_protocol::{
+        bedrock::{
+            client::raknet::connection::CConnectedPing, server::raknet::connection::SConnectedPong,
+        },
+        serial::{PacketRead, PacketWrite},
+    };
+
+    use super::record_round_trip;
+    use crate::net::latency::Latency;
+
+    /// The client echoes our ping time and appends its own time.
+    fn pong_for(ping_time: u64, pong_time: u64) -> SConnectedPong {
+        let mut buf = Vec::new();
+        CConnectedPing::new(ping_time).write(&mut buf).unwrap();
+        buf.extend_from_slice(&pong_time.to_be_bytes());
+        SConnectedPong::read(&mut Cursor::new(buf.as_slice())).unwrap()
+    }
+
+    #[test]
+    fn ping_exchange_measures_latency() {
+        let latency = Latency::default();
+
+        record_round_trip(&latency, &pong_for(1_000, 50), 1_080);
+        assert_eq!(latency.get(), 80);
+
+        // Later measurements are smoothed with the previous value
+        record_round_trip(&latency, &pong_for(16_000, 15_050), 16_040);
+        assert_eq!(latency.get(), 70);
+    }
 }
@@ -1,6 +1,9 @@
 use crate::net::authentication::MOJANG_BEDROCK_PUBLIC_KEY_BASE64;
 use crate::{
-    net::{ClientPlatform, DisconnectReason, GameProfile, bedrock::BedrockClient},
+    net::{
+        ClientPlatform, DisconnectReason, GameProfile, bedrock::BedrockClient, can_not_join,
+        is_server_full, login_denied_by_plugins,
+    },
     server::Server,
 };
 use pumpkin_config::networking::compression::CompressionInfo;
@@ -18,6 +21,7 @@ use pumpkin_protocol::{
     codec::var_uint::VarUInt,
 };
 use pumpkin_util::jwt::{AuthError, verify_chain};
+use pumpkin_util::text::TextComponent;
 use pumpkin_world::CURRENT_BEDROCK_MC_VERSION;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -91,6 +95,22 @@ impl BedrockClient {
             profile_actions: None,
         };
 
+        if is_server_full(server).await {
+            let message = TextComponent::translate("multiplayer.disconnect.server_full", []);
+            self.kick(DisconnectReason::ServerFull, message.get_text())
+                .await;
+            return Ok(());
+        }
+        if let Some(reason) = can_not_join(&profile, &self.address, server).await {
+            self.kick(DisconnectReason::NotAllowed, reason.get_text())
+                .await;
+            return Ok(());
+        }
+        if let Some(reason) = login_denied_by_plugins(&profile, &self.address).await {
+            self.kick(DisconnectReason::Kicked, reason.get_text()).await;
+            return Ok(());
+        }
+
         //let raw_token = unsafe { String::from_utf8_unchecked(packet.raw_token) };
         //let raw_token: Vec<&str> = raw_token.split('.').collect();
         // We dont care about the validation, we just want to get the data
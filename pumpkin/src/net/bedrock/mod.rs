@@ -29,7 +29,8 @@ use pumpkin_protocol::{
             player_auth_input::SPlayerAuthInput,
             raknet::{
                 connection::{
-                    SConnectedPing, SConnectionRequest, SDisconnect, SNewIncomingConnection,
+                    SConnectedPing, SConnectedPong, SConnectionRequest, SDisconnect,
+                    SNewIncomingConnection,
                 },
                 open_connection::{SOpenConnectionRequest1, SOpenConnectionRequest2},
                 unconnected_ping::SUnconnectedPing,
@@ -56,8 +57,14 @@ use tokio_util::task::TaskTracker;
 pub mod connection;
 pub mod login;
 pub mod open_connection;
+pub mod receive_window;
 pub mod unconnected;
-use crate::{entity::player::Player, net::DisconnectReason, server::Server};
+use crate::{
+    entity::player::Player,
+    net::{DisconnectReason, latency::Latency},
+    server::Server,
+};
+use receive_window::ReceiveWindow;
 
 pub struct BedrockClient {
     socket: Arc<UdpSocket>,
@@ -67,6 +74,8 @@ pub struct BedrockClient {
     /// All Bedrock clients
     /// This list is used to remove the client if the connection gets closed
     pub be_clients: Arc<Mutex<HashMap<SocketAddr, Arc<Self>>>>,
+    /// The round trip time measured with RakNet connected pings.
+    pub latency: Latency,
 
     tasks: TaskTracker,
     outgoing_packet_queue_send: Sender<Bytes>,
@@ -93,7 +102,8 @@ pub struct BedrockClient {
 
     /// Store Fragments until the packet is complete
     compounds: Arc<Mutex<HashMap<u16, Vec<Option<Frame>>>>>,
-    //input_sequence_number: AtomicU32,
+    /// Tracks received datagrams for ACKs and puts ordered frames back in order
+    receive_window: Mutex<ReceiveWindow>,
 }
 
 impl BedrockClient {
@@ -109,6 +119,7 @@ impl BedrockClient {
             player: Mutex::new(None),
             address,
             be_clients,
+            latency: Latency::default(),
             network_writer: Arc::new(Mutex::new(UDPNetworkEncoder::new())),
             network_reader: Mutex::new(UDPNetworkDecoder::new()),
             tasks: TaskTracker::new(),
@@ -121,9 +132,9 @@ impl BedrockClient {
             output_sequenced_index: AtomicU32::new(0),
             output_ordered_index: AtomicU32::new(0),
             compounds: Arc::new(Mutex::new(HashMap::new())),
+            receive_window: Mutex::new(ReceiveWindow::default()),
             closed: Arc::new(AtomicBool::new(false)),
             close_interrupt: Arc::new(Notify::new()),
-            //input_sequence_number: AtomicU32::new(0),
         }
     }
 
@@ -386,12 +397,21 @@ impl BedrockClient {
     pub async fn send_ack(&self, ack: &Ack) {
         let mut packet_buf = Vec::new();
         ack.write(&mut packet_buf).unwrap();
+        self.send_acknowledgement(&packet_buf).await;
+    }
 
+    pub async fn send_nack(&self, nack: &Ack) {
+        let mut packet_buf = Vec::new();
+        nack.write_nack(&mut packet_buf).unwrap();
+        self.send_acknowledgement(&packet_buf).await;
+    }
+
+    async fn send_acknowledgement(&self, packet_buf: &[u8]) {
         if let Err(err) = self
             .network_writer
             .lock()
             .await
-            .write_packet(&packet_buf, self.address, &self.socket)
+            .write_packet(packet_buf, self.address, &self.socket)
             .await
         {
             log::warn!("Failed to send packet to client: {err}");
@@ -426,19 +446,53 @@ impl BedrockClient {
     fn handle_ack(_ack: &Ack) {}
 
     async fn handle_frame_set(self: &Arc<Self>, server: &Arc<Server>, frame_set: FrameSet) {
+        let frames: Vec<Frame> = {
+            let mut window = self.receive_window.lock().await;
+            if window.receive_datagram(frame_set.sequence.0) {
+                frame_set
+                    .frames
+                    .into_iter()
+                    .filter(|frame| {
+                        !frame.reliability.is_reliable()
+                            || window.receive_reliable(frame.reliable_number)
+                    })
+                    .collect()
+            } else {
+                // A datagram we already handled, it only gets acknowledged again
+                Vec::new()
+            }
+        };
         // TODO: Send all ACKs in short intervals in batches
-        self.send_ack(&Ack::new(vec![frame_set.sequence.0])).await;
-        // TODO
-        for frame in frame_set.frames {
-            self.handle_frame(server, frame).await.unwrap();
+        self.send_acknowledgements().await;
+
+        for frame in frames {
+            let Some(frame) = self.reassemble_frame(frame).await else {
+                continue;
+            };
+            let ready = self.receive_window.lock().await.order(frame);
+            for frame in ready {
+                if let Err(error) = self.handle_frame(server, frame).await {
+                    log::warn!("Bedrock: Failed to handle frame: {error}");
+                }
+            }
         }
     }
 
-    async fn handle_frame(
-        self: &Arc<Self>,
-        server: &Arc<Server>,
-        mut frame: Frame,
-    ) -> Result<(), Error> {
+    async fn send_acknowledgements(&self) {
+        let (acks, nacks) = {
+            let mut window = self.receive_window.lock().await;
+            (window.take_acks(), window.take_nacks())
+        };
+        if !acks.is_empty() {
+            self.send_ack(&Ack::new(acks)).await;
+        }
+        if !nacks.is_empty() {
+            self.send_nack(&Ack::new(nacks)).await;
+        }
+    }
+
+    /// Collects the fragments of split frames, returns the whole frame once every fragment arrived.
+    async fn reassemble_frame(&self, mut frame: Frame) -> Option<Frame> {
         if frame.split_size > 0 {
             let fragment_index = frame.split_index as usize;
             let compound_id = frame.split_id;
@@ -450,11 +504,11 @@ impl BedrockClient {
                 vec
             });
 
-            entry[fragment_index] = Some(frame);
+            *entry.get_mut(fragment_index)? = Some(frame);
 
             // Check if all fragments are received
             if entry.iter().any(Option::is_none) {
-                return Ok(());
+                return None;
             }
 
             let mut frames = compounds.remove(&compound_id).unwrap();
@@ -476,7 +530,14 @@ impl BedrockClient {
             frame.payload = merged;
             frame.split_size = 0;
         }
+        Some(frame)
+    }
 
+    async fn handle_frame(
+        self: &Arc<Self>,
+        server: &Arc<Server>,
+        frame: Frame,
+    ) -> Result<(), Error> {
         let mut payload = Cursor::new(frame.payload);
         let id = u8::read(&mut payload)?;
         self.handle_raknet_packet(server, i32::from(id), payload)
@@ -577,6 +638,9 @@ impl BedrockClient {
                 self.handle_connected_ping(SConnectedPing::read(reader)?)
                     .await;
             }
+            SConnectedPong::PACKET_ID => {
+                self.handle_connected_pong(&SConnectedPong::read(reader)?);
+            }
             SDisconnect::PACKET_ID => {
                 self.close().await;
             }
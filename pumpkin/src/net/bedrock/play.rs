@@ -30,7 +30,7 @@ use pumpkin_util::{
 };
 
 use crate::{
-    command::CommandSender,
+    command::{CommandSender, cooldown},
     entity::{EntityBase, player::Player},
     net::{DisconnectReason, bedrock::BedrockClient},
     plugin::player::{player_chat::PlayerChatEvent, player_command_send::PlayerCommandSendEvent},
@@ -208,6 +208,9 @@ impl BedrockClient {
 
             'after: {
                 let command = event.command;
+                if !cooldown::check_cooldown(player, server, &command).await {
+                    break 'after;
+                }
                 let command_clone = command.clone();
                 // Some commands can take a long time to execute. If they do, they block packet processing for the player.
                 // That's why we will spawn a task instead.
@@ -0,0 +1,234 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use pumpkin_protocol::bedrock::frame_set::Frame;
+
+/// Gaps larger than this are not NACKed, the client most likely restarted its sequence numbers.
+const MAX_NACK_GAP: u32 = 512;
+/// How far behind the newest datagram we still wait for skipped ones.
+const MAX_TRACKED_MISSING: u32 = 2048;
+/// How far ahead of the oldest missing frame reliable and ordered frames are still accepted,
+/// anything further ahead is dropped so a client can't make us buffer without limit.
+const MAX_WINDOW_SIZE: u32 = 2048;
+/// Sequence numbers and frame indices are 24 bit and wrap around.
+const U24_MASK: u32 = (1 << 24) - 1;
+
+/// How many steps `index` is ahead of `base`, negative if it is behind, modulo 2^24.
+fn u24_distance(base: u32, index: u32) -> i32 {
+    let distance = index.wrapping_sub(base) & U24_MASK;
+    if distance > U24_MASK / 2 {
+        distance as i32 - (1 << 24)
+    } else {
+        distance as i32
+    }
+}
+
+fn u24_next(index: u32) -> u32 {
+    index.wrapping_add(1) & U24_MASK
+}
+
+/// Keeps track of what a client sent us on the datagram layer.
+///
+/// It remembers which datagrams have to be acknowledged, drops duplicated reliable frames and
+/// holds back ordered frames until all frames before them arrived.
+#[derive(Default)]
+pub struct ReceiveWindow {
+    /// The datagram sequence number we expect next
+    next_sequence: u32,
+    /// Datagrams we received but did not acknowledge yet
+    pending_acks: BTreeSet<u32>,
+    /// Datagrams that were skipped and were not received yet, oldest first
+    missing: VecDeque<u32>,
+    /// Skipped datagrams we did not NACK yet
+    pending_nacks: BTreeSet<u32>,
+    /// Every reliable frame before this number was received
+    reliable_base: u32,
+    /// Reliable frames received after `reliable_base`
+    received_reliable: BTreeSet<u32>,
+    channels: HashMap<u8, OrderingChannel>,
+}
+
+#[derive(Default)]
+struct OrderingChannel {
+    next_order_index: u32,
+    highest_sequence_index: u32,
+    /// Ordered frames that arrived before the frames in front of them
+    waiting: BTreeMap<u32, Frame>,
+}
+
+impl ReceiveWindow {
+    /// Registers a received datagram, returns `false` if it was already received before.
+    pub fn receive_datagram(&mut self, sequence: u32) -> bool {
+        let sequence = sequence & U24_MASK;
+        // Resent datagrams are acknowledged again, our first ACK may have been lost
+        self.pending_acks.insert(sequence);
+        let ahead = u24_distance(self.next_sequence, sequence);
+        if ahead < 0 {
+            self.pending_nacks.remove(&sequence);
+            let Some(index) = self.missing.iter().position(|&missing| missing == sequence) else {
+                return false;
+            };
+            self.missing.remove(index);
+            return true;
+        }
+        if ahead as u32 <= MAX_NACK_GAP {
+            let skipped = (0..ahead as u32).map(|offset| (self.next_sequence + offset) & U24_MASK);
+            self.missing.extend(skipped.clone());
+            self.pending_nacks.extend(skipped);
+        }
+        self.next_sequence = u24_next(sequence);
+        // Give up on datagrams that are too old, the client won't resend them anymore
+        while let Some(&oldest) = self.missing.front() {
+            if u24_distance(oldest, self.next_sequence) as u32 <= MAX_TRACKED_MISSING {
+                break;
+            }
+            self.missing.pop_front();
+        }
+        true
+    }
+
+    /// Returns the datagrams to acknowledge, in ascending order.
+    pub fn take_acks(&mut self) -> Vec<u32> {
+        std::mem::take(&mut self.pending_acks).into_iter().collect()
+    }
+
+    /// Returns the skipped datagrams the client should send again, in ascending order.
+    pub fn take_nacks(&mut self) -> Vec<u32> {
+        std::mem::take(&mut self.pending_nacks)
+            .into_iter()
+            .collect()
+    }
+
+    /// Registers a reliable frame, returns `false` if it is a duplicate or outside the window and
+    /// should be dropped.
+    pub fn receive_reliable(&mut self, reliable_number: u32) -> bool {
+        let reliable_number = reliable_number & U24_MASK;
+        let ahead = u24_distance(self.reliable_base, reliable_number);
+        if ahead < 0
+            || ahead as u32 >= MAX_WINDOW_SIZE
+            || !self.received_reliable.insert(reliable_number)
+        {
+            return false;
+        }
+        while self.received_reliable.remove(&self.reliable_base) {
+            self.reliable_base = u24_next(self.reliable_base);
+        }
+        true
+    }
+
+    /// Returns the frames that can be handled now that `frame` arrived.
+    ///
+    /// Ordered frames are only handed out once every frame before them on their channel was,
+    /// sequenced frames are dropped if a newer one was already handled.
+    pub fn order(&mut self, frame: Frame) -> Vec<Frame> {
+        if !frame.reliability.is_ordered() {
+            return vec![frame];
+        }
+        let channel = self.channels.entry(frame.order_channel).or_default();
+        let ahead = u24_distance(channel.next_order_index, frame.order_index);
+        if frame.reliability.is_sequenced() {
+            if ahead < 0 || u24_distance(channel.highest_sequence_index, frame.sequence_index) < 0 {
+                return Vec::new();
+            }
+            channel.highest_sequence_index = u24_next(frame.sequence_index);
+            return vec![frame];
+        }
+
+        if ahead < 0 || ahead as u32 >= MAX_WINDOW_SIZE {
+            return Vec::new();
+        }
+        channel.waiting.insert(frame.order_index & U24_MASK, frame);
+        let mut ready = Vec::new();
+        while let Some(frame) = channel.waiting.remove(&channel.next_order_index) {
+            channel.next_order_index = u24_next(channel.next_order_index);
+            // Sequenced frames start over once the next ordered frame arrives
+            channel.highest_sequence_index = 0;
+            ready.push(frame);
+        }
+        ready
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::bedrock::RakReliability;
+    use pumpkin_protocol::bedrock::frame_set::Frame;
+
+    use super::{MAX_WINDOW_SIZE, ReceiveWindow, U24_MASK};
+
+    fn ordered_frame(reliable_number: u32, order_index: u32) -> Frame {
+        Frame {
+            reliability: RakReliability::ReliableOrdered,
+            payload: vec![order_index as u8],
+            reliable_number,
+            order_index,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn out_of_order_frames_are_delivered_in_order() {
+        let mut window = ReceiveWindow::default();
+
+        assert!(window.receive_datagram(0));
+        assert!(window.receive_datagram(2));
+        assert_eq!(window.take_acks(), vec![0, 2]);
+        assert_eq!(window.take_nacks(), vec![1]);
+        // The skipped datagram is resent after the NACK
+        assert!(window.receive_datagram(1));
+        assert!(!window.receive_datagram(1));
+        assert_eq!(window.take_acks(), vec![1]);
+        assert!(window.take_nacks().is_empty());
+
+        let mut delivered = Vec::new();
+        for (reliable_number, order_index) in [(2, 2), (0, 0), (0, 0), (1, 1), (3, 3)] {
+            if !window.receive_reliable(reliable_number) {
+                continue;
+            }
+            for frame in window.order(ordered_frame(reliable_number, order_index)) {
+                delivered.push(frame.payload[0]);
+            }
+            if order_index == 2 {
+                // Frame 2 has to wait for the frames in front of it
+                assert!(delivered.is_empty());
+            }
+        }
+        assert_eq!(delivered, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn indices_wrap_around() {
+        let mut window = ReceiveWindow {
+            next_sequence: U24_MASK - 1,
+            ..Default::default()
+        };
+        assert!(window.receive_datagram(U24_MASK - 1));
+        window.take_acks();
+        assert!(window.take_nacks().is_empty());
+
+        assert!(window.receive_datagram(1));
+        assert_eq!(window.take_nacks(), vec![0, U24_MASK]);
+        assert!(window.receive_datagram(U24_MASK));
+        assert!(!window.receive_datagram(U24_MASK - 1));
+        assert!(!window.receive_datagram(1));
+
+        window.reliable_base = U24_MASK;
+        window.channels.entry(0).or_default().next_order_index = U24_MASK;
+        let mut delivered = Vec::new();
+        for (reliable_number, order_index) in [(0, 0), (U24_MASK, U24_MASK), (0, 0)] {
+            if window.receive_reliable(reliable_number) {
+                delivered.extend(window.order(ordered_frame(reliable_number, order_index)));
+            }
+        }
+        let delivered: Vec<_> = delivered.iter().map(|frame| frame.order_index).collect();
+        assert_eq!(delivered, vec![U24_MASK, 0]);
+    }
+
+    #[test]
+    fn frames_outside_the_window_are_dropped() {
+        let mut window = ReceiveWindow::default();
+        assert!(!window.receive_reliable(MAX_WINDOW_SIZE));
+        assert!(window.receive_reliable(MAX_WINDOW_SIZE - 1));
+        assert!(window.order(ordered_frame(0, MAX_WINDOW_SIZE)).is_empty());
+        assert!(window.channels[&0].waiting.is_empty());
+    }
+}
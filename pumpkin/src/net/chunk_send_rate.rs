@@ -0,0 +1,71 @@
+/// The lowest rate a client may ask chunks to be sent with.
+pub const MIN_CHUNKS_PER_TICK: f32 = 0.01;
+/// The highest rate a client may ask chunks to be sent with.
+pub const MAX_CHUNKS_PER_TICK: f32 = 64.0;
+
+/// Spreads the chunks sent to a client over the ticks, at the rate it asks for in its chunk
+/// batch acknowledgements.
+///
+/// Rates below one chunk per tick are honored by saving up for a chunk over several ticks.
+pub struct ChunkSendRate {
+    chunks_per_tick: f32,
+    quota: f32,
+}
+
+impl ChunkSendRate {
+    #[must_use]
+    pub const fn new(chunks_per_tick: f32) -> Self {
+        Self {
+            chunks_per_tick,
+            quota: 0.0,
+        }
+    }
+
+    /// The rate chunks are currently sent with.
+    #[must_use]
+    pub const fn chunks_per_tick(&self) -> f32 {
+        self.chunks_per_tick
+    }
+
+    /// Applies the rate a client asked for, which is clamped to sane values.
+    pub fn set_chunks_per_tick(&mut self, chunks_per_tick: f32) {
+        self.chunks_per_tick = if chunks_per_tick.is_nan() {
+            MIN_CHUNKS_PER_TICK
+        } else {
+            chunks_per_tick.clamp(MIN_CHUNKS_PER_TICK, MAX_CHUNKS_PER_TICK)
+        };
+    }
+
+    /// Called once per tick, returns how many of the `queued` chunks may be sent this tick.
+    pub fn take(&mut self, queued: usize) -> usize {
+        // Don't save up a burst while waiting, at most one tick worth of chunks
+        self.quota = (self.quota + self.chunks_per_tick).min(self.chunks_per_tick.max(1.0));
+        let count = (self.quota.floor() as usize).min(queued);
+        self.quota -= count as f32;
+        count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChunkSendRate, MAX_CHUNKS_PER_TICK, MIN_CHUNKS_PER_TICK};
+
+    #[test]
+    fn chunks_are_sent_at_the_requested_rate() {
+        let mut rate = ChunkSendRate::new(16.0);
+        assert_eq!(rate.take(100), 16);
+        assert_eq!(rate.take(3), 3);
+
+        // A slow client gets a chunk every fourth tick
+        rate.set_chunks_per_tick(0.25);
+        let sent: Vec<usize> = (0..8).map(|_| rate.take(100)).collect();
+        assert_eq!(sent.iter().sum::<usize>(), 2);
+        assert!(sent.iter().all(|count| *count <= 1));
+
+        rate.set_chunks_per_tick(f32::NAN);
+        assert_eq!(rate.chunks_per_tick(), MIN_CHUNKS_PER_TICK);
+        rate.set_chunks_per_tick(f32::INFINITY);
+        assert_eq!(rate.chunks_per_tick(), MAX_CHUNKS_PER_TICK);
+        assert_eq!(rate.take(1000), 64);
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::VecDeque;
+use std::net::IpAddr;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+use pumpkin_config::networking::connection_throttle::ConnectionThrottleConfig;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// How many addresses are remembered, the least recently seen ones are forgotten first.
+const TRACKED_ADDRESSES: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+
+/// A trusted IP address or CIDR range, like the one of a proxy.
+pub struct IpRange {
+    network: IpAddr,
+    prefix_len: u32,
+}
+
+impl IpRange {
+    #[must_use]
+    pub fn parse(range: &str) -> Option<Self> {
+        let (address, prefix_len) = match range.trim().split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len.parse().ok()?)),
+            None => (range.trim(), None),
+        };
+        let network: IpAddr = address.parse().ok()?;
+        let max_prefix_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = prefix_len.unwrap_or(max_prefix_len);
+        (prefix_len <= max_prefix_len).then_some(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    #[must_use]
+    pub fn contains(&self, address: IpAddr) -> bool {
+        // Dual stack sockets report IPv4 clients as mapped IPv6 addresses
+        let address = match address {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(address, IpAddr::V4),
+            IpAddr::V4(_) => address,
+        };
+        match (self.network, address) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix_len).unwrap_or(0);
+                u32::from(network) & mask == u32::from(address) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix_len).unwrap_or(0);
+                u128::from(network) & mask == u128::from(address) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Decides whether a newly accepted connection may be handled or should be dropped right away.
+pub struct ConnectionThrottle {
+    max_connections_per_ip: usize,
+    window: Duration,
+    /// Recent connection timestamps of every address, oldest first.
+    recent_connections: Mutex<LruCache<IpAddr, VecDeque<Instant>>>,
+    /// One permit per client that has not reached the play state yet.
+    handshaking: Option<Arc<Semaphore>>,
+    /// Addresses which bypass every limit.
+    allowlist: Vec<IpRange>,
+}
+
+impl ConnectionThrottle {
+    #[must_use]
+    pub fn new(config: &ConnectionThrottleConfig) -> Self {
+        let max_connections_per_ip = if config.enabled {
+            config.max_connections_per_ip as usize
+        } else {
+            0
+        };
+        let handshaking = (config.enabled && config.max_handshaking > 0)
+            .then(|| Arc::new(Semaphore::new(config.max_handshaking as usize)));
+        let allowlist = config
+            .allowlist
+            .iter()
+            .filter_map(|range| {
+                let parsed = IpRange::parse(range);
+                if parsed.is_none() {
+                    log::warn!("Ignoring invalid connection throttle allowlist entry {range}");
+                }
+                parsed
+            })
+            .collect();
+        Self {
+            max_connections_per_ip,
+            window: Duration::from_millis(config.window_ms),
+            recent_connections: Mutex::new(LruCache::new(TRACKED_ADDRESSES)),
+            handshaking,
+            allowlist,
+        }
+    }
+
+    /// Whether `address` is trusted and therefore never throttled.
+    pub fn is_allowlisted(&self, address: IpAddr) -> bool {
+        self.allowlist.iter().any(|range| range.contains(address))
+    }
+
+    /// Records a connection from `address`, returns false if it exceeds the per IP limit.
+    pub fn allow_address(&self, address: IpAddr, now: Instant) -> bool {
+        if self.max_connections_per_ip == 0 || self.is_allowlisted(address) {
+            return true;
+        }
+
+        let mut recent_connections = self.recent_connections.lock().unwrap();
+        let timestamps = recent_connections.get_or_insert_mut(address, VecDeque::new);
+        while timestamps
+            .front()
+            .is_some_and(|timestamp| now.duration_since(*timestamp) >= self.window)
+        {
+            timestamps.pop_front();
+        }
+        if timestamps.len() >= self.max_connections_per_ip {
+            return false;
+        }
+        timestamps.push_back(now);
+        true
+    }
+
+    /// Reserves a handshaking slot, which is freed once the permit is dropped.
+    ///
+    /// Returns `Some(None)` if the amount of handshaking clients is not limited or `address` is
+    /// allowlisted and `None` if all slots are taken.
+    pub fn try_start_handshake(&self, address: IpAddr) -> Option<Option<OwnedSemaphorePermit>> {
+        if self.is_allowlisted(address) {
+            return Some(None);
+        }
+        match &self.handshaking {
+            Some(handshaking) => handshaking.clone().try_acquire_owned().ok().map(Some),
+            None => Some(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::time::{Duration, Instant};
+
+    use pumpkin_config::networking::connection_throttle::ConnectionThrottleConfig;
+
+    use super::ConnectionThrottle;
+
+    #[test]
+    fn per_ip_limit() {
+        let throttle = ConnectionThrottle::new(&ConnectionThrottleConfig {
+            enabled: true,
+            max_connections_per_ip: 2,
+            window_ms: 1000,
+            max_handshaking: 1,
+            allowlist: Vec::new(),
+        });
+        let address = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let now = Instant::now();
+
+        assert!(throttle.allow_address(address, now));
+        assert!(throttle.allow_address(address, now));
+        assert!(!throttle.allow_address(address, now));
+        assert!(throttle.allow_address(other, now));
+        assert!(throttle.allow_address(address, now + Duration::from_secs(1)));
+
+        let permit = throttle.try_start_handshake(address);
+        assert!(matches!(permit, Some(Some(_))));
+        assert!(throttle.try_start_handshake(address).is_none());
+        drop(permit);
+        assert!(throttle.try_start_handshake(address).is_some());
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let throttle = ConnectionThrottle::new(&ConnectionThrottleConfig::default());
+        let address = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let now = Instant::now();
+        for _ in 0..16 {
+            assert!(throttle.allow_address(address, now));
+            assert!(matches!(throttle.try_start_handshake(address), Some(None)));
+        }
+    }
+
+    #[test]
+    fn allowlisted_addresses_are_not_throttled() {
+        let throttle = ConnectionThrottle::new(&ConnectionThrottleConfig {
+            enabled: true,
+            max_connections_per_ip: 1,
+            window_ms: 1000,
+            max_handshaking: 1,
+            allowlist: vec!["10.1.0.0/16".to_string(), "::1".to_string()],
+        });
+        let proxy = IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3));
+        let other = IpAddr::V4(Ipv4Addr::new(10, 2, 0, 1));
+        let now = Instant::now();
+
+        let _permit = throttle.try_start_handshake(other);
+        for _ in 0..8 {
+            assert!(throttle.allow_address(proxy, now));
+            assert!(matches!(throttle.try_start_handshake(proxy), Some(None)));
+        }
+        assert!(throttle.allow_address(IpAddr::V6(Ipv6Addr::LOCALHOST), now));
+        assert!(
+            throttle.allow_address(IpAddr::V6(Ipv4Addr::new(10, 1, 0, 1).to_ipv6_mapped()), now)
+        );
+
+        assert!(throttle.allow_address(other, now));
+        assert!(!throttle.allow_address(other, now));
+        assert!(throttle.try_start_handshake(other).is_none());
+    }
+}
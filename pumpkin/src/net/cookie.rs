@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use pumpkin_util::resource_location::ResourceLocation;
+use tokio::sync::{Mutex, oneshot};
+
+/// The largest cookie the vanilla client accepts, in bytes.
+pub const MAX_COOKIE_SIZE: usize = 5120;
+/// How long to wait for a client to answer a cookie request before giving up.
+pub const COOKIE_RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+type CookieSender = oneshot::Sender<Option<Vec<u8>>>;
+
+/// Cookie requests waiting for the client's response. The protocol has no request ids, so
+/// responses are matched to requests by their key.
+#[derive(Default)]
+pub struct PendingCookies(Mutex<HashMap<ResourceLocation, Vec<CookieSender>>>);
+
+impl PendingCookies {
+    /// Registers a request for `key`, must be called before sending the request to the client.
+    pub async fn register(&self, key: ResourceLocation) -> oneshot::Receiver<Option<Vec<u8>>> {
+        let (send, recv) = oneshot::channel();
+        self.0.lock().await.entry(key).or_default().push(send);
+        recv
+    }
+
+    /// Hands a response to every request waiting for `key`, returns whether any were waiting.
+    pub async fn respond(&self, key: &ResourceLocation, payload: Option<&[u8]>) -> bool {
+        let Some(senders) = self.0.lock().await.remove(key) else {
+            return false;
+        };
+        let mut delivered = false;
+        for sender in senders {
+            delivered |= sender.send(payload.map(<[u8]>::to_vec)).is_ok();
+        }
+        delivered
+    }
+
+    /// Waits for the response to a request registered for `key`.
+    ///
+    /// Returns `None` if the client has no such cookie or does not answer within `timeout`.
+    pub async fn wait(
+        &self,
+        key: &ResourceLocation,
+        response: oneshot::Receiver<Option<Vec<u8>>>,
+        timeout: Duration,
+    ) -> Option<Vec<u8>> {
+        if let Ok(Ok(payload)) = tokio::time::timeout(timeout, response).await {
+            return payload;
+        }
+        // Forget the requests nobody is waiting for anymore
+        let mut pending = self.0.lock().await;
+        if let Some(senders) = pending.get_mut(key) {
+            senders.retain(|sender| !sender.is_closed());
+            if senders.is_empty() {
+                pending.remove(key);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use pumpkin_util::resource_location::ResourceLocation;
+
+    use super::PendingCookies;
+
+    #[tokio::test]
+    async fn cookie_responses_are_correlated_by_key() {
+        let pending = PendingCookies::default();
+        let session = ResourceLocation::from("proxy:session");
+        let other = ResourceLocation::from("proxy:other");
+
+        let response = pending.register(session.clone()).await;
+        assert!(!pending.respond(&other, Some(b"nope")).await);
+        assert!(pending.respond(&session, Some(b"token")).await);
+        assert_eq!(
+            pending
+                .wait(&session, response, Duration::from_secs(1))
+                .await,
+            Some(b"token".to_vec())
+        );
+
+        // A client that never answers
+        let response = pending.register(session.clone()).await;
+        assert_eq!(
+            pending
+                .wait(&session, response, Duration::from_millis(10))
+                .await,
+            None
+        );
+        assert!(pending.0.lock().await.is_empty());
+        assert!(!pending.respond(&session, Some(b"late")).await);
+    }
+}
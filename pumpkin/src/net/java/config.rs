@@ -180,11 +180,23 @@ impl JavaClient {
     pub async fn handle_config_acknowledged(self: &Arc<Self>, server: &Server) {
         log::debug!("Handling config acknowledgement");
         self.connection_state.store(ConnectionState::Play);
+        // Only now, the exempt packets are given by their IDs in the play state
+        self.set_compression_exempt(
+            &server
+                .advanced_config
+                .networking
+                .packet_compression
+                .exempt_packets,
+        )
+        .await;
+        self.handshake_permit.lock().await.take();
 
         let profile = self.gameprofile.lock().await.clone();
         let profile = profile.unwrap();
-        let address = self.address.lock().await;
+        let address = *self.address.lock().await;
 
+        // Checked again, as the player could have been banned since logging in. The client
+        // already is in the play state, so it gets kicked with a play disconnect.
         if let Some(reason) = can_not_join(&profile, &address, server).await {
             self.kick(reason).await;
             return;
@@ -5,15 +5,32 @@ use pumpkin_util::text::TextComponent;
 use pumpkin_world::CURRENT_MC_VERSION;
 
 use crate::net::java::JavaClient;
+use crate::server::Server;
 
 impl JavaClient {
-    pub async fn handle_handshake(&self, handshake: SHandShake) {
+    pub async fn handle_handshake(&self, server: &Server, handshake: SHandShake) {
         let version = handshake.protocol_version.0;
+        let forced_hosts = &server.advanced_config.networking.forced_hosts;
+        let allowed = !forced_hosts.enabled
+            || is_allowed_host(
+                &forced_hosts.allowed_hosts,
+                &handshake.server_address,
+                handshake.server_port,
+            );
         *self.server_address.lock().await = handshake.server_address;
 
         log::debug!("Handshake: next state is {:?}", &handshake.next_state);
         self.connection_state.store(handshake.next_state);
         if self.connection_state.load() != ConnectionState::Status {
+            if !allowed {
+                log::debug!(
+                    "Client {} joined through a host which is not allowed",
+                    self.id
+                );
+                self.kick(TextComponent::text(forced_hosts.kick_message.clone()))
+                    .await;
+                return;
+            }
             let protocol = version;
             match protocol.cmp(&(CURRENT_MC_PROTOCOL as i32)) {
                 std::cmp::Ordering::Less => {
@@ -35,3 +52,59 @@ impl JavaClient {
         }
     }
 }
+
+/// The hostname of a handshake's server address, without the suffix mods like Forge append after
+/// a null character and without the trailing dot of fully qualified names.
+fn handshake_host(address: &str) -> &str {
+    let host = address.split('\0').next().unwrap_or_default();
+    host.strip_suffix('.').unwrap_or(host)
+}
+
+/// Whether `address` and `port` from a handshake match one of the `allowed` hosts, which may
+/// include a port.
+///
+/// Clients resolve SRV records before connecting, so for those the handshake holds the target of
+/// the record and not the name the player entered, which is why only the target can be matched.
+fn is_allowed_host(allowed: &[String], address: &str, port: u16) -> bool {
+    let host = handshake_host(address);
+    allowed.iter().any(|entry| {
+        let (allowed_host, allowed_port) = match entry.rsplit_once(':') {
+            Some((allowed_host, allowed_port)) if !allowed_host.ends_with(':') => {
+                match allowed_port.parse::<u16>() {
+                    Ok(allowed_port) => (allowed_host, Some(allowed_port)),
+                    Err(_) => return false,
+                }
+            }
+            _ => (entry.as_str(), None),
+        };
+        let allowed_host = allowed_host.trim_start_matches('[').trim_end_matches(']');
+        let allowed_host = allowed_host.strip_suffix('.').unwrap_or(allowed_host);
+        allowed_host.eq_ignore_ascii_case(host.trim_start_matches('[').trim_end_matches(']'))
+            && allowed_port.is_none_or(|allowed_port| allowed_port == port)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_allowed_host;
+
+    #[test]
+    fn only_allowed_hosts_can_join() {
+        let allowed = vec![
+            "play.example.com".to_string(),
+            "mc.example.com:25566".to_string(),
+        ];
+
+        assert!(is_allowed_host(&allowed, "play.example.com", 25565));
+        assert!(is_allowed_host(&allowed, "PLAY.example.com.", 1234));
+        // Forge appends its marker after a null character
+        assert!(is_allowed_host(&allowed, "play.example.com\0FML3\0", 25565));
+        assert!(is_allowed_host(&allowed, "mc.example.com", 25566));
+        assert!(!is_allowed_host(&allowed, "mc.example.com", 25565));
+        // Behind an SRV record for play.example.com only its target is sent
+        assert!(!is_allowed_host(&allowed, "node1.example.net", 25565));
+        // Connecting directly through the IP
+        assert!(!is_allowed_host(&allowed, "203.0.113.7", 25565));
+        assert!(!is_allowed_host(&allowed, "example.com", 25565));
+    }
+}
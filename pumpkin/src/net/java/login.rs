@@ -15,9 +15,9 @@ use crate::{
     net::{
         GameProfile,
         authentication::{self, AuthError},
-        is_valid_player_name,
+        can_not_join, is_server_full, is_valid_player_name,
         java::JavaClient,
-        offline_uuid,
+        login_denied_by_plugins, offline_uuid,
         proxy::{bungeecord, velocity},
     },
     server::Server,
@@ -28,10 +28,7 @@ impl JavaClient {
         log::debug!("login start");
 
         // Don't allow new logons when the server is full.
-        // If `max_players` is set to zero, then there is no max player count enforced.
-        // TODO: If client is an operator or has otherwise suitable elevated permissions, allow the client to bypass this requirement.
-        let max_players = server.basic_config.max_players;
-        if max_players > 0 && server.get_player_count().await >= max_players as usize {
+        if is_server_full(server).await {
             self.kick(TextComponent::translate(
                 "multiplayer.disconnect.server_full",
                 [],
@@ -62,7 +59,7 @@ impl JavaClient {
                 {
                     Ok((_ip, profile)) => {
                         // self.address.lock() = ip;
-                        self.finish_login(&profile).await;
+                        self.finish_login(server, &profile).await;
                         *gameprofile = Some(profile);
                     }
                     Err(error) => self.kick(TextComponent::text(error.to_string())).await,
@@ -94,7 +91,7 @@ impl JavaClient {
                 )
                 .await;
             } else {
-                self.finish_login(&profile).await;
+                self.finish_login(server, &profile).await;
             }
 
             *gameprofile = Some(profile);
@@ -140,6 +137,7 @@ impl JavaClient {
                         e => TextComponent::text(e.to_string()),
                     })
                     .await;
+                    return;
                 }
             }
         }
@@ -178,25 +176,34 @@ impl JavaClient {
             return;
         }
 
-        self.finish_login(profile).await;
+        self.finish_login(server, profile).await;
     }
 
     async fn enable_compression(&self, server: &Server) {
-        let compression = server
-            .advanced_config
-            .networking
-            .packet_compression
-            .info
-            .clone();
+        let compression = &server.advanced_config.networking.packet_compression;
         // We want to wait until we have sent the compression packet to the client
         self.send_packet_now(&CSetCompression::new(
-            compression.threshold.try_into().unwrap(),
+            compression.info.threshold.try_into().unwrap(),
         ))
         .await;
-        self.set_compression(compression).await;
+        self.set_compression(compression.info.clone()).await;
+        self.set_max_decompressed_size(compression.max_decompressed_size)
+            .await;
     }
 
-    async fn finish_login(&self, profile: &GameProfile) {
+    /// Turns away banned and not whitelisted players and lets plugins deny the login, then tells
+    /// the client to continue with the config state.
+    async fn finish_login(&self, server: &Server, profile: &GameProfile) {
+        let address = *self.address.lock().await;
+        if let Some(reason) = can_not_join(profile, &address, server).await {
+            self.kick(reason).await;
+            return;
+        }
+        if let Some(reason) = login_denied_by_plugins(profile, &address).await {
+            // Still in the login state, so the client is told with a login disconnect
+            self.kick(reason).await;
+            return;
+        }
         let packet = CLoginSuccess::new(&profile.id, &profile.name, &profile.properties);
         self.send_packet_now(&packet).await;
     }
@@ -277,10 +284,10 @@ impl JavaClient {
                 plugin_response,
             ) {
                 Ok((profile, new_address)) => {
-                    self.finish_login(&profile).await;
-                    *self.gameprofile.lock().await = Some(profile);
                     *address = new_address;
                     drop(address);
+                    self.finish_login(server, &profile).await;
+                    *self.gameprofile.lock().await = Some(profile);
                 }
                 Err(error) => self.kick(TextComponent::text(error.to_string())).await,
             }
@@ -386,3 +393,90 @@ impl JavaClient {
         .await;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_protocol::{
+        ConnectionState,
+        codec::var_int::VarInt,
+        java::client::login::{CLoginDisconnect, CLoginSuccess},
+        packet::Packet,
+        ser::ReadingError,
+    };
+    use pumpkin_util::text::TextComponent;
+    use tokio::{io::AsyncReadExt, net::TcpStream};
+    use uuid::Uuid;
+
+    use crate::PLUGIN_MANAGER;
+    use crate::net::{GameProfile, java::JavaClient};
+    use crate::plugin::player::player_login::PlayerLoginEvent;
+    use crate::plugin::{BoxFuture, Cancellable, EventHandler, EventPriority};
+    use crate::server::Server;
+
+    struct ExternalWhitelist;
+
+    impl EventHandler<PlayerLoginEvent> for ExternalWhitelist {
+        fn handle_blocking<'a>(
+            &'a self,
+            _server: &'a Arc<Server>,
+            event: &'a mut PlayerLoginEvent,
+        ) -> BoxFuture<'a, ()> {
+            Box::pin(async move {
+                if event.profile.name == "Stranger" {
+                    event.kick_message = TextComponent::text("You are not on the whitelist");
+                    event.set_cancelled(true);
+                }
+            })
+        }
+    }
+
+    /// Reads the ids of the packets the client sent right away, until it is dropped.
+    async fn sent_packet_ids(peer: &mut TcpStream) -> Vec<i32> {
+        let mut ids = Vec::new();
+        loop {
+            let length = match VarInt::decode_async(peer).await {
+                Ok(length) => length.0,
+                Err(ReadingError::CleanEOF(_)) => return ids,
+                Err(err) => panic!("Failed to read a packet: {err}"),
+            };
+            let mut packet = vec![0; length as usize];
+            peer.read_exact(&mut packet).await.unwrap();
+            ids.push(VarInt::decode(&mut &packet[..]).unwrap().0);
+        }
+    }
+
+    async fn log_in(server: &Server, name: &str) -> Vec<i32> {
+        let (client, _, mut peer) = JavaClient::new_for_test_with_peer().await;
+        client.connection_state.store(ConnectionState::Login);
+        let profile = GameProfile {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        };
+        client.finish_login(server, &profile).await;
+        drop(client);
+        sent_packet_ids(&mut peer).await
+    }
+
+    #[tokio::test]
+    async fn cancelled_logins_never_succeed() {
+        let (server, _world_dir) = Server::new_for_test().await;
+        PLUGIN_MANAGER.set_server(server.clone()).await;
+        PLUGIN_MANAGER
+            .register::<PlayerLoginEvent, _>(
+                Arc::new(ExternalWhitelist),
+                EventPriority::Normal,
+                true,
+            )
+            .await;
+
+        assert_eq!(
+            log_in(&server, "Stranger").await,
+            [CLoginDisconnect::PACKET_ID]
+        );
+        assert_eq!(log_in(&server, "Friend").await, [CLoginSuccess::PACKET_ID]);
+    }
+}
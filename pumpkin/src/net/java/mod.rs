@@ -12,7 +12,8 @@ use pumpkin_protocol::java::server::play::{
     SPlayPingRequest, SPlayerAbilities, SPlayerAction, SPlayerCommand, SPlayerInput, SPlayerLoaded,
     SPlayerPosition, SPlayerPositionRotation, SPlayerRotation, SPlayerSession,
     SPlaceRecipe, SRecipeBookChangeSettings, SRecipeBookSeenRecipe, SSeenAdvancements, SSetCommandBlock,
-    SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm, SUpdateSign, SUseItem, SUseItemOn,
+    SSelectTrade, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm, SUpdateSign, SUseItem,
+    SUseItemOn,
 };
 use pumpkin_protocol::{
     ClientPacket, ConnectionState, PacketDecodeError, RawPacket, ServerPacket,
@@ -37,8 +38,9 @@ use pumpkin_protocol::{
     packet::Packet,
     ser::{NetworkWriteExt, ReadingError, WritingError},
 };
+use pumpkin_util::math::vector2::Vector2;
 use pumpkin_util::text::TextComponent;
-use tokio::sync::Notify;
+use tokio::sync::{Notify, OwnedSemaphorePermit};
 use tokio::{
     io::{BufReader, BufWriter},
     net::{
@@ -56,12 +58,25 @@ use tokio_util::task::TaskTracker;
 pub mod config;
 pub mod handshake;
 pub mod login;
+pub mod packet_queue;
 pub mod play;
 pub mod status;
 
 use crate::entity::player::Player;
-use crate::net::{GameProfile, PlayerConfig};
+use crate::net::packet_dump::{PacketDirection, PacketDump};
+use crate::net::{GameProfile, PlayerConfig, cookie::PendingCookies, latency::Latency};
 use crate::{error::PumpkinError, net::EncryptionError, server::Server};
+use packet_queue::{BulkGeneration, LaneState, QueuedChunks, QueuedPacket, next_packet};
+
+/// The ids of the packets waiting in `packets`, in the order they were queued.
+#[cfg(test)]
+pub fn queued_packet_ids(packets: &mut Receiver<Bytes>) -> Vec<i32> {
+    let mut ids = Vec::new();
+    while let Ok(packet) = packets.try_recv() {
+        ids.push(VarInt::decode(&mut &packet[..]).unwrap().0);
+    }
+    ids
+}
 
 pub struct JavaClient {
     pub id: u64,
@@ -80,6 +95,15 @@ pub struct JavaClient {
     /// The client's brand or modpack information, Optional.
     pub brand: Mutex<Option<String>>,
     pub player: Mutex<Option<Arc<Player>>>,
+    /// Holds a handshaking slot of the connection throttle until the client reaches the play state.
+    pub handshake_permit: Mutex<Option<OwnedSemaphorePermit>>,
+    /// The round trip time measured with keep alives.
+    pub latency: Latency,
+    /// Cookie requests waiting for the client's response.
+    pub pending_cookies: PendingCookies,
+    /// Records the packets of this client if packet dumping is enabled, must be set before
+    /// starting the outgoing packet task.
+    pub packet_dump: Option<Arc<PacketDump>>,
     /// A collection of tasks associated with this client. The tasks await completion when removing the client.
     tasks: TaskTracker,
     /// An notifier that is triggered when this client is closed.
@@ -88,6 +112,14 @@ pub struct JavaClient {
     outgoing_packet_queue_send: Sender<Bytes>,
     /// A queue of serialized packets to send to the network
     outgoing_packet_queue_recv: Option<Receiver<Bytes>>,
+    /// A queue of bulk packets like chunks, they are mostly sent while the other queue is empty
+    outgoing_bulk_packet_queue_send: Sender<QueuedPacket>,
+    /// A queue of bulk packets like chunks, they are mostly sent while the other queue is empty
+    outgoing_bulk_packet_queue_recv: Option<Receiver<QueuedPacket>>,
+    /// The chunks with packets in the bulk queue, which block updates have to wait for
+    queued_chunks: QueuedChunks,
+    /// Bulk packets queued in an older generation are dropped, see [`Self::discard_bulk_packets`]
+    bulk_generation: BulkGeneration,
     /// The packet encoder for outgoing packets.
     network_writer: Arc<Mutex<TCPNetworkEncoder<BufWriter<OwnedWriteHalf>>>>,
     /// The packet decoder for incoming packets.
@@ -99,6 +131,7 @@ impl JavaClient {
     pub fn new(tcp_stream: TcpStream, address: SocketAddr, id: u64) -> Self {
         let (read, write) = tcp_stream.into_split();
         let (send, recv) = tokio::sync::mpsc::channel(128);
+        let (bulk_send, bulk_recv) = tokio::sync::mpsc::channel(128);
         Self {
             id,
             gameprofile: Mutex::new(None),
@@ -111,13 +144,41 @@ impl JavaClient {
             tasks: TaskTracker::new(),
             outgoing_packet_queue_send: send,
             outgoing_packet_queue_recv: Some(recv),
+            outgoing_bulk_packet_queue_send: bulk_send,
+            outgoing_bulk_packet_queue_recv: Some(bulk_recv),
+            queued_chunks: QueuedChunks::default(),
+            bulk_generation: BulkGeneration::default(),
 
             network_writer: Arc::new(Mutex::new(TCPNetworkEncoder::new(BufWriter::new(write)))),
             network_reader: Mutex::new(TCPNetworkDecoder::new(BufReader::new(read))),
             brand: Mutex::new(None),
             player: Mutex::new(None),
+            handshake_permit: Mutex::new(None),
+            latency: Latency::default(),
+            pending_cookies: PendingCookies::default(),
+            packet_dump: None,
         }
     }
+
+    /// A client connected to a local socket, for tests. Queued packets are not written, they end
+    /// up in the returned receiver, see [`queued_packet_ids`].
+    #[cfg(test)]
+    pub async fn new_for_test() -> (Self, Receiver<Bytes>) {
+        let (client, packets, _) = Self::new_for_test_with_peer().await;
+        (client, packets)
+    }
+
+    /// Like [`Self::new_for_test`], also returning the other end of the socket which receives
+    /// the packets that are sent right away.
+    #[cfg(test)]
+    pub async fn new_for_test_with_peer() -> (Self, Receiver<Bytes>, TcpStream) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (stream, peer) = tokio::join!(TcpStream::connect(address), listener.accept());
+        let mut client = Self::new(stream.unwrap(), address, 0);
+        let packets = client.outgoing_packet_queue_recv.take().unwrap();
+        (client, packets, peer.unwrap().0)
+    }
     pub async fn set_encryption(
         &self,
         shared_secret: &[u8], // decrypted
@@ -146,6 +207,22 @@ impl JavaClient {
             .set_compression((compression.threshold as usize, compression.level));
     }
 
+    /// Sets the largest a packet sent by the client may be once decompressed.
+    pub async fn set_max_decompressed_size(&self, size: usize) {
+        self.network_reader
+            .lock()
+            .await
+            .set_max_decompressed_size(size);
+    }
+
+    /// Sets the IDs of the packets which are never compressed.
+    pub async fn set_compression_exempt(&self, packet_ids: &[i32]) {
+        self.network_writer
+            .lock()
+            .await
+            .set_compression_exempt(packet_ids.iter().copied());
+    }
+
     /// Processes all packets received from the connected client in a loop.
     ///
     /// This function continuously dequeues packets from the client's packet queue and processes them.
@@ -209,7 +286,61 @@ impl JavaClient {
     ///
     /// * `packet`: A reference to a packet object implementing the `ClientPacket` trait.
     pub async fn enqueue_packet_data(&self, packet_data: Bytes) {
-        if let Err(err) = self.outgoing_packet_queue_send.send(packet_data).await {
+        self.enqueue_to(&self.outgoing_packet_queue_send, packet_data)
+            .await;
+    }
+
+    /// Queues a bulk packet, which is mostly sent while no other packets are queued. This keeps
+    /// keep alives and gameplay packets from waiting behind a flood of chunks. Bulk packets are
+    /// sent in-order among themselves, but not relative to other packets.
+    pub async fn enqueue_bulk_packet<P: ClientPacket>(&self, packet: &P) {
+        self.enqueue_bulk_packet_of(None, packet).await;
+    }
+
+    /// Queues the data of `chunk` as a bulk packet. Block updates of the chunk are queued behind
+    /// it until it was written, see [`Self::enqueue_block_packet`].
+    pub async fn enqueue_chunk_packet<P: ClientPacket>(&self, chunk: Vector2<i32>, packet: &P) {
+        self.queued_chunks.queue(chunk);
+        self.enqueue_bulk_packet_of(Some(chunk), packet).await;
+    }
+
+    /// Queues a packet changing blocks or block entities in `chunk`. It is queued behind the
+    /// chunk's data if that was not written yet, otherwise the client would drop it.
+    pub async fn enqueue_block_packet<P: ClientPacket>(&self, chunk: Vector2<i32>, packet: &P) {
+        if self.queued_chunks.queue_behind(chunk) {
+            self.enqueue_bulk_packet_of(Some(chunk), packet).await;
+        } else {
+            self.enqueue_packet(packet).await;
+        }
+    }
+
+    /// Drops the bulk packets queued so far, like the chunks of a world the client is leaving.
+    /// Has to be called before the packet switching worlds is queued, bulk packets queued after
+    /// it are only written once that packet was.
+    pub fn discard_bulk_packets(&self) {
+        self.bulk_generation.advance();
+        self.queued_chunks.clear();
+    }
+
+    async fn enqueue_bulk_packet_of<P: ClientPacket>(
+        &self,
+        chunk: Option<Vector2<i32>>,
+        packet: &P,
+    ) {
+        let mut buf = Vec::new();
+        let writer = &mut buf;
+        Self::write_packet(packet, writer).unwrap();
+        let packet = QueuedPacket {
+            data: buf.into(),
+            chunk,
+            generation: self.bulk_generation.current(),
+        };
+        self.enqueue_to(&self.outgoing_bulk_packet_queue_send, packet)
+            .await;
+    }
+
+    async fn enqueue_to<T>(&self, queue: &Sender<T>, packet: T) {
+        if let Err(err) = queue.send(packet).await {
             // This is expected to fail if we are closed
             if !self.closed.load(Ordering::Relaxed) {
                 log::error!(
@@ -234,7 +365,16 @@ impl JavaClient {
             },
             packet_result = network_reader.get_raw_packet() => {
                 match packet_result {
-                    Ok(packet) => Some(packet),
+                    Ok(packet) => {
+                        if let Some(packet_dump) = &self.packet_dump {
+                            packet_dump.record(
+                                PacketDirection::Serverbound,
+                                packet.id,
+                                &packet.payload,
+                            );
+                        }
+                        Some(packet)
+                    }
                     Err(err) => {
                         if !matches!(err, PacketDecodeError::ConnectionClosed) {
                             log::warn!("Failed to decode packet from client {}: {}", self.id, err);
@@ -276,6 +416,9 @@ impl JavaClient {
     }
 
     pub async fn send_packet_now_data(&self, packet: Vec<u8>) {
+        if let Some(packet_dump) = &self.packet_dump {
+            packet_dump.record_clientbound(&packet);
+        }
         if let Err(err) = self
             .network_writer
             .lock()
@@ -332,7 +475,7 @@ impl JavaClient {
         packet: &RawPacket,
     ) -> Result<(), ReadingError> {
         match self.connection_state.load() {
-            ConnectionState::HandShake => self.handle_handshake_packet(packet).await,
+            ConnectionState::HandShake => self.handle_handshake_packet(server, packet).await,
             ConnectionState::Status => self.handle_status_packet(server, packet).await,
             // TODO: Check config if transfer is enabled
             ConnectionState::Login | ConnectionState::Transfer => {
@@ -363,12 +506,17 @@ impl JavaClient {
         }
     }
 
-    async fn handle_handshake_packet(&self, packet: &RawPacket) -> Result<(), ReadingError> {
+    async fn handle_handshake_packet(
+        &self,
+        server: &Server,
+        packet: &RawPacket,
+    ) -> Result<(), ReadingError> {
         log::debug!("Handling handshake group");
         let payload = &packet.payload[..];
         match packet.id {
             0 => {
-                self.handle_handshake(SHandShake::read(payload)?).await;
+                self.handle_handshake(server, SHandShake::read(payload)?)
+                    .await;
                 Ok(())
             }
             _ => Err(ReadingError::Message(format!(
@@ -407,26 +555,55 @@ impl JavaClient {
             .outgoing_packet_queue_recv
             .take()
             .expect("This was set in the new fn");
+        let mut bulk_packet_receiver = self
+            .outgoing_bulk_packet_queue_recv
+            .take()
+            .expect("This was set in the new fn");
+        let queued_chunks = self.queued_chunks.clone();
+        let bulk_generation = self.bulk_generation.clone();
         let close_interrupt = self.close_interrupt.clone();
         let closed = self.closed.clone();
         let writer = self.network_writer.clone();
         let id = self.id;
+        let packet_dump = self.packet_dump.clone();
         self.spawn_task(async move {
+            let mut lane_state = LaneState::default();
             while !closed.load(Ordering::Relaxed) {
                 let recv_result = tokio::select! {
+                    biased;
                     () = close_interrupt.notified() => {
                         None
                     },
-                    recv_result = packet_receiver.recv() => {
+                    recv_result = next_packet(
+                        &mut packet_receiver,
+                        &mut bulk_packet_receiver,
+                        &mut lane_state,
+                        &bulk_generation,
+                    ) => {
                         recv_result
                     }
                 };
 
-                let Some(packet_data) = recv_result else {
+                let Some(QueuedPacket {
+                    data,
+                    chunk,
+                    generation,
+                }) = recv_result
+                else {
                     break;
                 };
+                if let Some(packet_dump) = &packet_dump {
+                    packet_dump.record_clientbound(&data);
+                }
 
-                if let Err(err) = writer.lock().await.write_packet(packet_data).await {
+                let result = writer.lock().await.write_packet(data).await;
+                // The chunks were forgotten if the generation went up in the meantime
+                if let Some(chunk) = chunk
+                    && generation == bulk_generation.current()
+                {
+                    queued_chunks.written(chunk);
+                }
+                if let Err(err) = result {
                     // It is expected that the packet will fail if we are closed
                     if !closed.load(Ordering::Relaxed) {
                         log::warn!("Failed to send packet to client {id}: {err}",);
@@ -580,12 +757,16 @@ impl JavaClient {
                 // TODO
             }
             SPlayerPosition::PACKET_ID => {
-                self.handle_position(player, SPlayerPosition::read(payload)?)
+                self.handle_position(player, SPlayerPosition::read(payload)?, server)
                     .await;
             }
             SPlayerPositionRotation::PACKET_ID => {
-                self.handle_position_rotation(player, SPlayerPositionRotation::read(payload)?)
-                    .await;
+                self.handle_position_rotation(
+                    player,
+                    SPlayerPositionRotation::read(payload)?,
+                    server,
+                )
+                .await;
             }
             SPlayerRotation::PACKET_ID => {
                 self.handle_rotation(player, SPlayerRotation::read(payload)?)
@@ -651,7 +832,8 @@ impl JavaClient {
                     .await;
             }
             SPCookieResponse::PACKET_ID => {
-                self.handle_cookie_response(&SPCookieResponse::read(payload)?);
+                self.handle_cookie_response(&SPCookieResponse::read(payload)?)
+                    .await;
             }
             SCloseContainer::PACKET_ID => {
                 self.handle_close_container(player, server, SCloseContainer::read(payload)?)
@@ -672,6 +854,10 @@ impl JavaClient {
                 self.handle_seen_advancements(player, SSeenAdvancements::read(payload)?)
                     .await;
             }
+            SSelectTrade::PACKET_ID => {
+                self.handle_select_trade(player, SSelectTrade::read(payload)?)
+                    .await;
+            }
             SPlaceRecipe::PACKET_ID => {
                 self.handle_place_recipe(player, SPlaceRecipe::read(payload)?)
                     .await;
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use pumpkin_util::math::vector2::Vector2;
+use tokio::sync::mpsc::Receiver;
+
+/// After this many packets in a row from the priority queue, a waiting bulk packet is sent,
+/// so a busy priority queue can't starve the bulk queue.
+const MAX_PRIORITY_STREAK: u32 = 16;
+
+/// A serialized packet waiting to be written.
+pub struct QueuedPacket {
+    pub data: Bytes,
+    /// The chunk this packet belongs to, if it has to stay in order with the chunk's data.
+    pub chunk: Option<Vector2<i32>>,
+    /// The [`BulkGeneration`] the packet was queued in.
+    pub generation: u64,
+}
+
+/// Goes up whenever the client switches worlds, bulk packets queued before that belong to the
+/// world it left and are dropped instead of written.
+#[derive(Clone, Default)]
+pub struct BulkGeneration(Arc<AtomicU64>);
+
+impl BulkGeneration {
+    pub fn current(&self) -> u64 {
+        self.0.load(Ordering::Acquire)
+    }
+
+    pub fn advance(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// What the writer remembers between two calls of [`next_packet`].
+#[derive(Default)]
+pub struct LaneState {
+    /// The priority packets written since the last bulk packet
+    priority_streak: u32,
+    /// The generation up to which the priority lane was emptied
+    generation: u64,
+}
+
+/// Counts the queued bulk packets of each chunk that were not written yet.
+///
+/// Block updates for such a chunk have to be queued behind it, the client would drop them if
+/// they arrived before the chunk itself.
+#[derive(Clone, Default)]
+pub struct QueuedChunks(Arc<Mutex<HashMap<Vector2<i32>, usize>>>);
+
+impl QueuedChunks {
+    /// Registers a bulk packet of `chunk`.
+    pub fn queue(&self, chunk: Vector2<i32>) {
+        *self.0.lock().unwrap().entry(chunk).or_default() += 1;
+    }
+
+    /// Registers a packet of `chunk` if packets of it are still queued. Returns `false` if there
+    /// are none, then the packet can be sent right away.
+    pub fn queue_behind(&self, chunk: Vector2<i32>) -> bool {
+        match self.0.lock().unwrap().get_mut(&chunk) {
+            Some(count) => {
+                *count += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forgets all queued chunks, for when their packets are dropped.
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+
+    /// Registers that a queued packet of `chunk` was written.
+    pub fn written(&self, chunk: Vector2<i32>) {
+        let mut chunks = self.0.lock().unwrap();
+        if let Some(count) = chunks.get_mut(&chunk) {
+            *count -= 1;
+            if *count == 0 {
+                chunks.remove(&chunk);
+            }
+        }
+    }
+}
+
+/// Waits for the next packet to write, returns `None` once both queues are closed and empty.
+///
+/// Priority packets are preferred, but after [`MAX_PRIORITY_STREAK`] of them a bulk packet gets
+/// its turn. Bulk packets of an older `generation` are dropped. After the generation went up,
+/// bulk packets wait until the priority lane was empty once, so the chunks of the new world
+/// can't overtake the packet switching to it.
+pub async fn next_packet(
+    priority: &mut Receiver<Bytes>,
+    bulk: &mut Receiver<QueuedPacket>,
+    state: &mut LaneState,
+    generation: &BulkGeneration,
+) -> Option<QueuedPacket> {
+    loop {
+        let current = generation.current();
+        if state.generation == current
+            && state.priority_streak >= MAX_PRIORITY_STREAK
+            && let Ok(packet) = bulk.try_recv()
+        {
+            state.priority_streak = 0;
+            if packet.generation == current {
+                return Some(packet);
+            }
+            continue;
+        }
+        let packet = tokio::select! {
+            biased;
+            Some(data) = priority.recv() => {
+                state.priority_streak += 1;
+                return Some(QueuedPacket { data, chunk: None, generation: current });
+            }
+            Some(packet) = bulk.recv() => packet,
+            else => return None,
+        };
+        // Nothing was waiting in the priority lane
+        state.priority_streak = 0;
+        state.generation = current;
+        if packet.generation == current {
+            return Some(packet);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bytes::Bytes;
+    use pumpkin_util::math::vector2::Vector2;
+    use tokio::sync::mpsc::channel;
+
+    use super::{
+        BulkGeneration, LaneState, MAX_PRIORITY_STREAK, QueuedChunks, QueuedPacket, next_packet,
+    };
+
+    #[tokio::test]
+    async fn block_updates_follow_their_queued_chunk() {
+        let (priority_send, mut priority) = channel(8);
+        let (bulk_send, mut bulk) = channel(8);
+        let chunks = QueuedChunks::default();
+        let chunk = Vector2::new(0, 0);
+
+        chunks.queue(chunk);
+        let packet = |data: &'static [u8]| QueuedPacket {
+            data: Bytes::from_static(data),
+            chunk: Some(chunk),
+            generation: 0,
+        };
+        bulk_send.send(packet(b"chunk")).await.unwrap();
+        // The update of the queued chunk is queued behind it, an update elsewhere is not
+        assert!(chunks.queue_behind(chunk));
+        bulk_send.send(packet(b"update")).await.unwrap();
+        assert!(!chunks.queue_behind(Vector2::new(1, 0)));
+        priority_send
+            .send(Bytes::from_static(b"other update"))
+            .await
+            .unwrap();
+
+        let mut state = LaneState::default();
+        let generation = BulkGeneration::default();
+        let mut written = Vec::new();
+        for _ in 0..3 {
+            let packet = next_packet(&mut priority, &mut bulk, &mut state, &generation)
+                .await
+                .unwrap();
+            if let Some(chunk) = packet.chunk {
+                chunks.written(chunk);
+            }
+            written.push(packet.data);
+        }
+        assert_eq!(written, ["other update", "chunk", "update"]);
+        // Everything of the chunk was written, new updates are sent right away
+        assert!(!chunks.queue_behind(chunk));
+    }
+
+    #[tokio::test]
+    async fn bulk_packets_are_not_starved() {
+        let (priority_send, mut priority) = channel(64);
+        let (bulk_send, mut bulk) = channel(8);
+        for _ in 0..2 * MAX_PRIORITY_STREAK {
+            priority_send
+                .send(Bytes::from_static(b"priority"))
+                .await
+                .unwrap();
+        }
+        bulk_send
+            .send(QueuedPacket {
+                data: Bytes::from_static(b"bulk"),
+                chunk: None,
+                generation: 0,
+            })
+            .await
+            .unwrap();
+
+        let mut state = LaneState::default();
+        let generation = BulkGeneration::default();
+        let mut position = None;
+        for i in 0..=MAX_PRIORITY_STREAK {
+            let packet = next_packet(&mut priority, &mut bulk, &mut state, &generation)
+                .await
+                .unwrap();
+            if packet.data == "bulk" {
+                position = Some(i);
+            }
+        }
+        assert_eq!(position, Some(MAX_PRIORITY_STREAK));
+    }
+
+    #[tokio::test]
+    async fn old_world_chunks_are_dropped_on_respawn() {
+        let (priority_send, mut priority) = channel(64);
+        let (bulk_send, mut bulk) = channel(8);
+        let generation = BulkGeneration::default();
+        let bulk_packet = |data: &'static [u8]| QueuedPacket {
+            data: Bytes::from_static(data),
+            chunk: None,
+            generation: generation.current(),
+        };
+        // Enough priority packets that a bulk packet would be due
+        for _ in 0..MAX_PRIORITY_STREAK {
+            priority_send
+                .send(Bytes::from_static(b"priority"))
+                .await
+                .unwrap();
+        }
+        bulk_send.send(bulk_packet(b"old chunk")).await.unwrap();
+
+        generation.advance();
+        priority_send
+            .send(Bytes::from_static(b"respawn"))
+            .await
+            .unwrap();
+        bulk_send.send(bulk_packet(b"new chunk")).await.unwrap();
+        drop(priority_send);
+        drop(bulk_send);
+
+        let mut state = LaneState::default();
+        let mut written = Vec::new();
+        while let Some(packet) =
+            next_packet(&mut priority, &mut bulk, &mut state, &generation).await
+        {
+            if packet.data != "priority" {
+                written.push(packet.data);
+            }
+        }
+        assert_eq!(written, ["respawn", "new chunk"]);
+    }
+
+    #[tokio::test]
+    async fn priority_packets_outlive_the_bulk_queue() {
+        let (priority_send, mut priority) = channel(8);
+        let (bulk_send, mut bulk) = channel::<QueuedPacket>(8);
+        drop(bulk_send);
+        priority_send
+            .send(Bytes::from_static(b"disconnect"))
+            .await
+            .unwrap();
+
+        let mut state = LaneState::default();
+        let generation = BulkGeneration::default();
+        let packet = next_packet(&mut priority, &mut bulk, &mut state, &generation)
+            .await
+            .unwrap();
+        assert_eq!(packet.data, "disconnect");
+        drop(priority_send);
+        assert!(
+            next_packet(&mut priority, &mut bulk, &mut state, &generation)
+                .await
+                .is_none()
+        );
+    }
+}
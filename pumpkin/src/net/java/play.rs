@@ -10,14 +10,16 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 use crate::block::BlockHitResult;
+use crate::block::blocks::command::CommandBlock;
 use crate::block::registry::BlockActionResult;
 use crate::block::{self, BlockIsReplacing};
-use crate::command::CommandSender;
+use crate::command::{CommandSender, cooldown};
 use crate::entity::EntityBase;
 use crate::entity::player::{ChatMode, ChatSession, Player};
 use crate::error::PumpkinError;
 use crate::net::PlayerConfig;
 use crate::net::java::JavaClient;
+use crate::plugin::block::block_place::BlockPlaceEvent;
 use crate::plugin::player::player_chat::PlayerChatEvent;
 use crate::plugin::player::player_command_send::PlayerCommandSendEvent;
 use crate::plugin::player::player_interact_event::{InteractAction, PlayerInteractEvent};
@@ -28,19 +30,22 @@ use pumpkin_data::block_properties::{
     BlockProperties, CommandBlockLikeProperties, WaterLikeProperties,
 };
 use pumpkin_data::data_component_impl::{ConsumableImpl, EquipmentSlot, EquippableImpl, FoodImpl};
+use pumpkin_data::effect::StatusEffect;
 use pumpkin_data::item::Item;
 use pumpkin_data::sound::{Sound, SoundCategory};
 use pumpkin_data::{Block, BlockDirection, BlockState};
 use pumpkin_inventory::InventoryError;
+use pumpkin_inventory::merchant::merchant_screen_handler::MerchantScreenHandler;
 use pumpkin_inventory::player::player_inventory::PlayerInventory;
 use pumpkin_inventory::screen_handler::{InventoryPlayer, ScreenHandler};
 use pumpkin_macros::send_cancellable;
+use pumpkin_protocol::codec::item_stack_seralizer::ItemStackSerializer;
 use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::java::client::play::{
     CBlockUpdate, CCommandSuggestions, CEntityPositionSync, CHeadRot, COpenSignEditor,
-    CPingResponse, CPlayerInfoUpdate, CPlayerPosition, CSelectAdvancementTab, CSetSelectedSlot,
-    CSystemChatMessage, CUpdateEntityPos, CUpdateEntityPosRot, CUpdateEntityRot, InitChat,
-    PlayerAction,
+    CPingResponse, CPlayerInfoUpdate, CPlayerPosition, CSelectAdvancementTab, CSetPlayerInventory,
+    CSetSelectedSlot, CSystemChatMessage, CUpdateEntityPos, CUpdateEntityPosRot, CUpdateEntityRot,
+    InitChat, PlayerAction,
 };
 use pumpkin_protocol::java::server::play::{
     Action, ActionType, CommandBlockMode, FLAG_ON_GROUND, SChangeGameMode, SChatCommand,
@@ -49,15 +54,17 @@ use pumpkin_protocol::java::server::play::{
     SKeepAlive, SPickItemFromBlock, SPlayPingRequest, SPlayerAbilities, SPlayerAction,
     SPlayerCommand, SPlayerInput, SPlayerPosition, SPlayerPositionRotation, SPlayerRotation,
     SPlaceRecipe, SPlayerSession, SRecipeBookChangeSettings, SRecipeBookSeenRecipe, SSeenAdvancements,
-    SeenAdvancementsAction, SSetCommandBlock, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround,
-    SSwingArm, SUpdateSign, SUseItem, SUseItemOn, Status,
+    SeenAdvancementsAction, SSelectTrade, SSetCommandBlock, SSetCreativeSlot, SSetHeldItem,
+    SSetPlayerGround, SSwingArm, SUpdateSign, SUseItem, SUseItemOn, Status,
 };
+use pumpkin_util::math::boundingbox::BoundingBox;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::math::{polynomial_rolling_hash, position::BlockPos, wrap_degrees};
 use pumpkin_util::text::color::NamedColor;
 use pumpkin_util::{GameMode, text::TextComponent};
 use pumpkin_world::block::entities::command_block::CommandBlockEntity;
 use pumpkin_world::block::entities::sign::SignBlockEntity;
+use pumpkin_world::data::player_stats::Stat;
 use pumpkin_world::item::ItemStack;
 use pumpkin_world::world::BlockFlags;
 use tokio::sync::Mutex;
@@ -66,6 +73,13 @@ use tokio::sync::Mutex;
 /// Vanilla: 2 minutes
 const CHAT_MESSAGE_MAX_AGE: i64 = 1000 * 60 * 2;
 
+/// The maximum amount of characters in a single sign line
+/// Vanilla: `ServerboundSignUpdatePacket.MAX_STRING_LENGTH`
+const SIGN_LINE_MAX_LENGTH: usize = 384;
+
+/// How far below a player's feet a block may be for them to count as standing on it
+const GROUND_DISTANCE: f64 = 0.05;
+
 #[derive(Debug, Error)]
 pub enum BlockPlacingError {
     BlockOutOfReach,
@@ -73,6 +87,7 @@ pub enum BlockPlacingError {
     InvalidBlockFace,
     BlockOutOfWorld,
     InvalidGamemode,
+    Cancelled,
 }
 
 impl std::fmt::Display for BlockPlacingError {
@@ -84,21 +99,27 @@ impl std::fmt::Display for BlockPlacingError {
 impl PumpkinError for BlockPlacingError {
     fn is_kick(&self) -> bool {
         match self {
-            Self::BlockOutOfReach | Self::BlockOutOfWorld | Self::InvalidGamemode => false,
+            Self::BlockOutOfReach
+            | Self::BlockOutOfWorld
+            | Self::InvalidGamemode
+            | Self::Cancelled => false,
             Self::InvalidBlockFace | Self::InvalidHand => true,
         }
     }
 
     fn severity(&self) -> log::Level {
         match self {
-            Self::BlockOutOfWorld | Self::InvalidGamemode => log::Level::Trace,
+            Self::BlockOutOfWorld | Self::InvalidGamemode | Self::Cancelled => log::Level::Trace,
             Self::BlockOutOfReach | Self::InvalidBlockFace | Self::InvalidHand => log::Level::Warn,
         }
     }
 
     fn client_kick_reason(&self) -> Option<String> {
         match self {
-            Self::BlockOutOfReach | Self::BlockOutOfWorld | Self::InvalidGamemode => None,
+            Self::BlockOutOfReach
+            | Self::BlockOutOfWorld
+            | Self::InvalidGamemode
+            | Self::Cancelled => None,
             Self::InvalidBlockFace => Some("Invalid block face".into()),
             Self::InvalidHand => Some("Invalid hand".into()),
         }
@@ -201,7 +222,9 @@ impl JavaClient {
         change_game_mode: SChangeGameMode,
     ) {
         if player.permission_lvl.load() >= PermissionLvl::Two {
-            player.set_gamemode(change_game_mode.game_mode).await;
+            player
+                .set_gamemode(change_game_mode.game_mode, &player.gameprofile.name)
+                .await;
             let gamemode_string = format!(
                 "gameMode.{}",
                 change_game_mode.game_mode.to_str().to_lowercase()
@@ -223,6 +246,54 @@ impl JavaClient {
         pos.clamp(-2.0E7, 2.0E7)
     }
 
+    /// Checks the move of a player against the movement limits, returns whether the move should
+    /// be applied. Rejected moves send the player back to their last valid position.
+    async fn validate_movement(player: &Arc<Player>, server: &Server, to: Vector3<f64>) -> bool {
+        let config = &server.advanced_config.movement;
+        if !config.enabled {
+            return true;
+        }
+        let mut validator = player.movement_validator.lock().await;
+        // Moves sent before the client confirmed a teleport still start at the old position
+        if player.awaiting_teleport.lock().await.is_some() {
+            validator.reset();
+            return true;
+        }
+        let entity = &player.living_entity.entity;
+        let exempt = {
+            let abilities = player.abilities.lock().await;
+            abilities.flying || abilities.allow_flying
+        } || entity.touching_water.load(Ordering::Relaxed)
+            || entity.touching_lava.load(Ordering::Relaxed)
+            || entity.fall_flying.load(Ordering::Relaxed)
+            || player.living_entity.climbing.load(Ordering::Relaxed)
+            || entity.vehicle.lock().await.is_some()
+            || player
+                .living_entity
+                .has_effect(&StatusEffect::LEVITATION)
+                .await;
+        // The client's own on ground flag can't be trusted, so look for blocks below its feet
+        let feet = BoundingBox::new_from_pos(to.x, to.y, to.z, &entity.entity_dimension.load());
+        let on_ground = !entity
+            .world
+            .is_space_empty(feet.stretch(Vector3::new(0.0, -GROUND_DISTANCE, 0.0)))
+            .await;
+        let from = entity.pos.load();
+        let Err(violation) = validator.check(config, from, to, on_ground, exempt) else {
+            return true;
+        };
+        log::warn!("{} moved wrongly: {violation}", player.gameprofile.name);
+        if !config.teleport_back {
+            return true;
+        }
+        let back = validator.last_valid_pos().unwrap_or(from);
+        drop(validator);
+        player
+            .request_teleport(back, entity.yaw.load(), entity.pitch.load())
+            .await;
+        false
+    }
+
     pub fn handle_player_loaded(player: &Player) {
         player.set_client_loaded(true);
     }
@@ -263,7 +334,12 @@ impl JavaClient {
         true
     }
 
-    pub async fn handle_position(&self, player: &Arc<Player>, packet: SPlayerPosition) {
+    pub async fn handle_position(
+        &self,
+        player: &Arc<Player>,
+        packet: SPlayerPosition,
+        server: &Server,
+    ) {
         if !player.has_client_loaded() {
             return;
         }
@@ -282,6 +358,9 @@ impl JavaClient {
             Self::clamp_vertical(position.y),
             Self::clamp_horizontal(position.z),
         );
+        if !Self::validate_movement(player, server, position).await {
+            return;
+        }
 
         send_cancellable! {{
             PlayerMoveEvent {
@@ -305,12 +384,11 @@ impl JavaClient {
                 entity.on_ground.store(packet.collision & FLAG_ON_GROUND != 0, Ordering::Relaxed);
                 let world = &player.world();
 
-                // TODO: Warn when player moves to quickly
                 if !self.sync_position(player, world, pos, last_pos, entity.yaw.load(), entity.pitch.load(), packet.collision & FLAG_ON_GROUND != 0).await {
                     // Send the new position to all other players.
                     world
-                        .broadcast_packet_except(
-                            &[player.gameprofile.id],
+                        .broadcast_to_trackers(
+                            player.entity_id(),
                             &CUpdateEntityPos::new(
                                 player.entity_id().into(),
                                 Vector3::new(
@@ -324,6 +402,7 @@ impl JavaClient {
                         .await;
                 }
 
+                player.living_entity.check_climbing().await;
                 if !player.abilities.lock().await.flying {
                     player.living_entity
                         .fall(
@@ -360,6 +439,7 @@ impl JavaClient {
         &self,
         player: &Arc<Player>,
         packet: SPlayerPositionRotation,
+        server: &Server,
     ) {
         if !player.has_client_loaded() {
             return;
@@ -385,6 +465,9 @@ impl JavaClient {
             Self::clamp_vertical(position.y),
             Self::clamp_horizontal(position.z),
         );
+        if !Self::validate_movement(player, server, position).await {
+            return;
+        }
 
         send_cancellable! {{
             PlayerMoveEvent::new(
@@ -419,15 +502,14 @@ impl JavaClient {
                 // let head_yaw = (entity.head_yaw * 256.0 / 360.0).floor();
                 let world = &entity.world;
 
-                // TODO: Warn when player moves to quickly
                 if !self
                     .sync_position(player, world, pos, last_pos, yaw, pitch, (packet.collision & FLAG_ON_GROUND) != 0)
                     .await
                 {
                     // Send the new position to all other players.
                     world
-                        .broadcast_packet_except(
-                            &[player.gameprofile.id],
+                        .broadcast_to_trackers(
+                            entity_id,
                             &CUpdateEntityPosRot::new(
                                 entity_id.into(),
                                 Vector3::new(
@@ -444,11 +526,9 @@ impl JavaClient {
                 }
 
                 world
-                    .broadcast_packet_except(
-                        &[player.gameprofile.id],
-                        &CHeadRot::new(entity_id.into(), yaw as u8),
-                    )
+                    .broadcast_to_trackers(entity_id, &CHeadRot::new(entity_id.into(), yaw as u8))
                     .await;
+                player.living_entity.check_climbing().await;
                 if !player.abilities.lock().await.flying {
                     player.living_entity
                         .fall(
@@ -541,6 +621,9 @@ impl JavaClient {
 
             'after: {
                 let command = event.command;
+                if !cooldown::check_cooldown(player, server, &command).await {
+                    break 'after;
+                }
                 let command_clone = command.clone();
                 // Some commands can take a long time to execute. If they do, they block packet processing for the player.
                 // That's why we will spawn a task instead.
@@ -683,9 +766,8 @@ impl JavaClient {
             let command_block = CommandBlockEntity {
                 position: pos,
                 powered: old_command_block.powered.load(Ordering::SeqCst).into(),
-                condition_met: old_command_block
-                    .condition_met
-                    .load(Ordering::SeqCst)
+                condition_met: CommandBlock::conditions_met(player.world(), &pos)
+                    .await
                     .into(),
                 auto: (command.flags & 0x4 != 0).into(),
                 dirty: old_command_block.dirty.load(Ordering::SeqCst).into(),
@@ -764,6 +846,9 @@ impl JavaClient {
         let sneak = input.input & SPlayerInput::SNEAK != 0;
         if player.get_entity().sneaking.load(Ordering::Relaxed) != sneak {
             player.get_entity().set_sneaking(sneak).await;
+            if sneak {
+                player.get_entity().stop_riding().await;
+            }
         }
     }
 
@@ -1136,7 +1221,7 @@ impl JavaClient {
             }
             1 => {
                 // Request stats
-                log::debug!("todo");
+                player.send_stats().await;
             }
             _ => {
                 self.kick(TextComponent::text("Invalid client status"))
@@ -1147,7 +1232,7 @@ impl JavaClient {
 
     pub async fn handle_interact(
         &self,
-        player: &Player,
+        player: &Arc<Player>,
         interact: SInteract,
         server: &Arc<Server>,
     ) {
@@ -1223,14 +1308,20 @@ impl JavaClient {
             }
             ActionType::Interact | ActionType::InteractAt => {
                 // TODO: split this up
-                let entity = player.world().get_player_by_id(entity_id.0).await;
-                if let Some(entity) = entity {
+                let world = player.world();
+                if let Some(entity) = world.get_player_by_id(entity_id.0).await {
                     let held = player.inventory.held_item();
                     let mut stack = held.lock().await;
                     server
                         .item_registry
                         .use_on_entity(&mut stack, player, entity)
                         .await;
+                } else if action == ActionType::Interact
+                    && interact.hand.is_none_or(|hand| hand.0 == 0)
+                    && let Some(entity) = world.get_entity_by_id(entity_id.0).await
+                {
+                    // Only the main hand is handled, the client sends one interaction per hand
+                    entity.interact(player).await;
                 }
             }
         }
@@ -1315,6 +1406,8 @@ impl JavaClient {
                                 .block_registry
                                 .broken(world, block, player, &position, server, broken_state)
                                 .await;
+                            player.damage_held_item_for_block(broken_state).await;
+                            player.increment_stat(Stat::Mined(block), 1).await;
                         } else {
                             player.mining.store(true, Ordering::Relaxed);
                             *player.mining_pos.lock().await = position;
@@ -1345,7 +1438,6 @@ impl JavaClient {
                     self.update_sequence(player, player_action.sequence.0);
                 }
                 Status::FinishedDigging => {
-                    // TODO: do validation
                     let location = player_action.position;
                     if !player.can_interact_with_block_at(&location, 1.0) {
                         log::warn!(
@@ -1359,11 +1451,35 @@ impl JavaClient {
                     // Block break & play sound
                     let entity = &player.living_entity.entity;
                     let world = &entity.world;
+                    let (block, state) = world.get_block_and_state(&location).await;
+
+                    let config = &server.advanced_config.world.block_breaking;
+                    if config.validate && player.gamemode.load() != GameMode::Creative {
+                        let was_mining = player.mining.load(Ordering::Relaxed)
+                            && *player.mining_pos.lock().await == location;
+                        let elapsed = player.tick_counter.load(Ordering::Relaxed)
+                            - player.start_mining_time.load(Ordering::Relaxed);
+                        let speed = block::calc_block_breaking(player, state, block).await;
+                        if !was_mining || !block::is_break_in_time(speed, elapsed, config) {
+                            log::debug!(
+                                "Player {} broke {location} after {elapsed} of {:?} ticks",
+                                player.gameprofile.name,
+                                block::break_ticks(speed)
+                            );
+                            player.mining.store(false, Ordering::Relaxed);
+                            world.set_block_breaking(entity, location, -1).await;
+                            self.enqueue_packet(&CBlockUpdate::new(
+                                location,
+                                VarInt(i32::from(state.id)),
+                            ))
+                            .await;
+                            self.update_sequence(player, player_action.sequence.0);
+                            return;
+                        }
+                    }
 
                     player.mining.store(false, Ordering::Relaxed);
                     world.set_block_breaking(entity, location, -1).await;
-
-                    let (block, state) = world.get_block_and_state(&location).await;
                     let block_drop = player.gamemode.load() != GameMode::Creative
                         && player.can_harvest(state, block).await;
 
@@ -1383,6 +1499,10 @@ impl JavaClient {
                         .block_registry
                         .broken(world, block, player, &location, server, state)
                         .await;
+                    player.damage_held_item_for_block(state).await;
+                    if player.gamemode.load() != GameMode::Creative {
+                        player.increment_stat(Stat::Mined(block), 1).await;
+                    }
 
                     self.update_sequence(player, player_action.sequence.0);
                 }
@@ -1408,6 +1528,8 @@ impl JavaClient {
             && keep_alive.keep_alive_id == player.keep_alive_id.load(Ordering::Relaxed)
         {
             player.wait_for_keep_alive.store(false, Ordering::Relaxed);
+            let round_trip = player.last_keep_alive_time.load().elapsed().as_millis();
+            self.latency.record(round_trip as u32);
         } else {
             self.kick(TextComponent::text(
                 "Timeout, The server probably has a deadlock!",
@@ -1448,7 +1570,7 @@ impl JavaClient {
 
     pub async fn handle_use_item_on(
         &self,
-        player: &Player,
+        player: &Arc<Player>,
         use_item_on: SUseItemOn,
         server: &Arc<Server>,
     ) -> Result<(), BlockPlacingError> {
@@ -1536,9 +1658,26 @@ impl JavaClient {
         // Check if the item is a block, because not every item can be placed :D
         let item_id = stack.item.id;
         if let Some(block) = Block::from_item_id(item_id) {
-            should_try_decrement = self
+            should_try_decrement = match self
                 .run_is_block_place(player, block, server, use_item_on, position, face)
-                .await?;
+                .await
+            {
+                Err(BlockPlacingError::Cancelled) => {
+                    // The client already took the item out of its hand
+                    let slot = if matches!(hand, Hand::Left) {
+                        usize::from(inventory.get_selected_slot())
+                    } else {
+                        PlayerInventory::OFF_HAND_SLOT
+                    };
+                    self.enqueue_packet(&CSetPlayerInventory::new(
+                        VarInt(slot as i32),
+                        &ItemStackSerializer::from(stack.clone()),
+                    ))
+                    .await;
+                    return Err(BlockPlacingError::Cancelled);
+                }
+                result => result?,
+            };
         }
 
         if should_try_decrement {
@@ -1615,6 +1754,37 @@ impl JavaClient {
         if sign_entity.is_waxed.load(Ordering::Relaxed) {
             return;
         }
+        // Only the player the sign was opened for may edit it, and only while still in reach
+        let mut currently_editing = sign_entity.currently_editing_player.lock().await;
+        if *currently_editing != Some(player.gameprofile.id)
+            || !player.can_interact_with_block_at(&sign_data.location, 4.0)
+        {
+            log::warn!(
+                "{} tried to change the sign at {} without editing it",
+                player.gameprofile.name,
+                sign_data.location
+            );
+            return;
+        }
+
+        let lines = [
+            sign_data.line_1,
+            sign_data.line_2,
+            sign_data.line_3,
+            sign_data.line_4,
+        ];
+        if let Some(line) = lines
+            .iter()
+            .find(|line| line.chars().count() > SIGN_LINE_MAX_LENGTH)
+        {
+            log::warn!(
+                "{} sent a sign update at {} with a line of {} characters, ignoring it",
+                player.gameprofile.name,
+                sign_data.location,
+                line.chars().count()
+            );
+            return;
+        }
 
         let text = if sign_data.is_front_text {
             &sign_entity.front_text
@@ -1622,13 +1792,9 @@ impl JavaClient {
             &sign_entity.back_text
         };
 
-        *text.messages.lock().unwrap() = [
-            sign_data.line_1,
-            sign_data.line_2,
-            sign_data.line_3,
-            sign_data.line_4,
-        ];
-        *sign_entity.currently_editing_player.lock().await = None;
+        *text.messages.lock().unwrap() = lines.map(|line| strip_sign_formatting(&line));
+        *currently_editing = None;
+        drop(currently_editing);
         world.update_block_entity(&block_entity).await;
     }
 
@@ -1687,6 +1853,9 @@ impl JavaClient {
             )
         };
         let mut held = item_in_hand.lock().await;
+        if player.is_item_cooling_down(&held).await {
+            return;
+        }
         if held.get_data_component::<ConsumableImpl>().is_some() {
             // If its food we want to make sure we can actually consume it
             if let Some(food) = held.get_data_component::<FoodImpl>() {
@@ -1812,14 +1981,12 @@ impl JavaClient {
     }
 
     pub async fn handle_chunk_batch(&self, player: &Player, packet: SChunkBatch) {
-        player
-            .chunk_manager
-            .lock()
-            .await
-            .handle_acknowledge(packet.chunks_per_tick);
+        let mut chunk_manager = player.chunk_manager.lock().await;
+        chunk_manager.handle_acknowledge(packet.chunks_per_tick);
         log::trace!(
-            "Client requested {} chunks per tick",
-            packet.chunks_per_tick
+            "Client requested {} chunks per tick, sending {}",
+            packet.chunks_per_tick,
+            chunk_manager.chunks_per_tick()
         );
     }
 
@@ -1832,6 +1999,21 @@ impl JavaClient {
         player.on_handled_screen_closed().await;
     }
 
+    pub async fn handle_select_trade(&self, player: &Player, packet: SSelectTrade) {
+        let Ok(index) = usize::try_from(packet.selected_slot.0) else {
+            return;
+        };
+        let screen_handler = player.current_screen_handler.lock().await.clone();
+        let mut screen_handler = screen_handler.lock().await;
+        if let Some(merchant_handler) = screen_handler
+            .as_any()
+            .downcast_ref::<MerchantScreenHandler>()
+        {
+            merchant_handler.switch_to(index).await;
+            screen_handler.send_content_updates().await;
+        }
+    }
+
     pub async fn handle_command_suggestion(
         &self,
         player: &Arc<Player>,
@@ -1865,13 +2047,15 @@ impl JavaClient {
         self.enqueue_packet(&response).await;
     }
 
-    pub fn handle_cookie_response(&self, packet: &SPCookieResponse) {
-        // TODO: allow plugins to access this
+    pub async fn handle_cookie_response(&self, packet: &SPCookieResponse) {
         log::debug!(
             "Received cookie_response[play]: key: \"{}\", payload_length: \"{:?}\"",
             packet.key,
             packet.payload.as_ref().map(|p| p.len())
         );
+        self.pending_cookies
+            .respond(&packet.key, packet.payload.as_deref())
+            .await;
     }
 
     const WORLD_LOWEST_Y: i8 = -64;
@@ -1880,7 +2064,7 @@ impl JavaClient {
     #[expect(clippy::too_many_lines)]
     async fn run_is_block_place(
         &self,
-        player: &Player,
+        player: &Arc<Player>,
         block: &'static Block,
         server: &Server,
         use_item_on: SUseItemOn,
@@ -1993,6 +2177,16 @@ impl JavaClient {
                 }
             };
 
+        if world.is_spawn_protected(&final_block_pos, player).await {
+            let state_id = world.get_block_state_id(&final_block_pos).await;
+            self.enqueue_packet(&CBlockUpdate::new(
+                final_block_pos,
+                VarInt(i32::from(state_id)),
+            ))
+            .await;
+            return Ok(false);
+        }
+
         if !server
             .block_registry
             .can_place_at(
@@ -2035,6 +2229,28 @@ impl JavaClient {
             }
         }
 
+        send_cancellable! {{
+            BlockPlaceEvent::new(
+                player.clone(),
+                block,
+                clicked_block,
+                final_block_pos,
+                final_face,
+                new_state,
+            );
+
+            'cancelled: {
+                let state_id = world.get_block_state_id(&final_block_pos).await;
+                self.enqueue_packet(&CBlockUpdate::new(
+                    final_block_pos,
+                    VarInt(i32::from(state_id)),
+                ))
+                .await;
+                return Err(BlockPlacingError::Cancelled);
+            }
+        }}
+        let new_state = event.block_state_id;
+
         let _replaced_id = world
             .set_block_state(&final_block_pos, new_state, BlockFlags::NOTIFY_ALL)
             .await;
@@ -2046,7 +2262,7 @@ impl JavaClient {
 
         server
             .block_registry
-            .player_placed(world, block, new_state, &final_block_pos, face, player.clone())
+            .player_placed(world, block, new_state, &final_block_pos, face, player)
             .await;
 
         // Trigger placed_block advancement
@@ -2144,3 +2360,99 @@ impl JavaClient {
         log::debug!("Recipe book seen recipe: {}", packet.recipe_id);
     }
 }
+
+/// Removes the characters that are not allowed in chat from a sign line. Like vanilla, only the
+/// section sign of a formatting code is dropped, the character after it stays.
+fn strip_sign_formatting(line: &str) -> String {
+    line.chars()
+        .filter(|&c| c != '§' && c >= ' ' && c != '\x7F')
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_protocol::java::server::play::SUpdateSign;
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_util::math::vector3::Vector3;
+    use pumpkin_world::block::entities::sign::SignBlockEntity;
+
+    use super::{SIGN_LINE_MAX_LENGTH, strip_sign_formatting};
+    use crate::entity::player::Player;
+    use crate::net::ClientPlatform;
+    use crate::server::Server;
+
+    #[test]
+    fn sign_lines_keep_formatted_text() {
+        assert_eq!(strip_sign_formatting("§cRed §lbold"), "cRed lbold");
+        assert_eq!(strip_sign_formatting("Tab\there\x7F§"), "Tabhere");
+        assert_eq!(strip_sign_formatting("Plain text"), "Plain text");
+    }
+
+    fn update(location: BlockPos, line: &str) -> SUpdateSign {
+        SUpdateSign {
+            location,
+            is_front_text: true,
+            line_1: line.to_string(),
+            line_2: String::new(),
+            line_3: String::new(),
+            line_4: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn only_the_editing_player_changes_signs() {
+        let (server, _world_dir) = Server::new_for_test().await;
+        let world = server.worlds.read().await[0].clone();
+        let (editor, _packets) = Player::new_for_test(world.clone(), "Editor").await;
+        let (other, _other_packets) = Player::new_for_test(world.clone(), "Other").await;
+        let ClientPlatform::Java(editor_client) = &editor.client else {
+            unreachable!();
+        };
+        let ClientPlatform::Java(other_client) = &other.client else {
+            unreachable!();
+        };
+        let location = BlockPos::new(0, 100, 1);
+        let sign = Arc::new(SignBlockEntity::empty(location));
+        world.add_block_entity(sign.clone()).await;
+        let front_line = || sign.front_text.messages.lock().unwrap()[0].clone();
+
+        // Nobody opened the sign yet
+        editor_client
+            .handle_sign_update(&editor, update(location, "Unopened"))
+            .await;
+        assert_eq!(front_line(), "");
+
+        *sign.currently_editing_player.lock().await = Some(editor.gameprofile.id);
+        other_client
+            .handle_sign_update(&other, update(location, "Not yours"))
+            .await;
+        assert_eq!(front_line(), "");
+
+        let too_long = "a".repeat(SIGN_LINE_MAX_LENGTH + 1);
+        editor_client
+            .handle_sign_update(&editor, update(location, &too_long))
+            .await;
+        assert_eq!(front_line(), "");
+
+        let longest = "a".repeat(SIGN_LINE_MAX_LENGTH);
+        editor_client
+            .handle_sign_update(&editor, update(location, &longest))
+            .await;
+        assert_eq!(front_line(), longest);
+        // Saving the text closes the sign
+        assert_eq!(*sign.currently_editing_player.lock().await, None);
+
+        // Walking away while the sign is open
+        *sign.currently_editing_player.lock().await = Some(editor.gameprofile.id);
+        editor
+            .living_entity
+            .entity
+            .set_pos(Vector3::new(100.0, 100.0, 100.0));
+        editor_client
+            .handle_sign_update(&editor, update(location, "Too far"))
+            .await;
+        assert_eq!(front_line(), longest);
+    }
+}
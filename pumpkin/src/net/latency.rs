@@ -0,0 +1,23 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// The smoothed round trip time of a client in milliseconds, shared by Java and Bedrock clients.
+#[derive(Default)]
+pub struct Latency(AtomicU32);
+
+impl Latency {
+    /// Records a measured round trip, weighting it like vanilla does with the previous value.
+    pub fn record(&self, round_trip_ms: u32) {
+        let previous = self.0.load(Ordering::Relaxed);
+        let latency = if previous == 0 {
+            round_trip_ms
+        } else {
+            ((u64::from(previous) * 3 + u64::from(round_trip_ms)) / 4) as u32
+        };
+        self.0.store(latency, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn get(&self) -> u32 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
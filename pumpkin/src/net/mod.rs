@@ -5,17 +5,21 @@ use std::{
 };
 
 use crate::{
+    PLUGIN_MANAGER,
     data::{
-        banned_ip_data::BANNED_IP_LIST, banned_player_data::BANNED_PLAYER_LIST,
-        op_data::OPERATOR_CONFIG, whitelist_data::WHITELIST_CONFIG,
+        banned_ip_data::BANNED_IP_LIST,
+        banned_player_data::BANNED_PLAYER_LIST,
+        op_data::OPERATOR_CONFIG,
+        whitelist_data::{WHITELIST_CONFIG, not_whitelisted_message},
     },
     entity::player::ChatMode,
     net::{bedrock::BedrockClient, java::JavaClient},
+    plugin::player::player_login::PlayerLoginEvent,
     server::Server,
 };
 
 use pumpkin_protocol::{ClientPacket, Property};
-use pumpkin_util::{Hand, ProfileAction, text::TextComponent};
+use pumpkin_util::{Hand, ProfileAction, math::vector2::Vector2, text::TextComponent};
 use serde::Deserialize;
 use sha1::Digest;
 use sha2::Sha256;
@@ -25,11 +29,18 @@ use thiserror::Error;
 use uuid::Uuid;
 pub mod authentication;
 pub mod bedrock;
+pub mod chunk_send_rate;
+pub mod connection_throttle;
+pub mod cookie;
 pub mod java;
 pub mod lan_broadcast;
+pub mod latency;
+pub mod packet_dump;
 mod proxy;
+pub mod proxy_protocol;
 pub mod query;
 pub mod rcon;
+pub mod status_endpoint;
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct GameProfile {
@@ -126,6 +137,16 @@ impl ClientPlatform {
         unreachable!()
     }
 
+    /// The smoothed round trip time in milliseconds, measured with keep alives on Java and
+    /// RakNet pings on Bedrock.
+    #[must_use]
+    pub fn latency(&self) -> u32 {
+        match self {
+            Self::Java(java) => java.latency.get(),
+            Self::Bedrock(bedrock) => bedrock.latency.get(),
+        }
+    }
+
     #[must_use]
     pub fn closed(&self) -> bool {
         match self {
@@ -159,6 +180,30 @@ impl ClientPlatform {
         }
     }
 
+    /// See [`JavaClient::discard_bulk_packets`].
+    pub fn discard_bulk_packets(&self) {
+        match self {
+            Self::Java(java) => java.discard_bulk_packets(),
+            Self::Bedrock(_) => (),
+        }
+    }
+
+    /// See [`JavaClient::enqueue_bulk_packet`].
+    pub async fn enqueue_bulk_packet<P: ClientPacket>(&self, packet: &P) {
+        match self {
+            Self::Java(java) => java.enqueue_bulk_packet(packet).await,
+            Self::Bedrock(_) => (),
+        }
+    }
+
+    /// See [`JavaClient::enqueue_block_packet`].
+    pub async fn enqueue_block_packet<P: ClientPacket>(&self, chunk: Vector2<i32>, packet: &P) {
+        match self {
+            Self::Java(java) => java.enqueue_block_packet(chunk, packet).await,
+            Self::Bedrock(_) => (),
+        }
+    }
+
     pub async fn send_packet_now<P: ClientPacket>(&self, packet: &P) {
         match self {
             Self::Java(java) => java.send_packet_now(packet).await,
@@ -166,6 +211,8 @@ impl ClientPlatform {
         }
     }
 
+    /// Disconnects the client with `message`. Only Bedrock clients are told the `reason`, Java
+    /// clients have no reason codes.
     pub async fn kick(&self, reason: DisconnectReason, message: TextComponent) {
         match self {
             Self::Java(java) => java.kick(message).await,
@@ -174,30 +221,21 @@ impl ClientPlatform {
     }
 }
 
+/// Whether `max_players` players are online already, `0` means there is no limit.
+// TODO: Let operators bypass the limit
+pub async fn is_server_full(server: &Server) -> bool {
+    let max_players = server.basic_config.max_players;
+    max_players > 0 && server.get_player_count().await >= max_players as usize
+}
+
 pub async fn can_not_join(
     profile: &GameProfile,
     address: &SocketAddr,
     server: &Server,
 ) -> Option<TextComponent> {
-    const FORMAT_DESCRIPTION: &[time::format_description::FormatItem<'static>] = time::macros::format_description!(
-        "[year]-[month]-[day] at [hour]:[minute]:[second] [offset_hour sign:mandatory]:[offset_minute]"
-    );
-
     let mut banned_players = BANNED_PLAYER_LIST.write().await;
     if let Some(entry) = banned_players.get_entry(profile) {
-        let text = TextComponent::translate(
-            "multiplayer.disconnect.banned.reason",
-            [TextComponent::text(entry.reason.clone())],
-        );
-        return Some(match entry.expires {
-            Some(expires) => text.add_child(TextComponent::translate(
-                "multiplayer.disconnect.banned.expiration",
-                [TextComponent::text(
-                    expires.format(FORMAT_DESCRIPTION).unwrap(),
-                )],
-            )),
-            None => text,
-        });
+        return Some(entry.disconnect_message());
     }
     drop(banned_players);
 
@@ -205,33 +243,36 @@ pub async fn can_not_join(
         let ops = OPERATOR_CONFIG.read().await;
         let whitelist = WHITELIST_CONFIG.read().await;
 
-        if ops.get_entry(&profile.id).is_none() && !whitelist.is_whitelisted(profile) {
-            return Some(TextComponent::translate(
-                "multiplayer.disconnect.not_whitelisted",
-                &[],
-            ));
+        if !whitelist.allows(&ops, profile) {
+            return Some(not_whitelisted_message(&server.basic_config));
         }
     }
 
     if let Some(entry) = BANNED_IP_LIST.write().await.get_entry(&address.ip()) {
-        let text = TextComponent::translate(
-            "multiplayer.disconnect.banned_ip.reason",
-            [TextComponent::text(entry.reason.clone())],
-        );
-        return Some(match entry.expires {
-            Some(expires) => text.add_child(TextComponent::translate(
-                "multiplayer.disconnect.banned_ip.expiration",
-                [TextComponent::text(
-                    expires.format(FORMAT_DESCRIPTION).unwrap(),
-                )],
-            )),
-            None => text,
-        });
+        return Some(entry.disconnect_message());
     }
 
     None
 }
 
+/// Fires the [`PlayerLoginEvent`], returning the kick message if a plugin denied the login.
+pub async fn login_denied_by_plugins(
+    profile: &GameProfile,
+    address: &SocketAddr,
+) -> Option<TextComponent> {
+    if !PLUGIN_MANAGER.has_handlers::<PlayerLoginEvent>().await {
+        return None;
+    }
+    let event = PLUGIN_MANAGER
+        .fire(PlayerLoginEvent::new(
+            profile.clone(),
+            *address,
+            TextComponent::text("You have been kicked from the server"),
+        ))
+        .await;
+    event.cancelled.then_some(event.kick_message)
+}
+
 #[derive(Error, Debug)]
 pub enum EncryptionError {
     #[error("failed to decrypt shared secret")]
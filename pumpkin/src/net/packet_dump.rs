@@ -0,0 +1,151 @@
+use std::fmt::{self, Display, Write as _};
+use std::fs::{self, File};
+use std::io::{LineWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use pumpkin_config::networking::packet_dump::PacketDumpConfig;
+use pumpkin_protocol::codec::var_int::VarInt;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PacketDirection {
+    Serverbound,
+    Clientbound,
+}
+
+/// A single line of a packet dump.
+///
+/// Lines look like `<ms since connect> <S|C> <id> <payload length> <payload hex>`, so they can
+/// be read back with [`DumpedPacket::parse`] to replay a session.
+#[derive(PartialEq, Eq, Debug)]
+pub struct DumpedPacket {
+    pub elapsed: Duration,
+    pub direction: PacketDirection,
+    pub id: i32,
+    pub payload: Vec<u8>,
+}
+
+impl DumpedPacket {
+    #[must_use]
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.split(' ');
+        let elapsed = Duration::from_millis(parts.next()?.parse().ok()?);
+        let direction = match parts.next()? {
+            "S" => PacketDirection::Serverbound,
+            "C" => PacketDirection::Clientbound,
+            _ => return None,
+        };
+        let id = i32::from_str_radix(parts.next()?.strip_prefix("0x")?, 16).ok()?;
+        let length: usize = parts.next()?.parse().ok()?;
+        let hex = parts.next().unwrap_or_default();
+        if hex.len() != length * 2 {
+            return None;
+        }
+        let payload = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+            .collect::<Option<_>>()?;
+        Some(Self {
+            elapsed,
+            direction,
+            id,
+            payload,
+        })
+    }
+}
+
+impl Display for DumpedPacket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            PacketDirection::Serverbound => 'S',
+            PacketDirection::Clientbound => 'C',
+        };
+        let mut hex = String::with_capacity(self.payload.len() * 2);
+        for byte in &self.payload {
+            write!(hex, "{byte:02x}")?;
+        }
+        write!(
+            f,
+            "{} {direction} 0x{:02x} {} {hex}",
+            self.elapsed.as_millis(),
+            self.id,
+            self.payload.len()
+        )
+    }
+}
+
+/// Writes the decrypted and decompressed packets of a single client to a file.
+pub struct PacketDump {
+    connected_at: Instant,
+    file: Mutex<LineWriter<File>>,
+}
+
+impl PacketDump {
+    /// Creates the dump file of a client, returns `None` if dumping is disabled or the file
+    /// could not be created.
+    #[must_use]
+    pub fn for_client(config: &PacketDumpConfig, client_id: u64) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let path = Path::new(&config.directory).join(format!("client-{client_id}.txt"));
+        let file = fs::create_dir_all(&config.directory).and_then(|()| File::create(&path));
+        match file {
+            Ok(file) => Some(Self {
+                connected_at: Instant::now(),
+                file: Mutex::new(LineWriter::new(file)),
+            }),
+            Err(err) => {
+                log::warn!("Failed to create packet dump {}: {err}", path.display());
+                None
+            }
+        }
+    }
+
+    pub fn record(&self, direction: PacketDirection, id: i32, payload: &[u8]) {
+        let packet = DumpedPacket {
+            elapsed: self.connected_at.elapsed(),
+            direction,
+            id,
+            payload: payload.to_vec(),
+        };
+        if let Err(err) = writeln!(self.file.lock().unwrap(), "{packet}") {
+            log::warn!("Failed to write packet dump: {err}");
+        }
+    }
+
+    /// Records a clientbound packet as it is queued for the network, its id followed by the payload.
+    pub fn record_clientbound(&self, packet_data: &[u8]) {
+        let mut payload = packet_data;
+        if let Ok(id) = VarInt::decode(&mut payload) {
+            self.record(PacketDirection::Clientbound, id.0, payload);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{DumpedPacket, PacketDirection};
+
+    #[test]
+    fn dumped_lines_can_be_replayed() {
+        let packet = DumpedPacket {
+            elapsed: Duration::from_millis(1520),
+            direction: PacketDirection::Clientbound,
+            id: 0x2b,
+            payload: vec![0x00, 0xff, 0x10],
+        };
+        let line = packet.to_string();
+
+        assert_eq!(line, "1520 C 0x2b 3 00ff10");
+        assert_eq!(DumpedPacket::parse(&line), Some(packet));
+        assert_eq!(
+            DumpedPacket::parse("0 S 0x00 0 ").map(|packet| packet.payload),
+            Some(Vec::new())
+        );
+        assert_eq!(DumpedPacket::parse("0 S 0x00 2 00"), None);
+    }
+}
@@ -0,0 +1,252 @@
+//! The PROXY protocol, with which TCP load balancers tell the server the real address of a client
+//! before passing its connection on. <https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt>
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use pumpkin_config::networking::proxy_protocol::ProxyProtocolConfig;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::time::timeout;
+
+use super::connection_throttle::IpRange;
+
+const V1_PREFIX: &[u8; 5] = b"PROXY";
+/// The longest possible version 1 header, including the line break.
+const V1_MAX_LENGTH: usize = 107;
+const V2_SIGNATURE: &[u8; 12] = b"\r\n\r\n\0\r\nQUIT\n";
+/// Proxies send the header right away, a connection which doesn't is closed after this time.
+const HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Error, Debug)]
+pub enum ProxyProtocolError {
+    #[error("Failed to read the PROXY header: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The PROXY header was not sent in time")]
+    Timeout,
+    #[error("The connection doesn't start with a PROXY header")]
+    MissingHeader,
+    #[error("Malformed PROXY header: {0}")]
+    Malformed(&'static str),
+}
+
+/// Decides which connections start with a PROXY header.
+pub struct ProxyProtocol {
+    trusted_proxies: Vec<IpRange>,
+}
+
+impl ProxyProtocol {
+    #[must_use]
+    pub fn new(config: &ProxyProtocolConfig) -> Self {
+        if !config.enabled {
+            return Self {
+                trusted_proxies: Vec::new(),
+            };
+        }
+        let trusted_proxies: Vec<_> = config
+            .trusted_proxies
+            .iter()
+            .filter_map(|range| {
+                let parsed = IpRange::parse(range);
+                if parsed.is_none() {
+                    log::warn!("Ignoring invalid trusted proxy {range}");
+                }
+                parsed
+            })
+            .collect();
+        if trusted_proxies.is_empty() {
+            log::warn!("The PROXY protocol is enabled, but no proxies are trusted");
+        }
+        Self { trusted_proxies }
+    }
+
+    /// Whether connections from `address` have to start with a PROXY header.
+    #[must_use]
+    pub fn expects_header(&self, address: IpAddr) -> bool {
+        self.trusted_proxies
+            .iter()
+            .any(|range| range.contains(address))
+    }
+}
+
+/// Reads the PROXY header at the start of a connection, leaving the data after it untouched.
+/// Returns the address of the client, or `None` if the proxy connected on its own behalf, like
+/// for a health check.
+pub async fn read_header<R: AsyncRead + Unpin>(
+    stream: &mut R,
+) -> Result<Option<SocketAddr>, ProxyProtocolError> {
+    timeout(HEADER_TIMEOUT, read_header_now(stream))
+        .await
+        .map_err(|_| ProxyProtocolError::Timeout)?
+}
+
+async fn read_header_now<R: AsyncRead + Unpin>(
+    stream: &mut R,
+) -> Result<Option<SocketAddr>, ProxyProtocolError> {
+    let mut start = [0; 5];
+    stream.read_exact(&mut start).await?;
+    if start == *V1_PREFIX {
+        let mut line = start.to_vec();
+        while !line.ends_with(b"\r\n") {
+            if line.len() >= V1_MAX_LENGTH {
+                return Err(ProxyProtocolError::Malformed("the header is too long"));
+            }
+            line.push(stream.read_u8().await?);
+        }
+        parse_v1(&line)
+    } else if start == V2_SIGNATURE[..5] {
+        let mut header = [0; 16];
+        header[..5].copy_from_slice(&start);
+        stream.read_exact(&mut header[5..]).await?;
+        let length = u16::from_be_bytes([header[14], header[15]]);
+        let mut addresses = vec![0; usize::from(length)];
+        stream.read_exact(&mut addresses).await?;
+        parse_v2(&header, &addresses)
+    } else {
+        Err(ProxyProtocolError::MissingHeader)
+    }
+}
+
+/// Parses a human-readable version 1 header like `PROXY TCP4 <source> <destination> <source port>
+/// <destination port>\r\n`.
+fn parse_v1(line: &[u8]) -> Result<Option<SocketAddr>, ProxyProtocolError> {
+    use ProxyProtocolError::Malformed;
+
+    let line = std::str::from_utf8(line).map_err(|_| Malformed("the header isn't ASCII"))?;
+    let line = line
+        .strip_suffix("\r\n")
+        .ok_or(Malformed("the header doesn't end with a line break"))?;
+    let mut parts = line.split(' ');
+    if parts.next() != Some("PROXY") {
+        return Err(Malformed("the header doesn't start with PROXY"));
+    }
+    let ipv4 = match parts.next() {
+        Some("TCP4") => true,
+        Some("TCP6") => false,
+        Some("UNKNOWN") => return Ok(None),
+        _ => return Err(Malformed("unknown protocol")),
+    };
+    let (Some(source), Some(_), Some(source_port), Some(_), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return Err(Malformed("wrong number of fields"));
+    };
+    let source: IpAddr = source
+        .parse()
+        .map_err(|_| Malformed("invalid source address"))?;
+    if source.is_ipv4() != ipv4 {
+        return Err(Malformed("the source address doesn't match the protocol"));
+    }
+    let source_port = source_port
+        .parse()
+        .map_err(|_| Malformed("invalid source port"))?;
+    Ok(Some(SocketAddr::new(source, source_port)))
+}
+
+/// Parses a binary version 2 header, made of 16 fixed bytes and the addresses after them.
+fn parse_v2(header: &[u8; 16], addresses: &[u8]) -> Result<Option<SocketAddr>, ProxyProtocolError> {
+    use ProxyProtocolError::Malformed;
+
+    if header[..12] != *V2_SIGNATURE {
+        return Err(Malformed("invalid signature"));
+    }
+    if header[12] >> 4 != 2 {
+        return Err(Malformed("unsupported version"));
+    }
+    match header[12] & 0x0F {
+        // LOCAL, the proxy itself connected
+        0 => return Ok(None),
+        // PROXY
+        1 => {}
+        _ => return Err(Malformed("unknown command")),
+    }
+    let cut_off = Malformed("the addresses are cut off");
+    let source = match header[13] >> 4 {
+        // IPv4, followed by the destination address and both ports
+        1 => {
+            let addresses = addresses.get(..12).ok_or(cut_off)?;
+            let ip: [u8; 4] = addresses[..4].try_into().unwrap();
+            let port = u16::from_be_bytes([addresses[8], addresses[9]]);
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::from(ip)), port)
+        }
+        // IPv6
+        2 => {
+            let addresses = addresses.get(..36).ok_or(cut_off)?;
+            let ip: [u8; 16] = addresses[..16].try_into().unwrap();
+            let port = u16::from_be_bytes([addresses[32], addresses[33]]);
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::from(ip)), port)
+        }
+        // Unspecified or unix sockets, which have no address to use
+        _ => return Ok(None),
+    };
+    Ok(Some(source))
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddr;
+
+    use tokio::io::AsyncReadExt;
+
+    use super::{ProxyProtocolError, read_header};
+
+    fn address(address: &str) -> Option<SocketAddr> {
+        Some(address.parse().unwrap())
+    }
+
+    #[tokio::test]
+    async fn headers_are_read_without_the_data_after_them() {
+        let mut stream: &[u8] = b"PROXY TCP4 192.168.0.1 10.0.0.2 56324 25565\r\n\x10\x00";
+        let client = read_header(&mut stream).await.unwrap();
+        assert_eq!(client, address("192.168.0.1:56324"));
+        assert_eq!(stream.read_u8().await.unwrap(), 0x10);
+
+        let mut stream: &[u8] =
+            b"PROXY TCP6 2001:db8::1 2001:db8::2 4000 25565\r\nPROXY UNKNOWN\r\n";
+        let client = read_header(&mut stream).await.unwrap();
+        assert_eq!(client, address("[2001:db8::1]:4000"));
+        assert_eq!(read_header(&mut stream).await.unwrap(), None);
+
+        let mut v2 = b"\r\n\r\n\0\r\nQUIT\n\x21\x11\x00\x0C".to_vec();
+        v2.extend([203, 0, 113, 7, 10, 0, 0, 2, 0x1F, 0x90, 0x63, 0xDD, 0x10]);
+        let mut stream = v2.as_slice();
+        let client = read_header(&mut stream).await.unwrap();
+        assert_eq!(client, address("203.0.113.7:8080"));
+        assert_eq!(stream, [0x10]);
+
+        let mut stream: &[u8] = b"\r\n\r\n\0\r\nQUIT\n\x20\x00\x00\x00";
+        assert_eq!(read_header(&mut stream).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn malformed_headers_are_rejected() {
+        let read = |data: &'static [u8]| async move {
+            let mut stream = data;
+            read_header(&mut stream).await
+        };
+        assert!(matches!(
+            read(b"\x10\x00\xFA\x05").await,
+            Err(ProxyProtocolError::MissingHeader)
+        ));
+        assert!(matches!(
+            read(b"PROXY TCP4 2001:db8::1 10.0.0.2 1 2\r\n").await,
+            Err(ProxyProtocolError::Malformed(_))
+        ));
+        assert!(matches!(
+            read(b"PROXY TCP4 192.168.0.1 10.0.0.2 56324\r\n").await,
+            Err(ProxyProtocolError::Malformed(_))
+        ));
+        assert!(matches!(
+            read(b"PROXY TCP4 192.168.0.1").await,
+            Err(ProxyProtocolError::Io(_))
+        ));
+        assert!(matches!(
+            read(b"\r\n\r\n\0\r\nQUIT\n\x21\x11\x00\x04\x01\x02\x03\x04").await,
+            Err(ProxyProtocolError::Malformed(_))
+        ));
+    }
+}
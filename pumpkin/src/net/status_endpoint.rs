@@ -0,0 +1,184 @@
+//! A tiny HTTP/1.1 endpoint serving the server status as JSON, for dashboards which shouldn't
+//! need RCON access.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::{select, time};
+
+use crate::server::Server;
+use crate::{SHOULD_STOP, STOP_INTERRUPT};
+
+/// Requests with a longer head are rejected, the endpoint never needs a body.
+const MAX_REQUEST_SIZE: usize = 8192;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Serialize)]
+struct StatusReport {
+    online_players: usize,
+    max_players: u32,
+    players: Vec<PlayerStatus>,
+    /// The average time of the last 100 ticks, in milliseconds.
+    mspt: f64,
+    worlds: Vec<WorldStatus>,
+}
+
+#[derive(Serialize)]
+struct PlayerStatus {
+    name: String,
+    uuid: String,
+}
+
+#[derive(Serialize)]
+struct WorldStatus {
+    dimension: &'static str,
+    players: usize,
+    entities: usize,
+    loaded_chunks: usize,
+    /// Chunk tasks waiting for the generation threads.
+    generation_queue: usize,
+}
+
+pub async fn start_status_endpoint(server: Arc<Server>, address: SocketAddr) {
+    let listener = match TcpListener::bind(address).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            log::error!("Failed to start the status endpoint on {address}: {err}");
+            return;
+        }
+    };
+    log::info!("Status endpoint running on http://{address}/status");
+
+    while !SHOULD_STOP.load(Ordering::Relaxed) {
+        let accepted = select! {
+            accepted = listener.accept() => accepted,
+            () = STOP_INTERRUPT.notified() => break,
+        };
+        let Ok((stream, _)) = accepted else {
+            continue;
+        };
+        let server = server.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, &server).await {
+                log::debug!("Status endpoint connection failed: {err}");
+            }
+        });
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, server: &Server) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    let head_read = time::timeout(REQUEST_TIMEOUT, async {
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+            let read = stream.read(&mut buf).await?;
+            if read == 0 || request.len() + read > MAX_REQUEST_SIZE {
+                return Ok(false);
+            }
+            request.extend_from_slice(&buf[..read]);
+        }
+        Ok::<_, std::io::Error>(true)
+    })
+    .await;
+    if !matches!(head_read, Ok(Ok(true))) {
+        return Ok(());
+    }
+
+    let response = match parse_request_line(&request) {
+        Some(("GET", "/" | "/status")) => {
+            let report = status_report(server).await;
+            match serde_json::to_string(&report) {
+                Ok(json) => http_response("200 OK", &json),
+                Err(err) => {
+                    log::error!("Failed to serialize the server status: {err}");
+                    http_response("500 Internal Server Error", r#"{"error":"internal"}"#)
+                }
+            }
+        }
+        Some(("GET", _)) => http_response("404 Not Found", r#"{"error":"not found"}"#),
+        Some(_) => http_response(
+            "405 Method Not Allowed",
+            r#"{"error":"method not allowed"}"#,
+        ),
+        None => http_response("400 Bad Request", r#"{"error":"bad request"}"#),
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// The method and path without its query of an HTTP/1.x request.
+fn parse_request_line(request: &[u8]) -> Option<(&str, &str)> {
+    let request = std::str::from_utf8(request).ok()?;
+    let line = request.split("\r\n").next()?;
+    let mut parts = line.split(' ');
+    let method = parts.next()?;
+    let target = parts.next()?;
+    let version = parts.next()?;
+    if !version.starts_with("HTTP/1.") || parts.next().is_some() {
+        return None;
+    }
+    let path = target.split_once('?').map_or(target, |(path, _)| path);
+    Some((method, path))
+}
+
+fn http_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+async fn status_report(server: &Server) -> StatusReport {
+    let mut players = Vec::new();
+    let mut worlds = Vec::new();
+    for world in server.worlds.read().await.iter() {
+        let world_players = world.players.read().await;
+        players.extend(world_players.values().map(|player| PlayerStatus {
+            name: player.gameprofile.name.clone(),
+            uuid: player.gameprofile.id.to_string(),
+        }));
+        worlds.push(WorldStatus {
+            dimension: world.dimension.minecraft_name,
+            players: world_players.len(),
+            entities: world.entities.read().await.len(),
+            loaded_chunks: world.level.loaded_chunk_count(),
+            generation_queue: world.level.generation_queue_len.load(Ordering::Relaxed),
+        });
+    }
+    StatusReport {
+        online_players: players.len(),
+        max_players: server.basic_config.max_players,
+        players,
+        mspt: server.get_average_tick_time_nanos() as f64 / 1_000_000.0,
+        worlds,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{http_response, parse_request_line};
+
+    #[test]
+    fn parses_http_requests() {
+        assert_eq!(
+            parse_request_line(b"GET /status?pretty HTTP/1.1\r\nHost: localhost\r\n\r\n"),
+            Some(("GET", "/status"))
+        );
+        assert_eq!(
+            parse_request_line(b"POST / HTTP/1.0\r\n\r\n"),
+            Some(("POST", "/"))
+        );
+        assert_eq!(parse_request_line(b"GET /\r\n\r\n"), None);
+        assert_eq!(parse_request_line(b"\x16\x03\x01 TLS"), None);
+
+        assert_eq!(
+            http_response("200 OK", "{}"),
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}"
+        );
+    }
+}
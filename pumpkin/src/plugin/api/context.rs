@@ -161,6 +161,11 @@ impl Context {
             format!("{plugin_name}:{permission}")
         };
 
+        self.server
+            .plugin_commands
+            .lock()
+            .await
+            .push((tree.clone(), full_permission_node.clone()));
         {
             let mut dispatcher_lock = self.server.command_dispatcher.write().await;
             dispatcher_lock.register(tree, full_permission_node);
@@ -179,6 +184,11 @@ impl Context {
     /// # Arguments
     /// - `name`: The name of the command to unregister.
     pub async fn unregister_command(&self, name: &str) {
+        self.server
+            .plugin_commands
+            .lock()
+            .await
+            .retain(|(tree, _)| tree.names.first().is_none_or(|primary| primary != name));
         {
             let mut dispatcher_lock = self.server.command_dispatcher.write().await;
             dispatcher_lock.unregister(name);
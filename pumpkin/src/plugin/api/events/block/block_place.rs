@@ -1,5 +1,7 @@
-use pumpkin_data::Block;
+use pumpkin_data::{Block, BlockDirection};
 use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
 use std::sync::Arc;
 
 use crate::entity::player::Player;
@@ -9,7 +11,7 @@ use super::BlockEvent;
 /// An event that occurs when a block is placed.
 ///
 /// This event contains information about the player placing the block, the block being placed,
-/// the block being placed against, and whether the player can build.
+/// the block being placed against, where it is placed and the state it is placed with.
 #[cancellable]
 #[derive(Event, Clone)]
 pub struct BlockPlaceEvent {
@@ -22,10 +24,54 @@ pub struct BlockPlaceEvent {
     /// The block that the new block is being placed against.
     pub block_placed_against: &'static Block,
 
+    /// The position the block is placed at.
+    pub block_position: BlockPos,
+
+    /// The face of the block that was clicked to place the block.
+    pub face: BlockDirection,
+
+    /// The state the block is placed with, plugins may change it.
+    pub block_state_id: BlockStateId,
+
     /// A boolean indicating whether the player can build.
     pub can_build: bool,
 }
 
+impl BlockPlaceEvent {
+    /// Creates a new instance of `BlockPlaceEvent`.
+    ///
+    /// # Arguments
+    /// - `player`: The player placing the block.
+    /// - `block_placed`: The block that is being placed.
+    /// - `block_placed_against`: The block that the new block is being placed against.
+    /// - `block_position`: The position the block is placed at.
+    /// - `face`: The face of the block that was clicked.
+    /// - `block_state_id`: The state the block is placed with.
+    ///
+    /// # Returns
+    /// A new instance of `BlockPlaceEvent`.
+    #[must_use]
+    pub fn new(
+        player: Arc<Player>,
+        block_placed: &'static Block,
+        block_placed_against: &'static Block,
+        block_position: BlockPos,
+        face: BlockDirection,
+        block_state_id: BlockStateId,
+    ) -> Self {
+        Self {
+            player,
+            block_placed,
+            block_placed_against,
+            block_position,
+            face,
+            block_state_id,
+            can_build: true,
+            cancelled: false,
+        }
+    }
+}
+
 impl BlockEvent for BlockPlaceEvent {
     fn get_block(&self) -> &Block {
         self.block_placed
@@ -1,21 +1,22 @@
 use pumpkin_macros::{Event, cancellable};
 use pumpkin_util::text::TextComponent;
-use std::sync::Arc;
+use std::net::SocketAddr;
 
-use crate::entity::player::Player;
+use crate::net::GameProfile;
 
-use super::PlayerEvent;
-
-/// An event that occurs before a player joins the game.
-///
-/// If the event is cancelled, the player will be kicked from the server.
+/// An event that occurs once the game profile of a connecting player is known, before the player
+/// is told that the login succeeded. It is fired for Java and Bedrock players.
 ///
-/// This event contains information about the player joining and has the option to set a kick message.
+/// If the event is cancelled, the client is disconnected with the kick message before it joins.
+/// Blocking handlers are awaited, so they can ask an external service like a ban list first.
 #[cancellable]
 #[derive(Event, Clone)]
 pub struct PlayerLoginEvent {
-    /// The player who is joining the game.
-    pub player: Arc<Player>,
+    /// The profile of the player trying to log in.
+    pub profile: GameProfile,
+
+    /// The address the player connects from.
+    pub address: SocketAddr,
 
     /// The kick message to display if the event is cancelled.
     pub kick_message: TextComponent,
@@ -25,22 +26,18 @@ impl PlayerLoginEvent {
     /// Creates a new instance of `PlayerLoginEvent`.
     ///
     /// # Arguments
-    /// - `player`: A reference to the player joining the game.
-    /// - `kick_message`: The message to display upon joining.
+    /// - `profile`: The profile of the player trying to log in.
+    /// - `address`: The address the player connects from.
+    /// - `kick_message`: The message to display if the login is denied.
     ///
     /// # Returns
     /// A new instance of `PlayerLoginEvent`.
-    pub fn new(player: Arc<Player>, kick_message: TextComponent) -> Self {
+    pub fn new(profile: GameProfile, address: SocketAddr, kick_message: TextComponent) -> Self {
         Self {
-            player,
+            profile,
+            address,
             kick_message,
             cancelled: false,
         }
     }
 }
-
-impl PlayerEvent for PlayerLoginEvent {
-    fn get_player(&self) -> &Arc<Player> {
-        &self.player
-    }
-}
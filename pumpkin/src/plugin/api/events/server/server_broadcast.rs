@@ -11,6 +11,8 @@ pub struct ServerBroadcastEvent {
     pub message: TextComponent,
     /// The name of the sender as a `TextComponent`.
     pub sender: TextComponent,
+    /// Whether this is a system broadcast, which is never shown as a player message.
+    pub system: bool,
 }
 
 impl ServerBroadcastEvent {
@@ -27,6 +29,24 @@ impl ServerBroadcastEvent {
         Self {
             message,
             sender,
+            system: false,
+            cancelled: false,
+        }
+    }
+
+    /// Creates a new instance of `ServerBroadcastEvent` for a system broadcast.
+    ///
+    /// # Arguments
+    /// - `message`: The message being broadcast.
+    ///
+    /// # Returns
+    /// A new instance of `ServerBroadcastEvent` without a sender.
+    #[must_use]
+    pub fn system(message: TextComponent) -> Self {
+        Self {
+            message,
+            sender: TextComponent::text(""),
+            system: true,
             cancelled: false,
         }
     }
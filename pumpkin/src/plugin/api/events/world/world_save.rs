@@ -0,0 +1,124 @@
+use crate::world::World;
+use pumpkin_macros::Event;
+use std::sync::Arc;
+
+/// Whether a world is about to be saved or has just been saved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorldSavePhase {
+    Pre,
+    Post,
+}
+
+/// An event that occurs right before and right after a world is saved.
+///
+/// Plugins can use it to persist their own data alongside the world.
+#[derive(Event, Clone)]
+pub struct WorldSaveEvent {
+    /// The world being saved.
+    pub world: Arc<World>,
+
+    /// Whether the save is about to start or has finished.
+    pub phase: WorldSavePhase,
+}
+
+impl WorldSaveEvent {
+    /// Creates a new instance of `WorldSaveEvent`.
+    ///
+    /// # Arguments
+    /// - `world`: The world being saved.
+    /// - `phase`: Whether the save is about to start or has finished.
+    ///
+    /// # Returns
+    /// A new instance of `WorldSaveEvent`.
+    #[must_use]
+    pub const fn new(world: Arc<World>, phase: WorldSavePhase) -> Self {
+        Self { world, phase }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::Ordering;
+    use std::sync::{Arc, Mutex};
+
+    use pumpkin_config::{AdvancedConfiguration, BasicConfiguration};
+
+    use super::{WorldSaveEvent, WorldSavePhase};
+    use crate::PLUGIN_MANAGER;
+    use crate::plugin::{BoxFuture, EventHandler, EventPriority};
+    use crate::server::{AUTOSAVE_INTERVAL_TICKS, Server, is_autosave_tick, should_autosave};
+
+    #[derive(Default)]
+    struct SaveHandler {
+        phases: Mutex<Vec<WorldSavePhase>>,
+    }
+
+    impl EventHandler<WorldSaveEvent> for SaveHandler {
+        fn handle_blocking<'a>(
+            &'a self,
+            _server: &'a Arc<Server>,
+            event: &'a mut WorldSaveEvent,
+        ) -> BoxFuture<'a, ()> {
+            Box::pin(async move { self.phases.lock().unwrap().push(event.phase) })
+        }
+    }
+
+    #[tokio::test]
+    async fn autosaves_fire_the_save_event() {
+        assert!(!is_autosave_tick(0));
+        assert!(!is_autosave_tick(AUTOSAVE_INTERVAL_TICKS - 1));
+        assert!(is_autosave_tick(AUTOSAVE_INTERVAL_TICKS));
+
+        let world_dir = tempfile::tempdir().unwrap();
+        let basic_config = BasicConfiguration {
+            default_level_name: world_dir.path().to_string_lossy().into_owned(),
+            ..Default::default()
+        };
+        let server = Server::new(basic_config, AdvancedConfiguration::default()).await;
+        PLUGIN_MANAGER.set_server(server.clone()).await;
+        let handler = Arc::new(SaveHandler::default());
+        PLUGIN_MANAGER
+            .register::<WorldSaveEvent, _>(handler.clone(), EventPriority::Normal, true)
+            .await;
+
+        // Nothing is saved between autosaves
+        server
+            .tick_count
+            .store(AUTOSAVE_INTERVAL_TICKS - 1, Ordering::Relaxed);
+        server.autosave().await;
+        assert!(handler.phases.lock().unwrap().is_empty());
+
+        server
+            .tick_count
+            .store(AUTOSAVE_INTERVAL_TICKS, Ordering::Relaxed);
+        server.autosave().await;
+        let worlds = server.worlds.read().await.len();
+        assert_eq!(
+            *handler.phases.lock().unwrap(),
+            [WorldSavePhase::Pre, WorldSavePhase::Post].repeat(worlds)
+        );
+    }
+
+    #[test]
+    fn autosaves_wait_for_players() {
+        let autosave = AUTOSAVE_INTERVAL_TICKS;
+        let between = AUTOSAVE_INTERVAL_TICKS - 1;
+
+        // By default the worlds are saved whether or not anyone is online
+        assert!(should_autosave(autosave, false, false, false));
+        assert!(!should_autosave(between, false, true, false));
+
+        let only_with_players =
+            |tick, had_players, has_players| should_autosave(tick, true, had_players, has_players);
+        // Nobody online, nothing to save
+        assert!(!only_with_players(autosave, false, false));
+        assert!(only_with_players(autosave, true, true));
+        assert!(!only_with_players(between, true, true));
+
+        // The last player left, save once even between autosaves
+        assert!(only_with_players(between, true, false));
+        assert!(!only_with_players(autosave, false, false));
+        // A player joining doesn't save
+        assert!(!only_with_players(between, false, true));
+    }
+}
@@ -620,6 +620,15 @@ impl PluginManager {
             .push(Box::new(typed_handler));
     }
 
+    /// Returns whether any handler is registered for the event, so callers can skip building it.
+    pub async fn has_handlers<E: Payload + 'static>(&self) -> bool {
+        self.handlers
+            .read()
+            .await
+            .get(&E::get_name_static())
+            .is_some_and(|handlers| !handlers.is_empty())
+    }
+
     /// Fire an event to all registered handlers
     pub async fn fire<E: Payload + Send + Sync + 'static>(&self, mut event: E) -> E {
         if let Some(server) = self.server.read().await.as_ref() {
@@ -0,0 +1,227 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use pumpkin_config::logging::LoggingConfig;
+use serde::Serialize;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+/// An administrative action recorded in the audit log.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Op,
+    Deop,
+    Ban,
+    BanIp,
+    Pardon,
+    PardonIp,
+    Kick,
+    GameModeChange,
+}
+
+/// One line of the audit log.
+#[derive(Serialize)]
+struct AuditEntry<'a> {
+    timestamp: String,
+    action: AuditAction,
+    actor: &'a str,
+    target: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'a str>,
+}
+
+/// Appends administrative actions like op, ban, kick and game mode changes to a dedicated file,
+/// one JSON object per line.
+pub struct AuditLog {
+    /// `None` if audit logging is disabled.
+    file: Option<Mutex<File>>,
+}
+
+impl AuditLog {
+    #[must_use]
+    pub fn new(config: &LoggingConfig) -> Self {
+        if !config.audit {
+            return Self::disabled();
+        }
+        match Self::open(Path::new(&config.audit_file)) {
+            Ok(log) => log,
+            Err(err) => {
+                log::error!("Couldn't open audit log {}: {err}", config.audit_file);
+                Self::disabled()
+            }
+        }
+    }
+
+    #[must_use]
+    pub const fn disabled() -> Self {
+        Self { file: None }
+    }
+
+    /// Opens the audit log at `path`, entries are appended to what is already there.
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Some(Mutex::new(file)),
+        })
+    }
+
+    /// Records that `actor` performed `action` on `target`.
+    pub fn record(&self, action: AuditAction, actor: &str, target: &str, reason: Option<&str>) {
+        let Some(file) = &self.file else {
+            return;
+        };
+        let entry = AuditEntry {
+            timestamp: OffsetDateTime::now_utc()
+                .format(&Rfc3339)
+                .unwrap_or_default(),
+            action,
+            actor,
+            target,
+            reason,
+        };
+        let mut line = serde_json::to_string(&entry).expect("Audit entries are valid JSON");
+        line.push('\n');
+        if let Err(err) = file.lock().unwrap().write_all(line.as_bytes()) {
+            log::error!("Couldn't write to the audit log: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use pumpkin_config::op::Op;
+    use pumpkin_config::{AdvancedConfiguration, BasicConfiguration};
+    use pumpkin_util::permission::PermissionLvl;
+    use pumpkin_util::text::TextComponent;
+    use uuid::Uuid;
+
+    use super::AuditLog;
+    use crate::data::banlist_serializer::{BannedIpEntry, BannedPlayerEntry};
+    use crate::data::banned_ip_data::BannedIpList;
+    use crate::data::banned_player_data::BannedPlayerList;
+    use crate::data::op_data::OperatorConfig;
+    use crate::entity::player::Player;
+    use crate::net::{DisconnectReason, GameProfile};
+    use crate::server::Server;
+
+    fn read_entries(path: &std::path::Path) -> Vec<serde_json::Value> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn bans_are_audit_logged() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let log = AuditLog::open(file.path()).unwrap();
+        let profile = GameProfile {
+            id: Uuid::new_v4(),
+            name: "Griefer".to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        };
+
+        let mut players = BannedPlayerList::default();
+        players.ban(
+            BannedPlayerEntry::new(&profile, "Admin".to_string(), None, "Spamming".into()),
+            &log,
+        );
+        assert!(players.pardon("griefer", "Admin", &log));
+        // Nothing to pardon, nothing to record
+        assert!(!players.pardon("Griefer", "Admin", &log));
+
+        let ip = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7));
+        let mut ips = BannedIpList::default();
+        ips.ban(
+            BannedIpEntry::new(ip, "Server".to_string(), None, "Bots".into()),
+            &log,
+        );
+        assert!(ips.pardon(&ip, "Server", &log));
+        // A disabled log records nothing
+        players.ban(
+            BannedPlayerEntry::new(&profile, "Admin".to_string(), None, "Again".into()),
+            &AuditLog::disabled(),
+        );
+
+        let entries = read_entries(file.path());
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0]["action"], "ban");
+        assert_eq!(entries[0]["actor"], "Admin");
+        assert_eq!(entries[0]["target"], "Griefer");
+        assert_eq!(entries[0]["reason"], "Spamming");
+        assert!(entries[0]["timestamp"].is_string());
+        assert_eq!(entries[1]["action"], "pardon");
+        assert_eq!(entries[1]["target"], "Griefer");
+        assert!(entries[1].get("reason").is_none());
+        assert_eq!(entries[2]["action"], "ban_ip");
+        assert_eq!(entries[2]["target"], "203.0.113.7");
+        assert_eq!(entries[3]["action"], "pardon_ip");
+    }
+
+    #[test]
+    fn operator_changes_are_audit_logged() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let log = AuditLog::open(file.path()).unwrap();
+        let uuid = Uuid::new_v4();
+
+        let mut ops = OperatorConfig::default();
+        ops.op(
+            Op::new(uuid, "Admin".to_string(), PermissionLvl::Four, false),
+            "Server",
+            &log,
+        );
+        assert!(ops.get_entry(&uuid).is_some());
+        ops.deop(&uuid, "Admin", "Server", &log);
+        assert!(ops.get_entry(&uuid).is_none());
+
+        let entries = read_entries(file.path());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["action"], "op");
+        assert_eq!(entries[0]["actor"], "Server");
+        assert_eq!(entries[0]["target"], "Admin");
+        assert_eq!(entries[1]["action"], "deop");
+    }
+
+    #[tokio::test]
+    async fn kicks_are_audit_logged() {
+        let world_dir = tempfile::tempdir().unwrap();
+        let log_path = world_dir.path().join("audit.log");
+        let basic_config = BasicConfiguration {
+            default_level_name: world_dir
+                .path()
+                .join("world")
+                .to_string_lossy()
+                .into_owned(),
+            ..Default::default()
+        };
+        let mut advanced_config = AdvancedConfiguration::default();
+        advanced_config.logging.audit = true;
+        advanced_config.logging.audit_file = log_path.to_string_lossy().into_owned();
+        let server = Server::new(basic_config, advanced_config).await;
+        let world = server.worlds.read().await[0].clone();
+        let (player, _packets) = Player::new_for_test(world, "Griefer").await;
+
+        player
+            .kick_by("Admin", TextComponent::text("Spamming"))
+            .await;
+        // Kicks the server does on its own aren't administrative actions
+        player
+            .kick(DisconnectReason::Kicked, TextComponent::text("Timed out"))
+            .await;
+
+        let entries = read_entries(&log_path);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["action"], "kick");
+        assert_eq!(entries[0]["actor"], "Admin");
+        assert_eq!(entries[0]["target"], "Griefer");
+        assert_eq!(entries[0]["reason"], "Spamming");
+    }
+}
@@ -1,6 +1,5 @@
 use crate::entity::player::Player;
 use base64::{Engine as _, engine::general_purpose};
-use core::error;
 use pumpkin_config::BasicConfiguration;
 use pumpkin_data::packet::CURRENT_MC_PROTOCOL;
 use pumpkin_protocol::{
@@ -10,16 +9,45 @@ use pumpkin_protocol::{
 };
 use pumpkin_world::CURRENT_MC_VERSION;
 use std::{fs::File, io::Read, path::Path};
+use thiserror::Error;
 
 const DEFAULT_ICON: &[u8] = include_bytes!("../../../assets/default_icon.png");
+/// The client only shows server icons of exactly this size
+const ICON_SIZE: u32 = 64;
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+#[derive(Error, Debug)]
+pub enum FaviconError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not a PNG image")]
+    NotPng,
+    #[error("icon must be 64x64 pixels, but is {0}x{1}")]
+    InvalidSize(u32, u32),
+}
 
-fn load_icon_from_file<P: AsRef<Path>>(path: P) -> Result<String, Box<dyn error::Error>> {
+fn load_icon_from_file<P: AsRef<Path>>(path: P) -> Result<String, FaviconError> {
     let mut icon_file = File::open(path)?;
     let mut buf = Vec::new();
     icon_file.read_to_end(&mut buf)?;
+    let (width, height) = png_dimensions(&buf).ok_or(FaviconError::NotPng)?;
+    if width != ICON_SIZE || height != ICON_SIZE {
+        return Err(FaviconError::InvalidSize(width, height));
+    }
     Ok(load_icon_from_bytes(&buf))
 }
 
+/// Reads the width and height from the `IHDR` chunk, which always comes first in a PNG.
+fn png_dimensions(png_data: &[u8]) -> Option<(u32, u32)> {
+    let header = png_data.get(..24)?;
+    if &header[..8] != PNG_SIGNATURE || &header[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(header[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(header[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
 fn load_icon_from_bytes(png_data: &[u8]) -> String {
     assert!(!png_data.is_empty(), "PNG data is empty");
     let mut result = "data:image/png;base64,".to_owned();
@@ -122,16 +150,14 @@ impl CachedStatus {
             match load_icon_from_file(icon_path) {
                 Ok(icon) => Some(icon),
                 Err(e) => {
-                    let error_message = e.downcast_ref::<std::io::Error>().map_or_else(
-                        || format!("other error: {e}; using default."),
-                        |io_err| {
-                            if io_err.kind() == std::io::ErrorKind::NotFound {
-                                "not found; using default.".to_string()
-                            } else {
-                                format!("I/O error: {io_err}; using default.")
-                            }
-                        },
-                    );
+                    let error_message = match e {
+                        FaviconError::Io(io_err)
+                            if io_err.kind() == std::io::ErrorKind::NotFound =>
+                        {
+                            "not found; using default.".to_string()
+                        }
+                        e => format!("{e}; using default."),
+                    };
                     log::warn!("Failed to load favicon from '{icon_path}': {error_message}");
 
                     // Attempt to load default icon
@@ -167,3 +193,46 @@ impl Default for CachedStatus {
         Self::new(&BasicConfiguration::default())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use pumpkin_config::BasicConfiguration;
+
+    use super::{
+        CachedStatus, DEFAULT_ICON, FaviconError, load_icon_from_bytes, load_icon_from_file,
+    };
+
+    #[test]
+    fn only_64x64_icons_are_sent() {
+        let mut icon = tempfile::NamedTempFile::new().unwrap();
+        icon.write_all(DEFAULT_ICON).unwrap();
+        let config = BasicConfiguration {
+            use_favicon: true,
+            favicon_path: icon.path().to_string_lossy().into_owned(),
+            ..Default::default()
+        };
+        let favicon = CachedStatus::build_response(&config).favicon.unwrap();
+        assert!(favicon.starts_with("data:image/png;base64,"));
+        assert_eq!(favicon, load_icon_from_bytes(DEFAULT_ICON));
+
+        // Same PNG, but claiming to be 32x32
+        let mut small_icon = DEFAULT_ICON.to_vec();
+        small_icon[16..20].copy_from_slice(&32u32.to_be_bytes());
+        small_icon[20..24].copy_from_slice(&32u32.to_be_bytes());
+        let mut icon = tempfile::NamedTempFile::new().unwrap();
+        icon.write_all(&small_icon).unwrap();
+        assert!(matches!(
+            load_icon_from_file(icon.path()),
+            Err(FaviconError::InvalidSize(32, 32))
+        ));
+
+        let mut icon = tempfile::NamedTempFile::new().unwrap();
+        icon.write_all(b"not an image").unwrap();
+        assert!(matches!(
+            load_icon_from_file(icon.path()),
+            Err(FaviconError::NotPng)
+        ));
+    }
+}
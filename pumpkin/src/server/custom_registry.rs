@@ -0,0 +1,192 @@
+//! Custom entries for the registries which are synced to clients. They are read from
+//! `config/registries` in the layout of a datapack, like
+//! `config/registries/<namespace>/worldgen/biome/<name>.json`.
+
+use std::fs;
+use std::path::Path;
+
+use pumpkin_data::registry::{Registry, RegistryEntryData};
+use pumpkin_util::resource_location::ResourceLocation;
+use serde_json::Value;
+
+pub const REGISTRIES_FOLDER: &str = "config/registries";
+
+/// The registries which accept custom entries and the validation of their entries.
+const CUSTOM_REGISTRIES: [(&str, fn(&Value) -> Result<(), String>); 1] =
+    [("worldgen/biome", validate_biome)];
+
+/// Adds the custom entries found in `dir` to `registries`. An entry with the id of an existing
+/// one replaces it, which allows tinting the vanilla biomes. Malformed entries are skipped.
+pub fn load_custom_entries(registries: &mut [Registry], dir: &Path) {
+    let Ok(namespaces) = fs::read_dir(dir) else {
+        return;
+    };
+    for namespace in namespaces.flatten() {
+        let namespace_dir = namespace.path();
+        let Some(namespace) = namespace_dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        for (registry_path, validate) in CUSTOM_REGISTRIES {
+            let Some(registry) = registries
+                .iter_mut()
+                .find(|registry| registry.registry_id == ResourceLocation::vanilla(registry_path))
+            else {
+                continue;
+            };
+            let Ok(files) = fs::read_dir(namespace_dir.join(registry_path)) else {
+                continue;
+            };
+            for file in files.flatten() {
+                let path = file.path();
+                if path.extension().is_none_or(|extension| extension != "json") {
+                    continue;
+                }
+                let Some(name) = path.file_stem().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+                if !is_valid_id(namespace) || !is_valid_id(name) {
+                    log::warn!(
+                        "Skipping the custom {registry_path} entry at {}: ids may only contain \
+                         lowercase letters, digits, '_', '-' and '.'",
+                        path.display()
+                    );
+                    continue;
+                }
+                let entry_id = ResourceLocation::from(format!("{namespace}:{name}").as_str());
+                let data = fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|content| read_entry(&content, validate));
+                match data {
+                    Ok(data) => {
+                        log::debug!("Loaded the custom {registry_path} entry {entry_id}");
+                        add_entry(registry, entry_id, data);
+                    }
+                    Err(err) => log::warn!(
+                        "Skipping the custom {registry_path} entry at {}: {err}",
+                        path.display()
+                    ),
+                }
+            }
+        }
+    }
+}
+
+fn is_valid_id(part: &str) -> bool {
+    !part.is_empty()
+        && part
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.'))
+}
+
+/// Parses and validates an entry, returns it encoded as NBT.
+fn read_entry(
+    content: &str,
+    validate: fn(&Value) -> Result<(), String>,
+) -> Result<Box<[u8]>, String> {
+    let value: Value = serde_json::from_str(content).map_err(|err| err.to_string())?;
+    validate(&value)?;
+    let mut data = Vec::new();
+    pumpkin_nbt::serializer::to_bytes_unnamed(&value, &mut data).map_err(|err| err.to_string())?;
+    Ok(data.into_boxed_slice())
+}
+
+fn add_entry(registry: &mut Registry, entry_id: ResourceLocation, data: Box<[u8]>) {
+    match registry
+        .registry_entries
+        .iter_mut()
+        .find(|entry| entry.entry_id == entry_id)
+    {
+        Some(entry) => entry.data = Some(data),
+        None => registry.registry_entries.push(RegistryEntryData {
+            entry_id,
+            data: Some(data),
+        }),
+    }
+}
+
+fn validate_biome(biome: &Value) -> Result<(), String> {
+    let field = |name: &str| biome.get(name).ok_or_else(|| format!("missing {name}"));
+    if !field("has_precipitation")?.is_boolean() {
+        return Err("has_precipitation has to be a boolean".to_string());
+    }
+    for name in ["temperature", "downfall"] {
+        if !field(name)?.is_number() {
+            return Err(format!("{name} has to be a number"));
+        }
+    }
+    let effects = field("effects")?
+        .as_object()
+        .ok_or("effects has to be an object")?;
+    if !effects.contains_key("water_color") {
+        return Err("missing effects.water_color".to_string());
+    }
+    for name in [
+        "water_color",
+        "foliage_color",
+        "dry_foliage_color",
+        "grass_color",
+    ] {
+        if effects.get(name).is_some_and(|color| !is_color(color)) {
+            return Err(format!("effects.{name} has to be a color like \"#3f76e4\""));
+        }
+    }
+    Ok(())
+}
+
+/// Colors are either written as `#rrggbb` or as a number.
+fn is_color(value: &Value) -> bool {
+    match value {
+        Value::String(color) => color
+            .strip_prefix('#')
+            .is_some_and(|hex| hex.len() == 6 && u32::from_str_radix(hex, 16).is_ok()),
+        Value::Number(color) => color.as_u64().is_some_and(|color| color <= 0xFF_FFFF),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::registry::Registry;
+    use pumpkin_util::resource_location::ResourceLocation;
+    use serde_json::json;
+
+    use super::{add_entry, read_entry, validate_biome};
+
+    #[test]
+    fn custom_biomes_are_validated_and_merged() {
+        let biome = json!({
+            "has_precipitation": true,
+            "temperature": 0.8,
+            "downfall": 0.4,
+            "effects": {"water_color": "#ff0000", "grass_color": 65280}
+        });
+        assert!(validate_biome(&biome).is_ok());
+        let mut missing_water = biome.clone();
+        missing_water["effects"] = json!({"grass_color": "#00ff00"});
+        assert!(validate_biome(&missing_water).is_err());
+        let mut bad_color = biome.clone();
+        bad_color["effects"]["water_color"] = json!("red");
+        assert!(validate_biome(&bad_color).is_err());
+        assert!(read_entry("{not json", validate_biome).is_err());
+
+        let mut registries = Registry::get_synced();
+        let biomes = registries
+            .iter_mut()
+            .find(|registry| registry.registry_id == ResourceLocation::vanilla("worldgen/biome"))
+            .unwrap();
+        let count = biomes.registry_entries.len();
+        let data = read_entry(&biome.to_string(), validate_biome).unwrap();
+
+        add_entry(biomes, ResourceLocation::vanilla("plains"), data.clone());
+        assert_eq!(biomes.registry_entries.len(), count);
+        let plains = biomes
+            .registry_entries
+            .iter()
+            .find(|entry| entry.entry_id == ResourceLocation::vanilla("plains"))
+            .unwrap();
+        assert_eq!(plains.data.as_ref(), Some(&data));
+
+        add_entry(biomes, ResourceLocation::from("maps:red_sea"), data);
+        assert_eq!(biomes.registry_entries.len(), count + 1);
+    }
+}
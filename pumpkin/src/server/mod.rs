@@ -1,22 +1,33 @@
 use crate::advancement::ServerAdvancementRegistry;
 use crate::block::registry::BlockRegistry;
-use crate::command::commands::default_dispatcher;
+use crate::command::client_suggestions;
 use crate::command::commands::defaultgamemode::DefaultGamemode;
+use crate::command::commands::{builtin_dispatcher, default_dispatcher};
+use crate::command::cooldown::CommandCooldowns;
+use crate::command::tree::CommandTree;
+use crate::data::permission_group_data::PermissionGroupConfig;
 use crate::data::player_server_data::ServerPlayerData;
+use crate::data::{LoadJSONConfiguration, SaveJSONConfiguration};
 use crate::entity::{EntityBase, NBTStorage};
 use crate::item::registry::ItemRegistry;
-use crate::net::{ClientPlatform, DisconnectReason, EncryptionError, GameProfile, PlayerConfig};
-use crate::plugin::player::player_login::PlayerLoginEvent;
+use crate::net::{ClientPlatform, EncryptionError, GameProfile, PlayerConfig};
 use crate::plugin::server::server_broadcast::ServerBroadcastEvent;
+use crate::plugin::world::world_save::{WorldSaveEvent, WorldSavePhase};
 use crate::server::tick_rate_manager::ServerTickRateManager;
 use crate::world::custom_bossbar::CustomBossbars;
+use crate::world::natural_spawner::MobCaps;
+use crate::world::scoreboard::Teams;
+use crate::{PERMISSION_MANAGER, PERMISSION_REGISTRY, PLUGIN_MANAGER};
 use crate::{command::dispatcher::CommandDispatcher, entity::player::Player, world::World};
+use audit_log::AuditLog;
 use connection_cache::{CachedBranding, CachedStatus};
+use crossbeam::atomic::AtomicCell;
 use key_store::KeyStore;
-use pumpkin_config::{AdvancedConfiguration, BasicConfiguration};
+use pumpkin_config::{AdvancedConfiguration, BasicConfiguration, LoadConfiguration};
 use pumpkin_data::dimension::Dimension;
 use pumpkin_data::registry::Registry;
 use pumpkin_world::dimension::into_level;
+use reload::ConfigChanges;
 
 use crate::command::CommandSender;
 use pumpkin_macros::send_cancellable;
@@ -37,6 +48,7 @@ use rsa::RsaPublicKey;
 use std::collections::HashSet;
 use std::fs;
 use std::net::IpAddr;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU32};
 use std::{future::Future, sync::atomic::Ordering, time::Duration};
@@ -44,8 +56,11 @@ use tokio::sync::{Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tokio_util::task::TaskTracker;
 
+pub mod audit_log;
 mod connection_cache;
+pub mod custom_registry;
 mod key_store;
+pub mod reload;
 pub mod seasonal_events;
 pub mod tick_rate_manager;
 pub mod ticker;
@@ -54,6 +69,37 @@ use super::command::args::entities::{
     EntityFilter, EntityFilterSort, EntitySelectorType, TargetSelector, ValueCondition,
 };
 
+/// How often the chunk save progress is logged while shutting down
+const SAVE_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+/// How often the worlds are saved while the server is running, 5 minutes like in vanilla.
+pub const AUTOSAVE_INTERVAL_TICKS: i32 = 6000;
+
+/// Returns whether the worlds should be saved on this tick.
+#[must_use]
+pub const fn is_autosave_tick(tick_count: i32) -> bool {
+    tick_count > 0 && tick_count % AUTOSAVE_INTERVAL_TICKS == 0
+}
+
+/// Returns whether the worlds should be saved on this tick, `had_players` and `has_players` are
+/// whether players were online on the last tick and are online now.
+///
+/// When autosaving only with players online, one final save is done once the last player left.
+#[must_use]
+pub const fn should_autosave(
+    tick_count: i32,
+    only_with_players: bool,
+    had_players: bool,
+    has_players: bool,
+) -> bool {
+    if !only_with_players {
+        return is_autosave_tick(tick_count);
+    }
+    if had_players && !has_players {
+        return true;
+    }
+    has_players && is_autosave_tick(tick_count)
+}
+
 /// Represents a Minecraft server instance.
 pub struct Server {
     pub basic_config: BasicConfiguration,
@@ -67,6 +113,19 @@ pub struct Server {
     branding: CachedBranding,
     /// Saves and dispatches commands to appropriate handlers.
     pub command_dispatcher: RwLock<CommandDispatcher>,
+    /// The commands plugins registered with their permission, registered again on reload.
+    pub plugin_commands: Mutex<Vec<(CommandTree, String)>>,
+    /// A dispatcher built by a reload, swapped in on the next tick. Commands hold a read lock on
+    /// [`Self::command_dispatcher`] while they run, so `/reload` can't swap it itself.
+    pending_dispatcher: Mutex<Option<CommandDispatcher>>,
+    /// The configuration of the last reload, which the next one is compared against.
+    reloaded_config: Mutex<Option<AdvancedConfiguration>>,
+    /// Cooldowns of the commands players use.
+    pub command_cooldowns: CommandCooldowns,
+    /// Records administrative actions like op, ban and kick.
+    pub audit_log: AuditLog,
+    /// How many mobs may naturally spawn, can be changed by reloading the configuration.
+    pub mob_caps: AtomicCell<MobCaps>,
     /// Block behaviour.
     pub block_registry: Arc<BlockRegistry>,
     /// Item behaviour.
@@ -84,6 +143,8 @@ pub struct Server {
     pub mojang_public_keys: Mutex<Vec<RsaPublicKey>>,
     /// The server's custom bossbars
     pub bossbars: Mutex<CustomBossbars>,
+    /// The scoreboard teams, shared by all worlds
+    pub teams: Mutex<Teams>,
     /// The default gamemode when a player joins the server (reset every restart)
     pub defaultgamemode: Mutex<DefaultGamemode>,
     /// Manages player data storage
@@ -98,6 +159,8 @@ pub struct Server {
     pub aggregated_tick_times_nanos: AtomicI64,
     /// Total number of ticks processed by the server
     pub tick_count: AtomicI32,
+    /// Whether players were online during the last world tick, used to save once the last one left
+    players_online: AtomicBool,
     /// Random unique Server ID used by Bedrock Edition
     pub server_guid: u64,
     tasks: TaskTracker,
@@ -113,6 +176,19 @@ pub struct Server {
 }
 
 impl Server {
+    /// A server with the default configuration for tests, its worlds are stored in the returned
+    /// temporary directory.
+    #[cfg(test)]
+    pub async fn new_for_test() -> (Arc<Self>, tempfile::TempDir) {
+        let world_dir = tempfile::tempdir().unwrap();
+        let basic_config = BasicConfiguration {
+            default_level_name: world_dir.path().to_string_lossy().into_owned(),
+            ..Default::default()
+        };
+        let server = Self::new(basic_config, AdvancedConfiguration::default()).await;
+        (server, world_dir)
+    }
+
     #[expect(clippy::too_many_lines)]
     #[must_use]
     pub async fn new(
@@ -120,7 +196,14 @@ impl Server {
         advanced_config: AdvancedConfiguration,
     ) -> Arc<Self> {
         // First register the default commands. After that, plugins can put in their own.
-        let command_dispatcher = RwLock::new(default_dispatcher(&basic_config).await);
+        let command_dispatcher = default_dispatcher(&basic_config).await;
+        PERMISSION_REGISTRY.write().await.set_default_overrides(
+            command_dispatcher.permission_overrides(&advanced_config.commands.permission_levels),
+        );
+        let command_dispatcher = RwLock::new(command_dispatcher);
+        let command_cooldowns = CommandCooldowns::new(&advanced_config.commands);
+        let audit_log = AuditLog::new(&advanced_config.logging);
+        let mob_caps = AtomicCell::new(MobCaps::from_config(&advanced_config.world.mob_caps));
         let world_path = basic_config.get_world_path();
 
         let block_registry = super::block::registry::default_registry();
@@ -171,6 +254,7 @@ impl Server {
         });
         let player_data_storage = ServerPlayerData::new(
             world_path.join("playerdata"),
+            world_path.join("stats"),
             Duration::from_secs(advanced_config.player_data.save_player_cron_interval),
             advanced_config.player_data.save_player_data,
         );
@@ -178,11 +262,17 @@ impl Server {
 
         let tick_rate_manager = Arc::new(ServerTickRateManager::new(basic_config.tps));
 
+        let mut cached_registry = Registry::get_synced();
+        custom_registry::load_custom_entries(
+            &mut cached_registry,
+            Path::new(custom_registry::REGISTRIES_FOLDER),
+        );
+
         let server = Self {
             basic_config,
             advanced_config,
 
-            cached_registry: Registry::get_synced(),
+            cached_registry,
             container_id: 0.into(),
             worlds: RwLock::new(vec![]),
             dimensions: vec![
@@ -191,12 +281,19 @@ impl Server {
                 Dimension::THE_END,
             ],
             command_dispatcher,
+            plugin_commands: Mutex::new(Vec::new()),
+            pending_dispatcher: Mutex::new(None),
+            reloaded_config: Mutex::new(None),
+            command_cooldowns,
+            audit_log,
+            mob_caps,
             block_registry: block_registry.clone(),
             item_registry: super::item::items::default_registry(),
             key_store: KeyStore::new(),
             listing,
             branding: CachedBranding::new(),
             bossbars: Mutex::new(CustomBossbars::new()),
+            teams: Mutex::new(Teams::default()),
             defaultgamemode,
             player_data_storage,
             white_list,
@@ -204,6 +301,7 @@ impl Server {
             tick_times_nanos: Mutex::new([0; 100]),
             aggregated_tick_times_nanos: AtomicI64::new(0),
             tick_count: AtomicI32::new(0),
+            players_online: AtomicBool::new(false),
             tasks: TaskTracker::new(),
             server_guid: rand::random(),
             mojang_public_keys: Mutex::new(Vec::new()),
@@ -213,6 +311,8 @@ impl Server {
             _locker: Arc::new(locker),
         };
 
+        server.load_permission_groups().await;
+
         let server = Arc::new(server);
         let weak = Arc::downgrade(&server);
         let level_config = &server.advanced_config.world;
@@ -380,37 +480,28 @@ impl Server {
         if let Some(mut nbt_data) = nbt {
             player.read_nbt(&mut nbt_data).await;
         }
+        *player.stats.get_mut() = self.player_data_storage.load_stats(&player.gameprofile.id);
 
         // Wrap in Arc after data is loaded
         let player = Arc::new(player);
 
-        send_cancellable! {{
-            PlayerLoginEvent::new(player.clone(), TextComponent::text("You have been kicked from the server"));
-            'after: {
-                player.screen_handler_sync_handler.store_player(player.clone()).await;
-                #[expect(clippy::if_then_some_else_none)]
-                if world
-                    .add_player(player.gameprofile.id, player.clone())
-                    .await.is_ok() {
-                    // TODO: Config if we want increase online
-                    if let Some(config) = config {
-                        // TODO: Config so we can also just ignore this hehe
-                        if config.server_listing {
-                            self.listing.lock().await.add_player(&player);
-                        }
-                    }
-
-                    Some((player, world.clone()))
-                } else {
-                    None
-                }
+        player
+            .screen_handler_sync_handler
+            .store_player(player.clone())
+            .await;
+        world
+            .add_player(player.gameprofile.id, player.clone())
+            .await
+            .ok()?;
+        // TODO: Config if we want increase online
+        if let Some(config) = config {
+            // TODO: Config so we can also just ignore this hehe
+            if config.server_listing {
+                self.listing.lock().await.add_player(&player);
             }
+        }
 
-            'cancelled: {
-                player.kick(DisconnectReason::Kicked, event.kick_message).await;
-                None
-            }
-        }}
+        Some((player, world))
     }
 
     pub async fn remove_player(&self, player: &Player) {
@@ -426,18 +517,110 @@ impl Server {
 
         log::info!("Starting worlds");
         for world in self.worlds.read().await.iter() {
-            world.shutdown().await;
+            fire_world_save(world, WorldSavePhase::Pre).await;
+            world
+                .level
+                .save_progress
+                .report_while(world.shutdown(), SAVE_PROGRESS_INTERVAL, |saved, total| {
+                    log::info!(
+                        "Saving {}: {saved}/{total} chunks ({}%), {} changed",
+                        world.dimension.minecraft_name,
+                        saved * 100 / total,
+                        world.level.save_progress.dirty()
+                    );
+                })
+                .await;
+            fire_world_save(world, WorldSavePhase::Post).await;
         }
-        let level_data = self.level_info.read().await;
         // then lets save the world info
+        self.save_world_info().await;
+        log::info!("Completed worlds");
+        self.save_permission_groups().await;
+    }
+
+    /// Saves all worlds and the level.dat while the server keeps running.
+    pub async fn save_all(&self) {
+        for world in self.worlds.read().await.iter() {
+            fire_world_save(world, WorldSavePhase::Pre).await;
+            world.save().await;
+            fire_world_save(world, WorldSavePhase::Post).await;
+        }
+        self.save_world_info().await;
+        self.save_permission_groups().await;
+    }
+
+    /// Reads `features.toml` again and rebuilds the command dispatcher from the builtin and the
+    /// plugin commands. Only the settings in [`reload::LIVE_SETTINGS`] are applied, the others
+    /// are logged as needing a restart. The new dispatcher is used from the next tick on.
+    pub async fn reload(&self, config_dir: &Path) -> Result<(), String> {
+        let config = AdvancedConfiguration::reload(config_dir)?;
+        let mut reloaded_config = self.reloaded_config.lock().await;
+        ConfigChanges::between(
+            reloaded_config.as_ref().unwrap_or(&self.advanced_config),
+            &config,
+        )
+        .log();
+        self.mob_caps
+            .store(MobCaps::from_config(&config.world.mob_caps));
+        self.command_cooldowns.reload(&config.commands);
+
+        let mut dispatcher = builtin_dispatcher(&self.basic_config);
+        for (tree, permission) in self.plugin_commands.lock().await.iter() {
+            dispatcher.register(tree.clone(), permission.clone());
+        }
+        PERMISSION_REGISTRY.write().await.set_default_overrides(
+            dispatcher.permission_overrides(&config.commands.permission_levels),
+        );
+        *self.pending_dispatcher.lock().await = Some(dispatcher);
+        *reloaded_config = Some(config);
+        Ok(())
+    }
+
+    /// Swaps in the dispatcher of the last reload once no command is running anymore.
+    async fn apply_pending_dispatcher(&self) {
+        let mut pending = self.pending_dispatcher.lock().await;
+        if pending.is_none() {
+            return;
+        }
+        let Ok(mut current) = self.command_dispatcher.try_write() else {
+            // A command is still running, try again next tick
+            return;
+        };
+        *current = pending.take().unwrap();
+        drop(pending);
+
+        let dispatcher = current.downgrade();
+        for player in self.get_all_players().await {
+            client_suggestions::send_c_commands_packet(&player, &dispatcher).await;
+        }
+    }
+
+    /// Loads the permission groups from the config and the players assigned to them.
+    async fn load_permission_groups(&self) {
+        let mut manager = PERMISSION_MANAGER.write().await;
+        manager.set_groups(self.advanced_config.permissions.groups.clone());
+        for (player, groups) in PermissionGroupConfig::load().player_groups {
+            for group in groups {
+                if let Err(err) = manager.assign_group(player, &group) {
+                    log::warn!("Couldn't assign {player} to a permission group: {err}");
+                }
+            }
+        }
+    }
 
+    async fn save_permission_groups(&self) {
+        let player_groups = PERMISSION_MANAGER.read().await.player_groups.clone();
+        PermissionGroupConfig { player_groups }.save();
+    }
+
+    async fn save_world_info(&self) {
+        let level_data = self.level_info.read().await;
         if let Err(err) = self
             .world_info_writer
             .write_world_info(&level_data, &self.basic_config.get_world_path())
         {
             log::error!("Failed to save level.dat: {err}");
         }
-        log::info!("Completed worlds");
     }
 
     /// Broadcasts a packet to all players in all worlds.
@@ -476,6 +659,22 @@ impl Server {
         }}
     }
 
+    /// Broadcasts a message of the server itself, e.g. from the console or a plugin.
+    ///
+    /// Unlike `broadcast_message` this is always sent as an unsigned system message, so it can't be
+    /// mistaken for a message of a player. The sender of the event is ignored.
+    pub async fn broadcast_system(&self, message: &TextComponent) {
+        send_cancellable! {{
+            ServerBroadcastEvent::system(message.clone());
+
+            'after: {
+                for world in self.worlds.read().await.iter() {
+                    world.broadcast_system_message(&event.message).await;
+                }
+            }
+        }}
+    }
+
     /// Sets the difficulty of the server.
     ///
     /// This function updates the difficulty level of the server and broadcasts the change to all players.
@@ -508,13 +707,24 @@ impl Server {
         drop(level_info);
 
         for world in &*self.worlds.read().await {
-            world.level_info.write().await.difficulty = difficulty;
+            world.set_difficulty(difficulty).await;
         }
 
         self.broadcast_packet_all(&CChangeDifficulty::new(difficulty as u8, locked))
             .await;
     }
 
+    /// Locks or unlocks the difficulty, a locked difficulty can only be changed with commands.
+    pub async fn set_difficulty_locked(&self, locked: bool) {
+        let mut level_info = self.level_info.write().await;
+        level_info.difficulty_locked = locked;
+        let difficulty = level_info.difficulty;
+        drop(level_info);
+
+        self.broadcast_packet_all(&CChangeDifficulty::new(difficulty as u8, locked))
+            .await;
+    }
+
     /// Searches for a player by their username across all worlds.
     ///
     /// This function iterates through each world managed by the server and attempts to find a player with the specified username.
@@ -651,6 +861,7 @@ impl Server {
     /// Main server tick method. This now handles both player/network ticking (which always runs)
     /// and world/game logic ticking (which is affected by freeze state).
     pub async fn tick(self: &Arc<Self>) {
+        self.apply_pending_dispatcher().await;
         if self.tick_rate_manager.runs_normally() || self.tick_rate_manager.is_sprinting() {
             self.tick_worlds().await;
             // Always run player and network ticking, even when game is frozen
@@ -693,6 +904,22 @@ impl Server {
         if let Err(e) = self.player_data_storage.tick(self).await {
             log::error!("Error ticking player data: {e}");
         }
+        self.autosave().await;
+    }
+
+    /// Saves all worlds if it's time for an autosave, see [`should_autosave`].
+    pub async fn autosave(&self) {
+        let has_players = self.has_n_players(1).await;
+        let had_players = self.players_online.swap(has_players, Ordering::Relaxed);
+        if should_autosave(
+            self.tick_count.load(Ordering::Relaxed),
+            self.advanced_config.world.autosave_only_with_players,
+            had_players,
+            has_players,
+        ) {
+            log::debug!("Autosaving worlds");
+            self.save_all().await;
+        }
     }
 
     /// Updates the tick time statistics with the duration of the last tick.
@@ -877,3 +1104,12 @@ impl Server {
         }
     }
 }
+
+/// Lets plugins know a world is being saved, without building the event when nobody listens.
+async fn fire_world_save(world: &Arc<World>, phase: WorldSavePhase) {
+    if PLUGIN_MANAGER.has_handlers::<WorldSaveEvent>().await {
+        PLUGIN_MANAGER
+            .fire(WorldSaveEvent::new(world.clone(), phase))
+            .await;
+    }
+}
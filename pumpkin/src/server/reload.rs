@@ -0,0 +1,81 @@
+use pumpkin_config::{AdvancedConfiguration, changed_values};
+
+/// The settings of `features.toml` which are looked up whenever they are used, so `/reload`
+/// applies them right away. Everything else is only read on startup and needs a restart.
+pub const LIVE_SETTINGS: [&str; 4] = [
+    "world.mob_caps",
+    "commands.cooldowns_ms",
+    "commands.cooldown_bypass_level",
+    "commands.permission_levels",
+];
+
+/// The changes between the running and a reloaded configuration.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConfigChanges {
+    /// Changed values which are applied by the reload.
+    pub live: Vec<String>,
+    /// Changed values which only take effect after a restart.
+    pub needs_restart: Vec<String>,
+}
+
+impl ConfigChanges {
+    #[must_use]
+    pub fn between(old: &AdvancedConfiguration, new: &AdvancedConfiguration) -> Self {
+        let mut changes = Self::default();
+        for value in changed_values(old, new) {
+            let live = LIVE_SETTINGS.iter().any(|setting| {
+                value
+                    .strip_prefix(setting)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            });
+            if live {
+                changes.live.push(value);
+            } else {
+                changes.needs_restart.push(value);
+            }
+        }
+        changes
+    }
+
+    pub fn log(&self) {
+        for value in &self.live {
+            log::info!("Applied the new value of {value}");
+        }
+        for value in &self.needs_restart {
+            log::warn!("{value} changed, the server has to be restarted to apply it");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pumpkin_config::AdvancedConfiguration;
+
+    use super::ConfigChanges;
+
+    #[test]
+    fn reload_tells_apart_live_and_restart_settings() {
+        let old = AdvancedConfiguration::default();
+        assert_eq!(ConfigChanges::between(&old, &old), ConfigChanges::default());
+
+        let mut new = AdvancedConfiguration::default();
+        new.world.mob_caps.monster = 10;
+        new.commands.cooldowns_ms = HashMap::from([("fill".to_string(), 1000)]);
+        new.logging.color = !old.logging.color;
+        new.networking.packet_compression.enabled = !old.networking.packet_compression.enabled;
+
+        let mut changes = ConfigChanges::between(&old, &new);
+        changes.live.sort();
+        changes.needs_restart.sort();
+        assert_eq!(
+            changes.live,
+            ["commands.cooldowns_ms.fill", "world.mob_caps.monster"]
+        );
+        assert_eq!(
+            changes.needs_restart,
+            ["logging.color", "networking.packet_compression.enabled"]
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crossbeam::atomic::AtomicCell;
 use pumpkin_protocol::java::client::play::{CSystemChatMessage, CTickingState, CTickingStep};
@@ -9,6 +9,17 @@ use crate::entity::player::Player;
 use crate::server::Server;
 const NANOSECONDS_PER_SECOND: i64 = 1_000_000_000;
 
+/// What a finished sprint did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SprintReport {
+    /// How many ticks were actually sprinted
+    pub ticks: i64,
+    /// Time spent working on the sprinted ticks
+    pub work_nanos: i64,
+    /// Real time since the sprint started
+    pub elapsed: Duration,
+}
+
 pub struct ServerTickRateManager {
     tickrate: AtomicCell<f32>,
     nanoseconds_per_tick: AtomicI64,
@@ -19,6 +30,7 @@ pub struct ServerTickRateManager {
     // Sprinting state
     remaining_sprint_ticks: AtomicI64,
     sprint_tick_start_time: AtomicCell<Instant>,
+    sprint_start_time: AtomicCell<Instant>,
     sprint_time_spend: AtomicI64,
     scheduled_current_sprint_ticks: AtomicI64,
     previous_is_frozen: AtomicBool,
@@ -35,6 +47,7 @@ impl ServerTickRateManager {
             is_frozen: AtomicBool::new(false),
             remaining_sprint_ticks: AtomicI64::new(0),
             sprint_tick_start_time: AtomicCell::new(Instant::now()),
+            sprint_start_time: AtomicCell::new(Instant::now()),
             sprint_time_spend: AtomicI64::new(0),
             scheduled_current_sprint_ticks: AtomicI64::new(0),
             previous_is_frozen: AtomicBool::new(false),
@@ -74,6 +87,15 @@ impl ServerTickRateManager {
         self.remaining_sprint_ticks.load(Ordering::Relaxed) > 0
     }
 
+    /// How long a tick should take, sprints run as fast as possible.
+    pub fn tick_interval(&self) -> Duration {
+        if self.is_sprinting() {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos(self.nanoseconds_per_tick() as u64)
+        }
+    }
+
     pub fn is_stepping_forward(&self) -> bool {
         self.frozen_ticks_to_run.load(Ordering::Relaxed) > 0
     }
@@ -113,17 +135,48 @@ impl ServerTickRateManager {
     }
 
     pub async fn request_game_to_sprint(&self, server: &Server, ticks: i64) -> bool {
+        let was_sprinting = self.start_sprint(ticks);
+        self.update_state_to_clients(server).await;
+        was_sprinting
+    }
+
+    /// Runs the game as fast as possible for `ticks` ticks, unfreezing it until the sprint ends.
+    /// Returns whether a sprint was already running, it is replaced by the new one.
+    pub fn start_sprint(&self, ticks: i64) -> bool {
         let was_sprinting = self.is_sprinting();
         self.sprint_time_spend.store(0, Ordering::Relaxed);
+        self.sprint_start_time.store(Instant::now());
         self.scheduled_current_sprint_ticks
             .store(ticks, Ordering::Relaxed);
         self.remaining_sprint_ticks.store(ticks, Ordering::Relaxed);
-        self.previous_is_frozen
-            .store(self.is_frozen(), Ordering::Relaxed);
-        self.set_frozen(server, false).await;
+        // A replaced sprint already unfroze the game, keep what was there before it
+        if !was_sprinting {
+            self.previous_is_frozen
+                .store(self.is_frozen(), Ordering::Relaxed);
+        }
+        self.is_frozen.store(false, Ordering::Relaxed);
         was_sprinting
     }
 
+    /// Ends the current sprint and restores the frozen state from before it.
+    pub fn end_sprint(&self) -> SprintReport {
+        let report = SprintReport {
+            ticks: self.scheduled_current_sprint_ticks.load(Ordering::Relaxed)
+                - self.remaining_sprint_ticks.load(Ordering::Relaxed).max(0),
+            work_nanos: self.sprint_time_spend.load(Ordering::Relaxed),
+            elapsed: self.sprint_start_time.load().elapsed(),
+        };
+        self.scheduled_current_sprint_ticks
+            .store(0, Ordering::Relaxed);
+        self.sprint_time_spend.store(0, Ordering::Relaxed);
+        self.remaining_sprint_ticks.store(0, Ordering::Relaxed);
+        self.is_frozen.store(
+            self.previous_is_frozen.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+        report
+    }
+
     pub async fn stop_sprinting(&self, server: &Server) -> bool {
         if self.is_sprinting() {
             self.finish_tick_sprint(server).await;
@@ -150,9 +203,13 @@ impl ServerTickRateManager {
     }
 
     pub async fn finish_tick_sprint(&self, server: &Server) {
-        let total_sprinted_ticks = self.scheduled_current_sprint_ticks.load(Ordering::Relaxed)
-            - self.remaining_sprint_ticks.load(Ordering::Relaxed);
-        let time_spent_nanos = self.sprint_time_spend.load(Ordering::Relaxed);
+        let report = self.end_sprint();
+        let total_sprinted_ticks = report.ticks;
+        let time_spent_nanos = report.work_nanos;
+        log::info!(
+            "Sprinted {total_sprinted_ticks} ticks in {:.2}s",
+            report.elapsed.as_secs_f64()
+        );
 
         let inner_message = if total_sprinted_ticks > 0 && time_spent_nanos > 0 {
             let time_spent_ms = time_spent_nanos as f64 / 1_000_000.0;
@@ -183,13 +240,7 @@ impl ServerTickRateManager {
             .broadcast_packet_all(&CSystemChatMessage::new(&final_report, false))
             .await;
 
-        // Reset state after sending the report
-        self.scheduled_current_sprint_ticks
-            .store(0, Ordering::Relaxed);
-        self.sprint_time_spend.store(0, Ordering::Relaxed);
-        self.remaining_sprint_ticks.store(0, Ordering::Relaxed);
-        self.set_frozen(server, self.previous_is_frozen.load(Ordering::Relaxed))
-            .await;
+        self.update_state_to_clients(server).await;
         // server.on_tick_rate_changed();
     }
 
@@ -222,3 +273,37 @@ impl ServerTickRateManager {
             .await;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    use super::ServerTickRateManager;
+
+    #[test]
+    fn sprint_runs_ticks_then_restores_rate() {
+        let manager = ServerTickRateManager::new(20.0);
+        manager.is_frozen.store(true, Ordering::Relaxed);
+        assert!(!manager.start_sprint(100));
+        assert!(!manager.is_frozen());
+        assert_eq!(manager.tick_interval(), Duration::ZERO);
+
+        let mut world_ticks = 0;
+        let mut report = None;
+        while manager.is_sprinting() {
+            manager.tick();
+            assert!(manager.runs_normally());
+            manager.start_sprint_tick_work();
+            world_ticks += 1;
+            if manager.end_sprint_tick_work() {
+                report = Some(manager.end_sprint());
+            }
+        }
+
+        assert_eq!(world_ticks, 100);
+        assert_eq!(report.map(|report| report.ticks), Some(100));
+        assert!(manager.is_frozen());
+        assert_eq!(manager.tick_interval(), Duration::from_millis(50));
+    }
+}
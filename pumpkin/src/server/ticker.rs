@@ -1,7 +1,7 @@
 use crate::{SHOULD_STOP, server::Server};
 use std::{
     sync::{Arc, atomic::Ordering},
-    time::{Duration, Instant},
+    time::Instant,
 };
 use tokio::time::sleep;
 
@@ -56,13 +56,7 @@ impl Ticker {
             let now = Instant::now();
             let elapsed = now.duration_since(self.last_tick);
 
-            let tick_interval = if manager.is_sprinting() {
-                Duration::ZERO
-            } else {
-                Duration::from_nanos(manager.nanoseconds_per_tick() as u64)
-            };
-
-            if let Some(sleep_time) = tick_interval.checked_sub(elapsed)
+            if let Some(sleep_time) = manager.tick_interval().checked_sub(elapsed)
                 && !sleep_time.is_zero()
             {
                 sleep(sleep_time).await;
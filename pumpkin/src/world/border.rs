@@ -12,9 +12,14 @@ use super::World;
 pub struct Worldborder {
     pub center_x: f64,
     pub center_z: f64,
+    /// The diameter at the start of the current size change.
     pub old_diameter: f64,
+    /// The diameter the border moves to, equal to `old_diameter` when it isn't moving.
     pub new_diameter: f64,
+    /// How long the current size change takes in milliseconds.
     pub speed: i64,
+    /// How many ticks are left until the border reaches `new_diameter`.
+    pub remaining_ticks: i64,
     pub portal_teleport_boundary: i32,
     pub warning_blocks: i32,
     pub warning_time: i32,
@@ -38,6 +43,7 @@ impl Worldborder {
             old_diameter: diameter,
             new_diameter: diameter,
             speed,
+            remaining_ticks: 0,
             portal_teleport_boundary: 29_999_984,
             warning_blocks,
             warning_time,
@@ -46,14 +52,37 @@ impl Worldborder {
         }
     }
 
+    /// The current diameter, between `old_diameter` and `new_diameter` while the border moves.
+    #[must_use]
+    pub fn diameter(&self) -> f64 {
+        let total_ticks = self.speed / 50;
+        if self.remaining_ticks <= 0 || total_ticks <= 0 {
+            return self.new_diameter;
+        }
+        let progress = 1.0 - self.remaining_ticks as f64 / total_ticks as f64;
+        self.old_diameter + (self.new_diameter - self.old_diameter) * progress
+    }
+
+    /// Moves the border towards `new_diameter`. Clients interpolate on their own, so nothing has
+    /// to be sent.
+    pub fn tick(&mut self) {
+        if self.remaining_ticks > 0 {
+            self.remaining_ticks -= 1;
+            if self.remaining_ticks == 0 {
+                self.old_diameter = self.new_diameter;
+                self.speed = 0;
+            }
+        }
+    }
+
     pub async fn init_client(&self, client: &Arc<JavaClient>) {
         client
             .enqueue_packet(&CInitializeWorldBorder::new(
                 self.center_x,
                 self.center_z,
-                self.old_diameter,
+                self.diameter(),
                 self.new_diameter,
-                self.speed.into(),
+                (self.remaining_ticks * 50).into(),
                 self.portal_teleport_boundary.into(),
                 self.warning_blocks.into(),
                 self.warning_time.into(),
@@ -70,9 +99,15 @@ impl Worldborder {
             .await;
     }
 
+    /// Changes the diameter, over `speed` milliseconds if given and otherwise at once.
     pub async fn set_diameter(&mut self, world: &World, diameter: f64, speed: Option<i64>) {
-        self.old_diameter = self.new_diameter;
+        self.old_diameter = self.diameter();
         self.new_diameter = diameter;
+        self.speed = speed.unwrap_or(0);
+        self.remaining_ticks = self.speed / 50;
+        if self.remaining_ticks == 0 {
+            self.old_diameter = diameter;
+        }
 
         match speed {
             Some(speed) => {
@@ -93,7 +128,7 @@ impl Worldborder {
     }
 
     pub async fn add_diameter(&mut self, world: &World, offset: f64, speed: Option<i64>) {
-        self.set_diameter(world, self.new_diameter + offset, speed)
+        self.set_diameter(world, self.diameter() + offset, speed)
             .await;
     }
 
@@ -113,3 +148,29 @@ impl Worldborder {
             .await;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Worldborder;
+
+    #[test]
+    fn border_moves_over_time() {
+        let mut border = Worldborder::new(0.0, 0.0, 100.0, 0, 5, 15);
+        border.new_diameter = 200.0;
+        border.speed = 1000;
+        border.remaining_ticks = 20;
+        assert!((border.diameter() - 100.0).abs() < f64::EPSILON);
+
+        for _ in 0..10 {
+            border.tick();
+        }
+        assert!((border.diameter() - 150.0).abs() < 1e-9);
+
+        for _ in 0..15 {
+            border.tick();
+        }
+        assert!((border.diameter() - 200.0).abs() < f64::EPSILON);
+        assert!((border.old_diameter - 200.0).abs() < f64::EPSILON);
+        assert_eq!(border.speed, 0);
+    }
+}
@@ -61,27 +61,26 @@ pub async fn update_position(player: &Arc<Player>) {
         // ensure what we unload when the player disconnects is correct.
         let level = &entity.world.level;
         level.mark_chunks_as_newly_watched(&loading_chunks).await;
-        let chunks_to_clean = level.mark_chunks_as_not_watched(&unloading_chunks).await;
+        level.mark_chunks_as_not_watched(&unloading_chunks).await;
 
-        {
+        let forgotten_chunks = {
             let mut chunk_manager = player.chunk_manager.lock().await;
             chunk_manager.update_center_and_view_distance(
                 new_chunk_center,
                 view_distance.into(),
                 level,
-            );
+            )
         };
 
         player.watched_section.store(new_cylindrical);
 
-        if !chunks_to_clean.is_empty() {
-            // level.clean_chunks(&chunks_to_clean).await;
-            for chunk in unloading_chunks {
-                player
-                    .client
-                    .enqueue_packet(&CUnloadChunk::new(chunk.x, chunk.y))
-                    .await;
-            }
+        // Unload the chunks the client has but which are out of view now, whether or not other
+        // players still watch them. Otherwise the client keeps them around as ghost chunks.
+        for chunk in forgotten_chunks {
+            player
+                .client
+                .enqueue_bulk_packet(&CUnloadChunk::new(chunk.x, chunk.y))
+                .await;
         }
 
         if !loading_chunks.is_empty() {
@@ -0,0 +1,178 @@
+//! Living entities standing in each other push each other apart. `LivingEntity.tickCramming()`
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use pumpkin_util::math::boundingbox::BoundingBox;
+use pumpkin_util::math::vector3::Vector3;
+
+use super::scoreboard::{Teams, teams_push};
+use crate::entity::EntityBase;
+use crate::entity::player::Player;
+
+/// An entity is pushed by at most this many others each tick, so dense mob stacks don't cost
+/// quadratic time.
+pub const MAX_PUSHES_PER_TICK: usize = 24;
+
+/// Entities are sorted into square cells of this width, only entities in neighbouring cells
+/// can collide.
+const CELL_SIZE: f64 = 2.0;
+
+/// The velocity added to an entity at `pos` when it is pushed by one at `other`, the other one
+/// gets the opposite. `None` if both stand at the same spot. `Entity.pushAwayFrom()`
+#[must_use]
+pub fn push_velocity(pos: Vector3<f64>, other: Vector3<f64>) -> Option<Vector3<f64>> {
+    let dx = other.x - pos.x;
+    let dz = other.z - pos.z;
+    let distance = dx.abs().max(dz.abs());
+    if distance < 0.01 {
+        return None;
+    }
+    let distance = distance.sqrt();
+    let scale = (1.0 / distance).min(1.0) * 0.05 / distance;
+    Some(Vector3::new(-dx * scale, 0.0, -dz * scale))
+}
+
+/// The pairs of overlapping `boxes` which push each other, each box takes part in at most
+/// [`MAX_PUSHES_PER_TICK`] pairs.
+#[must_use]
+pub fn colliding_pairs(boxes: &[BoundingBox]) -> Vec<(usize, usize)> {
+    let cell = |bounding_box: &BoundingBox| {
+        let center = (bounding_box.min + bounding_box.max) * 0.5;
+        (
+            (center.x / CELL_SIZE).floor() as i32,
+            (center.z / CELL_SIZE).floor() as i32,
+        )
+    };
+    let mut cells: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    for (i, bounding_box) in boxes.iter().enumerate() {
+        cells.entry(cell(bounding_box)).or_default().push(i);
+    }
+
+    let mut pushes = vec![0; boxes.len()];
+    let mut pairs = Vec::new();
+    for (i, bounding_box) in boxes.iter().enumerate() {
+        let (x, z) = cell(bounding_box);
+        let neighbours = (x - 1..=x + 1).flat_map(|x| (z - 1..=z + 1).map(move |z| (x, z)));
+        'search: for neighbour in neighbours {
+            let Some(others) = cells.get(&neighbour) else {
+                continue;
+            };
+            for &j in others {
+                if pushes[i] >= MAX_PUSHES_PER_TICK {
+                    break 'search;
+                }
+                // Every pair only once
+                if j <= i {
+                    continue;
+                }
+                if pushes[j] < MAX_PUSHES_PER_TICK && bounding_box.intersects(&boxes[j]) {
+                    pushes[i] += 1;
+                    pushes[j] += 1;
+                    pairs.push((i, j));
+                }
+            }
+        }
+    }
+    pairs
+}
+
+/// Pushes the overlapping living entities apart. Players push mobs, but are only pushed by their
+/// own client. Spectators, entities without collision and vehicles with passengers are left out,
+/// as are members of teams whose collision rule doesn't allow it.
+pub async fn push_entities(
+    players: &[Arc<Player>],
+    entities: &[Arc<dyn EntityBase>],
+    teams: &Teams,
+) {
+    let mut pushable: Vec<(&dyn EntityBase, bool)> = Vec::new();
+    for player in players {
+        if !player.is_spectator() && player.living_entity.entity.is_alive() {
+            pushable.push((player.as_ref(), false));
+        }
+    }
+    for entity in entities {
+        let Some(living) = entity.get_living_entity() else {
+            continue;
+        };
+        let base = &living.entity;
+        if !base.is_alive()
+            || base.no_clip.load(Ordering::Relaxed)
+            || base.vehicle.lock().await.is_some()
+        {
+            continue;
+        }
+        let receives = !base.has_passengers().await;
+        pushable.push((entity.as_ref(), receives));
+    }
+
+    let boxes: Vec<_> = pushable
+        .iter()
+        .map(|(entity, _)| entity.get_entity().bounding_box.load())
+        .collect();
+    let entity_teams: Vec<_> = pushable
+        .iter()
+        .map(|(entity, _)| match entity.get_player() {
+            Some(player) => teams.team_of(&player.gameprofile.name),
+            None => teams.team_of(&entity.get_entity().entity_uuid.to_string()),
+        })
+        .collect();
+    for (i, j) in colliding_pairs(&boxes) {
+        if !teams_push(entity_teams[i], entity_teams[j]) {
+            continue;
+        }
+        let (a, a_receives) = pushable[i];
+        let (b, b_receives) = pushable[j];
+        let (a, b) = (a.get_entity(), b.get_entity());
+        let Some(push) = push_velocity(a.pos.load(), b.pos.load()) else {
+            continue;
+        };
+        if a_receives {
+            a.velocity.store(a.velocity.load() + push);
+            a.velocity_dirty.store(true, Ordering::SeqCst);
+        }
+        if b_receives {
+            b.velocity.store(b.velocity.load() - push);
+            b.velocity_dirty.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::boundingbox::{BoundingBox, EntityDimensions};
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{MAX_PUSHES_PER_TICK, colliding_pairs, push_velocity};
+
+    #[test]
+    fn overlapping_entities_are_pushed_apart() {
+        let push =
+            push_velocity(Vector3::new(0.0, 64.0, 0.0), Vector3::new(0.25, 64.0, 0.0)).unwrap();
+        assert!(push.x < 0.0);
+        assert!(push.y.abs() < f64::EPSILON && push.z.abs() < f64::EPSILON);
+        assert!(push.x.abs() <= 0.05);
+        assert!(
+            push_velocity(Vector3::new(0.0, 64.0, 0.0), Vector3::new(0.0, 65.0, 0.0)).is_none()
+        );
+
+        let size = EntityDimensions::new(0.6, 1.8, 1.62);
+        let boxes = [
+            BoundingBox::new_from_pos(0.0, 64.0, 0.0, &size),
+            BoundingBox::new_from_pos(0.3, 64.0, 0.0, &size),
+            BoundingBox::new_from_pos(5.0, 64.0, 0.0, &size),
+        ];
+        assert_eq!(colliding_pairs(&boxes), [(0, 1)]);
+
+        // A whole mob stack in one block
+        let stack: Vec<_> = (0..100)
+            .map(|i| BoundingBox::new_from_pos(f64::from(i) * 0.001, 64.0, 0.0, &size))
+            .collect();
+        let pairs = colliding_pairs(&stack);
+        for i in 0..stack.len() {
+            let pushes = pairs.iter().filter(|(a, b)| *a == i || *b == i).count();
+            assert!(pushes <= MAX_PUSHES_PER_TICK);
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use pumpkin_data::Block;
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::generation::end_spike::{SPIKE_COUNT, spike_center};
+use pumpkin_world::world::BlockFlags;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::World;
+use super::bossbar::{Bossbar, BossbarColor, BossbarFlags};
+use crate::entity::EntityBase;
+use crate::entity::boss::ender_dragon::EnderDragonEntity;
+use crate::entity::r#type::from_type;
+
+/// Players this close to the main end island see the dragon's bossbar.
+const BOSSBAR_RANGE: f64 = 192.0;
+/// How often the players seeing the bossbar are updated.
+const VIEWER_UPDATE_INTERVAL: u32 = 20;
+/// Where the dragon spawns, above the main end island.
+const DRAGON_SPAWN: Vector3<f64> = Vector3::new(0.0, 128.0, 0.0);
+
+/// The ender dragon fight of the end.
+///
+/// Sets the end up when a player first enters it, by spawning the end crystals on the obsidian
+/// pillars and the dragon, and shows the dragon's health to the players near the main island.
+pub struct DragonFight {
+    dragon: Mutex<Option<Arc<EnderDragonEntity>>>,
+    bossbar: Mutex<Bossbar>,
+    /// The players currently seeing the bossbar.
+    viewers: Mutex<HashSet<Uuid>>,
+    ticks: AtomicU32,
+}
+
+impl Default for DragonFight {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DragonFight {
+    #[must_use]
+    pub fn new() -> Self {
+        let mut bossbar = Bossbar::new(TextComponent::translate(
+            "entity.minecraft.ender_dragon",
+            [],
+        ));
+        bossbar.health = 1.0;
+        bossbar.color = BossbarColor::Pink;
+        bossbar.flags = BossbarFlags::DragonBar;
+        Self {
+            dragon: Mutex::new(None),
+            bossbar: Mutex::new(bossbar),
+            viewers: Mutex::new(HashSet::new()),
+            ticks: AtomicU32::new(0),
+        }
+    }
+
+    pub async fn tick(&self, world: &Arc<World>) {
+        if world.players.read().await.is_empty() {
+            return;
+        }
+        let needs_setup = {
+            let level_info = world.level_info.read().await;
+            level_info.dragon_fight.needs_state_scanning && !level_info.dragon_fight.dragon_killed
+        };
+        if needs_setup && Self::spikes_loaded(world) {
+            Self::set_up(world).await;
+            world
+                .level_info
+                .write()
+                .await
+                .dragon_fight
+                .needs_state_scanning = false;
+        }
+
+        if self.ticks.fetch_add(1, Ordering::Relaxed) % VIEWER_UPDATE_INTERVAL == 0 {
+            self.update_viewers(world).await;
+        }
+    }
+
+    /// Whether all the chunks with obsidian pillars are loaded.
+    fn spikes_loaded(world: &World) -> bool {
+        (0..SPIKE_COUNT).all(|i| {
+            let (x, z) = spike_center(i);
+            world
+                .level
+                .loaded_chunks
+                .contains_key(&Vector2::new(x >> 4, z >> 4))
+        })
+    }
+
+    /// Puts an end crystal on top of every obsidian pillar and spawns the dragon.
+    async fn set_up(world: &Arc<World>) {
+        log::debug!("Spawning the ender dragon");
+        for i in 0..SPIKE_COUNT {
+            let (x, z) = spike_center(i);
+            let top = world.get_top_block(Vector2::new(x, z)).await;
+            world
+                .set_block_state(
+                    &BlockPos::new(x, top + 1, z),
+                    Block::BEDROCK.default_state.id,
+                    BlockFlags::NOTIFY_ALL,
+                )
+                .await;
+            world
+                .set_block_state(
+                    &BlockPos::new(x, top + 2, z),
+                    Block::FIRE.default_state.id,
+                    BlockFlags::NOTIFY_ALL,
+                )
+                .await;
+            let position = Vector3::new(f64::from(x) + 0.5, f64::from(top + 2), f64::from(z) + 0.5);
+            let crystal =
+                from_type(&EntityType::END_CRYSTAL, position, world, Uuid::new_v4()).await;
+            world.spawn_entity(crystal).await;
+        }
+
+        let dragon = from_type(
+            &EntityType::ENDER_DRAGON,
+            DRAGON_SPAWN,
+            world,
+            Uuid::new_v4(),
+        )
+        .await;
+        world.spawn_entity(dragon).await;
+    }
+
+    /// Called by the dragon when it is created, so the fight keeps track of it.
+    pub async fn set_dragon(&self, dragon: Arc<EnderDragonEntity>) {
+        *self.dragon.lock().await = Some(dragon);
+    }
+
+    /// Called every tick by the dragon with its health, from 0 to 1.
+    pub async fn update_health(&self, world: &World, health: f32) {
+        let mut bossbar = self.bossbar.lock().await;
+        if bossbar.health == health {
+            return;
+        }
+        bossbar.health = health;
+        let players = world.players.read().await;
+        for viewer in self.viewers.lock().await.iter() {
+            if let Some(player) = players.get(viewer) {
+                player.update_bossbar_health(&bossbar.uuid, health).await;
+            }
+        }
+    }
+
+    /// Called by the dragon once it died.
+    pub async fn on_dragon_killed(&self, world: &World, dragon: Uuid) {
+        {
+            let mut current = self.dragon.lock().await;
+            if current
+                .as_ref()
+                .is_none_or(|current| current.get_entity().entity_uuid != dragon)
+            {
+                return;
+            }
+            *current = None;
+        }
+        let mut level_info = world.level_info.write().await;
+        level_info.dragon_fight.dragon_killed = true;
+        level_info.dragon_fight.previously_killed = true;
+        drop(level_info);
+        self.remove_viewers(world).await;
+    }
+
+    /// Called when an end crystal gets destroyed, hurts the dragon if the crystal was healing it.
+    pub async fn on_crystal_destroyed(
+        &self,
+        crystal: &dyn EntityBase,
+        cause: Option<&dyn EntityBase>,
+    ) {
+        let dragon = self.dragon.lock().await.clone();
+        if let Some(dragon) = dragon {
+            dragon.on_crystal_destroyed(crystal, cause).await;
+        }
+    }
+
+    /// Shows the bossbar to the players near the main island while the dragon is alive.
+    async fn update_viewers(&self, world: &World) {
+        let dragon = self
+            .dragon
+            .lock()
+            .await
+            .as_ref()
+            .map(|dragon| dragon.get_entity().entity_uuid);
+        let alive = match dragon {
+            Some(dragon) => world.entities.read().await.contains_key(&dragon),
+            None => false,
+        };
+        if !alive {
+            self.remove_viewers(world).await;
+            return;
+        }
+
+        let bossbar = self.bossbar.lock().await;
+        let mut viewers = self.viewers.lock().await;
+        let players = world.players.read().await;
+        for player in players.values() {
+            let pos = player.living_entity.entity.pos.load();
+            let in_range = pos.x * pos.x + pos.z * pos.z < BOSSBAR_RANGE * BOSSBAR_RANGE;
+            let uuid = player.gameprofile.id;
+            if in_range && viewers.insert(uuid) {
+                player.send_bossbar(&bossbar).await;
+            } else if !in_range && viewers.remove(&uuid) {
+                player.remove_bossbar(bossbar.uuid).await;
+            }
+        }
+        // The players which left the end are still shown the bossbar until it is removed
+        let left: Vec<Uuid> = viewers
+            .iter()
+            .filter(|uuid| !players.contains_key(uuid))
+            .copied()
+            .collect();
+        viewers.retain(|uuid| players.contains_key(uuid));
+        let uuid = bossbar.uuid;
+        drop(players);
+        drop(viewers);
+        drop(bossbar);
+        remove_bossbar(world, uuid, left).await;
+    }
+
+    async fn remove_viewers(&self, world: &World) {
+        let uuid = self.bossbar.lock().await.uuid;
+        let viewers: Vec<Uuid> = self.viewers.lock().await.drain().collect();
+        remove_bossbar(world, uuid, viewers).await;
+    }
+}
+
+/// Removes the bossbar for the `viewers`, wherever they are on the server now.
+async fn remove_bossbar(world: &World, bossbar: Uuid, viewers: Vec<Uuid>) {
+    let Some(server) = world.server.upgrade() else {
+        return;
+    };
+    for viewer in viewers {
+        if let Some(player) = server.get_player_by_uuid(viewer).await {
+            player.remove_bossbar(bossbar).await;
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Caps how many entities of each kind a chunk holds. The entities of a chunk are the ones in its
+//! entity chunk, which the world keeps up to date as entities move and are saved.
+
+use std::sync::Arc;
+
+use pumpkin_config::world::EntityLimitsConfig;
+use pumpkin_data::entity::{EntityType, MobCategory};
+use pumpkin_util::math::vector2::Vector2;
+
+use super::World;
+use crate::entity::EntityBase;
+
+/// The kinds of entities which have their own limit per chunk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityKind {
+    Mob,
+    /// Dropped items and experience orbs, which are culled instead of refused.
+    Item,
+    Other,
+}
+
+impl EntityKind {
+    #[must_use]
+    pub fn of(entity_type: &EntityType) -> Self {
+        if entity_type == &EntityType::ITEM || entity_type == &EntityType::EXPERIENCE_ORB {
+            Self::Item
+        } else if entity_type.category != &MobCategory::MISC {
+            Self::Mob
+        } else {
+            Self::Other
+        }
+    }
+
+    /// The most entities of this kind one chunk may hold, `None` if there is no limit.
+    #[must_use]
+    pub fn limit(self, config: &EntityLimitsConfig) -> Option<usize> {
+        let limit = match self {
+            Self::Mob => config.mobs,
+            Self::Item => config.items,
+            Self::Other => config.other,
+        };
+        (limit > 0).then_some(limit as usize)
+    }
+}
+
+/// The entities which have to be removed so one more fits under `limit`, the oldest ones first.
+/// Entity ids are handed out in spawn order, so lower ids belong to older entities.
+#[must_use]
+pub fn entities_to_cull(mut ids: Vec<i32>, limit: usize) -> Vec<i32> {
+    let excess = (ids.len() + 1).saturating_sub(limit);
+    ids.sort_unstable();
+    ids.truncate(excess);
+    ids
+}
+
+async fn entities_in_chunk(
+    world: &World,
+    chunk: Vector2<i32>,
+    kind: EntityKind,
+) -> Vec<Arc<dyn EntityBase>> {
+    let uuids: Vec<_> = world
+        .level
+        .get_entity_chunk(chunk)
+        .await
+        .read()
+        .await
+        .data
+        .keys()
+        .copied()
+        .collect();
+    let entities = world.entities.read().await;
+    uuids
+        .iter()
+        .filter_map(|uuid| entities.get(uuid))
+        .filter(|entity| EntityKind::of(entity.get_entity().entity_type) == kind)
+        .cloned()
+        .collect()
+}
+
+/// How many more entities of `kind` fit into the chunk, `None` if there is no limit.
+pub async fn room_in_chunk(
+    world: &World,
+    config: &EntityLimitsConfig,
+    chunk: Vector2<i32>,
+    kind: EntityKind,
+) -> Option<usize> {
+    let limit = kind.limit(config)?;
+    let count = entities_in_chunk(world, chunk, kind).await.len();
+    Some(limit.saturating_sub(count))
+}
+
+/// Whether one more entity of `kind` may spawn in the chunk. Full chunks make room for new items
+/// and experience orbs by removing their oldest ones, other entities are refused.
+pub async fn make_room(
+    world: &World,
+    config: &EntityLimitsConfig,
+    chunk: Vector2<i32>,
+    kind: EntityKind,
+) -> bool {
+    let Some(limit) = kind.limit(config) else {
+        return true;
+    };
+    let entities = entities_in_chunk(world, chunk, kind).await;
+    if entities.len() < limit {
+        return true;
+    }
+    if kind != EntityKind::Item {
+        return false;
+    }
+
+    let ids = entities
+        .iter()
+        .map(|entity| entity.get_entity().entity_id)
+        .collect();
+    let culled = entities_to_cull(ids, limit);
+    log::info!(
+        "Removing the {} oldest items in chunk {}, {} as it reached the limit of {limit}",
+        culled.len(),
+        chunk.x,
+        chunk.y,
+    );
+    for entity in &entities {
+        let entity = entity.get_entity();
+        if culled.contains(&entity.entity_id) {
+            entity.remove().await;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::world::EntityLimitsConfig;
+    use pumpkin_data::entity::EntityType;
+
+    use super::{EntityKind, entities_to_cull};
+
+    #[test]
+    fn oldest_items_are_culled_first() {
+        let config = EntityLimitsConfig {
+            mobs: 2,
+            items: 3,
+            other: 0,
+        };
+        assert_eq!(EntityKind::of(&EntityType::ZOMBIE).limit(&config), Some(2));
+        assert_eq!(
+            EntityKind::of(&EntityType::EXPERIENCE_ORB).limit(&config),
+            Some(3)
+        );
+        assert_eq!(EntityKind::of(&EntityType::ARROW).limit(&config), None);
+
+        assert_eq!(entities_to_cull(vec![7, 3, 5], 3), [3]);
+        assert_eq!(entities_to_cull(vec![7, 3, 5, 9], 2), [3, 5, 7]);
+        assert!(entities_to_cull(vec![7, 3], 3).is_empty());
+    }
+}
@@ -5,7 +5,11 @@ use pumpkin_data::{Block, BlockState};
 use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
 
 use crate::{
-    block::{ExplodeArgs, drop_loot},
+    block::{
+        ExplodeArgs,
+        blocks::fire::{FireBlockBase, fire::FireBlock},
+        drop_loot,
+    },
     world::loot::LootContextParameters,
 };
 
@@ -14,11 +18,23 @@ use super::{BlockFlags, World};
 pub struct Explosion {
     power: f32,
     pos: Vector3<f64>,
+    /// Whether the explosion sets fire to the blocks it destroyed.
+    create_fire: bool,
 }
 impl Explosion {
     #[must_use]
     pub fn new(power: f32, pos: Vector3<f64>) -> Self {
-        Self { power, pos }
+        Self {
+            power,
+            pos,
+            create_fire: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_fire(mut self) -> Self {
+        self.create_fire = true;
+        self
     }
     async fn get_blocks_to_destroy(
         &self,
@@ -76,7 +92,7 @@ impl Explosion {
     /// Returns the removed block count
     pub async fn explode(&self, world: &Arc<World>) -> u32 {
         let blocks = self.get_blocks_to_destroy(world).await;
-        // TODO: Entity damage, fire
+        // TODO: Entity damage
         for (pos, (block, state)) in &blocks {
             if state.is_air() {
                 continue;
@@ -103,6 +119,28 @@ impl Explosion {
                     .await;
             }
         }
+        if self.create_fire {
+            Self::create_fire(world, blocks.keys()).await;
+        }
         blocks.len() as u32
     }
+
+    /// Sets fire to a third of the now empty positions standing on a full block.
+    async fn create_fire(world: &Arc<World>, positions: impl Iterator<Item = &BlockPos>) {
+        for pos in positions {
+            if rand::random::<u8>() % 3 != 0
+                || !world.get_block_state(pos).await.is_air()
+                || !world.get_block_state(&pos.down()).await.is_full_cube()
+            {
+                continue;
+            }
+            let fire_block = FireBlockBase::get_fire_type(world, pos).await;
+            let state_id = FireBlock
+                .get_state_for_position(world, &fire_block, pos)
+                .await;
+            world
+                .set_block_state(pos, state_id, BlockFlags::NOTIFY_ALL)
+                .await;
+        }
+    }
 }
@@ -1,8 +1,8 @@
-use pumpkin_data::{Block, BlockState, item::Item};
+use pumpkin_data::{Block, BlockState, Enchantment, item::Item, tag::Taggable};
 use pumpkin_util::{
     loot_table::{
-        LootCondition, LootFunctionNumberProvider, LootFunctionTypes, LootPoolEntry,
-        LootPoolEntryTypes, LootTable,
+        LootCondition, LootFunction, LootFunctionBonusParameter, LootFunctionNumberProvider,
+        LootFunctionTypes, LootPoolEntry, LootPoolEntryTypes, LootTable,
     },
     random::{RandomGenerator, RandomImpl, get_seed, xoroshiro128::Xoroshiro},
 };
@@ -14,6 +14,18 @@ pub struct LootContextParameters {
     pub explosion_radius: Option<f32>,
     pub block_state: Option<&'static BlockState>,
     pub killed_by_player: Option<bool>,
+    /// The item used to break the block, its enchantments change the drops.
+    pub tool: Option<ItemStack>,
+}
+
+impl LootContextParameters {
+    /// The level of `enchantment` on the tool, or 0 without a tool.
+    fn enchantment_level(&self, enchantment: &str) -> i32 {
+        match (&self.tool, Enchantment::from_name(enchantment)) {
+            (Some(tool), Some(enchantment)) => tool.get_enchantment_level(enchantment),
+            _ => 0,
+        }
+    }
 }
 
 pub trait LootTableExt {
@@ -34,6 +46,7 @@ impl LootTableExt for LootTable {
                 }
 
                 let rolls = pool.rolls.get(&mut random).round() as i32;
+                let mut pool_stacks = Vec::new();
 
                 for _ in 0..rolls {
                     let mut total_weight = 0;
@@ -44,10 +57,10 @@ impl LootTableExt for LootTable {
                             .conditions
                             .as_ref()
                             .is_none_or(|c| c.iter().all(|cond| cond.is_fulfilled(&params)))
+                            && entry.weight > 0
                         {
-                            let w = 1; // TODO: weight
-                            total_weight += w;
-                            valid_entries.push((entry, w));
+                            total_weight += entry.weight;
+                            valid_entries.push(entry);
                         }
                     }
 
@@ -57,22 +70,24 @@ impl LootTableExt for LootTable {
 
                     let mut r = random.next_bounded_i32(total_weight);
 
-                    for (entry, weight) in valid_entries {
-                        r -= weight;
+                    for entry in valid_entries {
+                        r -= entry.weight;
                         if r < 0 {
                             if let Some(loot) = entry.get_loot(&params) {
-                                for stack in loot {
-                                    if stack.item_count > 0 {
-                                        stacks.push(stack);
-                                    }
-                                }
+                                pool_stacks.extend(loot);
                             }
                             break;
                         }
                     }
                 }
+
+                apply_functions(pool.functions, &mut pool_stacks, &params);
+                stacks.extend(pool_stacks);
             }
         }
+
+        apply_functions(self.functions, &mut stacks, &params);
+        stacks.retain(|stack| stack.item_count > 0);
         stacks
     }
 }
@@ -90,68 +105,125 @@ impl LootPoolEntryExt for LootPoolEntry {
         }
 
         let mut stacks = self.content.get_stacks(params);
+        apply_functions(self.functions, &mut stacks, params);
+        Some(stacks)
+    }
+}
 
-        if let Some(functions) = self.functions {
-            for function in functions {
-                if let Some(conditions) = function.conditions
-                    && !conditions.iter().all(|cond| cond.is_fulfilled(params))
-                {
-                    continue;
-                }
+/// Applies the functions of an entry, a pool or a whole table to the dropped `stacks`.
+fn apply_functions(
+    functions: Option<&[LootFunction]>,
+    stacks: &mut [ItemStack],
+    params: &LootContextParameters,
+) {
+    let Some(functions) = functions else {
+        return;
+    };
+    for function in functions {
+        if let Some(conditions) = function.conditions
+            && !conditions.iter().all(|cond| cond.is_fulfilled(params))
+        {
+            continue;
+        }
 
-                match &function.content {
-                    LootFunctionTypes::SetCount { count, add } => {
-                        for stack in &mut stacks {
-                            if *add {
-                                stack.item_count += count.generate().round() as u8;
-                            } else {
-                                stack.item_count = count.generate().round() as u8;
-                            }
-                        }
+        match &function.content {
+            LootFunctionTypes::SetCount { count, add } => {
+                for stack in stacks.iter_mut() {
+                    if *add {
+                        stack.item_count += count.generate().round() as u8;
+                    } else {
+                        stack.item_count = count.generate().round() as u8;
                     }
-                    LootFunctionTypes::LimitCount { min, max } => {
-                        if let Some(min) = min.map(|min| min.round() as u8) {
-                            for stack in &mut stacks {
-                                if stack.item_count < min {
-                                    stack.item_count = min;
-                                }
-                            }
+                }
+            }
+            LootFunctionTypes::LimitCount { min, max } => {
+                if let Some(min) = min.map(|min| min.round() as u8) {
+                    for stack in stacks.iter_mut() {
+                        if stack.item_count < min {
+                            stack.item_count = min;
                         }
+                    }
+                }
 
-                        if let Some(max) = max.map(|max| max.round() as u8) {
-                            for stack in &mut stacks {
-                                if stack.item_count > max {
-                                    stack.item_count = max;
-                                }
-                            }
+                if let Some(max) = max.map(|max| max.round() as u8) {
+                    for stack in stacks.iter_mut() {
+                        if stack.item_count > max {
+                            stack.item_count = max;
                         }
                     }
-                    LootFunctionTypes::ApplyBonus {
-                        enchantment: _,
-                        formula: _,
-                        parameters: _,
-                    }
-                    | LootFunctionTypes::CopyComponents {
-                        source: _,
-                        include: _,
-                    }
-                    | LootFunctionTypes::CopyState {
-                        block: _,
-                        properties: _,
-                    }
-                    | LootFunctionTypes::EnchantedCountIncrease
-                    | LootFunctionTypes::SetOminousBottleAmplifier
-                    | LootFunctionTypes::SetPotion
-                    | LootFunctionTypes::FurnaceSmelt
-                    | LootFunctionTypes::ExplosionDecay => {
-                        // TODO: shouldnt crash here but needs to be implemented someday
+                }
+            }
+            LootFunctionTypes::ApplyBonus {
+                enchantment,
+                formula,
+                parameters,
+            } => {
+                let level = params.enchantment_level(enchantment);
+                for stack in stacks.iter_mut() {
+                    stack.item_count =
+                        apply_bonus(formula, parameters.as_ref(), level, stack.item_count);
+                }
+            }
+            LootFunctionTypes::ExplosionDecay => {
+                // Every item survives the explosion on its own. `ApplyExplosionDecayLootFunction`
+                if let Some(radius) = params.explosion_radius {
+                    let mut rng = rand::rng();
+                    for stack in stacks.iter_mut() {
+                        stack.item_count = (0..stack.item_count)
+                            .filter(|_| rng.random::<f32>() <= 1.0 / radius)
+                            .count() as u8;
                     }
                 }
             }
+            LootFunctionTypes::CopyComponents {
+                source: _,
+                include: _,
+            }
+            | LootFunctionTypes::CopyState {
+                block: _,
+                properties: _,
+            }
+            | LootFunctionTypes::EnchantedCountIncrease
+            | LootFunctionTypes::SetOminousBottleAmplifier
+            | LootFunctionTypes::SetPotion
+            | LootFunctionTypes::FurnaceSmelt => {
+                // TODO: shouldnt crash here but needs to be implemented someday
+            }
         }
+    }
+}
 
-        Some(stacks)
+/// Raises `count` by the `formula` of an `apply_bonus` function for an enchantment at `level`.
+/// `ApplyBonusLootFunction`
+fn apply_bonus(
+    formula: &str,
+    parameters: Option<&LootFunctionBonusParameter>,
+    level: i32,
+    count: u8,
+) -> u8 {
+    if level <= 0 {
+        return count;
     }
+    let mut rng = rand::rng();
+    let count = i32::from(count);
+    let count = match (formula, parameters) {
+        ("minecraft:ore_drops", _) => count * rng.random_range(0..level + 2).max(1),
+        (
+            "minecraft:uniform_bonus_count",
+            Some(LootFunctionBonusParameter::Multiplier { bonus_multiplier }),
+        ) => count + rng.random_range(0..=bonus_multiplier * level),
+        (
+            "minecraft:binomial_with_bonus_count",
+            Some(LootFunctionBonusParameter::Probability { extra, probability }),
+        ) => {
+            count
+                + (0..level + extra)
+                    .filter(|_| rng.random::<f32>() < *probability)
+                    .count() as i32
+        }
+        _ => count,
+    };
+    count.clamp(0, i32::from(u8::MAX)) as u8
 }
 
 trait LootPoolEntryTypesExt {
@@ -198,6 +270,37 @@ impl LootConditionExt for LootCondition {
                 true
             }
             Self::KilledByPlayer => params.killed_by_player.unwrap_or(false),
+            Self::Inverted { term } => !term.is_fulfilled(params),
+            Self::AnyOf { terms } => terms.iter().any(|term| term.is_fulfilled(params)),
+            Self::AllOf { terms } => terms.iter().all(|term| term.is_fulfilled(params)),
+            Self::MatchTool {
+                items,
+                enchantments,
+            } => {
+                let Some(tool) = &params.tool else {
+                    return false;
+                };
+                let item_matches = items.is_none_or(|items| {
+                    if items.starts_with('#') {
+                        tool.item.is_tagged_with(items).unwrap_or(false)
+                    } else {
+                        items.strip_prefix("minecraft:").unwrap_or(items) == tool.item.registry_key
+                    }
+                });
+                item_matches
+                    && enchantments.iter().all(|(enchantment, min_level)| {
+                        params.enchantment_level(enchantment) >= *min_level
+                    })
+            }
+            Self::TableBonus {
+                enchantment,
+                chances,
+            } => {
+                let level = params.enchantment_level(enchantment).max(0) as usize;
+                chances
+                    .get(level.min(chances.len().saturating_sub(1)))
+                    .is_some_and(|chance| rand::rng().random::<f32>() < *chance)
+            }
             Self::BlockStateProperty {
                 block: _,
                 properties,
@@ -246,3 +349,68 @@ impl LootFunctionNumberProviderExt for LootFunctionNumberProvider {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, Enchantment, item::Item};
+    use pumpkin_util::loot_table::LootFunctionBonusParameter;
+    use pumpkin_world::item::ItemStack;
+
+    use super::{LootContextParameters, LootTableExt, apply_bonus};
+
+    fn mine_coal_ore(tool: ItemStack, explosion_radius: Option<f32>) -> Vec<ItemStack> {
+        let loot_table = Block::COAL_ORE.loot_table.as_ref().unwrap();
+        loot_table.get_loot(LootContextParameters {
+            explosion_radius,
+            block_state: Some(Block::COAL_ORE.default_state),
+            tool: Some(tool),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn silk_touch_drops_the_ore() {
+        let pickaxe = ItemStack::new(1, &Item::DIAMOND_PICKAXE);
+        let drops = mine_coal_ore(pickaxe.clone(), None);
+        assert_eq!(drops.len(), 1);
+        assert_eq!(drops[0].item.id, Item::COAL.id);
+        assert_eq!(drops[0].item_count, 1);
+
+        let mut silk_touch = pickaxe.clone();
+        silk_touch.enchant(&Enchantment::SILK_TOUCH, 1);
+        let drops = mine_coal_ore(silk_touch, None);
+        assert_eq!(drops.len(), 1);
+        assert_eq!(drops[0].item.id, Item::COAL_ORE.id);
+
+        let mut fortune = pickaxe.clone();
+        fortune.enchant(&Enchantment::FORTUNE, 3);
+        for _ in 0..20 {
+            let drops = mine_coal_ore(fortune.clone(), None);
+            assert_eq!(drops[0].item.id, Item::COAL.id);
+            assert!((1..=4).contains(&drops[0].item_count));
+        }
+
+        // A huge explosion destroys nearly all drops, a tiny one keeps them
+        let survived = (0..100)
+            .filter(|_| !mine_coal_ore(pickaxe.clone(), Some(1000.0)).is_empty())
+            .count();
+        assert!(survived < 10);
+        assert_eq!(mine_coal_ore(pickaxe, Some(1.0)).len(), 1);
+    }
+
+    #[test]
+    fn fortune_bonus_stays_in_range() {
+        let multiplier = LootFunctionBonusParameter::Multiplier {
+            bonus_multiplier: 1,
+        };
+        for _ in 0..100 {
+            // Without fortune nothing changes
+            assert_eq!(apply_bonus("minecraft:ore_drops", None, 0, 1), 1);
+
+            let ore = apply_bonus("minecraft:ore_drops", None, 3, 1);
+            assert!((1..=4).contains(&ore));
+            let uniform = apply_bonus("minecraft:uniform_bonus_count", Some(&multiplier), 3, 2);
+            assert!((2..=5).contains(&uniform));
+        }
+    }
+}
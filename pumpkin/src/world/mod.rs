@@ -3,7 +3,7 @@ use std::sync::Weak;
 use std::sync::atomic::Ordering::Relaxed;
 use std::time::Duration;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, atomic::Ordering},
 };
 
@@ -34,16 +34,14 @@ use crate::{
 };
 use crate::{block::BlockEvent, entity::item::ItemEntity};
 use border::Worldborder;
-use bytes::BufMut;
 use crossbeam::queue::SegQueue;
+use dragon_fight::DragonFight;
+use entity_limits::EntityKind;
 use explosion::Explosion;
 use pumpkin_config::BasicConfiguration;
-use pumpkin_data::data_component_impl::EquipmentSlot;
 use pumpkin_data::dimension::Dimension;
 use pumpkin_data::entity::MobCategory;
 use pumpkin_data::fluid::{Falling, FluidProperties, FluidState};
-use pumpkin_data::meta_data_type::MetaDataType;
-use pumpkin_data::tracked_data::TrackedData;
 use pumpkin_data::{
     Block,
     entity::{EntityStatus, EntityType},
@@ -57,14 +55,13 @@ use pumpkin_inventory::screen_handler::InventoryPlayer;
 use pumpkin_nbt::{compound::NbtCompound, to_bytes_unnamed};
 use pumpkin_protocol::bedrock::client::start_game::CStartGame;
 use pumpkin_protocol::java::client::play::CPlayerSpawnPosition;
-use pumpkin_protocol::java::client::play::{CSetEntityMetadata, Metadata};
-use pumpkin_protocol::ser::serializer::Serializer;
+use pumpkin_protocol::java::client::play::{CBlockEvent, CRemoveMobEffect};
 use pumpkin_protocol::{
     BClientPacket, ClientPacket, IdOr, SoundEvent,
     bedrock::{
         client::{
             creative_content::{CreativeContent, Group},
-            gamerules_changed::GameRules,
+            gamerules_changed::{CGamerulesChanged, GameRules},
             play_status::CPlayStatus,
             start_game::{Experiments, GamePublishSetting, LevelSettings},
             update_artributes::{Attribute, CUpdateAttributes},
@@ -79,22 +76,18 @@ use pumpkin_protocol::{
         self,
         client::play::{
             CBlockEntityData, CEntityStatus, CGameEvent, CLogin, CMultiBlockUpdate,
-            CPlayerChatMessage, CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo,
-            CSetSelectedSlot, CSoundEffect, CSpawnEntity, FilterType, GameEvent, InitChat,
+            CPlayerChatMessage, CPlayerInfoUpdate, CRecipeBookAdd, CRemoveEntities,
+            CRemovePlayerInfo, CSetSelectedSlot, CSoundEffect, FilterType, GameEvent, InitChat,
             PlayerAction, PlayerInfoFlags,
         },
         server::play::SChatMessage,
     },
 };
-use pumpkin_protocol::{
-    codec::item_stack_seralizer::ItemStackSerializer,
-    java::client::play::{CBlockEvent, CRemoveMobEffect, CSetEquipment},
-};
 use pumpkin_protocol::{
     codec::var_int::VarInt,
     java::client::play::{
         CBlockUpdate, CDisguisedChatMessage, CExplosion, CRespawn, CSetBlockDestroyStage,
-        CWorldEvent,
+        CSystemChatMessage, CWorldEvent,
     },
 };
 use pumpkin_util::resource_location::ResourceLocation;
@@ -107,7 +100,11 @@ use pumpkin_util::{
     math::{position::chunk_section_from_pos, vector2::Vector2},
     random::{RandomImpl, get_seed, xoroshiro128::Xoroshiro},
 };
+use pumpkin_world::block::entities::block_entity_from_nbt;
 use pumpkin_world::chunk::palette::BlockPalette;
+use pumpkin_world::generation::structure::template::{
+    BlockMirror, BlockRotation, StructureTemplate, transform_state,
+};
 use pumpkin_world::inventory::Clearable;
 use pumpkin_world::world::{GetBlockError, WorldFuture};
 use pumpkin_world::{
@@ -120,22 +117,24 @@ use pumpkin_world::{world::BlockFlags, world_info::LevelData};
 use rand::seq::SliceRandom;
 use rand::{Rng, rng};
 use scoreboard::Scoreboard;
-use serde::Serialize;
 use time::LevelTime;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 
 pub mod border;
 pub mod bossbar;
+pub mod crowding;
 pub mod custom_bossbar;
+pub mod dragon_fight;
+pub mod entity_limits;
 pub mod natural_spawner;
 pub mod scoreboard;
+pub mod tracking;
 pub mod weather;
 
 use crate::world::natural_spawner::{SpawnState, spawn_for_chunk};
 use pumpkin_data::effect::StatusEffect;
-use pumpkin_world::chunk::ChunkHeightmapType::MotionBlocking;
-use pumpkin_world::generation::settings::gen_settings_from_dimension;
+use pumpkin_world::chunk::ChunkHeightmapType::{MotionBlocking, WorldSurface};
 use uuid::Uuid;
 use weather::Weather;
 
@@ -185,6 +184,10 @@ pub struct World {
     pub min_y: i32,
     /// The world's weather, including rain and thunder levels.
     pub weather: Mutex<Weather>,
+    /// The ender dragon fight, only present in the end.
+    pub dragon_fight: Option<DragonFight>,
+    /// Job site blocks claimed by villagers, keyed by position and holding the villager's UUID.
+    pub job_site_claims: Mutex<HashMap<BlockPos, uuid::Uuid>>,
     /// Block Behaviour
     pub block_registry: Arc<BlockRegistry>,
     pub server: Weak<Server>,
@@ -204,8 +207,7 @@ impl World {
         block_registry: Arc<BlockRegistry>,
         server: Weak<Server>,
     ) -> Self {
-        // TODO
-        let generation_settings = gen_settings_from_dimension(&dimension);
+        let generation_settings = level.world_gen.setting.settings();
         Self {
             level,
             level_info,
@@ -216,6 +218,8 @@ impl World {
             level_time: Mutex::new(LevelTime::new()),
             dimension,
             weather: Mutex::new(Weather::new()),
+            dragon_fight: (dimension == Dimension::THE_END).then(DragonFight::new),
+            job_site_claims: Mutex::new(HashMap::new()),
             block_registry,
             sea_level: generation_settings.sea_level,
             min_y: i32::from(generation_settings.shape.min_y),
@@ -234,6 +238,19 @@ impl World {
         self.level.shutdown().await;
     }
 
+    /// Saves the entities and chunks of this world without unloading anything.
+    pub async fn save(&self) {
+        for (uuid, entity) in self.entities.read().await.iter() {
+            self.save_entity(uuid, entity).await;
+        }
+        self.level.save_chunks().await;
+    }
+
+    /// Writes a single chunk to disk right away, without waiting for the next autosave.
+    pub async fn save_chunk(&self, position: Vector2<i32>) {
+        self.level.save_chunk(position).await;
+    }
+
     async fn save_entity(&self, uuid: &uuid::Uuid, entity: &Arc<dyn EntityBase>) {
         // First lets see if the entity was saved on an other chunk, and if the current chunk does not match we remove it
         // Otherwise we just update the nbt data
@@ -301,9 +318,11 @@ impl World {
     }
 
     pub async fn set_difficulty(&self, difficulty: Difficulty) {
-        let mut level_info = self.level_info.write().await;
+        self.level_info.write().await.difficulty = difficulty;
 
-        level_info.difficulty = difficulty;
+        if difficulty == Difficulty::Peaceful {
+            self.remove_monsters().await;
+        }
     }
 
     pub async fn add_synced_block_event(&self, pos: BlockPos, r#type: u8, data: u8) {
@@ -354,6 +373,40 @@ impl World {
         }
     }
 
+    /// Broadcasts a packet changing blocks or block entities in `chunk`, it stays behind the
+    /// chunk's data for players who were not sent the chunk yet.
+    pub async fn broadcast_block_packet<P: ClientPacket>(&self, chunk: Vector2<i32>, packet: &P) {
+        for player in self.players.read().await.values() {
+            player.client.enqueue_block_packet(chunk, packet).await;
+        }
+    }
+
+    /// Sends a packet about an entity to the players whose clients know about it, see
+    /// [`tracking`]. Players don't track themselves, so a player entity isn't sent the packet.
+    pub async fn broadcast_to_trackers<P: ClientPacket>(&self, entity_id: i32, packet: &P) {
+        for player in self.players.read().await.values() {
+            if player.tracked_entities.lock().await.contains(entity_id) {
+                player.client.enqueue_packet(packet).await;
+            }
+        }
+    }
+
+    /// Like [`Self::broadcast_to_trackers`], but a player entity is sent the packet as well,
+    /// like for its own velocity or metadata.
+    pub async fn broadcast_to_trackers_and_self<P: ClientPacket>(
+        &self,
+        entity_id: i32,
+        packet: &P,
+    ) {
+        for player in self.players.read().await.values() {
+            if player.entity_id() == entity_id
+                || player.tracked_entities.lock().await.contains(entity_id)
+            {
+                player.client.enqueue_packet(packet).await;
+            }
+        }
+    }
+
     pub async fn broadcast_message(
         &self,
         message: &TextComponent,
@@ -368,6 +421,22 @@ impl World {
         self.broadcast_editioned(&je_packet, &be_packet).await;
     }
 
+    /// Sends `message` as an unsigned system message, which clients never show as player chat.
+    pub async fn broadcast_system_message(&self, message: &TextComponent) {
+        let (je_packet, be_packet) = system_message_packets(message);
+        self.broadcast_editioned(&je_packet, &be_packet).await;
+    }
+
+    /// Sends a packet to the Bedrock players of the world, for what Java players are told in
+    /// another way.
+    pub async fn broadcast_bedrock<B: BClientPacket>(&self, packet: &B) {
+        for player in self.players.read().await.values() {
+            if let ClientPlatform::Bedrock(client) = &player.client {
+                client.send_game_packet(packet).await;
+            }
+        }
+    }
+
     // This should replace broadcast_packet_all at some point
     pub async fn broadcast_editioned<J: ClientPacket, B: BClientPacket>(
         &self,
@@ -384,6 +453,21 @@ impl World {
         }
     }
 
+    /// Tells Bedrock clients whether to advance the time on their own. Java clients get this with
+    /// every time update.
+    pub async fn send_daylight_cycle(&self, advance_time: bool) {
+        let packet = CGamerulesChanged {
+            rule_data: GameRules::daylight_cycle(advance_time),
+        };
+        let time = self.level_time.lock().await.bedrock_packet();
+        for player in self.players.read().await.values() {
+            if let ClientPlatform::Bedrock(client) = &player.client {
+                client.send_game_packet(&packet).await;
+                client.send_game_packet(&time).await;
+            }
+        }
+    }
+
     pub async fn broadcast_secure_player_chat(
         &self,
         sender: &Arc<Player>,
@@ -561,15 +645,25 @@ impl World {
         self.flush_block_updates().await;
         self.flush_synced_block_events().await;
         self.tick_environment().await;
+        self.worldborder.lock().await.tick();
+        if let Some(dragon_fight) = &self.dragon_fight {
+            dragon_fight.tick(self).await;
+        }
 
         // 2. Chunks
         let chunk_start = tokio::time::Instant::now();
-        self.tick_chunks().await;
+        let players_to_tick: Vec<_> = self.players.read().await.values().cloned().collect();
+        let simulated_chunks = simulated_chunks(
+            players_to_tick
+                .iter()
+                .map(|player| player.living_entity.entity.chunk_pos.load()),
+            i32::from(server.basic_config.simulation_distance.get()),
+        );
+        self.tick_chunks(server, &simulated_chunks).await;
         let chunk_elapsed = chunk_start.elapsed();
 
         // 3. Players
         let player_start = tokio::time::Instant::now();
-        let players_to_tick: Vec<_> = self.players.read().await.values().cloned().collect();
         let player_count = players_to_tick.len();
         for player in &players_to_tick {
             player.tick(server).await;
@@ -578,10 +672,18 @@ impl World {
 
         // 4. Entities & Collision
         let entity_start = tokio::time::Instant::now();
-        let entities_to_tick: Vec<_> = self.entities.read().await.values().cloned().collect();
+        // Entities outside of the simulation distance of every player are frozen
+        let entities_to_tick: Vec<_> = self
+            .entities
+            .read()
+            .await
+            .values()
+            .filter(|entity| simulated_chunks.contains(&entity.get_entity().chunk_pos.load()))
+            .cloned()
+            .collect();
         let entity_count = entities_to_tick.len();
 
-        for entity in entities_to_tick {
+        for entity in &entities_to_tick {
             entity.get_entity().age.fetch_add(1, Relaxed);
             entity.tick(entity.clone(), server).await;
 
@@ -599,11 +701,47 @@ impl World {
                 }
             }
         }
+        if let Some(server) = self.server.upgrade() {
+            let teams = server.teams.lock().await;
+            crowding::push_entities(&players_to_tick, &entities_to_tick, &teams).await;
+        }
+
+        // Passengers follow their vehicle
+        for player in &players_to_tick {
+            player
+                .living_entity
+                .entity
+                .update_passenger_positions()
+                .await;
+        }
+        for entity in &entities_to_tick {
+            entity.get_entity().update_passenger_positions().await;
+        }
+
+        // Entities which came into or left the tracking range of players
+        let ranges = &server.advanced_config.world.entity_tracking;
+        let entities: Vec<_> = self.entities.read().await.values().cloned().collect();
+        for player in &players_to_tick {
+            tracking::update_tracked_entities(ranges, player, &players_to_tick, &entities).await;
+        }
         let entity_elapsed = entity_start.elapsed();
 
+        // 5. Entity metadata, sent once all changes of this tick were made
+        let metadata_interval = server.advanced_config.world.entity_metadata_interval;
+        for player in &players_to_tick {
+            player
+                .living_entity
+                .entity
+                .flush_meta_data(metadata_interval)
+                .await;
+        }
+        for entity in &entities_to_tick {
+            entity.get_entity().flush_meta_data(metadata_interval).await;
+        }
+
         self.level.chunk_loading.lock().unwrap().send_change();
 
-        // 5. Detailed Slow Tick Logging
+        // 6. Detailed Slow Tick Logging
         let total_elapsed = start.elapsed();
         if total_elapsed.as_millis() > 50 {
             log::debug!(
@@ -636,13 +774,14 @@ impl World {
             }
             if chunk_section.len() == 1 {
                 let (block_pos, block_state_id) = chunk_section[0];
-                self.broadcast_packet_all(&CBlockUpdate::new(
-                    block_pos,
-                    i32::from(block_state_id).into(),
-                ))
+                self.broadcast_block_packet(
+                    block_pos.chunk_position(),
+                    &CBlockUpdate::new(block_pos, i32::from(block_state_id).into()),
+                )
                 .await;
             } else {
-                self.broadcast_packet_all(&CMultiBlockUpdate::new(chunk_section.clone()))
+                let chunk = chunk_section[0].0.chunk_position();
+                self.broadcast_block_packet(chunk, &CMultiBlockUpdate::new(chunk_section.clone()))
                     .await;
             }
         }
@@ -672,6 +811,7 @@ impl World {
         }
 
         let mut weather = self.weather.lock().await;
+        weather.weather_cycle_enabled = advance_weather;
         weather.tick_weather(self).await;
 
         if self.should_skip_night().await && level_time.is_night() {
@@ -684,15 +824,20 @@ impl World {
             }
 
             if weather.weather_cycle_enabled && (weather.raining || weather.thundering) {
-                weather.reset_weather_cycle(self).await;
+                weather.reset_weather_cycle();
             }
         } else if level_time.world_age % 20 == 0 {
             level_time.send_time(self).await;
         }
     }
 
-    pub async fn tick_chunks(self: &Arc<Self>) {
-        let tick_data = self.level.get_tick_data().await;
+    /// Ticks the blocks of the chunks in `simulated_chunks` and spawns mobs in them.
+    pub async fn tick_chunks(
+        self: &Arc<Self>,
+        server: &Server,
+        simulated_chunks: &HashSet<Vector2<i32>>,
+    ) {
+        let tick_data = self.level.get_tick_data(simulated_chunks).await;
         for scheduled_tick in tick_data.block_ticks {
             let block = self.get_block(&scheduled_tick.position).await;
             if let Some(pumpkin_block) = self.block_registry.get_pumpkin_block(block.id) {
@@ -731,42 +876,31 @@ impl World {
 
         let spawn_entity_clock_start = tokio::time::Instant::now();
 
-        let mut spawning_chunks_map = HashMap::new();
         // TODO use FixedPlayerDistanceChunkTracker
-
-        for i in self.players.read().await.values() {
-            let center = i.living_entity.entity.chunk_pos.load();
-            for dx in -8..=8 {
-                for dy in -8..=8 {
-                    // if dx.abs() <= 2 || dy.abs() <= 2 || dx.abs() >= 6 || dy.abs() >= 6 { // this is only for debug, spawning runs too slow
-                    //     continue;
-                    // }
-                    let chunk_pos = center.add_raw(dx, dy);
-                    if !spawning_chunks_map.contains_key(&chunk_pos)
-                        && let Some(chunk) = self.level.try_get_chunk(&chunk_pos)
-                    {
-                        spawning_chunks_map.entry(chunk_pos).or_insert(chunk);
-                    }
-                }
-            }
-        }
-
-        let mut spawning_chunks: Vec<(Vector2<i32>, Arc<RwLock<ChunkData>>)> =
-            spawning_chunks_map.into_iter().collect();
+        let mut spawning_chunks: Vec<(Vector2<i32>, Arc<RwLock<ChunkData>>)> = simulated_chunks
+            .iter()
+            .filter_map(|chunk_pos| {
+                self.level
+                    .try_get_chunk(chunk_pos)
+                    .map(|chunk| (*chunk_pos, chunk))
+            })
+            .collect();
 
         let get_chunks_clock = spawn_entity_clock_start.elapsed();
         // log::debug!("spawning chunks size {}", spawning_chunks.len());
 
+        let mob_caps = server.mob_caps.load();
         let mut spawn_state =
-            SpawnState::new(spawning_chunks.len() as i32, &self.entities, self).await; // TODO store it
+            SpawnState::new(spawning_chunks.len() as i32, &self.entities, self, mob_caps).await; // TODO store it
 
         // TODO gamerule this.spawnEnemies || this.spawnFriendlies
         let spawn_passives = self.level_time.lock().await.time_of_day % 400 == 0;
+        let spawn_enemies = self.level_info.read().await.difficulty != Difficulty::Peaceful;
         let spawn_list: Vec<&'static MobCategory> =
             natural_spawner::get_filtered_spawning_categories(
                 &spawn_state,
                 true,
-                true,
+                spawn_enemies,
                 spawn_passives,
             );
 
@@ -1004,6 +1138,16 @@ impl World {
         inside
     }
 
+    /// Returns whether `bounding_box` touches the collision shape of the block at `pos`, e.g. an
+    /// entity pressed against the side of a cactus, which never reaches its outline.
+    pub fn check_contact(bounding_box: &BoundingBox, pos: BlockPos, state: &BlockState) -> bool {
+        let contact_box = bounding_box.expand_all(1.0E-7);
+        state
+            .get_block_collision_shapes()
+            .iter()
+            .any(|shape| shape.at_pos(pos).intersects(&contact_box))
+    }
+
     pub fn check_collision<F>(
         bounding_box: &BoundingBox,
         pos: BlockPos,
@@ -1050,12 +1194,14 @@ impl World {
 
         let max = bounding_box.max_block_pos();
 
+        let states = self.get_states_between(min, max).await;
+
         for x in min.0.x..=max.0.x {
             for y in min.0.y..=max.0.y {
                 for z in min.0.z..=max.0.z {
                     let pos = BlockPos::new(x, y, z);
 
-                    let state = self.get_block_state(&pos).await;
+                    let state = states[&pos];
 
                     let collided = Self::check_collision(
                         &bounding_box,
@@ -1078,18 +1224,38 @@ impl World {
     }
 
     pub async fn is_space_empty(&self, bounding_box: BoundingBox) -> bool {
-        let min = bounding_box.min_block_pos();
-        let max = bounding_box.max_block_pos();
+        let states = self.get_states_in_box(&bounding_box).await;
 
-        for pos in BlockPos::iterate(min, max) {
-            let state = self.get_block_state(&pos).await;
-            let collided = Self::check_collision(&bounding_box, pos, state, false, |_| ());
+        !states
+            .into_iter()
+            .any(|(pos, state)| Self::check_collision(&bounding_box, pos, state, false, |_| ()))
+    }
 
-            if collided {
-                return false;
-            }
-        }
-        true
+    /// Gets the states of all blocks the bounding box touches.
+    ///
+    /// Unlike calling [`World::get_block_state`] for every position, every chunk is only locked
+    /// once, which matters for large boxes.
+    pub async fn get_states_in_box(
+        &self,
+        bounding_box: &BoundingBox,
+    ) -> HashMap<BlockPos, &'static BlockState> {
+        self.get_states_between(bounding_box.min_block_pos(), bounding_box.max_block_pos())
+            .await
+    }
+
+    /// Gets the states of all blocks between `min` and `max`, inclusive, see
+    /// [`World::get_states_in_box`].
+    pub async fn get_states_between(
+        &self,
+        min: BlockPos,
+        max: BlockPos,
+    ) -> HashMap<BlockPos, &'static BlockState> {
+        self.level
+            .get_block_states_in_box(min, max)
+            .await
+            .into_iter()
+            .map(|(pos, state)| (pos, BlockState::from_id(state.0)))
+            .collect()
     }
 
     pub async fn tick_spawning_chunk(
@@ -1100,7 +1266,6 @@ impl World {
         spawn_state: &mut SpawnState,
     ) {
         // this.level.tickThunder(chunk);
-        //TODO check in simulation distance
         let weather = self.weather.lock().await;
         if weather.raining && weather.thundering && rng().random_range(0..100_000) == 0 {
             let rand_value = rng().random::<i32>() >> 2;
@@ -1157,15 +1322,26 @@ impl World {
 
     /// Gets the y position of the first non air block from the top down
     pub async fn get_top_block(&self, position: Vector2<i32>) -> i32 {
-        for y in (self.dimension.min_y..self.dimension.height).rev() {
-            let pos = BlockPos::new(position.x, y, position.y);
-            let block = self.get_block_state(&pos).await;
-            if block.is_air() {
-                continue;
-            }
-            return y;
+        let chunk = self
+            .level
+            .get_chunk(Vector2::new(position.x >> 4, position.y >> 4))
+            .await;
+        chunk
+            .read()
+            .await
+            .heightmap
+            .get(WorldSurface, position.x, position.y, self.dimension.min_y)
+    }
+
+    /// Whether it is raining on `position`, which has to be open to the sky.
+    pub async fn is_raining_at(&self, position: &BlockPos) -> bool {
+        // TODO: Check the biome's precipitation
+        if !self.weather.lock().await.is_raining() {
+            return false;
         }
-        self.dimension.min_y
+        self.get_top_block(Vector2::new(position.0.x, position.0.z))
+            .await
+            < position.0.y
     }
 
     #[expect(clippy::too_many_lines)]
@@ -1197,7 +1373,12 @@ impl World {
             editor_world_type: VarInt(0),
             is_created_in_editor: false,
             is_exported_from_editor: false,
-            day_cycle_stop_time: VarInt(-1),
+            // Bedrock clients advance the time on their own unless it is stopped
+            day_cycle_stop_time: if level_info.game_rules.advance_time {
+                VarInt(-1)
+            } else {
+                VarInt(self.level_time.lock().await.time_of_day as _)
+            },
             education_edition_offer: VarInt(0),
             has_education_features_enabled: false,
             education_product_id: String::new(),
@@ -1210,9 +1391,7 @@ impl World {
             platform_broadcast_setting: GamePublishSetting::Public,
             commands_enabled: level_info.allow_commands,
             is_texture_packs_required: false,
-            rule_data: GameRules {
-                list_size: VarUInt(0),
-            },
+            rule_data: GameRules::daylight_cycle(level_info.game_rules.advance_time),
             experiments: Experiments {
                 names_size: 0,
                 experiments_ever_toggled: false,
@@ -1246,6 +1425,7 @@ impl World {
             scenario_id: String::new(),
             owner_id: String::new(),
         };
+        let weather_events = weather.bedrock_join_events(weather::has_weather(&self.dimension));
         drop(level_info);
         drop(weather);
 
@@ -1266,7 +1446,7 @@ impl World {
                 is_trial: false,
                 rewind_history_size: VarInt(0),
                 server_authoritative_block_breaking: false,
-                current_level_time: self.level_time.lock().await.world_age as _,
+                current_level_time: self.level_time.lock().await.time_of_day as _,
                 enchantment_seed: VarInt(0),
                 block_properties_size: VarUInt(0),
                 // TODO Make this unique
@@ -1288,6 +1468,10 @@ impl World {
                 disable_client_sounds: false,
             })
             .await;
+        // Where the weather goes from the levels in the start game packet
+        for event in &weather_events {
+            client.send_game_packet(event).await;
+        }
         // chunker::update_position(&player).await;
         client
             .send_game_packet(&CreativeContent {
@@ -1419,8 +1603,6 @@ impl World {
             (position, info.spawn_yaw, info.spawn_pitch)
         };
 
-        let velocity = player.living_entity.entity.velocity.load();
-
         log::debug!("Sending player teleport to {}", player.gameprofile.name);
         player.request_teleport(position, yaw, pitch).await;
 
@@ -1499,105 +1681,9 @@ impl World {
                 .await;
         };
 
-        let gameprofile = &player.gameprofile;
-
-        log::debug!("Broadcasting player spawn for {}", player.gameprofile.name);
-        // Spawn the player for every client.
-        self.broadcast_packet_except(
-            &[player.gameprofile.id],
-            &CSpawnEntity::new(
-                entity_id.into(),
-                gameprofile.id,
-                i32::from(EntityType::PLAYER.id).into(),
-                position,
-                pitch,
-                yaw,
-                yaw,
-                0.into(),
-                velocity,
-            ),
-        )
-        .await;
-
-        // Spawn players for our client.
-        let id = player.gameprofile.id;
-        for (_, existing_player) in current_players.iter().filter(|c| c.0 != &id) {
-            let entity = &existing_player.living_entity.entity;
-            let pos = entity.pos.load();
-            let gameprofile = &existing_player.gameprofile;
-            log::debug!("Sending player entities to {}", player.gameprofile.name);
-
-            client
-                .enqueue_packet(&CSpawnEntity::new(
-                    existing_player.entity_id().into(),
-                    gameprofile.id,
-                    i32::from(EntityType::PLAYER.id).into(),
-                    pos,
-                    entity.pitch.load(),
-                    entity.yaw.load(),
-                    entity.head_yaw.load(),
-                    0.into(),
-                    entity.velocity.load(),
-                ))
-                .await;
-            {
-                let config = existing_player.config.read().await;
-                let mut buf = Vec::new();
-                {
-                    let meta = Metadata::new(
-                        TrackedData::DATA_PLAYER_MODE_CUSTOMIZATION_ID,
-                        MetaDataType::Byte,
-                        config.skin_parts,
-                    );
-                    let mut serializer_buf = Vec::new();
-
-                    let mut serializer = Serializer::new(&mut serializer_buf);
-                    meta.serialize(&mut serializer).unwrap();
-                    buf.extend(serializer_buf);
-                };
-                drop(config);
-                // END
-                buf.put_u8(255);
-                client
-                    .enqueue_packet(&CSetEntityMetadata::new(
-                        existing_player.get_entity().entity_id.into(),
-                        buf.into(),
-                    ))
-                    .await;
-            };
-
-            {
-                let mut equipment_list = Vec::new();
-
-                equipment_list.push((
-                    EquipmentSlot::MAIN_HAND.discriminant(),
-                    existing_player.inventory.held_item().lock().await.clone(),
-                ));
-
-                for (slot, item_arc_mutex) in &existing_player
-                    .inventory
-                    .entity_equipment
-                    .lock()
-                    .await
-                    .equipment
-                {
-                    let item_stack = item_arc_mutex.lock().await.clone();
-                    equipment_list.push((slot.discriminant(), item_stack));
-                }
-
-                let equipment: Vec<(i8, ItemStackSerializer)> = equipment_list
-                    .iter()
-                    .map(|(slot, stack)| (*slot, ItemStackSerializer::from(stack.clone())))
-                    .collect();
-
-                client
-                    .enqueue_packet(&CSetEquipment::new(
-                        existing_player.entity_id().into(),
-                        equipment,
-                    ))
-                    .await;
-            }
-        }
+        // The other players are spawned for our client and our player for theirs once they are
+        // in tracking range, see `tracking::update_tracked_entities`
+        drop(current_players);
         player.send_client_information().await;
 
         player.send_abilities_update().await;
@@ -1609,6 +1695,18 @@ impl World {
             ))
             .await;
 
+        // Sync the recipe book
+        let recipes = player
+            .advancement_tracker
+            .lock()
+            .await
+            .unlocked_recipes()
+            .cloned()
+            .collect();
+        client
+            .enqueue_packet(&CRecipeBookAdd::init_recipes(recipes))
+            .await;
+
         // Start waiting for level chunks. Sets the "Loading Terrain" screen
         log::debug!("Sending waiting chunks to {}", player.gameprofile.name);
         client
@@ -1643,26 +1741,13 @@ impl World {
             .await;
 
         // Send initial weather state
-        let weather = self.weather.lock().await;
-        if weather.raining {
-            client
-                .enqueue_packet(&CGameEvent::new(GameEvent::BeginRaining, 0.0))
-                .await;
-
-            // Calculate rain and thunder levels directly from public fields
-            let rain_level = weather.rain_level.clamp(0.0, 1.0);
-            let thunder_level = weather.thunder_level.clamp(0.0, 1.0);
-            drop(weather);
-
-            client
-                .enqueue_packet(&CGameEvent::new(GameEvent::RainLevelChange, rain_level))
-                .await;
-            client
-                .enqueue_packet(&CGameEvent::new(
-                    GameEvent::ThunderLevelChange,
-                    thunder_level,
-                ))
-                .await;
+        let weather_events = self
+            .weather
+            .lock()
+            .await
+            .java_join_events(weather::has_weather(&self.dimension));
+        for event in &weather_events {
+            client.enqueue_packet(event).await;
         }
 
         // if let Some(bossbars) = self..lock().await.get_player_bars(&player.gameprofile.id) {
@@ -1677,44 +1762,14 @@ impl World {
             .await;
 
         player.send_active_effects().await;
-        self.send_player_equipment(&player).await;
+        server.teams.lock().await.send_teams(&player).await;
 
         if let Some(server) = self.server.upgrade() {
             crate::advancement::AdvancementTriggers::send_initial_advancements(&player, &server).await;
         }
     }
 
-    async fn send_player_equipment(&self, from: &Player) {
-        let mut equipment_list = Vec::new();
-
-        equipment_list.push((
-            EquipmentSlot::MAIN_HAND.discriminant(),
-            from.inventory.held_item().lock().await.clone(),
-        ));
-
-        for (slot, item_arc_mutex) in &from.inventory.entity_equipment.lock().await.equipment {
-            let item_stack = item_arc_mutex.lock().await.clone();
-            equipment_list.push((slot.discriminant(), item_stack));
-        }
-
-        let equipment: Vec<(i8, ItemStackSerializer)> = equipment_list
-            .iter()
-            .map(|(slot, stack)| (*slot, ItemStackSerializer::from(stack.clone())))
-            .collect();
-        self.broadcast_packet_except(
-            &[from.get_entity().entity_uuid],
-            &CSetEquipment::new(from.entity_id().into(), equipment),
-        )
-        .await;
-    }
-
-    pub async fn send_world_info(
-        &self,
-        player: &Arc<Player>,
-        position: Vector3<f64>,
-        yaw: f32,
-        pitch: f32,
-    ) {
+    pub async fn send_world_info(&self, player: &Arc<Player>) {
         if let ClientPlatform::Java(client) = &player.client {
             self.worldborder.lock().await.init_client(client).await;
         }
@@ -1728,22 +1783,12 @@ impl World {
 
         let entity = &player.living_entity.entity;
 
-        self.broadcast_packet_except(
-            &[player.gameprofile.id],
-            // TODO: add velo
-            &CSpawnEntity::new(
-                entity.entity_id.into(),
-                player.gameprofile.id,
-                i32::from(EntityType::PLAYER.id).into(),
-                position,
-                pitch,
-                yaw,
-                yaw,
-                0.into(),
-                Vector3::new(0.0, 0.0, 0.0),
-            ),
-        )
-        .await;
+        // The client forgot all entities, and the other players spawn the player again as it
+        // comes into their tracking range
+        player.tracked_entities.lock().await.clear();
+        let players: Vec<_> = self.players.read().await.values().cloned().collect();
+        tracking::untrack(&players, entity.entity_id).await;
+        entity.resend_meta_data().await;
         player.send_client_information().await;
 
         chunker::update_position(player).await;
@@ -1753,7 +1798,22 @@ impl World {
     }
 
     pub async fn explode(self: &Arc<Self>, position: Vector3<f64>, power: f32) {
-        let explosion = Explosion::new(power, position);
+        self.create_explosion(Explosion::new(power, position), position, power)
+            .await;
+    }
+
+    /// Like [`Self::explode`], but also sets fire to the destroyed blocks.
+    pub async fn explode_with_fire(self: &Arc<Self>, position: Vector3<f64>, power: f32) {
+        self.create_explosion(Explosion::new(power, position).with_fire(), position, power)
+            .await;
+    }
+
+    async fn create_explosion(
+        self: &Arc<Self>,
+        explosion: Explosion,
+        position: Vector3<f64>,
+        power: f32,
+    ) {
         let block_count = explosion.explode(self).await;
         let particle = if power < 2.0 {
             Particle::Explosion
@@ -1792,6 +1852,8 @@ impl World {
 
         // TODO: switch world in player entity to new world
 
+        // The client forgets the chunks it was sent so far
+        player.client.discard_bulk_packets();
         player
             .client
             .enqueue_packet(&CRespawn::new(
@@ -1850,7 +1912,7 @@ impl World {
 
         // TODO: difficulty, exp bar, status effect
 
-        self.send_world_info(player, position, yaw, pitch).await;
+        self.send_world_info(player).await;
     }
 
     /// Returns true if enough players are sleeping and we should skip the night.
@@ -1976,6 +2038,11 @@ impl World {
                     }
 
                     if !ids.is_empty() {
+                        let mut tracked = player.tracked_entities.lock().await;
+                        for id in &ids {
+                            tracked.remove(id.0);
+                        }
+                        drop(tracked);
                         player
                             .client
                             .enqueue_packet(&CRemoveEntities::new(&ids))
@@ -2006,10 +2073,6 @@ impl World {
                         from_type(entity_type, Vector3::new(0.0, 0.0, 0.0), &world, *uuid).await;
                     entity.read_nbt_non_mut(entity_nbt).await;
                     let base_entity = entity.get_entity();
-                    player
-                        .client
-                        .enqueue_packet(&base_entity.create_spawn_packet())
-                        .await;
                     entity.init_data_tracker().await;
                     current_entities.insert(base_entity.entity_uuid, entity);
                 }
@@ -2293,8 +2356,8 @@ impl World {
         let uuid = player.gameprofile.id;
         self.broadcast_packet_all(&CRemovePlayerInfo::new(&[uuid]))
             .await;
-        self.broadcast_packet_all(&CRemoveEntities::new(&[player.entity_id().into()]))
-            .await;
+        let players: Vec<_> = self.players.read().await.values().cloned().collect();
+        tracking::untrack(&players, player.entity_id()).await;
 
         if fire_event {
             let msg_comp = TextComponent::translate(
@@ -2317,14 +2380,26 @@ impl World {
         }
     }
 
-    /// Adds an entity to the world.
+    /// Adds an entity to the world, unless its chunk already holds too many entities of its kind.
     pub async fn spawn_entity(&self, entity: Arc<dyn EntityBase>) {
         let base_entity = entity.get_entity();
-        self.broadcast_packet_all(&base_entity.create_spawn_packet())
-            .await;
+        let chunk_coordinate = base_entity.block_pos.load().chunk_position();
+        if let Some(server) = self.server.upgrade() {
+            let limits = &server.advanced_config.world.entity_limits;
+            let kind = EntityKind::of(base_entity.entity_type);
+            if !entity_limits::make_room(self, limits, chunk_coordinate, kind).await {
+                log::debug!(
+                    "Not spawning {} in the full chunk {}, {}",
+                    base_entity.entity_type.resource_name,
+                    chunk_coordinate.x,
+                    chunk_coordinate.y,
+                );
+                return;
+            }
+        }
+        // Players spawn it once it is in their tracking range
         entity.init_data_tracker().await;
 
-        let chunk_coordinate = base_entity.block_pos.load().chunk_position();
         let chunk = self.level.get_entity_chunk(chunk_coordinate).await;
         {
             let mut chunk = chunk.write().await;
@@ -2340,12 +2415,27 @@ impl World {
 
     pub async fn remove_entity(&self, entity: &Entity) {
         self.entities.write().await.remove(&entity.entity_uuid);
-        self.broadcast_packet_all(&CRemoveEntities::new(&[entity.entity_id.into()]))
-            .await;
+        let players: Vec<_> = self.players.read().await.values().cloned().collect();
+        tracking::untrack(&players, entity.entity_id).await;
 
         self.remove_entity_data(entity).await;
     }
 
+    /// Removes every monster from the world, as they can't exist on peaceful difficulty.
+    pub async fn remove_monsters(&self) {
+        let monsters: Vec<_> = self
+            .entities
+            .read()
+            .await
+            .values()
+            .filter(|entity| entity.get_entity().entity_type.category == &MobCategory::MONSTER)
+            .cloned()
+            .collect();
+        for monster in monsters {
+            monster.get_entity().remove().await;
+        }
+    }
+
     pub async fn set_block_breaking(&self, from: &Entity, location: BlockPos, progress: i32) {
         self.broadcast_packet_except(
             &[from.entity_uuid],
@@ -2552,7 +2642,7 @@ impl World {
         else {
             panic!("Timed out while waiting to acquire chunk write lock")
         };
-        let replaced_block_state_id = chunk.section.set_block_absolute_y(
+        let replaced_block_state_id = chunk.set_block_absolute_y(
             relative.x as usize,
             relative.y,
             relative.z as usize,
@@ -2713,11 +2803,26 @@ impl World {
         flags: BlockFlags,
     ) {
         let (broken_block, broken_block_state) = self.get_block_and_state_id(position).await;
-        let event = BlockBreakEvent::new(cause.clone(), broken_block, *position, 0, false);
+        let mut event = BlockBreakEvent::new(cause.clone(), broken_block, *position, 0, false);
+        // Plugins may still allow breaking the block
+        if let Some(player) = &cause {
+            event.cancelled = self.is_spawn_protected(position, player).await;
+        }
 
         let event = PLUGIN_MANAGER.fire::<BlockBreakEvent>(event).await;
 
-        if !event.cancelled {
+        if event.cancelled {
+            // The client already removed the block
+            if let Some(player) = &cause {
+                player
+                    .client
+                    .enqueue_packet(&CBlockUpdate::new(
+                        *position,
+                        i32::from(broken_block_state).into(),
+                    ))
+                    .await;
+            }
+        } else {
             let new_state_id = if broken_block
                 .properties(broken_block_state)
                 .and_then(|properties| {
@@ -2746,7 +2851,7 @@ impl World {
                     broken_state_id.into(),
                     false,
                 );
-                match cause {
+                match &cause {
                     Some(player) => {
                         self.broadcast_packet_except(&[player.gameprofile.id], &particles_packet)
                             .await;
@@ -2756,8 +2861,13 @@ impl World {
             }
 
             if !flags.contains(BlockFlags::SKIP_DROPS) {
+                let tool = match &cause {
+                    Some(player) => Some(player.inventory.held_item().lock().await.clone()),
+                    None => None,
+                };
                 let params = LootContextParameters {
                     block_state: Some(BlockState::from_id(broken_state_id)),
+                    tool,
                     ..Default::default()
                 };
                 block::drop_loot(self, broken_block, position, true, params).await;
@@ -2969,6 +3079,32 @@ impl World {
         (Block::from_state_id(id), id)
     }
 
+    /// Whether `player` is denied changing the block at `position` because it is too close to
+    /// the world spawn.
+    ///
+    /// Only the overworld spawn is protected, within the configured `spawn_protection` radius.
+    /// Players with the `pumpkin:bypass.spawn_protection` permission can change blocks anywhere.
+    pub async fn is_spawn_protected(&self, position: &BlockPos, player: &Player) -> bool {
+        let Some(server) = self.server.upgrade() else {
+            return false;
+        };
+        let radius = server.basic_config.spawn_protection;
+        if radius == 0 || self.dimension != Dimension::OVERWORLD {
+            return false;
+        }
+        let (spawn_x, spawn_z) = {
+            let level_info = self.level_info.read().await;
+            (level_info.spawn_x, level_info.spawn_z)
+        };
+        let distance = (position.0.x - spawn_x)
+            .unsigned_abs()
+            .max((position.0.z - spawn_z).unsigned_abs());
+        distance <= radius
+            && !player
+                .has_permission("pumpkin:bypass.spawn_protection")
+                .await
+    }
+
     /// Updates neighboring blocks of a block
     pub async fn update_neighbors(
         self: &Arc<Self>,
@@ -3086,11 +3222,14 @@ impl World {
         if let Some(nbt) = &block_entity_nbt {
             let mut bytes = Vec::new();
             to_bytes_unnamed(nbt, &mut bytes).unwrap();
-            self.broadcast_packet_all(&CBlockEntityData::new(
-                block_entity.get_position(),
-                VarInt(block_entity.get_id() as i32),
-                bytes.into_boxed_slice(),
-            ))
+            self.broadcast_block_packet(
+                block_pos.chunk_position(),
+                &CBlockEntityData::new(
+                    block_entity.get_position(),
+                    VarInt(block_entity.get_id() as i32),
+                    bytes.into_boxed_slice(),
+                ),
+            )
             .await;
         }
 
@@ -3098,6 +3237,65 @@ impl World {
         chunk.mark_dirty(true);
     }
 
+    /// Merges `nbt` into the block entity at `pos`, the position and id of the block entity can't be
+    /// changed.
+    pub async fn merge_block_entity_nbt(&self, pos: &BlockPos, nbt: &NbtCompound) {
+        let Some(block_entity) = self.get_block_entity(pos).await else {
+            return;
+        };
+        // `put` keeps existing values, so the given NBT has to be added first
+        let mut merged = NbtCompound::new();
+        for (key, value) in &nbt.child_tags {
+            if !matches!(key.as_str(), "id" | "x" | "y" | "z") {
+                merged.put(key, value.clone());
+            }
+        }
+        block_entity.write_internal(&mut merged).await;
+        if let Some(block_entity) = block_entity_from_nbt(&merged) {
+            self.add_block_entity(block_entity).await;
+        }
+    }
+
+    /// Places a structure template so that its `(0, 0, 0)` block ends up at `origin`. The mirror and
+    /// then the rotation turn the other blocks around that position, so with a rotation `origin` is
+    /// no longer the lowest corner. Entities of the structure are not spawned yet.
+    pub async fn place_structure(
+        self: &Arc<Self>,
+        origin: BlockPos,
+        structure: &StructureTemplate,
+        rotation: BlockRotation,
+        mirror: BlockMirror,
+    ) {
+        for block in &structure.blocks {
+            if Block::from_state_id(block.state) == &Block::STRUCTURE_VOID {
+                continue;
+            }
+            let offset = StructureTemplate::transform_pos(block.pos, rotation, mirror);
+            let pos = origin.offset(offset.0);
+            let state = transform_state(block.state, rotation, mirror);
+            self.set_block_state(
+                &pos,
+                state,
+                BlockFlags::NOTIFY_LISTENERS | BlockFlags::FORCE_STATE,
+            )
+            .await;
+
+            if let Some(nbt) = &block.nbt {
+                let mut nbt = nbt.clone();
+                nbt.put_int("x", pos.0.x);
+                nbt.put_int("y", pos.0.y);
+                nbt.put_int("z", pos.0.z);
+                if nbt.get_string("id").is_none() {
+                    log::warn!("Structure block entity at {pos:?} has no id, skipping it");
+                    continue;
+                }
+                if let Some(block_entity) = block_entity_from_nbt(&nbt) {
+                    self.add_block_entity(block_entity).await;
+                }
+            }
+        }
+    }
+
     pub async fn remove_block_entity(&self, block_pos: &BlockPos) {
         let chunk = self.level.get_chunk(block_pos.chunk_position()).await;
         let mut chunk: tokio::sync::RwLockWriteGuard<ChunkData> = chunk.write().await;
@@ -3114,11 +3312,14 @@ impl World {
         if let Some(nbt) = &block_entity_nbt {
             let mut bytes = Vec::new();
             to_bytes_unnamed(nbt, &mut bytes).unwrap();
-            self.broadcast_packet_all(&CBlockEntityData::new(
-                block_entity.get_position(),
-                VarInt(block_entity.get_id() as i32),
-                bytes.into_boxed_slice(),
-            ))
+            self.broadcast_block_packet(
+                block_pos.chunk_position(),
+                &CBlockEntityData::new(
+                    block_entity.get_position(),
+                    VarInt(block_entity.get_id() as i32),
+                    bytes.into_boxed_slice(),
+                ),
+            )
             .await;
         }
         chunk.mark_dirty(true);
@@ -3471,3 +3672,213 @@ impl BlockAccessor for World {
         Box::pin(async move { self.get_block_and_state(position).await })
     }
 }
+
+/// The chunks within `simulation_distance` chunks of any of the `centers`, like the chunks players
+/// are in.
+fn simulated_chunks(
+    centers: impl IntoIterator<Item = Vector2<i32>>,
+    simulation_distance: i32,
+) -> HashSet<Vector2<i32>> {
+    let mut chunks = HashSet::new();
+    for center in centers {
+        for dx in -simulation_distance..=simulation_distance {
+            for dz in -simulation_distance..=simulation_distance {
+                chunks.insert(center.add_raw(dx, dz));
+            }
+        }
+    }
+    chunks
+}
+
+fn system_message_packets(message: &TextComponent) -> (CSystemChatMessage<'_>, SText) {
+    (
+        CSystemChatMessage::new(message, false),
+        SText::system_message(message.clone().get_text()),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::block_properties::{
+        BlockProperties, ChestLikeProperties, HorizontalFacing, OakStairsLikeProperties,
+    };
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_protocol::{
+        bedrock::server::text::TextPacketType,
+        java::client::play::{CDisguisedChatMessage, CPlayerChatMessage, CSystemChatMessage},
+        packet::Packet,
+    };
+    use pumpkin_util::math::boundingbox::{BoundingBox, EntityDimensions};
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_util::text::TextComponent;
+
+    use pumpkin_util::math::vector2::Vector2;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use std::time::Duration;
+
+    use pumpkin_world::generation::structure::template::{
+        BlockMirror, BlockRotation, StructureBlockInfo, StructureTemplate,
+    };
+    use pumpkin_world::world::BlockFlags;
+
+    use super::{World, simulated_chunks, system_message_packets};
+    use crate::server::Server;
+
+    fn packet_id<P: Packet>(_packet: &P) -> i32 {
+        P::PACKET_ID
+    }
+
+    #[test]
+    fn system_broadcast_is_not_player_chat() {
+        let message = TextComponent::text("The server restarts in 5 minutes");
+        let (java, bedrock) = system_message_packets(&message);
+
+        assert_eq!(packet_id(&java), CSystemChatMessage::PACKET_ID);
+        assert_ne!(packet_id(&java), CPlayerChatMessage::PACKET_ID);
+        assert_ne!(packet_id(&java), CDisguisedChatMessage::PACKET_ID);
+        assert!(!java.overlay);
+
+        assert!(matches!(bedrock.r#type, TextPacketType::SystemMessage));
+        assert!(bedrock.player_name.is_empty());
+    }
+
+    #[test]
+    fn entities_touching_a_cactus_collide_with_it() {
+        let cactus = BlockPos::new(0, 64, 0);
+        let state = Block::CACTUS.default_state;
+        let size = EntityDimensions::new(0.6, 1.8, 1.62);
+
+        // Walked into the side of the cactus, which is inset by a pixel
+        let touching = BoundingBox::new_from_pos(-0.2375, 64.0, 0.5, &size);
+        assert!(World::check_contact(&touching, cactus, state));
+        // Standing in the next block over
+        let adjacent = BoundingBox::new_from_pos(-0.5, 64.0, 0.5, &size);
+        assert!(!World::check_contact(&adjacent, cactus, state));
+    }
+
+    #[test]
+    fn only_chunks_near_players_are_simulated() {
+        assert!(simulated_chunks(Vec::new(), 10).is_empty());
+
+        let chunks = simulated_chunks([Vector2::new(0, 0), Vector2::new(3, 0)], 2);
+        // Two overlapping 5x5 squares
+        assert_eq!(chunks.len(), 40);
+        assert!(chunks.contains(&Vector2::new(-2, 2)));
+        assert!(chunks.contains(&Vector2::new(5, -2)));
+        assert!(!chunks.contains(&Vector2::new(6, 0)));
+        assert!(!chunks.contains(&Vector2::new(0, 3)));
+    }
+
+    #[tokio::test]
+    async fn shutdown_counts_the_changed_chunks_it_saves() {
+        let (server, world_dir) = Server::new_for_test().await;
+        let world = server.worlds.read().await[0].clone();
+        for chunk_x in 0..3 {
+            for chunk_z in 0..3 {
+                world
+                    .set_block_state(
+                        &BlockPos::new(chunk_x * 16, 300, chunk_z * 16),
+                        Block::STONE.default_state.id,
+                        BlockFlags::empty(),
+                    )
+                    .await;
+            }
+        }
+
+        let mut reports = Vec::new();
+        world
+            .level
+            .save_progress
+            .report_while(
+                world.shutdown(),
+                Duration::from_millis(1),
+                |saved, total| {
+                    reports.push((saved, total));
+                },
+            )
+            .await;
+
+        let (saved, total) = world.level.save_progress.get();
+        assert!(total >= 9);
+        assert_eq!(saved, total);
+        // At least the changed chunks were dirty, chunks that were generated are too
+        let dirty = world.level.save_progress.dirty();
+        assert!((9..=total).contains(&dirty));
+        assert!(
+            reports
+                .iter()
+                .all(|&(saved, report_total)| saved < report_total)
+        );
+        assert!(world_dir.path().join("region").join("r.0.0.mca").exists());
+    }
+
+    #[tokio::test]
+    async fn rotated_structures_turn_around_the_origin() {
+        let (server, _world_dir) = Server::new_for_test().await;
+        let world = server.worlds.read().await[0].clone();
+
+        let mut stairs = OakStairsLikeProperties::default(&Block::OAK_STAIRS);
+        stairs.facing = HorizontalFacing::North;
+        let mut chest = ChestLikeProperties::default(&Block::CHEST);
+        chest.facing = HorizontalFacing::East;
+        let mut chest_nbt = NbtCompound::new();
+        chest_nbt.put_string("id", "minecraft:chest".to_string());
+        let structure = StructureTemplate {
+            size: Vector3::new(2, 1, 2),
+            blocks: vec![
+                StructureBlockInfo {
+                    pos: BlockPos::new(0, 0, 0),
+                    state: Block::STONE.default_state.id,
+                    nbt: None,
+                },
+                StructureBlockInfo {
+                    pos: BlockPos::new(1, 0, 0),
+                    state: stairs.to_state_id(&Block::OAK_STAIRS),
+                    nbt: None,
+                },
+                StructureBlockInfo {
+                    pos: BlockPos::new(1, 0, 1),
+                    state: chest.to_state_id(&Block::CHEST),
+                    nbt: Some(chest_nbt),
+                },
+            ],
+            entities: Vec::new(),
+        };
+
+        let origin = BlockPos::new(8, 300, 8);
+        world
+            .place_structure(
+                origin,
+                &structure,
+                BlockRotation::Clockwise90,
+                BlockMirror::None,
+            )
+            .await;
+
+        // A quarter turn clockwise maps (x, z) to (-z, x) around the origin
+        assert_eq!(world.get_block(&origin).await, &Block::STONE);
+        let stairs_pos = BlockPos::new(8, 300, 9);
+        let (block, state) = world.get_block_and_state_id(&stairs_pos).await;
+        assert_eq!(block, &Block::OAK_STAIRS);
+        assert_eq!(
+            OakStairsLikeProperties::from_state_id(state, block).facing,
+            HorizontalFacing::East
+        );
+        let chest_pos = BlockPos::new(7, 300, 9);
+        let (block, state) = world.get_block_and_state_id(&chest_pos).await;
+        assert_eq!(block, &Block::CHEST);
+        assert_eq!(
+            ChestLikeProperties::from_state_id(state, block).facing,
+            HorizontalFacing::South
+        );
+        let chest_entity = world.get_block_entity(&chest_pos).await.unwrap();
+        assert_eq!(chest_entity.get_position(), chest_pos);
+
+        // Nothing ends up where the unrotated template would have put its blocks
+        for pos in [BlockPos::new(9, 300, 8), BlockPos::new(9, 300, 9)] {
+            assert!(world.get_block_state(&pos).await.is_air());
+        }
+    }
+}
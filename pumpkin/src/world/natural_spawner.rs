@@ -1,6 +1,8 @@
 use crate::entity::EntityBase;
 use crate::entity::r#type::from_type;
 use crate::world::World;
+use crate::world::entity_limits::{EntityKind, room_in_chunk};
+use pumpkin_config::world::MobCapsConfig;
 use pumpkin_data::biome::Spawner;
 use pumpkin_data::entity::{EntityType, MobCategory, SpawnLocation};
 use pumpkin_data::tag::Block::MINECRAFT_PREVENT_MOB_SPAWNING_INSIDE;
@@ -39,8 +41,51 @@ impl MobCounts {
         self.0[category.id] += 1;
     }
     #[inline]
-    const fn can_spawn(&self, category: &'static MobCategory) -> bool {
-        self.0[category.id] < category.max
+    const fn can_spawn(&self, category: &'static MobCategory, caps: &MobCaps) -> bool {
+        self.0[category.id] < caps.get(category)
+    }
+}
+
+/// The most mobs of each category that may be around a player, indexed by the category id.
+#[derive(Clone, Copy, Debug)]
+pub struct MobCaps([i32; 8]);
+
+impl MobCaps {
+    #[must_use]
+    pub fn from_config(config: &MobCapsConfig) -> Self {
+        let mut caps = Self::default();
+        for (category, cap) in [
+            (&MobCategory::MONSTER, config.monster),
+            (&MobCategory::CREATURE, config.creature),
+            (&MobCategory::AMBIENT, config.ambient),
+            (&MobCategory::AXOLOTLS, config.axolotls),
+            (
+                &MobCategory::UNDERGROUND_WATER_CREATURE,
+                config.underground_water_creature,
+            ),
+            (&MobCategory::WATER_CREATURE, config.water_creature),
+            (&MobCategory::WATER_AMBIENT, config.water_ambient),
+        ] {
+            caps.0[category.id] = i32::try_from(cap).unwrap_or(i32::MAX);
+        }
+        caps
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn get(&self, category: &'static MobCategory) -> i32 {
+        self.0[category.id]
+    }
+}
+
+impl Default for MobCaps {
+    /// The vanilla mob caps.
+    fn default() -> Self {
+        let mut caps = [0; 8];
+        for category in MobCategory::SPAWNING_CATEGORIES {
+            caps[category.id] = category.max;
+        }
+        Self(caps)
     }
 }
 
@@ -109,13 +154,14 @@ impl LocalMobCapCalculator {
     pub async fn can_spawn(
         &mut self,
         category: &'static MobCategory,
+        caps: &MobCaps,
         world: &Arc<World>,
         chunk_pos: &Vector2<i32>,
     ) -> bool {
         let players = Self::get_players_near(&mut self.players_near_chunk, world, chunk_pos).await;
         for player in players {
             if let Some(count) = self.player_mob_counts.get(player) {
-                if count.can_spawn(category) {
+                if count.can_spawn(category, caps) {
                     return true;
                 }
             } else {
@@ -166,6 +212,7 @@ pub struct SpawnState {
     last_checked_pos: BlockPos,
     last_checked_type: &'static EntityType,
     last_charge: f64,
+    mob_caps: MobCaps,
 }
 
 impl fmt::Debug for SpawnState {
@@ -178,6 +225,7 @@ impl fmt::Debug for SpawnState {
             .field("last_checked_pos", &self.last_checked_pos)
             .field("last_checked_type", &self.last_checked_type.resource_name)
             .field("last_charge", &self.last_charge)
+            .field("mob_caps", &self.mob_caps)
             .finish()
     }
 }
@@ -187,6 +235,7 @@ impl SpawnState {
         chunk_count: i32,
         entities: &Arc<RwLock<HashMap<Uuid, Arc<dyn EntityBase>>>>,
         world: &Arc<World>,
+        mob_caps: MobCaps,
     ) -> Self {
         let mut potential = PotentialCalculator::default();
         let mut local_mob_cap = LocalMobCapCalculator::default();
@@ -219,12 +268,13 @@ impl SpawnState {
             last_checked_pos: BlockPos::new(i32::MAX, i32::MAX, i32::MAX),
             last_checked_type: &EntityType::PLAYER,
             last_charge: 0.,
+            mob_caps,
         }
     }
     #[inline]
     fn can_spawn_for_category_global(&self, category: &'static MobCategory) -> bool {
         self.mob_category_counts.0[category.id]
-            < category.max * self.spawnable_chunk_count / MAGIC_NUMBER
+            < self.mob_caps.get(category) * self.spawnable_chunk_count / MAGIC_NUMBER
     }
     async fn can_spawn_for_category_local(
         &mut self,
@@ -232,9 +282,12 @@ impl SpawnState {
         category: &'static MobCategory,
         chunk_pos: &Vector2<i32>,
     ) -> bool {
-        self.local_mob_cap_calculator
-            .can_spawn(category, world, chunk_pos)
-            .await
+        // A cap of 0 disables the category, even for players without any mobs around
+        self.mob_caps.get(category) > 0
+            && self
+                .local_mob_cap_calculator
+                .can_spawn(category, &self.mob_caps, world, chunk_pos)
+                .await
     }
     async fn can_spawn(
         &mut self,
@@ -434,39 +487,44 @@ pub async fn spawn_category_for_position(
         }
     }
 
-    // Spawn in batch
-    if !batch_buffer.is_empty() {
-        let mut prepared_data = Vec::with_capacity(batch_buffer.len());
-
-        for entity in &batch_buffer {
-            entity.init_data_tracker().await;
-            let base_entity = entity.get_entity();
-            let packet = base_entity.create_spawn_packet();
-            let mut nbt = NbtCompound::new();
-            entity.write_nbt(&mut nbt).await;
-            prepared_data.push((base_entity.entity_uuid, nbt, packet));
+    // Full chunks take no more mobs
+    if let Some(server) = world.server.upgrade() {
+        let limits = &server.advanced_config.world.entity_limits;
+        if let Some(room) = room_in_chunk(world, limits, *chunk_pos, EntityKind::Mob).await {
+            batch_buffer.truncate(room);
         }
-        {
-            let chunk_handle = world.level.get_entity_chunk(*chunk_pos).await;
-            let mut chunk_lock = chunk_handle.write().await;
-            let mut entities_lock = world.entities.write().await;
-
-            for (uuid, nbt, _) in &prepared_data {
-                let entity_ref = batch_buffer
-                    .iter()
-                    .find(|e| e.get_entity().entity_uuid == *uuid)
-                    .unwrap();
-
-                entities_lock.insert(*uuid, entity_ref.clone());
-                chunk_lock.data.insert(*uuid, nbt.clone());
-            }
-            chunk_lock.mark_dirty(true);
-        };
+    }
 
-        for (_, _, packet) in prepared_data {
-            world.broadcast_packet_all(&packet).await;
-        }
+    add_spawned_mobs(world, chunk_pos, batch_buffer).await;
+}
+
+/// Adds naturally spawned mobs to the world and their entity chunk in one batch. Players spawn
+/// them once they are in their tracking range, see [`crate::world::tracking`].
+pub async fn add_spawned_mobs(
+    world: &World,
+    chunk_pos: &Vector2<i32>,
+    mobs: Vec<Arc<dyn EntityBase>>,
+) {
+    if mobs.is_empty() {
+        return;
     }
+    let mut prepared_data = Vec::with_capacity(mobs.len());
+    for entity in mobs {
+        entity.init_data_tracker().await;
+        let mut nbt = NbtCompound::new();
+        entity.write_nbt(&mut nbt).await;
+        prepared_data.push((entity, nbt));
+    }
+
+    let chunk_handle = world.level.get_entity_chunk(*chunk_pos).await;
+    let mut chunk_lock = chunk_handle.write().await;
+    let mut entities_lock = world.entities.write().await;
+    for (entity, nbt) in prepared_data {
+        let uuid = entity.get_entity().entity_uuid;
+        chunk_lock.data.insert(uuid, nbt);
+        entities_lock.insert(uuid, entity);
+    }
+    chunk_lock.mark_dirty(true);
 }
 
 #[must_use]
@@ -608,3 +666,106 @@ pub fn is_valid_empty_spawn_block(state: &'static BlockState) -> bool {
     // TODO !entityType.isBlockDangerous(blockState);
     !Block::from_state_id(state.id).has_tag(&MINECRAFT_PREVENT_MOB_SPAWNING_INSIDE)
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::world::MobCapsConfig;
+    use pumpkin_data::entity::{EntityType, MobCategory};
+    use pumpkin_protocol::java::client::play::CSpawnEntity;
+    use pumpkin_protocol::packet::Packet;
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_util::math::vector2::Vector2;
+    use pumpkin_util::math::vector3::Vector3;
+    use uuid::Uuid;
+
+    use super::{
+        LocalMobCapCalculator, MAGIC_NUMBER, MobCaps, MobCounts, PotentialCalculator, SpawnState,
+        add_spawned_mobs, get_filtered_spawning_categories,
+    };
+    use crate::entity::player::Player;
+    use crate::entity::r#type::from_type;
+    use crate::net::java::queued_packet_ids;
+    use crate::server::Server;
+    use crate::world::tracking::update_tracked_entities;
+
+    fn spawn_state(mob_caps: MobCaps) -> SpawnState {
+        SpawnState {
+            spawnable_chunk_count: MAGIC_NUMBER,
+            mob_category_counts: MobCounts::default(),
+            spawn_potential: PotentialCalculator::default(),
+            local_mob_cap_calculator: LocalMobCapCalculator::default(),
+            last_checked_pos: BlockPos::new(i32::MAX, i32::MAX, i32::MAX),
+            last_checked_type: &EntityType::PLAYER,
+            last_charge: 0.,
+            mob_caps,
+        }
+    }
+
+    #[test]
+    fn mob_caps_limit_spawning_categories() {
+        let config = MobCapsConfig::default();
+        let caps = MobCaps::from_config(&config);
+        for category in MobCategory::SPAWNING_CATEGORIES {
+            assert_eq!(caps.get(category), MobCaps::default().get(category));
+        }
+        assert_eq!(caps.get(&MobCategory::MONSTER), MobCategory::MONSTER.max);
+
+        let categories = get_filtered_spawning_categories(&spawn_state(caps), true, true, true);
+        assert!(categories.contains(&&MobCategory::MONSTER));
+
+        let config = MobCapsConfig {
+            monster: 0,
+            ..MobCapsConfig::default()
+        };
+        let mut state = spawn_state(MobCaps::from_config(&config));
+        let categories = get_filtered_spawning_categories(&state, true, true, true);
+        assert!(!categories.contains(&&MobCategory::MONSTER));
+        assert!(categories.contains(&&MobCategory::CREATURE));
+
+        // A full category stops spawning
+        for _ in 0..MobCategory::CREATURE.max {
+            state.mob_category_counts.add(&MobCategory::CREATURE);
+        }
+        let categories = get_filtered_spawning_categories(&state, true, true, true);
+        assert!(!categories.contains(&&MobCategory::CREATURE));
+    }
+
+    #[tokio::test]
+    async fn spawned_mobs_only_reach_players_tracking_them() {
+        let (server, _world_dir) = Server::new_for_test().await;
+        let world = server.worlds.read().await[0].clone();
+        let (near, mut near_packets) = Player::new_for_test(world.clone(), "Near").await;
+        let (far, mut far_packets) = Player::new_for_test(world.clone(), "Far").await;
+        far.living_entity
+            .entity
+            .set_pos(Vector3::new(10_000.0, 100.0, 0.0));
+
+        let zombie = from_type(
+            &EntityType::ZOMBIE,
+            Vector3::new(8.0, 100.0, 8.0),
+            &world,
+            Uuid::new_v4(),
+        )
+        .await;
+        add_spawned_mobs(&world, &Vector2::new(0, 0), vec![zombie]).await;
+        let spawns = |ids: Vec<i32>| {
+            ids.into_iter()
+                .filter(|id| *id == CSpawnEntity::PACKET_ID)
+                .count()
+        };
+        // Nobody gets the mob before the tracking update
+        assert_eq!(spawns(queued_packet_ids(&mut near_packets)), 0);
+        assert_eq!(spawns(queued_packet_ids(&mut far_packets)), 0);
+
+        let players = [near.clone(), far.clone()];
+        let entities: Vec<_> = world.entities.read().await.values().cloned().collect();
+        let ranges = &server.advanced_config.world.entity_tracking;
+        for _ in 0..2 {
+            for player in &players {
+                update_tracked_entities(ranges, player, &players, &entities).await;
+            }
+        }
+        assert_eq!(spawns(queued_packet_ids(&mut near_packets)), 1);
+        assert_eq!(spawns(queued_packet_ids(&mut far_packets)), 0);
+    }
+}
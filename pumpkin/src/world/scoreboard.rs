@@ -1,19 +1,23 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
 use pumpkin_protocol::{
     NumberFormat,
     codec::var_int::VarInt,
-    java::client::play::{CDisplayObjective, CUpdateObjectives, CUpdateScore, RenderType},
+    java::client::play::{
+        CDisplayObjective, CSetPlayerTeam, CUpdateObjectives, CUpdateScore, CollisionRule,
+        NameTagVisibility, RenderType, TeamInfo, TeamMethod,
+    },
 };
-use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
 
 use super::World;
+use crate::entity::player::Player;
+use crate::server::Server;
 
 #[derive(Default)]
 pub struct Scoreboard {
     objectives: HashMap<String, ScoreboardObjective<'static>>,
-    //  teams: HashMap<String, Team>,
 }
 
 impl Scoreboard {
@@ -61,13 +65,202 @@ impl Scoreboard {
             ))
             .await;
     }
+}
 
-    // pub fn add_team(&mut self, name: String) {
-    //     if self.teams.contains_key(&name) {
-    //         // Maybe make this an error ?
-    //         log::warn!("Tried to create Team which does already exist, {}", name);
-    //     }
-    // }
+/// The teams of the server, they are shared by all worlds like in vanilla.
+#[derive(Default)]
+pub struct Teams {
+    teams: HashMap<String, Team>,
+    /// The team of each member.
+    member_teams: HashMap<String, String>,
+}
+
+impl Teams {
+    /// Adds `team` without members, returns `false` if a team with its name exists already.
+    pub async fn add_team(&mut self, server: &Server, team: Team) -> bool {
+        if self.teams.contains_key(&team.name) {
+            return false;
+        }
+        server
+            .broadcast_packet_all(&CSetPlayerTeam::new(
+                &team.name,
+                TeamMethod::Create(team.info(), &[]),
+            ))
+            .await;
+        self.teams.insert(team.name.clone(), team);
+        true
+    }
+
+    /// Removes the team and with it the team of its members.
+    pub async fn remove_team(&mut self, server: &Server, name: &str) -> Option<Team> {
+        let team = self.teams.remove(name)?;
+        for member in &team.members {
+            self.member_teams.remove(member);
+        }
+        server
+            .broadcast_packet_all(&CSetPlayerTeam::new(name, TeamMethod::Remove))
+            .await;
+        Some(team)
+    }
+
+    /// Changes the display of a team, returns `false` if there is no such team.
+    pub async fn update_team(
+        &mut self,
+        server: &Server,
+        name: &str,
+        update: impl FnOnce(&mut Team),
+    ) -> bool {
+        let Some(team) = self.teams.get_mut(name) else {
+            return false;
+        };
+        update(team);
+        server
+            .broadcast_packet_all(&CSetPlayerTeam::new(name, TeamMethod::Update(team.info())))
+            .await;
+        true
+    }
+
+    /// Adds `member` to the team, after leaving its previous team. Returns `false` if there is no
+    /// such team.
+    pub async fn join_team(&mut self, server: &Server, name: &str, member: String) -> bool {
+        if !self.teams.contains_key(name) {
+            return false;
+        }
+        self.leave_team(server, &member).await;
+        server
+            .broadcast_packet_all(&CSetPlayerTeam::new(
+                name,
+                TeamMethod::AddMembers(&[member.clone()]),
+            ))
+            .await;
+        if let Some(team) = self.teams.get_mut(name) {
+            team.members.insert(member.clone());
+        }
+        self.member_teams.insert(member, name.to_string());
+        true
+    }
+
+    /// Removes `member` from its team, returns `false` if it wasn't in one.
+    pub async fn leave_team(&mut self, server: &Server, member: &str) -> bool {
+        let Some(name) = self.member_teams.remove(member) else {
+            return false;
+        };
+        if let Some(team) = self.teams.get_mut(&name) {
+            team.members.remove(member);
+        }
+        server
+            .broadcast_packet_all(&CSetPlayerTeam::new(
+                &name,
+                TeamMethod::RemoveMembers(&[member.to_string()]),
+            ))
+            .await;
+        true
+    }
+
+    #[must_use]
+    pub fn get_team(&self, name: &str) -> Option<&Team> {
+        self.teams.get(name)
+    }
+
+    /// The team of a player name or entity UUID.
+    #[must_use]
+    pub fn team_of(&self, member: &str) -> Option<&Team> {
+        self.teams.get(self.member_teams.get(member)?)
+    }
+
+    pub fn teams(&self) -> impl Iterator<Item = &Team> {
+        self.teams.values()
+    }
+
+    /// Sends all teams to a player joining the server.
+    pub async fn send_teams(&self, player: &Player) {
+        for team in self.teams.values() {
+            let members: Vec<String> = team.members.iter().cloned().collect();
+            player
+                .client
+                .enqueue_packet(&CSetPlayerTeam::new(
+                    &team.name,
+                    TeamMethod::Create(team.info(), &members),
+                ))
+                .await;
+        }
+    }
+
+    /// Whether `attacker` may hurt `victim`, which only teams without friendly fire prevent.
+    /// `PlayerEntity.canHarmPlayer()`
+    #[must_use]
+    pub fn can_hurt(&self, attacker: &str, victim: &str) -> bool {
+        match (self.team_of(attacker), self.team_of(victim)) {
+            (Some(team), Some(other)) => team.name != other.name || team.friendly_fire,
+            _ => true,
+        }
+    }
+}
+
+/// A group of players and entities, which share how their names are shown and whether they hurt
+/// and push each other.
+pub struct Team {
+    pub name: String,
+    pub display_name: TextComponent,
+    /// The color of the member names, `None` keeps their default color.
+    pub color: Option<NamedColor>,
+    pub prefix: TextComponent,
+    pub suffix: TextComponent,
+    pub friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+    pub name_tag_visibility: NameTagVisibility,
+    pub collision_rule: CollisionRule,
+    /// Player names and the UUIDs of other entities.
+    pub members: HashSet<String>,
+}
+
+impl Team {
+    #[must_use]
+    pub fn new(name: String, display_name: TextComponent) -> Self {
+        Self {
+            name,
+            display_name,
+            color: None,
+            prefix: TextComponent::text(""),
+            suffix: TextComponent::text(""),
+            friendly_fire: true,
+            see_friendly_invisibles: true,
+            name_tag_visibility: NameTagVisibility::Always,
+            collision_rule: CollisionRule::Always,
+            members: HashSet::new(),
+        }
+    }
+
+    fn info(&self) -> TeamInfo<'_> {
+        TeamInfo {
+            display_name: &self.display_name,
+            friendly_fire: self.friendly_fire,
+            see_friendly_invisibles: self.see_friendly_invisibles,
+            name_tag_visibility: self.name_tag_visibility,
+            collision_rule: self.collision_rule,
+            color: self.color,
+            prefix: &self.prefix,
+            suffix: &self.suffix,
+        }
+    }
+}
+
+/// Whether the members of `team` and `other` push each other, both are `None` for entities
+/// without a team. `EntityPredicates.canBePushedBy()`
+#[must_use]
+pub fn teams_push(team: Option<&Team>, other: Option<&Team>) -> bool {
+    let rule = team.map_or(CollisionRule::Always, |team| team.collision_rule);
+    let other_rule = other.map_or(CollisionRule::Always, |team| team.collision_rule);
+    if rule == CollisionRule::Never || other_rule == CollisionRule::Never {
+        return false;
+    }
+    let same_team = team.is_some_and(|team| other.is_some_and(|other| team.name == other.name));
+    let rules = [rule, other_rule];
+    if same_team {
+        !rules.contains(&CollisionRule::PushOwnTeam)
+    } else {
+        !rules.contains(&CollisionRule::PushOtherTeams)
+    }
 }
 
 pub struct ScoreboardObjective<'a> {
@@ -120,3 +313,39 @@ impl<'a> ScoreboardScore<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::java::client::play::CollisionRule;
+    use pumpkin_util::text::TextComponent;
+
+    use super::{Team, teams_push};
+
+    fn team(name: &str, collision_rule: CollisionRule) -> Team {
+        let mut team = Team::new(name.to_string(), TextComponent::text(name.to_string()));
+        team.collision_rule = collision_rule;
+        team
+    }
+
+    #[test]
+    fn collision_rules_decide_who_pushes() {
+        let red = team("red", CollisionRule::Always);
+        let blue = team("blue", CollisionRule::Always);
+        assert!(teams_push(None, None));
+        assert!(teams_push(Some(&red), Some(&red)));
+        assert!(teams_push(Some(&red), Some(&blue)));
+
+        let ghosts = team("ghosts", CollisionRule::Never);
+        assert!(!teams_push(Some(&ghosts), None));
+        assert!(!teams_push(Some(&red), Some(&ghosts)));
+
+        // Like vanilla, the rules keep their swapped meaning
+        let own = team("own", CollisionRule::PushOwnTeam);
+        assert!(!teams_push(Some(&own), Some(&own)));
+        assert!(teams_push(Some(&own), Some(&red)));
+        let other = team("other", CollisionRule::PushOtherTeams);
+        assert!(teams_push(Some(&other), Some(&other)));
+        assert!(!teams_push(Some(&other), Some(&red)));
+        assert!(!teams_push(Some(&other), None));
+    }
+}
@@ -34,15 +34,27 @@ impl LevelTime {
         }
     }
 
+    /// Sends the time to the players of both editions in `world`.
     pub async fn send_time(&self, world: &World) {
+        let advance_time = world.level_info.read().await.game_rules.advance_time;
         world
-            .broadcast_editioned(
-                &CUpdateTime::new(self.world_age, self.time_of_day, true),
-                &CSetTime::new(self.time_of_day as _),
-            )
+            .broadcast_editioned(&self.java_packet(advance_time), &self.bedrock_packet())
             .await;
     }
 
+    /// The time for Java clients, which keep advancing it on their own if `advance_time` is set.
+    #[must_use]
+    pub fn java_packet(&self, advance_time: bool) -> CUpdateTime {
+        CUpdateTime::new(self.world_age, self.time_of_day, advance_time)
+    }
+
+    /// The time for Bedrock clients. Like for Java this is the time of day including the days
+    /// passed, so the moon phase matches.
+    #[must_use]
+    pub const fn bedrock_packet(&self) -> CSetTime {
+        CSetTime::new(self.time_of_day as _)
+    }
+
     pub fn add_time(&mut self, time: i64) {
         self.time_of_day += time;
     }
@@ -66,6 +78,14 @@ impl LevelTime {
         self.time_of_day / 24000
     }
 
+    /// The angle of the sun in the sky, 0.0 at noon and 0.5 at midnight.
+    #[must_use]
+    pub fn sky_angle(&self) -> f32 {
+        let progress = (self.time_of_day as f64 / 24000.0 - 0.25).rem_euclid(1.0);
+        let smoothed = 0.5 - (progress * std::f64::consts::PI).cos() / 2.0;
+        ((progress * 2.0 + smoothed) / 3.0) as f32
+    }
+
     #[must_use]
     pub fn is_night(&self) -> bool {
         (self.time_of_day % 24000) >= 12000 && (self.time_of_day % 24000) <= 23999
@@ -0,0 +1,260 @@
+//! Entities are only spawned for the clients of players which have them in their tracking range,
+//! and removed again once they leave it. Each player keeps the ids of the entities its client
+//! knows about in [`Player::tracked_entities`], updates of an entity are only sent to those
+//! players, see [`World::broadcast_to_trackers`](super::World::broadcast_to_trackers).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use pumpkin_config::world::EntityTrackingConfig;
+use pumpkin_data::data_component_impl::EquipmentSlot;
+use pumpkin_data::entity::{EntityType, MobCategory};
+use pumpkin_protocol::codec::item_stack_seralizer::ItemStackSerializer;
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::java::client::play::{
+    CRemoveEntities, CSetEntityMetadata, CSetEquipment, CSetPassengers,
+};
+use pumpkin_util::math::vector3::Vector3;
+
+use crate::entity::EntityBase;
+use crate::entity::player::Player;
+use crate::world::chunker::get_view_distance;
+
+/// The entities a player's client knows about.
+#[derive(Default)]
+pub struct TrackedEntities {
+    /// The ids of the tracked entities, with the update they were last in range in
+    entities: HashMap<i32, u32>,
+    /// Counts the tracking updates, so entities which left the range don't have to be collected
+    /// in a new set every tick
+    update: u32,
+}
+
+impl TrackedEntities {
+    #[must_use]
+    pub fn contains(&self, entity_id: i32) -> bool {
+        self.entities.contains_key(&entity_id)
+    }
+
+    /// Stops tracking the entity, returns whether it was tracked.
+    pub fn remove(&mut self, entity_id: i32) -> bool {
+        self.entities.remove(&entity_id).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.entities.clear();
+    }
+
+    /// Starts a tracking update, every entity which is still in range has to be seen again.
+    const fn start_update(&mut self) {
+        self.update = self.update.wrapping_add(1);
+    }
+
+    /// Marks the entity as in range during this update, returns whether the client doesn't know
+    /// about it yet.
+    fn see(&mut self, entity_id: i32) -> bool {
+        self.entities.insert(entity_id, self.update).is_none()
+    }
+
+    /// Stops tracking the entities which were not seen during this update and appends them to
+    /// `removed`.
+    fn finish_update(&mut self, removed: &mut Vec<VarInt>) {
+        let update = self.update;
+        self.entities.retain(|entity_id, seen| {
+            if *seen != update {
+                removed.push(VarInt(*entity_id));
+            }
+            *seen == update
+        });
+    }
+}
+
+/// How close `entity_type` has to be to a player to be spawned for them, in blocks.
+#[must_use]
+pub fn tracking_range(config: &EntityTrackingConfig, entity_type: &EntityType) -> f64 {
+    let range = if entity_type == &EntityType::PLAYER {
+        config.players
+    } else if entity_type == &EntityType::ITEM || entity_type == &EntityType::EXPERIENCE_ORB {
+        config.items
+    } else if entity_type.category != &MobCategory::MISC {
+        config.mobs
+    } else {
+        config.other
+    };
+    f64::from(range)
+}
+
+/// Whether an entity at `pos` is in the tracking range of a player at `viewer`. The range is a
+/// square around the player, the height doesn't matter.
+#[must_use]
+pub fn in_range(viewer: Vector3<f64>, pos: Vector3<f64>, range: f64) -> bool {
+    (pos.x - viewer.x).abs() <= range && (pos.z - viewer.z).abs() <= range
+}
+
+/// Spawns the entities which came into the tracking range of `player` and removes the ones which
+/// left it or the world. Ranges are never larger than the player's view distance.
+pub async fn update_tracked_entities(
+    config: &EntityTrackingConfig,
+    player: &Player,
+    players: &[Arc<Player>],
+    entities: &[Arc<dyn EntityBase>],
+) {
+    let viewer = player.living_entity.entity.pos.load();
+    let max_range = f64::from(get_view_distance(player).await.get()) * 16.0;
+    let candidates = players
+        .iter()
+        .filter(|other| other.entity_id() != player.entity_id())
+        .map(|other| other.as_ref() as &dyn EntityBase)
+        .chain(entities.iter().map(AsRef::as_ref));
+
+    let mut tracked = player.tracked_entities.lock().await;
+    tracked.start_update();
+    let mut spawned = Vec::new();
+    for entity in candidates {
+        let base = entity.get_entity();
+        let range = tracking_range(config, base.entity_type).min(max_range);
+        if in_range(viewer, base.pos.load(), range) && tracked.see(base.entity_id) {
+            spawned.push(entity);
+        }
+    }
+    let mut removed = Vec::new();
+    tracked.finish_update(&mut removed);
+
+    if !removed.is_empty() {
+        player
+            .client
+            .enqueue_packet(&CRemoveEntities::new(&removed))
+            .await;
+    }
+    for entity in &spawned {
+        spawn_for(player, *entity).await;
+    }
+
+    // Riders can only be mounted once the client knows both entities
+    let mut vehicles = HashMap::new();
+    for entity in &spawned {
+        let base = entity.get_entity();
+        if base.has_passengers().await {
+            vehicles.insert(base.entity_id, base.get_passengers().await);
+        }
+        if let Some(vehicle) = base.get_vehicle().await {
+            let vehicle = vehicle.get_entity();
+            if tracked.contains(vehicle.entity_id) {
+                vehicles.insert(vehicle.entity_id, vehicle.get_passengers().await);
+            }
+        }
+    }
+    for (id, passengers) in vehicles {
+        let passenger_ids: Vec<VarInt> = passengers
+            .iter()
+            .map(|passenger| passenger.get_entity().entity_id.into())
+            .collect();
+        player
+            .client
+            .enqueue_packet(&CSetPassengers::new(id.into(), &passenger_ids))
+            .await;
+    }
+}
+
+/// Removes the entity from the clients of all players which track it, like when it is removed
+/// from the world.
+pub async fn untrack(players: &[Arc<Player>], entity_id: i32) {
+    for player in players {
+        if player.tracked_entities.lock().await.remove(entity_id) {
+            player
+                .client
+                .enqueue_packet(&CRemoveEntities::new(&[entity_id.into()]))
+                .await;
+        }
+    }
+}
+
+async fn spawn_for(player: &Player, entity: &dyn EntityBase) {
+    let base = entity.get_entity();
+    player
+        .client
+        .enqueue_packet(&base.create_spawn_packet())
+        .await;
+    // New viewers get all metadata, not only the latest changes
+    if let Some(buf) = base.full_meta_data().await {
+        player
+            .client
+            .enqueue_packet(&CSetEntityMetadata::new(base.entity_id.into(), buf.into()))
+            .await;
+    }
+
+    let Some(living) = entity.get_living_entity() else {
+        return;
+    };
+    let mut equipment = Vec::new();
+    if let Some(holder) = entity.get_player() {
+        let held = holder.inventory.held_item().lock().await.clone();
+        equipment.push((EquipmentSlot::MAIN_HAND.discriminant(), held));
+    }
+    for (slot, stack) in &living.entity_equipment.lock().await.equipment {
+        let stack = stack.lock().await.clone();
+        if !stack.is_empty() {
+            equipment.push((slot.discriminant(), stack));
+        }
+    }
+    if !equipment.is_empty() {
+        let equipment = equipment
+            .into_iter()
+            .map(|(slot, stack)| (slot, ItemStackSerializer::from(stack)))
+            .collect();
+        player
+            .client
+            .enqueue_packet(&CSetEquipment::new(base.entity_id.into(), equipment))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::world::EntityTrackingConfig;
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_protocol::codec::var_int::VarInt;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{TrackedEntities, in_range, tracking_range};
+
+    #[test]
+    fn entities_are_tracked_within_their_range() {
+        let config = EntityTrackingConfig {
+            players: 48,
+            mobs: 40,
+            items: 16,
+            other: 64,
+        };
+        assert!((tracking_range(&config, &EntityType::PLAYER) - 48.0).abs() < f64::EPSILON);
+        assert!((tracking_range(&config, &EntityType::ZOMBIE) - 40.0).abs() < f64::EPSILON);
+        assert!((tracking_range(&config, &EntityType::ITEM) - 16.0).abs() < f64::EPSILON);
+        assert!((tracking_range(&config, &EntityType::ARROW) - 64.0).abs() < f64::EPSILON);
+
+        let viewer = Vector3::new(0.0, 64.0, 0.0);
+        assert!(in_range(viewer, Vector3::new(16.0, 200.0, -16.0), 16.0));
+        assert!(!in_range(viewer, Vector3::new(16.5, 64.0, 0.0), 16.0));
+    }
+
+    #[test]
+    fn entities_leaving_the_range_are_removed() {
+        let mut tracked = TrackedEntities::default();
+        let mut update = |visible: &[i32]| {
+            tracked.start_update();
+            let spawned: Vec<i32> = visible
+                .iter()
+                .copied()
+                .filter(|id| tracked.see(*id))
+                .collect();
+            let mut removed = Vec::new();
+            tracked.finish_update(&mut removed);
+            (spawned, removed)
+        };
+
+        assert_eq!(update(&[1, 2, 3]), (vec![1, 2, 3], Vec::new()));
+        assert_eq!(update(&[2, 3, 4]), (vec![4], vec![VarInt(1)]));
+        assert_eq!(update(&[2, 3, 4]), (Vec::new(), Vec::new()));
+        assert_eq!(update(&[]).0, Vec::<i32>::new());
+        assert!(!tracked.contains(2));
+    }
+}
@@ -1,5 +1,8 @@
 use super::World;
+use pumpkin_data::dimension::Dimension;
+use pumpkin_protocol::bedrock::client::level_event::CLevelEvent;
 use pumpkin_protocol::java::client::play::{CGameEvent, GameEvent};
+use pumpkin_util::math::vector3::Vector3;
 use rand::Rng;
 
 // Weather timing constants
@@ -13,6 +16,29 @@ const THUNDER_DURATION_MIN: i32 = 3_600;
 const THUNDER_DURATION_MAX: i32 = 15_600;
 
 const WEATHER_TRANSITION_SPEED: f32 = 0.01;
+/// Rain level above which clients render rain
+const RAINING_THRESHOLD: f32 = 0.2;
+/// Intensity Bedrock clients fade the rain or thunder to after a start event
+const BEDROCK_FULL_INTENSITY: i32 = 65535;
+
+/// Whether rain and thunder happen in a dimension, like vanilla only dimensions with skylight
+/// have weather, so the nether and the end never do.
+#[must_use]
+pub fn has_weather(dimension: &Dimension) -> bool {
+    dimension.has_skylight
+}
+
+/// What changed during a weather tick, `None` if the clients don't need to be told.
+#[derive(Default, PartialEq, Debug)]
+struct WeatherChanges {
+    raining: Option<bool>,
+    rain_level: Option<f32>,
+    thunder_level: Option<f32>,
+    /// Bedrock clients fade the weather on their own, so they are only told when the rain or
+    /// thunder starts or stops.
+    rain_started: Option<bool>,
+    thunder_started: Option<bool>,
+}
 
 pub struct Weather {
     pub clear_weather_time: i32,
@@ -20,6 +46,9 @@ pub struct Weather {
     pub rain_time: i32,
     pub thundering: bool,
     pub thunder_time: i32,
+    /// The targets of the previous tick, to tell when the rain or thunder starts or stops
+    pub old_raining: bool,
+    pub old_thundering: bool,
 
     pub rain_level: f32,
     pub old_rain_level: f32,
@@ -44,6 +73,8 @@ impl Weather {
             rain_time: 0,
             thundering: false,
             thunder_time: 0,
+            old_raining: false,
+            old_thundering: false,
             rain_level: 0.0,
             old_rain_level: 0.0,
             thunder_level: 0.0,
@@ -52,78 +83,137 @@ impl Weather {
         }
     }
 
-    pub async fn set_weather_parameters(
+    /// Sets the target weather state. `rain_level` and `thunder_level` are moved towards it
+    /// over the following ticks by [`Weather::tick_weather`].
+    pub fn set_weather_parameters(
         &mut self,
-        world: &World,
         clear_time: i32,
         rain_time: i32,
         raining: bool,
         thundering: bool,
     ) {
-        let was_raining = self.raining;
-
         self.clear_weather_time = clear_time;
         self.rain_time = rain_time;
         self.thunder_time = rain_time;
         self.raining = raining;
         self.thundering = thundering;
+    }
 
-        if was_raining != raining {
-            if was_raining {
-                world
-                    .broadcast_packet_all(&CGameEvent::new(GameEvent::EndRaining, 0.0))
-                    .await;
-            } else {
-                world
-                    .broadcast_packet_all(&CGameEvent::new(GameEvent::BeginRaining, 0.0))
-                    .await;
-            }
-        }
+    /// Whether clients currently render rain, this lags behind `raining` while transitioning.
+    #[must_use]
+    pub fn is_raining(&self) -> bool {
+        self.rain_level > RAINING_THRESHOLD
     }
 
     pub async fn tick_weather(&mut self, world: &World) {
-        if !self.weather_cycle_enabled {
-            self.advance_weather_cycle();
-        }
-
-        // Update visual transitions
-        self.old_rain_level = self.rain_level;
-        self.old_thunder_level = self.thunder_level;
+        let changes = self.step(has_weather(&world.dimension));
 
-        if self.raining {
-            self.rain_level = (self.rain_level + WEATHER_TRANSITION_SPEED).min(1.0);
-        } else {
-            self.rain_level = (self.rain_level - WEATHER_TRANSITION_SPEED).max(0.0);
-        }
-
-        if self.thundering {
-            self.thunder_level = (self.thunder_level + WEATHER_TRANSITION_SPEED).min(1.0);
-        } else {
-            self.thunder_level = (self.thunder_level - WEATHER_TRANSITION_SPEED).max(0.0);
+        if let Some(raining) = changes.raining {
+            let event = if raining {
+                GameEvent::BeginRaining
+            } else {
+                GameEvent::EndRaining
+            };
+            world
+                .broadcast_packet_all(&CGameEvent::new(event, 0.0))
+                .await;
         }
 
         // Broadcast level changes if needed
-        if (self.old_rain_level - self.rain_level).abs() > f32::EPSILON {
+        if let Some(rain_level) = changes.rain_level {
             world
-                .broadcast_packet_all(&CGameEvent::new(
-                    GameEvent::RainLevelChange,
-                    self.rain_level,
-                ))
+                .broadcast_packet_all(&CGameEvent::new(GameEvent::RainLevelChange, rain_level))
                 .await;
         }
-
-        if (self.old_thunder_level - self.thunder_level).abs() > f32::EPSILON {
+        if let Some(thunder_level) = changes.thunder_level {
             world
                 .broadcast_packet_all(&CGameEvent::new(
                     GameEvent::ThunderLevelChange,
-                    self.thunder_level,
+                    thunder_level,
                 ))
                 .await;
         }
+
+        if let Some(started) = changes.rain_started {
+            world.broadcast_bedrock(&bedrock_rain_event(started)).await;
+        }
+        if let Some(started) = changes.thunder_started {
+            world
+                .broadcast_bedrock(&bedrock_thunder_event(started))
+                .await;
+        }
+    }
+
+    /// The events that bring the weather of a Java player joining the world up to date.
+    /// Players joining mid-transition get the interpolated levels.
+    #[must_use]
+    pub fn java_join_events(&self, has_weather: bool) -> Vec<CGameEvent> {
+        if !has_weather || self.rain_level <= 0.0 {
+            return Vec::new();
+        }
+        vec![
+            CGameEvent::new(GameEvent::BeginRaining, 0.0),
+            CGameEvent::new(GameEvent::RainLevelChange, self.rain_level.clamp(0.0, 1.0)),
+            CGameEvent::new(
+                GameEvent::ThunderLevelChange,
+                self.thunder_level.clamp(0.0, 1.0),
+            ),
+        ]
+    }
+
+    /// Bedrock players joining the world get the interpolated levels with their
+    /// `LevelSettings`, these events tell them where the weather is heading from there.
+    #[must_use]
+    pub fn bedrock_join_events(&self, has_weather: bool) -> Vec<CLevelEvent> {
+        if !has_weather {
+            return Vec::new();
+        }
+        let mut events = Vec::new();
+        if self.raining || self.rain_level > 0.0 {
+            events.push(bedrock_rain_event(self.raining));
+        }
+        if self.thundering || self.thunder_level > 0.0 {
+            events.push(bedrock_thunder_event(self.thundering));
+        }
+        events
+    }
+
+    /// Advances the weather by one tick and returns what the players of the world have to be
+    /// told. Dimensions without weather never change and never tell their players anything.
+    fn step(&mut self, has_weather: bool) -> WeatherChanges {
+        if !has_weather {
+            return WeatherChanges::default();
+        }
+        if self.weather_cycle_enabled {
+            self.advance_weather_cycle();
+        }
+
+        let was_raining = self.is_raining();
+
+        // Update visual transitions
+        self.old_rain_level = self.rain_level;
+        self.old_thunder_level = self.thunder_level;
+
+        self.rain_level = approach(self.rain_level, self.raining);
+        self.thunder_level = approach(self.thunder_level, self.thundering);
+
+        let rain_started = (self.old_raining != self.raining).then_some(self.raining);
+        let thunder_started = (self.old_thundering != self.thundering).then_some(self.thundering);
+        self.old_raining = self.raining;
+        self.old_thundering = self.thundering;
+
+        WeatherChanges {
+            raining: (was_raining != self.is_raining()).then_some(!was_raining),
+            rain_level: ((self.old_rain_level - self.rain_level).abs() > f32::EPSILON)
+                .then_some(self.rain_level),
+            thunder_level: ((self.old_thunder_level - self.thunder_level).abs() > f32::EPSILON)
+                .then_some(self.thunder_level),
+            rain_started,
+            thunder_started,
+        }
     }
 
     fn advance_weather_cycle(&mut self) {
-        // Removed async since there are no await calls
         if self.clear_weather_time > 0 {
             self.clear_weather_time -= 1;
             self.thunder_time = i32::from(!self.thundering);
@@ -158,8 +248,43 @@ impl Weather {
         }
     }
 
-    pub async fn reset_weather_cycle(&mut self, world: &World) {
-        self.set_weather_parameters(world, 0, 0, false, false).await;
+    pub fn reset_weather_cycle(&mut self) {
+        self.set_weather_parameters(0, 0, false, false);
+    }
+}
+
+/// Moves a weather level one step towards 1.0 if `active`, otherwise towards 0.0.
+fn approach(level: f32, active: bool) -> f32 {
+    if active {
+        (level + WEATHER_TRANSITION_SPEED).min(1.0)
+    } else {
+        (level - WEATHER_TRANSITION_SPEED).max(0.0)
+    }
+}
+
+/// Bedrock has no level change event, the client fades to the intensity of the start event
+/// and back to nothing after the stop event.
+fn bedrock_rain_event(started: bool) -> CLevelEvent {
+    if started {
+        CLevelEvent::new(
+            CLevelEvent::START_RAINING,
+            Vector3::default(),
+            BEDROCK_FULL_INTENSITY,
+        )
+    } else {
+        CLevelEvent::new(CLevelEvent::STOP_RAINING, Vector3::default(), 0)
+    }
+}
+
+fn bedrock_thunder_event(started: bool) -> CLevelEvent {
+    if started {
+        CLevelEvent::new(
+            CLevelEvent::START_THUNDERSTORM,
+            Vector3::default(),
+            BEDROCK_FULL_INTENSITY,
+        )
+    } else {
+        CLevelEvent::new(CLevelEvent::STOP_THUNDERSTORM, Vector3::default(), 0)
     }
 }
 
@@ -171,6 +296,8 @@ impl Clone for Weather {
             rain_time: self.rain_time,
             thundering: self.thundering,
             thunder_time: self.thunder_time,
+            old_raining: self.old_raining,
+            old_thundering: self.old_thundering,
             rain_level: self.rain_level,
             old_rain_level: self.old_rain_level,
             thunder_level: self.thunder_level,
@@ -179,3 +306,118 @@ impl Clone for Weather {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::dimension::Dimension;
+    use pumpkin_protocol::bedrock::client::level_event::CLevelEvent;
+    use pumpkin_protocol::java::client::play::GameEvent;
+
+    use super::{Weather, has_weather};
+
+    #[test]
+    fn rain_in_the_overworld_stays_in_the_overworld() {
+        let mut overworld = Weather::new();
+        let mut nether = Weather::new();
+        assert!(has_weather(&Dimension::OVERWORLD));
+        assert!(!has_weather(&Dimension::THE_NETHER));
+        assert!(!has_weather(&Dimension::THE_END));
+
+        overworld.set_weather_parameters(0, 6000, true, false);
+        let changes = overworld.step(has_weather(&Dimension::OVERWORLD));
+        assert!(changes.rain_level.is_some());
+
+        // Even if the nether were told to rain, its players are never sent anything
+        nether.set_weather_parameters(0, 6000, true, true);
+        for _ in 0..100 {
+            assert_eq!(
+                nether.step(has_weather(&Dimension::THE_NETHER)),
+                Default::default()
+            );
+        }
+        assert!(!nether.is_raining());
+    }
+
+    #[test]
+    fn weather_fades_in_and_out() {
+        let mut weather = Weather::new();
+        weather.weather_cycle_enabled = false;
+        weather.set_weather_parameters(0, 6000, true, false);
+
+        let first = weather.step(true);
+        assert_eq!(first.rain_started, Some(true));
+        assert_eq!(first.thunder_started, None);
+        assert_eq!(first.thunder_level, None);
+        let mut began_raining = Vec::new();
+        for tick in 2..=100u8 {
+            let changes = weather.step(true);
+            // Bedrock is only told once, Java gets every step
+            assert_eq!(changes.rain_started, None);
+            let level = changes.rain_level.unwrap();
+            assert!((level - f32::from(tick) * 0.01).abs() < 1e-4);
+            if let Some(raining) = changes.raining {
+                began_raining.push((raining, level));
+            }
+        }
+        // Clients render rain once the level passes the threshold
+        assert_eq!(began_raining.len(), 1);
+        assert!(began_raining[0].0);
+        assert!((began_raining[0].1 - 0.2).abs() < 0.015);
+        for _ in 0..5 {
+            weather.step(true);
+        }
+        assert!((weather.rain_level - 1.0).abs() < f32::EPSILON);
+        assert_eq!(weather.step(true), Default::default());
+
+        weather.set_weather_parameters(6000, 0, false, false);
+        let stop = weather.step(true);
+        assert_eq!(stop.rain_started, Some(false));
+        assert!(stop.rain_level.unwrap() < 1.0);
+        for _ in 0..110 {
+            assert_eq!(weather.step(true).rain_started, None);
+        }
+        assert!(weather.rain_level.abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn joining_mid_transition_gets_the_current_weather() {
+        let mut weather = Weather::new();
+        weather.weather_cycle_enabled = false;
+        assert!(weather.java_join_events(true).is_empty());
+        assert!(weather.bedrock_join_events(true).is_empty());
+
+        weather.set_weather_parameters(0, 6000, true, false);
+        for _ in 0..50 {
+            weather.step(true);
+        }
+        let java = weather.java_join_events(true);
+        let events: Vec<_> = java.iter().map(|event| event.event).collect();
+        assert_eq!(
+            events,
+            [
+                GameEvent::BeginRaining as u8,
+                GameEvent::RainLevelChange as u8,
+                GameEvent::ThunderLevelChange as u8,
+            ]
+        );
+        assert!((java[1].value - 0.5).abs() < 1e-4);
+        assert!(java[2].value.abs() < f32::EPSILON);
+        let bedrock = weather.bedrock_join_events(true);
+        assert_eq!(bedrock.len(), 1);
+        assert_eq!(bedrock[0].event_id.0, CLevelEvent::START_RAINING);
+
+        // Still fading out, so Bedrock players have to be told it stopped
+        weather.set_weather_parameters(6000, 0, false, false);
+        for _ in 0..10 {
+            weather.step(true);
+        }
+        assert!(weather.rain_level > 0.0);
+        let bedrock = weather.bedrock_join_events(true);
+        assert_eq!(bedrock.len(), 1);
+        assert_eq!(bedrock[0].event_id.0, CLevelEvent::STOP_RAINING);
+
+        // Nothing to catch up on in a dimension without weather
+        assert!(weather.java_join_events(false).is_empty());
+        assert!(weather.bedrock_join_events(false).is_empty());
+    }
+}